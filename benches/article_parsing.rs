@@ -1,5 +1,6 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use extrablatt_v2::{Article, DefaultExtractor, Extractor};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use extrablatt_v2::extract_node::article_node;
+use extrablatt_v2::{Article, DefaultExtractor, Extractor, Language};
 use select::document::Document;
 use url::Url;
 
@@ -12,6 +13,14 @@ fn bench_full_article_parsing(c: &mut Criterion) {
             Article::new(black_box(URL), black_box(HTML)).unwrap()
         });
     });
+
+    c.bench_function("article_from_document", |b| {
+        b.iter_batched(
+            || Document::from(HTML),
+            |doc| Article::from_document(black_box(URL), doc).unwrap(),
+            BatchSize::SmallInput,
+        );
+    });
 }
 
 fn bench_individual_extractors(c: &mut Criterion) {
@@ -81,5 +90,48 @@ fn bench_individual_extractors(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, bench_full_article_parsing, bench_individual_extractors);
+/// The text, images and videos of the article node from one node selection,
+/// compared to selecting the node for each of them.
+fn bench_node_content(c: &mut Criterion) {
+    let doc = Document::from(HTML);
+    let extractor = DefaultExtractor::default();
+    let base_url = Url::parse(URL).ok();
+
+    c.bench_function("extract_content", |b| {
+        b.iter(|| {
+            extractor.content(black_box(&doc), Language::Russian, base_url.as_ref())
+        });
+    });
+
+    c.bench_function("extract_text_images_videos_separately", |b| {
+        b.iter(|| {
+            let text = extractor.text(black_box(&doc), Language::Russian);
+            let images = article_node(black_box(&doc), Language::Russian)
+                .map(|node| node.images(base_url.as_ref()));
+            let videos = article_node(black_box(&doc), Language::Russian).map(|node| node.videos().len());
+            (text, images, videos)
+        });
+    });
+}
+
+/// The text of a page with `itemprop="articleBody"`, which skips the scoring
+/// of the text nodes, so the noise checks of the text walk dominate.
+fn bench_explicit_body(c: &mut Criterion) {
+    let doc = Document::from(include_str!("../tests/fixtures/body/explicit_body.html"));
+    let extractor = DefaultExtractor::default();
+
+    c.bench_function("extract_text_explicit_body", |b| {
+        b.iter(|| {
+            extractor.text(black_box(&doc), Language::English)
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_full_article_parsing,
+    bench_individual_extractors,
+    bench_node_content,
+    bench_explicit_body
+);
 criterion_main!(benches);
@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use extrablatt_v2::text::ArticleTextNodeExtractor;
+use extrablatt_v2::Language;
+use select::document::Document;
+
+/// A long article with many paragraph siblings, next to teasers and
+/// comments, so every candidate checks the language of its siblings for the
+/// boost.
+static HTML: &str = include_str!("fixtures/long_article.html");
+
+fn bench_best_node(c: &mut Criterion) {
+    let doc = Document::from(HTML);
+    let custom = Language::custom("en", ["harbour", "council", "quay"]);
+
+    c.bench_function("best_node_builtin_language", |b| {
+        b.iter(|| ArticleTextNodeExtractor::calculate_best_node(black_box(&doc), Language::English))
+    });
+
+    c.bench_function("best_node_custom_language", |b| {
+        b.iter(|| ArticleTextNodeExtractor::calculate_best_node(black_box(&doc), custom.clone()))
+    });
+}
+
+criterion_group!(benches, bench_best_node);
+criterion_main!(benches);
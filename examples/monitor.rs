@@ -0,0 +1,48 @@
+//! Check a news site every few minutes and print the articles that weren't
+//! seen before.
+//!
+//! ```bash
+//! cargo run --example monitor -- https://some-news.com/ seen.json
+//! ```
+
+use std::time::Duration;
+
+use extrablatt_v2::monitor::{MonitorConfig, MonitorEvent};
+use extrablatt_v2::Extrablatt;
+use futures::StreamExt;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let url = args.next().ok_or("usage: monitor <url> [state.json]")?;
+    let state_path = args.next().map(Into::into);
+
+    let site = Extrablatt::builder(url)?.build().await?;
+    let config = MonitorConfig {
+        interval: Duration::from_secs(300),
+        categories: true,
+        max_new_per_cycle: Some(50),
+        state_path,
+        ..Default::default()
+    };
+
+    let mut monitor = Box::pin(site.monitor(config)?);
+    while let Some(event) = monitor.next().await {
+        match event {
+            Ok(MonitorEvent::Article(article)) => {
+                println!("{} '{}'", article.url, article.content.title.as_deref().unwrap_or_default())
+            }
+            Ok(MonitorEvent::Cycle(stats)) => println!(
+                "cycle {}: {} new of {} discovered, {} articles, {} failures in {:?}",
+                stats.cycle,
+                stats.new,
+                stats.discovered,
+                stats.articles,
+                stats.failures,
+                stats.duration
+            ),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+    Ok(())
+}
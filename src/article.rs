@@ -1,5 +1,8 @@
 use std::borrow::{Borrow, Cow};
 use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::sync::Arc;
+#[cfg(feature = "net")]
 use std::time::Duration;
 
 use std::fmt;
@@ -8,7 +11,25 @@ pub enum ArticleError {
     UrlNotInitialized,
     UserAgentParseError,
     UnsuccessfulRequest(String),
+    /// Followed more redirects than allowed, holds the last url of the
+    /// redirect chain.
+    TooManyRedirects(Url),
+    /// A meta refresh that wasn't followed, because it leads back to a page
+    /// of the chain or exceeds
+    /// [`crate::extract_meta_refresh::MAX_META_REFRESH_HOPS`], holds the url of the stub
+    /// page with the refresh.
+    TooManyMetaRefreshes(Url),
     DocumentReadError(String),
+    /// The url of the article is invalid.
+    UrlParse(url::ParseError),
+    /// A cookie of [`ArticleBuilder::cookies_for`] can't be sent.
+    #[cfg(feature = "cookies")]
+    Cookie(crate::cookies::CookieError),
+    /// An option of the [`ArticleBuilder`] was set that
+    /// [`ArticleBuilder::get_blocking`] doesn't support.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    UnsupportedBlockingOption(&'static str),
+    #[cfg(feature = "net")]
     Reqwest(reqwest::Error),
     Other(String),
 }
@@ -19,7 +40,19 @@ impl fmt::Display for ArticleError {
             ArticleError::UrlNotInitialized => write!(f, "Url of the article must be initialized."),
             ArticleError::UserAgentParseError => write!(f, "Failed to parse user agent header."),
             ArticleError::UnsuccessfulRequest(url) => write!(f, "Unsuccessful request to {}", url),
+            ArticleError::TooManyRedirects(url) => write!(f, "Exceeded the redirect limit at {}", url),
+            ArticleError::TooManyMetaRefreshes(url) => {
+                write!(f, "Stopped following the meta refreshes at {}", url)
+            }
             ArticleError::DocumentReadError(url) => write!(f, "Failed to read {} html as document.", url),
+            ArticleError::UrlParse(e) => write!(f, "Invalid url: {}", e),
+            #[cfg(feature = "cookies")]
+            ArticleError::Cookie(e) => write!(f, "{}", e),
+            #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+            ArticleError::UnsupportedBlockingOption(option) => {
+                write!(f, "The blocking api doesn't support the `{}` option", option)
+            }
+            #[cfg(feature = "net")]
             ArticleError::Reqwest(e) => write!(f, "Reqwest error: {}", e),
             ArticleError::Other(msg) => write!(f, "{}", msg),
         }
@@ -29,30 +62,127 @@ impl fmt::Display for ArticleError {
 impl std::error::Error for ArticleError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
+            ArticleError::UrlParse(e) => Some(e),
+            #[cfg(feature = "cookies")]
+            ArticleError::Cookie(e) => Some(e),
+            #[cfg(feature = "net")]
             ArticleError::Reqwest(e) => Some(e),
             _ => None,
         }
     }
 }
 
+impl From<url::ParseError> for ArticleError {
+    fn from(e: url::ParseError) -> Self {
+        ArticleError::UrlParse(e)
+    }
+}
+
+#[cfg(feature = "cookies")]
+impl From<crate::cookies::CookieError> for ArticleError {
+    fn from(e: crate::cookies::CookieError) -> Self {
+        ArticleError::Cookie(e)
+    }
+}
+
+#[cfg(feature = "net")]
 impl From<reqwest::Error> for ArticleError {
     fn from(e: reqwest::Error) -> Self {
-        ArticleError::Reqwest(e)
+        match e.url() {
+            Some(url) if e.is_redirect() => ArticleError::TooManyRedirects(url.clone()),
+            _ => ArticleError::Reqwest(e),
+        }
     }
 }
 
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(feature = "net")]
+use bytes::Bytes;
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
 use reqwest::header::{HeaderMap, USER_AGENT};
-use reqwest::{Client, IntoUrl, Url};
+#[cfg(feature = "net")]
+use reqwest::Client;
 use select::document::Document;
 #[cfg(feature = "serde0")]
 use serde::{Deserialize, Serialize};
-
-use crate::date::ArticleDate;
-#[cfg(not(target_arch = "wasm32"))]
+use url::Url;
+
+#[cfg(feature = "net")]
+use crate::charset::content_type;
+use crate::charset::decode;
+use crate::clean::ExtractionFilters;
+use crate::completeness::Incompleteness;
+#[cfg(feature = "cookies")]
+use crate::cookies::Cookies;
+use crate::date::{ArticleDate, ArticleDates, Date};
+#[cfg(all(feature = "net", not(target_arch = "wasm32")))]
 use crate::extrablatt::Config;
 use crate::extract::{DefaultExtractor, Extractor};
-use crate::language::Language;
+#[cfg(feature = "net")]
+use crate::extract_meta_refresh::can_follow_meta_refresh;
+use crate::extract_node::article_node_adaptive_with_filters;
+use crate::extract_canonical::CanonicalLink;
+use crate::extract_description::DescriptionSource;
+use crate::extract_meta_data::MetaMap;
+use crate::extract_hreflang::LanguageTag;
+use crate::extract_ids::ArticleIds;
+use crate::extract_publisher::Publisher;
+use crate::fetch_info::FetchInfo;
+use crate::paywall::ContentAccess;
+use crate::promo::{Attribution, PromoBlock};
+use crate::quote::Quote;
+use crate::reference::{classify_references, ClassifiedRefs, Reference};
+use crate::audio::Audio;
+use crate::embed::SocialEmbed;
+#[cfg(feature = "net")]
+use crate::retry::RetryPolicy;
+#[cfg(feature = "net")]
+use crate::source_span::approximate_source_span;
+use crate::summarize::summarize;
+use crate::text::{ArticleTextNodeExtractor, Diagnostics, ExtractionProfile};
+#[cfg(feature = "net")]
+use crate::trace;
+use crate::language::{DetectedLanguage, Language};
+#[cfg(feature = "net")]
+use crate::wayback::{is_wayback_url, unwrap_wayback_links, wayback_original_url};
+
+/// Conversion into the url of an article, implemented for [`Url`]s and
+/// strings.
+///
+/// Unlike `reqwest::IntoUrl` it's available without the `net` feature, so
+/// articles can be extracted from local html on any target.
+pub trait IntoUrl {
+    fn into_url(self) -> Result<Url, url::ParseError>;
+}
+
+impl IntoUrl for Url {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Ok(self)
+    }
+}
+
+impl IntoUrl for &Url {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Ok(self.clone())
+    }
+}
+
+impl IntoUrl for &str {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(self)
+    }
+}
+
+impl IntoUrl for &String {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(self)
+    }
+}
+
+impl IntoUrl for String {
+    fn into_url(self) -> Result<Url, url::ParseError> {
+        Url::parse(&self)
+    }
+}
 
 /// Extension for documents that are considered valid sources for articles.
 pub const ALLOWED_FILE_EXT: [&str; 12] = [
@@ -104,6 +234,7 @@ pub const BAD_DOMAINS: [&str; 4] = ["amazon", "doubleclick", "twitter", "outbrai
 
 /// An identified url to an article and it's title.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct ArticleUrl {
     /// The url of the article.
     pub url: Url,
@@ -183,7 +314,8 @@ impl Borrow<Url> for ArticleUrl {
 /// ```
 #[derive(Debug)]
 pub struct Article {
-    /// The url of the article.
+    /// The url the article was fetched from, see [`Article::canonical_url`]
+    /// for the url the page declares.
     pub url: Url,
     /// The parsed response html `Document`.
     pub doc: Document,
@@ -191,6 +323,12 @@ pub struct Article {
     pub content: ArticleContent<'static>,
     /// The expected language of the article.
     pub language: Language,
+    /// Whether the page was answered by the [`crate::http_cache::HttpCache`]
+    /// because it didn't change since the previous download.
+    pub from_cache: bool,
+    /// The final url, status and headers of the response the page was
+    /// downloaded with, `None` for articles extracted from local html.
+    pub fetch_info: Option<FetchInfo>,
 }
 
 impl Article {
@@ -199,6 +337,57 @@ impl Article {
         Self::with_extractor(url, doc, &DefaultExtractor::default())
     }
 
+    /// Extract the article from the downloaded `body` using the
+    /// [`DefaultExtractor`].
+    ///
+    /// The body is transcoded to UTF-8 according to its `<meta>` charset
+    /// declaration, see [`crate::charset::detect_encoding`].
+    pub fn from_bytes<U: IntoUrl>(url: U, body: &[u8]) -> Result<Article, ArticleError> {
+        Self::with_extractor(url, decode(body, None), &DefaultExtractor::default())
+    }
+
+    /// Extract the article from an already parsed `doc` using the
+    /// [`DefaultExtractor`].
+    ///
+    /// The article takes ownership of the `doc` instead of parsing the html
+    /// again.
+    pub fn from_document<U: IntoUrl>(url: U, doc: Document) -> Result<Article, ArticleError> {
+        Self::from_document_with_extractor(url, doc, &DefaultExtractor::default(), Default::default())
+    }
+
+    /// Extract the article from an already parsed `doc` using the provided
+    /// `extractor` and `language`.
+    pub fn from_document_with_extractor<U, TExtract>(
+        url: U,
+        doc: Document,
+        extractor: &TExtract,
+        language: Language,
+    ) -> Result<Article, ArticleError>
+    where
+        U: IntoUrl,
+        TExtract: Extractor,
+    {
+        let url = url.into_url()?;
+        let mut content = extractor
+            .article_content(&doc, Some(&url), Some(language.clone()))
+            .into_owned();
+        content.canonical = extractor.canonical_link_detailed(&doc, Some(&url));
+        content.alternate_languages = extractor.alternate_languages(&doc, Some(&url));
+        content.x_default_url = extractor.x_default_url(&doc, Some(&url));
+
+        // Use the detected language from content if available, otherwise use the provided language
+        let final_language = content.language.clone().unwrap_or(language);
+
+        Ok(Article {
+            url,
+            doc,
+            content,
+            language: final_language,
+            from_cache: false,
+            fetch_info: None,
+        })
+    }
+
     pub fn with_extractor<U, T, TExtract>(url: U, doc: T, extractor: &TExtract) -> Result<Article, ArticleError>
     where
         U: IntoUrl,
@@ -222,24 +411,96 @@ impl Article {
         TExtract: Extractor,
     {
         let url = url.into_url()?;
-        let doc = Document::from(doc.as_ref());
-        let content = extractor
-            .article_content(
-                &doc,
-                extractor.base_url(&doc).as_ref(),
-                Some(language.clone()),
-            )
-            .into_owned();
+        Self::from_document_with_extractor(url, Document::from(doc.as_ref()), extractor, language)
+    }
 
-        // Use the detected language from content if available, otherwise use the provided language
-        let final_language = content.language.clone().unwrap_or(language);
+    /// The language detected from the article's text and its confidence.
+    pub fn detected_language(&self) -> Option<&DetectedLanguage> {
+        self.content.detected_language.as_ref()
+    }
 
-        Ok(Article {
-            url,
-            doc,
-            content,
-            language: final_language,
-        })
+    /// The `n` most frequent words of the text in the article's language,
+    /// see [`ArticleTextNodeExtractor::keywords`].
+    pub fn keywords(&self, n: usize) -> Vec<(String, f64)> {
+        self.content
+            .text
+            .as_deref()
+            .map(|text| ArticleTextNodeExtractor::keywords(text, self.language.clone(), n))
+            .unwrap_or_default()
+    }
+
+    /// The `max_sentences` sentences of the text that summarize the article
+    /// best, see [`crate::summarize::summarize`].
+    pub fn summary(&self, max_sentences: usize) -> Vec<String> {
+        let title = self.content.title.as_deref().unwrap_or_default();
+        self.content
+            .text
+            .as_deref()
+            .map(|text| summarize(text, title, self.language.clone(), max_sentences))
+            .unwrap_or_default()
+    }
+
+    /// The organization that published the article, see
+    /// [`crate::extract_publisher::publisher`].
+    pub fn publisher(&self) -> Option<&Publisher> {
+        self.content.publisher.as_ref()
+    }
+
+    /// Whether the page holds the full text of the article or only a teaser
+    /// of a paywall, see [`crate::paywall::content_access`].
+    pub fn access(&self) -> ContentAccess {
+        self.content.access
+    }
+
+    /// Confidence in the extracted text, between `0.0` and `1.0`.
+    ///
+    /// `0.0` if no text node was found, see [`ArticleContent::diagnostics`]
+    /// for the details.
+    pub fn extraction_confidence(&self) -> f64 {
+        self.content
+            .diagnostics
+            .as_ref()
+            .map(|diagnostics| diagnostics.confidence)
+            .unwrap_or_default()
+    }
+
+    /// The canonical url the page declares, unless it belongs to another
+    /// site than the [`Article::url`].
+    ///
+    /// See [`ArticleContent::canonical`] for urls of other sites.
+    pub fn canonical_url(&self) -> Option<&Url> {
+        self.content
+            .canonical
+            .as_ref()
+            .filter(|canonical| !canonical.cross_domain)
+            .map(|canonical| &canonical.url)
+    }
+
+    /// The [`ArticleContent::references`] grouped into links to the
+    /// article's own site, to other sites and to social networks.
+    pub fn classified_references(&self) -> ClassifiedRefs {
+        classify_references(&self.content.references, &self.url)
+    }
+
+    /// Stable identifiers of the article, see [`ArticleContent::ids`].
+    pub fn ids(&self) -> &ArticleIds {
+        &self.content.ids
+    }
+
+    /// The keys and values of all `<meta>` nodes of the page, see
+    /// [`ArticleContent::meta`].
+    pub fn meta(&self) -> &MetaMap {
+        &self.content.meta
+    }
+
+    /// The urls of the stub pages that redirected to the article with a
+    /// `<meta http-equiv="refresh">`, see
+    /// [`ArticleBuilder::follow_meta_refresh`].
+    pub fn redirect_chain(&self) -> &[Url] {
+        self.fetch_info
+            .as_ref()
+            .map(|fetch_info| fetch_info.redirect_chain.as_slice())
+            .unwrap_or_default()
     }
 
     /// Retrieves the [`ArticleContent`] from the `url`
@@ -254,17 +515,20 @@ impl Article {
     ///  #     Ok(())
     ///  # }
     /// ```
+    #[cfg(feature = "net")]
     pub async fn content<T: IntoUrl>(url: T) -> Result<ArticleContent<'static>, ArticleError> {
         let article = Self::get(url).await?;
         Ok(article.content)
     }
 
     /// Get the [`Article`] for the `url`
+    #[cfg(feature = "net")]
     pub async fn get<T: IntoUrl>(url: T) -> Result<Article, ArticleError> {
         Self::builder(url)?.get().await
     }
 
     /// Get the [`Article`] for the `url` using a specific `Extractor`
+    #[cfg(feature = "net")]
     pub async fn get_with_extractor<T: IntoUrl, TExtract: Extractor>(
         url: T,
         extractor: &TExtract,
@@ -272,26 +536,203 @@ impl Article {
         Self::builder(url)?.get_with_extractor(extractor).await
     }
 
+    /// Get the [`Article`] for the `url` without an async runtime.
+    ///
+    /// Same as calling [`ArticleBuilder::get_blocking`], which must not be
+    /// called from within an async runtime.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub fn get_blocking<T: IntoUrl>(url: T) -> Result<Article, ArticleError> {
+        Self::builder(url)?.get_blocking()
+    }
+
     /// Convenience method for creating a new [`ArticleBuilder`]
     ///
     /// Same as calling [`ArticleBuilder::new`]
+    #[cfg(feature = "net")]
     pub fn builder<T: IntoUrl>(url: T) -> Result<ArticleBuilder, ArticleError> {
         ArticleBuilder::new(url)
     }
 
+    /// Build the article from a body-less html fragment, like the full
+    /// article html some feeds provide in `content:encoded`.
+    ///
+    /// Only the text, images, references and videos are extracted from the
+    /// fragment, all fields that depend on the document's `<head>` are taken
+    /// from [`FragmentOptions::known`] instead.
+    pub fn from_fragment<T, U>(html_fragment: T, url: U, options: FragmentOptions) -> Result<Article, ArticleError>
+    where
+        T: AsRef<str>,
+        U: IntoUrl,
+    {
+        let url = url.into_url()?;
+        let doc = Document::from(
+            format!("<html><head></head><body>{}</body></html>", html_fragment.as_ref()).as_str(),
+        );
+        let FragmentOptions { known, language, filters, .. } = options;
+
+        let mut builder = ArticleContent::builder()
+            .authors(known.authors.into_iter().map(Cow::Owned).collect())
+            .keywords(known.keywords.into_iter().map(Cow::Owned).collect());
+        if let Some(title) = known.title {
+            builder = builder.title(Cow::Owned(title));
+        }
+        if let Some(description) = known.description {
+            builder = builder.description(Cow::Owned(description));
+        }
+        if let Some(date) = known.publishing_date {
+            builder = builder.publishing_date(date);
+        }
+        if let Some((txt_node, _)) = article_node_adaptive_with_filters(&doc, language.clone(), filters.as_ref()) {
+            let text = txt_node.clean_text();
+            builder = builder
+                .diagnostics(txt_node.diagnostics(&text))
+                .videos(
+                    txt_node
+                        .videos()
+                        .into_iter()
+                        .filter_map(|x| x.get_src_url(Some(&url)))
+                        .filter_map(|url| url.ok())
+                        .collect(),
+                )
+                .audios(txt_node.audios_detailed(Some(&url)))
+                .references(txt_node.references(Some(&url)))
+                .reference_details(txt_node.references_detailed(Some(&url), Some(&url)))
+                .promotional_content(txt_node.promotional_content(Some(&url)))
+                .affiliate_link_count(txt_node.affiliate_link_count(Some(&url)))
+                .attributions(txt_node.attributions(Some(&url)))
+                .quotes(txt_node.quotes())
+                .embeds(txt_node.embeds(Some(&url)))
+                .text(text.into())
+                .html(txt_node.clean_html(Some(&url)).into())
+                .images(txt_node.images(Some(&url)));
+        }
+        let content = builder.build().into_owned();
+
+        Ok(Article {
+            url,
+            doc,
+            content,
+            language,
+            from_cache: false,
+            fetch_info: None,
+        })
+    }
+
+    /// Build the article from the html fragment, but download and extract
+    /// the full page if the fragment's text is shorter than
+    /// [`FragmentOptions::min_text_len`].
+    #[cfg(feature = "net")]
+    pub async fn from_fragment_or_get<T, U>(
+        html_fragment: T,
+        url: U,
+        options: FragmentOptions,
+    ) -> Result<Article, ArticleError>
+    where
+        T: AsRef<str>,
+        U: IntoUrl,
+    {
+        let min_text_len = options.min_text_len;
+        let language = options.language.clone();
+        let article = Self::from_fragment(html_fragment, url, options)?;
+        let text_len = article
+            .content
+            .text
+            .as_ref()
+            .map(|txt| txt.chars().count())
+            .unwrap_or_default();
+        if text_len >= min_text_len {
+            return Ok(article);
+        }
+        Self::builder(article.url)?.language(language).get().await
+    }
+
     /// Drops the article's html [`select::document::Document`].
     pub fn drop_document(self) -> PureArticle {
+        self.into_pure()
+    }
+
+    /// Converts the article into a [`PureArticle`] that doesn't borrow from
+    /// the [`select::document::Document`] and can be serialized.
+    pub fn into_pure(self) -> PureArticle {
         PureArticle {
             url: self.url,
             content: self.content,
             language: self.language,
+            fetch_info: self.fetch_info,
+        }
+    }
+}
+
+/// Fields of an article that are already known without the page's `<head>`,
+/// e.g. from a feed item.
+#[derive(Debug, Clone, Default)]
+pub struct KnownFields {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub publishing_date: Option<ArticleDate>,
+    pub description: Option<String>,
+    pub keywords: Vec<String>,
+}
+
+/// Options for [`Article::from_fragment`].
+#[derive(Debug, Clone)]
+pub struct FragmentOptions {
+    /// Fields that can't be extracted from the fragment.
+    pub known: KnownFields,
+    /// The expected language of the fragment.
+    pub language: Language,
+    /// Min number of chars of the fragment's text before the full page is
+    /// downloaded instead.
+    pub min_text_len: usize,
+    /// The hooks and article body attributes the article node of the
+    /// fragment is selected with, see [`crate::Extractor::filters`].
+    pub filters: Option<Arc<ExtractionFilters>>,
+}
+
+impl FragmentOptions {
+    /// Default for [`FragmentOptions::min_text_len`].
+    pub const DEFAULT_MIN_TEXT_LEN: usize = 500;
+
+    pub fn new(known: KnownFields) -> Self {
+        Self {
+            known,
+            ..Default::default()
+        }
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn min_text_len(mut self, min_text_len: usize) -> Self {
+        self.min_text_len = min_text_len;
+        self
+    }
+
+    pub fn filters(mut self, filters: Option<Arc<ExtractionFilters>>) -> Self {
+        self.filters = filters;
+        self
+    }
+}
+
+impl Default for FragmentOptions {
+    fn default() -> Self {
+        Self {
+            known: KnownFields::default(),
+            language: Language::default(),
+            min_text_len: Self::DEFAULT_MIN_TEXT_LEN,
+            filters: None,
         }
     }
 }
 
 /// An [`crate::Article`] without the [`select::document::Document`], mainly to
 /// use serde.
-#[derive(Debug)]
+///
+/// With the `serde` feature the JSON representation has snake_case keys,
+/// urls as strings, dates in ISO 8601 and no keys for missing values.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct PureArticle {
     /// The url of the article.
@@ -300,15 +741,34 @@ pub struct PureArticle {
     pub content: ArticleContent<'static>,
     /// The expected language of the article.
     pub language: Language,
+    /// The final url, status and time of the response the page was
+    /// downloaded with, see [`Article::fetch_info`].
+    #[cfg_attr(feature = "serde0", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub fetch_info: Option<FetchInfo>,
 }
 
+#[cfg(feature = "net")]
 pub struct ArticleBuilder {
     url: Option<Url>,
     timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    max_redirects: Option<usize>,
+    danger_accept_invalid_certs: bool,
+    proxy: Option<String>,
     language: Option<Language>,
     browser_user_agent: Option<String>,
+    retry_policy: Option<RetryPolicy>,
+    identity_url: Option<Url>,
+    resolve_base: Option<Url>,
+    source_span: bool,
+    prefer_amp: bool,
+    follow_meta_refresh: bool,
+    #[cfg(feature = "cookies")]
+    cookies: Cookies,
+    client: Option<Client>,
 }
 
+#[cfg(feature = "net")]
 impl ArticleBuilder {
     pub fn new<T: IntoUrl>(url: T) -> Result<Self, ArticleError> {
         let url = url.into_url()?;
@@ -316,11 +776,40 @@ impl ArticleBuilder {
         Ok(ArticleBuilder {
             url: Some(url),
             timeout: None,
+            connect_timeout: None,
+            max_redirects: None,
+            danger_accept_invalid_certs: false,
+            proxy: None,
             language: None,
             browser_user_agent: None,
+            retry_policy: None,
+            identity_url: None,
+            resolve_base: None,
+            source_span: false,
+            prefer_amp: false,
+            follow_meta_refresh: false,
+            #[cfg(feature = "cookies")]
+            cookies: Cookies::default(),
+            client: None,
         })
     }
 
+    /// Create a builder that fetches a snapshot of the Wayback Machine, like
+    /// `https://web.archive.org/web/20200101000000/https://example.com/a.html`.
+    ///
+    /// The original url of the snapshot is used as both the
+    /// [`ArticleBuilder::identity_url`] and the
+    /// [`ArticleBuilder::resolve_base`].
+    pub fn from_archive<T: IntoUrl>(url: T) -> Result<Self, ArticleError> {
+        let url = url.into_url()?;
+        let original = wayback_original_url(&url).ok_or_else(|| {
+            ArticleError::Other(format!("{} is not a web.archive.org snapshot", url))
+        })?;
+        Ok(Self::new(url)?
+            .identity_url(original.clone())
+            .resolve_base(original))
+    }
+
     pub fn browser_user_agent<T: ToString>(mut self, browser_user_agent: T) -> Self {
         self.browser_user_agent = Some(browser_user_agent.to_string());
         self
@@ -331,11 +820,154 @@ impl ArticleBuilder {
         self
     }
 
+    /// Set the max duration of establishing a connection.
+    ///
+    /// Default is no limit besides the [`ArticleBuilder::timeout`]. Not
+    /// supported on wasm.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Set the max number of redirects followed per request, `0` follows
+    /// none.
+    ///
+    /// Requests that exceed the limit fail with
+    /// [`ArticleError::TooManyRedirects`]. Default is `10`. Not supported on
+    /// wasm.
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = Some(max_redirects);
+        self
+    }
+
+    /// Whether to accept invalid TLS certificates, like the self-signed
+    /// certificate of an internal mirror.
+    ///
+    /// **Dangerous**: any certificate is trusted, which exposes the request
+    /// to man-in-the-middle attacks. Only use it for hosts you control.
+    /// Default is `false`. Not supported on wasm.
+    pub fn danger_accept_invalid_certs(mut self, danger_accept_invalid_certs: bool) -> Self {
+        self.danger_accept_invalid_certs = danger_accept_invalid_certs;
+        self
+    }
+
+    /// Send the requests through a proxy, like `http://proxy:port` or
+    /// `socks5://proxy:port`, see [`crate::ExtrablattBuilder::proxy`]. Not
+    /// supported on wasm.
+    pub fn proxy<T: Into<String>>(mut self, proxy: T) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Whether the cookies set by responses are sent with the following
+    /// requests, like the one for the AMP page.
+    ///
+    /// Default is `false`. Not supported on wasm.
+    #[cfg(feature = "cookies")]
+    pub fn cookie_store(mut self, cookie_store: bool) -> Self {
+        self.cookies.store = cookie_store;
+        self
+    }
+
+    /// Send the `cookies` as `name=value` pairs to the `host`, like the
+    /// consent cookie `euconsent` a publisher requires before serving the
+    /// full article, see [`crate::ExtrablattBuilder::cookies_for`].
+    ///
+    /// Cookies set by responses are only kept with
+    /// [`ArticleBuilder::cookie_store`]. Fails with [`ArticleError::Cookie`]
+    /// if the `host` isn't valid or a name or value has a character a cookie
+    /// can't have, like a `;`. Not supported on wasm.
+    #[cfg(feature = "cookies")]
+    pub fn cookies_for<H, N, V>(mut self, host: H, cookies: Vec<(N, V)>) -> Self
+    where
+        H: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.cookies.insert(host, cookies);
+        self
+    }
+
     pub fn language(mut self, language: Language) -> Self {
         self.language = Some(language);
         self
     }
 
+    /// Set the policy for repeating a failed download.
+    ///
+    /// Default is [`RetryPolicy::default`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Set the url the article is identified by, if it differs from the url
+    /// it is fetched from, like for a page from a mirror or cache.
+    ///
+    /// Default is the url of the response.
+    pub fn identity_url(mut self, identity_url: Url) -> Self {
+        self.identity_url = Some(identity_url);
+        self
+    }
+
+    /// Set the url relative links in the article are resolved against.
+    ///
+    /// Default is the url of the response. A `<base>` of the document takes
+    /// precedence, a relative one is resolved against this url.
+    pub fn resolve_base(mut self, resolve_base: Url) -> Self {
+        self.resolve_base = Some(resolve_base);
+        self
+    }
+
+    /// Whether to locate the article's text node in the downloaded html and
+    /// set [`ArticleContent::approximate_source_span`].
+    ///
+    /// Default is `false`.
+    pub fn source_span(mut self, source_span: bool) -> Self {
+        self.source_span = source_span;
+        self
+    }
+
+    /// Whether to extract the article from its AMP version, if the page
+    /// advertises one with `<link rel="amphtml">`.
+    ///
+    /// AMP pages usually carry far less boilerplate. The article keeps the
+    /// url of the original page, which is also used if the AMP page can't be
+    /// downloaded. Default is `false`.
+    pub fn prefer_amp(mut self, prefer_amp: bool) -> Self {
+        self.prefer_amp = prefer_amp;
+        self
+    }
+
+    /// Whether to follow a `<meta http-equiv="refresh" content="0;url=...">`
+    /// of a stub page that redirects without an HTTP redirect.
+    ///
+    /// Up to [`crate::extract_meta_refresh::MAX_META_REFRESH_HOPS`] refreshes are
+    /// followed, the download fails with
+    /// [`ArticleError::TooManyMetaRefreshes`] if the stub page refreshes once
+    /// more or to an already downloaded page. The stub pages
+    /// are recorded in [`Article::redirect_chain`]. Default is `false`.
+    pub fn follow_meta_refresh(mut self, follow_meta_refresh: bool) -> Self {
+        self.follow_meta_refresh = follow_meta_refresh;
+        self
+    }
+
+    /// Download the article with the `client`, like a client that is shared
+    /// with the rest of the application and its connection pool.
+    ///
+    /// The client is used as is, so the
+    /// [`ArticleBuilder::browser_user_agent`], [`ArticleBuilder::timeout`],
+    /// [`ArticleBuilder::connect_timeout`], [`ArticleBuilder::max_redirects`],
+    /// [`ArticleBuilder::danger_accept_invalid_certs`],
+    /// [`ArticleBuilder::proxy`] and the cookies are ignored.
+    /// [`ArticleBuilder::get_blocking`] fails with
+    /// [`ArticleError::UnsupportedBlockingOption`], because the client is
+    /// async.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
     /// Downloads the article and extract it's content using the
     /// [`crate::DefaultExtractor`].
     pub async fn get(self) -> Result<Article, ArticleError> {
@@ -348,52 +980,303 @@ impl ArticleBuilder {
         self,
         extractor: &TExtract,
     ) -> Result<Article, ArticleError> {
-        let url = match self.url {
-            Some(u) => u,
-            None => return Err(ArticleError::UrlNotInitialized),
+        let url = self.url.clone().ok_or(ArticleError::UrlNotInitialized)?;
+
+        let client = match &self.client {
+            Some(client) => client.clone(),
+            None => self.build_client()?,
         };
+        let (mut fetch_info, mut bytes) = self.fetch(&client, url).await?;
+        let mut doc = parse(&fetch_info, &bytes);
+        while let Some(target) = self.meta_refresh_url(&fetch_info, &doc, extractor)? {
+            let (target_fetch_info, target_bytes) = self.fetch(&client, target).await?;
+            fetch_info = fetch_info.refreshed(target_fetch_info);
+            bytes = target_bytes;
+            doc = parse(&fetch_info, &bytes);
+        }
+        if let Some(amp_url) = self.amp_url(&fetch_info, &doc, extractor) {
+            match self.fetch(&client, amp_url.clone()).await {
+                Ok((amp_fetch_info, amp_bytes)) => {
+                    let amp_doc = parse(&amp_fetch_info, &amp_bytes);
+                    return self
+                        .canonical(fetch_info.final_url)
+                        .extract(amp_fetch_info, &amp_bytes, amp_doc, extractor);
+                }
+                Err(err) => log::debug!("Failed to download AMP page {}: {}", amp_url, err),
+            }
+        }
+        self.extract(fetch_info, &bytes, doc, extractor)
+    }
+
+    /// Send a GET request to the `url`.
+    ///
+    /// Returns the metadata of the response and its body.
+    async fn fetch(
+        &self,
+        client: &Client,
+        url: Url,
+    ) -> Result<(FetchInfo, Bytes), ArticleError> {
+        let retry_policy = self.retry_policy.clone().unwrap_or_default();
+        let fetch = async {
+            let (resp, attempts) = retry_policy.get(client, url.clone()).await;
+            let resp = resp?;
+
+            if !resp.status().is_success() {
+                return Err(ArticleError::UnsuccessfulRequest(format!(
+                    "{:?} with status {} after {} attempt(s)",
+                    resp.url(),
+                    resp.status(),
+                    attempts
+                )));
+            }
 
+            let fetch_info = FetchInfo::from_response(&resp);
+            let bytes = resp.bytes().await.map_err(ArticleError::Reqwest)?;
+            trace::record_bytes(bytes.len());
+            Ok::<_, ArticleError>((fetch_info, bytes))
+        };
+        trace::fetch(&url, fetch, |(fetch_info, _)| fetch_info.status).await
+    }
+
+    /// The url of the AMP version of the downloaded page, if
+    /// [`ArticleBuilder::prefer_amp`] is set.
+    fn amp_url<TExtract: Extractor>(
+        &self,
+        fetch_info: &FetchInfo,
+        doc: &Document,
+        extractor: &TExtract,
+    ) -> Option<Url> {
+        if !self.prefer_amp {
+            return None;
+        }
+        let url = &fetch_info.final_url;
+        extractor
+            .amp_url(doc, Some(url))
+            .filter(|amp_url| amp_url != url)
+    }
+
+    /// The url the downloaded page redirects to with a meta refresh, if
+    /// [`ArticleBuilder::follow_meta_refresh`] is set.
+    ///
+    /// Fails with [`ArticleError::TooManyMetaRefreshes`] after
+    /// [`crate::extract_meta_refresh::MAX_META_REFRESH_HOPS`] refreshes or if the
+    /// refresh leads back to a page of the chain.
+    fn meta_refresh_url<TExtract: Extractor>(
+        &self,
+        fetch_info: &FetchInfo,
+        doc: &Document,
+        extractor: &TExtract,
+    ) -> Result<Option<Url>, ArticleError> {
+        if !self.follow_meta_refresh {
+            return Ok(None);
+        }
+        match extractor.meta_refresh_url(doc, Some(&fetch_info.final_url)) {
+            Some(target) if !can_follow_meta_refresh(fetch_info, &target) => {
+                Err(ArticleError::TooManyMetaRefreshes(fetch_info.final_url.clone()))
+            }
+            target => Ok(target),
+        }
+    }
+
+    /// Identify the article by the `url` of the original page, unless an
+    /// [`ArticleBuilder::identity_url`] is set.
+    fn canonical(mut self, url: Url) -> Self {
+        self.identity_url = self.identity_url.or(Some(url));
+        self
+    }
+
+    /// Downloads the article without an async runtime and extract it's
+    /// content using the [`crate::DefaultExtractor`].
+    ///
+    /// # Panics
+    ///
+    /// Like all of [`reqwest::blocking`], this must not be called from
+    /// within an async runtime.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub fn get_blocking(self) -> Result<Article, ArticleError> {
+        self.get_blocking_with_extractor(&DefaultExtractor::default())
+    }
+
+    /// Downloads the article without an async runtime and extracts it's
+    /// content using the provided [`crate::Extractor`].
+    ///
+    /// # Panics
+    ///
+    /// Like all of [`reqwest::blocking`], this must not be called from
+    /// within an async runtime.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub fn get_blocking_with_extractor<TExtract: Extractor>(
+        self,
+        extractor: &TExtract,
+    ) -> Result<Article, ArticleError> {
+        if self.client.is_some() {
+            return Err(ArticleError::UnsupportedBlockingOption("client"));
+        }
+        let url = self.url.clone().ok_or(ArticleError::UrlNotInitialized)?;
+
+        let mut builder = reqwest::blocking::Client::builder()
+            .default_headers(self.default_headers()?)
+            .timeout(self.request_timeout())
+            .redirect(self.redirect_policy())
+            .danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+        if let Some(connect_timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.build_proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        #[cfg(feature = "cookies")]
+        if let Some(jar) = self.cookies.jar()? {
+            builder = builder.cookie_provider(jar);
+        }
+        let client = builder.build()?;
+
+        let (mut fetch_info, mut bytes) = self.fetch_blocking(&client, url)?;
+        let mut doc = parse(&fetch_info, &bytes);
+        while let Some(target) = self.meta_refresh_url(&fetch_info, &doc, extractor)? {
+            let (target_fetch_info, target_bytes) = self.fetch_blocking(&client, target)?;
+            fetch_info = fetch_info.refreshed(target_fetch_info);
+            bytes = target_bytes;
+            doc = parse(&fetch_info, &bytes);
+        }
+        if let Some(amp_url) = self.amp_url(&fetch_info, &doc, extractor) {
+            match self.fetch_blocking(&client, amp_url.clone()) {
+                Ok((amp_fetch_info, amp_bytes)) => {
+                    let amp_doc = parse(&amp_fetch_info, &amp_bytes);
+                    return self
+                        .canonical(fetch_info.final_url)
+                        .extract(amp_fetch_info, &amp_bytes, amp_doc, extractor);
+                }
+                Err(err) => log::debug!("Failed to download AMP page {}: {}", amp_url, err),
+            }
+        }
+        self.extract(fetch_info, &bytes, doc, extractor)
+    }
+
+    /// Like [`ArticleBuilder::fetch`], without an async runtime.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    fn fetch_blocking(
+        &self,
+        client: &reqwest::blocking::Client,
+        url: Url,
+    ) -> Result<(FetchInfo, Bytes), ArticleError> {
+        let retry_policy = self.retry_policy.clone().unwrap_or_default();
+        let (resp, attempts) = retry_policy.get_blocking(client, url);
+        let resp = resp?;
+
+        if !resp.status().is_success() {
+            return Err(ArticleError::UnsuccessfulRequest(format!(
+                "{:?} with status {} after {} attempt(s)",
+                resp.url(),
+                resp.status(),
+                attempts
+            )));
+        }
+
+        let fetch_info = FetchInfo::from_blocking_response(&resp);
+        let bytes = resp.bytes().map_err(ArticleError::Reqwest)?;
+        Ok((fetch_info, bytes))
+    }
+
+    /// The client configured by the options of the builder.
+    fn build_client(&self) -> Result<Client, ArticleError> {
         #[cfg(target_arch = "wasm32")]
         let builder = Client::builder();
 
         #[cfg(not(target_arch = "wasm32"))]
         let builder = {
-            let timeout = self
-                .timeout
-                .unwrap_or_else(|| Duration::from_secs(Config::DEFAULT_REQUEST_TIMEOUT_SEC));
-
-            let mut headers = HeaderMap::with_capacity(1);
-
-            let user_agent = self.browser_user_agent
-                .map(|x| x.parse())
-                .unwrap_or_else(|| Config::user_agent().parse());
-            let user_agent = match user_agent {
-                Ok(ua) => ua,
-                Err(_) => return Err(ArticleError::UserAgentParseError),
-            };
-            headers.insert(USER_AGENT, user_agent);
-
-            Client::builder().default_headers(headers).timeout(timeout)
+            let mut builder = Client::builder()
+                .default_headers(self.default_headers()?)
+                .timeout(self.request_timeout())
+                .redirect(self.redirect_policy())
+                .danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+            if let Some(connect_timeout) = self.connect_timeout {
+                builder = builder.connect_timeout(connect_timeout);
+            }
+            if let Some(proxy) = self.build_proxy()? {
+                builder = builder.proxy(proxy);
+            }
+            #[cfg(feature = "cookies")]
+            if let Some(jar) = self.cookies.jar()? {
+                builder = builder.cookie_provider(jar);
+            }
+            builder
         };
 
-        let resp = builder.build()?.get(url).send().await.map_err(ArticleError::Reqwest)?;
+        Ok(builder.build()?)
+    }
 
-        if !resp.status().is_success() {
-            return Err(ArticleError::UnsuccessfulRequest(format!("{:?}", resp.url())));
-        }
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_proxy(&self) -> Result<Option<reqwest::Proxy>, ArticleError> {
+        self.proxy
+            .as_deref()
+            .map(reqwest::Proxy::all)
+            .transpose()
+            .map_err(ArticleError::Reqwest)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn request_timeout(&self) -> Duration {
+        self.timeout
+            .unwrap_or_else(|| Duration::from_secs(Config::DEFAULT_REQUEST_TIMEOUT_SEC))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn redirect_policy(&self) -> reqwest::redirect::Policy {
+        self.max_redirects
+            .map(reqwest::redirect::Policy::limited)
+            .unwrap_or_default()
+    }
 
-        let url = resp.url().to_owned();
-        let bytes = resp.bytes().await.map_err(ArticleError::Reqwest)?;
-        let doc = Document::from_read(&*bytes)
-            .map_err(|_| ArticleError::DocumentReadError(format!("{:?}", url)))?;
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default_headers(&self) -> Result<HeaderMap, ArticleError> {
+        let mut headers = HeaderMap::with_capacity(1);
+        let user_agent = self
+            .browser_user_agent
+            .as_ref()
+            .map(|x| x.parse())
+            .unwrap_or_else(|| Config::user_agent().parse())
+            .map_err(|_| ArticleError::UserAgentParseError)?;
+        headers.insert(USER_AGENT, user_agent);
+        Ok(headers)
+    }
 
-        let content = extractor
-            .article_content(
-                &doc,
-                extractor.base_url(&doc).as_ref(),
-                self.language.clone(),
-            )
+    /// Parses the `body` downloaded with the response of the `fetch_info`
+    /// and extracts the article.
+    fn extract<TExtract: Extractor>(
+        self,
+        fetch_info: FetchInfo,
+        body: &[u8],
+        doc: Document,
+        extractor: &TExtract,
+    ) -> Result<Article, ArticleError> {
+        let url = fetch_info.final_url.clone();
+
+        let page_url = self
+            .identity_url
+            .clone()
+            .or_else(|| self.resolve_base.clone())
+            .unwrap_or_else(|| url.clone());
+        let base_url = self.resolve_base.unwrap_or_else(|| url.clone());
+        let mut content = extractor
+            .article_content(&doc, Some(&base_url), self.language.clone())
             .into_owned();
+        // the identity, not the url the page was fetched from, decides what
+        // is cross domain
+        content.canonical = extractor.canonical_link_detailed(&doc, Some(&page_url));
+        content.ids = extractor.article_ids(&doc, Some(&page_url));
+        content.alternate_languages = extractor.alternate_languages(&doc, Some(&page_url));
+        content.x_default_url = extractor.x_default_url(&doc, Some(&page_url));
+        if is_wayback_url(&url) {
+            unwrap_wayback_links(&mut content);
+        }
+        // offsets are only meaningful for bodies that are valid utf-8
+        if let (true, Ok(raw)) = (self.source_span, std::str::from_utf8(body)) {
+            let lang = content.language.clone().or(self.language.clone()).unwrap_or_default();
+            content.approximate_source_span =
+                article_node_adaptive_with_filters(&doc, lang, extractor.filters())
+                    .and_then(|(node, _)| approximate_source_span(raw, &node));
+        }
 
         // Use the detected language from content, or fall back to builder's language, or default
         let final_language = content.language.clone()
@@ -401,30 +1284,116 @@ impl ArticleBuilder {
             .unwrap_or_default();
 
         Ok(Article {
-            url,
+            url: self.identity_url.unwrap_or(url),
             doc,
             content,
             language: final_language,
+            from_cache: false,
+            fetch_info: Some(fetch_info),
         })
     }
 }
 
+/// Parse the `body` of the response of the `fetch_info`, transcoded to
+/// UTF-8.
+#[cfg(feature = "net")]
+fn parse(fetch_info: &FetchInfo, body: &[u8]) -> Document {
+    let content_type = content_type(&fetch_info.headers);
+    trace::parse(decode(body, content_type.as_deref()).as_ref())
+}
+
 /// Bundles all the content found for an article.
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct ArticleContent<'a> {
     pub authors: Vec<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub title: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub publishing_date: Option<ArticleDate>,
+    /// When the article was last modified, independent of whether a
+    /// published date was found.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub modified_date: Option<Date>,
     pub keywords: Vec<Cow<'a, str>>,
+    /// The section of the site the article belongs to, e.g. `Politics`.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub section: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub description: Option<Cow<'a, str>>,
+    /// Whether the `description` is the meta description of the page or an
+    /// excerpt of the text, if the page has none.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub description_source: Option<DescriptionSource>,
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub text: Option<Cow<'a, str>>,
+    /// Sanitized html of the article's content node.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub html: Option<Cow<'a, str>>,
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub language: Option<Language>,
+    /// The language detected from the text of the article.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub detected_language: Option<DetectedLanguage>,
+    /// The thresholds that selected the article's text node.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub extraction_profile: Option<ExtractionProfile>,
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub thumbnail: Option<Url>,
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub top_image: Option<Url>,
     pub references: Vec<Url>,
+    /// The [`ArticleContent::references`] with their anchor text, `rel` and
+    /// kind relative to the article's url.
+    pub reference_details: Vec<Reference>,
     pub images: Vec<Url>,
     pub videos: Vec<Url>,
+    /// Audio files in the article, like the episode of a podcast.
+    pub audios: Vec<Audio>,
+    /// Calls-to-action, ads and paragraphs with affiliate links.
+    pub promotional_content: Vec<PromoBlock>,
+    /// Number of affiliate links in the article's content.
+    pub affiliate_link_count: usize,
+    /// Paragraphs that list the sources of the article.
+    pub attributions: Vec<Attribution>,
+    /// Statements the article highlights in blockquotes and pull quotes,
+    /// they are part of the text as well.
+    pub quotes: Vec<Quote>,
+    /// Posts of social networks embedded in the article.
+    pub embeds: Vec<SocialEmbed>,
+    /// Approximate byte span of the article's text node in the raw html, see
+    /// [`crate::source_span::approximate_source_span`].
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub approximate_source_span: Option<Range<usize>>,
+    /// How the article's text node was selected.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub diagnostics: Option<Diagnostics>,
+    /// The canonical url the page declares.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub canonical: Option<CanonicalLink>,
+    /// The language editions of the article the page advertises with
+    /// `hreflang`.
+    pub alternate_languages: Vec<(LanguageTag, Url)>,
+    /// The page advertised for all other languages with
+    /// `hreflang="x-default"`.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub x_default_url: Option<Url>,
+    /// The organization that published the article.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub publisher: Option<Publisher>,
+    /// Whether the page holds the full text or only a teaser.
+    pub access: ContentAccess,
+    /// The keys and values of all `<meta>` nodes of the page, like custom
+    /// `parsely-*` keys.
+    #[cfg_attr(feature = "serde0", serde(default, skip_serializing_if = "MetaMap::is_empty"))]
+    pub meta: MetaMap,
+    /// Stable identifiers of the article, to recognize it across sources.
+    #[cfg_attr(feature = "serde0", serde(default, skip_serializing_if = "ArticleIds::is_empty"))]
+    pub ids: ArticleIds,
+    /// The criteria of the [`crate::completeness::Completeness`] an
+    /// incomplete article failed, if the policy only warns.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub incompleteness: Option<Incompleteness>,
 }
 
 impl<'a> ArticleContent<'a> {
@@ -433,6 +1402,16 @@ impl<'a> ArticleContent<'a> {
         ArticleContentBuilder::default()
     }
 
+    /// The published and the modified date of the article.
+    ///
+    /// The published date is `None` if only a modified date was found.
+    pub fn dates(&self) -> ArticleDates {
+        ArticleDates {
+            published: self.publishing_date.as_ref().map(|date| date.published.clone()),
+            modified: self.modified_date.clone(),
+        }
+    }
+
     /// Transfers ownership of the content directly to this `ArticleContent`.
     pub fn into_owned(self) -> ArticleContent<'static> {
         ArticleContent {
@@ -444,20 +1423,43 @@ impl<'a> ArticleContent<'a> {
                 .collect(),
             title: self.title.map(Cow::into_owned).map(Cow::Owned),
             publishing_date: self.publishing_date,
+            modified_date: self.modified_date,
             keywords: self
                 .keywords
                 .into_iter()
                 .map(Cow::into_owned)
                 .map(Cow::Owned)
                 .collect(),
+            section: self.section.map(Cow::into_owned).map(Cow::Owned),
             description: self.description.map(Cow::into_owned).map(Cow::Owned),
+            description_source: self.description_source,
             text: self.text.map(Cow::into_owned).map(Cow::Owned),
+            html: self.html.map(Cow::into_owned).map(Cow::Owned),
             language: self.language,
+            detected_language: self.detected_language,
+            extraction_profile: self.extraction_profile,
             thumbnail: self.thumbnail,
             top_image: self.top_image,
             references: self.references,
+            reference_details: self.reference_details,
             images: self.images,
             videos: self.videos,
+            audios: self.audios,
+            promotional_content: self.promotional_content,
+            affiliate_link_count: self.affiliate_link_count,
+            attributions: self.attributions,
+            quotes: self.quotes,
+            embeds: self.embeds,
+            approximate_source_span: self.approximate_source_span,
+            diagnostics: self.diagnostics,
+            canonical: self.canonical,
+            alternate_languages: self.alternate_languages,
+            x_default_url: self.x_default_url,
+            publisher: self.publisher,
+            access: self.access,
+            meta: self.meta,
+            ids: self.ids,
+            incompleteness: self.incompleteness,
         }
     }
 }
@@ -467,15 +1469,37 @@ pub struct ArticleContentBuilder<'a> {
     pub authors: Option<Vec<Cow<'a, str>>>,
     pub title: Option<Cow<'a, str>>,
     pub publishing_date: Option<ArticleDate>,
+    pub modified_date: Option<Date>,
     pub keywords: Option<Vec<Cow<'a, str>>>,
+    pub section: Option<Cow<'a, str>>,
     pub description: Option<Cow<'a, str>>,
+    pub description_source: Option<DescriptionSource>,
     pub text: Option<Cow<'a, str>>,
+    pub html: Option<Cow<'a, str>>,
     pub language: Option<Language>,
+    pub detected_language: Option<DetectedLanguage>,
+    pub extraction_profile: Option<ExtractionProfile>,
     pub thumbnail: Option<Url>,
     pub top_image: Option<Url>,
     pub references: Option<Vec<Url>>,
+    pub reference_details: Option<Vec<Reference>>,
     pub images: Option<Vec<Url>>,
     pub videos: Option<Vec<Url>>,
+    pub audios: Option<Vec<Audio>>,
+    pub promotional_content: Option<Vec<PromoBlock>>,
+    pub affiliate_link_count: Option<usize>,
+    pub attributions: Option<Vec<Attribution>>,
+    pub quotes: Option<Vec<Quote>>,
+    pub embeds: Option<Vec<SocialEmbed>>,
+    pub approximate_source_span: Option<Range<usize>>,
+    pub diagnostics: Option<Diagnostics>,
+    pub canonical: Option<CanonicalLink>,
+    pub alternate_languages: Option<Vec<(LanguageTag, Url)>>,
+    pub x_default_url: Option<Url>,
+    pub publisher: Option<Publisher>,
+    pub access: Option<ContentAccess>,
+    pub meta: Option<MetaMap>,
+    pub ids: Option<ArticleIds>,
 }
 
 impl<'a> ArticleContentBuilder<'a> {
@@ -494,26 +1518,56 @@ impl<'a> ArticleContentBuilder<'a> {
         self
     }
 
+    pub fn modified_date(mut self, date: Date) -> Self {
+        self.modified_date = Some(date);
+        self
+    }
+
     pub fn keywords(mut self, keywords: Vec<Cow<'a, str>>) -> Self {
         self.keywords = Some(keywords);
         self
     }
 
+    pub fn section(mut self, section: Cow<'a, str>) -> Self {
+        self.section = Some(section);
+        self
+    }
+
     pub fn description(mut self, description: Cow<'a, str>) -> Self {
         self.description = Some(description);
         self
     }
 
+    pub fn description_source(mut self, source: DescriptionSource) -> Self {
+        self.description_source = Some(source);
+        self
+    }
+
     pub fn text(mut self, text: Cow<'a, str>) -> Self {
         self.text = Some(text);
         self
     }
 
+    pub fn html(mut self, html: Cow<'a, str>) -> Self {
+        self.html = Some(html);
+        self
+    }
+
     pub fn language(mut self, language: Language) -> Self {
         self.language = Some(language);
         self
     }
 
+    pub fn detected_language(mut self, detected_language: DetectedLanguage) -> Self {
+        self.detected_language = Some(detected_language);
+        self
+    }
+
+    pub fn extraction_profile(mut self, extraction_profile: ExtractionProfile) -> Self {
+        self.extraction_profile = Some(extraction_profile);
+        self
+    }
+
     pub fn thumbnail(mut self, thumbnail: Url) -> Self {
         self.thumbnail = Some(thumbnail);
         self
@@ -529,6 +1583,11 @@ impl<'a> ArticleContentBuilder<'a> {
         self
     }
 
+    pub fn reference_details(mut self, reference_details: Vec<Reference>) -> Self {
+        self.reference_details = Some(reference_details);
+        self
+    }
+
     pub fn images(mut self, images: Vec<Url>) -> Self {
         self.images = Some(images);
         self
@@ -539,20 +1598,450 @@ impl<'a> ArticleContentBuilder<'a> {
         self
     }
 
+    pub fn audios(mut self, audios: Vec<Audio>) -> Self {
+        self.audios = Some(audios);
+        self
+    }
+
+    pub fn promotional_content(mut self, promotional_content: Vec<PromoBlock>) -> Self {
+        self.promotional_content = Some(promotional_content);
+        self
+    }
+
+    pub fn affiliate_link_count(mut self, affiliate_link_count: usize) -> Self {
+        self.affiliate_link_count = Some(affiliate_link_count);
+        self
+    }
+
+    pub fn attributions(mut self, attributions: Vec<Attribution>) -> Self {
+        self.attributions = Some(attributions);
+        self
+    }
+
+    pub fn quotes(mut self, quotes: Vec<Quote>) -> Self {
+        self.quotes = Some(quotes);
+        self
+    }
+
+    pub fn embeds(mut self, embeds: Vec<SocialEmbed>) -> Self {
+        self.embeds = Some(embeds);
+        self
+    }
+
+    pub fn approximate_source_span(mut self, approximate_source_span: Range<usize>) -> Self {
+        self.approximate_source_span = Some(approximate_source_span);
+        self
+    }
+
+    pub fn diagnostics(mut self, diagnostics: Diagnostics) -> Self {
+        self.diagnostics = Some(diagnostics);
+        self
+    }
+
+    pub fn canonical(mut self, canonical: CanonicalLink) -> Self {
+        self.canonical = Some(canonical);
+        self
+    }
+
+    pub fn alternate_languages(mut self, alternate_languages: Vec<(LanguageTag, Url)>) -> Self {
+        self.alternate_languages = Some(alternate_languages);
+        self
+    }
+
+    pub fn x_default_url(mut self, x_default_url: Url) -> Self {
+        self.x_default_url = Some(x_default_url);
+        self
+    }
+
+    pub fn publisher(mut self, publisher: Publisher) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    pub fn access(mut self, access: ContentAccess) -> Self {
+        self.access = Some(access);
+        self
+    }
+
+    pub fn meta(mut self, meta: MetaMap) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+
+    pub fn ids(mut self, ids: ArticleIds) -> Self {
+        self.ids = Some(ids);
+        self
+    }
+
     pub fn build(self) -> ArticleContent<'a> {
         ArticleContent {
             authors: self.authors.unwrap_or_default(),
             title: self.title,
             publishing_date: self.publishing_date,
+            modified_date: self.modified_date,
             keywords: self.keywords.unwrap_or_default(),
+            section: self.section,
             description: self.description,
+            description_source: self.description_source,
             text: self.text,
+            html: self.html,
             language: self.language,
+            detected_language: self.detected_language,
+            extraction_profile: self.extraction_profile,
             thumbnail: self.thumbnail,
             top_image: self.top_image,
             references: self.references.unwrap_or_default(),
+            reference_details: self.reference_details.unwrap_or_default(),
             images: self.images.unwrap_or_default(),
             videos: self.videos.unwrap_or_default(),
+            audios: self.audios.unwrap_or_default(),
+            promotional_content: self.promotional_content.unwrap_or_default(),
+            affiliate_link_count: self.affiliate_link_count.unwrap_or_default(),
+            attributions: self.attributions.unwrap_or_default(),
+            quotes: self.quotes.unwrap_or_default(),
+            embeds: self.embeds.unwrap_or_default(),
+            approximate_source_span: self.approximate_source_span,
+            diagnostics: self.diagnostics,
+            canonical: self.canonical,
+            alternate_languages: self.alternate_languages.unwrap_or_default(),
+            x_default_url: self.x_default_url,
+            publisher: self.publisher,
+            access: self.access.unwrap_or_default(),
+            meta: self.meta.unwrap_or_default(),
+            ids: self.ids.unwrap_or_default(),
+            incompleteness: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn article_from_fragment() {
+        let fragment = r#"<p>The city council voted on Tuesday to approve the new budget, which
+            includes funding for <a href="/transit/plan.html">public transit</a> and
+            schools. It was the first time in years that the measure passed without
+            opposition from any of the members of the council.</p>
+            <p><img src="/img/council.jpg"></p>"#;
+        let known = KnownFields {
+            title: Some("Council approves budget".to_string()),
+            authors: vec!["Jane Zhu".to_string()],
+            ..Default::default()
+        };
+        let article = Article::from_fragment(
+            fragment,
+            "https://example.com/news/budget.html",
+            FragmentOptions::new(known),
+        )
+        .unwrap();
+
+        assert_eq!(article.content.title.as_deref(), Some("Council approves budget"));
+        assert_eq!(article.content.authors, vec![Cow::Borrowed("Jane Zhu")]);
+        assert!(article
+            .content
+            .text
+            .as_deref()
+            .unwrap()
+            .starts_with("The city council voted"));
+        assert_eq!(
+            article.content.images,
+            vec![Url::parse("https://example.com/img/council.jpg").unwrap()]
+        );
+    }
+
+    #[test]
+    fn article_from_document() {
+        let html = r#"<html><head><title>Council approves budget</title></head><body>
+            <div itemprop="articleBody"><p>The council approved the budget for the next year on Tuesday.</p></div>
+            </body></html>"#;
+        let url = "https://example.com/news/budget.html";
+        let parsed = Article::new(url, html).unwrap();
+        let article = Article::from_document(url, Document::from(html)).unwrap();
+        assert_eq!(article.content.title, parsed.content.title);
+        assert_eq!(article.content.text, parsed.content.text);
+        assert_eq!(article.doc.nodes.len(), parsed.doc.nodes.len());
+        assert!(Article::from_document("not a url", Document::from(html)).is_err());
+    }
+
+    #[test]
+    fn fragments_and_xhtml() {
+        let text = "<p>The city council approved the expansion of the harbour on Tuesday after a long debate.</p>\
+                    <p>Construction will start next spring and is expected to last for ten years.</p>";
+        let fragment = format!(
+            r#"<div itemprop="articleBody">
+            <meta property="og:title" content="Harbour expansion approved">
+            <meta name="description" content="The council approved the harbour expansion.">
+            {}</div>"#,
+            text
+        );
+        let xhtml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <html xmlns="http://www.w3.org/1999/xhtml" xml:lang="en"><head>
+            <title>Harbour expansion approved</title>
+            <meta name="description" content="The council approved the harbour expansion." />
+            </head><body><div itemprop="articleBody">{}<br /></div></body></html>"#,
+            text
+        );
+        for html in [fragment, xhtml] {
+            let article = Article::new("https://example.com/news/harbour.html", &html).unwrap();
+            assert_eq!(article.content.title.as_deref(), Some("Harbour expansion approved"));
+            assert_eq!(
+                article.content.description.as_deref(),
+                Some("The council approved the harbour expansion.")
+            );
+            let text = article.content.text.as_deref().unwrap();
+            assert!(text.contains("approved the expansion"));
+            assert!(text.contains("ten years"));
+        }
+    }
+
+    #[test]
+    fn modified_without_published() {
+        let doc = r#"<html><head>
+            <meta property="article:modified_time" content="2024-03-05T08:00:00Z">
+            <title>Council approves budget</title></head><body></body></html>"#;
+        let article = Article::new("https://example.com/news/budget.html", doc).unwrap();
+        let dates = article.content.dates();
+        assert!(dates.published.is_none());
+        assert!(matches!(dates.modified, Some(Date::DateTime(_))));
+        assert!(article.content.publishing_date.is_none());
+    }
+
+    #[cfg(feature = "serde0")]
+    #[test]
+    fn pure_article_round_trip() {
+        let doc = r#"<html><head>
+            <meta property="article:published_time" content="2020-03-04T10:15:00">
+            <title>Council approves budget</title></head><body></body></html>"#;
+        let article = Article::from_fragment(
+            doc,
+            "https://example.com/news/budget.html",
+            FragmentOptions::new(KnownFields::default()),
+        )
+        .unwrap();
+        let json = serde_json::to_value(article.into_pure()).unwrap();
+
+        assert_eq!(json["url"], "https://example.com/news/budget.html");
+        assert_eq!(json["language"], "english");
+        assert!(json["content"].get("description").is_none());
+        assert!(json["content"]["images"].as_array().unwrap().is_empty());
+
+        let pure: PureArticle = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&pure).unwrap(), json);
+    }
+
+    #[test]
+    fn meta_outlives_document() {
+        let doc = r#"<html><head><title>Council approves budget</title>
+            <meta property="og:image" content="https://example.com/a.jpg">
+            <meta property="og:image" content="https://example.com/b.jpg">
+            <meta name="parsely-tags" content="budget,council"></head><body></body></html>"#;
+        let article = Article::new("https://example.com/news/budget.html", doc).unwrap();
+        assert_eq!(article.meta().get_all("og:image").len(), 2);
+
+        let pure = article.into_pure();
+        assert_eq!(pure.content.meta.get("PARSELY-TAGS"), Some("budget,council"));
+        #[cfg(feature = "serde0")]
+        {
+            let json = serde_json::to_value(&pure).unwrap();
+            assert_eq!(json["content"]["meta"][2], serde_json::json!(["parsely-tags", "budget,council"]));
+            let restored: PureArticle = serde_json::from_value(json).unwrap();
+            assert_eq!(restored.content.meta, pure.content.meta);
         }
     }
+
+    #[cfg(feature = "serde0")]
+    #[test]
+    fn article_date_iso_8601() {
+        let date = ArticleDate {
+            published: Date::DateTime(
+                chrono::NaiveDate::from_ymd_opt(2020, 3, 4)
+                    .unwrap()
+                    .and_hms_opt(10, 15, 0)
+                    .unwrap(),
+            ),
+            last_updated: None,
+            relative: false,
+            offset: None,
+        };
+        let json = serde_json::to_string(&date).unwrap();
+        assert_eq!(json, r#"{"published":{"date_time":"2020-03-04T10:15:00"}}"#);
+
+        let date = ArticleDate {
+            offset: chrono::FixedOffset::east_opt(3 * 3600),
+            ..date
+        };
+        let json = serde_json::to_string(&date).unwrap();
+        assert_eq!(
+            json,
+            r#"{"published":{"date_time":"2020-03-04T10:15:00"},"offset":10800}"#
+        );
+        let date: ArticleDate = serde_json::from_str(&json).unwrap();
+        assert_eq!(date.offset, chrono::FixedOffset::east_opt(3 * 3600));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn prefer_amp_keeps_canonical_url() {
+        let base = crate::test_server::serve_pages(vec![
+            (
+                "/news/festival.html",
+                "<html><head><title>Festival</title><link rel=\"amphtml\" href=\"/amp/news/festival.html\"></head>\
+                 <body><nav><a href=\"/\">Home</a></nav><article>\
+                 <p>The festival opened on Friday with a concert in the old town square.</p></article></body></html>",
+            ),
+            (
+                "/amp/news/festival.html",
+                "<html amp><head><title>Festival</title></head><body><article>\
+                 <p>The festival opened on Friday with a concert in the old town square, \
+                 the AMP version says.</p></article></body></html>",
+            ),
+            (
+                "/news/parade.html",
+                "<html><head><title>Festival</title><link rel=\"amphtml\" href=\"/amp/news/parade.html\"></head>\
+                 <body><nav><a href=\"/\">Home</a></nav><article>\
+                 <p>The parade is going to pass the old town square on Saturday afternoon.</p></article></body></html>",
+            ),
+        ]);
+
+        let url = base.join("news/festival.html").unwrap();
+        let article = Article::builder(url.clone())
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .prefer_amp(true)
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(article.url, url);
+        assert!(article.content.text.unwrap().contains("the AMP version says"));
+
+        // the AMP page is missing
+        let url = base.join("news/parade.html").unwrap();
+        let article = Article::builder(url.clone())
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .prefer_amp(true)
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(article.url, url);
+        assert!(article.content.text.unwrap().contains("Saturday afternoon"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn injected_client_is_used() {
+        // answers only requests with the default header of the shared client
+        let base = crate::test_server::serve(|request| match request.header("x-client") {
+            Some("shared") => crate::test_server::html(
+                "<html><head><title>Festival</title></head><body><article>\
+                 <p>The festival opened on Friday with a concert in the old town square.</p>\
+                 </article></body></html>",
+            ),
+            _ => crate::test_server::status("403 Forbidden"),
+        });
+        let url = base.join("news/festival.html").unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-client", "shared".parse().unwrap());
+        let client = Client::builder().default_headers(headers).build().unwrap();
+        let article = Article::builder(url.clone())
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .client(client)
+            .get()
+            .await
+            .unwrap();
+        assert!(article.content.text.unwrap().contains("old town square"));
+
+        let own_client = Article::builder(url).unwrap().retry_policy(RetryPolicy::none()).get().await;
+        assert!(matches!(own_client, Err(ArticleError::UnsuccessfulRequest(_))));
+    }
+
+    #[cfg(all(feature = "cookies", not(target_arch = "wasm32")))]
+    #[tokio::test]
+    async fn seeded_cookies_are_sent() {
+        // answers with the article only if the consent cookie is sent
+        let base = crate::test_server::serve(|request| match request.header("cookie") {
+            Some(cookie) if cookie.contains("euconsent=1") => crate::test_server::html(
+                "<html><head><title>Festival</title></head><body><article>\
+                 <p>The festival opened on Friday with a concert in the old town square.</p>\
+                 </article></body></html>",
+            ),
+            _ => crate::test_server::status("403 Forbidden"),
+        });
+        let url = base.join("news/festival.html").unwrap();
+
+        let article = Article::builder(url.clone())
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .cookies_for("127.0.0.1", vec![("euconsent", "1")])
+            .get()
+            .await
+            .unwrap();
+        assert!(article.content.text.unwrap().contains("old town square"));
+
+        let other_host = Article::builder(url)
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .cookies_for("www.example.com", vec![("euconsent", "1")])
+            .get()
+            .await;
+        assert!(matches!(other_host, Err(ArticleError::UnsuccessfulRequest(_))));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[tokio::test]
+    async fn follows_meta_refresh() {
+        use crate::extract_meta_refresh::MAX_META_REFRESH_HOPS;
+
+        // `/old` and `/moved` are stubs on the way to `/new`, `/loop/a` and
+        // `/loop/b` refresh to each other and `/hop/{n}` to `/hop/{n + 1}`
+        let base = crate::test_server::serve(|request| {
+            let refresh = match request.path.as_str() {
+                "/old" => Some("0;url=/moved".to_string()),
+                "/moved" => Some("0; URL='/new'".to_string()),
+                "/loop/a" => Some("0;url=/loop/b".to_string()),
+                "/loop/b" => Some("0;url=/loop/a".to_string()),
+                path => path
+                    .strip_prefix("/hop/")
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .map(|n| format!("0;url=/hop/{}", n + 1)),
+            };
+            let head = refresh
+                .map(|content| format!(r#"<meta http-equiv="refresh" content="{}">"#, content))
+                .unwrap_or_default();
+            crate::test_server::html(format!(
+                "<html><head>{}<title>Festival</title></head><body><article>\
+                 <p>The festival opened on Friday with a concert in the old town square.</p>\
+                 </article></body></html>",
+                head
+            ))
+        });
+        let get = |path: &str, follow: bool| {
+            Article::builder(base.join(path).unwrap())
+                .unwrap()
+                .retry_policy(RetryPolicy::none())
+                .follow_meta_refresh(follow)
+                .get()
+        };
+
+        let article = get("old", true).await.unwrap();
+        assert_eq!(article.url, base.join("new").unwrap());
+        assert_eq!(article.redirect_chain(), &[base.join("old").unwrap(), base.join("moved").unwrap()]);
+
+        let stub = get("old", false).await.unwrap();
+        assert_eq!(stub.url, base.join("old").unwrap());
+        assert!(stub.redirect_chain().is_empty());
+
+        // the stub that refreshes back or once too often isn't extracted
+        let looped = get("loop/a", true).await;
+        assert!(matches!(looped, Err(ArticleError::TooManyMetaRefreshes(url)) if url == base.join("loop/b").unwrap()));
+
+        let limited = get("hop/0", true).await;
+        let last = base.join(&format!("hop/{}", MAX_META_REFRESH_HOPS)).unwrap();
+        assert!(matches!(limited, Err(ArticleError::TooManyMetaRefreshes(url)) if url == last));
+    }
 }
@@ -0,0 +1,277 @@
+use std::ops::Deref;
+use std::time::Duration;
+
+use select::node::Node;
+use select::predicate::{Attr, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use crate::extract_json_ld::{has_type, text, values};
+
+/// Attributes of an audio player with the url of the file, in the order
+/// they are checked.
+pub const AUDIO_URL_ATTRS: [&str; 2] = ["data-audio-url", "data-audio-src"];
+
+/// The data of an audio of an article, like the episode on the show notes
+/// page of a podcast, independent of the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Audio {
+    /// The url of the audio file.
+    pub url: Url,
+    /// The type of the file, like `audio/mpeg`.
+    pub mime: Option<String>,
+    pub duration: Option<Duration>,
+    pub title: Option<String>,
+}
+
+impl Audio {
+    /// The audio of the `node`, `None` if it has no source.
+    ///
+    /// A missing duration, type or title is taken from the JSON-LD
+    /// `AudioObject` with the same `contentUrl` of the `json_ld` objects of
+    /// the document, see [`crate::extract_json_ld::json_ld`].
+    pub fn from_node(node: &AudioNode, base_url: Option<&Url>, json_ld: &[Value]) -> Option<Self> {
+        let url = node.source_url(base_url)?;
+        let mut audio = Self {
+            mime: node.mime(),
+            duration: node.duration(),
+            title: node.title(),
+            url,
+        };
+        if (audio.duration.is_none() || audio.mime.is_none() || audio.title.is_none())
+            && let Some(object) = audio_objects(json_ld).find(|object| {
+                text(object, "contentUrl")
+                    .and_then(|url| Url::options().base_url(base_url).parse(url).ok())
+                    .map(|url| url == audio.url)
+                    .unwrap_or_default()
+            })
+        {
+            audio.duration = audio.duration.or_else(|| text(object, "duration").and_then(parse_duration));
+            audio.mime = audio.mime.or_else(|| text(object, "encodingFormat").map(str::to_string));
+            audio.title = audio.title.or_else(|| text(object, "name").map(str::to_string));
+        }
+        Some(audio)
+    }
+}
+
+/// The JSON-LD `AudioObject`s, also those in the `audio` or
+/// `associatedMedia` of another object like a `PodcastEpisode`.
+fn audio_objects(objects: &[Value]) -> impl Iterator<Item = &Value> {
+    objects
+        .iter()
+        .flat_map(|object| {
+            std::iter::once(object)
+                .chain(values(object, "audio"))
+                .chain(values(object, "associatedMedia"))
+        })
+        .filter(|object| has_type(object, &["AudioObject"]))
+}
+
+pub struct AudioNode<'a> {
+    inner: Node<'a>,
+}
+
+impl<'a> AudioNode<'a> {
+    /// The common predicate to identify audio nodes: `<audio>`,
+    /// `<amp-audio>` and players with one of the [`AUDIO_URL_ATTRS`].
+    pub fn node_predicate() -> impl Predicate {
+        Name("audio")
+            .or(Name("amp-audio"))
+            .or(Attr(AUDIO_URL_ATTRS[0], ()))
+            .or(Attr(AUDIO_URL_ATTRS[1], ()))
+    }
+
+    pub fn new(inner: Node<'a>) -> Self {
+        Self { inner }
+    }
+
+    /// The source of the audio: one of the [`AUDIO_URL_ATTRS`], the `src`
+    /// or the first `<source>` child with a `src`.
+    pub fn get_src(&self) -> Option<&'a str> {
+        AUDIO_URL_ATTRS
+            .iter()
+            .chain(std::iter::once(&"src"))
+            .find_map(|name| attr(&self.inner, name))
+            .or_else(|| self.source().and_then(|source| attr(&source, "src")))
+    }
+
+    /// The source of the audio, resolved against the `base_url`.
+    pub fn source_url(&self, base_url: Option<&Url>) -> Option<Url> {
+        Url::options().base_url(base_url).parse(self.get_src()?).ok()
+    }
+
+    /// The `type` of the node or its first `<source>`, like `audio/mpeg`.
+    pub fn mime(&self) -> Option<String> {
+        attr(&self.inner, "type")
+            .or_else(|| attr(&self.inner, "data-audio-type"))
+            .or_else(|| self.source().and_then(|source| attr(&source, "type")))
+            .map(str::to_string)
+    }
+
+    /// The duration of the `duration` or `data-duration` attribute, in
+    /// seconds, like `1830`, as `30:30` or as ISO 8601 like `PT30M30S`.
+    pub fn duration(&self) -> Option<Duration> {
+        ["duration", "data-duration"]
+            .iter()
+            .find_map(|name| attr(&self.inner, name).and_then(parse_duration))
+    }
+
+    /// The `title`, `data-title` or `aria-label` of the node.
+    pub fn title(&self) -> Option<String> {
+        ["title", "data-title", "aria-label"]
+            .iter()
+            .find_map(|name| attr(&self.inner, name))
+            .map(str::to_string)
+    }
+
+    /// The first `<source>` child with a `src`, preferring audio types.
+    fn source(&self) -> Option<Node<'a>> {
+        let sources: Vec<_> = self
+            .inner
+            .find(Name("source"))
+            .filter(|source| attr(source, "src").is_some())
+            .collect();
+        sources
+            .iter()
+            .find(|source| {
+                attr(source, "type")
+                    .map(|ty| ty.to_ascii_lowercase().starts_with("audio/"))
+                    .unwrap_or_default()
+            })
+            .or_else(|| sources.first())
+            .copied()
+    }
+}
+
+impl<'a> Deref for AudioNode<'a> {
+    type Target = Node<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// The trimmed, non-empty value of the attribute.
+fn attr<'a>(node: &Node<'a>, name: &str) -> Option<&'a str> {
+    node.attr(name).map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Parse a duration in seconds like `1830` or `1830.5`, as `30:30` or
+/// `0:30:30`, or as ISO 8601 like `PT30M30S`.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if let Some(iso) = s.strip_prefix('P').or_else(|| s.strip_prefix('p')) {
+        return parse_iso_duration(iso);
+    }
+    if s.contains(':') {
+        let mut seconds = 0f64;
+        for part in s.split(':') {
+            seconds = seconds * 60.0 + part.trim().parse::<f64>().ok().filter(|n| *n >= 0.0)?;
+        }
+        return Duration::try_from_secs_f64(seconds).ok();
+    }
+    s.parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+}
+
+/// Parse the part after the `P` of an ISO 8601 duration, like `T1H2M3S` or
+/// `1DT2H`.
+fn parse_iso_duration(s: &str) -> Option<Duration> {
+    let mut seconds = 0f64;
+    let mut number = String::new();
+    let mut in_time = false;
+    let mut any = false;
+    for c in s.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if number.is_empty() => in_time = true,
+            c if c.is_ascii_digit() || c == '.' || c == ',' => number.push(if c == ',' { '.' } else { c }),
+            unit => {
+                let value: f64 = number.parse().ok()?;
+                number.clear();
+                seconds += value
+                    * match (unit, in_time) {
+                        ('W', false) => 7.0 * 86400.0,
+                        ('D', false) => 86400.0,
+                        ('H', true) => 3600.0,
+                        ('M', true) => 60.0,
+                        ('S', true) => 1.0,
+                        _ => return None,
+                    };
+                any = true;
+            }
+        }
+    }
+    if !number.is_empty() || !any {
+        return None;
+    }
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+
+    use super::*;
+    use crate::extract_json_ld::json_ld;
+
+    #[test]
+    fn durations() {
+        let secs = |s: &str| parse_duration(s).map(|duration| duration.as_secs());
+        assert_eq!(secs("1830"), Some(1830));
+        assert_eq!(secs("30:30"), Some(1830));
+        assert_eq!(secs("1:00:05"), Some(3605));
+        assert_eq!(secs("PT30M30S"), Some(1830));
+        assert_eq!(secs("PT1H2M3S"), Some(3723));
+        assert_eq!(secs("P1DT1H"), Some(90000));
+        assert_eq!(secs("PT"), None);
+        assert_eq!(secs("PT5X"), None);
+        assert_eq!(secs("live"), None);
+    }
+
+    #[test]
+    fn audio_players() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {"@context": "https://schema.org", "@type": "PodcastEpisode", "name": "Episode 12",
+             "associatedMedia": {"@type": "AudioObject", "contentUrl": "https://cdn.example.com/ep12.mp3",
+                                 "duration": "PT42M10S", "name": "Episode 12: The harbour"}}
+            </script></head><body>
+            <audio controls><source src="/ep12.ogg" type="audio/ogg"><source src="https://cdn.example.com/ep12.mp3" type="audio/mpeg"></audio>
+            <div class="player" data-audio-url="/audio/interview.mp3" data-duration="12:30" data-title="Interview"></div>
+            <audio src="/audio/clip.m4a" duration="95"></audio>
+            </body></html>"#,
+        );
+        let base = Url::parse("https://example.com/podcast/").unwrap();
+        let objects = json_ld(&doc);
+        let audios: Vec<_> = doc
+            .find(AudioNode::node_predicate())
+            .filter_map(|node| Audio::from_node(&AudioNode::new(node), Some(&base), &objects))
+            .collect();
+        assert_eq!(audios.len(), 3);
+
+        assert_eq!(audios[0].url.as_str(), "https://example.com/ep12.ogg");
+        assert_eq!(audios[0].mime.as_deref(), Some("audio/ogg"));
+        assert_eq!(audios[0].duration, None);
+
+        assert_eq!(audios[1].url.as_str(), "https://example.com/audio/interview.mp3");
+        assert_eq!(audios[1].duration, Some(Duration::from_secs(750)));
+        assert_eq!(audios[1].title.as_deref(), Some("Interview"));
+
+        assert_eq!(audios[2].duration, Some(Duration::from_secs(95)));
+
+        // the JSON-LD fills in the duration and title of the same file
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">
+            {"@type": "AudioObject", "contentUrl": "/ep12.mp3", "duration": "PT42M10S", "name": "Episode 12"}
+            </script></head><body><audio src="https://example.com/ep12.mp3" type="audio/mpeg"></audio></body></html>"#,
+        );
+        let node = AudioNode::new(doc.find(Name("audio")).next().unwrap());
+        let audio = Audio::from_node(&node, Some(&base), &json_ld(&doc)).unwrap();
+        assert_eq!(audio.duration, Some(Duration::from_secs(2530)));
+        assert_eq!(audio.title.as_deref(), Some("Episode 12"));
+    }
+}
@@ -0,0 +1,81 @@
+//! Parallel extraction of already downloaded documents with `rayon`.
+//!
+//! Extraction is CPU bound, so a large set of stored html files is
+//! extracted faster across all cores:
+//!
+//! ```no_run
+//! use extrablatt_v2::batch::extract_batch;
+//! use extrablatt_v2::{DefaultExtractor, Language};
+//! # fn stored_pages() -> Vec<(url::Url, String)> { Vec::new() }
+//!
+//! let articles = extract_batch(stored_pages(), &DefaultExtractor::default(), Language::English);
+//! ```
+//!
+//! The documents are parsed on the worker threads, the extractor is shared
+//! between them. The batch runs on the global `rayon` pool, use
+//! `rayon::ThreadPool::install` to limit the number of threads.
+
+use rayon::prelude::*;
+use url::Url;
+
+use crate::article::{Article, ArticleError, PureArticle};
+use crate::extract::Extractor;
+use crate::language::Language;
+
+/// Extract the articles from the `(url, html)` pairs in parallel with the
+/// `extractor` and the expected `lang`.
+///
+/// The results are in the order of the `inputs`, each is the same as that of
+/// [`Article::with_extractor_and_lang`] converted into a [`PureArticle`].
+pub fn extract_batch<I, E>(inputs: I, extractor: &E, lang: Language) -> Vec<Result<PureArticle, ArticleError>>
+where
+    I: IntoParallelIterator<Item = (Url, String)>,
+    E: Extractor + Sync,
+{
+    inputs
+        .into_par_iter()
+        .map(|(url, html)| {
+            Article::with_extractor_and_lang(url, html, extractor, lang.clone()).map(Article::into_pure)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DefaultExtractor;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/newspaper/harbour.html");
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn parallel_equals_serial() {
+        assert_send_sync::<DefaultExtractor>();
+        assert_send_sync::<Language>();
+
+        let extractor = DefaultExtractor::default();
+        let inputs: Vec<_> = (0..100)
+            .map(|n| {
+                let url = Url::parse(&format!("https://example.com/news/harbour-{}.html", n)).unwrap();
+                (url, FIXTURE.to_string())
+            })
+            .collect();
+        let serial: Vec<_> = inputs
+            .iter()
+            .map(|(url, html)| {
+                Article::with_extractor_and_lang(url.clone(), html, &extractor, Language::English)
+                    .map(Article::into_pure)
+                    .unwrap()
+            })
+            .collect();
+
+        let parallel = extract_batch(inputs, &extractor, Language::English);
+        assert_eq!(parallel.len(), serial.len());
+        for (parallel, serial) in parallel.into_iter().zip(serial) {
+            let parallel = parallel.unwrap();
+            assert_eq!(parallel.url, serial.url);
+            assert_eq!(format!("{:?}", parallel.content), format!("{:?}", serial.content));
+        }
+    }
+}
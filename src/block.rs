@@ -0,0 +1,413 @@
+use select::node::{Data, Node};
+use select::predicate::{Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::embed::is_social_embed;
+use crate::text::normalize_whitespace;
+
+/// The prefix of an item of a nested list per level of nesting.
+pub const NESTED_LIST_INDENT: &str = "  ";
+
+/// Elements whose text is separated from the text around them.
+const BLOCK_TAGS: [&str; 12] = [
+    "p", "div", "br", "li", "td", "th", "tr", "section", "figure", "figcaption", "header", "footer",
+];
+
+/// Elements that are part of the text around them, like the text of a
+/// paragraph.
+const INLINE_TAGS: [&str; 25] = [
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "data", "dfn", "em", "font", "i", "kbd", "mark", "q", "s",
+    "samp", "small", "span", "strong", "sub", "sup", "time", "u", "var",
+];
+
+/// Elements that never contribute text.
+const SKIPPED_TAGS: [&str; 6] = ["script", "style", "noscript", "template", "iframe", "svg"];
+
+/// A structural part of the article's text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum ContentBlock {
+    /// The text of a `<p>`.
+    Paragraph(String),
+    /// A `<h1>` to `<h6>` with its level.
+    Heading { level: u8, text: String },
+    /// The items of a `<ul>` or `<ol>`. The items of nested lists follow
+    /// their parent item, prefixed with [`NESTED_LIST_INDENT`] per level.
+    List { ordered: bool, items: Vec<String> },
+    /// The cells of a `<table>`, without empty rows.
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// The text of a `<blockquote>`, including its attribution.
+    Quote(String),
+}
+
+impl ContentBlock {
+    /// The text of a paragraph, heading or quote.
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            ContentBlock::Paragraph(text) | ContentBlock::Quote(text) => Some(text),
+            ContentBlock::Heading { text, .. } => Some(text),
+            ContentBlock::List { .. } | ContentBlock::Table { .. } => None,
+        }
+    }
+
+    /// The block as Markdown.
+    ///
+    /// All items of a list are numbered or bulleted like the outermost list.
+    pub fn to_markdown(&self) -> String {
+        match self {
+            ContentBlock::Paragraph(text) => text.clone(),
+            ContentBlock::Heading { level, text } => {
+                format!("{} {}", "#".repeat((*level).clamp(1, 6) as usize), text)
+            }
+            ContentBlock::List { ordered, items } => {
+                let (marker, indent) = if *ordered { ("1.", "   ") } else { ("-", "  ") };
+                items
+                    .iter()
+                    .map(|item| {
+                        let mut depth = 0;
+                        let mut text = item.as_str();
+                        while let Some(nested) = text.strip_prefix(NESTED_LIST_INDENT) {
+                            depth += 1;
+                            text = nested;
+                        }
+                        format!("{}{} {}", indent.repeat(depth), marker, text)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            ContentBlock::Table { headers, rows } => {
+                let columns = rows.iter().map(Vec::len).chain([headers.len()]).max().unwrap_or_default();
+                let row = |cells: &[String]| {
+                    let cells: Vec<String> = (0..columns)
+                        .map(|i| cells.get(i).map(|cell| cell.replace('|', "\\|")).unwrap_or_default())
+                        .collect();
+                    format!("| {} |", cells.join(" | "))
+                };
+                let mut lines = vec![row(headers), format!("|{}", " --- |".repeat(columns))];
+                lines.extend(rows.iter().map(|cells| row(cells)));
+                lines.join("\n")
+            }
+            ContentBlock::Quote(text) => format!("> {}", text),
+        }
+    }
+}
+
+/// The `blocks` as Markdown, separated by blank lines.
+pub fn to_markdown(blocks: &[ContentBlock]) -> String {
+    blocks
+        .iter()
+        .map(ContentBlock::to_markdown)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The blocks within the `node` in document order.
+///
+/// Descendants for which `skip` returns `true` are left out together with
+/// their subtree. Text placed directly in a container like a `<div>`,
+/// without a `<p>`, is a paragraph of its own. Tables that contain other
+/// tables are used for layout, so their cells are searched for blocks
+/// instead.
+pub fn blocks<F: Fn(&Node) -> bool>(node: &Node, skip: F) -> Vec<ContentBlock> {
+    let mut blocks = Vec::new();
+    container_blocks(*node, &skip, &mut blocks);
+    blocks
+}
+
+/// Push the blocks of the children of the container `node`. Each run of
+/// text and inline elements between its other children is a paragraph.
+fn container_blocks<F: Fn(&Node) -> bool>(node: Node, skip: &F, blocks: &mut Vec<ContentBlock>) {
+    let mut run = String::new();
+    for child in node.children() {
+        let inline = match child.data() {
+            Data::Text(_) => true,
+            Data::Element(..) => child.name().map(|name| INLINE_TAGS.contains(&name)).unwrap_or_default(),
+            Data::Comment(_) => continue,
+        };
+        if inline {
+            push_text(child, skip, &mut run);
+        } else {
+            push_paragraph(&mut run, blocks);
+            collect_blocks(child, skip, blocks);
+        }
+    }
+    push_paragraph(&mut run, blocks);
+}
+
+/// Push the text of the `run` as a paragraph, unless it's empty, and clear
+/// it.
+fn push_paragraph(run: &mut String, blocks: &mut Vec<ContentBlock>) {
+    blocks.extend(Some(ContentBlock::Paragraph(normalize_whitespace(run))).filter(has_text));
+    run.clear();
+}
+
+fn collect_blocks<F: Fn(&Node) -> bool>(node: Node, skip: &F, blocks: &mut Vec<ContentBlock>) {
+    let Some(name) = node.name() else {
+        return;
+    };
+    if SKIPPED_TAGS.contains(&name) || skip(&node) {
+        return;
+    }
+    let block = match name {
+        "p" => Some(ContentBlock::Paragraph(block_text(node, skip))).filter(has_text),
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Some(ContentBlock::Heading {
+            level: name[1..].parse().unwrap_or(1),
+            text: block_text(node, skip),
+        })
+        .filter(has_text),
+        "ul" | "ol" => {
+            let mut items = Vec::new();
+            list_items(node, 0, skip, &mut items);
+            Some(ContentBlock::List {
+                ordered: name == "ol",
+                items,
+            })
+            .filter(|block| matches!(block, ContentBlock::List { items, .. } if !items.is_empty()))
+        }
+        "table" if node.find(Name("table")).next().is_none() => table(node, skip),
+        "blockquote" if !is_social_embed(&node) => {
+            Some(ContentBlock::Quote(block_text(node, skip))).filter(has_text)
+        }
+        "blockquote" => None,
+        _ => {
+            container_blocks(node, skip, blocks);
+            None
+        }
+    };
+    blocks.extend(block);
+}
+
+fn has_text(block: &ContentBlock) -> bool {
+    block.text().map(|text| !text.is_empty()).unwrap_or_default()
+}
+
+/// Push the items of the list, those of nested lists indented by their
+/// `depth`.
+fn list_items<F: Fn(&Node) -> bool>(list: Node, depth: usize, skip: &F, items: &mut Vec<String>) {
+    for item in list.children().filter(|child| Name("li").matches(child)) {
+        if skip(&item) {
+            continue;
+        }
+        let text = item_text(item, skip);
+        if !text.is_empty() {
+            items.push(format!("{}{}", NESTED_LIST_INDENT.repeat(depth), text));
+        }
+        for nested in item.find(Name("ul").or(Name("ol"))) {
+            // only the lists directly within this item, deeper ones are
+            // handled by their own item
+            let parent_item = std::iter::successors(nested.parent(), Node::parent)
+                .find(|ancestor| Name("li").matches(ancestor));
+            if parent_item.map(|parent| parent.index()) == Some(item.index()) && !skip(&nested) {
+                list_items(nested, depth + 1, skip, items);
+            }
+        }
+    }
+}
+
+/// The text of a list item without its nested lists.
+fn item_text<F: Fn(&Node) -> bool>(item: Node, skip: &F) -> String {
+    block_text(item, &|node: &Node| skip(node) || Name("ul").or(Name("ol")).matches(node))
+}
+
+fn table<F: Fn(&Node) -> bool>(table: Node, skip: &F) -> Option<ContentBlock> {
+    let mut headers = Vec::new();
+    let mut rows = Vec::new();
+    for (row, in_head) in table_rows(table) {
+        if skip(&row) {
+            continue;
+        }
+        let cells: Vec<Node> = row
+            .children()
+            .filter(|cell| Name("td").or(Name("th")).matches(cell))
+            .collect();
+        let texts: Vec<String> = cells.iter().map(|cell| block_text(*cell, skip)).collect();
+        if texts.iter().all(String::is_empty) {
+            continue;
+        }
+        let is_header_row = in_head || cells.iter().all(|cell| Name("th").matches(cell));
+        if headers.is_empty() && rows.is_empty() && is_header_row {
+            headers = texts;
+        } else {
+            rows.push(texts);
+        }
+    }
+    if headers.is_empty() && rows.is_empty() {
+        return None;
+    }
+    Some(ContentBlock::Table { headers, rows })
+}
+
+/// The rows of the table, together with whether they are in its `<thead>`.
+fn table_rows(table: Node) -> Vec<(Node, bool)> {
+    let mut rows = Vec::new();
+    for child in table.children() {
+        match child.name() {
+            Some("tr") => rows.push((child, false)),
+            Some(section @ ("thead" | "tbody" | "tfoot")) => rows.extend(
+                child
+                    .children()
+                    .filter(|row| Name("tr").matches(row))
+                    .map(|row| (row, section == "thead")),
+            ),
+            _ => {}
+        }
+    }
+    rows
+}
+
+fn block_text<F: Fn(&Node) -> bool>(node: Node, skip: &F) -> String {
+    let mut text = String::new();
+    for child in node.children() {
+        push_text(child, skip, &mut text);
+    }
+    normalize_whitespace(&text)
+}
+
+fn push_text<F: Fn(&Node) -> bool>(node: Node, skip: &F, text: &mut String) {
+    match node.data() {
+        Data::Text(txt) => text.push_str(txt),
+        Data::Element(..) => {
+            let name = node.name().unwrap_or_default();
+            if SKIPPED_TAGS.contains(&name) || skip(&node) {
+                return;
+            }
+            let is_block = BLOCK_TAGS.contains(&name);
+            if is_block {
+                text.push(' ');
+            }
+            for child in node.children() {
+                push_text(child, skip, text);
+            }
+            if is_block {
+                text.push(' ');
+            }
+        }
+        Data::Comment(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+
+    use super::*;
+
+    #[test]
+    fn blocks_in_document_order() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <h2>Ingredients</h2>
+            <p>You need <b>fresh</b> herbs.</p>
+            <ul>
+              <li>Flour</li>
+              <li>Herbs<ul><li>Basil</li><li>Thyme<ol><li>dried</li></ol></li></ul></li>
+              <li class="ad">Buy our cookbook</li>
+            </ul>
+            <table>
+              <thead><tr><th>Team</th><th>Points</th></tr></thead>
+              <tbody><tr><td>Harbour FC</td><td>12</td></tr><tr><td></td><td></td></tr>
+              <tr><td>City United</td><td>9</td></tr></tbody>
+            </table>
+            <blockquote><p>Best soup in town.</p><footer>— A critic</footer></blockquote>
+            <blockquote class="twitter-tweet"><p>Big news!</p></blockquote>
+            <script>var x = 1;</script>
+            </article></body></html>"#,
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        let blocks = blocks(&article, |node| node.attr("class") == Some("ad"));
+        assert_eq!(
+            blocks,
+            vec![
+                ContentBlock::Heading {
+                    level: 2,
+                    text: "Ingredients".to_string()
+                },
+                ContentBlock::Paragraph("You need fresh herbs.".to_string()),
+                ContentBlock::List {
+                    ordered: false,
+                    items: vec![
+                        "Flour".to_string(),
+                        "Herbs".to_string(),
+                        "  Basil".to_string(),
+                        "  Thyme".to_string(),
+                        "    dried".to_string(),
+                    ],
+                },
+                ContentBlock::Table {
+                    headers: vec!["Team".to_string(), "Points".to_string()],
+                    rows: vec![
+                        vec!["Harbour FC".to_string(), "12".to_string()],
+                        vec!["City United".to_string(), "9".to_string()],
+                    ],
+                },
+                ContentBlock::Quote("Best soup in town. — A critic".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown() {
+        let blocks = vec![
+            ContentBlock::Heading {
+                level: 2,
+                text: "Ingredients".to_string(),
+            },
+            ContentBlock::List {
+                ordered: false,
+                items: vec!["Herbs".to_string(), "  Basil".to_string()],
+            },
+            ContentBlock::Table {
+                headers: vec!["Team".to_string(), "Points".to_string()],
+                rows: vec![vec!["Harbour | FC".to_string(), "12".to_string()]],
+            },
+            ContentBlock::Quote("Best soup in town.".to_string()),
+        ];
+        assert_eq!(
+            to_markdown(&blocks),
+            "## Ingredients\n\n- Herbs\n  - Basil\n\n| Team | Points |\n| --- | --- |\n| Harbour \\| FC | 12 |\n\n> Best soup in town."
+        );
+    }
+
+    #[test]
+    fn text_directly_in_containers() {
+        let doc = Document::from(
+            r#"<html><body><article><div>The harbour will be <b>expanded</b> over the next ten years.
+            <p>The first ships are expected in the spring.</p>
+            The port authority welcomed the decision.</div></article></body></html>"#,
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        assert_eq!(
+            blocks(&article, |_| false),
+            vec![
+                ContentBlock::Paragraph("The harbour will be expanded over the next ten years.".to_string()),
+                ContentBlock::Paragraph("The first ships are expected in the spring.".to_string()),
+                ContentBlock::Paragraph("The port authority welcomed the decision.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn layout_tables_are_searched() {
+        let doc = Document::from(
+            r#"<html><body><table><tr><td>
+            <p>The harbour will be expanded.</p>
+            <table><tr><th>Year</th></tr><tr><td>2030</td></tr></table>
+            </td></tr></table></body></html>"#,
+        );
+        let body = doc.find(Name("body")).next().unwrap();
+        assert_eq!(
+            blocks(&body, |_| false),
+            vec![
+                ContentBlock::Paragraph("The harbour will be expanded.".to_string()),
+                ContentBlock::Table {
+                    headers: vec!["Year".to_string()],
+                    rows: vec![vec!["2030".to_string()]],
+                },
+            ]
+        );
+    }
+}
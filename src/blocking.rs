@@ -0,0 +1,384 @@
+//! A blocking api for downloading articles without an async runtime.
+//!
+//! The extraction is the same as for the async api, only the requests are
+//! sent with [`reqwest::blocking`]. Like [`reqwest::blocking`] itself, none of
+//! this must be called from within an async runtime.
+//!
+//! ```no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let mut site = extrablatt_v2::Extrablatt::builder("https://cnn.com/")?.build_blocking()?;
+//!     site.download_all_remaining_categories();
+//!     for (url, content) in site.download_articles().successes() {
+//!         // ...
+//!     }
+//! #   Ok(())
+//! # }
+//! ```
+
+use fnv::FnvHashMap;
+use reqwest::blocking::{Client, Response};
+use reqwest::Url;
+use select::document::Document;
+use wasm_timer::Instant;
+
+use crate::article::ArticleUrl;
+use crate::charset::{content_type, decode};
+use crate::error::ExtrablattError;
+use crate::extrablatt::{ArticleDownloadIter, Config, DocumentDownloadState, CrawlQueue};
+use crate::extract::{DefaultExtractor, Extractor};
+use crate::fetch_info::FetchInfo;
+use crate::language::{Language, LanguageOverrides};
+use crate::retry::RetryPolicy;
+use crate::trace;
+use crate::url_class::UrlClassStats;
+use crate::Category;
+
+/// Caches article downloads, like [`crate::Extrablatt`], but blocks the
+/// current thread for each request.
+///
+/// Created with [`crate::ExtrablattBuilder::build_blocking`].
+#[derive(Debug)]
+pub struct Extrablatt<TExtractor: Extractor = DefaultExtractor> {
+    /// The blocking [`reqwest::blocking::Client`] that sends requests.
+    client: Client,
+    /// The expected language of this newspaper.
+    language: Language,
+    /// Languages of articles that differ from the language of the newspaper.
+    languages: LanguageOverrides,
+    /// The parsed main page.
+    main_page: Document,
+    /// Url of the main page.
+    base_url: Url,
+    /// The [`crate::Extractor`] used for content retrieval.
+    extractor: TExtractor,
+    /// The categories and articles of the crawl and their download state.
+    queue: CrawlQueue,
+    /// Configuration for article extraction.
+    config: Config,
+    /// When to repeat failed downloads.
+    retry_policy: RetryPolicy,
+}
+
+impl<TExtractor: Extractor> Extrablatt<TExtractor> {
+    /// Downloads the main page at `base_url`.
+    pub(crate) fn new(
+        client: Client,
+        base_url: Url,
+        language: Language,
+        extractor: TExtractor,
+        config: Config,
+        retry_policy: RetryPolicy,
+        queue: CrawlQueue,
+    ) -> Result<Self, ExtrablattError> {
+        let (resp, attempts) = retry_policy.get_blocking(&client, base_url.clone());
+        let (main_page, ..) = document_from_response(resp, attempts, true)?;
+        Ok(Self {
+            client,
+            language,
+            languages: LanguageOverrides::default(),
+            main_page,
+            base_url,
+            extractor,
+            queue,
+            config,
+            retry_policy,
+        })
+    }
+
+    /// Use the `languages` for the articles they match instead of the
+    /// language of the newspaper.
+    pub(crate) fn with_language_overrides(mut self, languages: LanguageOverrides) -> Self {
+        self.languages = languages;
+        self
+    }
+
+    /// The expected language of the articles.
+    #[inline]
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    /// The configuration for article extraction.
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The policy for repeating failed downloads.
+    #[inline]
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// The extractor used for content retrieval.
+    #[inline]
+    pub fn extractor(&self) -> &TExtractor {
+        &self.extractor
+    }
+
+    /// All known categories and their download state.
+    #[inline]
+    pub fn categories(&self) -> &FnvHashMap<Category, DocumentDownloadState> {
+        self.queue.categories()
+    }
+
+    /// All known articles and their download state.
+    #[inline]
+    pub fn articles(&self) -> &FnvHashMap<ArticleUrl, DocumentDownloadState> {
+        self.queue.articles()
+    }
+
+    /// How many article urls were classified as likely, ambiguous or
+    /// rejected, see [`crate::ExtrablattBuilder::article_url_filter`].
+    #[inline]
+    pub fn url_class_stats(&self) -> &UrlClassStats {
+        self.queue.url_class_stats()
+    }
+
+    /// Insert all categories extracted from the main page that pass the
+    /// category filter.
+    pub(crate) fn insert_new_categories(&mut self) {
+        for category in self.extractor.categories(&self.main_page, &self.base_url) {
+            if self.queue.is_wanted_url(&category.url) {
+                self.queue.insert_category(category);
+            }
+        }
+    }
+
+    /// Insert the category as not requested, unless it is already known.
+    pub(crate) fn insert_category(&mut self, category: Category) {
+        self.queue.insert_category(category);
+    }
+
+    /// For each successfully downloaded category document, queue their
+    /// article urls that aren't known yet.
+    fn insert_article_urls(&mut self, doc: &Document) {
+        for url in self.extractor.article_urls(doc, Some(&self.base_url)) {
+            if self.queue.is_wanted_url(&url.url) && !self.queue.articles().contains_key(&url.url) {
+                self.queue.insert_article(url);
+            }
+        }
+    }
+
+    /// Execute a GET request and return the parsed document, when it was
+    /// received and the response metadata.
+    fn get_document(&self, url: Url) -> Result<(Document, Instant, FetchInfo), ExtrablattError> {
+        let (resp, attempts) = self.retry_policy.get_blocking(&self.client, url);
+        document_from_response(resp, attempts, self.config.http_success_only())
+    }
+
+    /// Download and store all categories and their identified articles that
+    /// haven't been requested yet, one after another.
+    pub fn download_all_remaining_categories(
+        &mut self,
+    ) -> Vec<Result<Category, (Category, ExtrablattError)>> {
+        let items = self.queue.not_requested_categories();
+        let mut results = Vec::with_capacity(items.len());
+        for cat in items {
+            match self.get_document(cat.url.clone()) {
+                Ok((doc, received, fetch_info)) => {
+                    self.insert_article_urls(&doc);
+                    let state = DocumentDownloadState::Success {
+                        doc,
+                        received,
+                        from_cache: false,
+                        user_agent: None,
+                        fetch_info: Box::new(fetch_info),
+                    };
+                    self.queue.set_category_state(cat.clone(), state);
+                    results.push(Ok(cat));
+                }
+                Err(err) => {
+                    self.queue.set_category_state(cat.clone(), DocumentDownloadState::from_error(&err));
+                    results.push(Err((cat, err)));
+                }
+            }
+        }
+        results
+    }
+
+    /// Download and store all outstanding articles, one after another, likely
+    /// articles first, and returns an iterator over their results.
+    pub fn download_articles(&mut self) -> ArticleDownloadIter<'_, TExtractor> {
+        for url in self.queue.not_requested_article_urls() {
+            let state = match self.get_document(url.clone()) {
+                Ok((doc, received, fetch_info)) => DocumentDownloadState::Success {
+                    doc,
+                    received,
+                    from_cache: false,
+                    user_agent: None,
+                    fetch_info: Box::new(fetch_info),
+                },
+                Err(err) => DocumentDownloadState::from_error(&err),
+            };
+            self.queue.set_article_state(&url, state);
+        }
+        self.iter_articles()
+    }
+
+    /// Iterator over all known articles.
+    pub fn iter_articles(&self) -> ArticleDownloadIter<'_, TExtractor> {
+        self.queue
+            .iter_articles(&self.extractor, self.language.clone(), &self.languages, &self.base_url)
+    }
+}
+
+/// Reads the response into a [`select::document::Document`].
+///
+/// Non 2xx responses are only read if `http_success_only` is `false`.
+fn document_from_response(
+    response: Result<Response, reqwest::Error>,
+    attempts: usize,
+    http_success_only: bool,
+) -> Result<(Document, Instant, FetchInfo), ExtrablattError> {
+    let response = response.map_err(|error| ExtrablattError::request_failure(error, attempts))?;
+    if http_success_only && !response.status().is_success() {
+        return Err(ExtrablattError::http_status_blocking(response, attempts));
+    }
+    let fetch_info = FetchInfo::from_blocking_response(&response);
+    let content_type = content_type(response.headers());
+    let body = response
+        .bytes()
+        .map_err(|error| ExtrablattError::HttpRequestFailure { error, attempts })?;
+    Ok((
+        trace::parse(decode(&body, content_type.as_deref()).as_ref()),
+        Instant::now(),
+        fetch_info,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::ImageRegistry;
+    use crate::test_server::serve_pages;
+    use crate::url_class::{classify_article_url, RejectRule, UrlClass};
+    use crate::article::ArticleError;
+    use crate::Article;
+
+    use super::*;
+
+    const ARTICLE: &str = r#"<html><head><title>Harbour expansion approved</title></head>
+        <body><div itemprop="articleBody"><p>The harbour will be expanded over the next
+        ten years, the city announced on Monday.</p></div></body></html>"#;
+
+    #[test]
+    fn article_get_blocking() {
+        let url = serve_pages(vec![("/news/harbour.html", ARTICLE)]);
+        let article = Article::get_blocking(url.join("news/harbour.html").unwrap()).unwrap();
+        assert_eq!(
+            article.content.title.as_deref(),
+            Some("Harbour expansion approved")
+        );
+    }
+
+    #[test]
+    fn article_get_blocking_not_found() {
+        let url = serve_pages(vec![]);
+        assert!(Article::builder(url.join("missing.html").unwrap())
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .get_blocking()
+            .is_err());
+    }
+
+    #[test]
+    fn article_get_blocking_rejects_async_client() {
+        let err = Article::builder("https://example.com/news/harbour.html")
+            .unwrap()
+            .client(reqwest::Client::new())
+            .get_blocking()
+            .unwrap_err();
+        assert!(matches!(err, ArticleError::UnsupportedBlockingOption("client")));
+    }
+
+    #[test]
+    fn category_filter() {
+        let url = serve_pages(vec![
+            (
+                "/",
+                r#"<html><body><a href="/politics">Politics</a><a href="/live">Live</a>
+                <a href="/video">Video</a></body></html>"#,
+            ),
+            (
+                "/politics",
+                r#"<html><body>
+                <a href="/politics/harbour-expansion-approved-by-the-council.html">Harbour</a>
+                <a href="/live/harbour-expansion-approved-by-the-council.html">Live</a>
+                </body></html>"#,
+            ),
+            ("/special", "<html><body></body></html>"),
+        ]);
+        let mut site = crate::Extrablatt::builder(url.clone())
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .include_categories(vec![url.join("special").unwrap()])
+            .category_filter(|url| !url.path().starts_with("/live") && !url.path().starts_with("/video"))
+            .build_blocking()
+            .unwrap();
+
+        let mut categories: Vec<_> = site.categories().keys().map(|c| c.url.path().to_string()).collect();
+        categories.sort();
+        assert!(categories.contains(&"/politics".to_string()));
+        assert!(categories.contains(&"/special".to_string()));
+        assert!(!categories.iter().any(|c| c == "/live" || c == "/video"));
+
+        site.download_all_remaining_categories();
+        let articles: Vec<_> = site.articles().keys().map(|a| a.url.path().to_string()).collect();
+        assert_eq!(articles, vec!["/politics/harbour-expansion-approved-by-the-council.html"]);
+    }
+
+    #[test]
+    fn article_url_filter() {
+        let url = serve_pages(vec![
+            ("/", r#"<html><body><a href="/politics">Politics</a></body></html>"#),
+            (
+                "/politics",
+                r#"<html><body>
+                <a href="/politics/harbour-expansion-approved-by-the-council.html">Harbour</a>
+                <a href="/live/harbour-expansion-approved-by-the-council.html">Live</a>
+                </body></html>"#,
+            ),
+        ]);
+        let mut site = crate::Extrablatt::builder(url)
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .article_url_filter(|url| {
+                if url.path().starts_with("/live/") {
+                    UrlClass::Rejected(RejectRule::Custom)
+                } else {
+                    classify_article_url(url)
+                }
+            })
+            .build_blocking()
+            .unwrap();
+
+        site.download_all_remaining_categories();
+        let articles: Vec<_> = site.articles().keys().map(|a| a.url.path().to_string()).collect();
+        assert_eq!(articles, vec!["/politics/harbour-expansion-approved-by-the-council.html"]);
+        assert_eq!(site.url_class_stats().custom, 1);
+    }
+
+    #[test]
+    fn build_blocking() {
+        let url = serve_pages(vec![("/", "<html><head><title>Home</title></head><body></body></html>")]);
+        let site = crate::Extrablatt::builder(url)
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .build_blocking()
+            .unwrap();
+        assert!(site.articles().is_empty());
+    }
+
+    #[test]
+    fn build_blocking_rejects_unsupported_options() {
+        let err = crate::Extrablatt::builder("https://example.com/")
+            .unwrap()
+            .image_registry(ImageRegistry::default())
+            .build_blocking()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ExtrablattError::UnsupportedBlockingOption { option: "image_registry" }
+        ));
+    }
+}
@@ -1,12 +1,20 @@
+#[cfg(feature = "net")]
 use crate::error::ExtrablattError;
 use crate::language::Language;
+#[cfg(feature = "net")]
 use crate::{Article, ArticleStream, DefaultExtractor, Extractor};
+#[cfg(feature = "net")]
 use futures::Stream;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
+use std::fmt;
+use std::sync::Arc;
 use url::Url;
 
 /// A category e.g. Politics or sports
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub struct Category {
     /// The address for the category website.
     pub url: Url,
@@ -51,15 +59,17 @@ impl Category {
     /// Fetch all article urls from the page this category's url points to and
     /// return a new stream of articles using the
     /// [`crate::DefaultExtractor`].
+    #[cfg(feature = "net")]
     pub async fn into_stream(
         self,
     ) -> Result<impl Stream<Item = Result<Article, ExtrablattError>>, ExtrablattError> {
-        self.into_stream_with_extractor(DefaultExtractor).await
+        self.into_stream_with_extractor(DefaultExtractor::default()).await
     }
 
     /// Fetch all article urls from the page this category's url points to and
     /// return a new stream of article using a designated
     /// [`crate::Extractor`].
+    #[cfg(feature = "net")]
     pub async fn into_stream_with_extractor<TExtractor: Extractor + Unpin>(
         self,
         extractor: TExtractor,
@@ -68,6 +78,33 @@ impl Category {
     }
 }
 
+/// A predicate that decides which category urls, and article urls found in
+/// categories, are kept.
+///
+/// See [`crate::ExtrablattBuilder::category_filter`].
+#[derive(Clone)]
+pub struct UrlFilter(Arc<dyn Fn(&Url) -> bool + Send + Sync>);
+
+impl UrlFilter {
+    pub fn new<F>(filter: F) -> Self
+    where
+        F: Fn(&Url) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(filter))
+    }
+
+    /// Whether the url is kept.
+    pub fn matches(&self, url: &Url) -> bool {
+        (self.0)(url)
+    }
+}
+
+impl fmt::Debug for UrlFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UrlFilter(..)")
+    }
+}
+
 impl Borrow<str> for Category {
     fn borrow(&self) -> &str {
         self.url.as_str()
@@ -0,0 +1,133 @@
+//! Detection of the character encoding of downloaded pages, so pages that
+//! aren't served as UTF-8 are transcoded before they are parsed.
+
+use std::borrow::Cow;
+
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use lazy_static::lazy_static;
+use regex::bytes::Regex;
+#[cfg(feature = "net")]
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+
+/// Number of bytes at the start of a body that are searched for a
+/// `<meta>` charset declaration.
+pub const META_SNIFF_LEN: usize = 4096;
+
+lazy_static! {
+    static ref RE_META_CHARSET: Regex =
+        Regex::new(r#"(?i-u)<meta[^>]+charset\s*=\s*["']?\s*([a-z0-9_:.\-]+)"#).unwrap();
+}
+
+/// The encoding named by the `charset` parameter of a `Content-Type` header
+/// value like `text/html; charset=windows-1251`.
+pub fn encoding_from_content_type(content_type: &str) -> Option<&'static Encoding> {
+    content_type
+        .split(';')
+        .skip(1)
+        .filter_map(|param| param.split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+        .and_then(|(_, value)| Encoding::for_label(value.trim().trim_matches(['"', '\'']).as_bytes()))
+}
+
+/// The encoding declared by a `<meta charset>` or
+/// `<meta http-equiv="Content-Type">` element within the first
+/// [`META_SNIFF_LEN`] bytes of the `body`.
+pub fn encoding_from_meta(body: &[u8]) -> Option<&'static Encoding> {
+    let head = &body[..body.len().min(META_SNIFF_LEN)];
+    let label = RE_META_CHARSET.captures(head)?.get(1)?;
+    let encoding = Encoding::for_label(label.as_bytes())?;
+    // a document that declares a UTF-16 charset in ASCII can't be UTF-16
+    if encoding == encoding_rs::UTF_16LE || encoding == encoding_rs::UTF_16BE {
+        Some(UTF_8)
+    } else {
+        Some(encoding)
+    }
+}
+
+/// The encoding of the `body`, taken from the `Content-Type` header value,
+/// a `<meta>` declaration or, if both are missing, from the byte order mark
+/// and whether the body is valid UTF-8. Defaults to windows-1252 like
+/// browsers do.
+pub fn detect_encoding(body: &[u8], content_type: Option<&str>) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(body) {
+        return encoding;
+    }
+    content_type
+        .and_then(encoding_from_content_type)
+        .or_else(|| encoding_from_meta(body))
+        .unwrap_or_else(|| {
+            if std::str::from_utf8(body).is_ok() {
+                UTF_8
+            } else {
+                WINDOWS_1252
+            }
+        })
+}
+
+/// The `Content-Type` header value of a response.
+#[cfg(feature = "net")]
+pub(crate) fn content_type(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Transcode the `body` to UTF-8, see [`detect_encoding`].
+///
+/// Malformed sequences are replaced with U+FFFD.
+pub fn decode<'a>(body: &'a [u8], content_type: Option<&str>) -> Cow<'a, str> {
+    let (html, _, _) = detect_encoding(body, content_type).decode(body);
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Article;
+
+    use super::*;
+
+    const WINDOWS_1251: &[u8] = include_bytes!("../tests/fixtures/charset/windows-1251.html");
+    const ISO_8859_1: &[u8] = include_bytes!("../tests/fixtures/charset/iso-8859-1.html");
+
+    #[test]
+    fn content_type_charset() {
+        let encoding = |value| encoding_from_content_type(value).map(Encoding::name);
+        assert_eq!(encoding("text/html; charset=windows-1251"), Some("windows-1251"));
+        assert_eq!(encoding("text/html;Charset=\"GBK\""), Some("GBK"));
+        assert_eq!(encoding("text/html"), None);
+    }
+
+    #[test]
+    fn meta_charset() {
+        assert_eq!(encoding_from_meta(WINDOWS_1251), Some(encoding_rs::WINDOWS_1251));
+        // `iso-8859-1` is an alias of windows-1252
+        assert_eq!(encoding_from_meta(ISO_8859_1), Some(WINDOWS_1252));
+        assert_eq!(encoding_from_meta(br#"<meta charset="utf-16">"#), Some(UTF_8));
+    }
+
+    #[test]
+    fn header_overrides_meta() {
+        let body = "<meta charset=\"windows-1251\"><p>Grüße</p>".as_bytes();
+        assert_eq!(detect_encoding(body, Some("text/html; charset=utf-8")), UTF_8);
+        assert_eq!(detect_encoding(body, None), encoding_rs::WINDOWS_1251);
+        assert_eq!(detect_encoding(b"<p>Gr\xfc\xdfe</p>", None), WINDOWS_1252);
+    }
+
+    #[test]
+    fn transcoded_articles() {
+        let article = Article::from_bytes("https://example.ru/news/port.html", WINDOWS_1251).unwrap();
+        assert_eq!(
+            article.content.title.as_deref(),
+            Some("Расширение порта одобрено городским советом")
+        );
+        assert!(article.content.text.unwrap().contains("Городской совет во вторник одобрил"));
+
+        let article = Article::from_bytes("https://example.de/news/hafen.html", ISO_8859_1).unwrap();
+        assert_eq!(
+            article.content.title.as_deref(),
+            Some("Hafenerweiterung für Großprojekte genehmigt")
+        );
+        assert!(article.content.text.unwrap().contains("Die Bürger der Stadt"));
+    }
+}
@@ -5,6 +5,10 @@ use select::node::{Descendants, Node};
 use select::predicate::{Name, Predicate};
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::fmt;
+use std::iter::successors;
+
+use crate::text::ArticleBodyAttrs;
 
 lazy_static! {
 
@@ -26,40 +30,40 @@ pub trait DocumentCleaner {
             txt: &mut String,
             cleaner: &T,
         ) -> bool {
-            if cleaner.is_bad_node_name(node) {
+            if cleaner.is_removable(node) {
                 return false;
             }
 
             let mut txt_added = false;
-            if cleaner.is_good_node(node) {
-                let mut needs_ws = false;
-                for child in node.children() {
-                    if needs_ws {
-                        txt.push(' ');
-                        needs_ws = false;
+            let mut needs_ws = false;
+            for child in node.children() {
+                if needs_ws {
+                    txt.push(' ');
+                    needs_ws = false;
+                }
+                if let Some(txt_fragment) = child.as_text().map(str::trim) {
+                    let mut fragment = txt_fragment.to_string();
+                    cleaner.transform_text(node, &mut fragment);
+                    if !fragment.is_empty() {
+                        txt.push_str(&fragment);
+                        txt_added = true
                     }
-                    if let Some(txt_fragment) = child.as_text().map(str::trim) {
-                        if !txt_fragment.is_empty() {
-                            txt.push_str(txt_fragment);
-                            txt_added = true
-                        }
-                    } else if Name("a").matches(&child) {
-                        // escape the content of a `<a>...</a>` tag that is embedded between
-                        // text nodes
-                        let mut a = String::new();
-                        if recur_text(child, &mut a, cleaner) && txt_added {
-                            txt.push(' ');
-                            txt.push_str(&a);
-                            needs_ws = true;
-                        }
-                    } else {
-                        recur_text(child, txt, cleaner);
+                } else if Name("a").matches(&child) {
+                    // escape the content of a `<a>...</a>` tag that is embedded between
+                    // text nodes
+                    let mut a = String::new();
+                    if recur_text(child, &mut a, cleaner) && txt_added {
+                        txt.push(' ');
+                        txt.push_str(&a);
+                        needs_ws = true;
                     }
-                }
-                if (txt_added && is_para(node)) || needs_ws {
-                    txt.push('\n');
+                } else {
+                    recur_text(child, txt, cleaner);
                 }
             }
+            if (txt_added && is_para(node)) || needs_ws {
+                txt.push('\n');
+            }
             txt_added
         }
 
@@ -78,31 +82,71 @@ pub trait DocumentCleaner {
         is_bad_node(node)
     }
 
+    /// Whether the node and all its descendants should be dropped.
+    ///
+    /// By default these are nodes with a bad name and nodes that aren't good.
+    fn is_removable(&self, node: Node) -> bool {
+        self.is_bad_node_name(node) || !self.is_good_node(node)
+    }
+
+    /// Rewrite a trimmed text fragment of the `node` before it becomes part of
+    /// the text, clearing it drops the fragment.
+    fn transform_text(&self, _node: Node, _text: &mut String) {}
+
     /// Create an iterator that yields every node that this cleaner considers
     /// good
     fn iter_clean_nodes<'a>(&'a self, node: Node<'a>) -> CleanNodeIter<'a, Self>
     where
         Self: Sized,
     {
-        CleanNodeIter {
-            cleaner: self,
-            inner: node.descendants(),
-        }
+        CleanNodeIter::new(self, node)
+    }
+}
+
+impl<T: DocumentCleaner + ?Sized> DocumentCleaner for &T {
+    fn clean_node_text(&self, node: Node) -> String {
+        (**self).clean_node_text(node)
+    }
+
+    fn is_good_node(&self, node: Node) -> bool {
+        (**self).is_good_node(node)
+    }
+
+    fn is_bad_node_name(&self, node: Node) -> bool {
+        (**self).is_bad_node_name(node)
+    }
+
+    fn is_removable(&self, node: Node) -> bool {
+        (**self).is_removable(node)
+    }
+
+    fn transform_text(&self, node: Node, text: &mut String) {
+        (**self).transform_text(node, text)
     }
 }
 
 /// An Iterator that only yields those nodes that the `cleaner` considers good.
-pub struct CleanNodeIter<'a, T: DocumentCleaner> {
+pub struct CleanNodeIter<'a, T: DocumentCleaner + ?Sized> {
     cleaner: &'a T,
     inner: Descendants<'a>,
 }
 
-impl<'a, T: DocumentCleaner> Iterator for CleanNodeIter<'a, T> {
+impl<'a, T: DocumentCleaner + ?Sized> CleanNodeIter<'a, T> {
+    /// Iterate over the good descendants of the `node`.
+    pub fn new(cleaner: &'a T, node: Node<'a>) -> Self {
+        Self {
+            cleaner,
+            inner: node.descendants(),
+        }
+    }
+}
+
+impl<'a, T: DocumentCleaner + ?Sized> Iterator for CleanNodeIter<'a, T> {
     type Item = Node<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let node = self.inner.next()?;
-        if self.cleaner.is_bad_node_name(node) || !self.cleaner.is_good_node(node) {
+        if self.cleaner.is_removable(node) {
             // skip every node under this bad node
             for ignore in node.descendants() {
                 let next = self.inner.next()?;
@@ -117,10 +161,64 @@ impl<'a, T: DocumentCleaner> Iterator for CleanNodeIter<'a, T> {
 
 /// A standard implementation of a cleaner that only extracts good textual
 /// content form the nodes descendants
+#[derive(Debug, Clone, Copy, Default)]
 pub struct DefaultDocumentCleaner;
 
 impl DocumentCleaner for DefaultDocumentCleaner {}
 
+/// A [`DocumentCleaner`] that additionally drops link lists, like related
+/// articles and teasers, whose text is mostly made up of links.
+#[derive(Debug, Clone, Copy)]
+pub struct AggressiveCleaner {
+    /// The share of the text within links above which a node is dropped.
+    pub max_link_density: f64,
+    /// Nodes with at least this many chars are kept regardless of their
+    /// links.
+    pub min_text_len: usize,
+}
+
+impl AggressiveCleaner {
+    pub const DEFAULT_MAX_LINK_DENSITY: f64 = 0.5;
+
+    pub const DEFAULT_MIN_TEXT_LEN: usize = 80;
+
+    /// The share of the `node`'s text that is part of a link.
+    pub fn link_density(node: Node) -> f64 {
+        let text_len = node.text().trim().chars().count();
+        if text_len == 0 {
+            return 0.0;
+        }
+        let link_len: usize = node
+            .find(Name("a"))
+            .map(|link| link.text().trim().chars().count())
+            .sum();
+        link_len as f64 / text_len as f64
+    }
+}
+
+impl Default for AggressiveCleaner {
+    fn default() -> Self {
+        Self {
+            max_link_density: Self::DEFAULT_MAX_LINK_DENSITY,
+            min_text_len: Self::DEFAULT_MIN_TEXT_LEN,
+        }
+    }
+}
+
+impl DocumentCleaner for AggressiveCleaner {
+    fn is_removable(&self, node: Node) -> bool {
+        if self.is_bad_node_name(node) || !self.is_good_node(node) {
+            return true;
+        }
+        // links themselves are judged by their parent
+        if node.name().is_none() || Name("a").matches(&node) {
+            return false;
+        }
+        node.text().trim().chars().count() < self.min_text_len
+            && Self::link_density(node) > self.max_link_density
+    }
+}
+
 /// A [`DocumentCleaner`] that uses names to detect tags to ignore by default
 /// and a predicate to decide whether a tag should be ignore
 #[derive(Debug)]
@@ -211,6 +309,90 @@ fn is_para(node: Node) -> bool {
     }
 }
 
+/// User hooks that reject additional nodes and texts while extracting the
+/// article's text, like site specific noise.
+///
+/// The built-in filtering always applies, the hooks can only add rejections.
+/// A denied node is skipped together with its subtree when selecting the
+/// article node and when collecting its paragraphs and images. A denied text
+/// drops the candidate node or paragraph it belongs to, and images whose alt
+/// text it matches.
+///
+/// # Example
+///
+/// ```
+/// # use extrablatt_v2::clean::ExtractionFilters;
+/// # use extrablatt_v2::DefaultExtractor;
+/// let filters = ExtractionFilters::default()
+///     .deny_node(|node| node.attr("data-widget").is_some())
+///     .deny_text(|text| text.starts_with("Advertisement"));
+/// let extractor = DefaultExtractor::default().with_filters(filters);
+/// ```
+#[derive(Default)]
+pub struct ExtractionFilters {
+    /// Rejects a node and its subtree.
+    pub deny_node: Option<NodeFilter>,
+    /// Rejects the text of a candidate node, a paragraph or the alt text of
+    /// an image.
+    pub deny_text: Option<TextFilter>,
+    /// The attributes of the article node, [`ArticleBodyAttrs::default`] if
+    /// `None`.
+    pub article_body: Option<ArticleBodyAttrs>,
+}
+
+/// A hook of [`ExtractionFilters`] that rejects nodes.
+pub type NodeFilter = Box<dyn Fn(&Node) -> bool + Send + Sync>;
+
+/// A hook of [`ExtractionFilters`] that rejects texts.
+pub type TextFilter = Box<dyn Fn(&str) -> bool + Send + Sync>;
+
+impl ExtractionFilters {
+    /// Reject the nodes the `deny` hook matches.
+    pub fn deny_node<F: Fn(&Node) -> bool + Send + Sync + 'static>(mut self, deny: F) -> Self {
+        self.deny_node = Some(Box::new(deny));
+        self
+    }
+
+    /// Reject the texts the `deny` hook matches.
+    pub fn deny_text<F: Fn(&str) -> bool + Send + Sync + 'static>(mut self, deny: F) -> Self {
+        self.deny_text = Some(Box::new(deny));
+        self
+    }
+
+    /// Take the article node by the `attrs` instead of the
+    /// [`crate::text::ARTICLE_BODY_ATTR`].
+    pub fn article_body_attrs(mut self, attrs: ArticleBodyAttrs) -> Self {
+        self.article_body = Some(attrs);
+        self
+    }
+
+    /// Whether the `deny_node` hook rejects the node.
+    pub fn denies_node(&self, node: &Node) -> bool {
+        self.deny_node.as_ref().map(|deny| deny(node)).unwrap_or_default()
+    }
+
+    /// Whether the `deny_node` hook rejects the node or one of its
+    /// ancestors, so the node is within a rejected subtree.
+    pub fn denies_node_or_ancestor(&self, node: &Node) -> bool {
+        self.deny_node.is_some() && successors(Some(*node), Node::parent).any(|node| self.denies_node(&node))
+    }
+
+    /// Whether the `deny_text` hook rejects the text.
+    pub fn denies_text(&self, text: &str) -> bool {
+        self.deny_text.as_ref().map(|deny| deny(text)).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for ExtractionFilters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtractionFilters")
+            .field("deny_node", &self.deny_node.is_some())
+            .field("deny_text", &self.deny_text.is_some())
+            .field("article_body", &self.article_body)
+            .finish()
+    }
+}
+
 /// Ignore nodes that usually do not contain content for the article based on
 /// attributes.
 pub fn has_bad_attr(node: Node) -> bool {
@@ -0,0 +1,265 @@
+//! Requirements an extracted article has to fulfill, and the report of the
+//! criteria it failed.
+
+use std::fmt;
+
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::article::ArticleContent;
+use crate::text::ArticleTextNodeExtractor;
+
+/// A field an article is required to have by its [`Completeness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum Requirement {
+    /// A title that isn't blank.
+    Title,
+    /// The publishing date.
+    Date,
+    /// A top image or an image in the text.
+    Image,
+}
+
+/// A countable property of an article that is restricted to a range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum Measure {
+    /// Number of words of the text.
+    WordCount,
+    /// Number of bytes of the text.
+    TextLen,
+    /// Number of bytes of the title.
+    TitleLen,
+    /// Number of keywords.
+    Keywords,
+    /// Number of authors.
+    Authors,
+}
+
+impl Measure {
+    fn name(&self) -> &'static str {
+        match self {
+            Measure::WordCount => "word_count",
+            Measure::TextLen => "text_len",
+            Measure::TitleLen => "title_len",
+            Measure::Keywords => "keywords",
+            Measure::Authors => "authors",
+        }
+    }
+}
+
+/// A criterion an article failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum Criterion {
+    /// A required field is missing.
+    Missing(Requirement),
+    /// The `value` of the `measure` is below the `min`.
+    TooFew {
+        measure: Measure,
+        value: usize,
+        min: usize,
+    },
+    /// The `value` of the `measure` exceeds the `max`.
+    TooMany {
+        measure: Measure,
+        value: usize,
+        max: usize,
+    },
+    /// The page only holds a teaser of a paywall, see
+    /// [`crate::ConfigBuilder::reject_paywalled`].
+    Paywalled,
+}
+
+impl fmt::Display for Criterion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Criterion::Missing(requirement) => write!(f, "missing: {:?}", requirement),
+            Criterion::TooFew { measure, value, min } => write!(f, "{}: {} < {}", measure.name(), value, min),
+            Criterion::TooMany { measure, value, max } => write!(f, "{}: {} > {}", measure.name(), value, max),
+            Criterion::Paywalled => f.write_str("paywalled"),
+        }
+    }
+}
+
+/// The criteria an incomplete article failed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Incompleteness {
+    pub failed: Vec<Criterion>,
+}
+
+impl Incompleteness {
+    /// The required fields the article is missing.
+    pub fn missing(&self) -> impl Iterator<Item = Requirement> + '_ {
+        self.failed.iter().filter_map(|criterion| match criterion {
+            Criterion::Missing(requirement) => Some(*requirement),
+            _ => None,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+impl fmt::Display for Incompleteness {
+    /// Formats the failed criteria like `missing: [Date], word_count: 43 < 150`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let missing: Vec<_> = self.missing().collect();
+        let mut parts = Vec::new();
+        if !missing.is_empty() {
+            parts.push(format!("missing: {:?}", missing));
+        }
+        parts.extend(
+            self.failed
+                .iter()
+                .filter(|criterion| !matches!(criterion, Criterion::Missing(_)))
+                .map(Criterion::to_string),
+        );
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// The minimum an article needs to be complete.
+///
+/// Incomplete articles fail with
+/// [`crate::ExtrablattError::IncompleteArticle`], unless the policy only
+/// warns, then they are returned with the failed criteria in
+/// [`ArticleContent::incompleteness`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Completeness {
+    /// Min number of words of the text, `0` allows articles without text.
+    ///
+    /// Applies in addition to [`crate::ConfigBuilder::min_word_count`], so
+    /// the larger of both wins. An article that fails both is reported once,
+    /// with this min.
+    pub min_word_count: usize,
+    /// Whether the article needs a [`Requirement::Title`].
+    pub require_title: bool,
+    /// Whether the article needs a [`Requirement::Date`].
+    pub require_date: bool,
+    /// Whether the article needs a [`Requirement::Image`].
+    pub require_image: bool,
+    /// Whether incomplete articles are returned instead of rejected.
+    pub warn_only: bool,
+}
+
+impl Completeness {
+    /// A policy that requires nothing, so it never rejects an article.
+    ///
+    /// This is the default.
+    pub fn lenient() -> Self {
+        Self {
+            min_word_count: 0,
+            require_title: false,
+            require_date: false,
+            require_image: false,
+            warn_only: false,
+        }
+    }
+
+    /// Set the [`Completeness::min_word_count`].
+    pub fn min_word_count(mut self, min_word_count: usize) -> Self {
+        self.min_word_count = min_word_count;
+        self
+    }
+
+    /// Set whether the article needs a [`Requirement::Title`].
+    pub fn require_title(mut self, require_title: bool) -> Self {
+        self.require_title = require_title;
+        self
+    }
+
+    /// Set whether the article needs a [`Requirement::Date`].
+    pub fn require_date(mut self, require_date: bool) -> Self {
+        self.require_date = require_date;
+        self
+    }
+
+    /// Set whether the article needs a [`Requirement::Image`].
+    pub fn require_image(mut self, require_image: bool) -> Self {
+        self.require_image = require_image;
+        self
+    }
+
+    /// Return incomplete articles with the failed criteria instead of
+    /// rejecting them.
+    pub fn warn_only(mut self, warn_only: bool) -> Self {
+        self.warn_only = warn_only;
+        self
+    }
+
+    /// The criteria of this policy the `article` fails.
+    pub fn check(&self, article: &ArticleContent<'_>) -> Incompleteness {
+        let mut failed = Vec::new();
+        if self.require_title && article.title.as_deref().map(str::trim).unwrap_or_default().is_empty() {
+            failed.push(Criterion::Missing(Requirement::Title));
+        }
+        if self.require_date && article.publishing_date.is_none() {
+            failed.push(Criterion::Missing(Requirement::Date));
+        }
+        if self.require_image && article.top_image.is_none() && article.images.is_empty() {
+            failed.push(Criterion::Missing(Requirement::Image));
+        }
+        if self.min_word_count > 0 {
+            let words = word_count(article);
+            if words < self.min_word_count {
+                failed.push(Criterion::TooFew {
+                    measure: Measure::WordCount,
+                    value: words,
+                    min: self.min_word_count,
+                });
+            }
+        }
+        Incompleteness { failed }
+    }
+}
+
+impl Default for Completeness {
+    fn default() -> Self {
+        Self::lenient()
+    }
+}
+
+/// Number of words of the article's text.
+pub(crate) fn word_count(article: &ArticleContent<'_>) -> usize {
+    article
+        .text
+        .as_deref()
+        .map(|text| ArticleTextNodeExtractor::words(text).count())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    #[test]
+    fn report_failed_criteria() {
+        let content = ArticleContent::builder()
+            .title(Cow::Borrowed("Harbour expansion approved"))
+            .text(Cow::Borrowed("The city council approved the expansion of the harbour."))
+            .build();
+        let policy = Completeness::lenient()
+            .min_word_count(150)
+            .require_title(true)
+            .require_date(true)
+            .require_image(true);
+        let incompleteness = policy.check(&content);
+        assert_eq!(
+            incompleteness.missing().collect::<Vec<_>>(),
+            vec![Requirement::Date, Requirement::Image]
+        );
+        assert_eq!(
+            incompleteness.to_string(),
+            "missing: [Date, Image], word_count: 9 < 150"
+        );
+        assert!(Completeness::lenient().check(&content).is_empty());
+        assert!(Completeness::lenient().min_word_count(9).check(&content).is_empty());
+    }
+}
@@ -0,0 +1,180 @@
+//! Cookies sent with the requests, like the consent cookie some publishers
+//! require before they serve the full article instead of an interstitial.
+
+use std::fmt;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::cookie::{CookieStore, Jar};
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::header::HeaderValue;
+#[cfg(not(target_arch = "wasm32"))]
+use url::Url;
+
+/// Why the cookies can't be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CookieError {
+    /// A host of the cookies isn't valid.
+    InvalidHost(url::ParseError),
+    /// The name or value of the cookie with this name has a character that
+    /// isn't allowed, like the `;` that would start an attribute.
+    InvalidCookie(String),
+}
+
+impl fmt::Display for CookieError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CookieError::InvalidHost(err) => write!(f, "Invalid cookie host: {}", err),
+            CookieError::InvalidCookie(name) => write!(f, "Invalid cookie: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for CookieError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CookieError::InvalidHost(err) => Some(err),
+            CookieError::InvalidCookie(_) => None,
+        }
+    }
+}
+
+/// Whether the cookies of responses are sent with later requests, and the
+/// cookies that are sent to specific hosts from the first request on.
+#[derive(Debug, Clone, Default)]
+pub struct Cookies {
+    /// Whether the cookies set by responses are kept.
+    pub store: bool,
+    /// Names and values of the cookies, by host.
+    hosts: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl Cookies {
+    /// Send the `cookies` as `name=value` pairs to the `host`, like
+    /// `www.example.com`, but not to its subdomains.
+    pub fn insert<H, N, V>(&mut self, host: H, cookies: Vec<(N, V)>)
+    where
+        H: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let cookies = cookies
+            .into_iter()
+            .map(|(name, value)| (name.into(), value.into()))
+            .collect();
+        self.hosts.push((host.into().to_lowercase(), cookies));
+    }
+
+    /// Whether no cookies are stored or sent.
+    pub fn is_empty(&self) -> bool {
+        !self.store && self.hosts.is_empty()
+    }
+
+    /// The cookie jar of a client, seeded with the cookies of each host.
+    ///
+    /// `None` if no cookies are stored or sent. Cookies set by responses are
+    /// only kept if [`Cookies::store`] is set. Fails if a host isn't valid or
+    /// a name isn't a token or a value has characters other than the ones
+    /// RFC 6265 allows, like a `;` or whitespace.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn jar(&self) -> Result<Option<Arc<CookieJar>>, CookieError> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let jar = Jar::default();
+        for (host, cookies) in &self.hosts {
+            let url = Url::parse(&format!("http://{}/", host)).map_err(CookieError::InvalidHost)?;
+            for (name, value) in cookies {
+                if !is_token(name) || !value.chars().all(is_cookie_octet) {
+                    return Err(CookieError::InvalidCookie(name.clone()));
+                }
+                jar.add_cookie_str(&format!("{}={}; Path=/", name, value), &url);
+            }
+        }
+        Ok(Some(Arc::new(CookieJar { jar, store: self.store })))
+    }
+}
+
+/// The cookie store of a client, that only keeps the cookies of responses
+/// if storing them is enabled.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct CookieJar {
+    jar: Jar,
+    store: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        if self.store {
+            self.jar.set_cookies(cookie_headers, url);
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.jar.cookies(url)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Whether the cookie name is a token of RFC 7230.
+fn is_token(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(c))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+/// Whether the character may be part of a cookie value, see RFC 6265.
+fn is_cookie_octet(c: char) -> bool {
+    c.is_ascii_graphic() && !matches!(c, '"' | ',' | ';' | '\\')
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use reqwest::cookie::CookieStore;
+
+    use super::*;
+
+    #[test]
+    fn seeded_cookies() {
+        let mut cookies = Cookies::default();
+        assert!(cookies.jar().unwrap().is_none());
+        cookies.insert("News.example.com", vec![("euconsent", "1"), ("gdpr", "1")]);
+        let jar = cookies.jar().unwrap().unwrap();
+
+        let header = |url: &str| {
+            jar.cookies(&Url::parse(url).unwrap())
+                .map(|value| value.to_str().unwrap().to_string())
+        };
+        let sent = header("https://news.example.com/2024/article.html").unwrap();
+        assert!(sent.contains("euconsent=1") && sent.contains("gdpr=1"));
+        assert_eq!(header("https://example.com/"), None);
+        assert_eq!(header("https://video.news.example.com/"), None);
+
+        // not stored without the cookie store
+        let set_cookie = HeaderValue::from_static("session=abc; Path=/");
+        let url = Url::parse("https://news.example.com/").unwrap();
+        jar.set_cookies(&mut std::iter::once(&set_cookie), &url);
+        assert!(!header("https://news.example.com/").unwrap().contains("session"));
+        cookies.store = true;
+        let jar = cookies.jar().unwrap().unwrap();
+        jar.set_cookies(&mut std::iter::once(&set_cookie), &url);
+        assert!(jar.cookies(&url).unwrap().to_str().unwrap().contains("session=abc"));
+
+        cookies.insert("news example.com", vec![("a", "b")]);
+        assert!(matches!(cookies.jar(), Err(CookieError::InvalidHost(_))));
+    }
+
+    #[test]
+    fn invalid_cookies() {
+        for (name, value) in [("gdpr", "1; Domain=example.org"), ("gdpr", "a b"), ("gd pr", "1"), ("", "1")] {
+            let mut cookies = Cookies::default();
+            cookies.insert("www.example.com", vec![(name, value)]);
+            assert_eq!(cookies.jar().unwrap_err(), CookieError::InvalidCookie(name.to_string()));
+        }
+    }
+}
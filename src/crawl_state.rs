@@ -0,0 +1,197 @@
+//! Snapshots of the progress of an [`crate::Extrablatt`], so a crawl that
+//! dies can be resumed without downloading the same pages again.
+
+use std::collections::BTreeMap;
+use std::hash::Hasher;
+use std::sync::Arc;
+use std::time::Duration;
+
+use fnv::{FnvHashSet, FnvHasher};
+use reqwest::Url;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use wasm_timer::{SystemTime, UNIX_EPOCH};
+
+use crate::article::ArticleUrl;
+use crate::Category;
+
+/// The progress of a crawl, taken with [`crate::Extrablatt::save_state`] and
+/// restored with [`crate::ExtrablattBuilder::resume_from`].
+///
+/// Taking a snapshot doesn't copy the visited urls, the set is shared until
+/// the crawl visits the next page.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct CrawlState {
+    /// The articles that haven't been downloaded yet, including failed
+    /// downloads.
+    pub frontier: Vec<ArticleUrl>,
+    /// The categories that haven't been downloaded yet.
+    pub categories: Vec<Category>,
+    /// The [`url_hash`]es of the downloaded articles and categories.
+    #[cfg_attr(feature = "serde0", serde(with = "shared_set"))]
+    visited: Arc<FnvHashSet<u64>>,
+    /// The earliest time, since the unix epoch, the next request to each
+    /// host may start, see [`crate::ExtrablattBuilder::request_delay`].
+    pub next_requests: BTreeMap<String, Duration>,
+}
+
+impl CrawlState {
+    pub(crate) fn new(
+        frontier: Vec<ArticleUrl>,
+        categories: Vec<Category>,
+        visited: Arc<FnvHashSet<u64>>,
+        pending_delays: Vec<(String, Duration)>,
+    ) -> Self {
+        let now = since_epoch();
+        Self {
+            frontier,
+            categories,
+            visited,
+            next_requests: pending_delays
+                .into_iter()
+                .map(|(host, delay)| (host, now + delay))
+                .collect(),
+        }
+    }
+
+    /// Whether the page at the `url` was downloaded.
+    pub fn is_visited(&self, url: &Url) -> bool {
+        self.visited.contains(&url_hash(url))
+    }
+
+    /// Number of downloaded articles and categories.
+    pub fn visited_count(&self) -> usize {
+        self.visited.len()
+    }
+
+    pub(crate) fn visited(&self) -> &Arc<FnvHashSet<u64>> {
+        &self.visited
+    }
+
+    /// The time left until the next request to each host may start, for
+    /// hosts whose delay hasn't elapsed yet.
+    pub fn pending_delays(&self) -> impl Iterator<Item = (&str, Duration)> + '_ {
+        let now = since_epoch();
+        self.next_requests
+            .iter()
+            .filter_map(move |(host, next)| Some((host.as_str(), next.checked_sub(now)?)))
+            .filter(|(_, delay)| !delay.is_zero())
+    }
+}
+
+/// The hash the visited `url` is remembered by.
+pub fn url_hash(url: &Url) -> u64 {
+    let mut hasher = FnvHasher::default();
+    hasher.write(url.as_str().as_bytes());
+    hasher.finish()
+}
+
+fn since_epoch() -> Duration {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
+}
+
+/// Serializes the visited set as a sequence.
+#[cfg(feature = "serde0")]
+mod shared_set {
+    use std::sync::Arc;
+
+    use fnv::FnvHashSet;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(set: &Arc<FnvHashSet<u64>>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(set.iter())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<FnvHashSet<u64>>, D::Error> {
+        Vec::<u64>::deserialize(deserializer).map(|hashes| Arc::new(hashes.into_iter().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use futures::StreamExt;
+
+    use crate::extrablatt::Config;
+    use crate::test_server::{self, html};
+    use crate::{Extrablatt, RetryPolicy};
+
+    use super::*;
+
+    /// Serves a category at `/news` with six article links and counts the
+    /// requests per path.
+    fn serve() -> (Url, Arc<Mutex<HashMap<String, usize>>>) {
+        let requests = Arc::new(Mutex::new(HashMap::new()));
+        let counter = requests.clone();
+        let url = test_server::serve(move |request| {
+            let path = request.path.as_str();
+            *counter.lock().unwrap().entry(path.to_string()).or_insert(0) += 1;
+            if path == "/news" {
+                html(
+                    (0..6)
+                        .map(|i| format!("<a href=\"/news/harbour-expansion-approved-{}.html\">Harbour</a>", i))
+                        .collect::<String>(),
+                )
+            } else {
+                html(format!(
+                    "<html><head><title>{}</title></head><body><div itemprop=\"articleBody\">\
+                     <p>The harbour will be expanded over the next ten years.</p></div></body></html>",
+                    path
+                ))
+            }
+        });
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn resume_crawl_without_duplicate_fetches() {
+        let (url, requests) = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("news").unwrap()])
+            .config(Config::builder().concurrency(1).build())
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        let first: Vec<_> = paper.article_stream().take(2).collect().await;
+        assert_eq!(first.len(), 2);
+
+        let state = paper.save_state();
+        drop(paper);
+        assert_eq!(state.frontier.len(), 4);
+        assert!(state.categories.is_empty());
+        assert_eq!(state.visited_count(), 3);
+        assert!(state.is_visited(&url.join("news").unwrap()));
+        #[cfg(feature = "serde0")]
+        let state: CrawlState = serde_json::from_str(&serde_json::to_string(&state).unwrap()).unwrap();
+
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .include_categories(vec![url.join("news").unwrap()])
+            .retry_policy(RetryPolicy::none())
+            .resume_from(state)
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        let rest: Vec<_> = paper.article_stream().collect().await;
+        assert_eq!(rest.iter().filter(|res| res.is_ok()).count(), 4);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.get("/news"), Some(&1));
+        assert_eq!(
+            requests.iter().filter(|(path, _)| path.starts_with("/news/")).count(),
+            6
+        );
+        assert!(requests
+            .iter()
+            .filter(|(path, _)| path.starts_with("/news/"))
+            .all(|(_, count)| *count == 1));
+    }
+}
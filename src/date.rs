@@ -1,9 +1,14 @@
+use std::cell::OnceCell;
 use std::collections::HashMap;
 
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, Utc};
 
 use regex::Regex;
 use select::document::Document;
+use select::node::Node;
 
 use select::predicate::{Attr, Name, Predicate, Text};
 #[cfg(feature = "serde0")]
@@ -11,7 +16,51 @@ use serde::{Deserialize, Serialize};
 
 use lazy_static::lazy_static;
 
+pub use crate::date_locale::{DateLabel, DateLabelKind, DateLabels, DateLocale, DateOrder};
+use crate::date_locale::{parse_month_name, parse_numeric, parse_relative, time_of_day};
 use crate::extract::NodeValueQuery;
+use crate::clean::ExtractionFilters;
+use crate::extract_node::article_node_adaptive_with_filters;
+use crate::Language;
+use crate::extract_json_ld::{has_type, json_ld};
+
+/// Number of text nodes at the top of the article that are searched for
+/// labeled dates.
+pub const MAX_LABELED_TEXT_NODES: usize = 200;
+
+/// Max number of chars of a text with a labeled date, longer texts are
+/// sentences of the article rather than bylines.
+pub const MAX_LABELED_TEXT_LEN: usize = 120;
+
+/// The earliest year of a date in the path of an article's url, see
+/// [`DateExtractor::extract_from_url_path_at`].
+pub const MIN_URL_YEAR: i32 = 1990;
+
+/// English month names, the abbreviations of a date in a url path are their
+/// prefixes like `mar` or `sept`.
+const URL_MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// JSON-LD types whose `dateModified` is the article's.
+const ARTICLE_TYPES: &[&str] = &[
+    "Article",
+    "NewsArticle",
+    "BlogPosting",
+    "ReportageNewsArticle",
+    "AnalysisNewsArticle",
+];
 
 lazy_static! {
 
@@ -33,7 +82,11 @@ lazy_static! {
 
     /// Common nodes that hold the article's modification date.
     pub(crate) static ref  MODIFIED_DATE_NODES: Vec<NodeValueQuery<'static>> = {
-            let mut nodes = Vec::with_capacity(8);
+            let mut nodes = Vec::with_capacity(12);
+            nodes.push(NodeValueQuery::new( Name("meta"), Attr("property",  "article:modified_time"),
+             "content"));
+            nodes.push(NodeValueQuery::new( Name("meta"), Attr("property",  "og:updated_time"),
+             "content"));
             nodes.push(NodeValueQuery::new( Name("meta"), Attr("property",  "article:modified"),
              "content"));
             nodes.push(NodeValueQuery::new( Name("meta"), Attr("property",  "modified"),
@@ -46,6 +99,10 @@ lazy_static! {
              "content"));
             nodes.push(NodeValueQuery::new( Name("meta"), Attr("itemprop",  "dateModified"),
              "datetime"));
+            nodes.push(NodeValueQuery::new( Name("meta"), Attr("itemprop",  "dateModified"),
+             "content"));
+            nodes.push(NodeValueQuery::new( Name("time"), Attr("itemprop",  "dateModified"),
+             "datetime"));
             nodes.push(NodeValueQuery::new( Name("meta"), Attr("name",  "dateModified"),
              "content"));
             nodes.push(NodeValueQuery::new( Name("meta"), Attr("name",  "last_updated_date"),
@@ -91,6 +148,7 @@ lazy_static! {
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde0", serde(rename_all = "snake_case"))]
 pub enum Date {
     /// The ISO 8601 date, a pair of year, month and day of the year.
     Date(NaiveDate),
@@ -100,6 +158,7 @@ pub enum Date {
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde0", serde(rename_all = "snake_case"))]
 pub enum Update {
     /// The ISO 8601 date, a pair of year, month and day of the year.
     Date(NaiveDate),
@@ -115,7 +174,133 @@ pub struct ArticleDate {
     /// When the article was first published.
     pub published: Date,
     /// Last time the article was updated.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
     pub last_updated: Option<Update>,
+    /// Whether the published date was resolved from a relative expression
+    /// like `2 hours ago`, and is only as exact as the expression.
+    #[cfg_attr(
+        feature = "serde0",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
+    pub relative: bool,
+    /// The UTC offset the published date time was declared with, like the
+    /// `+03:00` of `2024-03-02T10:15:00+03:00`. The [`Date::DateTime`] is
+    /// the local time at this offset.
+    #[cfg_attr(
+        feature = "serde0",
+        serde(default, skip_serializing_if = "Option::is_none", with = "offset_seconds")
+    )]
+    pub offset: Option<FixedOffset>,
+}
+
+impl ArticleDate {
+    /// The published date time at its [`ArticleDate::offset`], if both are
+    /// known.
+    pub fn published_with_offset(&self) -> Option<DateTime<FixedOffset>> {
+        match &self.published {
+            Date::DateTime(date) => date.and_local_timezone(self.offset?).single(),
+            Date::Date(_) => None,
+        }
+    }
+}
+
+/// Serializes a [`FixedOffset`] as its seconds east of UTC.
+#[cfg(feature = "serde0")]
+mod offset_seconds {
+    use chrono::FixedOffset;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(offset: &Option<FixedOffset>, serializer: S) -> Result<S::Ok, S::Error> {
+        match offset {
+            Some(offset) => serializer.serialize_some(&offset.local_minus_utc()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<FixedOffset>, D::Error> {
+        Option::<i32>::deserialize(deserializer)?
+            .map(|secs| FixedOffset::east_opt(secs).ok_or_else(|| serde::de::Error::custom("invalid utc offset")))
+            .transpose()
+    }
+}
+
+/// The published and modified date of an article, extracted independently
+/// from each other.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ArticleDates {
+    /// When the article was first published.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub published: Option<Date>,
+    /// Last time the article was modified.
+    #[cfg_attr(feature = "serde0", serde(skip_serializing_if = "Option::is_none"))]
+    pub modified: Option<Date>,
+}
+
+/// The dates found behind labels in the visible text, see
+/// [`DateExtractor::extract_labeled`].
+#[derive(Debug, Clone, Default)]
+pub struct LabeledDates {
+    /// The date behind a [`DateLabelKind::Published`] label.
+    pub published: Option<Date>,
+    /// Whether the published date was resolved from a relative expression.
+    pub relative: bool,
+    /// The date or only the time behind a [`DateLabelKind::Modified`] label.
+    pub modified: Option<Update>,
+}
+
+impl LabeledDates {
+    /// The modified date, with the day of the `published` date if the label
+    /// only has a time, like `Обновлено в 14:02`.
+    pub fn modified_date(&self, published: Option<&Date>) -> Option<Date> {
+        match self.modified.as_ref()? {
+            Update::Date(date) => Some(Date::Date(*date)),
+            Update::DateTime(date) => Some(Date::DateTime(*date)),
+            Update::Time(time) => {
+                let day = match published.or(self.published.as_ref())? {
+                    Date::Date(date) => *date,
+                    Date::DateTime(date) => date.date(),
+                };
+                Some(Date::DateTime(day.and_time(*time)))
+            }
+        }
+    }
+}
+
+/// The document whose dates are extracted, with the parent of its article
+/// node that the dates are searched near. The article node is only selected
+/// if it's needed, and at most once.
+pub(crate) struct DateScope<'a> {
+    doc: &'a Document,
+    lang: Language,
+    filters: Option<&'a Arc<ExtractionFilters>>,
+    parent: OnceCell<Option<Node<'a>>>,
+}
+
+impl<'a> DateScope<'a> {
+    pub(crate) fn new(doc: &'a Document, lang: Language) -> Self {
+        Self {
+            doc,
+            lang,
+            filters: None,
+            parent: OnceCell::new(),
+        }
+    }
+
+    /// Select the article node with the `filters`, like the node of the
+    /// text.
+    pub(crate) fn filters(mut self, filters: Option<&'a Arc<ExtractionFilters>>) -> Self {
+        self.filters = filters;
+        self
+    }
+
+    /// The parent of the article node.
+    fn parent(&self) -> Option<Node<'a>> {
+        *self.parent.get_or_init(|| {
+            article_node_adaptive_with_filters(self.doc, self.lang.clone(), self.filters)
+                .and_then(|(node, _)| node.parent())
+        })
+    }
 }
 
 pub struct DateExtractor;
@@ -125,49 +310,148 @@ impl DateExtractor {
     ///
     /// 1. Look in the common `<meta>` nodes.
     /// 2. Regex the `<head>` node
+    /// 3. Look in the `<time>` elements, see
+    ///    [`DateExtractor::extract_from_time_elements`].
     pub fn extract_from_doc(doc: &Document) -> Option<ArticleDate> {
-        if let Some(published) =
-            DateExtractor::extract_date(doc, &PUBLISH_DATE_NODES, &RE_KEY_VALUE_PUBLISH_DATE)
+        DateExtractor::extract_from_doc_in(&DateScope::new(doc, Language::default()))
+    }
+
+    /// [`DateExtractor::extract_from_doc`] with the article node of the
+    /// `scope`.
+    pub(crate) fn extract_from_doc_in(scope: &DateScope) -> Option<ArticleDate> {
+        let doc = scope.doc;
+        let (published, offset) =
+            match DateExtractor::extract_date(doc, &PUBLISH_DATE_NODES, &RE_KEY_VALUE_PUBLISH_DATE) {
+                Some((date, offset)) => (Date::DateTime(date), offset),
+                None => DateExtractor::time_elements_in(scope)?,
+            };
+        let last_updated = DateExtractor::extract_modified(doc).map(Update::DateTime);
+        Some(ArticleDate {
+            published,
+            last_updated,
+            relative: false,
+            offset,
+        })
+    }
+
+    /// Extract the publishing date from the `<time>` elements of the
+    /// document.
+    ///
+    /// The `datetime` attribute is parsed, or the visible text if there is
+    /// none. Elements whose class marks them as update are skipped. Of the
+    /// elements labeled as published, by a `pubdate` attribute, an
+    /// `itemprop="datePublished"` or a class like `published` or
+    /// `article__date`, the earliest instant wins, comparing the dates at
+    /// their UTC offsets. Without a labeled element the first element inside
+    /// the parent of the article node is preferred over the first element of
+    /// the document.
+    pub fn extract_from_time_elements(doc: &Document) -> Option<Date> {
+        DateExtractor::time_elements_in(&DateScope::new(doc, Language::default())).map(|(date, _)| date)
+    }
+
+    /// [`DateExtractor::extract_from_time_elements`] with the article node of
+    /// the `scope`, together with the UTC offset of the date.
+    fn time_elements_in(scope: &DateScope) -> Option<(Date, Option<FixedOffset>)> {
+        let candidates: Vec<_> = scope
+            .doc
+            .find(Name("time"))
+            .filter(|time| !is_update_time(time))
+            .filter_map(|time| {
+                let (date, offset) = parse_time_element(&time)?;
+                Some((date, offset, time))
+            })
+            .collect();
+
+        if let Some((date, offset, _)) = candidates
+            .iter()
+            .filter(|(_, _, time)| is_published_time(time))
+            .min_by_key(|(date, offset, _)| instant(date, *offset))
         {
-            let last_updated = DateExtractor::extract_date(
-                &doc,
-                &MODIFIED_DATE_NODES,
-                &RE_KEY_VALUE_MODIFIED_DATE,
-            )
-            .map(Update::DateTime);
-            return Some(ArticleDate {
-                published: Date::DateTime(published),
-                last_updated,
-            });
+            return Some((date.clone(), *offset));
         }
-        None
+        if candidates.len() > 1
+            && let Some(parent) = scope.parent()
+            && let Some((date, offset, _)) = candidates.iter().find(|(_, _, time)| {
+                std::iter::successors(time.parent(), |n| n.parent())
+                    .any(|n| n.index() == parent.index())
+            })
+        {
+            return Some((date.clone(), *offset));
+        }
+        candidates.into_iter().next().map(|(date, offset, _)| (date, offset))
+    }
+
+    /// Extract the date the article was last modified:
+    ///
+    /// 1. Look in the common `<meta>` nodes, like `article:modified_time`.
+    /// 2. Look for a `<time datetime="..">` whose class contains `updated`.
+    /// 3. Look for the `dateModified` of an article in the JSON-LD.
+    /// 4. Regex the `<script>` nodes.
+    ///
+    /// This never falls back to the published date.
+    pub fn extract_modified(doc: &Document) -> Option<NaiveDateTime> {
+        DateExtractor::extract_from_nodes(doc, &MODIFIED_DATE_NODES)
+            .map(|(date, _)| date)
+            .or_else(|| {
+                doc.find(Name("time"))
+                    .filter(|n| {
+                        n.attr("class")
+                            .map(|class| class.to_lowercase().contains("updated"))
+                            .unwrap_or_default()
+                    })
+                    .filter_map(|n| n.attr("datetime"))
+                    .find_map(DateExtractor::fuzzy_dtparse)
+            })
+            .or_else(|| {
+                json_ld(doc)
+                    .iter()
+                    .filter(|object| has_type(object, ARTICLE_TYPES))
+                    .filter_map(|object| object.get("dateModified")?.as_str())
+                    .find_map(DateExtractor::fuzzy_dtparse)
+            })
+            .or_else(|| {
+                DateExtractor::extract_from_scripts(doc, &RE_KEY_VALUE_MODIFIED_DATE)
+                    .map(|(date, _)| date)
+            })
     }
 
     fn extract_date<'a>(
         doc: &Document,
         nodes: &[NodeValueQuery<'a>],
         regex: &Regex,
-    ) -> Option<NaiveDateTime> {
+    ) -> Option<(NaiveDateTime, Option<FixedOffset>)> {
+        DateExtractor::extract_from_nodes(doc, nodes)
+            .or_else(|| DateExtractor::extract_from_scripts(doc, regex))
+    }
+
+    fn extract_from_nodes<'a>(
+        doc: &Document,
+        nodes: &[NodeValueQuery<'a>],
+    ) -> Option<(NaiveDateTime, Option<FixedOffset>)> {
         for node in nodes {
             if let Some(content) = doc
                 .find(node.name.and(node.attr))
                 .filter_map(|n| n.attr(node.content_name))
                 .next()
             {
-                if let Some(date) = DateExtractor::fuzzy_dtparse(content) {
+                if let Some(date) = DateExtractor::fuzzy_dtparse_with_offset(content) {
                     return Some(date);
                 }
             }
         }
+        None
+    }
 
-        // look for a "publicationDate":"2019..." json value embedded in <script> tags
+    /// Look for a `"publicationDate":"2019..."` json value embedded in
+    /// `<script>` tags.
+    fn extract_from_scripts(doc: &Document, regex: &Regex) -> Option<(NaiveDateTime, Option<FixedOffset>)> {
         doc.find(Name("script").descendant(Text))
             .filter_map(|script| script.as_text())
             .filter_map(|script| {
                 if let Some(capture) = regex.captures(script) {
                     capture
                         .name("date")
-                        .and_then(|m| DateExtractor::fuzzy_dtparse(m.as_str()))
+                        .and_then(|m| DateExtractor::fuzzy_dtparse_with_offset(m.as_str()))
                 } else {
                     None
                 }
@@ -175,25 +459,416 @@ impl DateExtractor {
             .next()
     }
 
-    fn fuzzy_dtparse(s: &str) -> Option<NaiveDateTime> {
+    /// Extract the dates from the visible text behind labels like
+    /// `Updated: March 3, 2024 4:15 PM` or `Обновлено в 14:02`.
+    ///
+    /// The first [`MAX_LABELED_TEXT_NODES`] text nodes of the parent of the
+    /// article node are searched for the `labels` of the locale's language.
+    /// A label classifies the date as published or modified, and the text
+    /// after it, up to the next label, is parsed like
+    /// [`DateExtractor::extract_from_str_at`]. If that text has no date, like
+    /// a label in its own element, it's parsed together with the following
+    /// text node, unless it has more than two words. The first date of each
+    /// kind wins.
+    pub fn extract_labeled(
+        doc: &Document,
+        labels: &DateLabels,
+        now: NaiveDateTime,
+        locale: &DateLocale,
+    ) -> LabeledDates {
+        let scope = DateScope::new(doc, locale.language.clone().unwrap_or_default());
+        DateExtractor::extract_labeled_in(&scope, labels, now, locale)
+    }
+
+    /// [`DateExtractor::extract_labeled`] with the article node of the
+    /// `scope`.
+    pub(crate) fn extract_labeled_in(
+        scope: &DateScope,
+        labels: &DateLabels,
+        now: NaiveDateTime,
+        locale: &DateLocale,
+    ) -> LabeledDates {
+        let mut labels: Vec<&DateLabel> = labels.for_locale(locale).collect();
+        // `last updated` before `updated`
+        labels.sort_by_key(|label| std::cmp::Reverse(label.label.chars().count()));
+
+        let doc = scope.doc;
+        let scope = scope.parent().or_else(|| doc.find(Name("body")).next());
+        let nodes: Vec<Node> = match scope {
+            Some(scope) => scope.find(Text).collect(),
+            None => doc.find(Text).collect(),
+        };
+        let texts: Vec<String> = nodes
+            .into_iter()
+            .filter(|node| {
+                !std::iter::successors(node.parent(), Node::parent)
+                    .any(|parent| matches!(parent.name(), Some("script" | "style" | "noscript" | "template")))
+            })
+            .map(|node| node.as_text().unwrap_or_default().split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|text| !text.is_empty())
+            .take(MAX_LABELED_TEXT_NODES)
+            .collect();
+
+        let mut dates = LabeledDates::default();
+        for (i, text) in texts.iter().enumerate() {
+            if dates.published.is_some() && dates.modified.is_some() {
+                break;
+            }
+            if text.chars().count() > MAX_LABELED_TEXT_LEN {
+                continue;
+            }
+            for (kind, value) in labeled_values(text, &labels) {
+                let parsed = parse_labeled(&value, now, locale).or_else(|| {
+                    // only connecting words like `on` or `в` before the date
+                    if value.split_whitespace().count() > 2 {
+                        return None;
+                    }
+                    let next = texts.get(i + 1)?;
+                    parse_labeled(&format!("{} {}", value, next), now, locale)
+                });
+                let Some((update, relative)) = parsed else {
+                    continue;
+                };
+                match kind {
+                    DateLabelKind::Published if dates.published.is_none() => {
+                        dates.published = match update {
+                            Update::Date(date) => Some(Date::Date(date)),
+                            Update::DateTime(date) => Some(Date::DateTime(date)),
+                            // a time without a day isn't a publishing date
+                            Update::Time(_) => None,
+                        };
+                        dates.relative = relative;
+                    }
+                    DateLabelKind::Modified if dates.modified.is_none() => dates.modified = Some(update),
+                    _ => {}
+                }
+            }
+        }
+        dates
+    }
+
+    pub(crate) fn fuzzy_dtparse(s: &str) -> Option<NaiveDateTime> {
+        DateExtractor::fuzzy_dtparse_with_offset(s).map(|(date, _)| date)
+    }
+
+    /// Like [`DateExtractor::fuzzy_dtparse`], but also returns the UTC offset
+    /// of the date time, if it has one.
+    pub(crate) fn fuzzy_dtparse_with_offset(s: &str) -> Option<(NaiveDateTime, Option<FixedOffset>)> {
         DATE_PARSER
             .parse(
                 s, None, None, true, /* turns on fuzzy mode */
                 true, /* gives us the tokens that weren't recognized */
                 None, false, &TZINFO,
             )
-            .map(|(date, _, _)| date)
+            .map(|(date, offset, _)| (date, offset))
             .ok()
     }
 
-    /// Extract the publishing timestamp from plain text using fuzzy searching
-    /// with `dtparse`.
+    /// Extract the publishing timestamp from plain text, see
+    /// [`DateExtractor::extract_from_str_at`].
+    ///
+    /// Relative expressions are resolved against the current UTC time and
+    /// month names of all supported languages are tried.
     pub fn extract_from_str(s: &str) -> Option<ArticleDate> {
-        DateExtractor::fuzzy_dtparse(s).map(|published| ArticleDate {
-            published: Date::DateTime(published),
-            last_updated: None,
+        DateExtractor::extract_from_str_at(s, Utc::now().naive_utc(), &DateLocale::default())
+    }
+
+    /// Extract the publishing timestamp from plain text:
+    ///
+    /// 1. A relative expression like `2 hours ago` or `vor 2 Stunden`,
+    ///    resolved against `now`.
+    /// 2. A numeric date like `03/04/2024`, ambiguous dates are resolved
+    ///    with the locale's [`DateOrder`].
+    /// 3. A date with a month name of the locale's language, like
+    ///    `Jan. 5, 2024` or `5 января 2024`.
+    /// 4. Fuzzy searching with `dtparse`.
+    pub fn extract_from_str_at(
+        s: &str,
+        now: NaiveDateTime,
+        locale: &DateLocale,
+    ) -> Option<ArticleDate> {
+        if let Some(published) = parse_relative(s, now, locale) {
+            return Some(ArticleDate {
+                published: Date::DateTime(published),
+                last_updated: None,
+                relative: true,
+                offset: None,
+            });
+        }
+        parse_numeric(s, now, locale)
+            .or_else(|| parse_month_name(s, now, locale))
+            .or_else(|| DateExtractor::fuzzy_dtparse(s))
+            .map(|published| ArticleDate {
+                published: Date::DateTime(published),
+                last_updated: None,
+                relative: false,
+                offset: None,
+            })
+    }
+
+    /// Extract the publishing date from the path of an article's url, see
+    /// [`DateExtractor::extract_from_url_path_at`].
+    pub fn extract_from_url_path(path: &str) -> Option<ArticleDate> {
+        DateExtractor::extract_from_url_path_at(path, Utc::now().date_naive())
+    }
+
+    /// Extract the publishing date from the path of an article's url, like
+    /// `/2024/03/05/`, `/2024/mar/05/`, `/2024-03-05/`, `/20240305/` or
+    /// `/05-03-2024/`.
+    ///
+    /// Only calendar dates with a year between [`MIN_URL_YEAR`] and the
+    /// year after `today` are taken, so product numbers and impossible dates
+    /// like `/2024/13/45/` are skipped. Numbers glued to letters, like
+    /// `covid2020`, aren't dates. If the path has several dates the last one,
+    /// next to the slug, wins. Day and month of a date with the year last
+    /// are read day first, unless the first number can't be a month.
+    pub fn extract_from_url_path_at(path: &str, today: NaiveDate) -> Option<ArticleDate> {
+        url_path_dates(path, today)
+            .into_iter()
+            .max_by_key(|(end, _)| *end)
+            .map(|(_, date)| ArticleDate {
+                published: Date::Date(date),
+                last_updated: None,
+                relative: false,
+                offset: None,
+            })
+    }
+}
+
+/// A run of ASCII digits or letters of a url path and its byte offsets.
+struct PathToken<'a> {
+    start: usize,
+    end: usize,
+    text: &'a str,
+    numeric: bool,
+}
+
+fn path_tokens(path: &str) -> Vec<PathToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = path.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if !c.is_ascii_alphanumeric() {
+            continue;
+        }
+        let numeric = c.is_ascii_digit();
+        let mut end = start + 1;
+        while let Some(&(idx, c)) = chars.peek() {
+            if !c.is_ascii_alphanumeric() || c.is_ascii_digit() != numeric {
+                break;
+            }
+            end = idx + 1;
+            chars.next();
+        }
+        tokens.push(PathToken {
+            start,
+            end,
+            text: &path[start..end],
+            numeric,
+        });
+    }
+    tokens
+}
+
+/// The valid dates of a url path with the byte offset of their end, see
+/// [`DateExtractor::extract_from_url_path_at`].
+///
+/// The tokens are scanned from the left, the tokens of a date aren't part of
+/// another one.
+fn url_path_dates(path: &str, today: NaiveDate) -> Vec<(usize, NaiveDate)> {
+    let tokens = path_tokens(path);
+    let years = MIN_URL_YEAR..=today.year() + 1;
+    let date = |year: u32, month: u32, day: u32| {
+        NaiveDate::from_ymd_opt(year as i32, month, day).filter(|date| years.contains(&date.year()))
+    };
+    // tokens of digits and letters only touch if they are glued together
+    let glued_before = |idx: usize| idx > 0 && tokens[idx - 1].end == tokens[idx].start;
+    let glued_after = |idx: usize| tokens.get(idx + 1).map(|next| next.start == tokens[idx].end).unwrap_or_default();
+    let separated = |idx: usize| {
+        let (prev, next) = (&tokens[idx], &tokens[idx + 1]);
+        next.start == prev.end + 1 && matches!(&path[prev.end..next.start], "-" | "/" | "_" | ".")
+    };
+
+    let mut dates = Vec::new();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let token = &tokens[idx];
+        if token.numeric && token.text.len() == 8 && !glued_before(idx) && !glued_after(idx) {
+            let (year, month, day) = (&token.text[..4], &token.text[4..6], &token.text[6..]);
+            if let Some(date) = date(year.parse().unwrap(), month.parse().unwrap(), day.parse().unwrap()) {
+                dates.push((token.end, date));
+                idx += 1;
+                continue;
+            }
+        }
+        if idx + 2 < tokens.len()
+            && !glued_before(idx)
+            && !glued_after(idx + 2)
+            && separated(idx)
+            && separated(idx + 1)
+        {
+            let (first, second, third) = (&tokens[idx], &tokens[idx + 1], &tokens[idx + 2]);
+            let ymd = match (number(first, 4..=4), month(second), number(third, 1..=2)) {
+                (Some(year), Some(month), Some(day)) => date(year, month, day),
+                _ => None,
+            };
+            let dmy = || match (number(first, 1..=2), number(second, 1..=2), number(third, 4..=4)) {
+                (Some(first), Some(second), Some(year)) if first <= 12 && second > 12 => date(year, first, second),
+                (Some(day), Some(month), Some(year)) => date(year, month, day),
+                (Some(day), None, Some(year)) => date(year, month(second)?, day),
+                _ => None,
+            };
+            if let Some(date) = ymd.or_else(dmy) {
+                dates.push((third.end, date));
+                idx += 3;
+                continue;
+            }
+        }
+        idx += 1;
+    }
+    dates
+}
+
+/// The number of a token with a number of digits in the `len` range.
+fn number(token: &PathToken, len: RangeInclusive<usize>) -> Option<u32> {
+    if token.numeric && len.contains(&token.text.len()) {
+        token.text.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// The month of a token with one or two digits or an English month name.
+fn month(token: &PathToken) -> Option<u32> {
+    if token.numeric {
+        return number(token, 1..=2);
+    }
+    let name = token.text.to_ascii_lowercase();
+    if name.len() < 3 {
+        return None;
+    }
+    URL_MONTH_NAMES
+        .iter()
+        .position(|month| month.starts_with(&name))
+        .map(|idx| idx as u32 + 1)
+}
+
+/// Classes of `<time>` elements that hold the publishing date.
+const PUBLISHED_TIME_CLASSES: [&str; 5] = ["pub", "posted", "created", "article__date", "entry-date"];
+
+/// Classes of `<time>` elements that hold the date of the last update.
+const UPDATED_TIME_CLASSES: [&str; 2] = ["updated", "modified"];
+
+fn time_class(time: &Node) -> String {
+    time.attr("class").unwrap_or_default().to_lowercase()
+}
+
+fn is_update_time(time: &Node) -> bool {
+    let class = time_class(time);
+    UPDATED_TIME_CLASSES.iter().any(|c| class.contains(c))
+        || time.attr("itemprop") == Some("dateModified")
+}
+
+fn is_published_time(time: &Node) -> bool {
+    let class = time_class(time);
+    time.attr("pubdate").is_some()
+        || time.attr("itemprop") == Some("datePublished")
+        || PUBLISHED_TIME_CLASSES.iter().any(|c| class.contains(c))
+}
+
+/// Parse the `datetime` attribute of the `<time>` element, either RFC 3339
+/// or a date without time, and fall back to its visible text.
+fn parse_time_element(time: &Node) -> Option<(Date, Option<FixedOffset>)> {
+    if let Some(datetime) = time.attr("datetime").map(str::trim) {
+        if let Ok(date) = NaiveDate::parse_from_str(datetime, "%Y-%m-%d") {
+            return Some((Date::Date(date), None));
+        }
+        if let Some((date, offset)) = DateExtractor::fuzzy_dtparse_with_offset(datetime) {
+            return Some((Date::DateTime(date), offset));
+        }
+    }
+    let text = time.text();
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    DateExtractor::fuzzy_dtparse_with_offset(text).map(|(date, offset)| (Date::DateTime(date), offset))
+}
+
+/// The kind of each label in the lowercase `text` together with the text
+/// after it, up to the next label.
+///
+/// The `labels` are sorted from the longest to the shortest, so the longest
+/// label at a position wins.
+fn labeled_values(text: &str, labels: &[&DateLabel]) -> Vec<(DateLabelKind, String)> {
+    let lower = text.to_lowercase();
+    let is_boundary = |pos: usize| {
+        let before = lower[..pos].chars().next_back();
+        !before.map(char::is_alphanumeric).unwrap_or_default()
+    };
+    let mut matches: Vec<(usize, usize, DateLabelKind)> = Vec::new();
+    for label in labels {
+        for (start, _) in lower.match_indices(label.label.as_str()) {
+            let end = start + label.label.len();
+            let after = lower[end..].chars().next();
+            if !is_boundary(start) || after.map(char::is_alphanumeric).unwrap_or_default() {
+                continue;
+            }
+            if matches.iter().any(|(s, e, _)| start < *e && *s < end) {
+                continue;
+            }
+            matches.push((start, end, label.kind));
+        }
+    }
+    matches.sort_by_key(|(start, ..)| *start);
+    matches
+        .iter()
+        .enumerate()
+        .map(|(i, (_, end, kind))| {
+            let until = matches.get(i + 1).map(|(start, ..)| *start).unwrap_or(lower.len());
+            let value = lower[*end..until]
+                .trim_matches(|c: char| c.is_whitespace() || matches!(c, ':' | '-' | '–' | '—' | '|' | '·' | '•' | ','));
+            (*kind, value.to_string())
         })
+        .collect()
+}
+
+/// Parse the text behind a date label: a relative expression, a numeric
+/// date or one with a month name, a time of day only, or as a last resort
+/// fuzzy with `dtparse` if it has digits.
+///
+/// Returns the date together with whether it was relative.
+fn parse_labeled(text: &str, now: NaiveDateTime, locale: &DateLocale) -> Option<(Update, bool)> {
+    if text.is_empty() {
+        return None;
+    }
+    if let Some(date) = parse_relative(text, now, locale) {
+        return Some((Update::DateTime(date), true));
     }
+    if let Some(date) = parse_numeric(text, now, locale).or_else(|| parse_month_name(text, now, locale)) {
+        return Some((Update::DateTime(date), false));
+    }
+    if let Some(time) = time_of_day(text) {
+        return Some((Update::Time(time), false));
+    }
+    if !text.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    DateExtractor::fuzzy_dtparse(text).map(|date| (Update::DateTime(date), false))
+}
+
+/// The date as date time, a date without time at midnight.
+fn date_time(date: &Date) -> NaiveDateTime {
+    match date {
+        Date::Date(date) => date.and_time(NaiveTime::default()),
+        Date::DateTime(date) => *date,
+    }
+}
+
+/// The instant of the `date` at its UTC `offset`, dates without an offset
+/// are taken as UTC.
+fn instant(date: &Date, offset: Option<FixedOffset>) -> Option<DateTime<FixedOffset>> {
+    let offset = offset.unwrap_or_else(|| Utc.fix());
+    date_time(date).and_local_timezone(offset).single()
 }
 
 #[cfg(test)]
@@ -211,6 +886,198 @@ mod tests {
         )
     }
 
+    #[test]
+    fn modified_sources() {
+        for head in [
+            r#"<meta property="article:modified_time" content="2024-03-05T08:00:00Z">"#,
+            r#"<meta property="og:updated_time" content="2024-03-05T08:00:00Z">"#,
+            r#"<meta itemprop="dateModified" content="2024-03-05T08:00:00Z">"#,
+            r#"<script type="application/ld+json">{"@type": "NewsArticle", "dateModified": "2024-03-05T08:00:00Z"}</script>"#,
+        ] {
+            let doc = Document::from(format!("<html><head>{}</head><body></body></html>", head).as_str());
+            let modified = DateExtractor::extract_modified(&doc).unwrap();
+            assert_eq!(modified.to_string(), "2024-03-05 08:00:00", "{}", head);
+            assert!(DateExtractor::extract_from_doc(&doc).is_none(), "{}", head);
+        }
+
+        let doc = Document::from(
+            r#"<html><body><time class="entry-date updated" datetime="2024-03-05T08:00:00Z">March 5</time></body></html>"#,
+        );
+        assert!(DateExtractor::extract_modified(&doc).is_some());
+    }
+
+    fn time_elements(body: &str) -> Option<ArticleDate> {
+        DateExtractor::extract_from_doc(&Document::from(
+            format!("<html><head></head><body>{}</body></html>", body).as_str(),
+        ))
+    }
+
+    #[test]
+    fn time_element_with_offset() {
+        let date = time_elements(
+            r#"<div class="byline">By Jane Zhu
+            <time datetime="2024-03-02T10:15:00+03:00" class="article__date">2 March</time></div>"#,
+        )
+        .unwrap();
+        match date.published {
+            Date::DateTime(date) => assert_eq!(date.to_string(), "2024-03-02 10:15:00"),
+            date => panic!("expected a date time, got {:?}", date),
+        }
+        assert_eq!(date.offset, FixedOffset::east_opt(3 * 3600));
+    }
+
+    #[test]
+    fn earliest_time_element_across_offsets() {
+        // 07:15 UTC is earlier than 09:00 UTC
+        let date = time_elements(
+            r#"<time class="published" datetime="2024-03-02T09:00:00+00:00">2 March</time>
+            <time pubdate datetime="2024-03-02T10:15:00+03:00">2 March</time>"#,
+        )
+        .unwrap();
+        assert_eq!(
+            date.published_with_offset().unwrap().to_rfc3339(),
+            "2024-03-02T10:15:00+03:00"
+        );
+    }
+
+    #[test]
+    fn time_element_date_only() {
+        let date = time_elements(r#"<time datetime="2024-03-02">March 2, 2024</time>"#).unwrap();
+        assert!(matches!(
+            date.published,
+            Date::Date(date) if date == NaiveDate::from_ymd_opt(2024, 3, 2).unwrap()
+        ));
+    }
+
+    #[test]
+    fn time_element_text() {
+        let date = time_elements(r#"<time class="published">March 2, 2024 10:15</time>"#).unwrap();
+        assert!(matches!(date.published, Date::DateTime(_)));
+    }
+
+    #[test]
+    fn earliest_published_time_element() {
+        let date = time_elements(
+            r#"<time class="updated" datetime="2024-03-05T08:00:00Z">5 March</time>
+            <time class="published" datetime="2024-03-04T09:00:00+01:00">4 March</time>
+            <time pubdate datetime="2024-03-02T10:15:00+03:00">2 March</time>"#,
+        )
+        .unwrap();
+        match date.published {
+            Date::DateTime(date) => assert_eq!(date.to_string(), "2024-03-02 10:15:00"),
+            date => panic!("expected a date time, got {:?}", date),
+        }
+        assert!(date.last_updated.is_some());
+    }
+
+    #[test]
+    fn relative_from_str() {
+        let now = NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let locale = DateLocale::new(Language::Russian);
+        let date = DateExtractor::extract_from_str_at("3 часа назад", now, &locale).unwrap();
+        assert!(date.relative);
+        assert!(matches!(date.published, Date::DateTime(d) if d.to_string() == "2024-03-10 09:00:00"));
+
+        let date = DateExtractor::extract_from_str_at("5 января 2024", now, &locale).unwrap();
+        assert!(!date.relative);
+        assert!(matches!(date.published, Date::DateTime(d) if d.to_string() == "2024-01-05 00:00:00"));
+    }
+
+    #[test]
+    fn labeled_dates() {
+        let now = NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let doc = Document::from(include_str!("../tests/fixtures/dates/updated_en.html"));
+        let dates = DateExtractor::extract_labeled(&doc, &DateLabels::default(), now, &DateLocale::default());
+        assert!(matches!(dates.published, Some(Date::DateTime(d)) if d.to_string() == "2024-03-01 00:00:00"));
+        assert!(matches!(dates.modified, Some(Update::DateTime(d)) if d.to_string() == "2024-03-03 16:15:00"));
+        assert!(!dates.relative);
+
+        // only the time of the update, on the day it was published
+        let doc = Document::from(include_str!("../tests/fixtures/dates/updated_ru.html"));
+        let locale = DateLocale::new(Language::Russian);
+        let dates = DateExtractor::extract_labeled(&doc, &DateLabels::default(), now, &locale);
+        assert!(matches!(dates.published, Some(Date::DateTime(d)) if d.to_string() == "2024-03-01 10:00:00"));
+        assert!(matches!(dates.modified, Some(Update::Time(_))));
+        assert!(matches!(dates.modified_date(None), Some(Date::DateTime(d)) if d.to_string() == "2024-03-01 14:02:00"));
+
+        // the english labels don't apply to a german article
+        let doc = Document::from(include_str!("../tests/fixtures/dates/updated_en.html"));
+        let dates = DateExtractor::extract_labeled(&doc, &DateLabels::default(), now, &DateLocale::new(Language::German));
+        assert!(dates.published.is_none() && dates.modified.is_none());
+    }
+
+    #[test]
+    fn custom_date_labels() {
+        let now = NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let doc = Document::from(
+            r#"<html><body><article><p class="meta">Stand: 05.03.2024 | Erstellt: 04.03.2024</p>
+            <div itemprop="articleBody"><p>Der Stadtrat hat am Dienstag den Ausbau des Hafens beschlossen.</p></div>
+            </article></body></html>"#,
+        );
+        let labels = DateLabels::empty()
+            .add(Language::German, DateLabelKind::Modified, "Stand")
+            .add(Language::German, DateLabelKind::Published, "Erstellt");
+        let dates = DateExtractor::extract_labeled(&doc, &labels, now, &DateLocale::new(Language::German));
+        assert!(matches!(dates.published, Some(Date::DateTime(d)) if d.to_string() == "2024-03-04 00:00:00"));
+        assert!(matches!(dates.modified, Some(Update::DateTime(d)) if d.to_string() == "2024-03-05 00:00:00"));
+
+        let dates = DateExtractor::extract_labeled(&doc, &DateLabels::default(), now, &DateLocale::default());
+        assert!(dates.published.is_none() && dates.modified.is_none());
+    }
+
+    fn url_date(path: &str) -> Option<String> {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        DateExtractor::extract_from_url_path_at(path, today).map(|date| match date.published {
+            Date::Date(date) => date.to_string(),
+            Date::DateTime(date) => panic!("expected a date, got {}", date),
+        })
+    }
+
+    #[test]
+    fn dates_of_url_paths() {
+        for path in [
+            "/news/2024/03/05/harbour",
+            "/news/2024/mar/05/harbour",
+            "/news/2024-03-05/harbour",
+            "/news/2024-03-05-harbour-expansion",
+            "/news/20240305/harbour",
+            "/news/harbour_20240305.html",
+            "/news/05-03-2024/harbour",
+            "/news/5-march-2024/harbour",
+            "/archive/2019/01/01/news/2024/03/05/harbour",
+        ] {
+            assert_eq!(url_date(path).as_deref(), Some("2024-03-05"), "{}", path);
+        }
+        assert_eq!(url_date("/news/03-25-2024/harbour").as_deref(), Some("2024-03-25"));
+        assert_eq!(url_date("/news/2024/02/29/harbour").as_deref(), Some("2024-02-29"));
+        assert_eq!(url_date("/2023/12/11/2024-harbour").as_deref(), Some("2023-12-11"));
+        assert_eq!(url_date("/news/2025/01/01/harbour").as_deref(), Some("2025-01-01"));
+    }
+
+    #[test]
+    fn invalid_dates_of_url_paths() {
+        for path in [
+            "/news/2024/13/45/harbour",
+            "/news/2023/02/29/harbour",
+            "/news/2031/01/01/harbour",
+            "/product/1234-05-06/",
+            "/product/30125478/",
+            "/product/sku20240305",
+            "/news/covid19-harbour",
+        ] {
+            assert_eq!(url_date(path), None, "{}", path);
+        }
+    }
+
     #[test]
     fn publish_modified() {
         let caps = RE_KEY_VALUE_PUBLISH_DATE
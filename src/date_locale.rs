@@ -0,0 +1,523 @@
+//! Locale aware parsing of the dates regional outlets render as text, like
+//! `3 часа назад`, `vor 2 Stunden`, `5. Januar 2024` or `03/04/2024`, and
+//! the labels that precede them, like `Updated:` or `Обновлено`.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::Language;
+
+lazy_static! {
+    static ref RE_NUMERIC_DATE: Regex =
+        Regex::new(r"\b(\d{1,4})[./\-](\d{1,2})[./\-](\d{2,4})\b").unwrap();
+    static ref RE_TIME: Regex =
+        Regex::new(r"(?i)\b([01]?\d|2[0-3]):([0-5]\d)\b(?:\s*([ap])\.?\s?m\b\.?)?").unwrap();
+}
+
+/// The order of day, month and year in a numeric date like `03/04/2024`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// `03/04/2024` is the 3rd of April.
+    DayMonthYear,
+    /// `03/04/2024` is the 4th of March.
+    MonthDayYear,
+}
+
+impl DateOrder {
+    /// The usual order of numeric dates in the language, month first for
+    /// english, day first otherwise.
+    pub fn for_language(lang: &Language) -> Self {
+        match lang {
+            Language::English => DateOrder::MonthDayYear,
+            _ => DateOrder::DayMonthYear,
+        }
+    }
+}
+
+/// The locale used to parse dates from text.
+#[derive(Debug, Clone, Default)]
+pub struct DateLocale {
+    /// The language of the text. Month names and relative expressions of
+    /// all supported languages are tried if `None`.
+    pub language: Option<Language>,
+    /// The order of ambiguous numeric dates, derived from the
+    /// [`DateLocale::language`] if `None`.
+    pub order: Option<DateOrder>,
+}
+
+impl DateLocale {
+    pub fn new(language: Language) -> Self {
+        Self {
+            language: Some(language),
+            order: None,
+        }
+    }
+
+    pub fn order(mut self, order: DateOrder) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    fn date_order(&self) -> DateOrder {
+        self.order.unwrap_or_else(|| {
+            DateOrder::for_language(self.language.as_ref().unwrap_or(&Language::English))
+        })
+    }
+
+    /// The tables of the locale's language, or all tables if the language
+    /// is unknown or unsupported.
+    fn tables(&self) -> Vec<&'static LocaleTable> {
+        let table = self
+            .language
+            .as_ref()
+            .and_then(|lang| TABLES.iter().find(|table| table.language == *lang));
+        match table {
+            Some(table) => vec![table],
+            None => TABLES.iter().collect(),
+        }
+    }
+}
+
+/// Month names and relative expressions of a language.
+struct LocaleTable {
+    language: Language,
+    /// Names and abbreviations of each month, in all grammatical cases.
+    months: [&'static [&'static str]; 12],
+    /// Phrases that mark a relative date, like `ago`.
+    ago: &'static [&'static str],
+    /// Phrases for the day before.
+    yesterday: &'static [&'static str],
+    /// Stems of the units minute, hour, day and week.
+    units: [&'static [&'static str]; 4],
+}
+
+lazy_static! {
+    static ref TABLES: [LocaleTable; 5] = [
+        LocaleTable {
+            language: Language::English,
+            months: [
+                &["january", "jan"],
+                &["february", "feb"],
+                &["march", "mar"],
+                &["april", "apr"],
+                &["may"],
+                &["june", "jun"],
+                &["july", "jul"],
+                &["august", "aug"],
+                &["september", "sep", "sept"],
+                &["october", "oct"],
+                &["november", "nov"],
+                &["december", "dec"],
+            ],
+            ago: &["ago"],
+            yesterday: &["yesterday"],
+            units: [&["min"], &["hour", "hr"], &["day"], &["week"]],
+        },
+        LocaleTable {
+            language: Language::German,
+            months: [
+                &["januar", "jänner", "jan"],
+                &["februar", "feb"],
+                &["märz", "mär", "mrz"],
+                &["april", "apr"],
+                &["mai"],
+                &["juni", "jun"],
+                &["juli", "jul"],
+                &["august", "aug"],
+                &["september", "sep", "sept"],
+                &["oktober", "okt"],
+                &["november", "nov"],
+                &["dezember", "dez"],
+            ],
+            ago: &["vor"],
+            yesterday: &["gestern"],
+            units: [&["min"], &["stunde", "std"], &["tag"], &["woche"]],
+        },
+        LocaleTable {
+            language: Language::French,
+            months: [
+                &["janvier", "janv"],
+                &["février", "fevrier", "févr", "fevr"],
+                &["mars"],
+                &["avril", "avr"],
+                &["mai"],
+                &["juin"],
+                &["juillet", "juil"],
+                &["août", "aout"],
+                &["septembre", "sept"],
+                &["octobre", "oct"],
+                &["novembre", "nov"],
+                &["décembre", "decembre", "déc", "dec"],
+            ],
+            ago: &["il y a"],
+            yesterday: &["hier"],
+            units: [&["min"], &["heure"], &["jour"], &["semaine"]],
+        },
+        LocaleTable {
+            language: Language::Spanish,
+            months: [
+                &["enero", "ene"],
+                &["febrero", "feb"],
+                &["marzo", "mar"],
+                &["abril", "abr"],
+                &["mayo", "may"],
+                &["junio", "jun"],
+                &["julio", "jul"],
+                &["agosto", "ago"],
+                &["septiembre", "setiembre", "sep", "sept"],
+                &["octubre", "oct"],
+                &["noviembre", "nov"],
+                &["diciembre", "dic"],
+            ],
+            ago: &["hace"],
+            yesterday: &["ayer"],
+            units: [&["min"], &["hora"], &["día", "dia"], &["semana"]],
+        },
+        LocaleTable {
+            language: Language::Russian,
+            months: [
+                &["января", "январь", "янв"],
+                &["февраля", "февраль", "фев"],
+                &["марта", "март", "мар"],
+                &["апреля", "апрель", "апр"],
+                &["мая", "май"],
+                &["июня", "июнь", "июн"],
+                &["июля", "июль", "июл"],
+                &["августа", "август", "авг"],
+                &["сентября", "сентябрь", "сен", "сент"],
+                &["октября", "октябрь", "окт"],
+                &["ноября", "ноябрь", "ноя"],
+                &["декабря", "декабрь", "дек"],
+            ],
+            ago: &["назад"],
+            yesterday: &["вчера"],
+            units: [&["мин"], &["час"], &["дн", "день"], &["недел"]],
+        },
+    ];
+}
+
+/// Whether a [`DateLabel`] marks the date of publication or of the last
+/// update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DateLabelKind {
+    Published,
+    Modified,
+}
+
+/// A lowercase label that precedes a date in the visible text of a
+/// language, like `updated` in `Updated: March 3, 2024`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateLabel {
+    pub language: Language,
+    pub kind: DateLabelKind,
+    pub label: String,
+}
+
+/// The labels of dates in the visible text, see
+/// [`crate::date::DateExtractor::extract_labeled`].
+///
+/// The default holds built-in labels for english, german, french, spanish
+/// and russian, more can be added with [`DateLabels::add`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateLabels {
+    labels: Vec<DateLabel>,
+}
+
+impl DateLabels {
+    /// No labels at all.
+    pub fn empty() -> Self {
+        Self { labels: Vec::new() }
+    }
+
+    /// Add the `label` of the `kind` for the `language`.
+    pub fn add<T: AsRef<str>>(mut self, language: Language, kind: DateLabelKind, label: T) -> Self {
+        let label = label.as_ref().trim().to_lowercase();
+        if !label.is_empty() {
+            self.labels.push(DateLabel { language, kind, label });
+        }
+        self
+    }
+
+    pub fn labels(&self) -> &[DateLabel] {
+        &self.labels
+    }
+
+    /// The labels of the locale's language, or all labels if the language
+    /// is unknown.
+    pub fn for_locale<'a>(&'a self, locale: &'a DateLocale) -> impl Iterator<Item = &'a DateLabel> + 'a {
+        self.labels.iter().filter(move |label| {
+            locale.language.as_ref().map(|lang| *lang == label.language).unwrap_or(true)
+        })
+    }
+}
+
+impl Default for DateLabels {
+    fn default() -> Self {
+        DEFAULT_DATE_LABELS
+            .iter()
+            .flat_map(|(language, kind, labels)| labels.iter().map(move |label| (language, kind, label)))
+            .fold(DateLabels::empty(), |labels, (language, kind, label)| {
+                labels.add(language.clone(), *kind, label)
+            })
+    }
+}
+
+lazy_static! {
+    static ref DEFAULT_DATE_LABELS: [(Language, DateLabelKind, &'static [&'static str]); 10] = [
+        (
+            Language::English,
+            DateLabelKind::Published,
+            &["published", "first published", "posted", "published on", "posted on"],
+        ),
+        (
+            Language::English,
+            DateLabelKind::Modified,
+            &["updated", "last updated", "modified", "last modified", "updated on"],
+        ),
+        (Language::German, DateLabelKind::Published, &["veröffentlicht", "erschienen", "publiziert"]),
+        (
+            Language::German,
+            DateLabelKind::Modified,
+            &["aktualisiert", "zuletzt aktualisiert", "geändert"],
+        ),
+        (Language::French, DateLabelKind::Published, &["publié", "publié le"]),
+        (Language::French, DateLabelKind::Modified, &["mis à jour", "modifié", "actualisé"]),
+        (Language::Spanish, DateLabelKind::Published, &["publicado"]),
+        (Language::Spanish, DateLabelKind::Modified, &["actualizado", "modificado"]),
+        (
+            Language::Russian,
+            DateLabelKind::Published,
+            &["опубликовано", "опубликован", "дата публикации"],
+        ),
+        (
+            Language::Russian,
+            DateLabelKind::Modified,
+            &["обновлено", "обновлен", "дата обновления", "изменено"],
+        ),
+    ];
+}
+
+/// Lowercase words of the text, without punctuation.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// The time of day in the text, like `10:15` or `4:15 PM`.
+pub(crate) fn time_of_day(text: &str) -> Option<NaiveTime> {
+    let caps = RE_TIME.captures(text)?;
+    let mut hour: u32 = caps[1].parse().ok()?;
+    match caps.get(3).map(|m| m.as_str().to_ascii_lowercase()).as_deref() {
+        Some("p") if hour < 12 => hour += 12,
+        Some("a") if hour == 12 => hour = 0,
+        _ => {}
+    }
+    NaiveTime::from_hms_opt(hour, caps[2].parse().ok()?, 0)
+}
+
+/// Parse a relative expression like `2 hours ago`, `vor 2 Stunden`,
+/// `3 часа назад` or `yesterday` and resolve it against `now`.
+///
+/// Amounts too large to resolve, like `999999999 days ago`, are no date.
+pub fn parse_relative(text: &str, now: NaiveDateTime, locale: &DateLocale) -> Option<NaiveDateTime> {
+    let lower = text.to_lowercase();
+    let words = words(text);
+    for table in locale.tables() {
+        if table.yesterday.iter().any(|y| words.iter().any(|w| w == y)) {
+            let day = now.checked_sub_signed(TimeDelta::days(1))?.date();
+            return Some(day.and_time(time_of_day(text).unwrap_or_default()));
+        }
+        let is_ago = |ago: &&str| {
+            if ago.contains(' ') {
+                lower.contains(ago)
+            } else {
+                words.iter().any(|w| w == ago)
+            }
+        };
+        if !table.ago.iter().any(is_ago) {
+            continue;
+        }
+        for pair in words.windows(2) {
+            let amount = match pair[0].as_str() {
+                "a" | "an" | "one" | "ein" | "eine" | "einer" | "un" | "une" | "una" => 1,
+                n => match n.parse::<i64>() {
+                    Ok(n) => n,
+                    Err(_) => continue,
+                },
+            };
+            let unit = table
+                .units
+                .iter()
+                .position(|stems| stems.iter().any(|stem| pair[1].starts_with(stem)));
+            let duration = match unit {
+                Some(0) => TimeDelta::try_minutes(amount),
+                Some(1) => TimeDelta::try_hours(amount),
+                Some(2) => TimeDelta::try_days(amount),
+                Some(3) => TimeDelta::try_weeks(amount),
+                _ => continue,
+            };
+            return now.checked_sub_signed(duration?);
+        }
+    }
+    None
+}
+
+/// The month of the word in the locale's languages.
+fn month(word: &str, locale: &DateLocale) -> Option<u32> {
+    locale.tables().iter().find_map(|table| {
+        table
+            .months
+            .iter()
+            .position(|names| names.contains(&word))
+            .map(|pos| pos as u32 + 1)
+    })
+}
+
+/// Parse a date with a month name like `Jan. 5, 2024`, `5. Januar 2024`,
+/// `5 de enero de 2024` or `5 января 2024`.
+///
+/// The year of `now` is used if the text has none.
+pub fn parse_month_name(text: &str, now: NaiveDateTime, locale: &DateLocale) -> Option<NaiveDateTime> {
+    let words = words(text);
+    let pos = words.iter().position(|w| month(w, locale).is_some())?;
+    let month = month(&words[pos], locale)?;
+    let number = |w: &String| w.parse::<u32>().ok();
+    let day = words[..pos]
+        .iter()
+        .rev()
+        .take(2)
+        .chain(words[pos + 1..].iter().take(2))
+        .filter_map(number)
+        .find(|n| (1..=31).contains(n))?;
+    let year = words
+        .iter()
+        .filter_map(number)
+        .find(|n| (1000..=9999).contains(n))
+        .map(|n| n as i32)
+        .unwrap_or_else(|| now.year());
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+    Some(date.and_time(time_of_day(text).unwrap_or_default()))
+}
+
+/// Parse a numeric date like `2024-03-04`, `03/04/2024` or `03.04.24`.
+///
+/// Ambiguous dates are resolved with the locale's [`DateOrder`], unless
+/// one of the numbers can't be a month. A two-digit year is in this century
+/// up to the year after `now`, and in the last century after that, so `99`
+/// is 1999.
+pub fn parse_numeric(text: &str, now: NaiveDateTime, locale: &DateLocale) -> Option<NaiveDateTime> {
+    let caps = RE_NUMERIC_DATE.captures(text)?;
+    let first: u32 = caps[1].parse().ok()?;
+    let second: u32 = caps[2].parse().ok()?;
+    let third: u32 = caps[3].parse().ok()?;
+    let (year, month, day) = if caps[1].len() == 4 {
+        (first, second, third)
+    } else {
+        let year = if caps[3].len() == 2 {
+            let century = (now.year() as u32 + 1) / 100 * 100;
+            if century + third <= now.year() as u32 + 1 {
+                century + third
+            } else {
+                century - 100 + third
+            }
+        } else {
+            third
+        };
+        let day_first = match locale.date_order() {
+            _ if first > 12 => true,
+            _ if second > 12 => false,
+            DateOrder::DayMonthYear => true,
+            DateOrder::MonthDayYear => false,
+        };
+        if day_first {
+            (year, second, first)
+        } else {
+            (year, first, second)
+        }
+    };
+    let date = NaiveDate::from_ymd_opt(year as i32, month, day)?;
+    Some(date.and_time(time_of_day(text).unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn relative() {
+        let any = DateLocale::default();
+        let cases = [
+            ("2 hours ago", "2024-03-10 10:00:00"),
+            ("an hour ago", "2024-03-10 11:00:00"),
+            ("vor 2 Stunden", "2024-03-10 10:00:00"),
+            ("3 часа назад", "2024-03-10 09:00:00"),
+            ("il y a 5 minutes", "2024-03-10 11:55:00"),
+            ("hace 2 días", "2024-03-08 12:00:00"),
+            ("Yesterday, 18:30", "2024-03-09 18:30:00"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_relative(text, now(), &any).unwrap().to_string(), expected, "{}", text);
+        }
+        assert!(parse_relative("2 hours ago", now(), &DateLocale::new(Language::German)).is_none());
+    }
+
+    #[test]
+    fn month_names() {
+        let any = DateLocale::default();
+        let cases = [
+            ("Jan. 5, 2024", "2024-01-05 00:00:00"),
+            ("5. Januar 2024, 10:15 Uhr", "2024-01-05 10:15:00"),
+            ("le 5 janvier 2024", "2024-01-05 00:00:00"),
+            ("5 de enero de 2024", "2024-01-05 00:00:00"),
+            ("5 января 2024", "2024-01-05 00:00:00"),
+            ("5 января", "2024-01-05 00:00:00"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_month_name(text, now(), &any).unwrap().to_string(), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn twelve_hour_time() {
+        assert_eq!(time_of_day("March 3, 2024 4:15 PM").unwrap().to_string(), "16:15:00");
+        assert_eq!(time_of_day("12:30 a.m.").unwrap().to_string(), "00:30:00");
+        assert_eq!(time_of_day("в 14:02").unwrap().to_string(), "14:02:00");
+    }
+
+    #[test]
+    fn ambiguous_numeric() {
+        let date = |text, locale: &DateLocale| parse_numeric(text, now(), locale).unwrap().date().to_string();
+        assert_eq!(date("03/04/2024", &DateLocale::new(Language::English)), "2024-03-04");
+        assert_eq!(date("03/04/2024", &DateLocale::new(Language::German)), "2024-04-03");
+        assert_eq!(
+            date("03/04/2024", &DateLocale::new(Language::German).order(DateOrder::MonthDayYear)),
+            "2024-03-04"
+        );
+        assert_eq!(date("13/04/2024", &DateLocale::new(Language::English)), "2024-04-13");
+        assert_eq!(date("2024-03-04", &DateLocale::default()), "2024-03-04");
+        assert_eq!(date("03.04.24", &DateLocale::new(Language::Russian)), "2024-04-03");
+        assert_eq!(date("03.04.25", &DateLocale::new(Language::Russian)), "2025-04-03");
+        assert_eq!(date("03.04.99", &DateLocale::new(Language::Russian)), "1999-04-03");
+    }
+
+    #[test]
+    fn relative_overflow() {
+        let locale = DateLocale::new(Language::English);
+        assert!(parse_relative("999999999 days ago", now(), &locale).is_none());
+        assert!(parse_relative("9223372036854775807 weeks ago", now(), &locale).is_none());
+        assert_eq!(
+            parse_relative("2 days ago", now(), &locale).unwrap().to_string(),
+            "2024-03-08 12:00:00"
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Detection of articles that are reachable via several urls, so each one
+//! is downloaded and extracted only once per crawl.
+
+use std::collections::HashMap;
+use std::hash::Hasher;
+
+use fnv::FnvHasher;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use url::{form_urlencoded, Url};
+
+/// Query parameters that are removed by default: tracking parameters that
+/// don't select a different page.
+pub const DEFAULT_STRIPPED_PARAMS: [&str; 3] = ["utm_*", "fbclid", "gclid"];
+
+/// Rules for normalizing article urls before they are queued, so the same
+/// article linked with different tracking parameters is downloaded once.
+///
+/// By default the [`DEFAULT_STRIPPED_PARAMS`], the fragment and the
+/// trailing slash of the path are removed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlNormalizer {
+    /// Lowercase names of the removed query parameters, a trailing `*`
+    /// matches all parameters with that prefix.
+    stripped_params: Vec<String>,
+    strip_fragment: bool,
+    strip_trailing_slash: bool,
+}
+
+impl Default for UrlNormalizer {
+    fn default() -> Self {
+        Self {
+            stripped_params: DEFAULT_STRIPPED_PARAMS.iter().map(|param| param.to_string()).collect(),
+            strip_fragment: true,
+            strip_trailing_slash: true,
+        }
+    }
+}
+
+impl UrlNormalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A normalizer that keeps urls unchanged.
+    pub fn none() -> Self {
+        Self {
+            stripped_params: Vec::new(),
+            strip_fragment: false,
+            strip_trailing_slash: false,
+        }
+    }
+
+    /// Also remove the query parameter `param`, a trailing `*` matches all
+    /// parameters with that prefix, like `utm_*`.
+    pub fn strip_param<T: Into<String>>(mut self, param: T) -> Self {
+        self.stripped_params.push(param.into().to_lowercase());
+        self
+    }
+
+    /// Remove exactly these query parameters instead of the
+    /// [`DEFAULT_STRIPPED_PARAMS`].
+    pub fn stripped_params<I, T>(mut self, params: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.stripped_params = params.into_iter().map(|param| param.into().to_lowercase()).collect();
+        self
+    }
+
+    /// Whether to remove the fragment. Default is `true`.
+    pub fn strip_fragment(mut self, strip_fragment: bool) -> Self {
+        self.strip_fragment = strip_fragment;
+        self
+    }
+
+    /// Whether to remove the trailing slash of the path, the root path `/`
+    /// is kept. Default is `true`.
+    pub fn strip_trailing_slash(mut self, strip_trailing_slash: bool) -> Self {
+        self.strip_trailing_slash = strip_trailing_slash;
+        self
+    }
+
+    /// Whether the query parameter `name` is removed.
+    pub fn is_stripped_param(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.stripped_params.iter().any(|param| match param.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => *param == name,
+        })
+    }
+
+    /// The normalized `url`.
+    pub fn normalize(&self, url: &Url) -> Url {
+        let mut url = url.clone();
+        if self.strip_fragment {
+            url.set_fragment(None);
+        }
+        if let Some(query) = url.query() {
+            // the kept params stay as they are, not re-encoded
+            let params: Vec<&str> = query.split('&').collect();
+            let kept: Vec<&str> = params
+                .iter()
+                .copied()
+                .filter(|param| {
+                    !form_urlencoded::parse(param.as_bytes())
+                        .next()
+                        .is_some_and(|(key, _)| self.is_stripped_param(&key))
+                })
+                .collect();
+            if kept.len() < params.len() {
+                let query = kept.join("&");
+                url.set_query(Some(query.as_str()).filter(|query| !query.is_empty()));
+            }
+        }
+        if self.strip_trailing_slash && url.path().len() > 1 && url.path().ends_with('/') {
+            let path = url.path().trim_end_matches('/').to_string();
+            url.set_path(&path);
+        }
+        url
+    }
+}
+
+/// Number of duplicates skipped during a crawl.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct DedupStats {
+    /// Article urls that were already queued after normalization.
+    pub duplicate_urls: usize,
+    /// Downloaded articles whose canonical url was already processed.
+    pub duplicate_canonicals: usize,
+    /// Downloaded articles whose text nearly matches the text of an article
+    /// that was already processed.
+    pub duplicate_contents: usize,
+}
+
+impl DedupStats {
+    /// Number of all skipped duplicates.
+    pub fn total(&self) -> usize {
+        self.duplicate_urls + self.duplicate_canonicals + self.duplicate_contents
+    }
+}
+
+/// Remembers the canonical urls and, optionally, the text fingerprints of
+/// the processed articles to detect duplicates.
+#[derive(Debug, Clone, Default)]
+pub struct Deduplicator {
+    normalizer: UrlNormalizer,
+    /// Normalized canonical urls and the url of the article they were first
+    /// seen in.
+    canonicals: HashMap<Url, Url>,
+    /// The [`simhash`] of the text of each processed article, if content
+    /// fingerprints are enabled.
+    fingerprints: Option<Vec<(u64, Url)>>,
+    stats: DedupStats,
+}
+
+impl Deduplicator {
+    /// Max number of differing bits of two [`simhash`]es that are
+    /// considered the same text.
+    pub const MAX_FINGERPRINT_DISTANCE: u32 = 3;
+
+    pub fn new(normalizer: UrlNormalizer) -> Self {
+        Self {
+            normalizer,
+            ..Default::default()
+        }
+    }
+
+    /// Whether to also compare the text of the articles.
+    pub fn content_fingerprints(mut self, content_fingerprints: bool) -> Self {
+        self.fingerprints = content_fingerprints.then(Vec::new);
+        self
+    }
+
+    /// Whether the text of the articles is compared.
+    pub fn has_content_fingerprints(&self) -> bool {
+        self.fingerprints.is_some()
+    }
+
+    pub fn normalizer(&self) -> &UrlNormalizer {
+        &self.normalizer
+    }
+
+    /// Number of duplicates skipped so far.
+    pub fn stats(&self) -> &DedupStats {
+        &self.stats
+    }
+
+    /// The `url` normalized with the [`UrlNormalizer`].
+    pub fn normalize(&self, url: &Url) -> Url {
+        self.normalizer.normalize(url)
+    }
+
+    /// Count an article url that was already queued.
+    pub fn count_duplicate_url(&mut self) {
+        self.stats.duplicate_urls += 1;
+    }
+
+    /// Record the article downloaded from `url` with the `canonical` url,
+    /// the article's own url if it has none.
+    ///
+    /// Returns the url of the other article with the same canonical url, if
+    /// one was already recorded.
+    pub fn insert_canonical(&mut self, url: &Url, canonical: Option<&Url>) -> Option<Url> {
+        let key = self.normalize(canonical.unwrap_or(url));
+        match self.canonicals.get(&key) {
+            Some(original) if *original != *url => {
+                self.stats.duplicate_canonicals += 1;
+                Some(original.clone())
+            }
+            Some(_) => None,
+            None => {
+                self.canonicals.insert(key, url.clone());
+                None
+            }
+        }
+    }
+
+    /// Record the `text` of the article downloaded from `url`, if content
+    /// fingerprints are enabled.
+    ///
+    /// Returns the url of an article with nearly the same text, if one was
+    /// already recorded.
+    pub fn insert_content(&mut self, url: &Url, text: &str) -> Option<Url> {
+        let fingerprints = self.fingerprints.as_mut()?;
+        let fingerprint = simhash(text)?;
+        let original = fingerprints
+            .iter()
+            .find(|(other, other_url)| {
+                other_url != url && (fingerprint ^ other).count_ones() <= Self::MAX_FINGERPRINT_DISTANCE
+            })
+            .map(|(_, other_url)| other_url.clone());
+        if original.is_some() {
+            self.stats.duplicate_contents += 1;
+        } else {
+            fingerprints.push((fingerprint, url.clone()));
+        }
+        original
+    }
+
+    /// Forget all recorded articles, the stats are kept.
+    pub fn clear(&mut self) {
+        self.canonicals.clear();
+        if let Some(fingerprints) = &mut self.fingerprints {
+            fingerprints.clear();
+        }
+    }
+}
+
+/// The 64 bit simhash of the lowercase words of the `text`, `None` if the
+/// text has no words.
+///
+/// Texts that differ in a few words have hashes that differ in a few bits.
+pub fn simhash(text: &str) -> Option<u64> {
+    let mut weights = [0i64; 64];
+    let mut words = 0;
+    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|word| !word.is_empty()) {
+        let mut hasher = FnvHasher::default();
+        hasher.write(word.to_lowercase().as_bytes());
+        let hash = hasher.finish();
+        words += 1;
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+    if words == 0 {
+        return None;
+    }
+    Some(
+        weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 0)
+            .fold(0, |hash, (bit, _)| hash | (1 << bit)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_urls() {
+        let normalizer = UrlNormalizer::default();
+        let url = Url::parse("https://example.com/news/harbour/?utm_source=feed&id=3&FBCLID=x#comments").unwrap();
+        assert_eq!(normalizer.normalize(&url).as_str(), "https://example.com/news/harbour?id=3");
+        let url = Url::parse("https://example.com/?utm_medium=social").unwrap();
+        assert_eq!(normalizer.normalize(&url).as_str(), "https://example.com/");
+
+        let normalizer = UrlNormalizer::none().strip_param("ref");
+        let url = Url::parse("https://example.com/news/harbour/?ref=home&utm_source=feed#top").unwrap();
+        assert_eq!(
+            normalizer.normalize(&url).as_str(),
+            "https://example.com/news/harbour/?utm_source=feed#top"
+        );
+
+        // queries without stripped params are kept verbatim
+        let normalizer = UrlNormalizer::default();
+        for url in [
+            "https://example.com/a?x=1;y=2",
+            "https://example.com/a?amp",
+            "https://example.com/a?q=a%20b&r=%FF",
+        ] {
+            let url = Url::parse(url).unwrap();
+            assert_eq!(normalizer.normalize(&url), url);
+        }
+        let url = Url::parse("https://example.com/a?q=a%20b&utm_source=feed&amp&r=%FF").unwrap();
+        assert_eq!(normalizer.normalize(&url).as_str(), "https://example.com/a?q=a%20b&amp&r=%FF");
+    }
+
+    #[test]
+    fn duplicate_canonicals() {
+        let mut dedup = Deduplicator::default();
+        let article = Url::parse("https://example.com/news/harbour").unwrap();
+        let amp = Url::parse("https://example.com/news/harbour/amp").unwrap();
+        let category = Url::parse("https://example.com/local/harbour").unwrap();
+        let canonical = Url::parse("https://example.com/news/harbour/").unwrap();
+
+        assert_eq!(dedup.insert_canonical(&article, None), None);
+        assert_eq!(dedup.insert_canonical(&article, None), None);
+        assert_eq!(dedup.insert_canonical(&amp, Some(&canonical)), Some(article.clone()));
+        assert_eq!(dedup.insert_canonical(&category, Some(&canonical)), Some(article));
+        assert_eq!(dedup.stats().duplicate_canonicals, 2);
+    }
+
+    #[test]
+    fn near_duplicate_texts() {
+        let text = "The city council approved the expansion of the harbour on Tuesday. \
+            Construction is expected to start next spring and to last for three years, \
+            the mayor said after the vote. Local businesses welcomed the decision.";
+        let updated = text.replace("three years", "four years");
+        let other = "The national team lost the final after extra time. \
+            The coach praised the players for their effort during the whole tournament.";
+
+        let mut dedup = Deduplicator::default();
+        let first = Url::parse("https://example.com/a").unwrap();
+        let second = Url::parse("https://example.com/b").unwrap();
+        assert_eq!(dedup.insert_content(&first, text), None);
+        assert_eq!(dedup.insert_content(&second, text), None);
+
+        let mut dedup = dedup.content_fingerprints(true);
+        assert_eq!(dedup.insert_content(&first, text), None);
+        assert_eq!(dedup.insert_content(&second, text), Some(first.clone()));
+        assert_eq!(dedup.insert_content(&second, &updated), Some(first));
+        assert_eq!(dedup.insert_content(&second, other), None);
+        assert_eq!(dedup.stats().duplicate_contents, 2);
+        assert_eq!(simhash("  "), None);
+    }
+}
@@ -0,0 +1,263 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use select::node::Node;
+use select::predicate::{Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::text::normalize_whitespace;
+
+/// Minimum number of speaker changes before a sequence of paragraphs is
+/// considered an interview, so articles that merely quote a single exchange
+/// aren't detected.
+pub const MIN_DIALOGUE_ALTERNATIONS: usize = 3;
+
+/// Max number of chars of a speaker label like `Q:` or `Jane Zhu:`.
+const MAX_SPEAKER_LEN: usize = 40;
+
+lazy_static! {
+    /// Speaker labels at the start of a paragraph that aren't highlighted.
+    static ref RE_SPEAKER_PREFIX: Regex =
+        Regex::new(r"(?i)^(Q|A|В|О|Question|Answer|Вопрос|Ответ)\s*:\s*").unwrap();
+}
+
+/// A single turn of an interview or Q&A.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Turn {
+    /// The labeled speaker, e.g. `Q` or the name of the interviewee.
+    pub speaker: Option<String>,
+    /// What was said.
+    pub text: String,
+}
+
+impl Turn {
+    fn new(speaker: Option<String>, text: String) -> Self {
+        Self { speaker, text }
+    }
+}
+
+/// How a paragraph of a potential dialogue is formatted.
+enum Paragraph {
+    /// Starts with a dash: `— Question?`.
+    Dash(String),
+    /// Starts with a speaker label: `<strong>Q:</strong> Question?`.
+    Labeled(String, String),
+    /// The whole paragraph is bold: `<strong>Question?</strong>`.
+    BoldQuestion(String),
+    /// Regular paragraph.
+    Plain(String),
+}
+
+fn classify(para: &Node) -> Option<Paragraph> {
+    let text = normalize_whitespace(&para.text());
+    if text.is_empty() {
+        return None;
+    }
+    if let Some(rest) = text.strip_prefix(['—', '–']).or_else(|| text.strip_prefix("- ")) {
+        return Some(Paragraph::Dash(rest.trim().to_string()));
+    }
+
+    let first = para.children().find(|c| {
+        c.name().is_some() || c.as_text().map(|t| !t.trim().is_empty()).unwrap_or(false)
+    });
+    if let Some(first) = first.filter(|n| Name("strong").or(Name("b")).matches(n)) {
+        let label = normalize_whitespace(&first.text());
+        if label.ends_with(':') && label.chars().count() <= MAX_SPEAKER_LEN {
+            let speaker = label.trim_end_matches(':').trim().to_string();
+            let rest = text.strip_prefix(label.as_str()).unwrap_or(&text).trim();
+            return Some(Paragraph::Labeled(speaker, rest.to_string()));
+        }
+        let bold_len: usize = para
+            .find(Name("strong").or(Name("b")))
+            .map(|n| normalize_whitespace(&n.text()).chars().count())
+            .sum();
+        if bold_len * 10 >= text.chars().count() * 9 {
+            return Some(Paragraph::BoldQuestion(text));
+        }
+    }
+
+    if let Some(label) = RE_SPEAKER_PREFIX.captures(&text) {
+        let speaker = label[1].to_string();
+        let rest = text[label.get(0).unwrap().end()..].to_string();
+        return Some(Paragraph::Labeled(speaker, rest));
+    }
+    Some(Paragraph::Plain(text))
+}
+
+/// Detect question/answer and dash-dialogue formatting in the `paragraphs`.
+///
+/// Returns an empty vec unless there are at least
+/// [`MIN_DIALOGUE_ALTERNATIONS`] changes of the speaker. The speaker of an
+/// unlabeled turn is the interviewer if it's a question, so a list of
+/// dashed items without questions is no dialogue.
+pub fn detect_dialogue(paragraphs: &[Node]) -> Vec<Turn> {
+    let paragraphs: Vec<_> = paragraphs.iter().filter_map(classify).collect();
+
+    let (mut dashes, mut labels, mut bolds) = (0, 0, 0);
+    for para in &paragraphs {
+        match para {
+            Paragraph::Dash(_) => dashes += 1,
+            Paragraph::Labeled(..) => labels += 1,
+            Paragraph::BoldQuestion(_) => bolds += 1,
+            Paragraph::Plain(_) => {}
+        }
+    }
+    if dashes + labels + bolds == 0 {
+        return Vec::new();
+    }
+
+    let mut turns: Vec<Turn> = Vec::new();
+    // whether the turn is asked, for the alternation of unlabeled turns
+    let mut questions: Vec<bool> = Vec::new();
+    if dashes >= labels && dashes >= bolds {
+        // narrative paragraphs between the turns are not part of the dialogue
+        for para in paragraphs {
+            if let Paragraph::Dash(text) = para {
+                questions.push(text.contains('?'));
+                turns.push(Turn::new(None, text));
+            }
+        }
+    } else if labels >= bolds {
+        for para in paragraphs {
+            match para {
+                Paragraph::Labeled(speaker, text) => turns.push(Turn::new(Some(speaker), text)),
+                Paragraph::Plain(text) => {
+                    // answers spanning multiple paragraphs
+                    if let Some(turn) = turns.last_mut() {
+                        turn.text.push(' ');
+                        turn.text.push_str(&text);
+                    }
+                }
+                _ => {}
+            }
+        }
+    } else {
+        let mut last_was_question = false;
+        for para in paragraphs {
+            match para {
+                Paragraph::BoldQuestion(text) => {
+                    questions.push(true);
+                    turns.push(Turn::new(None, text));
+                    last_was_question = true;
+                }
+                Paragraph::Plain(text) => {
+                    if last_was_question {
+                        questions.push(false);
+                        turns.push(Turn::new(None, text));
+                        last_was_question = false;
+                    } else if let Some(turn) = turns.last_mut() {
+                        turn.text.push(' ');
+                        turn.text.push_str(&text);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let alternations = if questions.is_empty() {
+        turns
+            .windows(2)
+            .filter(|w| {
+                w[0].speaker.as_ref().map(|s| s.to_lowercase())
+                    != w[1].speaker.as_ref().map(|s| s.to_lowercase())
+            })
+            .count()
+    } else {
+        questions.windows(2).filter(|w| w[0] != w[1]).count()
+    };
+    if alternations < MIN_DIALOGUE_ALTERNATIONS {
+        return Vec::new();
+    }
+    turns
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+
+    use crate::text::ArticleTextNode;
+
+    use super::*;
+
+    fn body_node(doc: &Document) -> ArticleTextNode<'_> {
+        ArticleTextNode::new(doc.find(Name("body")).next().unwrap())
+    }
+
+    #[test]
+    fn russian_dash_interview() {
+        let doc = Document::from(
+            r#"<html><body>
+            <p>Мы поговорили с директором театра о новом сезоне.</p>
+            <p>— Каким будет новый сезон?</p>
+            <p>— Очень насыщенным, у нас пять премьер.</p>
+            <p>— Кто будет ставить спектакли?</p>
+            <p>— Молодые режиссёры из разных городов.</p>
+            </body></html>"#,
+        );
+        let node = body_node(&doc);
+        let dialogue = node.dialogue();
+        assert_eq!(dialogue.len(), 4);
+        assert_eq!(dialogue[0], Turn::new(None, "Каким будет новый сезон?".to_string()));
+        assert_eq!(
+            dialogue[3].text,
+            "Молодые режиссёры из разных городов."
+        );
+        assert_eq!(node.clean_text().lines().count(), 5);
+    }
+
+    #[test]
+    fn english_bold_qa() {
+        let doc = Document::from(
+            r#"<html><body>
+            <p>We talked to the author about her new novel.</p>
+            <p><strong>Q:</strong> What inspired the book?</p>
+            <p><strong>A:</strong> My grandmother's letters.</p>
+            <p>They were hidden in the attic for decades.</p>
+            <p><strong>Q:</strong> How long did it take to write?</p>
+            <p><strong>A:</strong> Almost seven years.</p>
+            </body></html>"#,
+        );
+        let dialogue = body_node(&doc).dialogue();
+        assert_eq!(dialogue.len(), 4);
+        assert_eq!(dialogue[0].speaker.as_deref(), Some("Q"));
+        assert_eq!(dialogue[0].text, "What inspired the book?");
+        assert_eq!(
+            dialogue[1].text,
+            "My grandmother's letters. They were hidden in the attic for decades."
+        );
+        assert_eq!(dialogue[3].speaker.as_deref(), Some("A"));
+    }
+
+    #[test]
+    fn dashed_list_is_no_dialogue() {
+        let doc = Document::from(
+            r#"<html><body>
+            <p>The council approved the following measures:</p>
+            <p>— a new quay for container ships,</p>
+            <p>— a second ferry line to the island,</p>
+            <p>— a cycle path along the harbour,</p>
+            <p>— longer opening hours of the port office.</p>
+            </body></html>"#,
+        );
+        let node = body_node(&doc);
+        assert!(node.dialogue().is_empty());
+        assert_eq!(node.clean_text().lines().count(), 1);
+    }
+
+    #[test]
+    fn single_exchange_is_no_dialogue() {
+        let doc = Document::from(
+            r#"<html><body>
+            <p>The minister was asked about the budget on Tuesday.</p>
+            <p>— Will taxes rise?</p>
+            <p>— No, they will not.</p>
+            <p>The opposition criticized the answer as evasive.</p>
+            </body></html>"#,
+        );
+        let node = body_node(&doc);
+        assert!(node.dialogue().is_empty());
+        assert_eq!(node.clean_text().lines().count(), 1);
+    }
+}
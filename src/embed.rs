@@ -0,0 +1,225 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use url::Url;
+use select::node::Node;
+use select::predicate::{Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    /// The handle in the credit line of an embed, like `(@jane_doe)`.
+    static ref RE_HANDLE: Regex = Regex::new(r"\(@([A-Za-z0-9_.]+)\)").unwrap();
+}
+
+/// The social network of an embedded post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum Platform {
+    /// Twitter or X.
+    Twitter,
+    Instagram,
+    TikTok,
+    Reddit,
+    Bluesky,
+}
+
+impl Platform {
+    /// The platform of an embed `<blockquote>` with the `class`, like
+    /// `twitter-tweet` or `instagram-media`.
+    pub fn from_class(class: &str) -> Option<Self> {
+        class.split_whitespace().find_map(|token| match token.to_lowercase().as_str() {
+            "twitter-tweet" | "twitter-video" => Some(Platform::Twitter),
+            "instagram-media" => Some(Platform::Instagram),
+            "tiktok-embed" => Some(Platform::TikTok),
+            "reddit-card" | "reddit-embed-bq" => Some(Platform::Reddit),
+            "bluesky-embed" => Some(Platform::Bluesky),
+            _ => None,
+        })
+    }
+
+    /// The platform that hosts the `url`.
+    pub fn from_url(url: &Url) -> Option<Self> {
+        let host = url.host_str()?.to_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+        let host = host.strip_prefix("mobile.").unwrap_or(host);
+        match host {
+            "twitter.com" | "x.com" => Some(Platform::Twitter),
+            "instagram.com" | "instagr.am" => Some(Platform::Instagram),
+            "tiktok.com" => Some(Platform::TikTok),
+            "reddit.com" | "old.reddit.com" => Some(Platform::Reddit),
+            "bsky.app" => Some(Platform::Bluesky),
+            _ => None,
+        }
+    }
+}
+
+/// A post of a social network embedded in the article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct SocialEmbed {
+    pub platform: Platform,
+    /// The permalink of the post, without query.
+    pub url: Url,
+    /// The handle of the author, like `@jane_doe`.
+    pub author_handle: Option<String>,
+    /// The text of the post, if the embed contains it.
+    pub text: Option<String>,
+}
+
+/// Whether the node is the `<blockquote>` of an embedded post.
+pub fn is_social_embed(node: &Node) -> bool {
+    Name("blockquote").matches(node) && node.attr("class").and_then(Platform::from_class).is_some()
+}
+
+/// All embedded posts within the `node`, in document order.
+///
+/// Embeds are the `<blockquote>` elements the embed scripts of the social
+/// networks replace, recognized by their class. The platform is taken from
+/// the host of the permalink, or the class if the host is unknown. Embeds
+/// without a permalink are skipped.
+///
+/// Relative urls are resolved against the `base_url`.
+pub fn embeds(node: &Node, base_url: Option<&Url>) -> Vec<SocialEmbed> {
+    node.find(is_social_embed)
+        .filter_map(|embed| social_embed(&embed, base_url))
+        .collect()
+}
+
+fn social_embed(embed: &Node, base_url: Option<&Url>) -> Option<SocialEmbed> {
+    let class_platform = embed.attr("class").and_then(Platform::from_class)?;
+    let options = Url::options().base_url(base_url);
+    let links: Vec<Url> = embed
+        .find(Name("a"))
+        .filter_map(|a| a.attr("href"))
+        .filter_map(|href| options.parse(href.trim()).ok())
+        .collect();
+    let mut url = embed
+        .attr("data-instgrm-permalink")
+        .or_else(|| embed.attr("cite"))
+        .and_then(|href| options.parse(href.trim()).ok())
+        .or_else(|| links.iter().rev().find(|url| is_post_url(url)).cloned())
+        .or_else(|| {
+            links
+                .iter()
+                .rev()
+                .find(|url| Platform::from_url(url) == Some(class_platform))
+                .cloned()
+        })?;
+    url.set_query(None);
+    url.set_fragment(None);
+    let platform = Platform::from_url(&url).unwrap_or(class_platform);
+
+    let author_handle = handle_from_url(&url, platform)
+        .or_else(|| {
+            RE_HANDLE
+                .captures(&embed.text())
+                .map(|caps| format!("@{}", &caps[1]))
+        });
+    let text = embed
+        .find(Name("p"))
+        .filter(|p| {
+            let link_len: usize = p.find(Name("a")).map(|a| a.text().trim().len()).sum();
+            p.text().trim().len() > link_len
+        })
+        .map(|p| p.text().split_whitespace().collect::<Vec<_>>().join(" "))
+        .find(|text| !text.is_empty());
+    Some(SocialEmbed {
+        platform,
+        url,
+        author_handle,
+        text,
+    })
+}
+
+/// Whether the url is the permalink of a single post.
+fn is_post_url(url: &Url) -> bool {
+    let path = url.path();
+    match Platform::from_url(url) {
+        Some(Platform::Twitter) => path.contains("/status/"),
+        Some(Platform::Instagram) => path.starts_with("/p/") || path.starts_with("/reel/"),
+        Some(Platform::TikTok) => path.contains("/video/"),
+        Some(Platform::Reddit) => path.contains("/comments/"),
+        Some(Platform::Bluesky) => path.contains("/post/"),
+        None => false,
+    }
+}
+
+/// The handle of the author in the path of the permalink, if the platform
+/// puts it there.
+fn handle_from_url(url: &Url, platform: Platform) -> Option<String> {
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    let handle = match platform {
+        Platform::Twitter => segments
+            .first()
+            .filter(|segment| !matches!(**segment, "i" | "intent" | "hashtag"))
+            .map(|segment| segment.trim_start_matches('@')),
+        Platform::TikTok => segments
+            .iter()
+            .find_map(|segment| segment.strip_prefix('@')),
+        Platform::Bluesky => segments
+            .iter()
+            .position(|segment| *segment == "profile")
+            .and_then(|idx| segments.get(idx + 1).copied()),
+        Platform::Reddit => segments
+            .iter()
+            .position(|segment| *segment == "user" || *segment == "u")
+            .and_then(|idx| segments.get(idx + 1).copied()),
+        Platform::Instagram => None,
+    }?;
+    Some(format!("@{}", handle))
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+
+    use crate::text::ArticleTextNode;
+
+    use super::*;
+
+    #[test]
+    fn social_embeds() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>The council approved the expansion of the harbour on Tuesday.</p>
+            <blockquote class="twitter-tweet"><p lang="en" dir="ltr">Big news for the harbour! <a href="https://t.co/abc">pic.twitter.com/abc</a></p>
+            &mdash; City of Example (@cityofexample) <a href="https://twitter.com/cityofexample/status/123?ref_src=twsrc%5Etfw">May 14, 2024</a></blockquote>
+            <blockquote class="instagram-media" data-instgrm-permalink="https://www.instagram.com/p/Cx12/?utm_source=ig_embed">
+            <p><a href="https://www.instagram.com/p/Cx12/?utm_source=ig_embed">View this post on Instagram</a></p>
+            <p><a href="https://www.instagram.com/p/Cx12/">A post shared by Port Authority (@port.authority)</a></p></blockquote>
+            <blockquote class="tiktok-embed" cite="https://www.tiktok.com/@harbourlife/video/7312" data-video-id="7312">
+            <section><p>Sunset at the new pier</p></section></blockquote>
+            <blockquote class="twitter-tweet"><p>Deleted</p></blockquote>
+            </article></body></html>"#,
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        let embeds = embeds(&article, None);
+        assert_eq!(
+            embeds,
+            vec![
+                SocialEmbed {
+                    platform: Platform::Twitter,
+                    url: Url::parse("https://twitter.com/cityofexample/status/123").unwrap(),
+                    author_handle: Some("@cityofexample".to_string()),
+                    text: Some("Big news for the harbour! pic.twitter.com/abc".to_string()),
+                },
+                SocialEmbed {
+                    platform: Platform::Instagram,
+                    url: Url::parse("https://www.instagram.com/p/Cx12/").unwrap(),
+                    author_handle: Some("@port.authority".to_string()),
+                    text: None,
+                },
+                SocialEmbed {
+                    platform: Platform::TikTok,
+                    url: Url::parse("https://www.tiktok.com/@harbourlife/video/7312").unwrap(),
+                    author_handle: Some("@harbourlife".to_string()),
+                    text: Some("Sunset at the new pier".to_string()),
+                },
+            ]
+        );
+
+        let text = ArticleTextNode::new(article).clean_text();
+        assert!(text.contains("approved the expansion"));
+        assert!(!text.contains("Big news"));
+    }
+}
@@ -1,22 +1,72 @@
+use std::time::Duration;
+
 use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use reqwest::{StatusCode, Url};
 use thiserror::Error;
 
 use crate::article::PureArticle;
+use crate::completeness::Incompleteness;
+use crate::stats::DocumentStats;
+
+/// Max number of bytes of an error response body that are kept in
+/// [`ExtrablattError::HttpStatus`].
+pub const MAX_BODY_SNIPPET_LEN: usize = 4096;
+
+/// A non success response, see [`ExtrablattError::HttpStatus`].
+#[derive(Debug, Clone)]
+pub struct ErrorResponse {
+    /// The url of the response.
+    pub url: Url,
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The headers of the response.
+    pub headers: HeaderMap,
+    /// The start of the response body, at most [`MAX_BODY_SNIPPET_LEN`]
+    /// bytes, decoded lossily.
+    pub body_snippet: String,
+}
 
 /// All different error types this crate uses.
 #[derive(Error, Debug)]
 pub enum ExtrablattError {
-    /// Received a good non success Http response
-    #[error("Expected a 2xx Success but got: {}", response.status())]
-    NoHttpSuccessResponse {
-        /// The good reqwest response.
-        response: reqwest::Response,
+    /// Received a non success Http response.
+    #[error(
+        "Expected a 2xx Success for {} but got: {} after {attempts} attempt(s)",
+        response.url,
+        response.status
+    )]
+    HttpStatus {
+        /// The response, boxed to keep the error small.
+        response: Box<ErrorResponse>,
+        /// Number of requests sent, including retries.
+        attempts: usize,
     },
     /// Failed to get a response.
-    #[error("Request failed: {error}")]
+    #[error("Request failed after {attempts} attempt(s): {error}")]
     HttpRequestFailure {
         /// The reqwest error.
         error: reqwest::Error,
+        /// Number of requests sent, including retries.
+        attempts: usize,
+    },
+    /// Followed more redirects than allowed, see
+    /// [`crate::ExtrablattBuilder::max_redirects`].
+    #[error("Exceeded the redirect limit at {url}")]
+    TooManyRedirects {
+        /// The last url of the redirect chain.
+        url: Url,
+        /// The reqwest error.
+        error: reqwest::Error,
+    },
+    /// A meta refresh of an article page wasn't followed, because it leads
+    /// back to a page of the chain or exceeds
+    /// [`crate::extract_meta_refresh::MAX_META_REFRESH_HOPS`], see
+    /// [`crate::ExtrablattBuilder::follow_meta_refresh`].
+    #[error("Stopped following the meta refreshes at {url}")]
+    TooManyMetaRefreshes {
+        /// The url of the stub page with the refresh.
+        url: Url,
     },
     /// Failed to read a document.
     #[error("Failed to read document")]
@@ -26,10 +76,39 @@ pub enum ExtrablattError {
     },
     /// Identified an article, but it's content doesn't fulfill the configured
     /// requirements.
-    #[error("Found incomplete Article for {}", article.url)]
+    #[error("Found incomplete Article for {}: {incompleteness}", article.url)]
     IncompleteArticle {
         /// The found article and its content.
         article: Box<PureArticle>,
+        /// The criteria the article failed.
+        incompleteness: Incompleteness,
+    },
+    /// The document was skipped, because its [`DocumentStats`] don't look
+    /// like an article.
+    #[error("Skipped {url}, the document doesn't look like an article")]
+    SkippedDocument {
+        /// The url of the document.
+        url: reqwest::Url,
+        /// The statistics of the skipped document.
+        stats: Box<DocumentStats>,
+    },
+    /// The article was skipped, because it duplicates an article processed
+    /// before, by its canonical url or its text.
+    #[error("Skipped {url}, duplicate of {original}")]
+    DuplicateArticle {
+        /// The url of the skipped article.
+        url: Url,
+        /// The url of the article processed before, boxed to keep the error
+        /// small.
+        original: Box<Url>,
+    },
+    /// Failed to read or write the state of a [`crate::monitor`].
+    #[error("Failed to access the monitor state at {path:?}: {error}")]
+    MonitorState {
+        /// The path of the state file.
+        path: std::path::PathBuf,
+        /// The io error.
+        error: std::io::Error,
     },
     /// The base URL was not initialized.
     #[error("Url of the article must be initialized.")]
@@ -40,8 +119,11 @@ pub enum ExtrablattError {
         url: reqwest::Url,
     },
     /// Failed to parse user agent header.
-    #[error("Failed to parse user agent header")]
-    UserAgentParseError,
+    #[error("Failed to parse user agent header {user_agent:?}")]
+    UserAgentParseError {
+        /// The invalid user agent.
+        user_agent: String,
+    },
     /// Error from reqwest.
     #[error("Reqwest error: {0}")]
     Reqwest(#[source] reqwest::Error),
@@ -50,4 +132,133 @@ pub enum ExtrablattError {
     UrlParseError {
         error: reqwest::Error,
     },
+    /// An option of the [`crate::ExtrablattBuilder`] was set that the
+    /// [`crate::blocking::Extrablatt`] doesn't support.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    #[error("The blocking api doesn't support the `{option}` option")]
+    UnsupportedBlockingOption {
+        option: &'static str,
+    },
+    /// A host of the [`crate::ExtrablattBuilder::cookies_for`] isn't valid.
+    #[error("Invalid cookie host: {0}")]
+    InvalidCookieHost(#[source] url::ParseError),
+    /// The name or value of a cookie of
+    /// [`crate::ExtrablattBuilder::cookies_for`] has a character a cookie
+    /// can't have, like a `;`.
+    #[error("Invalid cookie: {0}")]
+    InvalidCookie(String),
+}
+
+impl ExtrablattError {
+    /// The error for a request that failed after `attempts` attempts, a
+    /// [`ExtrablattError::TooManyRedirects`] if it exceeded the redirect
+    /// limit.
+    pub(crate) fn request_failure(error: reqwest::Error, attempts: usize) -> Self {
+        match error.url() {
+            Some(url) if error.is_redirect() => Self::TooManyRedirects {
+                url: url.clone(),
+                error,
+            },
+            _ => Self::HttpRequestFailure { error, attempts },
+        }
+    }
+
+    /// The error for a non success `response`, reading at most
+    /// [`MAX_BODY_SNIPPET_LEN`] bytes of its body.
+    pub(crate) async fn http_status(mut response: reqwest::Response, attempts: usize) -> Self {
+        let mut body = Vec::new();
+        while body.len() < MAX_BODY_SNIPPET_LEN {
+            match response.chunk().await {
+                Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+                _ => break,
+            }
+        }
+        Self::HttpStatus {
+            response: Box::new(ErrorResponse {
+                url: response.url().clone(),
+                status: response.status(),
+                headers: response.headers().clone(),
+                body_snippet: body_snippet(&body),
+            }),
+            attempts,
+        }
+    }
+
+    /// The error for a non success `response` to a blocking request, reading
+    /// at most [`MAX_BODY_SNIPPET_LEN`] bytes of its body.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub(crate) fn http_status_blocking(response: reqwest::blocking::Response, attempts: usize) -> Self {
+        use std::io::Read;
+
+        let url = response.url().clone();
+        let status = response.status();
+        let headers = response.headers().clone();
+        let mut body = Vec::new();
+        let _ = response.take(MAX_BODY_SNIPPET_LEN as u64).read_to_end(&mut body);
+        Self::HttpStatus {
+            response: Box::new(ErrorResponse {
+                url,
+                status,
+                headers,
+                body_snippet: body_snippet(&body),
+            }),
+            attempts,
+        }
+    }
+
+    /// The status of a non success response.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ExtrablattError::HttpStatus { response, .. } => Some(response.status),
+            _ => None,
+        }
+    }
+
+    /// The delay a non success response asked for with its `Retry-After`
+    /// header.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ExtrablattError::HttpStatus { response, .. } => crate::retry::retry_after(&response.headers),
+            _ => None,
+        }
+    }
+}
+
+/// The first [`MAX_BODY_SNIPPET_LEN`] bytes of the `body`, without a char
+/// that was cut in half.
+fn body_snippet(body: &[u8]) -> String {
+    let body = &body[..body.len().min(MAX_BODY_SNIPPET_LEN)];
+    let snippet = String::from_utf8_lossy(body);
+    snippet.trim_end_matches('\u{fffd}').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::{HeaderValue, RETRY_AFTER};
+
+    use super::*;
+
+    #[test]
+    fn status_error() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        let err = ExtrablattError::HttpStatus {
+            response: Box::new(ErrorResponse {
+                url: Url::parse("https://example.com/news").unwrap(),
+                status: StatusCode::SERVICE_UNAVAILABLE,
+                headers,
+                body_snippet: body_snippet("ä".repeat(MAX_BODY_SNIPPET_LEN).as_bytes()),
+            }),
+            attempts: 1,
+        };
+        assert_eq!(err.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(120)));
+        assert_eq!(
+            err.to_string(),
+            "Expected a 2xx Success for https://example.com/news but got: 503 Service Unavailable after 1 attempt(s)"
+        );
+        if let ExtrablattError::HttpStatus { response, .. } = err {
+            assert_eq!(response.body_snippet, "ä".repeat(MAX_BODY_SNIPPET_LEN / 2));
+        }
+    }
 }
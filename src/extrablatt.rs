@@ -1,26 +1,49 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io;
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::time::Duration;
 
 use bytes::Bytes;
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use futures::stream::{self, Stream};
 use futures::task::Poll;
-use futures::{Future, FutureExt, StreamExt, TryFutureExt};
-use reqwest::header::HeaderMap;
-#[cfg(not(target_arch = "wasm32"))]
-use reqwest::header::USER_AGENT;
-use reqwest::Response;
+use futures::{Future, StreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::{Response, StatusCode};
 use reqwest::{Client, IntoUrl, Url};
 use select::document::Document;
 use wasm_timer::Instant;
 
+use crate::clean::ExtractionFilters;
+use crate::completeness::{word_count, Completeness, Criterion, Incompleteness, Measure};
+#[cfg(feature = "cookies")]
+use crate::cookies::{CookieError, Cookies};
 use crate::error::ExtrablattError;
 
-use crate::article::{Article, ArticleContent, ArticleUrl, PureArticle};
+use crate::article::{Article, ArticleContent, ArticleUrl, FragmentOptions, KnownFields, PureArticle};
 use crate::extract::{DefaultExtractor, Extractor};
-use crate::language::Language;
-use crate::text::ArticleTextNodeExtractor;
+use crate::extract_amp::amp_url;
+use crate::extract_meta_refresh::{can_follow_meta_refresh, meta_refresh_url};
+use crate::extract_canonical::CanonicalLink;
+use crate::extract_feeds::{feed_items, FeedLink};
+use crate::fetch_info::FetchInfo;
+use crate::charset::{content_type, decode};
+use crate::crawl_state::{url_hash, CrawlState};
+use crate::dedup::{DedupStats, Deduplicator, UrlNormalizer};
+use crate::http_cache::{CachedEntry, HttpCache};
+use crate::image::ImageRegistry;
+use crate::language::{Language, LanguageOverrides};
+use crate::retry::RetryPolicy;
+use crate::sink::ArticleSink;
+use crate::stats::DocumentStats;
+use crate::throttle::Throttle;
+use crate::trace;
+use crate::url_class::{ArticleUrlFilter, UrlClass, UrlClassStats};
+use crate::user_agent::{user_agent_str, Rotation, UserAgentPool};
+use crate::category::UrlFilter;
 use crate::Category;
 
 /// Caches article downloads
@@ -30,6 +53,8 @@ pub struct Extrablatt<TExtractor: Extractor = DefaultExtractor> {
     client: Client,
     /// The expected language of this newspaper.
     language: Language,
+    /// Languages of articles that differ from the language of the newspaper.
+    languages: LanguageOverrides,
     /// The parsed main page.
     main_page: Document,
     /// Url of the main page.
@@ -38,12 +63,31 @@ pub struct Extrablatt<TExtractor: Extractor = DefaultExtractor> {
     ///
     /// Default is [`crate::DefaultExtractor`].
     extractor: TExtractor,
-    /// Cache for retrieved articles.
-    articles: FnvHashMap<ArticleUrl, DocumentDownloadState>,
-    /// All known categories for this newspaper.
-    categories: FnvHashMap<Category, DocumentDownloadState>,
+    /// The categories and articles of the crawl and their download state.
+    queue: CrawlQueue,
+    /// Articles built from the full html of feed items.
+    feed_articles: FnvHashMap<ArticleUrl, Article>,
     /// Configuration for article extraction.
     config: Config,
+    /// When to repeat failed downloads.
+    retry_policy: RetryPolicy,
+    /// Occurrences of the images across the crawled articles.
+    image_registry: ImageRegistry,
+    /// Limits the requests in flight and spaces requests to the same host.
+    throttle: Throttle,
+    /// Pages of previous downloads, for conditional requests.
+    http_cache: Option<Arc<dyn HttpCache>>,
+    /// Whether articles are extracted from their AMP version.
+    prefer_amp: bool,
+    /// Whether the meta refresh of stub pages of articles is followed.
+    follow_meta_refresh: bool,
+    /// Detects articles that are reachable via several urls.
+    dedup: Deduplicator,
+    /// User agents rotated across requests.
+    user_agents: Arc<UserAgentPool>,
+    /// The [`url_hash`]es of the downloaded articles and categories, shared
+    /// with the snapshots of [`Extrablatt::save_state`].
+    visited: Arc<FnvHashSet<u64>>,
 }
 
 impl Extrablatt<DefaultExtractor> {
@@ -73,38 +117,224 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         &mut self.config
     }
 
+    /// The policy for repeating failed downloads.
+    #[inline]
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// The limits for the requests of this crawl.
+    #[inline]
+    pub fn throttle(&self) -> &Throttle {
+        &self.throttle
+    }
+
+    /// The cache for conditional requests, if any.
+    #[inline]
+    pub fn http_cache(&self) -> Option<&Arc<dyn HttpCache>> {
+        self.http_cache.as_ref()
+    }
+
+    /// Occurrences of the images across all articles of this crawl.
+    #[inline]
+    pub fn image_registry(&self) -> &ImageRegistry {
+        &self.image_registry
+    }
+
+    /// Detects articles that are reachable via several urls.
+    #[inline]
+    pub fn dedup(&self) -> &Deduplicator {
+        &self.dedup
+    }
+
+    /// Number of duplicate urls and articles skipped so far.
+    #[inline]
+    pub fn dedup_stats(&self) -> &DedupStats {
+        self.dedup.stats()
+    }
+
+    /// Number of article urls queued and rejected so far per class, see
+    /// [`ExtrablattBuilder::article_url_filter`].
+    #[inline]
+    pub fn url_class_stats(&self) -> &UrlClassStats {
+        self.queue.url_class_stats()
+    }
+
+    /// Register the images of the article in the [`ImageRegistry`] and
+    /// remove the images that are considered site furniture, if
+    /// [`Config::furniture_image_pct`] is set.
+    pub fn register_images(&mut self, content: &mut ArticleContent<'_>) {
+        self.image_registry.register(
+            content
+                .images
+                .iter()
+                .chain(content.top_image.as_ref())
+                .chain(content.thumbnail.as_ref()),
+        );
+        if let Some(max_pct) = self.config.furniture_image_pct {
+            self.image_registry.remove_furniture(content, max_pct);
+        }
+    }
+
     /// The extractor used to retrieve content for an article.
     #[inline]
     pub fn extractor(&self) -> &TExtractor {
         &self.extractor
     }
 
+    /// Url of the main page.
+    #[inline]
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// The most recently downloaded main page.
+    #[inline]
+    pub fn main_page(&self) -> &Document {
+        &self.main_page
+    }
+
     /// All available categories.
     #[inline]
     pub fn categories(&self) -> &FnvHashMap<Category, DocumentDownloadState> {
-        &self.categories
+        self.queue.categories()
     }
 
     /// All available articles.
     #[inline]
     pub fn articles(&self) -> &FnvHashMap<ArticleUrl, DocumentDownloadState> {
-        &self.articles
+        self.queue.articles()
+    }
+
+    /// All articles built from the full html of feed items, see
+    /// [`Extrablatt::download_feeds`].
+    #[inline]
+    pub fn feed_articles(&self) -> &FnvHashMap<ArticleUrl, Article> {
+        &self.feed_articles
     }
 
     /// Clear all cached articles and categories.
     #[inline]
     pub fn clear(&mut self) {
-        self.articles.clear();
-        self.categories.clear()
+        self.queue.articles.clear();
+        self.feed_articles.clear();
+        self.queue.categories.clear()
+    }
+
+    /// Snapshot the progress of the crawl: the articles and categories that
+    /// haven't been downloaded yet, the downloaded urls and the delays of
+    /// the hosts.
+    ///
+    /// Continue the crawl with [`ExtrablattBuilder::resume_from`]. The
+    /// downloaded urls aren't copied, they are shared with the crawl until
+    /// it downloads the next page.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use extrablatt_v2::Extrablatt;
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut site = Extrablatt::builder("https://cnn.com/")?.build().await?;
+    ///     site.download_all_remaining_categories().await;
+    ///     let articles: Vec<_> = site.article_stream().take(100).collect().await;
+    ///     let state = site.save_state();
+    ///
+    ///     let mut site = Extrablatt::builder("https://cnn.com/")?
+    ///         .resume_from(state)
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn save_state(&self) -> CrawlState {
+        CrawlState::new(
+            self.queue
+                .articles
+                .iter()
+                .filter(|(_, state)| !state.is_success())
+                .map(|(article, _)| article.clone())
+                .collect(),
+            self.queue
+                .categories
+                .iter()
+                .filter(|(_, state)| !state.is_success())
+                .map(|(category, _)| category.clone())
+                .collect(),
+            Arc::clone(&self.visited),
+            self.throttle.pending_delays(),
+        )
+    }
+
+    /// Whether the page at the `url` was downloaded, in this crawl or one it
+    /// resumes.
+    fn is_visited(&self, url: &Url) -> bool {
+        self.visited.contains(&url_hash(url))
+    }
+
+    fn mark_visited(&mut self, url: &Url) {
+        Arc::make_mut(&mut self.visited).insert(url_hash(url));
+    }
+
+    /// How the pages of the articles are downloaded.
+    fn article_download_options(&self) -> ArticleDownloadOptions {
+        ArticleDownloadOptions {
+            prefer_amp: self.prefer_amp,
+            follow_meta_refresh: self.follow_meta_refresh,
+            http_success_only: self.config.http_success_only,
+        }
     }
 
-    /// Insert all categories extracted from the main page.
+    /// Insert all categories extracted from the main page that pass the
+    /// category filter and weren't downloaded yet.
     fn insert_new_categories(&mut self) {
         for category in self.extractor.categories(&self.main_page, &self.base_url) {
-            self.categories
-                .entry(category)
-                .or_insert(DocumentDownloadState::NotRequested);
+            if !self.is_wanted_url(&category.url) || self.is_visited(&category.url) {
+                continue;
+            }
+            self.queue.insert_category(category);
+        }
+    }
+
+    /// Queue the normalized url of the article, if it passes the category
+    /// filter, isn't rejected by the article url filter and isn't queued or
+    /// downloaded yet.
+    fn queue_article(&mut self, mut article: ArticleUrl) {
+        if !self.is_wanted_url(&article.url) {
+            return;
         }
+        article.url = self.dedup.normalize(&article.url);
+        if self.queue.articles.contains_key(&article.url)
+            || self.feed_articles.contains_key(&article.url)
+            || self.is_visited(&article.url)
+        {
+            self.dedup.count_duplicate_url();
+            return;
+        }
+        self.queue.insert_article(article);
+    }
+
+    /// The url of the article processed before that the article downloaded
+    /// from `url` duplicates, by its `canonical` link or, if
+    /// [`ExtrablattBuilder::dedup_content`] is enabled, its `text`.
+    ///
+    /// The canonical link is resolved against the final url the document was
+    /// served from after redirects, a cross domain canonical is ignored.
+    /// Records the article otherwise.
+    fn find_duplicate(&mut self, url: &Url, canonical: Option<&CanonicalLink>, text: Option<&str>) -> Option<Url> {
+        let canonical = canonical
+            .filter(|canonical| !canonical.cross_domain)
+            .map(|canonical| &canonical.url);
+        self.dedup
+            .insert_canonical(url, canonical)
+            .or_else(|| self.dedup.insert_content(url, text?))
+    }
+
+    /// Whether the category or article url found in a category passes the
+    /// category filter.
+    pub(crate) fn is_wanted_url(&self, url: &Url) -> bool {
+        self.queue.is_wanted_url(url)
     }
 
     /// Download and store all outstanding articles and returns an iterator over
@@ -127,72 +357,185 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
     /// # }
     /// ```
     pub async fn download_articles(&mut self) -> ArticleDownloadIter<'_, TExtractor> {
+        let (client, retry_policy, throttle) = (&self.client, &self.retry_policy, &self.throttle);
+        let (http_cache, options) = (self.http_cache.as_deref(), self.article_download_options());
+        let user_agents = &self.user_agents;
         let results = stream::iter(
-            self.articles
-                .iter()
-                .filter_map(|(article, state)| {
-                    if state.is_not_requested() {
-                        Some(article.url.clone())
-                    } else {
-                        None
-                    }
-                })
-                .map(|url| {
-                    self.client.get(url.clone()).send().then(|res| async {
-                        (url, DocumentDownloadState::from_response(res).await)
-                    })
+            self.queue.not_requested_article_urls()
+                .into_iter()
+                .map(|url| async move {
+                    let _permit = throttle.acquire(&url).await;
+                    let user_agent = user_agents.next();
+                    let result =
+                        download_article(client, retry_policy, http_cache, &url, user_agent, options).await;
+                    (url, result)
                 }),
         )
         .buffer_unordered(self.config.concurrency)
         .collect::<Vec<_>>()
         .await;
 
-        for (url, doc) in results {
-            let state = match doc {
-                Ok((doc, received)) => DocumentDownloadState::Success { received, doc },
-                Err((state, err)) => {
-                    if !self.config.http_success_only {
-                        if let Ok((doc, received)) =
-                            DocumentDownloadState::advance_non_http_success(err).await
-                        {
-                            DocumentDownloadState::Success { doc, received }
-                        } else {
-                            state
-                        }
-                    } else {
-                        state
-                    }
+        for (url, page) in results {
+            if let Ok(page) = &page {
+                self.mark_visited(&url);
+                let text = if self.dedup.has_content_fingerprints() {
+                    self.extractor
+                        .text(&page.doc, self.language.clone())
+                        .map(|text| text.into_owned())
+                } else {
+                    None
+                };
+                let canonical = self
+                    .extractor
+                    .canonical_link_detailed(&page.doc, Some(&page.fetch_info.final_url));
+                if let Some(original) = self.find_duplicate(&url, canonical.as_ref(), text.as_deref()) {
+                    log::debug!("Skipped {}, duplicate of {}", url, original);
+                    self.queue.articles.remove(&url);
+                    continue;
                 }
+            }
+            let state = match page {
+                Ok(page) => page.into_state(),
+                Err(err) => DocumentDownloadState::from_error(&err),
             };
 
-            *self.articles.get_mut(&url).unwrap() = state;
+            self.queue.set_article_state(&url, state);
         }
+        self.iter_articles()
+    }
+
+    /// Download all outstanding articles and yield each one as soon as its
+    /// page is fetched and parsed.
+    ///
+    /// Unlike [`Extrablatt::download_articles`] the documents aren't kept:
+    /// streamed articles are removed from the cache and failed downloads
+    /// keep their [`DocumentDownloadState`]. Errors of single urls are
+    /// yielded and don't end the stream. At most [`Config::concurrency`]
+    /// articles are downloaded ahead of the consumer, so a slow consumer
+    /// holds back new downloads.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use extrablatt_v2::Extrablatt;
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let mut site = Extrablatt::builder("https://cnn.com/")?.build().await?;
+    ///     site.download_all_remaining_categories().await;
+    ///     let mut articles = Box::pin(site.article_stream());
+    ///     while let Some(article) = articles.next().await {
+    ///         // ...
+    ///     }
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn article_stream(
+        &mut self,
+    ) -> impl Stream<Item = Result<Article, ExtrablattError>> + '_ {
+        self.article_body_stream().map(|article| article.map(|(article, _)| article))
+    }
+
+    /// Like [`Extrablatt::article_stream`], but yields every downloaded
+    /// article together with the response body it was extracted from.
+    /// Articles of feeds have no body.
+    fn article_body_stream(
+        &mut self,
+    ) -> impl Stream<Item = Result<(Article, Option<Bytes>), ExtrablattError>> + '_ {
+        let urls = self.queue.not_requested_article_urls();
+        let client = self.client.clone();
+        let retry_policy = self.retry_policy.clone();
+        let throttle = self.throttle.clone();
+        let http_cache = self.http_cache.clone();
+        let user_agents = self.user_agents.clone();
+        let options = self.article_download_options();
+        let feed_articles: Vec<_> = self
+            .feed_articles
+            .drain()
+            .map(|(_, article)| Ok((article, None)))
+            .collect();
+
+        let downloads = stream::iter(urls)
+            .map(move |url| {
+                let (client, retry_policy, throttle, http_cache, user_agents) = (
+                    client.clone(),
+                    retry_policy.clone(),
+                    throttle.clone(),
+                    http_cache.clone(),
+                    user_agents.clone(),
+                );
+                async move {
+                    let _permit = throttle.acquire(&url).await;
+                    let user_agent = user_agents.next();
+                    let page = download_article(
+                        &client,
+                        &retry_policy,
+                        http_cache.as_deref(),
+                        &url,
+                        user_agent,
+                        options,
+                    )
+                    .await
+                    .map_err(|err| (DocumentDownloadState::from_error(&err), err));
+                    (url, page)
+                }
+            })
+            .buffer_unordered(self.config.concurrency)
+            .map(move |(url, page)| match page {
+                Ok(page) => {
+                    self.queue.articles.remove(&url);
+                    self.mark_visited(&url);
+                    let body = page.body.clone();
+                    self.article_from_document(url, page)
+                        .map(|article| (article, Some(body)))
+                }
+                Err((state, err)) => {
+                    self.queue.set_article_state(&url, state);
+                    Err(err)
+                }
+            });
+        stream::iter(feed_articles).chain(downloads)
+    }
 
-        ArticleDownloadIter {
-            inner: self.articles.iter(),
-            extractor: &self.extractor,
-            language: self.language.clone(),
-            base_url: &self.base_url,
+    /// Download all outstanding articles and write each one to the `sink` as
+    /// soon as it's extracted, see [`Extrablatt::article_stream`].
+    ///
+    /// Every downloaded article is written with the response body it was
+    /// extracted from, articles of feeds without. Errors of single urls are
+    /// logged and skipped. The sink is finished after the last article.
+    /// Returns the number of written articles, or the first error of the
+    /// sink.
+    pub async fn download_articles_into<S: ArticleSink>(&mut self, mut sink: S) -> io::Result<usize> {
+        let mut written = 0;
+        let mut articles = Box::pin(self.article_body_stream());
+        while let Some(article) = articles.next().await {
+            match article {
+                Ok((article, body)) => {
+                    let article = article.into_pure();
+                    match body {
+                        Some(body) => sink.write_with_body(&article, &body)?,
+                        None => sink.write(&article)?,
+                    }
+                    written += 1;
+                }
+                Err(err) => log::debug!("Skipped article: {}", err),
+            }
         }
+        sink.finish()?;
+        Ok(written)
     }
 
     /// Iterator over all known articles.
     pub fn iter_articles(&self) -> ArticleDownloadIter<'_, TExtractor> {
-        ArticleDownloadIter {
-            inner: self.articles.iter(),
-            extractor: &self.extractor,
-            language: self.language.clone(),
-            base_url: &self.base_url,
-        }
+        self.queue
+            .iter_articles(&self.extractor, self.language.clone(), &self.languages, &self.base_url)
     }
 
     /// For each successfully downloaded category document, insert their article
     /// urls as unrequested.
     fn insert_article_urls(&mut self, doc: &Document) {
         for url in self.extractor.article_urls(doc, Some(&self.base_url)) {
-            self.articles
-                .entry(url)
-                .or_insert(DocumentDownloadState::NotRequested);
+            self.queue_article(url);
         }
     }
 
@@ -205,45 +548,33 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         category: Category,
     ) -> Result<&Document, ExtrablattError> {
         if self
+            .queue
             .categories
             .get(&category)
             .and_then(|s| s.success_document())
             .is_some()
         {
-            return Ok(self.categories[&category].success_document().unwrap());
+            return Ok(self.queue.categories[&category].success_document().unwrap());
         }
 
-        let result = match self.get_document(category.url.clone()).await {
-            Ok((doc, received)) => {
-                self.insert_article_urls(&doc);
-                Ok((doc, received))
-            }
-            Err((state, err)) => {
-                if !self.config.http_success_only {
-                    match DocumentDownloadState::advance_non_http_success(err).await {
-                        Ok((doc, received)) => {
-                            self.insert_article_urls(&doc);
-                            Ok((doc, received))
-                        }
-                        Err(err) => Err((state, err)),
-                    }
-                } else {
-                    Err((state, err))
-                }
-            }
-        };
+        let result = self.get_document(category.url.clone()).await;
+        if let Ok(page) = &result {
+            self.mark_visited(&category.url);
+            self.insert_article_urls(&page.doc);
+        }
         match result {
-            Ok((doc, received)) => {
-                self.categories.remove(&category);
+            Ok(page) => {
+                self.queue.categories.remove(&category);
                 Ok(self
+                    .queue
                     .categories
                     .entry(category)
-                    .or_insert(DocumentDownloadState::Success { doc, received })
+                    .or_insert(page.into_state())
                     .success_document()
                     .unwrap())
             }
             Err((state, err)) => {
-                self.categories.insert(category, state);
+                self.queue.set_category_state(category, state);
                 Err(err)
             }
         }
@@ -254,11 +585,14 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         &mut self,
         items: Vec<Category>,
     ) -> Vec<Result<Category, (Category, ExtrablattError)>> {
-        let requests = stream::iter(items.into_iter().map(|cat| {
-            self.client
-                .get(cat.url.clone())
-                .send()
-                .then(|res| async { (cat, DocumentDownloadState::from_response(res).await) })
+        let (client, retry_policy, throttle) = (&self.client, &self.retry_policy, &self.throttle);
+        let (http_cache, http_success_only) = (self.http_cache.as_deref(), self.config.http_success_only);
+        let user_agents = &self.user_agents;
+        let requests = stream::iter(items.into_iter().map(|cat| async move {
+            let _permit = throttle.acquire(&cat.url).await;
+            let user_agent = user_agents.next();
+            let result = download(client, retry_policy, http_cache, &cat.url, user_agent, http_success_only).await;
+            (cat, result)
         }))
         .buffer_unordered(self.config.concurrency)
         .collect::<Vec<_>>()
@@ -267,29 +601,15 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
 
         for (cat, res) in requests {
             let res = match res {
-                Ok((doc, received)) => {
-                    self.insert_article_urls(&doc);
-                    *self.categories.get_mut(&cat).unwrap() =
-                        DocumentDownloadState::Success { doc, received };
+                Ok(page) => {
+                    self.mark_visited(&cat.url);
+                    self.insert_article_urls(&page.doc);
+                    *self.queue.categories.get_mut(&cat).unwrap() = page.into_state();
                     Ok(cat)
                 }
-                Err((state, err)) => {
-                    if !self.config.http_success_only {
-                        match DocumentDownloadState::advance_non_http_success(err).await {
-                            Ok((doc, received)) => {
-                                *self.categories.get_mut(&cat).unwrap() =
-                                    DocumentDownloadState::Success { doc, received };
-                                Ok(cat)
-                            }
-                            Err(err) => {
-                                *self.categories.get_mut(&cat).unwrap() = state;
-                                Err((cat, err))
-                            }
-                        }
-                    } else {
-                        *self.categories.get_mut(&cat).unwrap() = state;
-                        Err((cat, err))
-                    }
+                Err(err) => {
+                    *self.queue.categories.get_mut(&cat).unwrap() = DocumentDownloadState::from_error(&err);
+                    Err((cat, err))
                 }
             };
             results.push(res);
@@ -303,6 +623,7 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         &mut self,
     ) -> Vec<Result<Category, (Category, ExtrablattError)>> {
         let items: Vec<_> = self
+            .queue
             .categories
             .iter()
             .filter_map(|(cat, state)| {
@@ -316,32 +637,99 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         self.download_categories(items).await
     }
 
+    /// Download all feeds the main page advertises and add the urls of their
+    /// items to the articles.
+    ///
+    /// Items that carry the full article html, like `content:encoded`, are
+    /// built with [`Article::from_fragment`] right away and kept in
+    /// [`Extrablatt::feed_articles`] instead of being downloaded again,
+    /// unless their text is shorter than
+    /// [`FragmentOptions::DEFAULT_MIN_TEXT_LEN`]. Item urls are subject to
+    /// the [`ExtrablattBuilder::category_filter`].
+    pub async fn download_feeds(&mut self) -> Vec<Result<FeedLink, (FeedLink, ExtrablattError)>> {
+        let feeds = self.extractor.feed_urls(&self.main_page, Some(&self.base_url));
+        let (client, retry_policy, throttle) = (&self.client, &self.retry_policy, &self.throttle);
+        let responses = stream::iter(feeds.into_iter().map(|feed| async move {
+            let _permit = throttle.acquire(&feed.url).await;
+            let (response, attempts) = retry_policy.get(client, feed.url.clone()).await;
+            let body = match response {
+                Ok(response) if response.status().is_success() => response
+                    .text()
+                    .await
+                    .map_err(|error| ExtrablattError::HttpRequestFailure { error, attempts }),
+                Ok(response) => Err(ExtrablattError::http_status(response, attempts).await),
+                Err(error) => Err(ExtrablattError::request_failure(error, attempts)),
+            };
+            (feed, body)
+        }))
+        .buffer_unordered(self.config.concurrency)
+        .collect::<Vec<_>>()
+        .await;
+
+        let mut results = Vec::with_capacity(responses.len());
+        for (feed, body) in responses {
+            match body {
+                Ok(body) => {
+                    for item in feed_items(&body, feed.kind, Some(&feed.url)) {
+                        if !self.is_wanted_url(&item.url.url) {
+                            continue;
+                        }
+                        match self.feed_article(&item.url.url, item.content, item.known) {
+                            Some(article) => {
+                                let mut url = item.url;
+                                url.url = self.dedup.normalize(&url.url);
+                                self.queue.articles.remove(&url.url);
+                                self.feed_articles.insert(url, article);
+                            }
+                            None => self.queue_article(item.url),
+                        }
+                    }
+                    results.push(Ok(feed));
+                }
+                Err(err) => results.push(Err((feed, err))),
+            }
+        }
+        results
+    }
+
+    /// The article built from the full html `content` of a feed item, if its
+    /// text is long enough to skip downloading the page.
+    fn feed_article(&mut self, url: &Url, content: Option<String>, known: KnownFields) -> Option<Article> {
+        let options = FragmentOptions::new(known)
+            .language(self.language.clone())
+            .filters(self.extractor.filters().cloned());
+        let min_text_len = options.min_text_len;
+        let mut article = Article::from_fragment(content?, url.clone(), options).ok()?;
+        let text_len = article
+            .content
+            .text
+            .as_ref()
+            .map(|txt| txt.chars().count())
+            .unwrap_or_default();
+        if text_len < min_text_len {
+            return None;
+        }
+        self.register_images(&mut article.content);
+        Some(article)
+    }
+
     /// Download and store all categories and their identified articles that
     /// haven't been requested yet.
     pub async fn download_all_remaining_categories(
         &mut self,
     ) -> Vec<Result<Category, (Category, ExtrablattError)>> {
-        let items: Vec<_> = self
-            .categories
-            .iter()
-            .filter_map(|(cat, state)| {
-                if state.is_not_requested() {
-                    Some(cat.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let items = self.queue.not_requested_categories();
         self.download_categories(items).await
     }
 
     /// Refresh the main page, insert new categories and return the old
     /// document.
     pub async fn refresh_homepage(&mut self) -> Result<Document, ExtrablattError> {
-        let (main_page, _) = self
+        let main_page = self
             .get_document(self.base_url.clone())
             .await
-            .map_err(|(_, err)| err)?;
+            .map_err(|(_, err)| err)?
+            .doc;
 
         // extract all available categories
         self.insert_new_categories();
@@ -349,14 +737,95 @@ impl<TExtractor: Extractor> Extrablatt<TExtractor> {
         Ok(std::mem::replace(&mut self.main_page, main_page))
     }
 
-    /// Execute a GET request and return the response wrapped in
-    /// [`DocumentDownloadState`].
-    async fn get_document(
+    /// Extract the [`Article`] of the downloaded `page`.
+    ///
+    /// Fails if [`Config::prefilter_documents`] is set and the document
+    /// doesn't look like an article, if it duplicates an article processed
+    /// before or if the content doesn't fulfill the configured requirements.
+    pub(crate) fn article_from_document(
+        &mut self,
+        url: Url,
+        page: DownloadedDocument,
+    ) -> Result<Article, ExtrablattError> {
+        let DownloadedDocument {
+            doc,
+            from_cache,
+            user_agent,
+            fetch_info,
+            ..
+        } = page;
+        if self.config.prefilter_documents {
+            let stats = DocumentStats::compute(&doc);
+            if !stats.looks_like_article() {
+                return Err(ExtrablattError::SkippedDocument {
+                    url,
+                    stats: Box::new(stats),
+                });
+            }
+        }
+        // relative urls, like the canonical link, are resolved against the
+        // url the document was served from after redirects
+        let (content, language) = extract_article(
+            &self.extractor,
+            &self.languages,
+            &self.language,
+            &url,
+            &doc,
+            &fetch_info.final_url,
+        );
+        let mut content = content.into_owned();
+        if let Some(original) = self.find_duplicate(&url, content.canonical.as_ref(), content.text.as_deref()) {
+            return Err(ExtrablattError::DuplicateArticle {
+                url,
+                original: Box::new(original),
+            });
+        }
+        self.register_images(&mut content);
+        record_user_agent(&mut content, user_agent);
+
+        let incompleteness = self.config.incompleteness(&content);
+        if !incompleteness.is_empty() {
+            if !self.config.completeness.warn_only {
+                return Err(ExtrablattError::IncompleteArticle {
+                    article: Box::new(PureArticle {
+                        url,
+                        content,
+                        language,
+                        fetch_info: Some(fetch_info),
+                    }),
+                    incompleteness,
+                });
+            }
+            log::warn!("Incomplete article {}: {}", url, incompleteness);
+            content.incompleteness = Some(incompleteness);
+        }
+        Ok(Article {
+            url,
+            doc,
+            content,
+            language,
+            from_cache,
+            fetch_info: Some(fetch_info),
+        })
+    }
+
+    /// Execute a GET request and return the parsed page.
+    pub(crate) async fn get_document(
         &self,
         url: Url,
-    ) -> Result<(Document, Instant), (DocumentDownloadState, ExtrablattError)> {
-        let resp = self.client.get(url).send().await;
-        DocumentDownloadState::from_response(resp).await
+    ) -> Result<DownloadedDocument, (DocumentDownloadState, ExtrablattError)> {
+        let _permit = self.throttle.acquire(&url).await;
+        let user_agent = self.user_agents.next();
+        download(
+            &self.client,
+            &self.retry_policy,
+            self.http_cache.as_deref(),
+            &url,
+            user_agent,
+            self.config.http_success_only,
+        )
+        .await
+        .map_err(|err| (DocumentDownloadState::from_error(&err), err))
     }
 
     #[cfg(feature = "archive")]
@@ -371,44 +840,55 @@ impl<TExtractor: Extractor + Unpin> Extrablatt<TExtractor> {
     pub fn into_stream(
         mut self,
     ) -> impl Stream<Item = Result<Article, ExtrablattError>> {
-        let mut articles = Vec::new();
-        let mut article_responses = Vec::new();
+        let mut articles: Vec<_> = self.feed_articles.drain().map(|(_, article)| Ok(article)).collect();
+        let mut pending = Vec::new();
+        let mut queued = FnvHashSet::default();
 
         let mut extracted = FnvHashMap::default();
-        std::mem::swap(&mut extracted, &mut self.articles);
+        std::mem::swap(&mut extracted, &mut self.queue.articles);
 
         for (article_url, doc) in extracted.into_iter() {
+            // known articles aren't queued again by the categories
+            queued.insert(article_url.url.clone());
             match doc {
                 DocumentDownloadState::NotRequested => {
-                    article_responses.push(self.get_response(article_url.url));
+                    pending.push(article_url.url);
                 }
-                DocumentDownloadState::Success { doc, .. } => {
-                    let article = Article {
-                        content: self
-                            .extractor
-                            .article_content(
-                                &doc,
-                                Some(&self.base_url),
-                                Some(self.language.clone()),
-                            )
-                            .into_owned(),
-                        url: article_url.url,
-                        language: self
-                            .extractor
-                            .meta_language(&doc)
-                            .unwrap_or_else(|| self.language.clone()),
+                DocumentDownloadState::Success {
+                    doc,
+                    received,
+                    from_cache,
+                    user_agent,
+                    fetch_info,
+                } => {
+                    // documents downloaded before are skipped or rejected like
+                    // the ones downloaded by the stream, the state doesn't keep
+                    // the body
+                    let page = DownloadedDocument {
                         doc,
+                        received,
+                        from_cache,
+                        user_agent,
+                        fetch_info: *fetch_info,
+                        body: Bytes::new(),
                     };
-                    articles.push(article);
+                    articles.push(self.article_from_document(article_url.url, page));
                 }
                 _ => {}
             }
         }
 
+        // likely articles first
+        let pending_articles = pending
+            .into_iter()
+            .enumerate()
+            .map(|(seq, url)| Reverse((self.queue.article_class(&url), seq, url)))
+            .collect();
+
         let mut categories = Vec::new();
         let mut category_responses = Vec::new();
         let mut extracted = FnvHashMap::default();
-        std::mem::swap(&mut extracted, &mut self.categories);
+        std::mem::swap(&mut extracted, &mut self.queue.categories);
 
         for (cat, doc) in extracted {
             match doc {
@@ -424,52 +904,55 @@ impl<TExtractor: Extractor + Unpin> Extrablatt<TExtractor> {
 
         ArticleStream {
             paper: self,
-            article_responses,
+            pending_articles,
+            article_responses: Vec::new(),
             articles,
             categories,
             category_responses,
+            queued,
         }
     }
 
     fn get_response(&self, url: Url) -> PaperResponse {
-        Box::pin(
-            self.client
-                .get(url.clone())
-                .send()
-                .map_err(|error| ExtrablattError::HttpRequestFailure { error })
-                .and_then(|response| async {
-                    if !response.status().is_success() {
-                        Err(ExtrablattError::NoHttpSuccessResponse { response })
-                    } else {
-                        response
-                            .bytes()
-                            .await
-                            .map(|bytes| (url, bytes))
-                            .map_err(|error| ExtrablattError::HttpRequestFailure { error })
-                    }
-                }),
-        )
+        let client = self.client.clone();
+        let retry_policy = self.retry_policy.clone();
+        let throttle = self.throttle.clone();
+        let http_cache = self.http_cache.clone();
+        let user_agents = self.user_agents.clone();
+        let http_success_only = self.config.http_success_only;
+        Box::pin(async move {
+            let _permit = throttle.acquire(&url).await;
+            let user_agent = user_agents.next();
+            download(&client, &retry_policy, http_cache.as_deref(), &url, user_agent, http_success_only)
+                .await
+                .map(|page| (url, page))
+        })
     }
 }
 
-type PaperResponse =
-    Pin<Box<dyn Future<Output = Result<(Url, Bytes), ExtrablattError>>>>;
+type PaperResponse = Pin<Box<dyn Future<Output = Result<(Url, DownloadedDocument), ExtrablattError>>>>;
 
-type ReadyResponse = (usize, Result<(Url, Bytes), ExtrablattError>);
+type ReadyResponse = (usize, Result<(Url, DownloadedDocument), ExtrablattError>);
 
 /// Stream for getting a `Article` each at a time.
 #[must_use = "streams do nothing unless polled"]
 pub struct ArticleStream<TExtractor: Extractor> {
     /// The origin newspaper.
     paper: Extrablatt<TExtractor>,
-    /// Pending responses for an Article html.
+    /// Queued articles that aren't requested yet, by their class and the
+    /// order they were queued in, so likely articles are requested first.
+    pending_articles: BinaryHeap<Reverse<(UrlClass, usize, Url)>>,
+    /// Pending responses for an Article html, in the order they were
+    /// requested.
     article_responses: Vec<PaperResponse>,
     /// Pending responses for Category html.
     category_responses: Vec<PaperResponse>,
-    /// Articles already available.
-    articles: Vec<Article>,
+    /// Articles already available, or why they were skipped.
+    articles: Vec<Result<Article, ExtrablattError>>,
     /// Categories already available.
     categories: Vec<(Category, Document)>,
+    /// Normalized urls of all queued articles.
+    queued: FnvHashSet<Url>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -496,7 +979,7 @@ impl ArticleStream<DefaultExtractor> {
     /// # }
     /// ```
     pub async fn new<T: IntoUrl>(url: T) -> Result<ArticleStream<DefaultExtractor>, ExtrablattError> {
-        Ok(ArticleStream::new_with_extractor(url, DefaultExtractor).await?)
+        ArticleStream::new_with_extractor(url, DefaultExtractor::default()).await
     }
 }
 
@@ -509,33 +992,78 @@ impl<TExtractor: Extractor + Unpin> ArticleStream<TExtractor> {
         extractor: TExtractor,
     ) -> Result<ArticleStream<TExtractor>, ExtrablattError> {
         let paper = ExtrablattBuilder::new(url)?.build_with_extractor(extractor).await?;
-
-        let article_responses = paper
+        let article_urls = paper
             .extractor()
-            .article_urls(&paper.main_page, Some(&paper.base_url))
-            .into_iter()
-            .map(|article_url| paper.get_response(article_url.url))
-            .collect();
+            .article_urls(&paper.main_page, Some(&paper.base_url));
 
-        Ok(ArticleStream {
+        let mut stream = ArticleStream {
             paper,
-            article_responses,
+            pending_articles: Default::default(),
+            article_responses: Default::default(),
             articles: Default::default(),
             categories: Default::default(),
             category_responses: Default::default(),
-        })
+            queued: Default::default(),
+        };
+        stream.queue_articles(article_urls);
+        Ok(stream)
+    }
+
+    /// Occurrences of the images across all articles of the stream.
+    pub fn image_registry(&self) -> &ImageRegistry {
+        self.paper.image_registry()
+    }
+
+    /// Number of duplicate urls and articles skipped so far.
+    pub fn dedup_stats(&self) -> &DedupStats {
+        self.paper.dedup_stats()
+    }
+
+    /// Number of article urls queued and rejected so far per class.
+    pub fn url_class_stats(&self) -> &UrlClassStats {
+        self.paper.queue.url_class_stats()
     }
 
     /// Queue in new requests for articles.
     fn queue_category_articles(&mut self, doc: &Document) {
-        for article_url in self
+        let article_urls = self
             .paper
             .extractor
-            .article_urls(&doc, Some(&self.paper.base_url))
+            .article_urls(doc, Some(&self.paper.base_url))
             .into_iter()
-        {
-            self.article_responses
-                .push(self.paper.get_response(article_url.url));
+            .filter(|article_url| self.paper.is_wanted_url(&article_url.url))
+            .collect();
+        self.queue_articles(article_urls);
+    }
+
+    /// Queue the normalized urls of articles that aren't queued yet and
+    /// aren't rejected by the article url filter.
+    ///
+    /// Likely articles are requested before all ambiguous ones queued so
+    /// far, urls of the same class in the order they were queued.
+    fn queue_articles(&mut self, article_urls: Vec<ArticleUrl>) {
+        for article_url in article_urls {
+            let url = self.paper.dedup.normalize(&article_url.url);
+            if !self.queued.insert(url.clone()) {
+                self.paper.dedup.count_duplicate_url();
+                continue;
+            }
+            let class = self.paper.queue.classify(&url);
+            if !class.is_rejected() {
+                let seq = self.queued.len();
+                self.pending_articles.push(Reverse((class, seq, url)));
+            }
+        }
+    }
+
+    /// Request the queued articles with the highest priority, until
+    /// [`Config::concurrency`] requests are pending.
+    fn request_articles(&mut self) {
+        while self.article_responses.len() < self.paper.config.concurrency().max(1) {
+            let Some(Reverse((_, _, url))) = self.pending_articles.pop() else {
+                break;
+            };
+            self.article_responses.push(self.paper.get_response(url));
         }
     }
 
@@ -563,12 +1091,15 @@ impl<TExtractor: Extractor + Unpin> Stream for ArticleStream<TExtractor> {
         cx: &mut core::task::Context<'_>,
     ) -> Poll<Option<Self::Item>> {
         if let Some(article) = self.articles.pop() {
-            return Poll::Ready(Some(Ok(article)));
+            return Poll::Ready(Some(article));
         }
-        if self.article_responses.is_empty() {
+        self.request_articles();
+        while self.article_responses.is_empty() {
             if let Some((_, doc)) = self.categories.pop() {
                 // add futures to article_response
                 self.queue_category_articles(&doc);
+                self.request_articles();
+                continue;
             }
 
             if self.category_responses.is_empty() {
@@ -583,14 +1114,9 @@ impl<TExtractor: Extractor + Unpin> Stream for ArticleStream<TExtractor> {
                 Some((idx, resp)) => {
                     let _ = self.category_responses.swap_remove(idx);
                     match resp {
-                        Ok((_, body)) => {
-                            if let Ok(doc) = Document::from_read(&*body) {
-                                self.queue_category_articles(&doc);
-                            } else {
-                                return Poll::Ready(Some(Err(
-                                    ExtrablattError::ReadDocumentError { body },
-                                )));
-                            }
+                        Ok((_, page)) => {
+                            self.queue_category_articles(&page.doc);
+                            self.request_articles();
                         }
                         Err(e) => {
                             return Poll::Ready(Some(Err(e)));
@@ -605,45 +1131,10 @@ impl<TExtractor: Extractor + Unpin> Stream for ArticleStream<TExtractor> {
 
         match item {
             Some((idx, resp)) => {
-                let _ = self.article_responses.swap_remove(idx);
+                // keep the order of the requests, the first are polled first
+                let _ = self.article_responses.remove(idx);
                 let article = match resp {
-                    Ok((url, body)) => {
-                        if let Ok(doc) = Document::from_read(&*body) {
-                            let content = self
-                                .paper
-                                .extractor
-                                .article_content(
-                                    &doc,
-                                    Some(&self.paper.base_url),
-                                    Some(self.paper.language.clone()),
-                                )
-                                .into_owned();
-                            let language = self
-                                .paper
-                                .extractor
-                                .meta_language(&doc)
-                                .unwrap_or_else(|| self.paper.language.clone());
-
-                            if self.paper.config.is_complete(&content) {
-                                Ok(Article {
-                                    url,
-                                    doc,
-                                    content,
-                                    language,
-                                })
-                            } else {
-                                Err(ExtrablattError::IncompleteArticle {
-                                    article: Box::new(PureArticle {
-                                        url,
-                                        content,
-                                        language,
-                                    }),
-                                })
-                            }
-                        } else {
-                            Err(ExtrablattError::ReadDocumentError { body })
-                        }
-                    }
+                    Ok((url, page)) => self.paper.article_from_document(url, page),
                     Err(error) => Err(error),
                 };
                 Poll::Ready(Some(article))
@@ -656,6 +1147,7 @@ impl<TExtractor: Extractor + Unpin> Stream for ArticleStream<TExtractor> {
         (
             self.articles
                 .len()
+                .saturating_add(self.pending_articles.len())
                 .saturating_add(self.article_responses.len()),
             None,
         )
@@ -667,6 +1159,8 @@ pub struct ExtrablattBuilder {
     base_url: Option<Url>,
     config: Option<Config>,
     language: Option<Language>,
+    /// Languages of articles that differ from the language of the site.
+    languages: LanguageOverrides,
     headers: Option<HeaderMap>,
     /// Whether to extract and store the categories of the news paper's main
     /// page.
@@ -674,6 +1168,56 @@ pub struct ExtrablattBuilder {
     /// Optional proxy URL for HTTP requests.
     /// Format: "http://proxy:port" or "https://proxy:port" or "socks5://proxy:port"
     proxy: Option<String>,
+    /// Max duration of a request, overrides [`Config::request_timeout`].
+    timeout: Option<Duration>,
+    /// Max duration of establishing a connection.
+    connect_timeout: Option<Duration>,
+    /// Max number of redirects followed per request.
+    max_redirects: Option<usize>,
+    /// Whether invalid TLS certificates are accepted.
+    danger_accept_invalid_certs: bool,
+    /// When to repeat failed downloads.
+    retry_policy: Option<RetryPolicy>,
+    /// Image occurrences of a previous crawl.
+    image_registry: Option<ImageRegistry>,
+    /// Categories that are added regardless of the main page.
+    include_categories: Vec<Url>,
+    /// Which categories and the article urls found in them are kept.
+    category_filter: Option<UrlFilter>,
+    /// Max number of requests in flight.
+    max_concurrent_requests: Option<usize>,
+    /// Minimal time between the start of two requests to the same host.
+    request_delay: Option<Duration>,
+    /// Pages of previous downloads, for conditional requests.
+    http_cache: Option<Arc<dyn HttpCache>>,
+    /// Whether the items of the feeds advertised by the main page are added
+    /// to the articles.
+    use_feeds: bool,
+    /// Whether articles are extracted from their AMP version.
+    prefer_amp: bool,
+    /// Whether the meta refresh of stub pages of articles is followed.
+    follow_meta_refresh: bool,
+    /// How article urls are normalized before they are queued.
+    url_normalizer: Option<UrlNormalizer>,
+    /// Whether articles with nearly the same text are skipped.
+    dedup_content: bool,
+    /// User agents rotated across requests.
+    user_agents: Vec<String>,
+    /// The order of the rotated user agents.
+    user_agent_rotation: Rotation,
+    /// The crawl to continue.
+    resume_from: Option<CrawlState>,
+    /// Hooks that reject nodes and texts of the default extractor.
+    extraction_filters: Option<ExtractionFilters>,
+    /// Decides which article urls are downloaded and in which order.
+    article_url_filter: Option<ArticleUrlFilter>,
+    /// Overrides the [`Config::completeness`].
+    completeness: Option<Completeness>,
+    /// Cookies sent with the requests.
+    #[cfg(feature = "cookies")]
+    cookies: Cookies,
+    /// The client to use instead of building one.
+    client: Option<Client>,
 }
 
 impl ExtrablattBuilder {
@@ -682,9 +1226,35 @@ impl ExtrablattBuilder {
             base_url: Some(base_url.into_url().map_err(|e| ExtrablattError::UrlParseError { error: e })?),
             config: None,
             language: None,
+            languages: LanguageOverrides::default(),
             headers: None,
             categories: true,
             proxy: None,
+            timeout: None,
+            connect_timeout: None,
+            max_redirects: None,
+            danger_accept_invalid_certs: false,
+            retry_policy: None,
+            image_registry: None,
+            include_categories: Vec::new(),
+            category_filter: None,
+            max_concurrent_requests: None,
+            request_delay: None,
+            http_cache: None,
+            use_feeds: false,
+            prefer_amp: false,
+            follow_meta_refresh: false,
+            url_normalizer: None,
+            dedup_content: false,
+            user_agents: Vec::new(),
+            user_agent_rotation: Rotation::default(),
+            resume_from: None,
+            extraction_filters: None,
+            article_url_filter: None,
+            completeness: None,
+            #[cfg(feature = "cookies")]
+            cookies: Cookies::default(),
+            client: None,
         })
     }
 
@@ -693,11 +1263,58 @@ impl ExtrablattBuilder {
         self
     }
 
+    /// Extract the articles on the `host` whose path starts with the
+    /// `path_prefix`, like the Arabic edition at `/ar`, in the `language`.
+    ///
+    /// The language of an article is, in this order:
+    ///   1. The language set for its host and path, or returned by the
+    ///      [`ExtrablattBuilder::language_resolver`]
+    ///   2. The language the page declares, see [`Extractor::meta_language`]
+    ///   3. The language detected from the text of the page
+    ///   4. The [`ExtrablattBuilder::language`]
+    ///
+    /// and stored in [`Article::language`].
+    pub fn language_for_host_path<H, P>(mut self, host: H, path_prefix: P, language: Language) -> Self
+    where
+        H: Into<String>,
+        P: Into<String>,
+    {
+        self.languages.insert(host, path_prefix, language);
+        self
+    }
+
+    /// Decide the language of articles by their url, if no
+    /// [`ExtrablattBuilder::language_for_host_path`] matches.
+    pub fn language_resolver<F>(mut self, resolver: F) -> Self
+    where
+        F: Fn(&Url) -> Option<Language> + Send + Sync + 'static,
+    {
+        self.languages.set_resolver(resolver);
+        self
+    }
+
     pub fn config(mut self, config: Config) -> Self {
         self.config = Some(config);
         self
     }
 
+    /// Send all requests with the `client`, like a client that is shared
+    /// with the rest of the application and its connection pool.
+    ///
+    /// The client is used as is: the options that configure the client the
+    /// builder would build are ignored, that is the
+    /// [`ExtrablattBuilder::headers`], [`ExtrablattBuilder::proxy`],
+    /// [`ExtrablattBuilder::timeout`], [`ExtrablattBuilder::connect_timeout`],
+    /// [`ExtrablattBuilder::max_redirects`],
+    /// [`ExtrablattBuilder::danger_accept_invalid_certs`] and the user agent
+    /// and request timeout of the [`Config`]. The
+    /// [`ExtrablattBuilder::user_agents`] are still rotated across requests.
+    /// Not supported by the [`crate::blocking::Extrablatt`].
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
     pub fn headers(mut self, headers: HeaderMap) -> Self {
         self.headers = Some(headers);
         self
@@ -708,86 +1325,699 @@ impl ExtrablattBuilder {
         self
     }
 
-    /// Set a proxy for all HTTP requests.
+    /// Add these categories in addition to the ones found on the main page.
     ///
-    /// The proxy URL should be in the format:
-    /// - `http://proxy:port` for HTTP proxy
-    /// - `https://proxy:port` for HTTPS proxy
-    /// - `socks5://proxy:port` for SOCKS5 proxy
+    /// They are added even if [`ExtrablattBuilder::categories`] is disabled
+    /// and aren't subject to the [`ExtrablattBuilder::category_filter`].
+    pub fn include_categories(mut self, categories: Vec<Url>) -> Self {
+        self.include_categories = categories;
+        self
+    }
+
+    /// Only keep the categories of the main page, and the article urls found
+    /// in categories, for which the `filter` returns `true`.
+    ///
+    /// The filter is applied before any page is requested.
     ///
     /// # Example
     ///
+    /// Skip tag pages, videos and live tickers.
+    ///
     /// ```no_run
     /// # use extrablatt_v2::Extrablatt;
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let site = Extrablatt::builder("https://example.com")?
-    ///         .proxy("http://127.0.0.1:8080")
+    ///         .category_filter(|url| {
+    ///             !["/tag/", "/video/", "/live/"]
+    ///                 .iter()
+    ///                 .any(|part| url.path().contains(part))
+    ///         })
     ///         .build()
     ///         .await?;
     /// #   Ok(())
     /// # }
     /// ```
-    pub fn proxy<T: Into<String>>(mut self, proxy: T) -> Self {
-        self.proxy = Some(proxy.into());
+    pub fn category_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Url) -> bool + Send + Sync + 'static,
+    {
+        self.category_filter = Some(UrlFilter::new(filter));
         self
     }
 
-    /// Create a new builder with a specific extractor.
-    pub async fn build_with_extractor<TExtractor: Extractor>(
-        self,
-        extractor: TExtractor,
-    ) -> Result<Extrablatt<TExtractor>, ExtrablattError> {
-        let base_url = self.base_url.ok_or(ExtrablattError::UrlNotInitialized)?;
-        if base_url.cannot_be_a_base() {
-            return Err(ExtrablattError::BaseUrlInvalid { url: base_url });
+    /// Classify the article urls before they are downloaded, instead of
+    /// with [`crate::url_class::classify_article_url`].
+    ///
+    /// Rejected urls aren't downloaded, likely articles are downloaded before
+    /// ambiguous ones. The number of urls per class is available with
+    /// [`Extrablatt::url_class_stats`].
+    ///
+    /// # Example
+    ///
+    /// Also reject the live blogs.
+    ///
+    /// ```no_run
+    /// # use extrablatt_v2::Extrablatt;
+    /// # use extrablatt_v2::url_class::{classify_article_url, RejectRule, UrlClass};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let site = Extrablatt::builder("https://cnn.com/")?
+    ///         .article_url_filter(|url| {
+    ///             if url.path().starts_with("/live/") {
+    ///                 UrlClass::Rejected(RejectRule::Custom)
+    ///             } else {
+    ///                 classify_article_url(url)
+    ///             }
+    ///         })
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn article_url_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Url) -> UrlClass + Send + Sync + 'static,
+    {
+        self.article_url_filter = Some(ArticleUrlFilter::new(filter));
+        self
+    }
+
+    /// The fields an article needs to be complete, see [`Completeness`].
+    ///
+    /// Overrides the [`ConfigBuilder::completeness`] of the
+    /// [`ExtrablattBuilder::config`].
+    pub fn completeness(mut self, completeness: Completeness) -> Self {
+        self.completeness = Some(completeness);
+        self
+    }
+
+    /// Set a proxy for all HTTP requests.
+    ///
+    /// The proxy URL should be in the format:
+    /// - `http://proxy:port` for HTTP proxy
+    /// - `https://proxy:port` for HTTPS proxy
+    /// - `socks5://proxy:port` for SOCKS5 proxy
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use extrablatt_v2::Extrablatt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let site = Extrablatt::builder("https://example.com")?
+    ///         .proxy("http://127.0.0.1:8080")
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn proxy<T: Into<String>>(mut self, proxy: T) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Whether the cookies set by responses are sent with the following
+    /// requests of the crawl.
+    ///
+    /// Default is `false`. Not supported on wasm.
+    #[cfg(feature = "cookies")]
+    pub fn cookie_store(mut self, cookie_store: bool) -> Self {
+        self.cookies.store = cookie_store;
+        self
+    }
+
+    /// Send the `cookies` as `name=value` pairs to the `host`, but not to its
+    /// subdomains, like the consent cookie a publisher requires before
+    /// serving the full article instead of an interstitial.
+    ///
+    /// Cookies set by responses are only kept with
+    /// [`ExtrablattBuilder::cookie_store`]. Building fails with
+    /// [`ExtrablattError::InvalidCookieHost`] if the `host` isn't valid and
+    /// with [`ExtrablattError::InvalidCookie`] if a name or value has a
+    /// character a cookie can't have, like a `;`. Not supported on wasm.
+    ///
+    /// ```no_run
+    /// # use extrablatt_v2::Extrablatt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let site = Extrablatt::builder("https://www.example.com")?
+    ///         .cookies_for("www.example.com", vec![("euconsent", "1"), ("gdpr", "1")])
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "cookies")]
+    pub fn cookies_for<H, N, V>(mut self, host: H, cookies: Vec<(N, V)>) -> Self
+    where
+        H: Into<String>,
+        N: Into<String>,
+        V: Into<String>,
+    {
+        self.cookies.insert(host, cookies);
+        self
+    }
+
+    /// Set the max duration of a request, from connecting until the body is
+    /// read.
+    ///
+    /// Overrides the [`Config::request_timeout`]. Not supported on wasm.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the max duration of establishing a connection.
+    ///
+    /// Default is no limit besides the [`ExtrablattBuilder::timeout`]. Not
+    /// supported on wasm.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Set the max number of redirects followed per request, `0` follows
+    /// none.
+    ///
+    /// Requests that exceed the limit fail with
+    /// [`ExtrablattError::TooManyRedirects`]. Default is `10`. Not supported
+    /// on wasm.
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = Some(max_redirects);
+        self
+    }
+
+    /// Whether to accept invalid TLS certificates, like the self-signed
+    /// certificate of an internal mirror.
+    ///
+    /// **Dangerous**: any certificate is trusted, including expired ones and
+    /// ones for other hosts, which exposes all requests to
+    /// man-in-the-middle attacks. Only use it for hosts you control. Default
+    /// is `false`. Not supported on wasm.
+    pub fn danger_accept_invalid_certs(mut self, danger_accept_invalid_certs: bool) -> Self {
+        self.danger_accept_invalid_certs = danger_accept_invalid_certs;
+        self
+    }
+
+    /// Set the policy for repeating failed downloads.
+    ///
+    /// Default is [`RetryPolicy::default`], use [`RetryPolicy::none`] to
+    /// disable retries.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Continue counting the image occurrences of a previous crawl, so site
+    /// furniture is detected from the first article on.
+    pub fn image_registry(mut self, image_registry: ImageRegistry) -> Self {
+        self.image_registry = Some(image_registry);
+        self
+    }
+
+    /// Set the max number of requests in flight, for category and article
+    /// pages alike.
+    ///
+    /// Default is [`Throttle::DEFAULT_MAX_CONCURRENT_REQUESTS`]. The
+    /// [`crate::blocking::Extrablatt`] always sends one request at a time.
+    pub fn max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = Some(max_concurrent_requests);
+        self
+    }
+
+    /// Set the minimal time between the start of two requests to the same
+    /// host.
+    ///
+    /// Default is no delay.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use extrablatt_v2::Extrablatt;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let site = Extrablatt::builder("https://example.com")?
+    ///         .max_concurrent_requests(2)
+    ///         .request_delay(Duration::from_millis(500))
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn request_delay(mut self, request_delay: Duration) -> Self {
+        self.request_delay = Some(request_delay);
+        self
+    }
+
+    /// Send conditional requests for pages stored in the `http_cache` and
+    /// store new pages in it.
+    ///
+    /// A `304 Not Modified` response reuses the stored page, articles of
+    /// such pages are flagged with [`Article::from_cache`]. Not supported
+    /// by the [`crate::blocking::Extrablatt`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use extrablatt_v2::Extrablatt;
+    /// # use extrablatt_v2::http_cache::MemoryHttpCache;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let cache = Arc::new(MemoryHttpCache::new());
+    ///     let site = Extrablatt::builder("https://example.com")?
+    ///         .http_cache(cache.clone())
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn http_cache(mut self, http_cache: Arc<dyn HttpCache>) -> Self {
+        self.http_cache = Some(http_cache);
+        self
+    }
+
+    /// Whether to download the RSS, Atom and JSON feeds advertised by the
+    /// main page and add their items to the articles, see
+    /// [`Extrablatt::download_feeds`].
+    ///
+    /// Default is `false`. Feeds that fail to download are skipped. Not
+    /// supported by the [`crate::blocking::Extrablatt`].
+    pub fn use_feeds(mut self, use_feeds: bool) -> Self {
+        self.use_feeds = use_feeds;
+        self
+    }
+
+    /// Whether to extract articles from their AMP version, if their page
+    /// advertises one with `<link rel="amphtml">`.
+    ///
+    /// Articles keep the url of the original page, which is also used if the
+    /// AMP page can't be downloaded. Default is `false`. Not supported by the
+    /// [`crate::blocking::Extrablatt`].
+    pub fn prefer_amp(mut self, prefer_amp: bool) -> Self {
+        self.prefer_amp = prefer_amp;
+        self
+    }
+
+    /// Whether to follow a `<meta http-equiv="refresh" content="0;url=...">`
+    /// of an article page that redirects without an HTTP redirect, see
+    /// [`crate::ArticleBuilder::follow_meta_refresh`].
+    ///
+    /// The download fails with [`ExtrablattError::TooManyMetaRefreshes`] if
+    /// the refreshes loop or exceed
+    /// [`crate::extract_meta_refresh::MAX_META_REFRESH_HOPS`]. Default is `false`. Not supported by the [`crate::blocking::Extrablatt`].
+    pub fn follow_meta_refresh(mut self, follow_meta_refresh: bool) -> Self {
+        self.follow_meta_refresh = follow_meta_refresh;
+        self
+    }
+
+    /// Skip the nodes and texts the `filters` deny when extracting the text
+    /// and images of articles, like site specific widgets.
+    ///
+    /// Only applies to the [`DefaultExtractor`] of
+    /// [`ExtrablattBuilder::build`] and [`ExtrablattBuilder::build_blocking`],
+    /// configure a custom extractor with [`DefaultExtractor::with_filters`]
+    /// instead.
+    pub fn extraction_filters(mut self, filters: ExtractionFilters) -> Self {
+        self.extraction_filters = Some(filters);
+        self
+    }
+
+    /// Set how article urls are normalized before they are queued, so the
+    /// same article linked with different urls is downloaded once.
+    ///
+    /// Default is [`UrlNormalizer::default`], use [`UrlNormalizer::none`]
+    /// to keep the urls unchanged. Downloaded articles whose canonical url
+    /// was already processed are always skipped, see
+    /// [`Extrablatt::dedup_stats`]. Not supported by the
+    /// [`crate::blocking::Extrablatt`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use extrablatt_v2::Extrablatt;
+    /// # use extrablatt_v2::dedup::UrlNormalizer;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let site = Extrablatt::builder("https://example.com")?
+    ///         .url_normalizer(UrlNormalizer::default().strip_param("ref"))
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn url_normalizer(mut self, url_normalizer: UrlNormalizer) -> Self {
+        self.url_normalizer = Some(url_normalizer);
+        self
+    }
+
+    /// Whether to also skip articles whose text nearly matches the text of
+    /// an article processed before, compared by their
+    /// [`crate::dedup::simhash`].
+    ///
+    /// Default is `false`. Not supported by the
+    /// [`crate::blocking::Extrablatt`].
+    pub fn dedup_content(mut self, dedup_content: bool) -> Self {
+        self.dedup_content = dedup_content;
+        self
+    }
+
+    /// Send one of the `user_agents` with each request instead of the
+    /// [`Config::user_agent`], in the order of the
+    /// [`ExtrablattBuilder::user_agent_rotation`].
+    ///
+    /// Each user agent is validated when the [`Extrablatt`] is built. The
+    /// user agent an article was requested with is recorded in its
+    /// [`crate::text::Diagnostics::user_agent`]. Not supported by the
+    /// [`crate::blocking::Extrablatt`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use extrablatt_v2::Extrablatt;
+    /// # use extrablatt_v2::user_agent::Rotation;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let site = Extrablatt::builder("https://example.com/")?
+    ///         .user_agents(vec!["agent/1".to_string(), "agent/2".to_string()])
+    ///         .user_agent_rotation(Rotation::Random { seed: 42 })
+    ///         .build()
+    ///         .await?;
+    /// #   Ok(())
+    /// # }
+    /// ```
+    pub fn user_agents<I, T>(mut self, user_agents: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.user_agents = user_agents.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Rotate the [`crate::user_agent::BROWSER_USER_AGENTS`] of current
+    /// desktop and mobile browsers, see [`ExtrablattBuilder::user_agents`].
+    pub fn browser_like_user_agent(self) -> Self {
+        self.user_agents(crate::user_agent::BROWSER_USER_AGENTS)
+    }
+
+    /// The order in which the [`ExtrablattBuilder::user_agents`] are sent.
+    ///
+    /// Default is [`Rotation::RoundRobin`].
+    pub fn user_agent_rotation(mut self, rotation: Rotation) -> Self {
+        self.user_agent_rotation = rotation;
+        self
+    }
+
+    /// Continue the crawl of the `state` taken with
+    /// [`Extrablatt::save_state`].
+    ///
+    /// Its articles and categories are queued, and articles and categories
+    /// it downloaded aren't requested again, even if they are found again.
+    /// Articles whose download failed are retried. Requests to hosts wait
+    /// for the delays of the crawl if a
+    /// [`ExtrablattBuilder::request_delay`] is set. Not supported by the
+    /// [`crate::blocking::Extrablatt`].
+    pub fn resume_from(mut self, state: CrawlState) -> Self {
+        self.resume_from = Some(state);
+        self
+    }
+
+    /// Create a new builder with a specific extractor.
+    pub async fn build_with_extractor<TExtractor: Extractor>(
+        mut self,
+        extractor: TExtractor,
+    ) -> Result<Extrablatt<TExtractor>, ExtrablattError> {
+        let base_url = self.base_url.take().ok_or(ExtrablattError::UrlNotInitialized)?;
+        if base_url.cannot_be_a_base() {
+            return Err(ExtrablattError::BaseUrlInvalid { url: base_url });
         }
-        let config = self.config.unwrap_or_default();
+        let mut config = self.config.take().unwrap_or_default();
+        if let Some(completeness) = self.completeness {
+            config.completeness = completeness;
+        }
+        let user_agents = UserAgentPool::new(
+            std::mem::take(&mut self.user_agents),
+            self.user_agent_rotation,
+        )?;
         #[cfg(target_arch = "wasm32")]
-        let client = { Client::builder().build().map_err(ExtrablattError::Reqwest)? };
+        let client = match &self.client {
+            Some(client) => client.clone(),
+            None => Client::builder().build().map_err(ExtrablattError::Reqwest)?,
+        };
         #[cfg(not(target_arch = "wasm32"))]
-        let client = {
-            let mut headers = self.headers.unwrap_or_else(|| HeaderMap::with_capacity(1));
-            if !headers.contains_key(USER_AGENT) {
-                headers.insert(
-                    USER_AGENT,
-                    config.user_agent.parse().map_err(|_| ExtrablattError::UserAgentParseError)?,
-                );
-            }
+        let client = if let Some(client) = &self.client {
+            client.clone()
+        } else {
             let mut client_builder = Client::builder()
-                .default_headers(headers)
-                .timeout(config.request_timeout);
+                .default_headers(self.default_headers(&config)?)
+                .timeout(self.timeout.unwrap_or(config.request_timeout))
+                .redirect(self.redirect_policy())
+                .danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+            if let Some(connect_timeout) = self.connect_timeout {
+                client_builder = client_builder.connect_timeout(connect_timeout);
+            }
 
             // Configure proxy if provided
-            if let Some(ref proxy_url) = self.proxy {
-                let proxy = reqwest::Proxy::all(proxy_url).map_err(ExtrablattError::Reqwest)?;
+            if let Some(proxy) = self.build_proxy()? {
                 client_builder = client_builder.proxy(proxy);
             }
+            #[cfg(feature = "cookies")]
+            if let Some(jar) = self.cookie_jar()? {
+                client_builder = client_builder.cookie_provider(jar);
+            }
 
             client_builder.build().map_err(ExtrablattError::Reqwest)?
         };
-        let resp = client.get(base_url.clone()).send().await;
-        let (main_page, _) = DocumentDownloadState::from_response(resp).await.map_err(|(_, err)| err)?;
+        let retry_policy = self.retry_policy.unwrap_or_default();
+        let throttle = Throttle::new(
+            self.max_concurrent_requests
+                .unwrap_or(Throttle::DEFAULT_MAX_CONCURRENT_REQUESTS),
+            self.request_delay.unwrap_or_default(),
+        );
+        let state = self.resume_from.unwrap_or_default();
+        for (host, delay) in state.pending_delays() {
+            throttle.delay_host(host, delay);
+        }
+        let main_page = {
+            let _permit = throttle.acquire(&base_url).await;
+            download(
+                &client,
+                &retry_policy,
+                self.http_cache.as_deref(),
+                &base_url,
+                user_agents.next(),
+                true,
+            )
+            .await?
+            .doc
+        };
         let mut paper = Extrablatt {
             client,
             language: self.language.unwrap_or_default(),
+            languages: self.languages,
             main_page,
             base_url,
             extractor,
-            categories: Default::default(),
-            articles: FnvHashMap::with_capacity_and_hasher(
+            queue: CrawlQueue::new(
                 config.max_doc_cache,
-                Default::default(),
+                self.category_filter,
+                self.article_url_filter.unwrap_or_default(),
             ),
+            feed_articles: Default::default(),
             config,
+            retry_policy,
+            image_registry: self.image_registry.unwrap_or_default(),
+            throttle,
+            http_cache: self.http_cache,
+            prefer_amp: self.prefer_amp,
+            follow_meta_refresh: self.follow_meta_refresh,
+            dedup: Deduplicator::new(self.url_normalizer.unwrap_or_default())
+                .content_fingerprints(self.dedup_content),
+            user_agents: Arc::new(user_agents),
+            visited: Arc::clone(state.visited()),
         };
         if self.categories {
             paper.insert_new_categories();
         }
+        for category in self.include_categories.into_iter().map(Category::new).chain(state.categories) {
+            if !paper.is_visited(&category.url) {
+                paper.queue.insert_category(category);
+            }
+        }
+        for article in state.frontier {
+            paper.queue_article(article);
+        }
+        if self.use_feeds {
+            for (feed, err) in paper.download_feeds().await.into_iter().filter_map(Result::err) {
+                log::debug!("Failed to download feed {}: {}", feed.url, err);
+            }
+        }
+        Ok(paper)
+    }
+    pub async fn build(mut self) -> Result<Extrablatt, ExtrablattError> {
+        let extractor = self.default_extractor();
+        self.build_with_extractor(extractor).await
+    }
+
+    /// Create a new [`crate::blocking::Extrablatt`] with a specific
+    /// extractor that doesn't need an async runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ExtrablattError::UnsupportedBlockingOption`] if an option
+    /// is set that only the async api supports, like
+    /// [`ExtrablattBuilder::image_registry`].
+    ///
+    /// # Panics
+    ///
+    /// Like all of [`reqwest::blocking`], this must not be called from
+    /// within an async runtime.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub fn build_blocking_with_extractor<TExtractor: Extractor>(
+        self,
+        extractor: TExtractor,
+    ) -> Result<crate::blocking::Extrablatt<TExtractor>, ExtrablattError> {
+        if let Some(option) = self.unsupported_blocking_option() {
+            return Err(ExtrablattError::UnsupportedBlockingOption { option });
+        }
+        let base_url = self.base_url.clone().ok_or(ExtrablattError::UrlNotInitialized)?;
+        if base_url.cannot_be_a_base() {
+            return Err(ExtrablattError::BaseUrlInvalid { url: base_url });
+        }
+        let mut config = self.config.clone().unwrap_or_default();
+        if let Some(completeness) = self.completeness {
+            config.completeness = completeness;
+        }
+        let mut client_builder = reqwest::blocking::Client::builder()
+            .default_headers(self.default_headers(&config)?)
+            .timeout(self.timeout.unwrap_or(config.request_timeout))
+            .redirect(self.redirect_policy())
+            .danger_accept_invalid_certs(self.danger_accept_invalid_certs);
+        if let Some(connect_timeout) = self.connect_timeout {
+            client_builder = client_builder.connect_timeout(connect_timeout);
+        }
+        if let Some(proxy) = self.build_proxy()? {
+            client_builder = client_builder.proxy(proxy);
+        }
+        #[cfg(feature = "cookies")]
+        if let Some(jar) = self.cookie_jar()? {
+            client_builder = client_builder.cookie_provider(jar);
+        }
+        let client = client_builder.build().map_err(ExtrablattError::Reqwest)?;
+        let queue = CrawlQueue::new(
+            config.max_doc_cache(),
+            self.category_filter,
+            self.article_url_filter.unwrap_or_default(),
+        );
+        let mut paper = crate::blocking::Extrablatt::new(
+            client,
+            base_url,
+            self.language.unwrap_or_default(),
+            extractor,
+            config,
+            self.retry_policy.unwrap_or_default(),
+            queue,
+        )?
+        .with_language_overrides(self.languages);
+        if self.categories {
+            paper.insert_new_categories();
+        }
+        for url in self.include_categories {
+            paper.insert_category(Category::new(url));
+        }
         Ok(paper)
     }
-    pub async fn build(self) -> Result<Extrablatt, ExtrablattError> {
-        self.build_with_extractor(Default::default()).await
+
+    /// Create a new [`crate::blocking::Extrablatt`] that doesn't need an
+    /// async runtime.
+    ///
+    /// # Panics
+    ///
+    /// Like all of [`reqwest::blocking`], this must not be called from
+    /// within an async runtime.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub fn build_blocking(mut self) -> Result<crate::blocking::Extrablatt, ExtrablattError> {
+        let extractor = self.default_extractor();
+        self.build_blocking_with_extractor(extractor)
+    }
+
+    /// The [`DefaultExtractor`] with the configured extraction filters.
+    fn default_extractor(&mut self) -> DefaultExtractor {
+        match self.extraction_filters.take() {
+            Some(filters) => DefaultExtractor::default().with_filters(filters),
+            None => DefaultExtractor::default(),
+        }
+    }
+
+    /// The first option that is set, but not supported by
+    /// [`crate::blocking::Extrablatt`].
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    fn unsupported_blocking_option(&self) -> Option<&'static str> {
+        [
+            ("image_registry", self.image_registry.is_some()),
+            ("request_delay", self.request_delay.is_some()),
+            ("http_cache", self.http_cache.is_some()),
+            ("use_feeds", self.use_feeds),
+            ("prefer_amp", self.prefer_amp),
+            ("follow_meta_refresh", self.follow_meta_refresh),
+            ("url_normalizer", self.url_normalizer.is_some()),
+            ("dedup_content", self.dedup_content),
+            ("user_agents", !self.user_agents.is_empty()),
+            ("resume_from", self.resume_from.is_some()),
+            ("client", self.client.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(option, is_set)| is_set.then_some(option))
+    }
+
+    /// The configured headers with a user agent.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default_headers(&self, config: &Config) -> Result<HeaderMap, ExtrablattError> {
+        let mut headers = self
+            .headers
+            .clone()
+            .unwrap_or_else(|| HeaderMap::with_capacity(1));
+        if !headers.contains_key(USER_AGENT) {
+            headers.insert(
+                USER_AGENT,
+                config
+                    .user_agent
+                    .parse()
+                    .map_err(|_| ExtrablattError::UserAgentParseError {
+                        user_agent: config.user_agent.clone(),
+                    })?,
+            );
+        }
+        Ok(headers)
+    }
+
+    /// The configured limit of redirects.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn redirect_policy(&self) -> reqwest::redirect::Policy {
+        self.max_redirects
+            .map(reqwest::redirect::Policy::limited)
+            .unwrap_or_default()
+    }
+
+    /// The configured proxy for all requests.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn build_proxy(&self) -> Result<Option<reqwest::Proxy>, ExtrablattError> {
+        self.proxy
+            .as_ref()
+            .map(|proxy_url| reqwest::Proxy::all(proxy_url).map_err(ExtrablattError::Reqwest))
+            .transpose()
+    }
+
+    /// The cookie jar shared by all requests.
+    #[cfg(all(feature = "cookies", not(target_arch = "wasm32")))]
+    fn cookie_jar(&self) -> Result<Option<Arc<crate::cookies::CookieJar>>, ExtrablattError> {
+        self.cookies.jar().map_err(|err| match err {
+            CookieError::InvalidHost(err) => ExtrablattError::InvalidCookieHost(err),
+            CookieError::InvalidCookie(name) => ExtrablattError::InvalidCookie(name),
+        })
     }
 }
 
@@ -802,6 +2032,14 @@ pub enum DocumentDownloadState {
         received: Instant,
         /// The parsed html body.
         doc: Document,
+        /// Whether a `304 Not Modified` response was answered by the
+        /// [`HttpCache`].
+        from_cache: bool,
+        /// The user agent the document was requested with, if user agents
+        /// are rotated, see [`ExtrablattBuilder::user_agents`].
+        user_agent: Option<String>,
+        /// The final url, status and headers of the response.
+        fetch_info: Box<FetchInfo>,
     },
     NoHttpSuccessResponse {
         /// Timestamp the response was received.
@@ -822,71 +2060,63 @@ pub enum DocumentDownloadState {
 
 impl DocumentDownloadState {
     /// Wraps the [`hyper::Response`] into the proper state.
+    ///
+    /// Non 2xx responses are only read into a document if
+    /// `http_success_only` is `false`.
     pub(crate) async fn from_response(
         response: Result<Response, reqwest::Error>,
-    ) -> Result<(Document, Instant), (Self, ExtrablattError)> {
+        attempts: usize,
+        http_success_only: bool,
+    ) -> Result<(Document, Instant, FetchInfo, Bytes), ExtrablattError> {
         match response {
             Ok(response) => {
-                if response.status().is_success() {
-                    Self::read_response(response).await
+                if response.status().is_success() || !http_success_only {
+                    Self::read_response(response, attempts).await
                 } else {
-                    Err((
-                        DocumentDownloadState::NoHttpSuccessResponse {
-                            received: Instant::now(),
-                        },
-                        ExtrablattError::NoHttpSuccessResponse { response },
-                    ))
+                    Err(ExtrablattError::http_status(response, attempts).await)
                 }
             }
-            Err(error) => Err((
-                DocumentDownloadState::HttpRequestFailure {
-                    received: Instant::now(),
-                },
-                ExtrablattError::HttpRequestFailure { error },
-            )),
+            Err(error) => Err(ExtrablattError::request_failure(error, attempts)),
         }
     }
 
     async fn read_response(
         response: Response,
-    ) -> Result<(Document, Instant), (DocumentDownloadState, ExtrablattError)> {
+        attempts: usize,
+    ) -> Result<(Document, Instant, FetchInfo, Bytes), ExtrablattError> {
+        let fetch_info = FetchInfo::from_response(&response);
+        let content_type = content_type(response.headers());
         match response.bytes().await {
-            Ok(body) => {
-                if let Ok(doc) = Document::from_read(&*body) {
-                    Ok((doc, Instant::now()))
-                } else {
-                    Err((
-                        DocumentDownloadState::DocumentReadFailure {
-                            received: Instant::now(),
-                        },
-                        ExtrablattError::ReadDocumentError { body },
-                    ))
-                }
-            }
-            Err(error) => Err((
-                DocumentDownloadState::HttpRequestFailure {
-                    received: Instant::now(),
-                },
-                ExtrablattError::HttpRequestFailure { error },
-            )),
+            Ok(body) => Self::read_body(body.clone(), content_type.as_deref())
+                .map(|(doc, received)| (doc, received, fetch_info, body)),
+            Err(error) => Err(ExtrablattError::HttpRequestFailure { error, attempts }),
         }
     }
 
-    /// If the error is due to an non 2xx response, try to read it into an
-    /// [`select::document::Document`] anyway.
-    async fn advance_non_http_success(
-        err: ExtrablattError,
-    ) -> Result<(Document, Instant), ExtrablattError> {
-        if let ExtrablattError::NoHttpSuccessResponse { response } = err {
-            match DocumentDownloadState::read_response(response).await {
-                Ok((doc, received)) => Ok((doc, received)),
-                Err((_, err)) => Err(err),
+    /// The state of a document whose download failed with the `error`.
+    pub(crate) fn from_error(error: &ExtrablattError) -> Self {
+        let received = Instant::now();
+        match error {
+            ExtrablattError::HttpStatus { .. } => {
+                DocumentDownloadState::NoHttpSuccessResponse { received }
             }
-        } else {
-            Err(err)
+            ExtrablattError::ReadDocumentError { .. } => {
+                DocumentDownloadState::DocumentReadFailure { received }
+            }
+            _ => DocumentDownloadState::HttpRequestFailure { received },
         }
     }
 
+    /// Parse the `body` of a response served with the `content_type`,
+    /// transcoded to UTF-8.
+    fn read_body(
+        body: Bytes,
+        content_type: Option<&str>,
+    ) -> Result<(Document, Instant), ExtrablattError> {
+        trace::record_bytes(body.len());
+        Ok((trace::parse(decode(&body, content_type).as_ref()), Instant::now()))
+    }
+
     /// The actual document if the request was successful.
     pub fn success_document(&self) -> Option<&Document> {
         match self {
@@ -895,6 +2125,15 @@ impl DocumentDownloadState {
         }
     }
 
+    /// The final url, status and headers of the response if the request was
+    /// successful.
+    pub fn fetch_info(&self) -> Option<&FetchInfo> {
+        match self {
+            DocumentDownloadState::Success { fetch_info, .. } => Some(fetch_info),
+            _ => None,
+        }
+    }
+
     pub fn is_http_failure(&self) -> bool {
         matches!(self, DocumentDownloadState::HttpRequestFailure { .. })
     }
@@ -914,6 +2153,197 @@ impl DocumentDownloadState {
     pub fn is_success(&self) -> bool {
         matches!(self, DocumentDownloadState::Success { .. })
     }
+
+    /// Whether the document was answered by the [`HttpCache`].
+    pub fn is_from_cache(&self) -> bool {
+        matches!(self, DocumentDownloadState::Success { from_cache: true, .. })
+    }
+}
+
+/// Extract the content of the article at the `url` in its language, see
+/// [`ExtrablattBuilder::language_for_host_path`] for the order the language
+/// is chosen in, and return it with the language.
+fn extract_article<'a, T: Extractor>(
+    extractor: &T,
+    languages: &LanguageOverrides,
+    default: &Language,
+    url: &Url,
+    doc: &'a Document,
+    base_url: &Url,
+) -> (ArticleContent<'a>, Language) {
+    let meta_lang = extractor.meta_language(doc);
+    let detected = extractor.detect_language(doc);
+    let language = languages
+        .language(url)
+        .or_else(|| meta_lang.clone())
+        .or_else(|| detected.as_ref().map(|detected| detected.language.clone()))
+        .unwrap_or_else(|| default.clone());
+    let mut content = extractor.article_content_in_language(doc, Some(base_url), language.clone());
+    content.language = meta_lang;
+    content.detected_language = detected;
+    (content, language)
+}
+
+/// Record the rotated `user_agent` an article was requested with in its
+/// [`crate::text::Diagnostics`].
+fn record_user_agent(content: &mut ArticleContent<'_>, user_agent: Option<String>) {
+    if let Some(diagnostics) = content.diagnostics.as_mut() {
+        diagnostics.user_agent = user_agent;
+    }
+}
+
+/// A downloaded and parsed page.
+#[derive(Debug)]
+pub(crate) struct DownloadedDocument {
+    /// The parsed html body.
+    pub(crate) doc: Document,
+    /// Timestamp the response was received.
+    pub(crate) received: Instant,
+    /// Whether a `304 Not Modified` response was answered by the
+    /// [`HttpCache`].
+    pub(crate) from_cache: bool,
+    /// The rotated user agent the page was requested with.
+    pub(crate) user_agent: Option<String>,
+    /// The final url, status and headers of the response.
+    pub(crate) fetch_info: FetchInfo,
+    /// The response body as it was received, before it was transcoded.
+    pub(crate) body: Bytes,
+}
+
+impl DownloadedDocument {
+    /// The [`DocumentDownloadState::Success`] of the page.
+    fn into_state(self) -> DocumentDownloadState {
+        DocumentDownloadState::Success {
+            received: self.received,
+            doc: self.doc,
+            from_cache: self.from_cache,
+            user_agent: self.user_agent,
+            fetch_info: Box::new(self.fetch_info),
+        }
+    }
+}
+
+/// Send a GET request to the `url`, conditional if the `http_cache` holds an
+/// entry for it, and parse the response.
+///
+/// A `304 Not Modified` response is answered with the cached body and bodies
+/// of new success responses are stored in the cache. Other non 2xx responses
+/// are only read if `http_success_only` is `false`. The `user_agent`
+/// replaces the one of the client.
+async fn download(
+    client: &Client,
+    retry_policy: &RetryPolicy,
+    http_cache: Option<&dyn HttpCache>,
+    url: &Url,
+    user_agent: Option<&HeaderValue>,
+    http_success_only: bool,
+) -> Result<DownloadedDocument, ExtrablattError> {
+    let cached = http_cache.and_then(|cache| cache.get(url));
+    let mut headers = cached
+        .as_ref()
+        .map(CachedEntry::conditional_headers)
+        .unwrap_or_default();
+    if let Some(user_agent) = user_agent {
+        headers.insert(USER_AGENT, user_agent.clone());
+    }
+    let downloaded = |(doc, received): (Document, Instant), from_cache, fetch_info, body| DownloadedDocument {
+        doc,
+        received,
+        from_cache,
+        user_agent: user_agent.and_then(user_agent_str),
+        fetch_info,
+        body,
+    };
+    let fetch = async {
+        let (response, attempts) = retry_policy.get_with_headers(client, url.clone(), headers).await;
+        match (response, cached, http_cache) {
+            (Ok(response), Some(cached), _) if response.status() == StatusCode::NOT_MODIFIED => {
+                // the page is served with the status and headers it was cached with
+                let fetch_info = FetchInfo::new(response.url().clone(), cached.status, cached.headers.clone());
+                DocumentDownloadState::read_body(cached.body.clone(), cached.content_type())
+                    .map(|page| downloaded(page, true, fetch_info, cached.body.clone()))
+            }
+            (Ok(response), _, Some(cache)) if response.status().is_success() => {
+                let fetch_info = FetchInfo::from_response(&response);
+                let headers = response.headers().clone();
+                match response.bytes().await {
+                    Ok(body) => {
+                        if let Some(entry) = CachedEntry::from_response(fetch_info.status, &headers, body.clone()) {
+                            cache.put(url, entry);
+                        }
+                        DocumentDownloadState::read_body(body.clone(), content_type(&headers).as_deref())
+                            .map(|page| downloaded(page, false, fetch_info, body))
+                    }
+                    Err(error) => Err(ExtrablattError::HttpRequestFailure { error, attempts }),
+                }
+            }
+            (response, ..) => DocumentDownloadState::from_response(response, attempts, http_success_only)
+                .await
+                .map(|(doc, received, fetch_info, body)| downloaded((doc, received), false, fetch_info, body)),
+        }
+    };
+    trace::fetch(url, fetch, |page| page.fetch_info.status).await
+}
+
+/// How the page of an article is downloaded.
+#[derive(Debug, Clone, Copy)]
+struct ArticleDownloadOptions {
+    /// Whether the AMP version of the page is downloaded instead.
+    prefer_amp: bool,
+    /// Whether the meta refresh of a stub page is followed.
+    follow_meta_refresh: bool,
+    /// Whether only 2xx responses are read.
+    http_success_only: bool,
+}
+
+/// Like [`download`], but if `follow_meta_refresh` is set and the page is a
+/// stub that redirects with a meta refresh, the page it refreshes to is
+/// downloaded instead, and if `prefer_amp` is set and the page advertises an
+/// AMP version, the document of the AMP page is returned instead.
+///
+/// Refreshes that loop or exceed
+/// [`crate::extract_meta_refresh::MAX_META_REFRESH_HOPS`] fail with
+/// [`ExtrablattError::TooManyMetaRefreshes`]. Falls back to the document of
+/// the page itself if the AMP page can't be downloaded.
+async fn download_article(
+    client: &Client,
+    retry_policy: &RetryPolicy,
+    http_cache: Option<&dyn HttpCache>,
+    url: &Url,
+    user_agent: Option<&HeaderValue>,
+    options: ArticleDownloadOptions,
+) -> Result<DownloadedDocument, ExtrablattError> {
+    let http_success_only = options.http_success_only;
+    let mut page = download(client, retry_policy, http_cache, url, user_agent, http_success_only).await?;
+    while options.follow_meta_refresh
+        && let Some(target) = meta_refresh_url(&page.doc, Some(&page.fetch_info.final_url))
+    {
+        if !can_follow_meta_refresh(&page.fetch_info, &target) {
+            return Err(ExtrablattError::TooManyMetaRefreshes {
+                url: page.fetch_info.final_url,
+            });
+        }
+        let target_page = download(client, retry_policy, http_cache, &target, user_agent, http_success_only).await?;
+        page = DownloadedDocument {
+            fetch_info: page.fetch_info.refreshed(target_page.fetch_info),
+            ..target_page
+        };
+    }
+    if !options.prefer_amp {
+        return Ok(page);
+    }
+    let page_url = &page.fetch_info.final_url;
+    let amp_url = match amp_url(&page.doc, Some(page_url)) {
+        Some(amp_url) if amp_url != *page_url => amp_url,
+        _ => return Ok(page),
+    };
+    match download(client, retry_policy, http_cache, &amp_url, user_agent, true).await {
+        Ok(amp_page) => Ok(amp_page),
+        Err(err) => {
+            log::debug!("Failed to download AMP page {}: {}", amp_url, err);
+            Ok(page)
+        }
+    }
 }
 
 impl Default for DocumentDownloadState {
@@ -956,6 +2386,16 @@ pub struct Config {
     request_timeout: Duration,
     /// Number of concurrent requests for downloading articles/categories.
     concurrency: usize,
+    /// Images that occur in more than this percentage of the articles are
+    /// removed as site furniture.
+    furniture_image_pct: Option<usize>,
+    /// Whether to skip documents whose [`DocumentStats`] don't look like an
+    /// article before the extraction.
+    prefilter_documents: bool,
+    /// Whether articles behind a paywall are incomplete.
+    reject_paywalled: bool,
+    /// The fields an article needs to be complete.
+    completeness: Completeness,
 }
 
 impl Config {
@@ -983,86 +2423,99 @@ impl Config {
         self.concurrency
     }
 
-    /// Checks that the article fulfills the configured restrictions.
-    pub fn is_complete<'a>(&self, article: &ArticleContent<'a>) -> bool {
-        macro_rules! range_check {
-            ($($el:expr => ($min:expr ; $max:expr)),*) => {
-                $(
-                 if let Some(min) = $min {
-                    if let Some(txt) = &$el {
-                        if min > txt.len() {
-                            return false
-                        }
-                    } else {
-                        return false
-                    }
-                 }
+    /// Images that occur in more than this percentage of the articles are
+    /// removed as site furniture.
+    #[inline]
+    pub fn furniture_image_pct(&self) -> Option<usize> {
+        self.furniture_image_pct
+    }
 
-                if let Some(max) = $max {
-                    if let Some(txt) = &$el {
-                        if max < txt.len() {
-                            return false
-                        }
-                    }
-                }
+    /// Whether documents that don't look like an article are skipped before
+    /// the extraction.
+    #[inline]
+    pub fn prefilter_documents(&self) -> bool {
+        self.prefilter_documents
+    }
 
-                )*
-            };
-        }
+    /// Whether articles whose page only holds a teaser of a paywall are
+    /// incomplete.
+    #[inline]
+    pub fn reject_paywalled(&self) -> bool {
+        self.reject_paywalled
+    }
 
-        let mut words = None;
-        if let Some(min_word_count) = self.min_word_count {
-            if let Some(txt) = &article.text {
-                let words_ctn = ArticleTextNodeExtractor::words(txt.as_ref()).count();
-                if min_word_count > words_ctn {
-                    return false;
-                }
-                words = Some(words_ctn);
-            } else {
-                return false;
-            }
-        }
+    /// Whether non 2XX responses are discarded.
+    #[inline]
+    pub fn http_success_only(&self) -> bool {
+        self.http_success_only
+    }
 
-        if let Some(max_word_count) = self.max_word_count {
-            if let Some(txt) = &article.text {
-                let words_ctn =
-                    words.unwrap_or_else(|| ArticleTextNodeExtractor::words(txt.as_ref()).count());
-                if max_word_count < words_ctn {
-                    return false;
-                }
-            }
-        }
+    /// Max. number of urls to cache for a news source.
+    #[inline]
+    pub fn max_doc_cache(&self) -> usize {
+        self.max_doc_cache
+    }
 
-        range_check!(
-            article.text => (self.min_text_len ; self.max_text_len),
-            article.title => (self.min_title_len ; self.max_title_len)
-        );
+    /// The policy for the fields an article needs to be complete.
+    #[inline]
+    pub fn completeness(&self) -> &Completeness {
+        &self.completeness
+    }
 
-        if let Some(min_keywords) = self.min_keywords {
-            if min_keywords > article.keywords.len() {
-                return false;
-            }
-        }
+    /// Checks that the article fulfills the configured restrictions.
+    pub fn is_complete<'a>(&self, article: &ArticleContent<'a>) -> bool {
+        self.incompleteness(article).is_empty()
+    }
 
-        if let Some(max_keywords) = self.max_keywords {
-            if max_keywords < article.keywords.len() {
-                return false;
-            }
+    /// The configured restrictions and [`Completeness`] criteria the article
+    /// fails.
+    ///
+    /// A missing text or title only fails their min restrictions.
+    pub fn incompleteness(&self, article: &ArticleContent<'_>) -> Incompleteness {
+        let mut incompleteness = self.completeness.check(article);
+        let failed = &mut incompleteness.failed;
+        if self.reject_paywalled && article.access.is_paywalled() {
+            failed.push(Criterion::Paywalled);
         }
 
-        if let Some(min_authors) = self.min_authors {
-            if min_authors > article.authors.len() {
-                return false;
+        let words = (self.min_word_count.is_some() || self.max_word_count.is_some())
+            .then(|| article.text.as_ref().map(|_| word_count(article)))
+            .flatten();
+        let measures = [
+            (Measure::WordCount, words, self.min_word_count, self.max_word_count),
+            (
+                Measure::TextLen,
+                article.text.as_ref().map(|text| text.len()),
+                self.min_text_len,
+                self.max_text_len,
+            ),
+            (
+                Measure::TitleLen,
+                article.title.as_ref().map(|title| title.len()),
+                self.min_title_len,
+                self.max_title_len,
+            ),
+            (Measure::Keywords, Some(article.keywords.len()), self.min_keywords, self.max_keywords),
+            (Measure::Authors, Some(article.authors.len()), self.min_authors, self.max_authors),
+        ];
+        for (measure, value, min, max) in measures {
+            if let Some(min) = min {
+                let value = value.unwrap_or_default();
+                // the completeness policy may have reported the same measure
+                let reported = failed
+                    .iter()
+                    .any(|criterion| matches!(criterion, Criterion::TooFew { measure: m, .. } if *m == measure));
+                if value < min && !reported {
+                    failed.push(Criterion::TooFew { measure, value, min });
+                }
             }
-        }
-
-        if let Some(max_authors) = self.max_authors {
-            if max_authors < article.authors.len() {
-                return false;
+            if let (Some(max), Some(value)) = (max, value) {
+                if value > max {
+                    failed.push(Criterion::TooMany { measure, value, max });
+                }
             }
         }
-
-        true
+        incompleteness
     }
 }
 
@@ -1106,9 +2559,19 @@ pub struct ConfigBuilder {
     request_timeout: Option<Duration>,
     /// Number of concurrent requests for downloading articles/categories.
     concurrency: Option<usize>,
+    /// Max. percentage of articles an image may occur in before it's removed.
+    furniture_image_pct: Option<usize>,
+    /// Whether to skip documents that don't look like an article.
+    prefilter_documents: Option<bool>,
+    /// Whether articles behind a paywall are incomplete.
+    reject_paywalled: Option<bool>,
+    /// The fields an article needs to be complete.
+    completeness: Option<Completeness>,
 }
 
 impl ConfigBuilder {
+    /// Min number of words of the text. Applies in addition to
+    /// [`Completeness::min_word_count`], so the larger of both wins.
     pub fn min_word_count(mut self, min_word_count: usize) -> Self {
         self.min_word_count = Some(min_word_count);
         self
@@ -1192,6 +2655,42 @@ impl ConfigBuilder {
         self
     }
 
+    /// Remove images that occur in more than `furniture_image_pct` percent
+    /// of the crawled articles, like logos and byline avatars.
+    ///
+    /// See [`crate::image::ImageRegistry`].
+    pub fn furniture_image_pct(mut self, furniture_image_pct: usize) -> Self {
+        self.furniture_image_pct = Some(furniture_image_pct);
+        self
+    }
+
+    /// Skip downloaded documents that don't look like an article, like
+    /// listing pages, without running the full extraction.
+    ///
+    /// See [`DocumentStats::looks_like_article`].
+    pub fn prefilter_documents(mut self, prefilter_documents: bool) -> Self {
+        self.prefilter_documents = Some(prefilter_documents);
+        self
+    }
+
+    /// Treat articles whose page only holds a teaser of a paywall as
+    /// incomplete, so they fail with [`ExtrablattError::IncompleteArticle`].
+    ///
+    /// Default is `false`, metered articles are never rejected. See
+    /// [`crate::paywall::content_access`].
+    pub fn reject_paywalled(mut self, reject_paywalled: bool) -> Self {
+        self.reject_paywalled = Some(reject_paywalled);
+        self
+    }
+
+    /// The fields an article needs to be complete, see [`Completeness`].
+    ///
+    /// Default is [`Completeness::lenient`], that requires nothing.
+    pub fn completeness(mut self, completeness: Completeness) -> Self {
+        self.completeness = Some(completeness);
+        self
+    }
+
     pub fn build(self) -> Config {
         Config {
             min_word_count: self.min_word_count,
@@ -1212,6 +2711,10 @@ impl ConfigBuilder {
                 .request_timeout
                 .unwrap_or_else(|| Duration::from_secs(Config::DEFAULT_REQUEST_TIMEOUT_SEC)),
             concurrency: self.concurrency.unwrap_or(Config::DEFAULT_CONCURRENCY),
+            furniture_image_pct: self.furniture_image_pct,
+            prefilter_documents: self.prefilter_documents.unwrap_or_default(),
+            reject_paywalled: self.reject_paywalled.unwrap_or_default(),
+            completeness: self.completeness.unwrap_or_default(),
         }
     }
 
@@ -1237,10 +2740,148 @@ impl ConfigBuilder {
             user_agent: None,
             request_timeout: None,
             concurrency: None,
+            furniture_image_pct: None,
+            prefilter_documents: None,
+            reject_paywalled: None,
+            completeness: None,
         }
     }
 }
 
+/// The categories and articles of a crawl with their download state and
+/// the filters that decide which of them are queued.
+///
+/// Shared by [`Extrablatt`] and the blocking `Extrablatt`, so both queue,
+/// order and record their downloads the same way.
+#[derive(Debug)]
+pub(crate) struct CrawlQueue {
+    /// Cache for retrieved articles.
+    articles: FnvHashMap<ArticleUrl, DocumentDownloadState>,
+    /// All known categories for this newspaper.
+    categories: FnvHashMap<Category, DocumentDownloadState>,
+    /// Which categories and the article urls found in them are kept.
+    category_filter: Option<UrlFilter>,
+    /// Decides which article urls are downloaded and in which order.
+    article_url_filter: ArticleUrlFilter,
+    /// Number of queued and rejected article urls per class.
+    url_class_stats: UrlClassStats,
+}
+
+impl CrawlQueue {
+    pub(crate) fn new(
+        max_doc_cache: usize,
+        category_filter: Option<UrlFilter>,
+        article_url_filter: ArticleUrlFilter,
+    ) -> Self {
+        Self {
+            articles: FnvHashMap::with_capacity_and_hasher(max_doc_cache, Default::default()),
+            categories: Default::default(),
+            category_filter,
+            article_url_filter,
+            url_class_stats: UrlClassStats::default(),
+        }
+    }
+
+    pub(crate) fn articles(&self) -> &FnvHashMap<ArticleUrl, DocumentDownloadState> {
+        &self.articles
+    }
+
+    pub(crate) fn categories(&self) -> &FnvHashMap<Category, DocumentDownloadState> {
+        &self.categories
+    }
+
+    pub(crate) fn url_class_stats(&self) -> &UrlClassStats {
+        &self.url_class_stats
+    }
+
+    /// Whether the category or article url found in a category passes the
+    /// category filter.
+    pub(crate) fn is_wanted_url(&self, url: &Url) -> bool {
+        self.category_filter
+            .as_ref()
+            .map(|filter| filter.matches(url))
+            .unwrap_or(true)
+    }
+
+    /// Insert the category as not requested, unless it is already known.
+    pub(crate) fn insert_category(&mut self, category: Category) {
+        self.categories
+            .entry(category)
+            .or_insert(DocumentDownloadState::NotRequested);
+    }
+
+    /// Insert the article as not requested, unless the article url filter
+    /// rejects it. The caller checks whether it's known already.
+    pub(crate) fn insert_article(&mut self, article: ArticleUrl) {
+        let class = self.classify(&article.url);
+        if !class.is_rejected() {
+            self.articles.insert(article, DocumentDownloadState::NotRequested);
+        }
+    }
+
+    /// The class of the article url, counted in the [`UrlClassStats`].
+    pub(crate) fn classify(&mut self, url: &Url) -> UrlClass {
+        let class = self.article_url_filter.classify(url);
+        self.url_class_stats.count(class);
+        class
+    }
+
+    /// The class of the article url, without counting it.
+    pub(crate) fn article_class(&self, url: &Url) -> UrlClass {
+        self.article_url_filter.classify(url)
+    }
+
+    /// Sort the article urls, likely articles first.
+    pub(crate) fn sort_article_urls(&self, urls: &mut [Url]) {
+        urls.sort_by_cached_key(|url| self.article_class(url));
+    }
+
+    /// The categories that weren't requested yet.
+    pub(crate) fn not_requested_categories(&self) -> Vec<Category> {
+        self.categories
+            .iter()
+            .filter(|(_, state)| state.is_not_requested())
+            .map(|(cat, _)| cat.clone())
+            .collect()
+    }
+
+    /// The urls of the articles that weren't requested yet, likely articles
+    /// first.
+    pub(crate) fn not_requested_article_urls(&self) -> Vec<Url> {
+        let mut urls: Vec<_> = self
+            .articles
+            .iter()
+            .filter(|(_, state)| state.is_not_requested())
+            .map(|(article, _)| article.url.clone())
+            .collect();
+        self.sort_article_urls(&mut urls);
+        urls
+    }
+
+    /// Record the download of the category.
+    pub(crate) fn set_category_state(&mut self, category: Category, state: DocumentDownloadState) {
+        self.categories.insert(category, state);
+    }
+
+    /// Record the download of the queued article at `url`.
+    pub(crate) fn set_article_state(&mut self, url: &Url, state: DocumentDownloadState) {
+        if let Some(entry) = self.articles.get_mut(url) {
+            *entry = state;
+        }
+    }
+
+    /// Iterator over all known articles and their extracted content.
+    pub(crate) fn iter_articles<'a, T: Extractor>(
+        &'a self,
+        extractor: &'a T,
+        language: Language,
+        languages: &'a LanguageOverrides,
+        base_url: &'a Url,
+    ) -> ArticleDownloadIter<'a, T> {
+        ArticleDownloadIter::new(self.articles.iter(), extractor, language, languages, base_url)
+    }
+}
+
 /// Iterator over the downloaded articles.
 pub struct ArticleDownloadIter<'a, T: Extractor> {
     /// Each found url for an article paired with the result of it's request.
@@ -1249,22 +2890,41 @@ pub struct ArticleDownloadIter<'a, T: Extractor> {
     extractor: &'a T,
     /// Language of the news source.
     language: Language,
+    /// Languages of articles that differ from the language of the news
+    /// source.
+    languages: &'a LanguageOverrides,
     /// Base url of the news source.
     base_url: &'a Url,
 }
 
 impl<'a, T: Extractor> ArticleDownloadIter<'a, T> {
+    pub(crate) fn new(
+        inner: std::collections::hash_map::Iter<'a, ArticleUrl, DocumentDownloadState>,
+        extractor: &'a T,
+        language: Language,
+        languages: &'a LanguageOverrides,
+        base_url: &'a Url,
+    ) -> Self {
+        Self {
+            inner,
+            extractor,
+            language,
+            languages,
+            base_url,
+        }
+    }
+
     /// All successfully retrieved Articles.
     pub fn successes(self) -> impl Iterator<Item = (&'a ArticleUrl, ArticleContent<'a>)> + 'a {
         let extractor = self.extractor;
         let language = self.language;
+        let languages = self.languages;
         let base_url = self.base_url;
         self.inner.filter_map(move |(url, doc)| {
-            if let DocumentDownloadState::Success { doc, .. } = doc {
-                Some((
-                    url,
-                    extractor.article_content(doc, Some(base_url), Some(language.clone())),
-                ))
+            if let DocumentDownloadState::Success { doc, user_agent, .. } = doc {
+                let (mut content, _) = extract_article(extractor, languages, &language, &url.url, doc, base_url);
+                record_user_agent(&mut content, user_agent.clone());
+                Some((url, content))
             } else {
                 None
             }
@@ -1285,3 +2945,678 @@ impl<'a, T: Extractor> DerefMut for ArticleDownloadIter<'a, T> {
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_server::{self, html, status};
+
+    use crate::url_class::{classify_article_url, RejectRule};
+
+    use super::*;
+
+    /// The text of the article in the `content:encoded` of the feed.
+    const FEED_ARTICLE_TEXT: &str = "The harbour will be expanded over the next ten years.";
+
+    /// Serves a category at `/news` with six article links, the first of
+    /// which is missing, a category at `/local` with links to the same
+    /// article under different urls and a main page that advertises a RSS
+    /// feed of two other articles and a third whose full text is in the
+    /// feed, and which is missing as page. `/redirect/{n}` redirects to
+    /// `/redirect/{n + 1}` and `/moved/{slug}` to `/news/{slug}`. A category
+    /// at `/editions` links to an Arabic and a Russian article.
+    fn serve() -> Url {
+        test_server::serve(|request| {
+            let path = request.path.as_str();
+            if let Some(n) = path.strip_prefix("/redirect/").and_then(|n| n.parse::<usize>().ok()) {
+                format!(
+                    "HTTP/1.1 302 Found\r\nLocation: /redirect/{}\r\nConnection: close\r\n\r\n",
+                    n + 1
+                )
+            } else if let Some(slug) = path.strip_prefix("/moved/") {
+                format!(
+                    "HTTP/1.1 301 Moved Permanently\r\nLocation: /news/{}\r\nConnection: close\r\n\r\n",
+                    slug
+                )
+            } else if path == "/news" {
+                let links = (0..6)
+                    .map(|i| {
+                        format!(
+                            "<a href=\"/news/harbour-expansion-approved-by-the-council-{}.html\">Harbour</a>",
+                            i
+                        )
+                    })
+                    .collect::<String>();
+                html(links)
+            } else if path == "/local" {
+                html(format!(
+                    "<a href=\"/news/{0}\">Harbour</a><a href=\"/news/{0}?utm_source=home\">Harbour</a>\
+                     <a href=\"/news/{0}#comments\">Comments</a><a href=\"/local/{0}\">Harbour</a>",
+                    "harbour-expansion-approved-by-the-council-1.html"
+                ))
+            } else if path == "/editions" {
+                html(
+                    "<a href=\"/ar/news/harbour-expansion-approved-by-the-council.html\">الميناء</a>\
+                     <a href=\"/ru/news/harbour-expansion-approved-by-the-council.html\">Порт</a>",
+                )
+            } else if path == "/briefs" {
+                html(
+                    "<a href=\"/news/harbour-brief-of-the-council-meeting-1.html\">Brief</a>\
+                     <a href=\"/news/harbour-brief-of-the-council-meeting-2.html\">Brief</a>",
+                )
+            } else if path.contains("-brief-") {
+                html("<html><body><h1>Harbour</h1><p>The council met.</p></body></html>")
+            } else if path.starts_with("/ar/") {
+                html(include_str!("../tests/fixtures/multilingual/ar.html"))
+            } else if path.starts_with("/ru/") {
+                html(include_str!("../tests/fixtures/multilingual/ru.html"))
+            } else if path == "/" {
+                html(
+                    "<html><head><link rel=\"alternate\" type=\"application/rss+xml\" href=\"/feed.xml\">\
+                     </head><body></body></html>",
+                )
+            } else if path == "/feed.xml" {
+                let full = format!("<p>{}</p>", FEED_ARTICLE_TEXT).repeat(10);
+                html(format!(
+                    "<rss version=\"2.0\"><channel>\
+                     <item><title>Harbour</title><link>/feed/harbour.html</link></item>\
+                     <item><title>Council</title><link>/feed/council.html</link></item>\
+                     <item><title>Expansion</title><link>/feed/expansion.html</link>\
+                     <dc:creator>Jane Doe</dc:creator>\
+                     <content:encoded><![CDATA[{}]]></content:encoded></item>\
+                     </channel></rss>",
+                    full
+                ))
+            } else if path.ends_with("-0.html") || path == "/feed/expansion.html" {
+                status("404 Not Found")
+            } else {
+                let slug = path.rsplit('/').next().unwrap_or_default();
+                html(format!(
+                    "<html><head><title>{}</title><link rel=\"canonical\" href=\"/news/{}\"></head>\
+                     <body><div itemprop=\"articleBody\">\
+                     <p>The harbour will be expanded over the next ten years.</p></div></body></html>",
+                    path, slug
+                ))
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn article_stream_yields_articles_and_errors() {
+        let url = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("news").unwrap()])
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        assert_eq!(paper.articles().len(), 6);
+
+        let results: Vec<_> = paper.article_stream().collect().await;
+        assert_eq!(results.len(), 6);
+        assert_eq!(results.iter().filter(|res| res.is_err()).count(), 1);
+        assert!(results
+            .iter()
+            .flatten()
+            .all(|article| article.fetch_info.as_ref().map(|info| info.status) == Some(StatusCode::OK)));
+
+        // only the failed download stays cached
+        assert_eq!(paper.articles().len(), 1);
+        assert!(paper.articles().values().all(|state| !state.is_not_requested()));
+    }
+
+    #[tokio::test]
+    async fn downloaded_documents_are_prefiltered_by_the_stream() {
+        let url = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("briefs").unwrap()])
+            .retry_policy(RetryPolicy::none())
+            .config(Config::builder().prefilter_documents(true).build())
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        assert_eq!(paper.download_articles().await.successes().count(), 2);
+
+        let results: Vec<_> = paper.into_stream().collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|res| matches!(res, Err(ExtrablattError::SkippedDocument { .. }))));
+    }
+
+    #[cfg(feature = "serde0")]
+    #[tokio::test]
+    async fn crawl_into_sinks() {
+        use crate::sink::{HtmlArchiveSink, NdjsonSink};
+
+        async fn crawl(url: &Url) -> Extrablatt {
+            let mut paper = Extrablatt::builder(url.clone())
+                .unwrap()
+                .categories(false)
+                .include_categories(vec![url.join("news").unwrap()])
+                .retry_policy(RetryPolicy::none())
+                .build()
+                .await
+                .unwrap();
+            paper.download_all_remaining_categories().await;
+            paper
+        }
+
+        let url = serve();
+        let mut sink = NdjsonSink::new(Vec::new());
+        assert_eq!(crawl(&url).await.download_articles_into(&mut sink).await.unwrap(), 5);
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        let articles: Vec<PureArticle> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(articles.len(), 5);
+        assert!(articles.iter().all(|article| article.url.path().starts_with("/news/harbour")));
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink = HtmlArchiveSink::new(dir.path()).unwrap();
+        assert_eq!(crawl(&url).await.download_articles_into(&mut sink).await.unwrap(), 5);
+        for n in 1..=5 {
+            let json = std::fs::read_to_string(dir.path().join(format!("{:06}.json", n))).unwrap();
+            let article: PureArticle = serde_json::from_str(&json).unwrap();
+            let html = std::fs::read_to_string(dir.path().join(format!("{:06}.html", n))).unwrap();
+            let path = article.url.path();
+            let slug = path.rsplit('/').next().unwrap();
+            // the response body is written unchanged, not the parsed document
+            assert_eq!(
+                html,
+                format!(
+                    "<html><head><title>{}</title><link rel=\"canonical\" href=\"/news/{}\"></head>\
+                     <body><div itemprop=\"articleBody\">\
+                     <p>The harbour will be expanded over the next ten years.</p></div></body></html>",
+                    path, slug
+                )
+            );
+        }
+        assert!(!dir.path().join("000006.json").exists());
+
+        // a second sink on the same directory continues the numbering
+        let mut sink = HtmlArchiveSink::new(dir.path()).unwrap();
+        assert_eq!(crawl(&url).await.download_articles_into(&mut sink).await.unwrap(), 5);
+        assert_eq!(sink.count(), 5);
+        assert!(dir.path().join("000010.html").exists());
+        assert!(!dir.path().join("000011.json").exists());
+    }
+
+    #[tokio::test]
+    async fn fetch_info_after_redirect() {
+        let url = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await
+            .unwrap();
+        let slug = "harbour-expansion-approved-by-the-council-1.html";
+        let requested = url.join(&format!("moved/{}", slug)).unwrap();
+        let page = paper.get_document(requested.clone()).await.unwrap();
+        let fetch_info = &page.fetch_info;
+        assert_eq!(fetch_info.final_url, url.join(&format!("news/{}", slug)).unwrap());
+        assert!(fetch_info.is_redirected(&requested));
+        assert_eq!(fetch_info.status, StatusCode::OK);
+        assert_eq!(fetch_info.content_type.as_ref().map(|mime| mime.essence_str()), Some("text/html"));
+
+        let article = paper.article_from_document(requested, page).unwrap();
+        let final_url = article.fetch_info.as_ref().map(|info| &info.final_url);
+        assert_eq!(article.canonical_url(), final_url);
+    }
+
+    #[tokio::test]
+    async fn injected_client_is_used() {
+        // answers only requests with the default header of the shared client
+        let url = test_server::serve(|request| match request.header("x-client") {
+            Some("shared") => html("<html><head></head><body></body></html>"),
+            _ => status("403 Forbidden"),
+        });
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-client", HeaderValue::from_static("shared"));
+        let client = Client::builder().default_headers(headers).build().unwrap();
+        let paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            // ignored in favor of the client
+            .proxy("http://127.0.0.1:9")
+            .client(client)
+            .build()
+            .await;
+        assert!(paper.is_ok());
+
+        let paper = Extrablatt::builder(url)
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await;
+        assert!(matches!(paper, Err(ExtrablattError::HttpStatus { .. })));
+    }
+
+    #[cfg(feature = "cookies")]
+    #[tokio::test]
+    async fn cookies_are_sent() {
+        // the main page needs the seeded consent cookie and sets a session
+        // cookie the category needs
+        let url = test_server::serve(|request| {
+            let cookie = request.header("cookie").unwrap_or_default();
+            match request.path.as_str() {
+                "/" if cookie.contains("gdpr=1") => "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\
+                     Set-Cookie: session=abc; Path=/\r\nConnection: close\r\n\r\n\
+                     <html><head></head><body></body></html>"
+                    .to_string(),
+                "/news" if cookie.contains("session=abc") => html("<html><head></head><body></body></html>"),
+                _ => status("403 Forbidden"),
+            }
+        });
+
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            .cookies_for("127.0.0.1", vec![("gdpr", "1")])
+            .cookie_store(true)
+            .build()
+            .await
+            .unwrap();
+        let category = Category::new(url.join("news").unwrap());
+        assert!(paper.download_category(category).await.is_ok());
+
+        let paper = Extrablatt::builder(url)
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            .cookie_store(true)
+            .build()
+            .await;
+        assert!(matches!(paper, Err(ExtrablattError::HttpStatus { .. })));
+    }
+
+    #[cfg(feature = "cookies")]
+    #[tokio::test]
+    async fn invalid_cookie_host() {
+        let paper = Extrablatt::builder("https://www.example.com")
+            .unwrap()
+            .cookies_for("www example.com", vec![("gdpr", "1")])
+            .build()
+            .await;
+        assert!(matches!(paper, Err(ExtrablattError::InvalidCookieHost(_))));
+        let paper = Extrablatt::builder("https://www.example.com")
+            .unwrap()
+            .cookies_for("www.example.com", vec![("gdpr", "1; Domain=example.org")])
+            .build()
+            .await;
+        assert!(matches!(paper, Err(ExtrablattError::InvalidCookie(name)) if name == "gdpr"));
+    }
+
+    #[tokio::test]
+    async fn incomplete_articles_are_rejected_or_reported() {
+        let url = serve();
+        let article_url = url.join("news/harbour-expansion-approved-by-the-council-1.html").unwrap();
+        let completeness = Completeness::lenient().min_word_count(150).require_date(true);
+        for warn_only in [false, true] {
+            let mut paper = Extrablatt::builder(url.clone())
+                .unwrap()
+                .categories(false)
+                .retry_policy(RetryPolicy::none())
+                .completeness(completeness.warn_only(warn_only))
+                .build()
+                .await
+                .unwrap();
+            let page = paper.get_document(article_url.clone()).await.unwrap();
+            let result = paper.article_from_document(article_url.clone(), page);
+            let incompleteness = if warn_only {
+                result.unwrap().content.incompleteness.unwrap()
+            } else {
+                match result {
+                    Err(ExtrablattError::IncompleteArticle { incompleteness, .. }) => incompleteness,
+                    other => panic!("expected an incomplete article, got {:?}", other.map(|article| article.url)),
+                }
+            };
+            assert_eq!(incompleteness.to_string(), "missing: [Date], word_count: 10 < 150");
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn downloads_are_traced() {
+        let url = serve();
+        let paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await
+            .unwrap();
+        let article_url = url.join("news/harbour-expansion-approved-by-the-council-1.html").unwrap();
+        paper.get_document(article_url.clone()).await.unwrap();
+        assert!(logs_contain(&format!("fetch{{url={}", article_url)));
+        assert!(logs_contain("status=200"));
+        assert!(logs_contain("bytes="));
+        assert!(logs_contain("parsed document"));
+    }
+
+    #[tokio::test]
+    async fn redirect_limit() {
+        let url = serve();
+        let paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            .max_redirects(2)
+            .build()
+            .await
+            .unwrap();
+        match paper.get_document(url.join("redirect/0").unwrap()).await {
+            Err((_, ExtrablattError::TooManyRedirects { url: last, .. })) => {
+                // `/redirect/0` -> `/redirect/1` -> `/redirect/2`, whose
+                // redirect exceeds the limit
+                assert_eq!(last, url.join("redirect/2").unwrap())
+            }
+            other => panic!("expected too many redirects, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[tokio::test]
+    async fn redirect_chain_within_limit() {
+        let requested = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let url = {
+            let requested = requested.clone();
+            test_server::serve(move |request| {
+                requested.lock().unwrap().push(request.path.clone());
+                match request.path.strip_prefix("/redirect/").and_then(|n| n.parse::<usize>().ok()) {
+                    Some(n) if n < 2 => format!(
+                        "HTTP/1.1 302 Found\r\nLocation: /redirect/{}\r\nConnection: close\r\n\r\n",
+                        n + 1
+                    ),
+                    _ => html("<html><head><title>Harbour</title></head><body></body></html>"),
+                }
+            })
+        };
+        let paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .retry_policy(RetryPolicy::none())
+            .max_redirects(2)
+            .build()
+            .await
+            .unwrap();
+        requested.lock().unwrap().clear();
+        let fetch_info = paper.get_document(url.join("redirect/0").unwrap()).await.unwrap().fetch_info;
+        assert_eq!(fetch_info.final_url, url.join("redirect/2").unwrap());
+        assert_eq!(
+            *requested.lock().unwrap(),
+            vec!["/redirect/0", "/redirect/1", "/redirect/2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn rotated_user_agents_are_recorded() {
+        let url = serve();
+        let invalid = Extrablatt::builder(url.clone())
+            .unwrap()
+            .user_agents(vec!["agent/1", "bad\nagent"])
+            .build()
+            .await;
+        assert!(matches!(invalid, Err(ExtrablattError::UserAgentParseError { .. })));
+
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("news").unwrap()])
+            .retry_policy(RetryPolicy::none())
+            .user_agents(vec!["agent/1", "agent/2"])
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        let articles: Vec<_> = paper.article_stream().filter_map(|res| async { res.ok() }).collect().await;
+        assert_eq!(articles.len(), 5);
+        let user_agents: FnvHashSet<_> = articles
+            .iter()
+            .filter_map(|article| article.content.diagnostics.as_ref()?.user_agent.clone())
+            .collect();
+        assert_eq!(user_agents.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn article_languages_are_resolved_per_article() {
+        let url = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("editions").unwrap()])
+            .retry_policy(RetryPolicy::none())
+            .language(Language::English)
+            .language_for_host_path(url.host_str().unwrap(), "/ar", Language::Arabic)
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        let articles: Vec<_> = paper.article_stream().filter_map(|res| async { res.ok() }).collect().await;
+        assert_eq!(articles.len(), 2);
+        for article in articles {
+            let text = article.content.text.as_deref().unwrap_or_default();
+            if article.url.path().starts_with("/ar/") {
+                // the override wins over the `lang` of the page's template
+                assert_eq!(article.language, Language::Arabic);
+                assert!(text.contains("وافق مجلس المدينة"));
+            } else {
+                assert_eq!(article.language, Language::Russian);
+                assert!(text.contains("Городской совет"));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn article_stream_follows_meta_refresh() {
+        let url = crate::test_server::serve_pages(vec![
+            ("/", "<html><body></body></html>"),
+            (
+                "/news",
+                "<a href=\"/news/2024/05/14/harbour-story.html\">Harbour</a>\
+                 <a href=\"/news/2024/05/14/loop-a.html\">Loop</a>",
+            ),
+            (
+                "/news/2024/05/14/harbour-story.html",
+                "<html><head><meta http-equiv=\"refresh\" content=\"0;url=/news/2024/05/15/harbour-story.html\">\
+                 </head><body></body></html>",
+            ),
+            (
+                "/news/2024/05/15/harbour-story.html",
+                "<html><head><title>Harbour</title></head><body><div itemprop=\"articleBody\">\
+                 <p>The harbour will be expanded over the next ten years.</p></div></body></html>",
+            ),
+            (
+                "/news/2024/05/14/loop-a.html",
+                "<html><head><meta http-equiv=\"refresh\" content=\"0;url=/news/2024/05/14/loop-b.html\"></head></html>",
+            ),
+            (
+                "/news/2024/05/14/loop-b.html",
+                "<html><head><meta http-equiv=\"refresh\" content=\"0;url=/news/2024/05/14/loop-a.html\"></head></html>",
+            ),
+        ]);
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("news").unwrap()])
+            .retry_policy(RetryPolicy::none())
+            .follow_meta_refresh(true)
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        assert_eq!(paper.articles().len(), 2);
+
+        let results: Vec<_> = paper.article_stream().collect().await;
+        let fetch_info = results
+            .iter()
+            .flatten()
+            .find_map(|article| article.fetch_info.as_ref())
+            .unwrap();
+        assert_eq!(fetch_info.final_url, url.join("/news/2024/05/15/harbour-story.html").unwrap());
+        assert_eq!(fetch_info.redirect_chain, vec![url.join("/news/2024/05/14/harbour-story.html").unwrap()]);
+        // the stub of the loop isn't extracted as the article
+        let looped = url.join("/news/2024/05/14/loop-b.html").unwrap();
+        assert!(results
+            .iter()
+            .any(|res| matches!(res, Err(ExtrablattError::TooManyMetaRefreshes { url }) if *url == looped)));
+    }
+
+    #[tokio::test]
+    async fn duplicate_articles_are_skipped() {
+        let url = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("local").unwrap()])
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        assert_eq!(paper.articles().len(), 2);
+        assert_eq!(paper.dedup_stats().duplicate_urls, 2);
+
+        let results: Vec<_> = paper.article_stream().collect().await;
+        assert_eq!(results.iter().filter(|res| res.is_ok()).count(), 1);
+        assert!(results
+            .iter()
+            .any(|res| matches!(res, Err(ExtrablattError::DuplicateArticle { .. }))));
+        assert_eq!(paper.dedup_stats().duplicate_canonicals, 1);
+    }
+
+    #[tokio::test]
+    async fn feed_items_are_articles() {
+        let url = serve();
+        let paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .use_feeds(true)
+            .build()
+            .await
+            .unwrap();
+        assert!(paper.articles().contains_key(&url.join("feed/harbour.html").unwrap()));
+        assert!(paper.articles().contains_key(&url.join("feed/council.html").unwrap()));
+        assert_eq!(paper.articles().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn feed_items_with_full_text_are_not_downloaded() {
+        let url = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .use_feeds(true)
+            .build()
+            .await
+            .unwrap();
+        let expansion = url.join("feed/expansion.html").unwrap();
+        assert!(!paper.articles().contains_key(&expansion));
+
+        let article = &paper.feed_articles()[&expansion];
+        assert_eq!(article.content.title.as_deref(), Some("Expansion"));
+        assert_eq!(article.content.authors, vec!["Jane Doe"]);
+        assert!(article.content.text.as_deref().unwrap().starts_with(FEED_ARTICLE_TEXT));
+
+        let streamed: Vec<_> = paper.article_stream().collect().await;
+        assert_eq!(streamed.len(), 3);
+        assert!(streamed.iter().all(Result::is_ok));
+        assert!(paper.feed_articles().is_empty());
+    }
+
+    #[tokio::test]
+    async fn stream_requests_likely_articles_first() {
+        let requested: Arc<std::sync::Mutex<Vec<String>>> = Default::default();
+        let url = {
+            let requested = requested.clone();
+            test_server::serve(move |request| {
+                if request.path == "/news" {
+                    let links = ["council-0", "likely-1", "council-2", "likely-3"]
+                        .iter()
+                        .map(|slug| {
+                            format!("<a href=\"/news/harbour-expansion-approved-by-the-{}.html\">Harbour</a>", slug)
+                        })
+                        .collect::<String>();
+                    return html(links);
+                }
+                if request.path.starts_with("/news/") {
+                    requested.lock().unwrap().push(request.path.clone());
+                }
+                html(
+                    "<html><body><div itemprop=\"articleBody\"><p>The harbour will be expanded.</p></div>\
+                     </body></html>",
+                )
+            })
+        };
+        let paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .config(Config::builder().concurrency(1).build())
+            .include_categories(vec![url.join("news").unwrap()])
+            .article_url_filter(|url| {
+                if url.path().contains("-likely-") {
+                    UrlClass::Likely
+                } else {
+                    UrlClass::Ambiguous
+                }
+            })
+            .build()
+            .await
+            .unwrap();
+        let streamed: Vec<_> = paper.into_stream().collect().await;
+        assert_eq!(streamed.len(), 4);
+        assert_eq!(
+            *requested.lock().unwrap(),
+            vec![
+                "/news/harbour-expansion-approved-by-the-likely-1.html",
+                "/news/harbour-expansion-approved-by-the-likely-3.html",
+                "/news/harbour-expansion-approved-by-the-council-0.html",
+                "/news/harbour-expansion-approved-by-the-council-2.html",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn likely_articles_are_downloaded_first() {
+        let url = serve();
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .use_feeds(true)
+            .include_categories(vec![url.join("news").unwrap()])
+            .article_url_filter(|url| {
+                if url.path().ends_with("-3.html") {
+                    UrlClass::Rejected(RejectRule::Custom)
+                } else {
+                    classify_article_url(url)
+                }
+            })
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        assert_eq!(
+            *paper.url_class_stats(),
+            UrlClassStats {
+                likely: 5,
+                ambiguous: 2,
+                custom: 1,
+                ..Default::default()
+            }
+        );
+
+        let urls = paper.queue.not_requested_article_urls();
+        assert_eq!(urls.len(), 7);
+        assert!(urls[..5].iter().all(|url| url.path().starts_with("/news/")));
+        assert!(urls[5..].iter().all(|url| url.path().starts_with("/feed/")));
+    }
+}
@@ -1,35 +1,76 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
 
-use reqwest::Url;
+use url::Url;
 use select::document::Document;
+use select::node::Node;
 use select::predicate::{Attr, Name};
 use url::Host;
 
 use crate::article::{
     ArticleContent, ArticleUrl, ALLOWED_FILE_EXT, BAD_DOMAINS, BAD_SEGMENTS, GOOD_SEGMENTS,
 };
-use crate::clean::{DefaultDocumentCleaner, DocumentCleaner};
-use crate::date::{ArticleDate, RE_DATE_SEGMENTS_M_D_Y, RE_DATE_SEGMENTS_Y_M_D};
+use crate::clean::{DefaultDocumentCleaner, DocumentCleaner, ExtractionFilters};
+use crate::date::{
+    ArticleDate, ArticleDates, Date, DateLabels, RE_DATE_SEGMENTS_M_D_Y, RE_DATE_SEGMENTS_Y_M_D,
+};
 
 use crate::category::Category;
 use crate::nlp::CATEGORY_STOPWORDS;
-use crate::video::VideoNode;
+use crate::video::{Video, VideoNode};
+use crate::audio::{Audio, AudioNode};
+use crate::language::DetectedLanguage;
 use crate::Language;
 use crate::extract_meta::meta_content;
-use crate::extract_title::title;
-use crate::extract_pb_date::publishing_date;
-use crate::extract_authors::authors;
-use crate::extract_node::article_node;
-use crate::extract_favicon::favicon;
-use crate::extract_meta_language::meta_language;
+use crate::extract_title::title_with_site_name;
+use crate::extract_pb_date::{modified_date_with_filters, publishing_date_with_filters};
+use crate::extract_authors::{authors, authors_detailed, Author, AuthorOrder};
+use crate::extract_node::{article_node_adaptive_with_filters, node_content, ExtractedContent};
+use crate::extract_favicon::{best_favicon, favicon, favicons, Favicon};
+use crate::extract_meta_language::{custom_language, detect_language_with_custom, meta_language, resolve_language_with_custom};
 use crate::extract_thumbnail::meta_thumbnail_url;
-use crate::extract_top_img::meta_img_url;
+use crate::extract_top_img::{meta_img_url, og_images, top_image_with_filters, OgImage};
 use crate::extract_urls::{all_urls, image_urls};
-use crate::extract_base_url::base_url;
-use crate::extract_meta_data::meta_data;
-use crate::extract_canonical::canonical_link;
-use crate::extract_videos::videos;
+use crate::extract_base_url::{base_url, resolve_base};
+use crate::extract_meta_data::{meta_data, MetaMap};
+use crate::extract_keywords::keywords;
+use crate::extract_canonical::{canonical_link, canonical_link_detailed, CanonicalLink};
+use crate::extract_ids::{article_ids, ArticleIds};
+use crate::extract_recipe::{recipe, Recipe};
+use crate::extract_videos::{videos_detailed_with_filters, videos_with_filters};
+use crate::extract_audios::{audios_detailed_with_filters, audios_with_filters};
+use crate::extract_amp::{amp_url, is_amp};
+use crate::extract_meta_refresh::meta_refresh_url;
+use crate::reference::HostFilter;
+use crate::extract_feeds::{feed_urls, FeedLink};
+use crate::extract_hreflang::{alternate_languages, x_default_url, LanguageTag};
+use crate::extract_og_article::{og_article, OpenGraphArticle};
+use crate::extract_section::section;
+use crate::extract_breadcrumbs::{breadcrumbs, Breadcrumb};
+use crate::extract_comments::{comment_info, CommentInfo};
+use crate::extract_description::{node_excerpt, DescriptionSource, DEFAULT_EXCERPT_LEN};
+use crate::text::ArticleTextNode;
+use crate::extract_publisher::{publisher, Publisher};
+use crate::extract_twitter_card::{twitter_card, TwitterCard};
+use crate::extract_microdata::microdata_value;
+use crate::paywall::{content_access, ContentAccess};
+
+/// Run an extractor call within a `tracing` span of the `name`.
+#[cfg(feature = "tracing")]
+macro_rules! traced {
+    ($name:literal, $call:expr) => {
+        tracing::debug_span!($name).in_scope(|| $call)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! traced {
+    ($name:literal, $call:expr) => {
+        $call
+    };
+}
 
 pub(crate) struct NodeValueQuery<'a> {
     pub name: Name<&'a str>,
@@ -63,9 +104,13 @@ pub trait Extractor {
     /// Extract the article title using advanced heuristics.
     ///
     /// Extraction priority:
-    /// 1. Try all known meta tags (TITLE_META_INFO) such as og:title, twitter:title, dc.title, etc.
-    /// 2. If not found, try the longest <h1> element (must be >2 words).
-    /// 3. If not found, try the <title> tag.
+    /// 1. Try all known meta tags (TITLE_META_INFO) such as og:title, twitter:title, dc.title, etc.,
+    ///    without a leading or trailing segment that matches the [`Extractor::meta_site_name`]
+    ///    or the domain.
+    /// 2. If not found, try the schema.org `headline` microdata, then the longest <h1> element
+    ///    (must be >2 words).
+    /// 3. If not found, try the <title> tag without the site name, split on the delimiters that
+    ///    appear only once.
     /// 4. If all above fail, apply advanced heuristics:
     ///    - Compare filtered versions of <title>, <h1>, and meta tag values (case-insensitive, alphanumeric only)
     ///    - Prefer <h1> if it matches <title> or meta tag after filtering
@@ -75,32 +120,162 @@ pub trait Extractor {
     ///    - Prefer <h1> if final candidate matches after filtering
     /// 5. Always postprocess the result using MOTLEY_REPLACEMENT and TITLE_REPLACEMENTS for cleanup.
     fn title<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
-        return title(doc);
+        return title_with_site_name(doc, self.meta_site_name(doc).as_deref());
     }
 
-    /// Extract all the listed authors for the article.
+    /// Extract all the listed authors for the article, in the
+    /// [`Extractor::author_order`].
     fn authors<'a>(&self, doc: &'a Document) -> Vec<Cow<'a, str>> {
-        return authors(doc);
+        let mut authors = authors(doc);
+        self.author_order().sort(&mut authors, |author| author.to_lowercase());
+        authors
+    }
+
+    /// Extract all the listed authors for the article together with their
+    /// profile url, job title and organization, in the
+    /// [`Extractor::author_order`].
+    fn authors_detailed(&self, doc: &Document, base_url: Option<&Url>) -> Vec<Author> {
+        let mut authors = authors_detailed(doc, base_url);
+        self.author_order().sort(&mut authors, |author| author.name.to_lowercase());
+        authors
+    }
+
+    /// The order of the authors, by default the order of the byline.
+    fn author_order(&self) -> AuthorOrder {
+        AuthorOrder::Document
+    }
+
+    /// The labels of dates in the visible text, like `Updated:`, the
+    /// default [`DateLabels`] if `None`.
+    fn date_labels(&self) -> Option<&DateLabels> {
+        None
     }
 
     /// When the article was published (and last updated).
+    ///
+    /// Dates in the text are searched near the article node selected with
+    /// the [`Extractor::filters`], the node of the text.
     fn publishing_date(&self, doc: &Document, base_url: Option<&Url>) -> Option<ArticleDate> {
-        return publishing_date(doc, base_url);
+        let labels = self.date_labels().map(Cow::Borrowed).unwrap_or_default();
+        publishing_date_with_filters(doc, base_url, &labels, self.filters())
+    }
+
+    /// When the article was last modified, even if it has no published date.
+    fn modified_date(&self, doc: &Document) -> Option<Date> {
+        let labels = self.date_labels().map(Cow::Borrowed).unwrap_or_default();
+        modified_date_with_filters(doc, &labels, self.filters())
+    }
+
+    /// The published and the modified date, extracted independently.
+    fn article_dates(&self, doc: &Document, base_url: Option<&Url>) -> ArticleDates {
+        ArticleDates {
+            published: self.publishing_date(doc, base_url).map(|date| date.published),
+            modified: self.modified_date(doc),
+        }
+    }
+
+    /// Collect the Open Graph `article:*` properties of the document.
+    fn og_article(&self, doc: &Document) -> Option<OpenGraphArticle> {
+        og_article(doc)
+    }
+
+    /// The section of the site the article belongs to, e.g. `Politics`.
+    ///
+    /// Tries the `article:section` and `section` meta tags, the breadcrumb
+    /// navigation and finally the path of the `base_url`, see
+    /// [`crate::extract_section::section`].
+    fn section<'a>(&self, doc: &'a Document, base_url: Option<&Url>) -> Option<Cow<'a, str>> {
+        section(doc, base_url)
+    }
+
+    /// The breadcrumb trail of the page, sorted by position.
+    ///
+    /// The last crumb is often the article itself, flagged with
+    /// [`Breadcrumb::is_current`]. See
+    /// [`crate::extract_breadcrumbs::breadcrumbs`].
+    fn breadcrumbs(&self, doc: &Document, base_url: Option<&Url>) -> Vec<Breadcrumb> {
+        breadcrumbs(doc, base_url)
+    }
+
+    /// Whether the article has comments and how many, from comment count
+    /// markers and the embeds of comment providers like Disqus. See
+    /// [`crate::extract_comments::comment_info`].
+    fn comment_info(&self, doc: &Document) -> Option<CommentInfo> {
+        comment_info(doc)
+    }
+
+    /// The organization that published the article, from the schema.org
+    /// `publisher` or the `og:site_name`. See
+    /// [`crate::extract_publisher::publisher`].
+    fn publisher(&self, doc: &Document, base_url: Option<&Url>) -> Option<Publisher> {
+        publisher(doc, base_url)
     }
 
-    /// Extract the favicon from a website.
+    /// The `twitter:*` metadata of the page for social previews. See
+    /// [`crate::extract_twitter_card::twitter_card`].
+    fn twitter_card(&self, doc: &Document, base_url: Option<&Url>) -> Option<TwitterCard> {
+        twitter_card(doc, base_url)
+    }
+
+    /// Whether the `text` of the article `node` is the full text or a teaser
+    /// of a paywall. See [`crate::paywall::content_access`].
+    fn content_access(
+        &self,
+        doc: &Document,
+        node: Option<&Node>,
+        text: Option<&str>,
+        description: Option<&str>,
+    ) -> ContentAccess {
+        content_access(doc, node, text, description)
+    }
+
+    /// All tags the article is labeled with.
+    fn tags<'a>(&self, doc: &'a Document) -> Vec<Cow<'a, str>> {
+        self.og_article(doc)
+            .map(|og| og.tags.into_iter().map(Cow::Owned).collect())
+            .unwrap_or_default()
+    }
+
+    /// Extract the favicon from a website, the url of
+    /// [`Extractor::best_favicon`].
     fn favicon(&self, doc: &Document, base_url: &Url) -> Option<Url> {
         favicon(doc, base_url)
     }
 
+    /// All icons the website declares, including `apple-touch-icon`s and
+    /// `mask-icon`s, or its `/favicon.ico` if it declares none. See
+    /// [`crate::extract_favicon::favicons`].
+    fn favicons(&self, doc: &Document, base_url: &Url) -> Vec<Favicon> {
+        favicons(doc, base_url)
+    }
+
+    /// The icon of the website with the highest quality, preferring the
+    /// largest PNG.
+    fn best_favicon(&self, doc: &Document, base_url: &Url) -> Option<Favicon> {
+        best_favicon(doc, base_url)
+    }
+
     /// Finds the href in the `<base>` tag.
     fn base_url(&self, doc: &Document) -> Option<Url> {
         base_url(doc)
     }
 
-    /// Extract content language from meta tag.
+    /// Extract content language from meta tag, as one of the
+    /// [`Extractor::custom_languages`] if it has the same identifier.
     fn meta_language(&self, doc: &Document) -> Option<Language> {
-        meta_language(doc)
+        meta_language(doc).map(|lang| custom_language(lang, self.custom_languages()))
+    }
+
+    /// Detect the language of the document from the text of its paragraphs,
+    /// including the [`Extractor::custom_languages`].
+    fn detect_language(&self, doc: &Document) -> Option<DetectedLanguage> {
+        detect_language_with_custom(doc, self.custom_languages())
+    }
+
+    /// The languages with stopwords of their own the page can be in, see
+    /// [`Language::custom`].
+    fn custom_languages(&self) -> &[Language] {
+        &[]
     }
 
     /// Finds all `<meta>` nodes in the document.
@@ -108,6 +283,12 @@ pub trait Extractor {
         meta_data(doc)
     }
 
+    /// The keys and values of the [`Extractor::meta_data`] nodes, which
+    /// outlive the document.
+    fn meta_map(&self, doc: &Document) -> MetaMap {
+        MetaMap::from_nodes(&self.meta_data(doc))
+    }
+
 
     /// Extract a given meta content form document.
     fn meta_content<'a, 'b>(
@@ -128,6 +309,18 @@ pub trait Extractor {
         meta_img_url(doc, base_url)
     }
 
+    /// All `og:image`s of the document with their size and alt text.
+    fn og_images(&self, doc: &Document, base_url: Option<&Url>) -> Vec<OgImage> {
+        og_images(doc, base_url)
+    }
+
+    /// Select the top image of the article, from the `og:image` or by
+    /// scoring the images of the article's text node, selected with the
+    /// [`Extractor::filters`].
+    fn top_image(&self, doc: &Document, base_url: Option<&Url>, lang: Language) -> Option<Url> {
+        top_image_with_filters(doc, base_url, lang, self.filters())
+    }
+
     /// Returns meta type of article, open graph protocol
     fn meta_type<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
         self.meta_content(doc, Attr("property", "og:type"))
@@ -138,11 +331,13 @@ pub trait Extractor {
         self.meta_content(doc, Attr("property", "og:site_name"))
     }
 
-    /// If the article has meta description set in the source, use that
+    /// If the article has meta description set in the source, use that,
+    /// else the schema.org `description` microdata.
     fn meta_description<'a>(&self, doc: &'a Document) -> Option<Cow<'a, str>> {
         [("property", "description"), ("name", "description")]
             .iter()
             .filter_map(|(k, v)| self.meta_content(doc, Attr(k, v)))
+            .chain(microdata_value(doc, "description"))
             .map(|desc| {
                 // Clean non-breaking spaces from description
                 match desc {
@@ -153,6 +348,31 @@ pub trait Extractor {
             .next()
     }
 
+    /// The [`Extractor::meta_description`], or else an excerpt of at most
+    /// `max_len` characters of the first paragraphs of the article's text
+    /// that skips the byline, see [`crate::extract_description::excerpt`].
+    fn description_or_excerpt<'a>(
+        &self,
+        doc: &'a Document,
+        lang: Language,
+        max_len: usize,
+    ) -> Option<(Cow<'a, str>, DescriptionSource)> {
+        if let Some(description) = self.meta_description(doc) {
+            return Some((description, DescriptionSource::Meta));
+        }
+        // a wrong or missing language would select the node by the wrong stopwords
+        let (lang, _) = resolve_language_with_custom(doc, lang, self.custom_languages());
+        let (node, _) = article_node_adaptive_with_filters(doc, lang, self.filters())?;
+        let published = self.publishing_date(doc, None);
+        description_or_node_excerpt(
+            None,
+            Some(&node),
+            &self.authors(doc),
+            published.as_ref().map(|date| &date.published),
+            max_len,
+        )
+    }
+
     /// If the article has meta keywords set in the source, use that.
     fn meta_keywords<'a>(&self, doc: &'a Document) -> Vec<Cow<'a, str>> {
         for (k, v) in &[
@@ -175,9 +395,63 @@ pub trait Extractor {
         Vec::new()
     }
 
-    /// Get the full text of the article.
+    /// All keywords of the `keywords`, `news_keywords`, `article:tag` and
+    /// `parsely-tags` meta tags.
+    fn keywords(&self, doc: &Document) -> Vec<String> {
+        keywords(doc)
+    }
+
+    /// The cleaner that decides which nodes of the article node are dropped
+    /// from the text and references.
+    ///
+    /// `None` keeps the built-in cleaning.
+    fn cleaner(&self) -> Option<&dyn DocumentCleaner> {
+        None
+    }
+
+    /// The hooks that reject additional nodes and texts of the article, on
+    /// top of the built-in noise detection.
+    ///
+    /// `None` keeps the built-in detection only.
+    fn filters(&self) -> Option<&Arc<ExtractionFilters>> {
+        None
+    }
+
+    /// The hosts the references of the article are restricted to or
+    /// skipped for.
+    ///
+    /// `None` keeps the references to all hosts.
+    fn reference_hosts(&self) -> Option<&HostFilter> {
+        None
+    }
+
+    /// Whether paragraphs that list the sources of the article, like
+    /// `Sources: Reuters, AP`, are part of the text or only available from
+    /// [`ArticleContent::attributions`].
+    ///
+    /// Default is `true`.
+    fn attributions_in_text(&self) -> bool {
+        true
+    }
+
+    /// Get the full text of the article, the text of
+    /// [`Extractor::content`].
     fn text<'a>(&self, doc: &'a Document, lang: Language) -> Option<Cow<'a, str>> {
-        self.text_with_cleaner(doc, lang, DefaultDocumentCleaner)
+        self.content(doc, lang, None).map(|content| Cow::Owned(content.text))
+    }
+
+    /// The text, paragraphs, images, references and videos of the article
+    /// node, which is only selected once. See
+    /// [`crate::extract_node::extracted_content`].
+    ///
+    /// The text is cleaned with the [`Extractor::cleaner`], like the text of
+    /// [`Extractor::article_content`].
+    fn content(&self, doc: &Document, lang: Language, base_url: Option<&Url>) -> Option<ExtractedContent> {
+        // a wrong or missing language would select the node by the wrong stopwords
+        let (lang, _) = resolve_language_with_custom(doc, lang, self.custom_languages());
+        let (node, profile) = article_node_adaptive_with_filters(doc, lang, self.filters())?;
+        let node = node.attributions_in_text(self.attributions_in_text());
+        Some(node_content(doc, &node, profile, base_url, self.cleaner()))
     }
 
     /// Get the full text of the article with a designated `DocumentCleaner`
@@ -187,8 +461,10 @@ pub trait Extractor {
         lang: Language,
         cleaner: T,
     ) -> Option<Cow<'a, str>> {
-        article_node(doc, lang)
-            .map(|n| cleaner.clean_node_text(*n).into())
+        // a wrong or missing language would select the node by the wrong stopwords
+        let (lang, _) = resolve_language_with_custom(doc, lang, self.custom_languages());
+        article_node_adaptive_with_filters(doc, lang, self.filters())
+            .map(|(n, _)| cleaner.clean_node_text(*n).into())
     }
 
     /// Extract the `href` attribute for all `<a>` tags of the document.
@@ -198,7 +474,8 @@ pub trait Extractor {
 
     /// Finds all urls from the document that might point to an article.
     fn article_urls(&self, doc: &Document, base_url: Option<&Url>) -> Vec<ArticleUrl> {
-        let options = Url::options().base_url(base_url);
+        let base = resolve_base(doc, base_url);
+        let options = Url::options().base_url(base.as_ref());
         let mut uniques = HashSet::new();
         let q = doc
             .find(Name("a"))
@@ -353,7 +630,8 @@ pub trait Extractor {
     /// Finds all of the top level urls, assuming that these are the category
     /// urls.
     fn categories(&self, doc: &Document, base_url: &Url) -> Vec<Category> {
-        let options = Url::options().base_url(Some(base_url));
+        let base = resolve_base(doc, Some(base_url));
+        let options = Url::options().base_url(base.as_ref());
         let category_urls: HashSet<_> = self
             .all_urls(doc)
             .into_iter()
@@ -372,53 +650,128 @@ pub trait Extractor {
     }
 
     /// Gathers all items for an article from the document.
+    ///
+    /// The text is extracted in the language the page declares, else in the
+    /// `lang`, unless the text is detected to be in another language.
     fn article_content<'a>(
         &self,
         doc: &'a Document,
         base_url: Option<&Url>,
         lang: Option<Language>,
     ) -> ArticleContent<'a> {
-        let mut builder = ArticleContent::builder()
-            .authors(self.authors(doc))
-            .keywords(self.meta_keywords(doc));
+        let meta_lang = self.meta_language(doc);
+        let lang = meta_lang.clone().unwrap_or_else(|| lang.unwrap_or_default());
+        let (lang, detected) = resolve_language_with_custom(doc, lang, self.custom_languages());
+        let mut content = self.article_content_in_language(doc, base_url, lang);
+        content.language = meta_lang;
+        content.detected_language = detected;
+        content
+    }
 
-        let lang = if let Some(meta_lang) = self.meta_language(doc) {
-            builder = builder.language(meta_lang.clone());
-            meta_lang
-        } else {
-            lang.unwrap_or_default()
-        };
+    /// Like [`Extractor::article_content`], but extracts the text in the
+    /// `lang` regardless of the language of the page.
+    fn article_content_in_language<'a>(
+        &self,
+        doc: &'a Document,
+        base_url: Option<&Url>,
+        lang: Language,
+    ) -> ArticleContent<'a> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("article_content", lang = ?lang).entered();
+        let authors = traced!("authors", self.authors(doc));
+        let mut builder = ArticleContent::builder()
+            .keywords(traced!("keywords", self.keywords(doc)).into_iter().map(Cow::Owned).collect());
 
-        if let Some(txt_node) = article_node(doc, lang) {
+        let description = traced!("meta_description", self.meta_description(doc));
+        let publishing_date = traced!("publishing_date", self.publishing_date(doc, base_url));
+        let mut access = None;
+        let article_node = traced!(
+            "article_node",
+            article_node_adaptive_with_filters(doc, lang.clone(), self.filters())
+        )
+        .map(|(txt_node, profile)| (txt_node.attributions_in_text(self.attributions_in_text()), profile));
+        if let Some((txt_node, profile)) = &article_node {
+            let profile = *profile;
+            // the same text, images and videos as `Extractor::content`
+            let content = traced!(
+                "content",
+                node_content(doc, txt_node, profile, base_url, self.cleaner())
+            );
+            let resolved = resolve_base(doc, base_url);
+            let base_url = resolved.as_ref();
+            let text = content.text;
+            access = Some(traced!(
+                "content_access",
+                self.content_access(doc, Some(&*txt_node), Some(&text), description.as_deref())
+            ));
+            let cleaner = self.cleaner().unwrap_or(&DefaultDocumentCleaner);
+            let mut reference_details = txt_node.references_detailed_with_cleaner(base_url, base_url, cleaner);
+            if let Some(hosts) = self.reference_hosts() {
+                reference_details.retain(|reference| hosts.allows(&reference.url));
+            }
+            let references = reference_details.iter().map(|reference| reference.url.clone()).collect();
             builder = builder
-                .videos(
-                    txt_node
-                        .videos()
-                        .into_iter()
-                        .filter_map(|x| x.get_src_url(base_url))
-                        .filter_map(|url| url.ok())
-                        .collect(),
-                )
-                .references(txt_node.references())
-                .text(txt_node.clean_text().into())
-                .images(txt_node.images(base_url));
+                .extraction_profile(profile)
+                .diagnostics(txt_node.diagnostics(&text))
+                .videos(content.videos)
+                .audios(txt_node.audios_detailed(base_url))
+                .references(references)
+                .reference_details(reference_details)
+                .promotional_content(txt_node.promotional_content(base_url))
+                .affiliate_link_count(txt_node.affiliate_link_count(base_url))
+                .attributions(txt_node.attributions(base_url))
+                .quotes(txt_node.quotes())
+                .embeds(txt_node.embeds(base_url))
+                .text(text.into())
+                .html(txt_node.clean_html(base_url).into())
+                .images(content.images);
         }
 
-        if let Some(description) = self.meta_description(doc) {
-            builder = builder.description(description);
+        builder = builder.access(
+            access.unwrap_or_else(|| {
+                traced!("content_access", self.content_access(doc, None, None, description.as_deref()))
+            }),
+        );
+        if let Some((description, source)) = description_or_node_excerpt(
+            description,
+            article_node.as_ref().map(|(txt_node, _)| txt_node),
+            &authors,
+            publishing_date.as_ref().map(|date| &date.published),
+            DEFAULT_EXCERPT_LEN,
+        ) {
+            builder = builder.description(description).description_source(source);
         }
-        if let Some(thumbnail) = self.meta_thumbnail_url(doc, base_url) {
+        builder = builder.authors(authors);
+        if let Some(thumbnail) = traced!("meta_thumbnail_url", self.meta_thumbnail_url(doc, base_url)) {
             builder = builder.thumbnail(thumbnail);
         }
-        if let Some(title) = self.title(doc) {
+        if let Some(title) = traced!("title", self.title(doc)) {
             builder = builder.title(title);
         }
-        if let Some(date) = self.publishing_date(doc, base_url) {
+        if let Some(date) = publishing_date {
             builder = builder.publishing_date(date);
         }
-        if let Some(img) = self.meta_img_url(doc, base_url) {
+        if let Some(date) = traced!("modified_date", self.modified_date(doc)) {
+            builder = builder.modified_date(date);
+        }
+        if let Some(section) = traced!("section", self.section(doc, base_url)) {
+            builder = builder.section(section);
+        }
+        if let Some(canonical) = traced!("canonical_link", self.canonical_link_detailed(doc, base_url)) {
+            builder = builder.canonical(canonical);
+        }
+        builder = builder.alternate_languages(traced!("alternate_languages", self.alternate_languages(doc, base_url)));
+        if let Some(url) = traced!("x_default_url", self.x_default_url(doc, base_url)) {
+            builder = builder.x_default_url(url);
+        }
+        if let Some(publisher) = traced!("publisher", self.publisher(doc, base_url)) {
+            builder = builder.publisher(publisher);
+        }
+        if let Some(img) = traced!("top_image", self.top_image(doc, base_url, lang)) {
             builder = builder.top_image(img);
         }
+        builder = builder.meta(traced!("meta", self.meta_map(doc)));
+        builder = builder.ids(traced!("article_ids", self.article_ids(doc, base_url)));
         builder.build()
     }
 
@@ -427,16 +780,136 @@ pub trait Extractor {
     /// Gets the first available value of:
     ///   1. The rel=canonical tag
     ///   2. The og:url tag
-    fn canonical_link(&self, doc: &Document) -> Option<Url> {
-        canonical_link(doc)
+    ///
+    /// Relative values are resolved against the `<base>` of the document or
+    /// the `base_url`.
+    fn canonical_link(&self, doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+        canonical_link(doc, base_url)
+    }
+
+    /// Like [`Extractor::canonical_link`], but also reports where the url was
+    /// found and whether it belongs to another site than the `base_url`.
+    fn canonical_link_detailed(&self, doc: &Document, base_url: Option<&Url>) -> Option<CanonicalLink> {
+        canonical_link_detailed(doc, base_url)
+    }
+
+    /// Stable identifiers of the article at the `url`: the id in the url,
+    /// publisher ids of meta tags and the DOI.
+    fn article_ids(&self, doc: &Document, url: Option<&Url>) -> ArticleIds {
+        article_ids(doc, url)
+    }
+
+    /// The ingredients and steps of the recipe of the page, from JSON-LD or
+    /// microdata.
+    fn recipe(&self, doc: &Document) -> Option<Recipe> {
+        recipe(doc)
+    }
+
+    /// The url of the AMP version of the page, advertised by
+    /// `<link rel="amphtml">`.
+    ///
+    /// Relative values are resolved against the `<base>` of the document or
+    /// the `base_url`.
+    fn amp_url(&self, doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+        amp_url(doc, base_url)
     }
 
-    /// All video content in the article.
+    /// The url a stub page redirects to with
+    /// `<meta http-equiv="refresh" content="0;url=...">`.
+    ///
+    /// Relative values are resolved against the `<base>` of the document or
+    /// the `base_url`.
+    fn meta_refresh_url(&self, doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+        meta_refresh_url(doc, base_url)
+    }
+
+    /// Whether the document is an AMP page.
+    fn is_amp(&self, doc: &Document) -> bool {
+        is_amp(doc)
+    }
+
+    /// All RSS, Atom and JSON feeds the page advertises with
+    /// `<link rel="alternate">` elements.
+    ///
+    /// Relative values are resolved against the `<base>` of the document or
+    /// the `base_url`.
+    fn feed_urls(&self, doc: &Document, base_url: Option<&Url>) -> Vec<FeedLink> {
+        feed_urls(doc, base_url)
+    }
+
+    /// All language editions of the page advertised by
+    /// `<link rel="alternate" hreflang="...">` elements, without the
+    /// `x-default` entry.
+    ///
+    /// Relative values are resolved against the `<base>` of the document or
+    /// the `base_url`.
+    fn alternate_languages(&self, doc: &Document, base_url: Option<&Url>) -> Vec<(LanguageTag, Url)> {
+        alternate_languages(doc, base_url)
+    }
+
+    /// The page for all languages without an edition of their own,
+    /// advertised by `<link rel="alternate" hreflang="x-default">`.
+    fn x_default_url(&self, doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+        x_default_url(doc, base_url)
+    }
+
+    /// All video content of the article node selected with the
+    /// [`Extractor::filters`].
     fn videos<'a>(&self, doc: &'a Document, lang: Option<Language>) -> Vec<VideoNode<'a>> {
-        videos(doc, lang)
+        videos_with_filters(doc, lang, self.filters())
+    }
+
+    /// All videos in the article with their provider, id, poster and
+    /// dimensions.
+    ///
+    /// Relative urls are resolved against the `<base>` of the document or
+    /// the `base_url`.
+    fn videos_detailed(
+        &self,
+        doc: &Document,
+        base_url: Option<&Url>,
+        lang: Option<Language>,
+    ) -> Vec<Video> {
+        videos_detailed_with_filters(doc, base_url, lang, self.filters())
+    }
+
+    /// All audio content of the article node selected with the
+    /// [`Extractor::filters`], like the episode of a podcast.
+    ///
+    /// Players in sidebars and other noise, like audio ads, are skipped.
+    fn audios<'a>(&self, doc: &'a Document, lang: Option<Language>) -> Vec<AudioNode<'a>> {
+        audios_with_filters(doc, lang, self.filters())
+    }
+
+    /// All audios in the article with their type, duration and title.
+    ///
+    /// Relative urls are resolved against the `<base>` of the document or
+    /// the `base_url`.
+    fn audios_detailed(
+        &self,
+        doc: &Document,
+        base_url: Option<&Url>,
+        lang: Option<Language>,
+    ) -> Vec<Audio> {
+        audios_detailed_with_filters(doc, base_url, lang, self.filters())
     }
 }
 
+/// The `description`, or else an excerpt of the article `node` without the
+/// bylines, see [`node_excerpt`].
+fn description_or_node_excerpt<'a, S: AsRef<str>>(
+    description: Option<Cow<'a, str>>,
+    node: Option<&ArticleTextNode>,
+    authors: &[S],
+    published: Option<&Date>,
+    max_len: usize,
+) -> Option<(Cow<'a, str>, DescriptionSource)> {
+    if let Some(description) = description {
+        return Some((description, DescriptionSource::Meta));
+    }
+    node_excerpt(node?, authors, published, max_len).map(|excerpt| (Cow::Owned(excerpt), DescriptionSource::Excerpt))
+}
+
 fn count_dashes_and_underscores<T: AsRef<str>>(s: T) -> (usize, usize) {
     let s = s.as_ref();
     s.chars().fold((0, 0), |(dashes, unders), c| {
@@ -481,15 +954,123 @@ fn is_valid_domain(url: &Url, base_url: &Url) -> bool {
 }
 
 /// An Extractor that only uses the default implementation in the `Extractor`
-/// trait.
-#[derive(Debug, Default)]
-pub struct DefaultExtractor;
+/// trait, optionally with a custom [`DocumentCleaner`] and
+/// [`ExtractionFilters`].
+#[derive(Clone, Default)]
+pub struct DefaultExtractor {
+    cleaner: Option<Arc<dyn DocumentCleaner + Send + Sync>>,
+    author_order: AuthorOrder,
+    filters: Option<Arc<ExtractionFilters>>,
+    date_labels: Option<Arc<DateLabels>>,
+    custom_languages: Vec<Language>,
+    reference_hosts: Option<Arc<HostFilter>>,
+    attributions_out_of_text: bool,
+}
+
+impl DefaultExtractor {
+    /// Use the `cleaner` to drop nodes from the text and references of
+    /// articles.
+    pub fn with_cleaner<T: DocumentCleaner + Send + Sync + 'static>(mut self, cleaner: T) -> Self {
+        self.cleaner = Some(Arc::new(cleaner));
+        self
+    }
+
+    /// Sort the authors in the `order`, like alphabetically instead of in the
+    /// order of the byline.
+    pub fn with_author_order(mut self, order: AuthorOrder) -> Self {
+        self.author_order = order;
+        self
+    }
+
+    /// Skip the nodes and texts the `filters` deny, in the text, paragraphs
+    /// and images of articles.
+    pub fn with_filters(mut self, filters: ExtractionFilters) -> Self {
+        self.filters = Some(Arc::new(filters));
+        self
+    }
+
+    /// Search the visible text for dates behind the `labels`, like the
+    /// default labels with site specific ones added.
+    pub fn with_date_labels(mut self, labels: DateLabels) -> Self {
+        self.date_labels = Some(Arc::new(labels));
+        self
+    }
+
+    /// Resolve the name of the [`Language::custom`] from the page and
+    /// detect it from the text, replacing a custom language with the same
+    /// name.
+    pub fn with_custom_language(mut self, language: Language) -> Self {
+        self.custom_languages
+            .retain(|custom| custom.identifier() != language.identifier());
+        self.custom_languages.push(language);
+        self
+    }
 
-impl Extractor for DefaultExtractor {}
+    /// Keep only the references of articles the `hosts` allow, like without
+    /// the links to an ad network.
+    pub fn with_reference_hosts(mut self, hosts: HostFilter) -> Self {
+        self.reference_hosts = Some(Arc::new(hosts));
+        self
+    }
+
+    /// Set whether paragraphs that list the sources of articles are part of
+    /// the text, or only available from [`ArticleContent::attributions`].
+    ///
+    /// Default is `true`.
+    pub fn with_attributions_in_text(mut self, attributions_in_text: bool) -> Self {
+        self.attributions_out_of_text = !attributions_in_text;
+        self
+    }
+}
+
+impl fmt::Debug for DefaultExtractor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultExtractor")
+            .field("custom_cleaner", &self.cleaner.is_some())
+            .field("author_order", &self.author_order)
+            .field("filters", &self.filters)
+            .field("date_labels", &self.date_labels)
+            .field("custom_languages", &self.custom_languages)
+            .field("reference_hosts", &self.reference_hosts)
+            .field("attributions_in_text", &!self.attributions_out_of_text)
+            .finish()
+    }
+}
+
+impl Extractor for DefaultExtractor {
+    fn cleaner(&self) -> Option<&dyn DocumentCleaner> {
+        self.cleaner.as_deref().map(|cleaner| cleaner as &dyn DocumentCleaner)
+    }
+
+    fn author_order(&self) -> AuthorOrder {
+        self.author_order
+    }
+
+    fn filters(&self) -> Option<&Arc<ExtractionFilters>> {
+        self.filters.as_ref()
+    }
+
+    fn date_labels(&self) -> Option<&DateLabels> {
+        self.date_labels.as_deref()
+    }
+
+    fn custom_languages(&self) -> &[Language] {
+        &self.custom_languages
+    }
+
+    fn reference_hosts(&self) -> Option<&HostFilter> {
+        self.reference_hosts.as_deref()
+    }
+
+    fn attributions_in_text(&self) -> bool {
+        !self.attributions_out_of_text
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::reference::RefKind;
 
     #[test]
     fn detect_articles() {
@@ -514,4 +1095,195 @@ mod tests {
                 "https://extrablatt.com/hmm-some-very-long-title-speparated",
         );
     }
+    #[test]
+    fn swap_cleaner() {
+        use crate::clean::AggressiveCleaner;
+
+        let doc = Document::from(
+            r#"<html><head><title>Harbour</title></head><body><div itemprop="articleBody">
+            <p>The city council approved the expansion of the harbour on Tuesday after a long debate.</p>
+            <p>Construction will start next spring and is expected to last for ten years, according to the
+            <a href="/about.html">port authority</a>.</p>
+            <div class="more"><p>Read also: <a href="/news/harbour.html">Harbour plans</a>
+            <a href="/news/council.html">Council vote</a></p></div>
+            </div></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/news/").unwrap();
+        let reference = Url::parse("https://example.com/news/council.html").unwrap();
+
+        let default = DefaultExtractor::default().article_content(&doc, Some(&base), None);
+        assert!(default.references.contains(&reference));
+
+        let aggressive = DefaultExtractor::default()
+            .with_cleaner(AggressiveCleaner::default())
+            .article_content(&doc, Some(&base), None);
+        assert!(!aggressive.references.contains(&reference));
+        assert!(aggressive
+            .references
+            .contains(&Url::parse("https://example.com/about.html").unwrap()));
+        let text = aggressive.text.unwrap();
+        assert!(text.contains("approved the expansion"));
+        assert!(!text.contains("Council vote"));
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    #[tracing_test::traced_test]
+    fn extraction_is_traced() {
+        let doc = Document::from(
+            r#"<html><head><title>Harbour expansion approved</title></head><body><div>
+            <p>The city council approved the expansion of the harbour on Tuesday after a long debate.</p>
+            <p>Construction will start next spring and is expected to last for ten years.</p>
+            </div></body></html>"#,
+        );
+        DefaultExtractor::default().article_content(&doc, None, Some(Language::English));
+        assert!(logs_contain("article_content"));
+        assert!(logs_contain("calculate_best_node"));
+        assert!(logs_contain("selected article node"));
+        assert!(logs_contain("candidates="));
+        assert!(logs_contain("score="));
+    }
+
+    #[test]
+    #[cfg(feature = "stopwords")]
+    fn custom_language_of_the_page() {
+        let doc = Document::from(include_str!("../tests/fixtures/multilingual/custom.html"));
+        let lang = Language::custom("xq", ["zor", "blen", "quix", "vrak", "plim", "drox"]);
+        let content = DefaultExtractor::default()
+            .with_custom_language(lang.clone())
+            .article_content(&doc, None, None);
+        assert_eq!(content.language, Some(lang.clone()));
+        assert_eq!(content.detected_language.map(|detected| detected.language), Some(lang));
+        assert!(content.text.unwrap().starts_with("Zor drambel blen"));
+
+        let content = DefaultExtractor::default().article_content(&doc, None, None);
+        assert_eq!(content.language, Some(Language::Other("xq".to_string())));
+        assert_eq!(content.detected_language, None);
+    }
+
+    #[test]
+    fn description_falls_back_to_excerpt() {
+        let body = r#"<body><div itemprop="articleBody">
+            <p class="byline">By Jane Zhu</p>
+            <p><time>May 14, 2024</time></p>
+            <p>The city council approved the expansion of the harbour on Tuesday after a long debate.</p>
+            <p>Construction will start next spring and is expected to last for ten years.</p>
+            </div></body>"#;
+        let extractor = DefaultExtractor::default();
+        let doc = Document::from(format!("<html><head></head>{}</html>", body).as_str());
+        let content = extractor.article_content(&doc, None, Some(Language::English));
+        assert_eq!(content.description_source, Some(DescriptionSource::Excerpt));
+        assert_eq!(
+            content.description.as_deref(),
+            Some(
+                "The city council approved the expansion of the harbour on Tuesday after a long debate. \
+                 Construction will start next spring and is expected to last for ten years."
+            )
+        );
+        let (excerpt, source) = extractor
+            .description_or_excerpt(&doc, Language::English, 50)
+            .unwrap();
+        assert_eq!(source, DescriptionSource::Excerpt);
+        assert_eq!(excerpt, "The city council approved the expansion of the…");
+
+        let doc = Document::from(
+            format!(
+                r#"<html><head><meta name="description" content="Harbour expansion approved"></head>{}</html>"#,
+                body
+            )
+            .as_str(),
+        );
+        let content = extractor.article_content(&doc, None, Some(Language::English));
+        assert_eq!(content.description_source, Some(DescriptionSource::Meta));
+        assert_eq!(content.description.as_deref(), Some("Harbour expansion approved"));
+    }
+
+    #[test]
+    fn reference_hosts_and_kinds() {
+        let doc = Document::from(
+            r#"<html><head><title>Harbour expansion approved</title></head><body><div>
+            <p>The city council approved the <a href="/news/harbour-plan">expansion of the harbour</a> on Tuesday
+            after a long debate, according to <a href="https://other.co.uk/report" rel="nofollow">a report</a>.</p>
+            <p>Construction will start next spring, the mayor <a href="https://twitter.com/harbour_city">wrote</a>,
+            and is expected to last for ten years. <a href="https://click.ads.example.net/x">Sponsored</a></p>
+            </div></body></html>"#,
+        );
+        let base_url = Url::parse("https://www.example.co.uk/news/harbour").unwrap();
+        let hosts = HostFilter::new().deny("ads.example.net");
+        let content = DefaultExtractor::default()
+            .with_reference_hosts(hosts)
+            .article_content(&doc, Some(&base_url), Some(Language::English));
+        let details: Vec<_> = content
+            .reference_details
+            .iter()
+            .map(|reference| (reference.url.as_str(), reference.kind, reference.anchor_text.as_str()))
+            .collect();
+        assert_eq!(
+            details,
+            vec![
+                ("https://www.example.co.uk/news/harbour-plan", RefKind::Internal, "expansion of the harbour"),
+                ("https://other.co.uk/report", RefKind::External, "a report"),
+                ("https://twitter.com/harbour_city", RefKind::Social, "wrote"),
+            ]
+        );
+        assert!(content.reference_details[1].nofollow);
+        assert_eq!(content.references.len(), 3);
+    }
+
+    #[test]
+    fn attributions_out_of_text() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>The harbour will be expanded over the next ten years, the city council decided on Tuesday.</p>
+            <p>The first ships are expected to use the new quay in the spring, the port authority said.</p>
+            <p>Sources: <a rel="nofollow" href="https://reuters.com/harbour">Reuters</a>, <a rel="nofollow" href="https://apnews.com/harbour">AP</a></p>
+            </article></body></html>"#,
+        );
+        let extractor = DefaultExtractor::default();
+        let content = extractor.article_content(&doc, None, Some(Language::English));
+        assert!(content.text.unwrap().contains("Sources: Reuters, AP"));
+
+        let extractor = extractor.with_attributions_in_text(false);
+        let content = extractor.article_content(&doc, None, Some(Language::English));
+        let text = content.text.unwrap();
+        assert!(!text.contains("Sources"));
+        assert_eq!(content.attributions.len(), 1);
+        let content = extractor.content(&doc, Language::English, None).unwrap();
+        assert_eq!(content.text, text);
+    }
+
+    #[test]
+    fn media_of_the_custom_article_body() {
+        let column = "<p>The ferry between the islands will run every hour again from next week on, \
+                      after the repairs of the pier that were finished ahead of the schedule.</p>"
+            .repeat(6);
+        let html = format!(
+            r#"<html><body><div class="column">{}<video src="/ferry.mp4"></video><img src="/ferry.jpg"></div>
+            <div class="story-body">
+            <p>The city council approved the expansion of the harbour on Tuesday after a long debate.</p>
+            <p>Construction will start in the spring, according to the port authority of the city.</p>
+            <video src="/harbour.mp4"></video><audio src="/harbour.mp3"></audio><img src="/harbour.jpg">
+            </div></body></html>"#,
+            column
+        );
+        let doc = Document::from(html.as_str());
+        let base = Url::parse("https://example.com/").unwrap();
+        let attrs = crate::text::ArticleBodyAttrs::default().add("class", "story-body");
+        let extractor =
+            DefaultExtractor::default().with_filters(ExtractionFilters::default().article_body_attrs(attrs));
+        let videos = extractor.videos_detailed(&doc, Some(&base), Some(Language::English));
+        assert_eq!(videos.len(), 1);
+        assert_eq!(videos[0].src.as_str(), "https://example.com/harbour.mp4");
+        let audios = extractor.audios_detailed(&doc, Some(&base), Some(Language::English));
+        assert_eq!(audios.len(), 1);
+        assert_eq!(audios[0].url.as_str(), "https://example.com/harbour.mp3");
+        assert_eq!(
+            extractor.top_image(&doc, Some(&base), Language::English).as_ref().map(Url::as_str),
+            Some("https://example.com/harbour.jpg")
+        );
+
+        // without the attrs the longer column is the article
+        let videos = DefaultExtractor::default().videos_detailed(&doc, Some(&base), Some(Language::English));
+        assert_eq!(videos[0].src.as_str(), "https://example.com/ferry.mp4");
+    }
 }
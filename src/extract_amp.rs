@@ -0,0 +1,148 @@
+use url::Url;
+use select::document::Document;
+use select::node::Node;
+use select::predicate::Name;
+
+use crate::extract_base_url::resolve_base;
+
+/// Elements of the AMP runtime that never hold article content.
+pub const AMP_NOISE_ELEMENTS: [&str; 8] = [
+    "amp-consent",
+    "amp-sticky-ad",
+    "amp-ad",
+    "amp-auto-ads",
+    "amp-analytics",
+    "amp-pixel",
+    "amp-user-notification",
+    "amp-geo",
+];
+
+/// Whether the document is an AMP page, marked by `<html ⚡>` or
+/// `<html amp>`.
+pub fn is_amp(doc: &Document) -> bool {
+    doc.find(Name("html"))
+        .next()
+        .map(|html| html.attr("⚡").is_some() || html.attr("amp").is_some())
+        .unwrap_or_default()
+}
+
+/// The url of the AMP version of the page, advertised by
+/// `<link rel="amphtml" href="...">`.
+///
+/// A relative href is resolved against the `<base>` of the document or the
+/// `base_url`, see [`resolve_base`].
+pub fn amp_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    doc.find(Name("link"))
+        .filter(|link| {
+            link.attr("rel")
+                .map(|rel| rel.split_whitespace().any(|rel| rel.eq_ignore_ascii_case("amphtml")))
+                .unwrap_or_default()
+        })
+        .filter_map(|link| link.attr("href").map(str::trim))
+        .filter(|href| !href.is_empty())
+        .find_map(|href| options.parse(href).ok())
+}
+
+/// Whether the node is AMP boilerplate: ads, consent dialogs, analytics or
+/// the layout placeholders of an AMP component.
+pub fn is_amp_noise(node: &Node) -> bool {
+    let name = match node.name() {
+        Some(name) => name,
+        None => return false,
+    };
+    if AMP_NOISE_ELEMENTS.contains(&name) || name == "i-amphtml-sizer" {
+        return true;
+    }
+    if node.attr("placeholder").is_some() || node.attr("fallback").is_some() {
+        return node
+            .parent()
+            .and_then(|parent| parent.name())
+            .map(|parent| parent.starts_with("amp-"))
+            .unwrap_or_default();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::text::ArticleTextNode;
+    use crate::video::VideoNode;
+
+    use super::*;
+
+    const CANONICAL: &str = r#"<html><body><article>
+        <p>The festival opened on Friday with a concert in the old town square.</p>
+        <img src="https://example.com/img/stage.jpg">
+        <iframe src="https://www.youtube.com/embed/abc123"></iframe>
+        <video src="https://example.com/video/crowd.mp4"></video>
+        </article></body></html>"#;
+
+    const AMP: &str = r#"<html ⚡><body>
+        <amp-consent id="consent" layout="nodisplay"><p>We use cookies.</p></amp-consent>
+        <article>
+        <p>The festival opened on Friday with a concert in the old town square.</p>
+        <amp-img src="https://example.com/img/stage.jpg" width="800" height="600" layout="responsive">
+            <div fallback>The image could not be loaded.</div>
+        </amp-img>
+        <amp-youtube data-videoid="abc123" width="480" height="270" layout="responsive"></amp-youtube>
+        <amp-video width="640" height="360" layout="responsive" poster="/img/crowd.jpg">
+            <source src="https://example.com/video/crowd.mp4" type="video/mp4">
+        </amp-video>
+        <amp-sticky-ad layout="nodisplay"><amp-ad type="doubleclick"></amp-ad></amp-sticky-ad>
+        </article></body></html>"#;
+
+    fn media(html: &str) -> (Vec<Url>, Vec<Url>) {
+        let doc = Document::from(html);
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        let videos = node
+            .videos()
+            .iter()
+            .filter_map(|video| video.get_src_url(None))
+            .filter_map(Result::ok)
+            .collect();
+        (node.images(None), videos)
+    }
+
+    #[test]
+    fn detect_amp() {
+        assert!(is_amp(&Document::from(AMP)));
+        assert!(is_amp(&Document::from("<html amp><body></body></html>")));
+        assert!(!is_amp(&Document::from(CANONICAL)));
+    }
+
+    #[test]
+    fn amphtml_link() {
+        let doc = Document::from(
+            r#"<html><head><link rel="canonical" href="/news/festival.html">
+            <link rel="amphtml" href="/amp/news/festival.html"></head><body></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/news/festival.html").unwrap();
+        assert_eq!(
+            amp_url(&doc, Some(&base)),
+            Some(Url::parse("https://example.com/amp/news/festival.html").unwrap())
+        );
+        assert_eq!(amp_url(&Document::from(CANONICAL), Some(&base)), None);
+    }
+
+    #[test]
+    fn amp_media_parity() {
+        let (images, videos) = media(AMP);
+        assert_eq!(media(CANONICAL), (images, videos.clone()));
+        assert_eq!(videos.len(), 2);
+
+        let doc = Document::from(AMP);
+        let poster = doc
+            .find(Name("amp-video"))
+            .map(VideoNode::new)
+            .find_map(|video| video.poster(Some(&Url::parse("https://example.com/").unwrap())));
+        assert_eq!(poster, Some(Url::parse("https://example.com/img/crowd.jpg").unwrap()));
+
+        let doc = Document::from(AMP);
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        let text = node.clean_text();
+        assert!(!text.contains("could not be loaded"));
+        assert!(!text.contains("cookies"));
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use url::Url;
+use select::document::Document;
+use crate::Language;
+use crate::clean::ExtractionFilters;
+use crate::audio::{Audio, AudioNode};
+use crate::extract_base_url::resolve_base;
+use crate::extract_node::article_node_adaptive_with_filters;
+
+/// All audio content in the article, like the episode of a podcast.
+pub fn audios<'a>(doc: &'a Document, lang: Option<Language>) -> Vec<AudioNode<'a>> {
+    audios_with_filters(doc, lang, None)
+}
+
+/// Like [`audios`], of the article node selected with the `filters`, so the
+/// audios belong to the node of the text.
+pub(crate) fn audios_with_filters<'a>(
+    doc: &'a Document,
+    lang: Option<Language>,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Vec<AudioNode<'a>> {
+    article_node_adaptive_with_filters(doc, lang.unwrap_or_default(), filters)
+        .map(|(node, _)| node.audios())
+        .unwrap_or_default()
+}
+
+/// All audios in the article with their type, duration and title.
+pub fn audios_detailed(doc: &Document, base_url: Option<&Url>, lang: Option<Language>) -> Vec<Audio> {
+    audios_detailed_with_filters(doc, base_url, lang, None)
+}
+
+/// Like [`audios_detailed`], of the article node selected with the
+/// `filters`.
+pub(crate) fn audios_detailed_with_filters(
+    doc: &Document,
+    base_url: Option<&Url>,
+    lang: Option<Language>,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Vec<Audio> {
+    article_node_adaptive_with_filters(doc, lang.unwrap_or_default(), filters)
+        .map(|(node, _)| node.audios_detailed(resolve_base(doc, base_url).as_ref()))
+        .unwrap_or_default()
+}
@@ -1,10 +1,22 @@
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::iter::successors;
 use regex::Regex;
 use select::document::Document;
+use select::node::Node;
+use select::predicate::{Any, Attr, Name, Predicate};
 use lazy_static::lazy_static;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
 
-use crate::text::{author_text};
+use crate::extract_base_url::resolve_base;
+use crate::extract_comments::is_comment_section;
+use crate::text::{author_text, class_parts, TextContainer};
+use crate::extract_json_ld::{json_ld, values};
+use crate::extract_og_article::og_article;
+use crate::extract_twitter_card::twitter_meta;
 
 /// Author extraction constants (from newspaper4k)
 const AUTHOR_ATTRS: [&str; 6] = ["name", "rel", "itemprop", "class", "id", "property"];
@@ -13,10 +25,42 @@ const AUTHOR_VALS: [&str; 12] = [
     "story-byline", "article-author", "parsely-author", "sailthru.author", "citation_author",
     "article-author"
 ];
+/// Agencies and job titles that are removed from bylines when they make up
+/// a whole trailing or parenthesized part, like `Jane Doe, Reuters` or
+/// `Anna Apfel (dpa)`, compared case-sensitively.
 const AUTHOR_STOP_WORDS: [&str; 13] = [
-    "By", "Reuters", "IANS", "AP", "AFP", "PTI", "ANI", "DPA", "Senior Reporter",
-    "Reporter", "Writer", "Opinion Writer", "Opinion Writer"
+    "Reuters", "IANS", "AP", "AFP", "PTI", "ANI", "DPA", "dpa", "Staff", "Senior Reporter",
+    "Reporter", "Writer", "Opinion Writer"
 ];
+/// Words that introduce a byline, like `By`, removed if a name starts with
+/// them.
+const BYLINE_PREFIXES: [&str; 3] = ["by", "von", "par"];
+/// Lowercase particles of names like `Jean-Claude de la Cruz`, `Von der
+/// Leyen`, `Rhys ap Gruffydd` or `محمد بن سلمان`, that don't count as given
+/// name or surname.
+const NAME_PARTICLES: [&str; 25] = [
+    "al", "ap", "bin", "bint", "da", "de", "del", "dem", "den", "der", "di", "du", "el", "ibn", "la", "las", "le",
+    "los", "van", "von", "y", "zu", "بن", "بنت", "ابن"
+];
+/// Max number of words of a name, or of a name with particles.
+const MAX_NAME_WORDS: usize = 4;
+const MAX_PARTICLE_NAME_WORDS: usize = 6;
+/// Words that join the names of a byline, like `Jane Zhu and Aaron Ball`.
+const BYLINE_CONJUNCTIONS: [&str; 5] = ["and", "&", "und", "et", "и"];
+/// Parts of class and id tokens of visible bylines, like `article__author`
+/// or `b-material__signature`.
+const BYLINE_PARTS: [&str; 9] = [
+    "byline", "author", "authors", "autor", "avtor", "writer", "signature", "credits", "by"
+];
+/// Labels of the roles in a byline like `Текст: Иван Петров | Фото: Анна
+/// Сидорова`, and whether the names after them are authors of the text.
+const BYLINE_LABELS: [(&str, bool); 10] = [
+    ("текст", true), ("автор", true), ("авторы", true), ("text", true), ("texte", true),
+    ("фото", false), ("photo", false), ("foto", false), ("видео", false), ("video", false)
+];
+/// Number of nodes after the headline that are searched for a visible
+/// byline, names further down belong to comments or related articles.
+const BYLINE_SEARCH_NODES: usize = 300;
 
 lazy_static! {
     /// Regex for cleaning author names.
@@ -27,6 +71,17 @@ lazy_static! {
 
     /// Regex for removing HTML tags in clean_author
     static ref RE_HTML_TAGS: Regex = Regex::new(r"<[^>]+>").unwrap();
+
+    /// The words of the [`AUTHOR_STOP_WORDS`], longer phrases first, so
+    /// `Senior Reporter` is removed as a whole.
+    static ref AUTHOR_STOP_PHRASES: Vec<Vec<&'static str>> = {
+        let mut phrases: Vec<Vec<&str>> = AUTHOR_STOP_WORDS
+            .iter()
+            .map(|stop| stop.split_whitespace().collect())
+            .collect();
+        phrases.sort_by_key(|phrase| std::cmp::Reverse(phrase.len()));
+        phrases
+    };
 }
 
 /// Check if a node is inside a footer/bottom section by examining its parents
@@ -55,9 +110,53 @@ fn is_in_footer_section(doc: &Document, node_index: usize) -> bool {
     false
 }
 
-/// Extract all the listed authors for the article.
+/// The order of the extracted authors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AuthorOrder {
+    /// The order of the first occurrence in the document, so the lead author
+    /// of the byline comes first.
+    #[default]
+    Document,
+    /// Sorted alphabetically, case-insensitive.
+    Alphabetical,
+}
+
+impl AuthorOrder {
+    /// Sort the `authors` in this order by the lowercase `key`.
+    pub fn sort<T>(&self, authors: &mut [T], key: impl Fn(&T) -> String) {
+        if *self == AuthorOrder::Alphabetical {
+            authors.sort_by_cached_key(key);
+        }
+    }
+}
+
+/// Extract all the listed authors for the article, in the order of the
+/// byline.
+///
+/// Names from the Open Graph `article:author` properties are preferred. If
+/// no element is tagged as author, the first visible byline near the
+/// headline is used, and if there is none the `twitter:creator` if it's a
+/// name rather than a handle.
 pub fn authors<'a>(doc: &'a Document) -> Vec<Cow<'a, str>> {
-    let mut authors = Vec::new();
+    let mut authors = byline_authors(doc);
+    if authors.is_empty() {
+        authors.extend(twitter_creator(doc));
+    }
+    authors.into_iter().map(Cow::Owned).collect()
+}
+
+/// The name of the `twitter:creator`, without a leading `@`, if it looks
+/// like a name.
+fn twitter_creator(doc: &Document) -> Option<String> {
+    let creator = twitter_meta(doc, "twitter:creator")?;
+    let name = clean_author(creator.trim().trim_start_matches('@'));
+    is_valid_name(&name).then_some(name)
+}
+
+/// The names of the `article:author` properties and the bylines, in the
+/// order of their first occurrence.
+fn byline_authors(doc: &Document) -> Vec<String> {
+    let mut authors: Vec<String> = og_article(doc).map(|og| og.authors).unwrap_or_default();
 
     for node in doc.nodes.iter() {
         if let select::node::Data::Element(tag, attrs) = &node.data {
@@ -107,6 +206,9 @@ pub fn authors<'a>(doc: &'a Document) -> Vec<Cow<'a, str>> {
             }
         }
     }
+    if authors.is_empty() {
+        authors.extend(visible_byline_authors(doc));
+    }
 
     // Deduplicate and filter (case-insensitive, trimmed)
     let mut seen = HashSet::new();
@@ -119,48 +221,446 @@ pub fn authors<'a>(doc: &'a Document) -> Vec<Cow<'a, str>> {
             result.push(a);
         }
     }
-    // Sort authors alphabetically for deterministic output
-    result.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()));
-    result.into_iter().map(Cow::Owned).collect()
+    result
+}
+
+/// An author of the article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Author {
+    pub name: String,
+    /// The author's profile page.
+    pub url: Option<Url>,
+    /// The job title, like `Senior Reporter`.
+    pub title: Option<String>,
+    /// The organization the author works for.
+    pub organization: Option<String>,
+}
+
+impl Author {
+    pub fn new<T: ToString>(name: T) -> Self {
+        Self {
+            name: name.to_string(),
+            url: None,
+            title: None,
+            organization: None,
+        }
+    }
+
+    /// The name in lowercase with normalized whitespace, to compare authors.
+    fn key(&self) -> String {
+        self.name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    }
+
+    /// Fill the missing fields with the ones of the `other` entry of the same
+    /// author.
+    fn merge(&mut self, other: Author) {
+        if self.url.is_none() {
+            self.url = other.url;
+        }
+        if self.title.is_none() {
+            self.title = other.title;
+        }
+        if self.organization.is_none() {
+            self.organization = other.organization;
+        }
+    }
+}
+
+/// Extract all authors of the article together with their profile url, job
+/// title and organization if available.
+///
+/// The authors are collected from the names found by [`authors`], the
+/// JSON-LD `author` objects, the `itemprop="author"` microdata and
+/// `rel="author"` and byline links. Entries with the same name are merged.
+/// The `twitter:creator` is only used if none of these names an author. The
+/// authors keep the order of their first occurrence.
+pub fn authors_detailed(doc: &Document, base_url: Option<&Url>) -> Vec<Author> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    let parse_url = |href: &str| options.parse(href.trim()).ok();
+
+    let mut found: Vec<Author> = byline_authors(doc).into_iter().map(Author::new).collect();
+
+    for object in json_ld(doc) {
+        for author in values(&object, "author") {
+            if let Some(author) = json_ld_author(author, &parse_url) {
+                found.push(author);
+            }
+        }
+    }
+
+    for node in doc.find(Attr("itemprop", "author")) {
+        if let Some(author) = microdata_author(node, &parse_url) {
+            found.push(author);
+        }
+    }
+
+    for link in doc.find(Name("a")) {
+        if link.is_noise_node() || is_in_footer_section(doc, link.index()) {
+            continue;
+        }
+        if link.attr("rel") != Some("author") && !is_in_byline(link) {
+            continue;
+        }
+        let name = clean_author(&author_text(link));
+        if is_valid_name(&name) {
+            let mut author = Author::new(name);
+            author.url = link.attr("href").and_then(parse_url);
+            found.push(author);
+        }
+    }
+
+    if found.is_empty() {
+        found.extend(twitter_creator(doc).map(Author::new));
+    }
+
+    let mut result: Vec<Author> = Vec::new();
+    for author in found {
+        if author.name.is_empty() {
+            continue;
+        }
+        match result.iter_mut().find(|a| a.key() == author.key()) {
+            Some(existing) if existing.url.is_none() && author.url.is_some() => {
+                let previous = std::mem::replace(existing, author);
+                existing.merge(previous);
+            }
+            Some(existing) => existing.merge(author),
+            None => result.push(author),
+        }
+    }
+    result
+}
+
+/// An author of a JSON-LD `author` property, a name or a `Person` object.
+fn json_ld_author(value: &Value, parse_url: &impl Fn(&str) -> Option<Url>) -> Option<Author> {
+    if let Some(name) = value.as_str() {
+        let name = clean_author(name);
+        return is_valid_name(&name).then(|| Author::new(name));
+    }
+    let name = clean_author(value.get("name")?.as_str()?);
+    if name.is_empty() {
+        return None;
+    }
+    let mut author = Author::new(name);
+    author.url = value
+        .get("url")
+        .or_else(|| value.get("@id"))
+        .and_then(Value::as_str)
+        .and_then(parse_url);
+    author.title = value
+        .get("jobTitle")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+    author.organization = value
+        .get("worksFor")
+        .or_else(|| value.get("affiliation"))
+        .and_then(|org| org.as_str().or_else(|| org.get("name")?.as_str()))
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
+    Some(author)
+}
+
+/// An author of an `itemprop="author"` element.
+fn microdata_author(node: Node, parse_url: &impl Fn(&str) -> Option<Url>) -> Option<Author> {
+    let prop = |name: &str| {
+        node.find(Attr("itemprop", name))
+            .next()
+            .map(|n| n.attr("content").map(str::to_string).unwrap_or_else(|| n.text()))
+            .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|s| !s.is_empty())
+    };
+    let name = prop("name").unwrap_or_else(|| author_text(node));
+    let name = clean_author(&name);
+    if !is_valid_name(&name) {
+        return None;
+    }
+    let mut author = Author::new(name);
+    author.url = node
+        .attr("href")
+        .or_else(|| {
+            node.find(Attr("itemprop", "url"))
+                .next()
+                .and_then(|n| n.attr("href").or_else(|| n.attr("content")))
+        })
+        .or_else(|| node.find(Name("a")).next()?.attr("href"))
+        .and_then(parse_url);
+    author.title = prop("jobTitle");
+    author.organization = node
+        .find(Attr("itemprop", "worksFor"))
+        .next()
+        .and_then(|org| {
+            org.find(Attr("itemprop", "name"))
+                .next()
+                .map(|n| n.text())
+                .or_else(|| Some(org.text()))
+        })
+        .map(|s| s.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|s| !s.is_empty());
+    Some(author)
+}
+
+/// Whether the node is part of a byline, an element whose class contains
+/// `byline` or `author`.
+fn is_in_byline(node: Node) -> bool {
+    let mut current = node.parent();
+    for _ in 0..3 {
+        let Some(parent) = current else {
+            break;
+        };
+        if let Some(class) = parent.attr("class") {
+            let class = class.to_lowercase();
+            if class.contains("byline") || class.contains("author") {
+                return true;
+            }
+        }
+        current = parent.parent();
+    }
+    false
 }
 
 // Helper functions for author extraction
+
+/// Remove html tags, a leading `By` and the [`AUTHOR_STOP_WORDS`] of a
+/// trailing part like `, Reuters` or a parenthesized one like `(dpa)` from
+/// the name, a name of only stop words is empty. Words are never changed.
 fn clean_author(s: &str) -> String {
-    let mut out = s.trim().to_string();
-    for stop in AUTHOR_STOP_WORDS.iter() {
-        out = out.replace(stop, "");
-    }
     // Remove HTML tags using cached regex
-    out = RE_HTML_TAGS.replace_all(&out, "").to_string();
-    out.trim_matches(|c: char| c == '.' || c == ',' || c == '-' || c == '/' || c.is_whitespace()).to_string()
+    let text = RE_HTML_TAGS.replace_all(s, "");
+    let mut text = text.trim();
+    loop {
+        if let Some(inner) = text.strip_suffix(')')
+            && let Some(start) = inner.rfind('(')
+            && inner[start + 1..].split([',', '/']).all(is_stop_words)
+        {
+            text = inner[..start].trim_end();
+        } else if let Some((name, tail)) = text.rsplit_once(',')
+            && is_stop_words(tail)
+        {
+            text = name.trim_end();
+        } else {
+            break;
+        }
+    }
+
+    let mut tokens: Vec<&str> = text.split_whitespace().collect();
+    if is_byline_prefix(&tokens) {
+        tokens.remove(0);
+    }
+    let name = tokens.join(" ");
+    if is_stop_words(&name) {
+        return String::new();
+    }
+    name.trim_matches(|c: char| c == '.' || c == ',' || c == '-' || c == '/' || c.is_whitespace())
+        .to_string()
+}
+
+/// Whether the first of the `tokens` is one of the [`BYLINE_PREFIXES`].
+///
+/// A prefix that is also one of the [`NAME_PARTICLES`], like `Von`, is
+/// only part of the byline if it's followed by a first name and a last
+/// name, so `Von Trapp` and `Von der Leyen` are kept.
+fn is_byline_prefix(tokens: &[&str]) -> bool {
+    let Some(first) = tokens.first() else {
+        return false;
+    };
+    let first = first.trim_end_matches(':').to_lowercase();
+    if !BYLINE_PREFIXES.contains(&first.as_str()) {
+        return false;
+    }
+    if !NAME_PARTICLES.contains(&first.as_str()) {
+        return tokens.len() > 1;
+    }
+    tokens.len() > 2 && !NAME_PARTICLES.contains(&tokens[1].to_lowercase().as_str())
+}
+
+/// Whether the `text` only consists of [`AUTHOR_STOP_WORDS`], like
+/// `Reuters Staff`.
+fn is_stop_words(text: &str) -> bool {
+    let tokens: Vec<&str> = text
+        .split_whitespace()
+        .map(|token| token.trim_matches(|c: char| matches!(c, '.' | ';' | ':')))
+        .collect();
+    let mut idx = 0;
+    while idx < tokens.len() {
+        match AUTHOR_STOP_PHRASES.iter().find(|phrase| tokens[idx..].starts_with(phrase.as_slice())) {
+            Some(phrase) => idx += phrase.len(),
+            None => return false,
+        }
+    }
+    !tokens.is_empty()
 }
 
 fn contains_digits(s: &str) -> bool {
     s.chars().any(|c| c.is_ascii_digit())
 }
 
+/// Whether the `text` looks like the name of a person.
+///
+/// Names in CJK scripts, which aren't separated by whitespace, have 2 to 4
+/// characters. Other names have 2 to [`MAX_NAME_WORDS`] words, or up to
+/// [`MAX_PARTICLE_NAME_WORDS`] with particles like `de` or `van`. Hyphenated
+/// compounds like `Jean-Claude` are one word.
 fn is_valid_name(s: &str) -> bool {
-    let word_count = s.split_whitespace().count();
-    // Allow up to 10 words to handle cases where job titles are included
-    // The parse_byline function will trim to first 2 words anyway
-    word_count > 1 && word_count < 10 && !contains_digits(s) && !s.contains('<') && !s.contains('>')
+    if contains_digits(s) || s.contains('<') || s.contains('>') {
+        return false;
+    }
+    let chars: Vec<char> = s.chars().filter(|c| !c.is_whitespace() && !matches!(c, '·' | '・')).collect();
+    if !chars.is_empty() && chars.iter().all(|c| is_cjk(*c)) {
+        return (2..=4).contains(&chars.len());
+    }
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let max = if words.iter().any(|word| is_particle(word)) {
+        MAX_PARTICLE_NAME_WORDS
+    } else {
+        MAX_NAME_WORDS
+    };
+    words.len() > 1 && words.len() <= max
+}
+
+fn is_particle(word: &str) -> bool {
+    NAME_PARTICLES.contains(&word)
+}
+
+/// Whether the char is a Han, Kana or Hangul character.
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{1100}'..='\u{11ff}'
+        | '\u{3130}'..='\u{318f}'
+        | '\u{ac00}'..='\u{d7af}'
+    )
+}
+
+/// The leading words of a byline that make up a name: the given name and
+/// the surname, like `Jane Doe` of `Jane Doe Political Correspondent`.
+///
+/// Names with particles, like `María de los Ángeles Pérez`, are kept whole
+/// if they aren't longer than [`MAX_PARTICLE_NAME_WORDS`].
+fn leading_name<'a>(words: &[&'a str]) -> Vec<&'a str> {
+    if words.len() <= MAX_PARTICLE_NAME_WORDS && words.iter().any(|word| is_particle(word)) {
+        return words.to_vec();
+    }
+    let mut name = Vec::new();
+    let mut parts = 0;
+    for word in words.iter().take(MAX_PARTICLE_NAME_WORDS) {
+        name.push(*word);
+        if !is_particle(word) {
+            parts += 1;
+            if parts == 2 {
+                break;
+            }
+        }
+    }
+    name
+}
+
+/// The names of the first visible byline near the top of the article, an
+/// element with a class or id like `byline`, `article__author` or
+/// `signature`.
+///
+/// The search starts at the headline, or at the article if there is none,
+/// and is limited to the next [`BYLINE_SEARCH_NODES`] nodes.
+fn visible_byline_authors(doc: &Document) -> Vec<String> {
+    let start = doc
+        .find(Name("h1").or(Name("article")))
+        .next()
+        .or_else(|| doc.find(Name("body")).next())
+        .map(|node| node.index())
+        .unwrap_or_default();
+    doc.find(Any)
+        .skip_while(|node| node.index() < start)
+        .take_while(|node| node.index() < start + BYLINE_SEARCH_NODES)
+        .filter(|node| is_visible_byline(node))
+        .filter(|node| {
+            !is_in_footer_section(doc, node.index())
+                && !successors(node.parent(), Node::parent).any(|n| is_comment_section(&n))
+        })
+        .map(|node| parse_byline(&author_text(node)))
+        .find(|names| !names.is_empty())
+        .unwrap_or_default()
+}
+
+fn is_visible_byline(node: &Node) -> bool {
+    if Name("script").or(Name("style")).or(Name("meta")).or(Name("figcaption")).matches(node) {
+        return false;
+    }
+    ["class", "id"]
+        .into_iter()
+        .filter_map(|attr| node.attr(attr))
+        .flat_map(str::split_whitespace)
+        .any(|token| class_parts(token).iter().any(|part| BYLINE_PARTS.contains(&part.as_str())))
+}
+
+/// The parts of a byline that name the authors of the text.
+///
+/// In a byline with role labels like `Текст: Иван Петров, Фото: Анна
+/// Сидорова` only the names after labels of the text are kept. Without such
+/// a label everything before the first other label is kept.
+fn byline_segments(s: &str) -> Vec<&str> {
+    let mut labels = Vec::new();
+    for (colon, _) in s.match_indices(':') {
+        let before = s[..colon].trim_end();
+        let word = before.rsplit(char::is_whitespace).next().unwrap_or_default();
+        let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+        if let Some((_, keep)) = BYLINE_LABELS.iter().find(|(label, _)| word.to_lowercase() == *label) {
+            labels.push((before.len() - word.len(), colon + 1, *keep));
+        }
+    }
+    let Some(&(first, _, _)) = labels.first() else {
+        return vec![s];
+    };
+    if !labels.iter().any(|(_, _, keep)| *keep) {
+        return vec![&s[..first]];
+    }
+    labels
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, _, keep))| *keep)
+        .map(|(i, (_, end, _))| {
+            let next = labels.get(i + 1).map(|(start, _, _)| *start).unwrap_or(s.len());
+            &s[*end..next]
+        })
+        .collect()
+}
+
+/// Split the `token` at standalone [`BYLINE_CONJUNCTIONS`].
+fn split_conjunctions(token: &str) -> Vec<String> {
+    let mut names = vec![Vec::new()];
+    for word in token.split_whitespace() {
+        if BYLINE_CONJUNCTIONS.contains(&word.to_lowercase().as_str()) {
+            names.push(Vec::new());
+        } else if let Some(name) = names.last_mut() {
+            name.push(word);
+        }
+    }
+    names.into_iter().map(|name| name.join(" ")).collect()
 }
 
 fn parse_byline(s: &str) -> Vec<String> {
     let s = s.replace(['\n', '\t', '\r', '\u{a0}'], " ");
     let mut out = Vec::new();
-    for token in s.split(|c| c == '·' || c == ',' || c == '|' || c == '/' || c == '\u{a0}') {
-        let t = token.trim();
-        // Extract first 2 words first (to handle cases like "Name Surname job title company.com")
-        let words: Vec<&str> = t.split_whitespace().collect();
-        if words.len() < 2 {
-            continue;  // Need at least 2 words for a name
-        }
-        let name = if words.len() > 2 {
-            words[..2].join(" ")
-        } else {
-            t.to_string()
-        };
+    let tokens = byline_segments(&s)
+        .into_iter()
+        .flat_map(|segment| segment.split(['·', ',', '|', '/', '\u{a0}']))
+        .flat_map(split_conjunctions);
+    for token in tokens {
+        // Extract the name first (to handle cases like "Name Surname job title company.com")
+        let mut words: Vec<&str> = token.split_whitespace().collect();
+        if is_byline_prefix(&words) {
+            words.remove(0);
+        }
+        let name = leading_name(&words).join(" ");
         // Now validate the extracted name (not the full string)
         if is_valid_name(&name) {
             out.push(clean_author(&name));
@@ -210,4 +710,167 @@ mod tests {
             .unwrap();
         assert_eq!(m.as_str(), "J\'oseph-Kelley");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn detailed_authors() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta name="author" content="Jane Doe">
+            <script type="application/ld+json">{"@type": "NewsArticle", "author": [
+                {"@type": "Person", "name": "John Roe", "url": "https://example.com/authors/john-roe",
+                 "jobTitle": "Senior Editor", "worksFor": {"@type": "Organization", "name": "Example News"}}
+            ]}</script>
+            </head><body>
+            <div class="article-byline">By <a href="/authors/jane-doe">Jane Doe</a></div>
+            <span itemprop="author" itemscope><span itemprop="name">Max Mustermann</span>,
+                <span itemprop="jobTitle">Reporter</span></span>
+            <p>The harbour will be expanded, <a href="/news/plan.html">the plan</a> says.</p>
+            </body></html>"#,
+        );
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let authors = authors_detailed(&doc, Some(&base_url));
+        assert_eq!(
+            authors,
+            vec![
+                Author {
+                    name: "Jane Doe".to_string(),
+                    url: Some(Url::parse("https://example.com/authors/jane-doe").unwrap()),
+                    title: None,
+                    organization: None,
+                },
+                Author {
+                    name: "Max Mustermann".to_string(),
+                    url: None,
+                    title: Some("Reporter".to_string()),
+                    organization: None,
+                },
+                Author {
+                    name: "John Roe".to_string(),
+                    url: Some(Url::parse("https://example.com/authors/john-roe").unwrap()),
+                    title: Some("Senior Editor".to_string()),
+                    organization: Some("Example News".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn twitter_creator_hint() {
+        let creator = |content: &str, byline: &str| {
+            let doc = Document::from(
+                format!(
+                    "<html><head><meta name=\"twitter:creator\" content=\"{}\"></head><body>{}</body></html>",
+                    content, byline
+                )
+                .as_str(),
+            );
+            authors(&doc).into_iter().map(Cow::into_owned).collect::<Vec<_>>()
+        };
+        assert_eq!(creator("@Jane Doe", ""), vec!["Jane Doe"]);
+        assert!(creator("@janedoe", "").is_empty());
+        assert_eq!(
+            creator("@Jane Doe", "<span class=\"byline\">John Roe</span>"),
+            vec!["John Roe"]
+        );
+    }
+
+    #[test]
+    fn visible_byline() {
+        assert_eq!(parse_byline("Текст: Иван Петров | Фото: Анна Сидорова"), vec!["Иван Петров"]);
+        assert_eq!(parse_byline("Фото: Анна Сидорова. Текст: Иван Петров"), vec!["Иван Петров"]);
+        assert_eq!(parse_byline("Jane Doe, Photo: John Roe"), vec!["Jane Doe"]);
+
+        let comments = "<p>Lorem ipsum dolor sit amet.</p>".repeat(BYLINE_SEARCH_NODES);
+        let doc = Document::from(
+            format!(
+                "<html><body><h1>Гавань расширят</h1>\
+                 <div class=\"b-material__signature\">Текст: Иван Петров, Фото: Анна Сидорова</div>\
+                 <p>Совет одобрил расширение гавани.</p>{}\
+                 <div class=\"post-writer\">Пётр Смирнов</div></body></html>",
+                comments
+            )
+            .as_str(),
+        );
+        assert_eq!(authors(&doc), vec!["Иван Петров"]);
+
+        let doc = Document::from(
+            format!(
+                "<html><body><h1>Гавань расширят</h1><p>Совет одобрил расширение гавани.</p>{}\
+                 <div class=\"post-writer\">Пётр Смирнов</div></body></html>",
+                comments
+            )
+            .as_str(),
+        );
+        assert!(authors(&doc).is_empty());
+    }
+
+    #[test]
+    fn byline_order() {
+        let doc = Document::from(
+            "<html><body><h1>Harbour</h1><p class=\"byline\">By Jane Zhu and Aaron Ball</p></body></html>",
+        );
+        assert_eq!(authors(&doc), vec!["Jane Zhu", "Aaron Ball"]);
+        assert_eq!(
+            authors_detailed(&doc, None).into_iter().map(|a| a.name).collect::<Vec<_>>(),
+            vec!["Jane Zhu", "Aaron Ball"]
+        );
+
+        let doc = Document::from(
+            "<html><head><meta property=\"article:author\" content=\"Jane Zhu\">\
+             <meta name=\"author\" content=\"Aaron Ball, jane zhu\"></head></html>",
+        );
+        let mut names = authors(&doc);
+        assert_eq!(names, vec!["Jane Zhu", "Aaron Ball"]);
+        AuthorOrder::Alphabetical.sort(&mut names, |name| name.to_lowercase());
+        assert_eq!(names, vec!["Aaron Ball", "Jane Zhu"]);
+    }
+
+    #[test]
+    fn script_aware_names() {
+        assert!(is_valid_name("王小明"));
+        assert!(is_valid_name("김민준"));
+        assert!(is_valid_name("山田・太郎"));
+        assert!(!is_valid_name("王"));
+        assert!(!is_valid_name("王小明是一名记者"));
+        assert!(is_valid_name("محمد بن سلمان"));
+        assert!(is_valid_name("Jean-Claude de la Cruz"));
+        assert!(is_valid_name("María de los Ángeles Pérez"));
+        assert!(!is_valid_name("Sting"));
+        assert!(!is_valid_name("Jane Doe 2024"));
+        assert!(!is_valid_name("Jane Doe Senior Political Correspondent"));
+
+        assert_eq!(parse_byline("王小明 | 김민준"), vec!["王小明", "김민준"]);
+        assert_eq!(
+            parse_byline("Jean-Claude de la Cruz, محمد بن سلمان"),
+            vec!["Jean-Claude de la Cruz", "محمد بن سلمان"]
+        );
+        assert_eq!(parse_byline("María de los Ángeles Pérez"), vec!["María de los Ángeles Pérez"]);
+        assert_eq!(parse_byline("Jane Doe Political Correspondent"), vec!["Jane Doe"]);
+    }
+
+    #[test]
+    fn clean_author_keeps_words() {
+        assert_eq!(clean_author("Byron Apter"), "Byron Apter");
+        assert_eq!(clean_author("APpleton Smith"), "APpleton Smith");
+        assert_eq!(clean_author("Reuters Staff"), "");
+        assert_eq!(clean_author("By PTI"), "");
+        assert_eq!(clean_author("Anna Apfel (dpa)"), "Anna Apfel");
+        assert_eq!(clean_author("By <b>Jane Doe</b>, Senior Reporter"), "Jane Doe");
+        assert_eq!(clean_author("Von Max Mustermann"), "Max Mustermann");
+        assert_eq!(clean_author("Von Trapp"), "Von Trapp");
+        assert_eq!(clean_author("Von der Leyen"), "Von der Leyen");
+        assert_eq!(clean_author("von Ursula von der Leyen"), "Ursula von der Leyen");
+        assert_eq!(clean_author("Jane Doe (AP)"), "Jane Doe");
+        assert_eq!(clean_author("Jane Doe, Reuters"), "Jane Doe");
+        assert_eq!(clean_author("Ani DiFranco"), "Ani DiFranco");
+        assert_eq!(clean_author("Jane Staff"), "Jane Staff");
+        assert_eq!(clean_author("Rhys ap Gruffydd"), "Rhys ap Gruffydd");
+
+        for name in ["Ani DiFranco", "Jane Staff", "Rhys ap Gruffydd"] {
+            let doc = Document::from(
+                format!(r#"<html><head><meta name="author" content="{}"></head></html>"#, name).as_str(),
+            );
+            assert_eq!(authors(&doc), vec![name]);
+        }
+    }
+}
@@ -1,11 +1,69 @@
-use reqwest::Url;
+use url::Url;
 use select::document::Document;
 use select::predicate::Name;
 
 /// Finds the href in the `<base>` tag.
 pub fn base_url(doc: &Document) -> Option<Url> {
+    resolve_base(doc, None)
+}
+
+/// The url relative urls of the document resolve against: the href of the
+/// first valid `<base>` tag, itself resolved against the `fallback` if it is
+/// relative, or the `fallback`.
+pub fn resolve_base(doc: &Document, fallback: Option<&Url>) -> Option<Url> {
+    let options = Url::options().base_url(fallback);
     doc.find(Name("base"))
         .filter_map(|n| n.attr("href"))
-        .filter_map(|href| Url::parse(href).ok())
-        .next()
+        .find_map(|href| options.parse(href.trim()).ok())
+        .or_else(|| fallback.cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extract::{DefaultExtractor, Extractor};
+    use crate::text::ArticleTextNodeExtractor;
+    use crate::{Article, Language};
+
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/base/cdn.html");
+
+    #[test]
+    fn relative_urls_resolve_against_base() {
+        let page = Url::parse("https://example.com/news/harbour.html").unwrap();
+        let doc = Document::from(FIXTURE);
+        assert_eq!(
+            resolve_base(&doc, Some(&page)).unwrap().as_str(),
+            "https://cdn.example.com/articles/"
+        );
+        let relative = Document::from(r#"<html><head><base href="../static/"></head></html>"#);
+        assert_eq!(
+            resolve_base(&relative, Some(&page)).unwrap().as_str(),
+            "https://example.com/static/"
+        );
+        assert_eq!(base_url(&relative), None);
+        assert_eq!(resolve_base(&Document::from("<html></html>"), Some(&page)), Some(page.clone()));
+
+        let article = Article::new(page.as_str(), FIXTURE).unwrap();
+        let content = &article.content;
+        let cdn = |path: &str| Url::parse("https://cdn.example.com/articles/").unwrap().join(path).unwrap();
+        assert_eq!(content.top_image, Some(cdn("img/harbour.jpg")));
+        assert_eq!(content.thumbnail, Some(cdn("img/harbour-thumb.jpg")));
+        assert_eq!(content.references, vec![cdn("reports/harbour-plan.pdf")]);
+        // images of a `<figure>` are only part of the detailed images
+        assert!(content.images.is_empty());
+        let node = ArticleTextNodeExtractor::calculate_best_node(&doc, Language::English).unwrap();
+        let base = resolve_base(&doc, Some(&page));
+        let images: Vec<_> = node.images_detailed(base.as_ref()).into_iter().map(|image| image.url).collect();
+        assert_eq!(images, vec![cdn("img/quay.jpg")]);
+        assert_eq!(
+            content.canonical.as_ref().map(|canonical| &canonical.url),
+            Some(&cdn("harbour-expansion.html"))
+        );
+
+        let extractor = DefaultExtractor::default();
+        assert_eq!(extractor.amp_url(&doc, Some(&page)), Some(cdn("amp/harbour-expansion.html")));
+        assert_eq!(extractor.favicon(&doc, &page), Some(cdn("icons/favicon-32.png")));
+        assert!(extractor.image_urls(&doc, Some(&page)).contains(&cdn("img/quay.jpg")));
+    }
 }
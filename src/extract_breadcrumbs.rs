@@ -0,0 +1,279 @@
+use url::Url;
+use select::document::Document;
+use select::node::Node;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::extract_base_url::resolve_base;
+use crate::extract_json_ld::{has_type, json_ld, text, values};
+use crate::extract_section::normalize_crumb;
+use crate::extract_title::title;
+
+/// A crumb of the breadcrumb trail of a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Breadcrumb {
+    /// The label of the crumb.
+    pub name: String,
+    /// The page the crumb links to.
+    pub url: Option<Url>,
+    /// The 1-based position of the crumb in the trail.
+    pub position: usize,
+    /// Whether the crumb is the article itself: it is marked with
+    /// `aria-current` or its name is the title of the article.
+    pub is_current: bool,
+}
+
+/// The breadcrumb trail of the page, sorted by position.
+///
+/// Uses the first of these that has crumbs:
+///   1. A schema.org `BreadcrumbList` in microdata, made of
+///      `itemprop="itemListElement"` elements with `name`, `item` and
+///      `position` properties
+///   2. A JSON-LD `BreadcrumbList`
+///   3. The `li` or `a` elements of the first element whose class contains
+///      `breadcrumb`
+///
+/// Relative urls are resolved against the `<base>` of the document or the
+/// `base_url`, see [`resolve_base`].
+pub fn breadcrumbs(doc: &Document, base_url: Option<&Url>) -> Vec<Breadcrumb> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    let parse = |href: &str| options.parse(href.trim()).ok();
+    let mut crumbs = microdata_crumbs(doc, &parse);
+    if crumbs.is_empty() {
+        crumbs = json_ld_crumbs(doc, &parse);
+    }
+    if crumbs.is_empty() {
+        crumbs = class_crumbs(doc, &parse);
+    }
+    crumbs.sort_by_key(|crumb| crumb.position);
+
+    if let Some(title) = title(doc).map(|title| normalize_crumb(&title)) {
+        for crumb in crumbs.iter_mut() {
+            crumb.is_current |= crumb.name.eq_ignore_ascii_case(&title);
+        }
+    }
+    crumbs
+}
+
+fn microdata_crumbs(doc: &Document, parse: &dyn Fn(&str) -> Option<Url>) -> Vec<Breadcrumb> {
+    let list = match doc.find(|node: &Node| {
+        node.attr("itemtype")
+            .map(|ty| ty.ends_with("/BreadcrumbList"))
+            .unwrap_or_default()
+    })
+    .next()
+    {
+        Some(list) => list,
+        None => return Vec::new(),
+    };
+    list.find(|node: &Node| has_itemprop(node, "itemListElement"))
+        .enumerate()
+        .filter_map(|(idx, element)| {
+            let name = element
+                .find(|node: &Node| has_itemprop(node, "name"))
+                .next()
+                .map(|node| node.attr("content").map(str::to_string).unwrap_or_else(|| node.text()))
+                .map(|name| normalize_crumb(&name))
+                .filter(|name| !name.is_empty())?;
+            let url = element
+                .find(|node: &Node| has_itemprop(node, "item"))
+                .next()
+                .and_then(|node| {
+                    node.attr("href")
+                        .or_else(|| node.attr("itemid"))
+                        .or_else(|| node.attr("content"))
+                })
+                .or_else(|| element.find(|node: &Node| node.attr("href").is_some()).next()?.attr("href"))
+                .and_then(parse);
+            let position = element
+                .find(|node: &Node| has_itemprop(node, "position"))
+                .next()
+                .and_then(|node| {
+                    node.attr("content")
+                        .map(str::to_string)
+                        .unwrap_or_else(|| node.text())
+                        .trim()
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(idx + 1);
+            Some(Breadcrumb {
+                name,
+                url,
+                position,
+                is_current: is_aria_current(&element),
+            })
+        })
+        .collect()
+}
+
+fn json_ld_crumbs(doc: &Document, parse: &dyn Fn(&str) -> Option<Url>) -> Vec<Breadcrumb> {
+    let list = match json_ld(doc)
+        .into_iter()
+        .find(|object| has_type(object, &["BreadcrumbList"]))
+    {
+        Some(list) => list,
+        None => return Vec::new(),
+    };
+    values(&list, "itemListElement")
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, element)| {
+            let item = element.get("item");
+            let name = text(element, "name")
+                .or_else(|| text(item?, "name"))
+                .map(normalize_crumb)
+                .filter(|name| !name.is_empty())?;
+            let url = item
+                .and_then(|item| {
+                    item.as_str()
+                        .or_else(|| item.get("@id")?.as_str())
+                        .or_else(|| item.get("url")?.as_str())
+                })
+                .and_then(parse);
+            let position = match element.get("position") {
+                Some(Value::Number(position)) => position.as_u64().map(|position| position as usize),
+                Some(Value::String(position)) => position.trim().parse().ok(),
+                _ => None,
+            }
+            .unwrap_or(idx + 1);
+            Some(Breadcrumb {
+                name,
+                url,
+                position,
+                is_current: false,
+            })
+        })
+        .collect()
+}
+
+fn class_crumbs(doc: &Document, parse: &dyn Fn(&str) -> Option<Url>) -> Vec<Breadcrumb> {
+    let trail = match doc
+        .find(|node: &Node| {
+            node.attr("class")
+                .map(|class| class.to_lowercase().contains("breadcrumb"))
+                .unwrap_or_default()
+        })
+        .next()
+    {
+        Some(trail) => trail,
+        None => return Vec::new(),
+    };
+    let items: Vec<Node> = trail.find(|node: &Node| node.name() == Some("li")).collect();
+    let items = if items.is_empty() {
+        trail.find(|node: &Node| node.name() == Some("a")).collect()
+    } else {
+        items
+    };
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let name = normalize_crumb(&item.text());
+            if name.is_empty() {
+                return None;
+            }
+            let url = if item.name() == Some("a") {
+                item.attr("href")
+            } else {
+                item.find(|node: &Node| node.name() == Some("a")).next().and_then(|a| a.attr("href"))
+            }
+            .and_then(parse);
+            let is_current = is_aria_current(&item);
+            Some((name, url, is_current))
+        })
+        .enumerate()
+        .map(|(idx, (name, url, is_current))| Breadcrumb {
+            name,
+            url,
+            position: idx + 1,
+            is_current,
+        })
+        .collect()
+}
+
+/// Whether the `itemprop` attribute of the node lists the `prop`.
+fn has_itemprop(node: &Node, prop: &str) -> bool {
+    node.attr("itemprop")
+        .map(|props| props.split_whitespace().any(|p| p == prop))
+        .unwrap_or_default()
+}
+
+/// Whether the node or one of its descendants is marked as current page.
+fn is_aria_current(node: &Node) -> bool {
+    node.attr("aria-current").is_some()
+        || node
+            .find(|node: &Node| node.attr("aria-current").is_some())
+            .next()
+            .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(crumbs: &[Breadcrumb]) -> Vec<&str> {
+        crumbs.iter().map(|crumb| crumb.name.as_str()).collect()
+    }
+
+    #[test]
+    fn microdata_breadcrumbs() {
+        let doc = Document::from(
+            r#"<html><head><title>Harbour expansion approved</title></head><body>
+            <ol itemscope itemtype="https://schema.org/BreadcrumbList">
+              <li itemprop="itemListElement" itemscope itemtype="https://schema.org/ListItem">
+                <a itemprop="item" href="/world/europe"><span itemprop="name">Europe</span></a>
+                <meta itemprop="position" content="2"></li>
+              <li itemprop="itemListElement" itemscope itemtype="https://schema.org/ListItem">
+                <a itemprop="item" href="/world"><span itemprop="name">World</span></a>
+                <meta itemprop="position" content="1"></li>
+              <li itemprop="itemListElement" itemscope itemtype="https://schema.org/ListItem">
+                <span itemprop="name">Harbour expansion approved</span>
+                <meta itemprop="position" content="3"></li>
+            </ol></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/news/harbour").unwrap();
+        let crumbs = breadcrumbs(&doc, Some(&base));
+        assert_eq!(names(&crumbs), vec!["World", "Europe", "Harbour expansion approved"]);
+        assert_eq!(crumbs[1].url.as_ref().map(Url::as_str), Some("https://example.com/world/europe"));
+        assert_eq!(crumbs[2].url, None);
+        assert_eq!(
+            crumbs.iter().map(|crumb| crumb.is_current).collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+    }
+
+    #[test]
+    fn json_ld_breadcrumbs() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">{"@context": "https://schema.org",
+            "@type": "BreadcrumbList", "itemListElement": [
+              {"@type": "ListItem", "position": 2, "name": "Politics", "item": "https://example.com/politics"},
+              {"@type": "ListItem", "position": "1", "item": {"@id": "https://example.com/", "name": "Home"}}
+            ]}</script></head><body></body></html>"#,
+        );
+        let crumbs = breadcrumbs(&doc, None);
+        assert_eq!(names(&crumbs), vec!["Home", "Politics"]);
+        assert_eq!(crumbs[0].url.as_ref().map(Url::as_str), Some("https://example.com/"));
+        assert_eq!(crumbs[1].position, 2);
+    }
+
+    #[test]
+    fn class_breadcrumbs() {
+        let doc = Document::from(
+            r#"<html><body><div class="c-breadcrumbs"><ul>
+            <li><a href="/">Home</a> ›</li>
+            <li><a href="/sport">Sport</a> ›</li>
+            <li aria-current="page">Final</li>
+            </ul></div></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/sport/final").unwrap();
+        let crumbs = breadcrumbs(&doc, Some(&base));
+        assert_eq!(names(&crumbs), vec!["Home", "Sport", "Final"]);
+        assert_eq!(crumbs[1].url.as_ref().map(Url::as_str), Some("https://example.com/sport"));
+        assert_eq!(crumbs[2].position, 3);
+        assert!(crumbs[2].is_current);
+    }
+}
@@ -1,25 +1,122 @@
-use reqwest::Url;
+use url::Url;
 use select::document::Document;
-use select::predicate::{Attr, Name, Predicate};
+use select::predicate::{Attr, Name};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::extract_base_url::resolve_base;
 use crate::extract_meta::meta_content;
+use crate::public_suffix::registrable_domain;
+
+/// Where the canonical url of a page was declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum CanonicalSource {
+    /// `<link rel="canonical">`
+    Link,
+    /// `<meta property="og:url">`
+    OgUrl,
+}
+
+/// The canonical url of a page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct CanonicalLink {
+    pub url: Url,
+    pub source: CanonicalSource,
+    /// Whether the url belongs to another site than the page, which makes
+    /// it untrustworthy: anyone can claim another site's page as canonical.
+    pub cross_domain: bool,
+}
 
 /// Return the article's canonical URL
 ///
 /// Gets the first available value of:
 ///   1. The rel=canonical tag
 ///   2. The og:url tag
-pub fn canonical_link(doc: &Document) -> Option<Url> {
-    if let Some(link) = doc
-        .find(Name("link").and(Attr("rel", "canonical")))
+///
+/// Relative values are resolved against the `<base>` of the document or the
+/// `base_url`, see [`resolve_base`].
+pub fn canonical_link(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+    canonical_link_detailed(doc, base_url).map(|canonical| canonical.url)
+}
+
+/// Like [`canonical_link`], but also reports where the url was found and
+/// whether it belongs to another site than the `base_url`.
+///
+/// A value that isn't a valid url is skipped.
+pub fn canonical_link_detailed(doc: &Document, base_url: Option<&Url>) -> Option<CanonicalLink> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    let link = doc
+        .find(Name("link"))
+        .filter(|node| {
+            node.attr("rel")
+                .map(|rel| rel.split_whitespace().any(|rel| rel.eq_ignore_ascii_case("canonical")))
+                .unwrap_or_default()
+        })
         .filter_map(|node| node.attr("href"))
-        .next()
-    {
-        return Url::parse(link).ok();
+        .find_map(|href| options.parse(href.trim()).ok())
+        .map(|url| (url, CanonicalSource::Link));
+    let (url, source) = link.or_else(|| {
+        let meta = meta_content(doc, Attr("property", "og:url"))?;
+        options
+            .parse(meta.trim())
+            .ok()
+            .map(|url| (url, CanonicalSource::OgUrl))
+    })?;
+    let cross_domain = base_url
+        .map(|base_url| !is_same_site(&url, base_url))
+        .unwrap_or_default();
+    Some(CanonicalLink {
+        url,
+        source,
+        cross_domain,
+    })
+}
+
+/// Whether both urls share their registrable domain according to the
+/// public suffix list, like `example.co.uk` for `www.example.co.uk` and
+/// `amp.example.co.uk`.
+pub fn is_same_site(a: &Url, b: &Url) -> bool {
+    match (a.host_str(), b.host_str()) {
+        (Some(a), Some(b)) => registrable_domain(a).eq_ignore_ascii_case(registrable_domain(b)),
+        _ => false,
     }
+}
 
-    if let Some(meta) = meta_content(doc, Attr("property", "og:url")) {
-        return Url::parse(&*meta).ok();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_canonical() {
+        let doc = Document::from(
+            r#"<html><head><link rel="canonical" href="/news/123">
+            <meta property="og:url" content="https://example.com/other"></head><body></body></html>"#,
+        );
+        let base = Url::parse("https://www.example.com/news/123?utm_source=feed").unwrap();
+        let canonical = canonical_link_detailed(&doc, Some(&base)).unwrap();
+        assert_eq!(canonical.url.as_str(), "https://www.example.com/news/123");
+        assert_eq!(canonical.source, CanonicalSource::Link);
+        assert!(!canonical.cross_domain);
     }
 
-    None
+    #[test]
+    fn cross_domain_og_url() {
+        let doc = Document::from(
+            r#"<html><head><link rel="canonical" href="http://[invalid">
+            <meta property="og:url" content="https://attacker.example.org/news/123"></head><body></body></html>"#,
+        );
+        let base = Url::parse("https://news.example.co.uk/news/123").unwrap();
+        let canonical = canonical_link_detailed(&doc, Some(&base)).unwrap();
+        assert_eq!(canonical.source, CanonicalSource::OgUrl);
+        assert!(canonical.cross_domain);
+
+        let amp = Url::parse("https://amp.example.co.uk/news/123").unwrap();
+        assert!(is_same_site(&base, &amp));
+        assert!(!is_same_site(&base, &Url::parse("https://other.co.uk/").unwrap()));
+        let pages = Url::parse("https://paper.github.io/news/123").unwrap();
+        assert!(!is_same_site(&pages, &Url::parse("https://attacker.github.io/").unwrap()));
+    }
 }
@@ -0,0 +1,225 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Any, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::extract_json_ld::json_ld;
+use crate::text::class_parts;
+
+/// Parts of class and id tokens of elements that show the number of
+/// comments, like `comments-count` or `commentCount`.
+const COUNT_PARTS: [&str; 3] = ["count", "counter", "number"];
+
+/// Parts of class and id tokens that, together with `comments`, name a
+/// comment section, like `article-comments` or `comments-wrapper`. Tokens
+/// with other parts, like `comments-open`, only describe the page.
+const SECTION_PARTS: [&str; 12] = [
+    "comments", "commentlist", "article", "post", "user", "reader", "js", "section", "area", "container",
+    "wrapper", "thread",
+];
+
+/// The service that hosts the comments of an article.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum CommentProvider {
+    Disqus,
+    Facebook,
+    /// The Coral Project.
+    Coral,
+    /// OpenWeb, formerly Spot.IM.
+    OpenWeb,
+}
+
+impl CommentProvider {
+    /// The provider whose embed is the node.
+    pub fn from_node(node: &Node) -> Option<Self> {
+        let attr = |name| node.attr(name).unwrap_or_default();
+        let src = attr("src").to_lowercase();
+        if attr("id") == "disqus_thread" || src.contains("disqus.com") {
+            Some(CommentProvider::Disqus)
+        } else if attr("class").split_whitespace().any(|token| token == "fb-comments")
+            || src.contains("facebook.com/plugins/comments")
+        {
+            Some(CommentProvider::Facebook)
+        } else if attr("id") == "coral_thread" {
+            Some(CommentProvider::Coral)
+        } else if node.attr("data-spotim-module").is_some() || src.contains("spot.im") {
+            Some(CommentProvider::OpenWeb)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether and how many comments an article has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct CommentInfo {
+    /// The number of comments the page shows.
+    pub count: Option<u64>,
+    /// The service that hosts the comments, `None` for comments of the site
+    /// itself.
+    pub provider: Option<CommentProvider>,
+}
+
+/// The comments of the article, `None` if the page has neither a comment
+/// count nor a comment section.
+///
+/// The count is taken from, in this order:
+///   1. The `itemprop="commentCount"` element
+///   2. The `commentCount` of a JSON-LD object
+///   3. The first element whose class or id is like `comments-count`
+///
+/// The provider is detected by its embed, like `<div id="disqus_thread">`.
+pub fn comment_info(doc: &Document) -> Option<CommentInfo> {
+    let count = microdata_count(doc)
+        .or_else(|| json_ld_count(doc))
+        .or_else(|| {
+            doc.find(is_comment_count)
+                .find_map(|node| parse_count(&node.text()))
+        });
+    let provider = doc.find(Any).find_map(|node| CommentProvider::from_node(&node));
+    if count.is_none() && provider.is_none() && doc.find(is_comment_section).next().is_none() {
+        return None;
+    }
+    Some(CommentInfo { count, provider })
+}
+
+/// Whether the node is the comment section of the page: an embed of a
+/// [`CommentProvider`] or an element with a class or id like `comments` or
+/// `article-comments`.
+pub fn is_comment_section(node: &Node) -> bool {
+    if CommentProvider::from_node(node).is_some() && !Name("script").matches(node) {
+        return true;
+    }
+    tokens(node).into_iter().any(|parts| {
+        parts.iter().any(|part| part == "comments" || part == "commentlist")
+            && parts.iter().all(|part| SECTION_PARTS.contains(&part.as_str()))
+    })
+}
+
+/// Whether the class or id of the node is like `comments-count`.
+fn is_comment_count(node: &Node) -> bool {
+    tokens(node).into_iter().any(|parts| {
+        parts.iter().any(|part| part == "comment" || part == "comments")
+            && parts.iter().any(|part| COUNT_PARTS.contains(&part.as_str()))
+    })
+}
+
+/// The parts of the class and id tokens of the node.
+fn tokens(node: &Node) -> Vec<Vec<String>> {
+    ["class", "id"]
+        .into_iter()
+        .filter_map(|attr| node.attr(attr))
+        .flat_map(str::split_whitespace)
+        .map(class_parts)
+        .collect()
+}
+
+fn microdata_count(doc: &Document) -> Option<u64> {
+    doc.find(|node: &Node| {
+        node.attr("itemprop")
+            .map(|props| props.split_whitespace().any(|prop| prop == "commentCount"))
+            .unwrap_or_default()
+    })
+    .find_map(|node| match node.attr("content") {
+        Some(content) => parse_count(content),
+        None => parse_count(&node.text()),
+    })
+}
+
+fn json_ld_count(doc: &Document) -> Option<u64> {
+    json_ld(doc)
+        .iter()
+        .find_map(|object| match object.get("commentCount")? {
+            Value::Number(count) => count.as_u64(),
+            Value::String(count) => parse_count(count),
+            _ => None,
+        })
+}
+
+/// The leading integer of the `text`, like `1234` of `1,234 comments` or
+/// `154` of `154 комментария`.
+///
+/// `,`, `.`, `'` and spaces are thousands separators if exactly three
+/// digits follow them.
+pub fn parse_count(text: &str) -> Option<u64> {
+    let start = text.find(|c: char| c.is_ascii_digit())?;
+    let chars: Vec<char> = text[start..].chars().collect();
+    let mut digits = String::new();
+    let mut idx = 0;
+    while idx < chars.len() {
+        let c = chars[idx];
+        if c.is_ascii_digit() {
+            digits.push(c);
+            idx += 1;
+        } else if matches!(c, ',' | '.' | '\'' | ' ' | '\u{a0}' | '\u{202f}')
+            && chars.len() > idx + 3
+            && chars[idx + 1..idx + 4].iter().all(char::is_ascii_digit)
+            && !chars.get(idx + 4).map(char::is_ascii_digit).unwrap_or_default()
+        {
+            idx += 1;
+        } else {
+            break;
+        }
+    }
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::text::ArticleTextNode;
+
+    use super::*;
+
+    #[test]
+    fn comment_counts() {
+        assert_eq!(parse_count("1,234 comments"), Some(1234));
+        assert_eq!(parse_count("Comments (1.234.567)"), Some(1_234_567));
+        assert_eq!(parse_count("154 комментария"), Some(154));
+        assert_eq!(parse_count("12 345 Kommentare"), Some(12345));
+        assert_eq!(parse_count("1.5k"), Some(1));
+        assert_eq!(parse_count("no comments"), None);
+    }
+
+    #[test]
+    fn disqus_comment_section() {
+        let doc = Document::from(
+            r##"<html><body><article class="post comments-open">
+            <p>The council approved the expansion of the harbour on Tuesday. Construction is expected
+            to start next spring and to last for three years, the mayor said after the vote.</p>
+            <a href="#comments"><span class="comments-count">1,024 comments</span></a>
+            <section id="comments"><div id="disqus_thread"><p>First! What a great decision for the city.</p></div></section>
+            </article></body></html>"##,
+        );
+        assert_eq!(
+            comment_info(&doc),
+            Some(CommentInfo {
+                count: Some(1024),
+                provider: Some(CommentProvider::Disqus),
+            })
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        let text = ArticleTextNode::new(article).clean_text();
+        assert!(text.contains("approved the expansion"));
+        assert!(!text.contains("First!"));
+    }
+
+    #[test]
+    fn microdata_comment_count() {
+        let doc = Document::from(
+            r#"<html><body><meta itemprop="commentCount" content="154">
+            <div class="fb-comments" data-href="https://example.com/news/harbour"></div></body></html>"#,
+        );
+        assert_eq!(
+            comment_info(&doc),
+            Some(CommentInfo {
+                count: Some(154),
+                provider: Some(CommentProvider::Facebook),
+            })
+        );
+        assert_eq!(comment_info(&Document::from("<html><body><p>Text</p></body></html>")), None);
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::NaiveDate;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::block::ContentBlock;
+use crate::date::{Date, DateExtractor};
+use crate::text::ArticleTextNode;
+
+/// Max number of characters of the excerpt that replaces a missing meta
+/// description of an article.
+pub const DEFAULT_EXCERPT_LEN: usize = 300;
+
+/// Max number of paragraphs an excerpt is taken from.
+pub const MAX_EXCERPT_PARAGRAPHS: usize = 2;
+
+/// Paragraphs up to this number of characters that contain the name of an
+/// author or the publishing date are considered the byline.
+pub const MAX_BYLINE_LEN: usize = 120;
+
+/// Appended to a truncated excerpt.
+pub const ELLIPSIS: char = '…';
+
+/// Where the description of an article comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum DescriptionSource {
+    /// The meta description or `description` microdata of the page.
+    Meta,
+    /// The first paragraphs of the article's text, see [`excerpt`].
+    Excerpt,
+}
+
+/// An excerpt of the first paragraphs of at most `max_len` characters.
+///
+/// Paragraphs of up to [`MAX_BYLINE_LEN`] characters that contain one of the
+/// `bylines`, like the names of the authors, or the `published` date of the
+/// article are skipped. The first paragraph is followed by the next one if it's shorter
+/// than half of `max_len`, in total [`MAX_EXCERPT_PARAGRAPHS`]. Longer texts
+/// are cut at the last sentence, or else word, that fits, and end with an
+/// [`ELLIPSIS`].
+pub fn excerpt<'a, I>(paragraphs: I, bylines: &[&str], published: Option<NaiveDate>, max_len: usize) -> Option<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut text = String::new();
+    for paragraph in paragraphs
+        .into_iter()
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty() && !is_byline(paragraph, bylines, published))
+        .take(MAX_EXCERPT_PARAGRAPHS)
+    {
+        if !text.is_empty() {
+            if text.chars().count() >= max_len / 2 {
+                break;
+            }
+            text.push(' ');
+        }
+        text.push_str(paragraph);
+    }
+    if text.is_empty() {
+        return None;
+    }
+    Some(truncate(&text, max_len))
+}
+
+/// An [`excerpt`] of the paragraphs of the article `node`, that skips the
+/// bylines with the names of the `authors` or the `published` date.
+pub fn node_excerpt<S: AsRef<str>>(
+    node: &ArticleTextNode,
+    authors: &[S],
+    published: Option<&Date>,
+    max_len: usize,
+) -> Option<String> {
+    let bylines: Vec<&str> = authors.iter().map(AsRef::as_ref).collect();
+    let published = published.map(|date| match date {
+        Date::Date(date) => *date,
+        Date::DateTime(date) => date.date(),
+    });
+    let blocks = node.blocks();
+    let paragraphs = blocks.iter().filter_map(|block| match block {
+        ContentBlock::Paragraph(text) => Some(text.as_str()),
+        _ => None,
+    });
+    excerpt(paragraphs, &bylines, published, max_len)
+}
+
+/// Whether the paragraph is short and contains one of the `bylines` or the
+/// `published` date.
+fn is_byline(paragraph: &str, bylines: &[&str], published: Option<NaiveDate>) -> bool {
+    if paragraph.chars().count() > MAX_BYLINE_LEN {
+        return false;
+    }
+    let lowercase = paragraph.to_lowercase();
+    bylines
+        .iter()
+        .map(|byline| byline.trim())
+        .filter(|byline| !byline.is_empty())
+        .any(|byline| lowercase.contains(&byline.to_lowercase()))
+        || published.is_some_and(|published| {
+            DateExtractor::fuzzy_dtparse(paragraph).map(|date| date.date()) == Some(published)
+        })
+}
+
+/// The `text` cut to at most `max_len` characters including the ellipsis.
+fn truncate(text: &str, max_len: usize) -> String {
+    if max_len == 0 {
+        return String::new();
+    }
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    let end = text
+        .char_indices()
+        .nth(max_len.saturating_sub(1))
+        .map(|(index, _)| index)
+        .unwrap_or(text.len());
+    let head = &text[..end];
+    // a sentence is only worth it if it keeps a third of the excerpt
+    let sentence_end = head
+        .match_indices(['.', '!', '?'])
+        .map(|(index, _)| index + 1)
+        .filter(|index| head[*index..].starts_with(char::is_whitespace))
+        .rfind(|index| head[..*index].chars().count() >= max_len / 3);
+    let cut = match sentence_end {
+        Some(index) => &head[..index],
+        None => match head.rfind(char::is_whitespace) {
+            Some(index) => head[..index].trim_end_matches([',', ';', ':', '-', '–', '—']),
+            None => head,
+        },
+    };
+    let mut excerpt = cut.trim_end().to_string();
+    excerpt.push(ELLIPSIS);
+    excerpt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excerpt_of_paragraphs() {
+        let paragraphs = [
+            "By Jane Zhu",
+            "May 14, 2024",
+            "The harbour will be expanded.",
+            "The city council decided on Tuesday, and the first ships are expected in the spring.",
+            "A third paragraph.",
+        ];
+        let published = NaiveDate::from_ymd_opt(2024, 5, 14);
+        assert_eq!(
+            excerpt(paragraphs, &["Jane Zhu"], published, 300).as_deref(),
+            Some(
+                "The harbour will be expanded. The city council decided on Tuesday, and the first ships \
+                 are expected in the spring."
+            )
+        );
+        assert_eq!(
+            excerpt(paragraphs, &["Jane Zhu"], published, 60).as_deref(),
+            Some("The harbour will be expanded.…")
+        );
+        assert_eq!(excerpt(paragraphs, &[], None, 12).as_deref(), Some("By Jane Zhu"));
+        assert_eq!(excerpt(paragraphs, &[], None, 10).as_deref(), Some("By Jane…"));
+        assert_eq!(excerpt([" "], &[], None, 300), None);
+
+        // only the date of the article is a byline, not any day in the text
+        let paragraphs = ["Updated May 14, 2024", "On Tuesday, the council met."];
+        assert_eq!(
+            excerpt(paragraphs, &[], NaiveDate::from_ymd_opt(2024, 5, 14), 300).as_deref(),
+            Some("On Tuesday, the council met.")
+        );
+    }
+
+    #[test]
+    fn truncate_at_words() {
+        let text = "The city council decided on Tuesday, that the harbour will be expanded";
+        let truncated = truncate(text, 40);
+        assert_eq!(truncated, "The city council decided on Tuesday…");
+        assert!(truncated.chars().count() <= 40);
+        assert_eq!(truncate(text, 0), "");
+    }
+}
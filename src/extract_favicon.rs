@@ -1,12 +1,221 @@
 use select::document::Document;
-use select::predicate::{Attr, Name, Predicate};
+use select::node::Node;
+use select::predicate::Name;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use url::Url;
 
+use crate::extract_base_url::resolve_base;
+
+/// The `rel` values of `<link>` elements that declare an icon of the site.
+pub const ICON_RELS: [&str; 5] = [
+    "icon",
+    "shortcut icon",
+    "apple-touch-icon",
+    "apple-touch-icon-precomposed",
+    "mask-icon",
+];
+
+/// An icon of the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Favicon {
+    pub url: Url,
+    /// The largest of the declared `sizes`, as width and height. `None` if
+    /// the sizes are missing or `any`, which marks scalable icons.
+    pub sizes: Option<(u32, u32)>,
+    /// The lowercase `rel` of the link, like `apple-touch-icon`.
+    pub rel: String,
+    /// The declared mime type, like `image/png`.
+    pub mime: Option<String>,
+}
+
+impl Favicon {
+    /// Whether the icon is a PNG, by its mime type or the extension of its
+    /// url.
+    pub fn is_png(&self) -> bool {
+        match &self.mime {
+            Some(mime) => mime.eq_ignore_ascii_case("image/png"),
+            None => self.url.path().to_lowercase().ends_with(".png"),
+        }
+    }
+
+    /// The number of pixels of the icon, `0` if its size is unknown.
+    pub fn area(&self) -> u64 {
+        self.sizes
+            .map(|(width, height)| width as u64 * height as u64)
+            .unwrap_or_default()
+    }
+}
+
+/// The best icon of the site, see [`best_favicon`].
 pub fn favicon(doc: &Document, base_url: &Url) -> Option<Url> {
-    let options = Url::options().base_url(Some(base_url));
+    best_favicon(doc, base_url).map(|favicon| favicon.url)
+}
+
+/// All icons the document declares with one of the [`ICON_RELS`], in
+/// document order.
+///
+/// Relative hrefs are resolved against the `<base>` of the document, or the
+/// `base_url` if it has none. If no icon is declared, the `/favicon.ico` of
+/// the `base_url` is returned.
+pub fn favicons(doc: &Document, base_url: &Url) -> Vec<Favicon> {
+    let base = resolve_base(doc, Some(base_url)).unwrap_or_else(|| base_url.clone());
+    let icons: Vec<Favicon> = doc
+        .find(Name("link"))
+        .filter_map(|link| favicon_link(&link, &base))
+        .collect();
+    if !icons.is_empty() {
+        return icons;
+    }
+    base_url
+        .join("/favicon.ico")
+        .map(|url| Favicon {
+            url,
+            sizes: None,
+            rel: "icon".to_string(),
+            mime: None,
+        })
+        .into_iter()
+        .collect()
+}
+
+/// The icon of the highest quality: the largest PNG, or the largest icon if
+/// there is no PNG.
+///
+/// Monochrome `mask-icon`s are only used if there is no other icon. Of icons
+/// with the same quality the first one is chosen.
+pub fn best_favicon(doc: &Document, base_url: &Url) -> Option<Favicon> {
+    let icons = favicons(doc, base_url);
+    let (masks, icons): (Vec<_>, Vec<_>) = icons.into_iter().partition(|icon| icon.rel == "mask-icon");
+    let best = |icons: Vec<Favicon>| {
+        icons
+            .into_iter()
+            .rev()
+            .max_by_key(|icon| (icon.is_png(), icon.area()))
+    };
+    best(icons).or_else(|| best(masks))
+}
+
+/// The url of the web app manifest, declared by `<link rel="manifest">`.
+///
+/// The icons of the manifest are read with [`manifest_icons`].
+pub fn manifest_url(doc: &Document, base_url: &Url) -> Option<Url> {
+    let base = resolve_base(doc, Some(base_url)).unwrap_or_else(|| base_url.clone());
+    doc.find(Name("link"))
+        .filter(|link| has_rel(link, "manifest"))
+        .filter_map(|link| link.attr("href"))
+        .find_map(|href| base.join(href.trim()).ok())
+}
+
+/// The icons of the web app manifest `json` served at `manifest_url`, with
+/// the `rel` `manifest`.
+///
+/// Returns no icons if the json is invalid.
+pub fn manifest_icons(json: &str, manifest_url: &Url) -> Vec<Favicon> {
+    let manifest: Value = match serde_json::from_str(json) {
+        Ok(manifest) => manifest,
+        Err(_) => return Vec::new(),
+    };
+    manifest
+        .get("icons")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|icon| {
+            let url = manifest_url.join(icon.get("src")?.as_str()?.trim()).ok()?;
+            Some(Favicon {
+                url,
+                sizes: icon.get("sizes").and_then(Value::as_str).and_then(parse_sizes),
+                rel: "manifest".to_string(),
+                mime: icon.get("type").and_then(Value::as_str).map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+fn favicon_link(link: &Node, base: &Url) -> Option<Favicon> {
+    let rel = link.attr("rel")?.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if !ICON_RELS.contains(&rel.as_str()) {
+        return None;
+    }
+    let url = base.join(link.attr("href")?.trim()).ok()?;
+    Some(Favicon {
+        url,
+        sizes: link.attr("sizes").and_then(parse_sizes),
+        rel,
+        mime: link.attr("type").map(|mime| mime.trim().to_lowercase()),
+    })
+}
+
+fn has_rel(link: &Node, rel: &str) -> bool {
+    link.attr("rel")
+        .map(|rels| rels.split_whitespace().any(|r| r.eq_ignore_ascii_case(rel)))
+        .unwrap_or_default()
+}
+
+/// The largest size of a `sizes` attribute like `16x16 32x32`, `None` for
+/// `any`.
+pub fn parse_sizes(sizes: &str) -> Option<(u32, u32)> {
+    sizes
+        .split_whitespace()
+        .filter_map(|size| {
+            let size = size.to_lowercase();
+            let (width, height) = size.split_once('x')?;
+            Some((width.parse().ok()?, height.parse().ok()?))
+        })
+        .max_by_key(|(width, height): &(u32, u32)| *width as u64 * *height as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn favicon_candidates() {
+        let doc = Document::from(
+            r##"<html><head><base href="/static/">
+            <link rel="shortcut icon" href="favicon.ico">
+            <link rel="icon" type="image/png" sizes="16x16 32x32" href="icon-32.png">
+            <link rel="apple-touch-icon" sizes="180x180" href="/apple-touch-icon.png">
+            <link rel="icon" type="image/svg+xml" sizes="any" href="icon.svg">
+            <link rel="mask-icon" href="mask.svg" color="#000">
+            <link rel="manifest" href="/site.webmanifest">
+            </head><body></body></html>"##,
+        );
+        let base_url = Url::parse("https://example.com/news/harbour.html").unwrap();
+        let icons = favicons(&doc, &base_url);
+        assert_eq!(
+            icons.iter().map(|icon| icon.url.as_str()).collect::<Vec<_>>(),
+            vec![
+                "https://example.com/static/favicon.ico",
+                "https://example.com/static/icon-32.png",
+                "https://example.com/apple-touch-icon.png",
+                "https://example.com/static/icon.svg",
+                "https://example.com/static/mask.svg",
+            ]
+        );
+        assert_eq!(icons[0].rel, "shortcut icon");
+        assert_eq!(icons[1].sizes, Some((32, 32)));
+        assert_eq!(icons[3].sizes, None);
+        assert_eq!(
+            favicon(&doc, &base_url).as_ref().map(Url::as_str),
+            Some("https://example.com/apple-touch-icon.png")
+        );
+
+        let manifest = manifest_url(&doc, &base_url).unwrap();
+        let icons = manifest_icons(
+            r#"{"icons": [{"src": "/android-512.png", "sizes": "512x512", "type": "image/png"}]}"#,
+            &manifest,
+        );
+        assert_eq!(icons[0].url.as_str(), "https://example.com/android-512.png");
+        assert_eq!(icons[0].sizes, Some((512, 512)));
 
-    doc.find(Name("link").and(Attr("rel", "icon")))
-        .filter_map(|node| node.attr("href"))
-        .filter_map(|href| options.parse(href).ok())
-        .next()
+        let doc = Document::from("<html><head></head><body></body></html>");
+        assert_eq!(
+            favicon(&doc, &base_url).as_ref().map(Url::as_str),
+            Some("https://example.com/favicon.ico")
+        );
+    }
 }
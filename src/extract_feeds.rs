@@ -0,0 +1,433 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use url::Url;
+use select::document::Document;
+use select::node::Node;
+use select::predicate::Name;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::article::{ArticleUrl, KnownFields};
+use crate::date::DateExtractor;
+use crate::extract_base_url::resolve_base;
+
+/// The format of a feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum FeedKind {
+    Rss,
+    Atom,
+    /// A [JSON Feed](https://www.jsonfeed.org/).
+    Json,
+}
+
+impl FeedKind {
+    /// The kind of a feed advertised with the mime `type`.
+    ///
+    /// Plain `application/json` isn't a feed type, CMS like WordPress
+    /// advertise their REST API with it.
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        let mime = mime.split(';').next().unwrap_or_default().trim().to_lowercase();
+        match mime.as_str() {
+            "application/rss+xml" | "application/rdf+xml" => Some(FeedKind::Rss),
+            "application/atom+xml" => Some(FeedKind::Atom),
+            "application/feed+json" => Some(FeedKind::Json),
+            _ => None,
+        }
+    }
+}
+
+/// A feed advertised by a page.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct FeedLink {
+    /// The address of the feed.
+    pub url: Url,
+    /// The title of the feed.
+    pub title: Option<String>,
+    /// The format of the feed.
+    pub kind: FeedKind,
+}
+
+/// All feeds advertised by `<link rel="alternate" type="...">` elements.
+///
+/// Relative hrefs are resolved against the `<base>` of the document or the
+/// `base_url`, see [`resolve_base`], duplicates are removed.
+pub fn feed_urls(doc: &Document, base_url: Option<&Url>) -> Vec<FeedLink> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    let mut uniques = HashSet::new();
+    doc.find(Name("link"))
+        .filter(|link| {
+            link.attr("rel")
+                .map(|rel| {
+                    rel.split_whitespace()
+                        .any(|rel| rel.eq_ignore_ascii_case("alternate") || rel.eq_ignore_ascii_case("feed"))
+                })
+                .unwrap_or_default()
+        })
+        .filter_map(|link| {
+            let kind = FeedKind::from_mime(link.attr("type")?)?;
+            let url = options.parse(link.attr("href")?.trim()).ok()?;
+            let title = link
+                .attr("title")
+                .map(str::trim)
+                .filter(|title| !title.is_empty())
+                .map(str::to_string);
+            Some(FeedLink { url, title, kind })
+        })
+        .filter(|feed| uniques.insert(feed.url.clone()))
+        .collect()
+}
+
+/// An item of a feed.
+#[derive(Debug, Clone)]
+pub struct FeedItem {
+    /// The url and title of the item's article.
+    pub url: ArticleUrl,
+    /// The full article html, if the feed carries it, e.g. in
+    /// `content:encoded`.
+    pub content: Option<String>,
+    /// The fields of the article the feed already provides.
+    pub known: KnownFields,
+}
+
+/// The urls of the items of a feed of the `kind`.
+///
+/// Relative urls are resolved against the `base_url`, duplicates are
+/// removed.
+pub fn feed_article_urls(body: &str, kind: FeedKind, base_url: Option<&Url>) -> Vec<ArticleUrl> {
+    feed_items(body, kind, base_url)
+        .into_iter()
+        .map(|item| item.url)
+        .collect()
+}
+
+/// The items of a feed of the `kind`.
+///
+/// Relative urls are resolved against the `base_url`, items with duplicate
+/// urls are removed.
+pub fn feed_items(body: &str, kind: FeedKind, base_url: Option<&Url>) -> Vec<FeedItem> {
+    let options = Url::options().base_url(base_url);
+    let items: Vec<(String, Option<String>, KnownFields)> = match kind {
+        FeedKind::Json => json_feed_items(body),
+        FeedKind::Rss => {
+            let doc = Document::from(unwrap_cdata(body).as_ref());
+            doc.find(Name("item"))
+                .filter_map(|item| {
+                    let known = KnownFields {
+                        title: child_text(&item, "title"),
+                        authors: child_texts(&item, &["dc:creator", "author"]),
+                        publishing_date: child_text(&item, "pubdate")
+                            .or_else(|| child_text(&item, "dc:date"))
+                            .and_then(|date| DateExtractor::extract_from_str(&date)),
+                        description: child_html(&item, "description").map(|html| html_text(&html)),
+                        ..Default::default()
+                    };
+                    Some((rss_item_link(&item)?, child_html(&item, "content:encoded"), known))
+                })
+                .collect()
+        }
+        FeedKind::Atom => {
+            let doc = Document::from(unwrap_cdata(body).as_ref());
+            doc.find(Name("entry"))
+                .filter_map(|entry| {
+                    let known = KnownFields {
+                        title: child_text(&entry, "title"),
+                        authors: child_texts(&entry, &["name"]),
+                        publishing_date: child_text(&entry, "published")
+                            .or_else(|| child_text(&entry, "updated"))
+                            .and_then(|date| DateExtractor::extract_from_str(&date)),
+                        description: child_html(&entry, "summary").map(|html| html_text(&html)),
+                        ..Default::default()
+                    };
+                    Some((atom_entry_link(&entry)?, child_html(&entry, "content"), known))
+                })
+                .collect()
+        }
+    };
+    let mut uniques = HashSet::new();
+    items
+        .into_iter()
+        .filter_map(|(link, content, known)| {
+            let url = options.parse(link.trim()).ok()?;
+            Some(FeedItem {
+                url: ArticleUrl::new_with_title(url, known.title.clone()),
+                content,
+                known,
+            })
+        })
+        .filter(|item| uniques.insert(item.url.url.clone()))
+        .collect()
+}
+
+/// The link of a RSS item.
+///
+/// The html parser treats `<link>` as void element, so its url ends up in
+/// the text right after it. Falls back to a permalink `<guid>`.
+fn rss_item_link(item: &Node) -> Option<String> {
+    let from_link = item.find(Name("link")).find_map(|link| {
+        let text = link.text();
+        if !text.trim().is_empty() {
+            return Some(text.trim().to_string());
+        }
+        link.next()
+            .and_then(|next| next.as_text().map(str::trim).map(str::to_string))
+            .filter(|text| !text.is_empty())
+    });
+    from_link.or_else(|| {
+        item.find(Name("guid"))
+            .find(|guid| guid.attr("ispermalink") != Some("false"))
+            .map(|guid| guid.text().trim().to_string())
+            .filter(|text| text.starts_with("http"))
+    })
+}
+
+/// The alternate link of an Atom entry.
+fn atom_entry_link(entry: &Node) -> Option<String> {
+    entry
+        .find(Name("link"))
+        .find(|link| matches!(link.attr("rel"), None | Some("alternate")))
+        .and_then(|link| link.attr("href"))
+        .map(str::to_string)
+}
+
+/// The trimmed text of the first child element `name`.
+fn child_text(node: &Node, name: &str) -> Option<String> {
+    node.find(Name(name))
+        .next()
+        .map(|child| child.text().trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+/// The trimmed texts of all child elements with one of the `names`.
+fn child_texts(node: &Node, names: &[&str]) -> Vec<String> {
+    node.descendants()
+        .filter(|child| child.name().is_some_and(|name| names.contains(&name)))
+        .map(|child| child.text().trim().to_string())
+        .filter(|text| !text.is_empty())
+        .collect()
+}
+
+/// Replaces the `CDATA` sections of a feed with their escaped content.
+///
+/// The html parser doesn't know `CDATA` outside of svg and math and would
+/// end the section at the first `>` of its content.
+fn unwrap_cdata(body: &str) -> Cow<'_, str> {
+    if !body.contains("<![CDATA[") {
+        return Cow::Borrowed(body);
+    }
+    let mut unwrapped = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find("<![CDATA[") {
+        unwrapped.push_str(&rest[..start]);
+        rest = &rest[start + "<![CDATA[".len()..];
+        let end = rest.find("]]>").unwrap_or(rest.len());
+        unwrapped.push_str(
+            &rest[..end]
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;"),
+        );
+        rest = rest.get(end + "]]>".len()..).unwrap_or_default();
+    }
+    unwrapped.push_str(rest);
+    Cow::Owned(unwrapped)
+}
+
+/// The html of the first child element `name`.
+///
+/// Feeds escape the html, wrap it in a `CDATA` section, see
+/// [`unwrap_cdata`], or embed it as xhtml.
+fn child_html(node: &Node, name: &str) -> Option<String> {
+    let child = node.find(Name(name)).next()?;
+    let html: String = child
+        .children()
+        .map(|child| match child.as_text() {
+            Some(text) => text.to_string(),
+            None => child.html(),
+        })
+        .collect();
+    let html = html.trim();
+    if html.is_empty() {
+        None
+    } else {
+        Some(html.to_string())
+    }
+}
+
+/// The text of a html snippet.
+fn html_text(html: &str) -> String {
+    Document::from(html)
+        .find(Name("body"))
+        .next()
+        .map(|body| body.text().trim().to_string())
+        .unwrap_or_default()
+}
+
+/// The url, content and known fields of the items of a JSON Feed.
+fn json_feed_items(body: &str) -> Vec<(String, Option<String>, KnownFields)> {
+    let feed: Value = match serde_json::from_str(body) {
+        Ok(feed) => feed,
+        Err(_) => return Vec::new(),
+    };
+    feed["items"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let url = item["url"].as_str().or_else(|| item["external_url"].as_str())?;
+                    let text = |key: &str| {
+                        item[key]
+                            .as_str()
+                            .map(str::trim)
+                            .filter(|text| !text.is_empty())
+                            .map(str::to_string)
+                    };
+                    let authors = item["authors"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .chain(Some(&item["author"]))
+                        .filter_map(|author| author["name"].as_str())
+                        .map(str::to_string)
+                        .collect();
+                    let known = KnownFields {
+                        title: text("title"),
+                        authors,
+                        publishing_date: text("date_published")
+                            .and_then(|date| DateExtractor::extract_from_str(&date)),
+                        description: text("summary"),
+                        ..Default::default()
+                    };
+                    Some((url.to_string(), text("content_html"), known))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn advertised_feeds() {
+        let doc = Document::from(
+            r#"<html><head>
+            <link rel="alternate" type="application/rss+xml" title="News" href="/feed.xml">
+            <link rel="alternate" type="application/atom+xml" href="https://example.com/atom">
+            <link rel="alternate" type="application/feed+json" title=" " href="feed.json">
+            <link rel="alternate" type="application/json" href="/wp-json/wp/v2/posts/1">
+            <link rel="alternate" hreflang="de" href="/de/">
+            <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+            </head><body></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/news/").unwrap();
+        let feeds = feed_urls(&doc, Some(&base));
+        assert_eq!(
+            feeds,
+            vec![
+                FeedLink {
+                    url: Url::parse("https://example.com/feed.xml").unwrap(),
+                    title: Some("News".to_string()),
+                    kind: FeedKind::Rss,
+                },
+                FeedLink {
+                    url: Url::parse("https://example.com/atom").unwrap(),
+                    title: None,
+                    kind: FeedKind::Atom,
+                },
+                FeedLink {
+                    url: Url::parse("https://example.com/news/feed.json").unwrap(),
+                    title: None,
+                    kind: FeedKind::Json,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn feed_item_urls() {
+        let base = Url::parse("https://example.com/").unwrap();
+        let urls = |articles: Vec<ArticleUrl>| {
+            articles
+                .into_iter()
+                .map(|article| article.url.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let rss = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>News</title>
+            <link>https://example.com/</link>
+            <item><title>Harbour</title><link>https://example.com/news/harbour.html</link></item>
+            <item><title>Council</title><guid isPermaLink="true">https://example.com/news/council.html</guid></item>
+            </channel></rss>"#;
+        let articles = feed_article_urls(rss, FeedKind::Rss, Some(&base));
+        assert_eq!(articles[0].title.as_deref(), Some("Harbour"));
+        assert_eq!(
+            urls(articles),
+            vec!["https://example.com/news/harbour.html", "https://example.com/news/council.html"]
+        );
+
+        let atom = r#"<?xml version="1.0"?><feed xmlns="http://www.w3.org/2005/Atom"><title>News</title>
+            <link href="https://example.com/"/>
+            <entry><title>Harbour</title><link rel="alternate" href="/news/harbour.html"/></entry>
+            </feed>"#;
+        assert_eq!(
+            urls(feed_article_urls(atom, FeedKind::Atom, Some(&base))),
+            vec!["https://example.com/news/harbour.html"]
+        );
+
+        let json = r#"{"version": "https://jsonfeed.org/version/1.1", "items": [
+            {"id": "1", "url": "https://example.com/news/harbour.html", "title": "Harbour"},
+            {"id": "2", "external_url": "https://example.org/council"}]}"#;
+        assert_eq!(
+            urls(feed_article_urls(json, FeedKind::Json, Some(&base))),
+            vec!["https://example.com/news/harbour.html", "https://example.org/council"]
+        );
+    }
+
+    #[test]
+    fn feed_item_content() {
+        let base = Url::parse("https://example.com/").unwrap();
+        let rss = r#"<?xml version="1.0"?><rss version="2.0"><channel><title>News</title>
+            <item><title><![CDATA[Harbour & Council]]></title><link>/news/harbour.html</link>
+            <dc:creator>Jane Doe</dc:creator>
+            <pubDate>Tue, 10 Jun 2003 04:00:00 GMT</pubDate>
+            <description>&lt;p&gt;The harbour grows.&lt;/p&gt;</description>
+            <content:encoded><![CDATA[<p>The harbour will be expanded.</p><p>By the council.</p>]]></content:encoded>
+            </item>
+            <item><title>Council</title><link>/news/council.html</link></item>
+            </channel></rss>"#;
+        let items = feed_items(rss, FeedKind::Rss, Some(&base));
+        assert_eq!(
+            items[0].content.as_deref(),
+            Some("<p>The harbour will be expanded.</p><p>By the council.</p>")
+        );
+        assert_eq!(items[0].known.title.as_deref(), Some("Harbour & Council"));
+        assert_eq!(items[0].known.authors, vec!["Jane Doe"]);
+        assert_eq!(items[0].known.description.as_deref(), Some("The harbour grows."));
+        assert!(items[0].known.publishing_date.is_some());
+        assert!(items[1].content.is_none());
+
+        let atom = r#"<?xml version="1.0"?><feed xmlns="http://www.w3.org/2005/Atom">
+            <entry><title>Harbour</title><link href="/news/harbour.html"/>
+            <author><name>Jane Doe</name></author>
+            <content type="html">&lt;p&gt;The harbour will be expanded.&lt;/p&gt;</content></entry>
+            </feed>"#;
+        let items = feed_items(atom, FeedKind::Atom, Some(&base));
+        assert_eq!(items[0].content.as_deref(), Some("<p>The harbour will be expanded.</p>"));
+        assert_eq!(items[0].known.authors, vec!["Jane Doe"]);
+
+        let json = r#"{"version": "https://jsonfeed.org/version/1.1", "items": [
+            {"id": "1", "url": "https://example.com/news/harbour.html",
+             "authors": [{"name": "Jane Doe"}],
+             "content_html": "<p>The harbour will be expanded.</p>"}]}"#;
+        let items = feed_items(json, FeedKind::Json, Some(&base));
+        assert_eq!(items[0].content.as_deref(), Some("<p>The harbour will be expanded.</p>"));
+        assert_eq!(items[0].known.authors, vec!["Jane Doe"]);
+    }
+}
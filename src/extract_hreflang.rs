@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use url::Url;
+use select::document::Document;
+use select::node::Node;
+use select::predicate::Name;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::extract_base_url::resolve_base;
+use crate::Language;
+
+/// The `hreflang` value that marks the page for all other languages.
+pub const X_DEFAULT: &str = "x-default";
+
+/// A BCP 47 language tag like `de`, `pt-BR` or `zh-Hant-TW`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// The tag if the trimmed `tag` is well formed: subtags of 1 to 8 ascii
+    /// letters or digits, separated by `-`, starting with a letter.
+    ///
+    /// `_` separators are accepted, the original string is preserved.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let tag = tag.trim();
+        let mut subtags = tag.split(['-', '_']);
+        let primary = subtags.next()?;
+        if primary.is_empty() || primary.len() > 8 || !primary.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        if !subtags.all(|subtag| !subtag.is_empty() && subtag.len() <= 8 && subtag.chars().all(|c| c.is_ascii_alphanumeric())) {
+            return None;
+        }
+        Some(LanguageTag(tag.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The lowercase primary language subtag, like `pt` of `pt-BR`.
+    pub fn primary(&self) -> String {
+        self.0
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+    }
+
+    /// The uppercase region subtag, like `BR` of `pt-BR` or `419` of
+    /// `es-419`.
+    pub fn region(&self) -> Option<String> {
+        self.0
+            .split(['-', '_'])
+            .skip(1)
+            .find(|subtag| {
+                (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+                    || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()))
+            })
+            .map(str::to_ascii_uppercase)
+    }
+
+    /// The [`Language`] of the primary subtag, if it is a known one.
+    pub fn language(&self) -> Option<Language> {
+        self.primary().parse().ok()
+    }
+
+    /// Whether this is the `x-default` tag.
+    pub fn is_x_default(&self) -> bool {
+        self.0.eq_ignore_ascii_case(X_DEFAULT)
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// All language editions of the page advertised by
+/// `<link rel="alternate" hreflang="..." href="...">` elements, in document
+/// order.
+///
+/// Relative hrefs are resolved against the `<base>` of the document or the
+/// `base_url`, see [`resolve_base`]. The `x-default` entry is skipped, see
+/// [`x_default_url`], as are malformed tags. Of duplicated tags only the
+/// first is kept.
+pub fn alternate_languages(doc: &Document, base_url: Option<&Url>) -> Vec<(LanguageTag, Url)> {
+    let mut uniques = HashSet::new();
+    hreflang_links(doc, base_url)
+        .filter(|(tag, _)| !tag.is_x_default())
+        .filter(|(tag, _)| uniques.insert(tag.as_str().replace('_', "-").to_ascii_lowercase()))
+        .collect()
+}
+
+/// The page for all languages without an edition of their own, advertised
+/// by `<link rel="alternate" hreflang="x-default">`.
+pub fn x_default_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+    hreflang_links(doc, base_url)
+        .find(|(tag, _)| tag.is_x_default())
+        .map(|(_, url)| url)
+}
+
+fn hreflang_links<'a>(
+    doc: &'a Document,
+    base_url: Option<&Url>,
+) -> impl Iterator<Item = (LanguageTag, Url)> + 'a {
+    let base = resolve_base(doc, base_url);
+    doc.find(Name("link"))
+        .filter(|link: &Node| {
+            link.attr("rel")
+                .map(|rel| rel.split_whitespace().any(|rel| rel.eq_ignore_ascii_case("alternate")))
+                .unwrap_or_default()
+        })
+        .filter_map(move |link| {
+            let tag = LanguageTag::parse(link.attr("hreflang")?)?;
+            let url = Url::options().base_url(base.as_ref()).parse(link.attr("href")?.trim()).ok()?;
+            Some((tag, url))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hreflang_alternates() {
+        let doc = Document::from(
+            r#"<html><head>
+            <link rel="alternate" hreflang="de" href="/de/hafen">
+            <link rel="alternate" hreflang="pt-BR" href="https://example.com/pt-br/porto">
+            <link rel="alternate" hreflang="x-default" href="/hafen">
+            <link rel="alternate" hreflang="DE" href="/de/other">
+            <link rel="alternate" hreflang="zh_Hant_TW" href="/zh-tw/harbour">
+            <link rel="alternate" hreflang="not a tag" href="/invalid">
+            <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+            </head><body></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/en/harbour").unwrap();
+        let alternates = alternate_languages(&doc, Some(&base));
+        let tags: Vec<_> = alternates.iter().map(|(tag, url)| (tag.as_str(), url.as_str())).collect();
+        assert_eq!(
+            tags,
+            vec![
+                ("de", "https://example.com/de/hafen"),
+                ("pt-BR", "https://example.com/pt-br/porto"),
+                ("zh_Hant_TW", "https://example.com/zh-tw/harbour"),
+            ]
+        );
+        assert_eq!(alternates[0].0.language(), Some(Language::German));
+        assert_eq!(alternates[1].0.region().as_deref(), Some("BR"));
+        assert_eq!(alternates[2].0.primary(), "zh");
+        assert_eq!(alternates[2].0.region().as_deref(), Some("TW"));
+        assert_eq!(
+            x_default_url(&doc, Some(&base)).as_ref().map(Url::as_str),
+            Some("https://example.com/hafen")
+        );
+    }
+}
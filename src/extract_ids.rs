@@ -0,0 +1,213 @@
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Attr, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::date::DateExtractor;
+use crate::extract_canonical::canonical_link;
+use crate::extract_meta_data::meta_data;
+
+/// Meta keys of the ids publishers and analytics tools assign to articles,
+/// compared case-insensitively.
+pub const ARTICLE_ID_META_KEYS: [&str; 7] = [
+    "parsely-post-id",
+    "article:id",
+    "articleid",
+    "article_id",
+    "content_id",
+    "cxenseparse:articleid",
+    "sailthru.contentid",
+];
+
+/// Meta keys of the DOI of an article, compared case-insensitively.
+pub const DOI_META_KEYS: [&str; 4] = ["citation_doi", "prism.doi", "dc.identifier", "bepress_citation_doi"];
+
+/// Query parameters with the id of an article, compared
+/// case-insensitively.
+pub const ID_QUERY_PARAMS: [&str; 6] = ["id", "article_id", "articleid", "story_id", "storyid", "contentid"];
+
+/// The attribute of an article element with the publisher's id.
+pub const ARTICLE_ID_ATTR: &str = "data-article-id";
+
+/// The min number of digits of an id in the path of a url, which excludes
+/// years and days of dated paths like `/2020/03/04/`.
+pub const MIN_URL_ID_DIGITS: usize = 5;
+
+/// Stable identifiers of an article, to recognize it across sources.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ArticleIds {
+    /// The id in the url of the article, see [`url_id`].
+    pub url_id: Option<String>,
+    /// The ids of the meta tags with one of the [`ARTICLE_ID_META_KEYS`]
+    /// and of the [`ARTICLE_ID_ATTR`], with the lowercase key they were
+    /// found under.
+    pub meta_ids: Vec<(String, String)>,
+    /// The DOI, like `10.1000/xyz123`, without a `doi:` or resolver
+    /// prefix.
+    pub doi: Option<String>,
+}
+
+impl ArticleIds {
+    /// Whether no id of any kind was found.
+    pub fn is_empty(&self) -> bool {
+        self.url_id.is_none() && self.meta_ids.is_empty() && self.doi.is_none()
+    }
+
+    /// The first id found under the `key`, like `parsely-post-id`.
+    pub fn meta_id(&self, key: &str) -> Option<&str> {
+        self.meta_ids
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, id)| id.as_str())
+    }
+}
+
+/// The identifiers of the article at the `url`.
+///
+/// The url id is taken from the `url`, or from the canonical url of the
+/// document if the `url` has none.
+pub fn article_ids(doc: &Document, url: Option<&Url>) -> ArticleIds {
+    let url_id = url
+        .and_then(url_id)
+        .or_else(|| canonical_link(doc, url).as_ref().and_then(url_id));
+
+    let mut meta_ids: Vec<(String, String)> = Vec::new();
+    let mut doi = None;
+    for meta in meta_data(doc) {
+        let (key, value) = match (meta.key(), meta.value()) {
+            (Some(key), Some(value)) => (key.trim().to_lowercase(), value.trim()),
+            _ => continue,
+        };
+        if value.is_empty() {
+            continue;
+        }
+        if ARTICLE_ID_META_KEYS.contains(&key.as_str()) {
+            if !meta_ids.iter().any(|(k, v)| *k == key && v == value) {
+                meta_ids.push((key, value.to_string()));
+            }
+        } else if doi.is_none() && DOI_META_KEYS.contains(&key.as_str()) {
+            doi = normalize_doi(value);
+        }
+    }
+    if let Some(id) = article_element(doc).and_then(|node| node.attr(ARTICLE_ID_ATTR)) {
+        let id = id.trim();
+        if !id.is_empty() {
+            meta_ids.push((ARTICLE_ID_ATTR.to_string(), id.to_string()));
+        }
+    }
+
+    ArticleIds { url_id, meta_ids, doi }
+}
+
+/// The element of the article with an [`ARTICLE_ID_ATTR`]: the `<html>`,
+/// `<body>` or `<main>`, else the `<article>` with the longest text, since
+/// the teasers of related articles often carry an id as well.
+fn article_element(doc: &Document) -> Option<Node<'_>> {
+    doc.find(Name("html").or(Name("body")).or(Name("main")).and(Attr(ARTICLE_ID_ATTR, ())))
+        .next()
+        .or_else(|| {
+            doc.find(Name("article").and(Attr(ARTICLE_ID_ATTR, ())))
+                .max_by_key(|node| node.text().chars().filter(|c| !c.is_whitespace()).count())
+        })
+}
+
+/// The id of an article in its `url`: the value of one of the
+/// [`ID_QUERY_PARAMS`], else the last path segment that is, or ends with,
+/// a number of at least [`MIN_URL_ID_DIGITS`] digits, like `76123751` in
+/// `/news/76123751/` or `/news/harbour-expansion-76123751.html`.
+///
+/// The slug segment is tried first, so its id wins over the one of a
+/// segment before it. Numbers that are compact dates, like `20240305` in
+/// `/news/20240305/harbour`, aren't ids, since articles of the same day
+/// share them.
+pub fn url_id(url: &Url) -> Option<String> {
+    let query_id = url.query_pairs().find_map(|(key, value)| {
+        let value = value.trim();
+        let valid = !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        (ID_QUERY_PARAMS.iter().any(|param| key.eq_ignore_ascii_case(param)) && valid).then(|| value.to_string())
+    });
+    if query_id.is_some() {
+        return query_id;
+    }
+    url.path_segments()?.rev().find_map(|segment| {
+        let stem = match segment.rsplit_once('.') {
+            Some((stem, extension)) if extension.chars().all(|c| c.is_ascii_alphabetic()) => stem,
+            _ => segment,
+        };
+        let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        let number = &stem[stem.len() - digits..];
+        let separated = digits == stem.len() || stem[..stem.len() - digits].ends_with(['-', '_', '.']);
+        let is_date = digits == 8 && DateExtractor::extract_from_url_path(&format!("/{}/", number)).is_some();
+        (digits >= MIN_URL_ID_DIGITS && separated && !is_date).then(|| number.to_string())
+    })
+}
+
+/// The DOI of a `value` like `10.1000/xyz123`, `doi:10.1000/xyz123` or
+/// `https://doi.org/10.1000/xyz123`.
+pub fn normalize_doi(value: &str) -> Option<String> {
+    let value = value.trim();
+    let lower = value.to_ascii_lowercase();
+    let start = ["https://doi.org/", "http://doi.org/", "https://dx.doi.org/", "http://dx.doi.org/", "doi:"]
+        .iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map(|prefix| prefix.len())
+        .unwrap_or_default();
+    let doi = value[start..].trim();
+    match doi.split_once('/') {
+        Some((prefix, suffix)) if prefix.starts_with("10.") && !suffix.is_empty() => Some(doi.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_of_urls() {
+        let id = |url: &str| url_id(&Url::parse(url).unwrap());
+        assert_eq!(id("https://example.com/news/76123751/"), Some("76123751".to_string()));
+        assert_eq!(id("https://example.com/news/76123751/harbour-expansion"), Some("76123751".to_string()));
+        assert_eq!(id("https://example.com/politik/harbour-expansion-1.347823"), Some("347823".to_string()));
+        assert_eq!(id("https://example.com/auto/harbour_expansion_1300105.html"), Some("1300105".to_string()));
+        assert_eq!(id("https://example.com/article.php?ID=4711&ref=home"), Some("4711".to_string()));
+        assert_eq!(id("https://example.com/2020/03/04/harbour-expansion/"), None);
+        assert_eq!(id("https://example.com/news/covid19-harbour"), None);
+        assert_eq!(id("https://example.com/news/76123751/harbour-expansion-347823"), Some("347823".to_string()));
+
+        // compact dates aren't ids, same day articles would share them
+        assert_eq!(id("https://example.com/news/20240305/harbour"), None);
+        assert_eq!(id("https://example.com/news/20240305/storm"), None);
+        assert_eq!(id("https://example.com/news/harbour-20240305.html"), None);
+        assert_eq!(id("https://example.com/news/20240305/harbour-76123751"), Some("76123751".to_string()));
+    }
+
+    #[test]
+    fn ids_of_document() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta name="parsely-post-id" content="76123751">
+            <meta property="article:id" content="a-76123751">
+            <meta name="citation_doi" content="doi:10.1000/harbour.2020.12">
+            <link rel="canonical" href="https://example.com/news/harbour-expansion-76123751.html">
+            </head><body>
+            <article data-article-id="76123751"><h1>Harbour expansion approved</h1>
+            <p>The city council approved the expansion of the harbour on Tuesday.</p></article>
+            <aside><article data-article-id="1234"><a href="/news/1234">More news</a></article></aside>
+            </body></html>"#,
+        );
+        let ids = article_ids(&doc, Some(&Url::parse("https://example.com/news/harbour").unwrap()));
+        assert_eq!(ids.url_id.as_deref(), Some("76123751"));
+        assert_eq!(ids.meta_id("Parsely-Post-Id"), Some("76123751"));
+        assert_eq!(ids.meta_id("article:id"), Some("a-76123751"));
+        assert_eq!(ids.meta_id(ARTICLE_ID_ATTR), Some("76123751"));
+        assert_eq!(ids.doi.as_deref(), Some("10.1000/harbour.2020.12"));
+
+        assert_eq!(normalize_doi("https://doi.org/10.1000/xyz123"), Some("10.1000/xyz123".to_string()));
+        assert_eq!(normalize_doi("urn:isbn:0451450523"), None);
+        assert!(article_ids(&Document::from("<html></html>"), None).is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+use std::iter::successors;
+
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Attr, Name, Predicate};
+use serde_json::Value;
+
+/// All JSON-LD objects of the document.
+///
+/// Top level arrays and `@graph` lists are flattened, scripts that aren't
+/// valid json are skipped.
+pub fn json_ld(doc: &Document) -> Vec<Value> {
+    parse_scripts(doc.find(json_ld_script()))
+}
+
+/// All JSON-LD objects of the document of the `node`, like [`json_ld`].
+///
+/// The scripts are searched from the root node of the document, so they are
+/// found wherever the `node` is.
+pub fn json_ld_of(node: &Node) -> Vec<Value> {
+    let root = successors(Some(*node), Node::parent).last().unwrap_or(*node);
+    parse_scripts(root.find(json_ld_script()))
+}
+
+fn json_ld_script() -> impl Predicate {
+    Name("script").and(Attr("type", "application/ld+json"))
+}
+
+fn parse_scripts<'a>(scripts: impl Iterator<Item = Node<'a>>) -> Vec<Value> {
+    let mut objects = Vec::new();
+    for script in scripts {
+        if let Ok(value) = serde_json::from_str::<Value>(script.text().trim()) {
+            flatten(value, &mut objects);
+        }
+    }
+    objects
+}
+
+fn flatten(value: Value, objects: &mut Vec<Value>) {
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                flatten(value, objects);
+            }
+        }
+        Value::Object(mut object) => {
+            if let Some(graph) = object.remove("@graph") {
+                flatten(graph, objects);
+            }
+            if object.keys().any(|key| key != "@context") {
+                objects.push(Value::Object(object));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Whether the `@type` of the JSON-LD object, a string or a list of
+/// strings, is one of the `types`.
+pub fn has_type(object: &Value, types: &[&str]) -> bool {
+    match object.get("@type") {
+        Some(Value::String(ty)) => types.contains(&ty.as_str()),
+        Some(Value::Array(tys)) => tys
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|ty| types.contains(&ty)),
+        _ => false,
+    }
+}
+
+/// The value of the property as list, so single values and arrays can be
+/// treated the same.
+pub fn values<'a>(object: &'a Value, property: &str) -> Vec<&'a Value> {
+    match object.get(property) {
+        Some(Value::Array(values)) => values.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(value) => vec![value],
+    }
+}
+
+/// The trimmed string of the property, or the `name` of the property if it
+/// is an object, like `"publisher": {"@type": "Organization", "name": ".."}`.
+pub fn text<'a>(object: &'a Value, property: &str) -> Option<&'a str> {
+    let value = object.get(property)?;
+    value
+        .as_str()
+        .or_else(|| value.get("name")?.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_and_arrays() {
+        let doc = Document::from(
+            r#"<html><head>
+            <script type="application/ld+json">{"@context": "https://schema.org", "@graph": [
+                {"@type": "WebSite", "name": "Example"},
+                {"@type": ["NewsArticle", "Article"], "headline": "Harbour", "publisher": {"name": " Example News "}}
+            ]}</script>
+            <script type="application/ld+json">[{"@type": "BreadcrumbList"}]</script>
+            <script type="application/ld+json">{ not json</script>
+            </head></html>"#,
+        );
+        let objects = json_ld(&doc);
+        assert_eq!(objects.len(), 3);
+        let article = objects
+            .iter()
+            .find(|o| has_type(o, &["NewsArticle"]))
+            .unwrap();
+        assert_eq!(text(article, "publisher"), Some("Example News"));
+        assert_eq!(values(article, "headline").len(), 1);
+        assert!(values(article, "author").is_empty());
+    }
+
+    #[test]
+    fn json_ld_of_a_node() {
+        let doc = Document::from(
+            r#"<html><head>
+            <script type="application/ld+json">{"@type": "PodcastEpisode", "name": "Episode 12"}</script>
+            </head><body><article><audio src="/ep12.mp3"></audio></article></body></html>"#,
+        );
+        let audio = doc.find(Name("audio")).next().unwrap();
+        assert_eq!(json_ld_of(&audio), json_ld(&doc));
+        assert_eq!(json_ld_of(&audio).len(), 1);
+    }
+}
@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+
+use select::document::Document;
+use select::predicate::{Attr, Predicate};
+
+use crate::extract_meta::head_meta;
+
+/// Keywords longer than this number of chars are dropped.
+pub const MAX_KEYWORD_LEN: usize = 60;
+
+/// The meta tags that hold keywords of the article.
+const KEYWORD_META_INFO: [(&str, &str); 5] = [
+    ("name", "keywords"),
+    ("name", "news_keywords"),
+    ("property", "keywords"),
+    ("property", "article:tag"),
+    ("name", "parsely-tags"),
+];
+
+/// Collect the keywords of all keyword meta tags of the document.
+///
+/// Merges `keywords`, `news_keywords`, `article:tag` and `parsely-tags`,
+/// splits their values on commas and semicolons and deduplicates them case
+/// insensitively. Entries that are longer than [`MAX_KEYWORD_LEN`] chars or
+/// contain html are dropped.
+pub fn keywords(doc: &Document) -> Vec<String> {
+    let mut uniques = HashSet::new();
+    let mut keywords = Vec::new();
+    for (k, v) in &KEYWORD_META_INFO {
+        let contents = head_meta(doc)
+            .into_iter()
+            .filter(|node| Attr(*k, *v).matches(node))
+            .filter_map(|node| node.attr("content"));
+        for content in contents {
+            for keyword in content.split([',', ';']) {
+                let keyword = keyword
+                    .replace('\u{a0}', " ")
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if keyword.is_empty()
+                    || keyword.chars().count() > MAX_KEYWORD_LEN
+                    || keyword.contains(['<', '>'])
+                {
+                    continue;
+                }
+                if uniques.insert(keyword.to_lowercase()) {
+                    keywords.push(keyword);
+                }
+            }
+        }
+    }
+    keywords
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keyword_metas() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta name="keywords" content="Politics, Elections; Berlin">
+            <meta name="news_keywords" content="elections,  Bundestag ,">
+            <meta property="article:tag" content="Berlin">
+            <meta property="article:tag" content="Coalition talks">
+            <meta name="parsely-tags" content="politics,<b>bold</b>,a tag that is far too long to be a keyword of any real article">
+            </head><body></body></html>"#,
+        );
+        assert_eq!(
+            keywords(&doc),
+            vec!["Politics", "Elections", "Berlin", "Bundestag", "Coalition talks"]
+        );
+    }
+}
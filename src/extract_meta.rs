@@ -1,13 +1,29 @@
 use std::borrow::Cow;
 use select::document::Document;
+use select::node::Node;
 use select::predicate::{Attr, Name, Predicate};
 
+/// The `<meta>` elements within the `<head>`.
+///
+/// Fragments and documents without a `<head>` have their `<meta>` elements
+/// in the body, so if the head has none all of the document's are returned.
+pub fn head_meta(doc: &Document) -> Vec<Node<'_>> {
+    let in_head: Vec<_> = doc.find(Name("head").descendant(Name("meta"))).collect();
+    if in_head.is_empty() {
+        doc.find(Name("meta")).collect()
+    } else {
+        in_head
+    }
+}
+
 /// Extract a given meta content form document.
 pub fn meta_content<'a, 'b>(
     doc: &'a Document,
     attr: Attr<&'b str, &'b str>,
 ) -> Option<Cow<'a, str>> {
-    doc.find(Name("head").descendant(Name("meta").and(attr)))
+    head_meta(doc)
+        .into_iter()
+        .filter(|node| attr.matches(node))
         .filter_map(|node| {
             node.attr("content")
                 .map(str::trim)
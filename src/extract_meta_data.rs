@@ -1,7 +1,10 @@
 use std::ops::Deref;
 use select::document::Document;
 use select::node::Node;
-use select::predicate::{Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::extract_meta::head_meta;
 
 /// Represents `<meta>` [`select::node::Node`] in a
 /// [`select::document::Document`].
@@ -65,8 +68,111 @@ impl<'a> Deref for MetaNode<'a> {
 
 /// Finds all `<meta>` nodes in the document.
 pub fn meta_data<'a>(doc: &'a Document) -> Vec<MetaNode<'a>> {
-    doc.find(Name("head").descendant(Name("meta")))
+    head_meta(doc)
+        .into_iter()
         .map(|node| MetaNode { inner: node })
         .filter(MetaNode::is_key_value)
         .collect()
 }
+
+/// The keys and values of the `<meta>` nodes of a document, independent of
+/// the document.
+///
+/// The pairs keep the order of the document, a key like a repeated
+/// `og:image` is kept once per node. Keys are looked up case-insensitively.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize), serde(transparent))]
+pub struct MetaMap {
+    entries: Vec<(String, String)>,
+}
+
+impl MetaMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The keys and values of the `nodes`, see [`MetaNode::key`] and
+    /// [`MetaNode::value`].
+    pub fn from_nodes(nodes: &[MetaNode]) -> Self {
+        nodes
+            .iter()
+            .filter_map(|node| Some((node.key()?, node.value()?)))
+            .collect()
+    }
+
+    /// Append the `value` of the `key`.
+    pub fn push<K: Into<String>, V: Into<String>>(&mut self, key: K, value: V) {
+        self.entries.push((key.into(), value.into()));
+    }
+
+    /// The first value of the `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// All values of the `key`, in the order of the document.
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// All keys and values, in the order of the document.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for MetaMap {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self {
+            entries: iter
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        }
+    }
+}
+
+/// The keys and values of all `<meta>` nodes in the document.
+pub fn meta_map(doc: &Document) -> MetaMap {
+    MetaMap::from_nodes(&meta_data(doc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_keys() {
+        let doc = Document::from(
+            r#"<html><head><meta property="og:image" content="https://example.com/a.jpg">
+            <meta property="og:image" content="https://example.com/b.jpg">
+            <meta name="parsely-section" content="Harbour"><meta name="robots">
+            <meta itemprop="wordCount" value="830"></head><body></body></html>"#,
+        );
+        let meta = meta_map(&doc);
+        assert_eq!(meta.get("OG:Image"), Some("https://example.com/a.jpg"));
+        assert_eq!(
+            meta.get_all("og:image"),
+            vec!["https://example.com/a.jpg", "https://example.com/b.jpg"]
+        );
+        assert_eq!(meta.get("Parsely-Section"), Some("Harbour"));
+        assert_eq!(meta.get("robots"), None);
+        assert!(meta.get_all("twitter:card").is_empty());
+        assert_eq!(meta.len(), 3);
+    }
+}
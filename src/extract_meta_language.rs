@@ -1,40 +1,125 @@
 use std::str::FromStr;
 use select::document::Document;
 use select::predicate::{Attr, Name};
+use crate::language::DetectedLanguage;
+use crate::text::ArticleTextNodeExtractor;
 use crate::Language;
 use crate::extract_meta::meta_content;
 
 /// Extract content language from meta tag or html lang attribute.
+///
+/// The `lang` and `xml:lang` of the `<html>`, the `Content-Language` and
+/// `lang` meta tags and the `og:locale`, like `ru_RU`, are tried in this
+/// order, see [`Language::from_str`] for the tags. If none of them is a
+/// supported language, the first of them is returned as
+/// [`Language::Other`].
 pub fn meta_language(doc: &Document) -> Option<Language> {
-    let mut unknown_lang: Option<Language> = None;
-
-    // First, check the standard html lang attribute
-    if let Some(html_tag) = doc.find(Name("html")).next() {
-        if let Some(lang_attr) = html_tag.attr("lang") {
-            match Language::from_str(lang_attr) {
-                Ok(lang) => return Some(lang),
-                Err(lang) => {
-                    unknown_lang = Some(lang);
-                }
-            }
-        }
-    }
+    let html = doc.find(Name("html")).next();
+    let tags = [
+        html.and_then(|html| html.attr("lang")).map(str::to_string),
+        html.and_then(|html| html.attr("xml:lang")).map(str::to_string),
+        meta_content(doc, Attr("http-equiv", "Content-Language")).map(|meta| meta.to_string()),
+        meta_content(doc, Attr("name", "lang")).map(|meta| meta.to_string()),
+        meta_content(doc, Attr("property", "og:locale")).map(|meta| meta.to_string()),
+    ];
 
-    if let Some(meta) = meta_content(doc, Attr("http-equiv", "Content-Language")) {
-        match Language::from_str(&*meta) {
+    let mut unknown_lang: Option<Language> = None;
+    for tag in tags.iter().flatten().filter(|tag| !tag.trim().is_empty()) {
+        match Language::from_str(tag) {
             Ok(lang) => return Some(lang),
             Err(lang) => {
-                unknown_lang = Some(lang);
+                unknown_lang.get_or_insert(lang);
             }
         }
     }
-    if let Some(meta) = meta_content(doc, Attr("name", "lang")) {
-        match Language::from_str(&*meta) {
-            Ok(lang) => return Some(lang),
-            Err(lang) => {
-                unknown_lang = Some(lang);
-            }
+    unknown_lang
+}
+
+/// The text of the `<p>` nodes of the document, up to
+/// [`Language::DETECTION_SAMPLE_WORDS`] words.
+pub fn text_sample(doc: &Document) -> String {
+    let mut sample = String::new();
+    let mut word_count = 0;
+    for para in doc.find(Name("p")) {
+        if word_count >= Language::DETECTION_SAMPLE_WORDS {
+            break;
         }
+        let text = para.text();
+        word_count += ArticleTextNodeExtractor::words(&text).count();
+        sample.push_str(text.trim());
+        sample.push('\n');
     }
-    unknown_lang
-}
\ No newline at end of file
+    sample
+}
+
+/// Detect the language of the document from the text of its paragraphs.
+pub fn detect_language(doc: &Document) -> Option<DetectedLanguage> {
+    detect_language_with_custom(doc, &[])
+}
+
+/// Like [`detect_language`], but also considers the `custom` languages, see
+/// [`Language::detect_with_custom`].
+pub fn detect_language_with_custom(doc: &Document, custom: &[Language]) -> Option<DetectedLanguage> {
+    Language::detect_with_custom(&text_sample(doc), custom)
+}
+
+/// The language of the `custom` languages with the identifier of `lang`,
+/// else `lang`.
+pub fn custom_language(lang: Language, custom: &[Language]) -> Language {
+    custom
+        .iter()
+        .find(|custom| custom.identifier().eq_ignore_ascii_case(lang.identifier()))
+        .cloned()
+        .unwrap_or(lang)
+}
+
+/// Checks whether `lang` fits the text of the document.
+///
+/// Falls back to the detected language if `lang` finds less than
+/// [`ArticleTextNodeExtractor::MINIMUM_STOPWORD_COUNT`] stopwords in the
+/// paragraphs. Returns the language to use together with the detected one.
+pub fn resolve_language(doc: &Document, lang: Language) -> (Language, Option<DetectedLanguage>) {
+    resolve_language_with_custom(doc, lang, &[])
+}
+
+/// Like [`resolve_language`], but also detects the `custom` languages.
+pub fn resolve_language_with_custom(
+    doc: &Document,
+    lang: Language,
+    custom: &[Language],
+) -> (Language, Option<DetectedLanguage>) {
+    let sample = text_sample(doc);
+    let detected = Language::detect_with_custom(&sample, custom);
+    let fits = lang
+        .stopword_count(&sample)
+        .map(|stats| stats.stopword_count >= ArticleTextNodeExtractor::MINIMUM_STOPWORD_COUNT)
+        .unwrap_or_default();
+    match detected {
+        Some(detected) if !fits => (detected.language.clone(), Some(detected)),
+        detected => (lang, detected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_of_tags() {
+        let lang = |html: &str| meta_language(&Document::from(html));
+        assert_eq!(lang(r#"<html lang="pt-BR"><head></head></html>"#), Some(Language::Portuguese));
+        assert_eq!(
+            lang(r#"<html xml:lang="zh-Hans-CN"><head></head></html>"#),
+            Some(Language::Chinese)
+        );
+        assert_eq!(
+            lang(r#"<html lang="tlh"><head><meta property="og:locale" content="ru_RU"></head></html>"#),
+            Some(Language::Russian)
+        );
+        assert_eq!(
+            lang(r#"<html lang="tlh"><head><meta property="og:locale" content="qya_ME"></head></html>"#),
+            Some(Language::Other("tlh".to_string()))
+        );
+        assert_eq!(lang("<html><head></head></html>"), None);
+    }
+}
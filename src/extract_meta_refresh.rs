@@ -0,0 +1,129 @@
+use url::Url;
+use select::document::Document;
+use select::predicate::{Attr, Name, Predicate};
+
+use crate::extract_base_url::resolve_base;
+use crate::fetch_info::FetchInfo;
+
+/// The longest delay in seconds of a refresh that is treated as a redirect.
+///
+/// Longer delays, like `content="300;url=/"` to periodically reload the
+/// front page, leave the reader on the page.
+pub const MAX_META_REFRESH_DELAY: u32 = 10;
+
+/// The max number of meta refreshes followed when downloading an article,
+/// see [`crate::article::ArticleBuilder::follow_meta_refresh`].
+pub const MAX_META_REFRESH_HOPS: usize = 3;
+
+/// The url a stub page redirects to with
+/// `<meta http-equiv="refresh" content="0;url=https://...">`.
+///
+/// Refreshes without a url, with a delay longer than
+/// [`MAX_META_REFRESH_DELAY`] or inside a `<noscript>` are ignored. A
+/// relative url is resolved against the `<base>` of the document or the
+/// `base_url`, see [`resolve_base`].
+pub fn meta_refresh_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    doc.find(Name("meta").and(Attr("http-equiv", ()).and(Attr("content", ()))))
+        .filter(|meta| {
+            meta.attr("http-equiv")
+                .map(|equiv| equiv.trim().eq_ignore_ascii_case("refresh"))
+                .unwrap_or_default()
+        })
+        .filter(|meta| {
+            !std::iter::successors(meta.parent(), |node| node.parent()).any(|node| node.is(Name("noscript")))
+        })
+        .filter_map(|meta| meta.attr("content").and_then(parse_meta_refresh))
+        .filter(|(delay, _)| *delay <= MAX_META_REFRESH_DELAY)
+        .find_map(|(_, url)| options.parse(url).ok())
+}
+
+/// Whether the refresh of the page of the `fetch_info` to the `target` may
+/// be followed.
+///
+/// It may not if the `target` is a page of the chain, which would loop, or
+/// if the chain already has [`MAX_META_REFRESH_HOPS`] refreshes.
+pub(crate) fn can_follow_meta_refresh(fetch_info: &FetchInfo, target: &Url) -> bool {
+    *target != fetch_info.final_url
+        && !fetch_info.redirect_chain.contains(target)
+        && fetch_info.redirect_chain.len() < MAX_META_REFRESH_HOPS
+}
+
+/// Split the `content` of a refresh into its delay in seconds and url, like
+/// `0;url=https://example.com/`, `0; URL='/story'` or `5, /story`.
+///
+/// A fractional delay is truncated. Returns `None` if the content has no url
+/// and only reloads the page.
+pub fn parse_meta_refresh(content: &str) -> Option<(u32, &str)> {
+    let content = content.trim_start();
+    let digits = content.find(|c: char| !c.is_ascii_digit()).unwrap_or(content.len());
+    let delay = content[..digits].parse().ok()?;
+    let rest = content[digits..].trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix(|c| c == ';' || c == ',').unwrap_or(rest).trim_start();
+    let rest = match rest.get(..3) {
+        Some(key) if key.eq_ignore_ascii_case("url") && rest[3..].trim_start().starts_with('=') => {
+            rest[3..].trim_start()[1..].trim_start()
+        }
+        _ => rest,
+    };
+    let url = match rest.chars().next() {
+        Some(quote @ ('\'' | '"')) => {
+            let rest = &rest[1..];
+            &rest[..rest.find(quote).unwrap_or(rest.len())]
+        }
+        _ => rest,
+    };
+    let url = url.trim();
+    if url.is_empty() {
+        None
+    } else {
+        Some((delay, url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_spellings() {
+        let url = "https://new.example.com/story";
+        for content in [
+            "0;url=https://new.example.com/story",
+            "0; URL=https://new.example.com/story",
+            "0 ; url = https://new.example.com/story ",
+            "0;Url='https://new.example.com/story'",
+            "0; url=\"https://new.example.com/story\"",
+            "0, url=https://new.example.com/story",
+            "0.5; url=https://new.example.com/story",
+            "0;https://new.example.com/story",
+            "  0 https://new.example.com/story",
+        ] {
+            assert_eq!(parse_meta_refresh(content), Some((0, url)), "{}", content);
+        }
+        assert_eq!(parse_meta_refresh("300"), None);
+        assert_eq!(parse_meta_refresh("0; url="), None);
+        assert_eq!(parse_meta_refresh("url=https://new.example.com/story"), None);
+        assert_eq!(parse_meta_refresh("5;url=/story"), Some((5, "/story")));
+    }
+
+    #[test]
+    fn refresh_url_of_document() {
+        let base = Url::parse("https://example.com/old/story").unwrap();
+        let doc = Document::from(
+            r#"<html><head><noscript><meta http-equiv="refresh" content="0;url=/no-js"></noscript>
+            <meta http-equiv="Refresh" content="0; URL='/new/story'"></head><body></body></html>"#,
+        );
+        assert_eq!(
+            meta_refresh_url(&doc, Some(&base)).unwrap().as_str(),
+            "https://example.com/new/story"
+        );
+
+        let doc = Document::from(
+            r#"<html><head><meta http-equiv="refresh" content="300;url=/"></head><body></body></html>"#,
+        );
+        assert_eq!(meta_refresh_url(&doc, Some(&base)), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::borrow::Cow;
+
+use select::document::Document;
+use select::node::Node;
+
+/// The value of the first schema.org microdata property `itemprop` of the
+/// document.
+///
+/// The value is the `content` of a `<meta>`, the `href` of a `<link>` or
+/// `<a>`, the `src` of an `<img>` or other media element, the `datetime` of
+/// a `<time>` or the text of any other element, with normalized whitespace.
+/// For an item like `<div itemprop="image" itemscope>` the value of its
+/// `url` or `contentUrl` property is used. Empty values are skipped.
+pub fn microdata_value<'a>(doc: &'a Document, itemprop: &str) -> Option<Cow<'a, str>> {
+    doc.find(|node: &Node| has_itemprop(node, itemprop))
+        .find_map(|node| {
+            if node.attr("itemscope").is_some() {
+                node.find(|node: &Node| has_itemprop(node, "url") || has_itemprop(node, "contentUrl"))
+                    .find_map(|node| property_value(&node))
+            } else {
+                property_value(&node)
+            }
+        })
+}
+
+/// Whether the space separated `itemprop` attribute of the node contains the
+/// `prop`.
+pub fn has_itemprop(node: &Node, prop: &str) -> bool {
+    node.attr("itemprop")
+        .map(|props| props.split_whitespace().any(|p| p == prop))
+        .unwrap_or_default()
+}
+
+pub(crate) fn property_value<'a>(node: &Node<'a>) -> Option<Cow<'a, str>> {
+    let attr = match node.name()? {
+        "meta" => "content",
+        "link" | "a" | "area" => "href",
+        "img" | "audio" | "video" | "source" | "iframe" | "embed" | "track" => "src",
+        "object" => "data",
+        "time" if node.attr("datetime").is_some() => "datetime",
+        "data" | "meter" => "value",
+        _ => {
+            let text = node.text().split_whitespace().collect::<Vec<_>>().join(" ");
+            return (!text.is_empty()).then_some(Cow::Owned(text));
+        }
+    };
+    node.attr(attr)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(Cow::Borrowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use crate::extract::{DefaultExtractor, Extractor};
+    use crate::extract_title::title;
+
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/microdata/article.html");
+
+    #[test]
+    fn microdata_metadata() {
+        let doc = Document::from(FIXTURE);
+        assert_eq!(microdata_value(&doc, "image").as_deref(), Some("/img/harbour.jpg"));
+        assert_eq!(microdata_value(&doc, "missing"), None);
+
+        let extractor = DefaultExtractor::default();
+        let base = Url::parse("https://example.com/news/harbour.html").unwrap();
+        assert_eq!(title(&doc).as_deref(), Some("Harbour expansion approved"));
+        assert_eq!(
+            extractor.meta_description(&doc).as_deref(),
+            Some("The city council approved the expansion of the harbour on Tuesday.")
+        );
+        assert_eq!(
+            extractor.meta_img_url(&doc, Some(&base)).as_ref().map(Url::as_str),
+            Some("https://example.com/img/harbour.jpg")
+        );
+    }
+}
@@ -1,21 +1,283 @@
+use std::sync::Arc;
+
+use url::Url;
 use select::document::Document;
-use select::predicate::{Name, Predicate};
+use select::node::Node;
+use select::predicate::{Attr, Name, Predicate};
 use crate::Language;
-use crate::text::{ArticleTextNode, ArticleTextNodeExtractor};
+use crate::clean::{DocumentCleaner, ExtractionFilters};
+use crate::extract_base_url::resolve_base;
+use crate::text::{
+    class_parts, ArticleBodyAttrs, ArticleTextNode, ArticleTextNodeExtractor, ExtractionProfile, TextContainer,
+};
+
+/// The text and media of the article node, see [`extracted_content`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedContent {
+    /// The cleaned text, see [`ArticleTextNode::clean_text`].
+    pub text: String,
+    /// The cleaned text of each paragraph of the `text`.
+    pub paragraphs: Vec<String>,
+    pub images: Vec<Url>,
+    pub references: Vec<Url>,
+    pub videos: Vec<Url>,
+    /// Confidence in the selected node, between `0.0` and `1.0`.
+    pub confidence: f64,
+    /// The thresholds the node was selected with.
+    pub profile: ExtractionProfile,
+}
 
 /// Detect the [`select::node::Node`] that contains the article's text.
 ///
 /// If the `doc`'s body contains a node that matches the
-/// [`crate::text::ARTICLE_BODY_ATTR`] attribute selectors, this node will
-/// be selected. Otherwise the article node will be calculated by analysing
-/// and scoring the textual content of text nodes.
+/// [`crate::text::ARTICLE_BODY_ATTR`] attribute selectors and passes
+/// [`is_plausible_article_body`], this node will be selected. Otherwise the
+/// article node will be calculated by analysing and scoring the textual
+/// content of text nodes.
 pub fn article_node<'a>(doc: &'a Document, lang: Language) -> Option<ArticleTextNode<'a>> {
-    let mut iter =
-        doc.find(Name("body").descendant(ArticleTextNodeExtractor::article_body_predicate()));
-    if let Some(node) = iter.next() {
-        if iter.next().is_none() {
-            return Some(ArticleTextNode::new(node));
-        }
+    if let Some(node) = explicit_article_body(doc, &lang, None) {
+        return Some(ArticleTextNode::new(node));
     }
     ArticleTextNodeExtractor::calculate_best_node(doc, lang)
-}
\ No newline at end of file
+}
+
+/// The only node within the `<body>` that matches the `attrs`, or the
+/// [`crate::text::ARTICLE_BODY_ATTR`] if `None`, if it passes
+/// [`is_plausible_article_body`].
+///
+/// Documents without a `<body>`, like XHTML with prefixed element names, are
+/// searched as a whole.
+fn explicit_article_body<'a>(
+    doc: &'a Document,
+    lang: &Language,
+    attrs: Option<&ArticleBodyAttrs>,
+) -> Option<Node<'a>> {
+    if !attrs.map(ArticleBodyAttrs::is_shortcut).unwrap_or(true) {
+        return None;
+    }
+    let predicate = |node: &Node| match attrs {
+        Some(attrs) => attrs.matches(node),
+        None => ArticleTextNodeExtractor::article_body_predicate()(node),
+    };
+    let mut nodes: Vec<_> = if doc.find(Name("body")).next().is_some() {
+        doc.find(Name("body").descendant(predicate)).take(2).collect()
+    } else {
+        doc.find(predicate).take(2).collect()
+    };
+    let node = if nodes.len() == 1 { nodes.pop()? } else { return None };
+    if is_plausible_article_body(&node, lang) {
+        Some(node)
+    } else {
+        log::debug!("article body attributes matched a node that fails the sanity check, scoring instead");
+        None
+    }
+}
+
+/// A cheap sanity check of a node that matches the attributes of the article
+/// body, before it's taken without scoring.
+///
+/// The node fails if it wraps navigation or a sidebar, if more than
+/// [`ArticleTextNodeExtractor::MAX_LINK_DENSITY`] of its text are links or if
+/// it has fewer than [`ArticleTextNodeExtractor::MINIMUM_STOPWORD_COUNT`]
+/// stopwords.
+pub fn is_plausible_article_body(node: &Node, lang: &Language) -> bool {
+    let wraps_page = node
+        .find(
+            Name("nav")
+                .or(Attr("role", "navigation"))
+                .or(Attr("role", "complementary"))
+                .or(|node: &Node| {
+                    node.attr("class")
+                        .map(|class| {
+                            class
+                                .split_whitespace()
+                                .any(|token| class_parts(token).iter().any(|part| part == "sidebar"))
+                        })
+                        .unwrap_or_default()
+                }),
+        )
+        .next()
+        .is_some();
+    if wraps_page || node.link_density() > ArticleTextNodeExtractor::MAX_LINK_DENSITY {
+        return false;
+    }
+    lang.stopword_count(&node.text())
+        .map(|stats| stats.stopword_count >= ArticleTextNodeExtractor::MINIMUM_STOPWORD_COUNT)
+        .unwrap_or(true)
+}
+
+/// Like [`article_node`], but falls back to the relaxed thresholds of
+/// [`ExtractionProfile::Relaxed`] if the strict pass fails.
+///
+/// Returns the node together with the profile that selected it.
+pub fn article_node_adaptive<'a>(
+    doc: &'a Document,
+    lang: Language,
+) -> Option<(ArticleTextNode<'a>, ExtractionProfile)> {
+    article_node_adaptive_with_filters(doc, lang, None)
+}
+
+/// Like [`article_node_adaptive`], but the nodes and texts the `filters`
+/// deny are neither selected nor part of the text and images of the node.
+pub fn article_node_adaptive_with_filters<'a>(
+    doc: &'a Document,
+    lang: Language,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Option<(ArticleTextNode<'a>, ExtractionProfile)> {
+    let attrs = filters.and_then(|filters| filters.article_body.as_ref());
+    if let Some(node) = explicit_article_body(doc, &lang, attrs)
+        && !filters.map(|filters| filters.denies_node_or_ancestor(&node)).unwrap_or_default()
+    {
+        return Some((
+            ArticleTextNode::new(node).filters(filters.cloned()),
+            ExtractionProfile::Strict,
+        ));
+    }
+    ArticleTextNodeExtractor::calculate_best_node_adaptive_with_filters(doc, lang, filters)
+}
+
+/// The text, images, references and videos of the node selected by
+/// [`article_node_adaptive`], so they all belong to the same node and the
+/// node is only selected once.
+///
+/// The text and references are cleaned with the `cleaner`, or the built-in
+/// cleaning if it's `None`. The nodes and texts the `filters` deny are
+/// skipped. Relative urls are resolved against the `<base>` of the document
+/// or the `base_url`.
+pub fn extracted_content(
+    doc: &Document,
+    lang: Language,
+    base_url: Option<&Url>,
+    cleaner: Option<&dyn DocumentCleaner>,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Option<ExtractedContent> {
+    let (node, profile) = article_node_adaptive_with_filters(doc, lang, filters)?;
+    Some(node_content(doc, &node, profile, base_url, cleaner))
+}
+
+/// The text, images, references and videos of the already selected `node`,
+/// see [`extracted_content`].
+pub fn node_content(
+    doc: &Document,
+    node: &ArticleTextNode,
+    profile: ExtractionProfile,
+    base_url: Option<&Url>,
+    cleaner: Option<&dyn DocumentCleaner>,
+) -> ExtractedContent {
+    let base = resolve_base(doc, base_url);
+    let base_url = base.as_ref();
+    let (text, paragraphs) = node.clean_text_and_paragraphs(cleaner);
+    let references = match cleaner {
+        Some(cleaner) => node.references_with_cleaner(base_url, cleaner),
+        None => node.references(base_url),
+    };
+    ExtractedContent {
+        paragraphs,
+        images: node.images(base_url),
+        references,
+        videos: node
+            .videos()
+            .into_iter()
+            .filter_map(|video| video.get_src_url(base_url)?.ok())
+            .collect(),
+        confidence: node.confidence_score(),
+        profile,
+        text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_of_one_node() {
+        let doc = Document::from(
+            r#"<html><head><base href="https://cdn.example.com/"></head><body>
+            <div class="teaser"><img src="teaser.jpg"></div>
+            <div itemprop="articleBody">
+            <p>The city council approved the expansion of the harbour on Tuesday after a long debate.</p>
+            <img src="img/harbour.jpg">
+            <p>Construction will start next spring, according to the <a href="/port.html">port authority</a>.</p>
+            <iframe src="https://www.youtube.com/embed/abc123"></iframe>
+            </div></body></html>"#,
+        );
+        let content = extracted_content(&doc, Language::English, None, None, None).unwrap();
+        assert_eq!(
+            content.paragraphs,
+            vec![
+                "The city council approved the expansion of the harbour on Tuesday after a long debate.",
+                "Construction will start next spring, according to the port authority.",
+            ]
+        );
+        assert_eq!(content.text, content.paragraphs.join(" "));
+        assert_eq!(content.images, vec![Url::parse("https://cdn.example.com/img/harbour.jpg").unwrap()]);
+        assert_eq!(content.references, vec![Url::parse("https://cdn.example.com/port.html").unwrap()]);
+        assert_eq!(content.videos, vec![Url::parse("https://www.youtube.com/embed/abc123").unwrap()]);
+        assert_eq!(content.profile, ExtractionProfile::Strict);
+    }
+
+    #[test]
+    fn paragraphs_of_an_interview() {
+        let doc = Document::from(
+            r#"<html><body><div itemprop="articleBody">
+            <p>Мы поговорили с директором театра о новом сезоне.</p>
+            <p>— Каким будет новый сезон?</p>
+            <p>— Очень насыщенным, у нас пять премьер.</p>
+            <p>— Кто будет ставить спектакли?</p>
+            <p>— Молодые режиссёры из разных городов.</p>
+            </div></body></html>"#,
+        );
+        let content = extracted_content(&doc, Language::Russian, None, None, None).unwrap();
+        assert_eq!(content.paragraphs.len(), 5);
+        assert_eq!(content.paragraphs[1], "— Каким будет новый сезон?");
+        assert_eq!(content.text, content.paragraphs.join("\n"));
+    }
+
+    #[test]
+    fn explicit_body_content() {
+        let doc = Document::from(include_str!("../tests/fixtures/body/explicit_body.html"));
+        let content = extracted_content(&doc, Language::English, None, None, None).unwrap();
+        let paragraphs = content.paragraphs.iter().filter(|para| para.ends_with("of the plan."));
+        assert_eq!(paragraphs.count(), 48);
+        assert!(!content.text.contains("Advertisement"));
+        assert!(!content.text.contains("city archive"));
+        assert!(!content.text.contains("Storm closes the bridge"));
+        assert_eq!(content.videos.len(), 6);
+    }
+
+    #[test]
+    fn page_wide_content_wrapper() {
+        let doc = Document::from(include_str!("../tests/fixtures/body/content_wrapper.html"));
+        let wrapper = doc.find(Attr("id", "content")).next().unwrap();
+        assert!(!is_plausible_article_body(&wrapper, &Language::English));
+
+        let node = article_node(&doc, Language::English).unwrap();
+        let text = node.clean_text();
+        assert!(text.contains("The city council approved the expansion of the harbour"));
+        assert!(!text.contains("Most read"));
+        assert!(!text.contains("Subscribe to our newsletter"));
+        assert!(!text.contains("Politics"));
+    }
+
+    #[test]
+    fn custom_article_body_attrs() {
+        let doc = Document::from(
+            r#"<html><body><div class="related"><p>Storm closes the bridge.</p></div>
+            <div class="story-body">
+            <p>The city council approved the expansion of the harbour on Tuesday after a long debate.</p>
+            <p>Construction will start in the spring, according to the port authority of the city.</p>
+            </div></body></html>"#,
+        );
+        let attrs = ArticleBodyAttrs::default().remove("id", "content").add("class", "story-body");
+        assert!(!attrs.attrs().any(|attr| attr == ("id", "content")));
+        let filters = Arc::new(ExtractionFilters::default().article_body_attrs(attrs.clone()));
+        let (node, _) = article_node_adaptive_with_filters(&doc, Language::English, Some(&filters)).unwrap();
+        assert_eq!(node.attr("class"), Some("story-body"));
+        assert!(explicit_article_body(&doc, &Language::English, Some(&attrs)).is_some());
+        assert!(explicit_article_body(&doc, &Language::English, None).is_none());
+
+        let attrs = attrs.shortcut(false);
+        assert!(explicit_article_body(&doc, &Language::English, Some(&attrs)).is_none());
+    }
+}
@@ -0,0 +1,115 @@
+use select::document::Document;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::date::{ArticleDate, Date, DateExtractor, Update};
+use crate::extract_meta::head_meta;
+
+/// The `article:*` namespace of the Open Graph protocol.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct OpenGraphArticle {
+    /// Value of `article:section`.
+    pub section: Option<String>,
+    /// All values of the repeated `article:tag` property.
+    pub tags: Vec<String>,
+    /// Raw value of `article:published_time`.
+    pub published_time: Option<String>,
+    /// Raw value of `article:modified_time`.
+    pub modified_time: Option<String>,
+    /// All values of the repeated `article:author` property.
+    pub authors: Vec<String>,
+}
+
+impl OpenGraphArticle {
+    /// Whether none of the `article:*` properties were found.
+    pub fn is_empty(&self) -> bool {
+        self.section.is_none()
+            && self.tags.is_empty()
+            && self.published_time.is_none()
+            && self.modified_time.is_none()
+            && self.authors.is_empty()
+    }
+
+    /// Parse `article:published_time` and `article:modified_time` into an
+    /// [`ArticleDate`].
+    pub fn article_date(&self) -> Option<ArticleDate> {
+        let (published, offset) =
+            DateExtractor::fuzzy_dtparse_with_offset(self.published_time.as_deref()?)?;
+        let last_updated = self
+            .modified_time
+            .as_deref()
+            .and_then(DateExtractor::fuzzy_dtparse)
+            .map(Update::DateTime);
+        Some(ArticleDate {
+            published: Date::DateTime(published),
+            last_updated,
+            relative: false,
+            offset,
+        })
+    }
+}
+
+/// Collect all `article:*` Open Graph properties of the document.
+///
+/// Unlike [`crate::extract_meta::meta_content`] every occurrence of a
+/// repeated property like `article:tag` is collected.
+pub fn og_article(doc: &Document) -> Option<OpenGraphArticle> {
+    let mut og = OpenGraphArticle::default();
+    for node in head_meta(doc) {
+        let (Some(property), Some(content)) = (node.attr("property"), node.attr("content")) else {
+            continue;
+        };
+        let content = content.trim();
+        if content.is_empty() {
+            continue;
+        }
+        match property.trim() {
+            "article:section" if og.section.is_none() => og.section = Some(content.to_string()),
+            "article:tag" if !og.tags.iter().any(|t| t.eq_ignore_ascii_case(content)) => {
+                og.tags.push(content.to_string())
+            }
+            "article:published_time" if og.published_time.is_none() => {
+                og.published_time = Some(content.to_string())
+            }
+            "article:modified_time" if og.modified_time.is_none() => {
+                og.modified_time = Some(content.to_string())
+            }
+            "article:author" if !og.authors.iter().any(|a| a == content) => {
+                og.authors.push(content.to_string())
+            }
+            _ => {}
+        }
+    }
+    if og.is_empty() { None } else { Some(og) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_repeated_tags() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta property="article:section" content="Politics">
+            <meta property="article:tag" content="Election">
+            <meta property="article:tag" content="Senate">
+            <meta property="article:tag" content="election">
+            <meta property="article:published_time" content="2024-03-02T10:15:00+03:00">
+            <meta property="article:author" content="Jane Zhu">
+            </head><body></body></html>"#,
+        );
+        let og = og_article(&doc).unwrap();
+        assert_eq!(og.section.as_deref(), Some("Politics"));
+        assert_eq!(og.tags, vec!["Election".to_string(), "Senate".to_string()]);
+        assert_eq!(og.authors, vec!["Jane Zhu".to_string()]);
+        assert!(og.article_date().is_some());
+    }
+
+    #[test]
+    fn no_og_article() {
+        let doc = Document::from("<html><head><title>t</title></head><body></body></html>");
+        assert!(og_article(&doc).is_none());
+    }
+}
@@ -1,14 +1,162 @@
+use std::sync::Arc;
+
+use chrono::Utc;
 use select::document::Document;
 use url::Url;
-use crate::date::{ArticleDate, DateExtractor};
+use crate::clean::ExtractionFilters;
+use crate::date::{ArticleDate, Date, DateExtractor, DateLabels, DateLocale, DateScope, LabeledDates};
+use crate::extract_meta_language::{detect_language, meta_language};
+use crate::extract_og_article::og_article;
+use crate::Language;
 
-/// Extract a publishing date from the document or URL path.
+/// Extract a publishing date from the document or URL path, see
+/// [`publishing_date_with_labels`] with the default [`DateLabels`].
 pub fn publishing_date(doc: &Document, base_url: Option<&Url>) -> Option<ArticleDate> {
-    if let Some(date) = DateExtractor::extract_from_doc(doc) {
+    publishing_date_with_labels(doc, base_url, &DateLabels::default())
+}
+
+/// Extract a publishing date from the document or URL path.
+///
+/// The Open Graph `article:published_time` is preferred over all other
+/// sources. If the markup has no date, the date behind one of the `labels`
+/// of the article's language in the visible text is used before the URL,
+/// and if it has no last update, the labeled update.
+pub fn publishing_date_with_labels(
+    doc: &Document,
+    base_url: Option<&Url>,
+    labels: &DateLabels,
+) -> Option<ArticleDate> {
+    publishing_date_with_filters(doc, base_url, labels, None)
+}
+
+/// Like [`publishing_date_with_labels`], but the dates are searched near the
+/// article node selected with the `filters`, the node of the text.
+pub(crate) fn publishing_date_with_filters(
+    doc: &Document,
+    base_url: Option<&Url>,
+    labels: &DateLabels,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Option<ArticleDate> {
+    let dates = LabeledDateScope::new(doc, labels, filters);
+    let date = og_article(doc)
+        .and_then(|og| og.article_date())
+        .or_else(|| DateExtractor::extract_from_doc_in(&dates.scope));
+    if let Some(mut date) = date {
+        if date.last_updated.is_none() {
+            date.last_updated = dates.labeled(labels).modified;
+        }
         return Some(date);
     }
+    let labeled = dates.labeled(labels);
+    if let Some(published) = labeled.published {
+        return Some(ArticleDate {
+            published,
+            last_updated: labeled.modified,
+            relative: labeled.relative,
+            offset: None,
+        });
+    }
     if let Some(url) = base_url {
-        return DateExtractor::extract_from_str(url.path());
+        return DateExtractor::extract_from_url_path(url.path());
     }
     None
-}
\ No newline at end of file
+}
+
+/// Extract the date the article was last modified, see
+/// [`modified_date_with_labels`] with the default [`DateLabels`].
+pub fn modified_date(doc: &Document) -> Option<Date> {
+    modified_date_with_labels(doc, &DateLabels::default())
+}
+
+/// Extract the date the article was last modified.
+///
+/// The Open Graph `article:modified_time` is preferred over all other
+/// sources, the date behind one of the `labels` of the article's language in
+/// the visible text is the last resort. A labeled time without a day is the time of the day the
+/// article was published. Unlike [`publishing_date`] this doesn't fall back
+/// to the URL.
+pub fn modified_date_with_labels(doc: &Document, labels: &DateLabels) -> Option<Date> {
+    modified_date_with_filters(doc, labels, None)
+}
+
+/// Like [`modified_date_with_labels`], but the labeled dates are searched
+/// near the article node selected with the `filters`.
+pub(crate) fn modified_date_with_filters(
+    doc: &Document,
+    labels: &DateLabels,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Option<Date> {
+    og_article(doc)
+        .and_then(|og| og.modified_time)
+        .and_then(|time| DateExtractor::fuzzy_dtparse(&time))
+        .or_else(|| DateExtractor::extract_modified(doc))
+        .map(Date::DateTime)
+        .or_else(|| {
+            // the article node is selected once for both
+            let dates = LabeledDateScope::new(doc, labels, filters);
+            let published = DateExtractor::extract_from_doc_in(&dates.scope).map(|date| date.published);
+            dates.labeled(labels).modified_date(published.as_ref())
+        })
+}
+
+/// The article node the dates are searched near and the locale of the
+/// labeled dates, both in the language of the article.
+struct LabeledDateScope<'a> {
+    scope: DateScope<'a>,
+    locale: DateLocale,
+}
+
+impl<'a> LabeledDateScope<'a> {
+    /// The declared language of the page, else the detected one. Without a
+    /// language that has `labels`, the labels of all languages are tried.
+    /// The article node is selected with the `filters`.
+    fn new(doc: &'a Document, labels: &DateLabels, filters: Option<&'a Arc<ExtractionFilters>>) -> Self {
+        let lang = meta_language(doc)
+            .filter(|lang| !matches!(lang, Language::Other(_)))
+            .or_else(|| detect_language(doc).map(|detected| detected.language));
+        let locale = match &lang {
+            Some(lang) if labels.labels().iter().any(|label| label.language == *lang) => {
+                DateLocale::new(lang.clone())
+            }
+            _ => DateLocale::default(),
+        };
+        Self {
+            scope: DateScope::new(doc, lang.unwrap_or_default()).filters(filters),
+            locale,
+        }
+    }
+
+    /// The dates behind the `labels` of the locale.
+    fn labeled(&self, labels: &DateLabels) -> LabeledDates {
+        DateExtractor::extract_labeled_in(&self.scope, labels, Utc::now().naive_utc(), &self.locale)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::date::DateLabelKind;
+
+    use super::*;
+
+    #[test]
+    fn labels_of_the_article_language() {
+        let labels = DateLabels::empty()
+            .add(Language::German, DateLabelKind::Modified, "Stand")
+            .add(Language::English, DateLabelKind::Modified, "Updated");
+        let html = |lang: &str| {
+            format!(
+                r#"<html lang="{}"><body><article><p class="meta">Stand: 05.03.2024</p>
+                <div itemprop="articleBody"><p>The council approved the expansion of the harbour.</p></div>
+                </article></body></html>"#,
+                lang
+            )
+        };
+        let doc = Document::from(html("de").as_str());
+        assert!(matches!(
+            modified_date_with_labels(&doc, &labels),
+            Some(Date::DateTime(d)) if d.to_string() == "2024-03-05 00:00:00"
+        ));
+        let doc = Document::from(html("en").as_str());
+        assert!(modified_date_with_labels(&doc, &labels).is_none());
+    }
+}
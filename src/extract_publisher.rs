@@ -0,0 +1,191 @@
+use url::Url;
+use select::document::Document;
+use select::node::Node;
+use select::predicate::Attr;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::extract_base_url::resolve_base;
+use crate::extract_favicon::favicon;
+use crate::extract_json_ld::{json_ld, text, values};
+use crate::extract_meta::meta_content;
+use crate::extract_microdata::{has_itemprop, property_value};
+
+/// The organization that published the article.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Publisher {
+    pub name: String,
+    /// The logo of the publisher, or the favicon of the site if the page
+    /// declares no publisher.
+    pub logo: Option<Url>,
+    /// The home page of the publisher.
+    pub url: Option<Url>,
+}
+
+/// The publisher of the article.
+///
+/// Uses the first of these that has a name:
+///   1. The `publisher` of a JSON-LD object, or the object its `@id`
+///      references
+///   2. The `itemprop="publisher"` microdata
+///   3. The `og:site_name`, with the favicon as logo and the root of the
+///      `base_url` as url
+///
+/// Relative urls are resolved against the `<base>` of the document or the
+/// `base_url`, see [`resolve_base`].
+pub fn publisher(doc: &Document, base_url: Option<&Url>) -> Option<Publisher> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    let parse = |href: &str| options.parse(href.trim()).ok();
+    json_ld_publisher(doc, &parse)
+        .or_else(|| microdata_publisher(doc, &parse))
+        .or_else(|| {
+            let name = meta_content(doc, Attr("property", "og:site_name"))?;
+            let name = name.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some(Publisher {
+                name: name.to_string(),
+                logo: base.as_ref().and_then(|base| favicon(doc, base)),
+                url: base_url.and_then(|url| url.join("/").ok()),
+            })
+        })
+}
+
+fn json_ld_publisher(doc: &Document, parse: &dyn Fn(&str) -> Option<Url>) -> Option<Publisher> {
+    let objects = json_ld(doc);
+    objects
+        .iter()
+        .flat_map(|object| values(object, "publisher"))
+        .find_map(|publisher| {
+            // `"publisher": {"@id": "https://example.com/#org"}` references an
+            // organization of the `@graph`
+            let publisher = match publisher.get("@id").and_then(Value::as_str) {
+                Some(id) if publisher.get("name").is_none() => objects
+                    .iter()
+                    .find(|object| object.get("@id").and_then(Value::as_str) == Some(id))?,
+                _ => publisher,
+            };
+            let name = text(publisher, "name")?;
+            let logo = values(publisher, "logo").into_iter().find_map(|logo| {
+                logo.as_str()
+                    .or_else(|| logo.get("url")?.as_str())
+                    .or_else(|| logo.get("contentUrl")?.as_str())
+                    .and_then(parse)
+            });
+            Some(Publisher {
+                name: name.to_string(),
+                logo,
+                url: publisher.get("url").and_then(Value::as_str).and_then(parse),
+            })
+        })
+}
+
+fn microdata_publisher(doc: &Document, parse: &dyn Fn(&str) -> Option<Url>) -> Option<Publisher> {
+    doc.find(|node: &Node| has_itemprop(node, "publisher"))
+        .find_map(|publisher| {
+            if publisher.attr("itemscope").is_none() {
+                return property_value(&publisher).map(|name| Publisher {
+                    name: name.into_owned(),
+                    logo: None,
+                    url: None,
+                });
+            }
+            let name = publisher
+                .find(|node: &Node| has_itemprop(node, "name") && !within_logo(node, &publisher))
+                .find_map(|node| property_value(&node))?
+                .into_owned();
+            let logo = publisher.find(|node: &Node| has_itemprop(node, "logo")).next().and_then(|logo| {
+                if logo.attr("itemscope").is_some() {
+                    logo.find(|node: &Node| has_itemprop(node, "url") || has_itemprop(node, "contentUrl"))
+                        .find_map(|node| property_value(&node).and_then(|value| parse(&value)))
+                } else {
+                    property_value(&logo).and_then(|value| parse(&value))
+                }
+            });
+            let url = publisher
+                .find(|node: &Node| has_itemprop(node, "url") && !within_logo(node, &publisher))
+                .find_map(|node| property_value(&node).and_then(|value| parse(&value)));
+            Some(Publisher { name, logo, url })
+        })
+}
+
+/// Whether the node is a property of the `logo` of the `publisher`.
+fn within_logo(node: &Node, publisher: &Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.index() == publisher.index() {
+            return false;
+        }
+        if has_itemprop(&parent, "logo") {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_ld_publisher_with_relative_logo() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">{"@context": "https://schema.org", "@graph": [
+            {"@type": "NewsArticle", "headline": "Harbour", "publisher": {"@id": "https://example.com/#org"}},
+            {"@type": "NewsMediaOrganization", "@id": "https://example.com/#org", "name": "Example News",
+             "url": "https://example.com/", "logo": {"@type": "ImageObject", "url": "/img/logo.png"}}]}
+            </script></head><body></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/news/harbour.html").unwrap();
+        assert_eq!(
+            publisher(&doc, Some(&base)),
+            Some(Publisher {
+                name: "Example News".to_string(),
+                logo: Some(Url::parse("https://example.com/img/logo.png").unwrap()),
+                url: Some(Url::parse("https://example.com/").unwrap()),
+            })
+        );
+    }
+
+    #[test]
+    fn microdata_and_site_name_publisher() {
+        let doc = Document::from(
+            r#"<html><body><article itemscope itemtype="https://schema.org/NewsArticle">
+            <div itemprop="publisher" itemscope itemtype="https://schema.org/Organization">
+              <div itemprop="logo" itemscope itemtype="https://schema.org/ImageObject">
+                <img itemprop="url" src="logo.svg"><meta itemprop="name" content="Logo">
+              </div>
+              <meta itemprop="name" content="Example News">
+              <link itemprop="url" href="https://example.com/">
+            </div></article></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/news/harbour.html").unwrap();
+        assert_eq!(
+            publisher(&doc, Some(&base)),
+            Some(Publisher {
+                name: "Example News".to_string(),
+                logo: Some(Url::parse("https://example.com/news/logo.svg").unwrap()),
+                url: Some(Url::parse("https://example.com/").unwrap()),
+            })
+        );
+
+        let doc = Document::from(
+            r#"<html><head><meta property="og:site_name" content="Example News">
+            <link rel="icon" href="/favicon.png"></head><body></body></html>"#,
+        );
+        assert_eq!(
+            publisher(&doc, Some(&base)),
+            Some(Publisher {
+                name: "Example News".to_string(),
+                logo: Some(Url::parse("https://example.com/favicon.png").unwrap()),
+                url: Some(Url::parse("https://example.com/").unwrap()),
+            })
+        );
+        assert_eq!(publisher(&Document::from("<html></html>"), Some(&base)), None);
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use select::document::Document;
+use select::node::Node;
+use select::predicate::Name;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::audio::parse_duration;
+use crate::extract_json_ld::{has_type, json_ld, text, values};
+use crate::extract_microdata::{has_itemprop, property_value};
+use crate::text::normalize_whitespace;
+
+/// The ingredients and steps of a schema.org `Recipe`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Recipe {
+    pub name: Option<String>,
+    /// The ingredients with their amounts, like `500 g flour`.
+    pub ingredients: Vec<String>,
+    /// The instructions, one per step.
+    pub steps: Vec<String>,
+    /// The `totalTime`, or the sum of the `prepTime` and `cookTime`.
+    pub total_time: Option<Duration>,
+    /// The quantity the recipe makes, like `4 servings`.
+    #[cfg_attr(feature = "serde0", serde(rename = "yield"))]
+    pub yield_: Option<String>,
+}
+
+impl Recipe {
+    /// Whether the recipe has neither ingredients nor steps.
+    pub fn is_empty(&self) -> bool {
+        self.ingredients.is_empty() && self.steps.is_empty()
+    }
+}
+
+/// The recipe of the document, from a JSON-LD `Recipe` or else from the
+/// microdata of a `Recipe` item.
+///
+/// Recipes without ingredients and steps are skipped.
+pub fn recipe(doc: &Document) -> Option<Recipe> {
+    json_ld(doc)
+        .iter()
+        .filter(|object| has_type(object, &["Recipe"]))
+        .map(json_ld_recipe)
+        .find(|recipe| !recipe.is_empty())
+        .or_else(|| microdata_recipe(doc).filter(|recipe| !recipe.is_empty()))
+}
+
+fn json_ld_recipe(object: &Value) -> Recipe {
+    let ingredients = ["recipeIngredient", "ingredients"]
+        .iter()
+        .map(|property| values(object, property))
+        .find(|values| !values.is_empty())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(Value::as_str)
+        .filter_map(normalize)
+        .collect();
+    let mut steps = Vec::new();
+    for instruction in values(object, "recipeInstructions") {
+        json_ld_steps(instruction, &mut steps);
+    }
+    let duration = |property: &str| text(object, property).and_then(parse_duration);
+    let total_time = duration("totalTime").or_else(|| match (duration("prepTime"), duration("cookTime")) {
+        (Some(prep), Some(cook)) => Some(prep + cook),
+        (prep, cook) => prep.or(cook),
+    });
+    let yield_ = values(object, "recipeYield").into_iter().find_map(|value| match value {
+        Value::String(s) => normalize(s),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    });
+    Recipe {
+        name: text(object, "name").and_then(normalize),
+        ingredients,
+        steps,
+        total_time,
+        yield_,
+    }
+}
+
+/// The steps of a `recipeInstructions` value: a text with one step per
+/// line, a `HowToStep` with a `text` or a `HowToSection` of steps.
+fn json_ld_steps(value: &Value, steps: &mut Vec<String>) {
+    match value {
+        Value::String(s) => steps.extend(s.lines().filter_map(normalize)),
+        Value::Array(values) => {
+            for value in values {
+                json_ld_steps(value, steps);
+            }
+        }
+        Value::Object(_) => {
+            let items = values(value, "itemListElement");
+            if items.is_empty() {
+                steps.extend(text(value, "text").or_else(|| text(value, "name")).and_then(normalize));
+            } else {
+                for item in items {
+                    json_ld_steps(item, steps);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn microdata_recipe(doc: &Document) -> Option<Recipe> {
+    let scope = doc.find(|node: &Node| {
+        node.attr("itemscope").is_some()
+            && node
+                .attr("itemtype")
+                .map(|ty| ty.split_whitespace().any(|ty| ty.trim_end_matches('/').ends_with("schema.org/Recipe")))
+                .unwrap_or_default()
+    })
+    .next()?;
+
+    let value = |prop: &str| properties(&scope, prop).into_iter().find_map(|node| property_value(&node));
+    let ingredients = ["recipeIngredient", "ingredients"]
+        .iter()
+        .map(|prop| {
+            properties(&scope, prop)
+                .iter()
+                .filter_map(|node| property_value(node).as_deref().and_then(normalize))
+                .collect::<Vec<_>>()
+        })
+        .find(|ingredients| !ingredients.is_empty())
+        .unwrap_or_default();
+    let mut steps = Vec::new();
+    for node in properties(&scope, "recipeInstructions") {
+        microdata_steps(&node, &mut steps);
+    }
+    let duration = |prop: &str| value(prop).as_deref().and_then(parse_duration);
+    let total_time = duration("totalTime").or_else(|| match (duration("prepTime"), duration("cookTime")) {
+        (Some(prep), Some(cook)) => Some(prep + cook),
+        (prep, cook) => prep.or(cook),
+    });
+    Some(Recipe {
+        name: value("name").as_deref().and_then(normalize),
+        ingredients,
+        steps,
+        total_time,
+        yield_: value("recipeYield").as_deref().and_then(normalize),
+    })
+}
+
+/// The steps of a `recipeInstructions` element: the `text` of a `HowToStep`
+/// item, the items of a list, the paragraphs or else the whole text.
+fn microdata_steps(node: &Node, steps: &mut Vec<String>) {
+    if node.attr("itemscope").is_some() {
+        let text = properties(node, "text").into_iter().find_map(|node| property_value(&node));
+        steps.extend(text.as_deref().and_then(normalize).or_else(|| normalize(&node.text())));
+        return;
+    }
+    for name in ["li", "p"] {
+        let parts: Vec<_> = node.find(Name(name)).filter_map(|part| normalize(&part.text())).collect();
+        if !parts.is_empty() {
+            steps.extend(parts);
+            return;
+        }
+    }
+    steps.extend(normalize(&node.text()));
+}
+
+/// The descendants of the item `scope` with the property `prop`, without the
+/// properties of nested items like the `name` of the `author`.
+fn properties<'a>(scope: &Node<'a>, prop: &str) -> Vec<Node<'a>> {
+    scope
+        .find(|node: &Node| has_itemprop(node, prop))
+        .filter(|node| {
+            std::iter::successors(node.parent(), Node::parent)
+                .find(|parent| parent.attr("itemscope").is_some())
+                .map(|item| item.index() == scope.index())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The text with collapsed whitespace, `None` if it's empty.
+fn normalize(text: &str) -> Option<String> {
+    Some(normalize_whitespace(text)).filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_ld_recipe_with_steps() {
+        let doc = Document::from(
+            r#"<html><head><script type="application/ld+json">{"@context": "https://schema.org", "@graph": [
+            {"@type": "WebPage", "name": "Harbour bakery"},
+            {"@type": "Recipe", "name": "Harbour bread", "recipeYield": ["1 loaf", "8 slices"],
+             "prepTime": "PT20M", "cookTime": "PT45M",
+             "recipeIngredient": ["500 g  flour", "350 ml water", "10 g salt"],
+             "recipeInstructions": [
+               {"@type": "HowToStep", "text": "Mix the flour, water and salt."},
+               {"@type": "HowToSection", "name": "Baking", "itemListElement": [
+                 {"@type": "HowToStep", "text": "Let the dough rise overnight."},
+                 {"@type": "HowToStep", "name": "Bake for 45 minutes."}]}]}]}</script>
+            </head><body><p>Our favourite bread.</p></body></html>"#,
+        );
+        let found = recipe(&doc).unwrap();
+        assert_eq!(found.name.as_deref(), Some("Harbour bread"));
+        assert_eq!(found.ingredients, vec!["500 g flour", "350 ml water", "10 g salt"]);
+        assert_eq!(
+            found.steps,
+            vec!["Mix the flour, water and salt.", "Let the dough rise overnight.", "Bake for 45 minutes."]
+        );
+        assert_eq!(found.total_time, Some(Duration::from_secs(65 * 60)));
+        assert_eq!(found.yield_.as_deref(), Some("1 loaf"));
+
+        let doc = Document::from(
+            r#"<script type="application/ld+json">{"@type": "Recipe", "totalTime": "PT1H",
+            "recipeInstructions": "Mix everything.\nBake for an hour.", "recipeYield": 4}</script>"#,
+        );
+        let found = recipe(&doc).unwrap();
+        assert_eq!(found.steps, vec!["Mix everything.", "Bake for an hour."]);
+        assert_eq!(found.total_time, Some(Duration::from_secs(3600)));
+        assert_eq!(found.yield_.as_deref(), Some("4"));
+    }
+
+    #[test]
+    fn microdata_recipe_with_steps() {
+        let doc = Document::from(
+            r#"<html><body><article itemscope itemtype="https://schema.org/Recipe">
+            <h1 itemprop="name">Harbour bread</h1>
+            <div itemprop="author" itemscope itemtype="https://schema.org/Person"><span itemprop="name">Jane Zhu</span></div>
+            <meta itemprop="totalTime" content="PT1H5M">
+            <span itemprop="recipeYield">1 loaf</span>
+            <ul><li itemprop="recipeIngredient">500 g flour</li><li itemprop="recipeIngredient">350 ml water</li></ul>
+            <ol itemprop="recipeInstructions"><li>Mix the flour and water.</li><li>Bake for 45 minutes.</li></ol>
+            </article></body></html>"#,
+        );
+        let found = recipe(&doc).unwrap();
+        assert_eq!(found.name.as_deref(), Some("Harbour bread"));
+        assert_eq!(found.ingredients, vec!["500 g flour", "350 ml water"]);
+        assert_eq!(found.steps, vec!["Mix the flour and water.", "Bake for 45 minutes."]);
+        assert_eq!(found.total_time, Some(Duration::from_secs(65 * 60)));
+        assert_eq!(found.yield_.as_deref(), Some("1 loaf"));
+
+        assert_eq!(recipe(&Document::from("<html><body><p>No recipe.</p></body></html>")), None);
+    }
+}
@@ -0,0 +1,143 @@
+use std::borrow::Cow;
+
+use url::Url;
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Attr, Name};
+
+use crate::extract_meta::meta_content;
+use crate::extract_title::title;
+use crate::text::normalize_whitespace;
+
+/// Url path segments that don't name a section.
+pub const GENERIC_SECTION_SEGMENTS: [&str; 16] = [
+    "news", "article", "articles", "story", "stories", "post", "posts", "content", "index", "amp",
+    "home", "en", "de", "fr", "es", "it",
+];
+
+/// Crumbs that only lead back to the front page.
+const HOME_CRUMBS: [&str; 4] = ["home", "homepage", "start", "startseite"];
+
+/// The section of the site the article belongs to.
+///
+/// Tries, in this order, the `article:section` meta property, the
+/// `<meta name="section">` tag, the deepest crumb of a
+/// `<nav aria-label="breadcrumb">` that isn't the article's title and
+/// finally the first path segment of the `base_url` that is neither generic
+/// like `news` nor part of a date.
+pub fn section<'a>(doc: &'a Document, base_url: Option<&Url>) -> Option<Cow<'a, str>> {
+    meta_content(doc, Attr("property", "article:section"))
+        .or_else(|| meta_content(doc, Attr("name", "section")))
+        .or_else(|| breadcrumb_section(doc).map(Cow::Owned))
+        .or_else(|| base_url.and_then(url_section).map(Cow::Owned))
+}
+
+/// The deepest crumb of the breadcrumb navigation that isn't the title of
+/// the article.
+fn breadcrumb_section(doc: &Document) -> Option<String> {
+    let nav = doc.find(Name("nav")).find(|nav| {
+        nav.attr("aria-label")
+            .map(|label| label.to_lowercase().contains("breadcrumb"))
+            .unwrap_or_default()
+    })?;
+    let title = title(doc).map(|title| normalize_crumb(&title));
+    let items: Vec<Node> = nav.find(Name("li")).collect();
+    let crumbs = if items.is_empty() {
+        nav.find(Name("a")).collect()
+    } else {
+        items
+    };
+    crumbs
+        .into_iter()
+        .rev()
+        .filter(|crumb| crumb.attr("aria-current").is_none())
+        .map(|crumb| normalize_crumb(&crumb.text()))
+        .filter(|crumb| !crumb.is_empty())
+        .filter(|crumb| !HOME_CRUMBS.contains(&crumb.to_lowercase().as_str()))
+        .find(|crumb| {
+            title
+                .as_deref()
+                .map(|title| !title.eq_ignore_ascii_case(crumb))
+                .unwrap_or(true)
+        })
+}
+
+/// The first path segment of the `url` that names a section, title cased.
+///
+/// The last segment is the article's slug and never a section.
+fn url_section(url: &Url) -> Option<String> {
+    let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+    let (_, sections) = segments.split_last()?;
+    sections
+        .iter()
+        .map(|segment| segment.to_lowercase())
+        .find(|segment| {
+            !GENERIC_SECTION_SEGMENTS.contains(&segment.as_str())
+                && !segment.contains('.')
+                && segment.chars().any(|c| c.is_alphabetic())
+                && !segment.starts_with(|c: char| c.is_ascii_digit())
+        })
+        .map(|segment| {
+            segment
+                .split(['-', '_'])
+                .filter(|word| !word.is_empty())
+                .map(capitalize)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Collapse all whitespace and strip the separators some sites put into the
+/// crumbs.
+pub(crate) fn normalize_crumb(text: &str) -> String {
+    normalize_whitespace(text)
+        .trim_matches(|c: char| matches!(c, '>' | '/' | '»' | '›' | '|') || c.is_whitespace())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_sources() {
+        let url = Url::parse("https://example.com/news/politics/2024/05/14/harbour-expansion.html").unwrap();
+
+        let doc = Document::from(
+            r#"<html><head><meta property="article:section" content="Local">
+            <meta name="section" content="Politics"></head><body></body></html>"#,
+        );
+        assert_eq!(section(&doc, Some(&url)).as_deref(), Some("Local"));
+
+        let doc = Document::from(
+            r#"<html><head><meta name="section" content="Politics"></head><body></body></html>"#,
+        );
+        assert_eq!(section(&doc, Some(&url)).as_deref(), Some("Politics"));
+
+        let doc = Document::from(
+            r#"<html><head><title>Harbour expansion approved</title></head><body>
+            <nav aria-label="Breadcrumb"><ol>
+            <li><a href="/">Home</a></li>
+            <li><a href="/world">World</a> ›</li>
+            <li><a href="/world/europe">Europe</a> ›</li>
+            <li>Harbour expansion approved</li>
+            </ol></nav></body></html>"#,
+        );
+        assert_eq!(section(&doc, Some(&url)).as_deref(), Some("Europe"));
+
+        let doc = Document::from("<html><head></head><body></body></html>");
+        assert_eq!(section(&doc, Some(&url)).as_deref(), Some("Politics"));
+        let url = Url::parse("https://example.com/2024/05/local-news/harbour-expansion/").unwrap();
+        assert_eq!(section(&doc, Some(&url)).as_deref(), Some("Local News"));
+        let url = Url::parse("https://example.com/news/harbour-expansion.html").unwrap();
+        assert_eq!(section(&doc, Some(&url)), None);
+    }
+}
@@ -1,11 +1,13 @@
 use select::document::Document;
 use select::predicate::{Attr};
 use url::Url;
+use crate::extract_base_url::resolve_base;
 use crate::extract_meta::meta_content;
 
 /// Extract the thumbnail for the article.
 pub fn meta_thumbnail_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
-    let options = Url::options().base_url(base_url);
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
     [("name", "thumbnail"), ("name", "thumbnailUrl")]
         .iter()
         .filter_map(|(k, v)| meta_content(doc, Attr(k, v)))
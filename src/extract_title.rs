@@ -1,7 +1,11 @@
 use std::borrow::Cow;
 use select::document::Document;
+use select::node::Node;
 use select::predicate::{Attr, Name};
+use crate::extract_canonical::canonical_link;
+use crate::public_suffix::registrable_domain;
 use crate::extract_meta::meta_content;
+use crate::extract_microdata::microdata_value;
 
 const MOTLEY_REPLACEMENT: (&str, &str) = ("&#65533;", "");
 const TITLE_REPLACEMENTS: (&str, &str) = ("&raquo;", "»");
@@ -16,25 +20,50 @@ const TITLE_META_INFO: [&str; 8] = [
     "twitter:title",
 ];
 
+/// Delimiters between the headline and the name of the site or section in a
+/// title with normalized whitespace, like `Storm hits coast | Example`.
+const TITLE_DELIMITERS: [&str; 9] = [" | ", " - ", " – ", " — ", " _ ", " / ", " » ", " :: ", " · "];
+
+/// The title of the article, see [`title_with_site_name`] with the
+/// `og:site_name` of the document.
 pub fn title<'a>(doc: &'a Document) -> Option<Cow<'a, str>> {
+    title_with_site_name(doc, None)
+}
+
+/// The title of the article, without the name of the site.
+///
+/// A leading or trailing segment of the title is stripped if it matches the
+/// `site_name`, the `og:site_name` if `None`, or the registrable domain of
+/// the canonical url, like `Example News` in `Storm hits coast - Example
+/// News`. Other segments stay, so the dashes of `What we know - and don't -
+/// about the outage` are kept, unless a `<title>` starts with the `og:title`
+/// followed by a delimiter.
+pub fn title_with_site_name<'a>(doc: &'a Document, site_name: Option<&str>) -> Option<Cow<'a, str>> {
+    let site_names = site_names(doc, site_name);
+
     // 1. Try og:title/twitter:title first
     for meta_name in &TITLE_META_INFO {
         if let Some(meta) = meta_content(doc, Attr("property", meta_name)) {
             let t = meta.trim();
             if !t.is_empty() {
-                return Some(Cow::Owned(postprocess_title(t)));
+                return Some(Cow::Owned(postprocess_title(&strip_site_name(t, &site_names))));
             }
         }
         if let Some(meta) = meta_content(doc, Attr("name", meta_name)) {
             let t = meta.trim();
             if !t.is_empty() {
-                return Some(Cow::Owned(postprocess_title(t)));
+                return Some(Cow::Owned(postprocess_title(&strip_site_name(t, &site_names))));
             }
         }
     }
 
+    // 1b. Try the schema.org `headline` microdata
+    if let Some(headline) = microdata_value(doc, "headline") {
+        return Some(Cow::Owned(postprocess_title(&headline)));
+    }
+
     // 2. Try <h1> (longest, >2 words)
-    let h1_list: Vec<String> = doc.find(Name("h1")).filter_map(|n| n.as_text().map(|s| s.trim().to_string())).collect();
+    let h1_list: Vec<String> = doc.find(Name("h1")).filter_map(|n| node_text(&n)).collect();
     if !h1_list.is_empty() {
         let mut sorted = h1_list.clone();
         sorted.sort_by_key(|s| s.len());
@@ -46,16 +75,13 @@ pub fn title<'a>(doc: &'a Document) -> Option<Cow<'a, str>> {
     }
 
     // 3. Try <title>
-    if let Some(title_tag) = doc.find(Name("title")).next().and_then(|n| n.as_text().map(str::trim)) {
-        let t = title_tag.trim();
-        if !t.is_empty() {
-            return Some(Cow::Owned(postprocess_title(t)));
-        }
+    if let Some(title_tag) = doc.find(Name("title")).next().and_then(|n| node_text(&n)) {
+        return Some(Cow::Owned(postprocess_title(&split_title(&title_tag, &site_names, &og_title(doc)))));
     }
 
     // 4. Advanced heuristics fallback
     // Re-extract <h1> and <title> for heuristics
-    let h1_list: Vec<String> = doc.find(Name("h1")).filter_map(|n| n.as_text().map(|s| s.trim().to_string())).collect();
+    let h1_list: Vec<String> = doc.find(Name("h1")).filter_map(|n| node_text(&n)).collect();
     let mut title_text_h1 = String::new();
     if !h1_list.is_empty() {
         let mut sorted = h1_list.clone();
@@ -64,8 +90,8 @@ pub fn title<'a>(doc: &'a Document) -> Option<Cow<'a, str>> {
         title_text_h1 = longest.clone();
     }
 
-    let title_tag = doc.find(Name("title")).next().and_then(|n| n.as_text().map(str::trim));
-    let mut title_text = title_tag.unwrap_or("").to_string();
+    let title_tag = doc.find(Name("title")).next().and_then(|n| node_text(&n));
+    let mut title_text = title_tag.unwrap_or_default();
 
     // Get og:title/twitter:title for fallback
     let mut title_text_fb = String::new();
@@ -108,27 +134,7 @@ pub fn title<'a>(doc: &'a Document) -> Option<Cow<'a, str>> {
 
     // Delimiter splitting
     if candidate.is_empty() && !title_text.is_empty() {
-        for delimiter in ["|", "-", "_", "/", " » "] {
-            if title_text.contains(delimiter) {
-                let pieces: Vec<&str> = title_text.split(delimiter).collect();
-                let mut large_text_length = 0;
-                let mut large_text_index = 0;
-                let hint = filter(&title_text_h1);
-                for (i, piece) in pieces.iter().enumerate() {
-                    let current = piece.trim();
-                    if !hint.is_empty() && filter(current).contains(&hint) {
-                        large_text_index = i;
-                        break;
-                    }
-                    if current.len() > large_text_length {
-                        large_text_length = current.len();
-                        large_text_index = i;
-                    }
-                }
-                candidate = pieces[large_text_index].trim().to_string();
-                break;
-            }
-        }
+        candidate = split_title(&title_text, &site_names, &og_title(doc));
     }
 
     // Final filter: prefer h1 if similar
@@ -143,8 +149,160 @@ pub fn title<'a>(doc: &'a Document) -> Option<Cow<'a, str>> {
     None
 }
 
+/// The names the site may appear under in a title: the `site_name` or the
+/// `og:site_name`, and the registrable domain of the canonical url with and
+/// without its public suffix, like `example.com` and `example`.
+fn site_names(doc: &Document, site_name: Option<&str>) -> Vec<String> {
+    let mut names = Vec::new();
+    let site_name = site_name
+        .map(str::to_string)
+        .or_else(|| meta_content(doc, Attr("property", "og:site_name")).map(|name| name.to_string()));
+    names.extend(site_name);
+    if let Some(host) = canonical_link(doc, None).as_ref().and_then(|url| url.host_str()) {
+        let domain = registrable_domain(host);
+        names.push(domain.to_string());
+        names.extend(domain.split('.').next().map(str::to_string));
+    }
+    names.retain(|name| !filter_title(name).is_empty());
+    names
+}
+
+/// Lowercase alphanumeric chars of the title, for comparisons.
+fn filter_title(title: &str) -> String {
+    title.chars().filter(|c| c.is_alphanumeric()).collect::<String>().to_lowercase()
+}
+
+/// Strip leading and trailing segments of the `title` that match one of the
+/// `site_names`, like `Example News` in `Storm hits coast - Example News`.
+fn strip_site_name(title: &str, site_names: &[String]) -> String {
+    let is_site_name = |segment: &str| {
+        let segment = filter_title(segment);
+        !segment.is_empty() && site_names.iter().any(|name| filter_title(name) == segment)
+    };
+    let mut title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    'strip: loop {
+        for delimiter in TITLE_DELIMITERS {
+            if let Some((rest, last)) = title.rsplit_once(delimiter)
+                && is_site_name(last)
+                && !filter_title(rest).is_empty()
+            {
+                title = rest.trim().to_string();
+                continue 'strip;
+            }
+            if let Some((first, rest)) = title.split_once(delimiter)
+                && is_site_name(first)
+                && !filter_title(rest).is_empty()
+            {
+                title = rest.trim().to_string();
+                continue 'strip;
+            }
+        }
+        return title;
+    }
+}
+
+/// The `og:title` with normalized whitespace, or an empty string.
+fn og_title(doc: &Document) -> String {
+    meta_content(doc, Attr("property", "og:title"))
+        .map(|title| title.split_whitespace().collect::<Vec<_>>().join(" "))
+        .unwrap_or_default()
+}
+
+/// Strip the site name of the `title`. The rest is only cut after the
+/// `og_title` if it starts with exactly the `og_title` followed by a
+/// delimiter, like `Storm hits coast` in `Storm hits coast - Live`.
+fn split_title(title: &str, site_names: &[String], og_title: &str) -> String {
+    let title = strip_site_name(title, site_names);
+    let og_title = strip_site_name(og_title, site_names);
+    if !og_title.is_empty()
+        && let Some(rest) = title.strip_prefix(og_title.as_str())
+        && TITLE_DELIMITERS.iter().any(|delimiter| rest.starts_with(delimiter))
+    {
+        return og_title;
+    }
+    title
+}
+
+/// The whitespace normalized text of the node and all its descendants, so
+/// nested markup like `<h1><span>Title</span></h1>` is included.
+fn node_text(node: &Node) -> Option<String> {
+    let text = node.text().split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
 fn postprocess_title(title: &str) -> String {
     let mut t = title.replace(MOTLEY_REPLACEMENT.0, MOTLEY_REPLACEMENT.1);
     t = t.replace(TITLE_REPLACEMENTS.0, TITLE_REPLACEMENTS.1);
     t.trim().to_string()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_h1() {
+        let doc = Document::from(
+            r#"<html><head><title>Site</title></head><body>
+            <h1><span class="headline">Bread &amp; Butter <a href="/x">prices</a> rise</span><!-- x --></h1>
+            </body></html>"#,
+        );
+        assert_eq!(title(&doc).as_deref(), Some("Bread & Butter prices rise"));
+    }
+
+    #[test]
+    fn nested_title_tag() {
+        let doc = Document::from(
+            r#"<html><head><title>
+                Rates   &amp; Bonds
+            </title></head><body><h1><em>Markets</em></h1></body></html>"#,
+        );
+        assert_eq!(title(&doc).as_deref(), Some("Rates & Bonds"));
+    }
+
+    #[test]
+    fn site_name_suffix() {
+        // only the site name is stripped, `Live` isn't one
+        let doc = Document::from(include_str!("../tests/fixtures/titles/live_prefix.html"));
+        assert_eq!(title(&doc).as_deref(), Some("Live | Storm hits coast"));
+        let doc = Document::from(r#"<html><head><title>Mayor resigns - a city in shocked state</title></head></html>"#);
+        assert_eq!(title(&doc).as_deref(), Some("Mayor resigns - a city in shocked state"));
+
+        // the dashes are part of the headline, the domain is the site name
+        let doc = Document::from(include_str!("../tests/fixtures/titles/delimiter_in_headline.html"));
+        assert_eq!(
+            title(&doc).as_deref(),
+            Some("Q&A: What we know - and don't - about the outage")
+        );
+    }
+
+    #[test]
+    fn site_name_of_meta_title() {
+        let doc = Document::from(
+            r#"<html><head><meta property="og:title" content="Example News - Storm hits coast - Live">
+            </head><body></body></html>"#,
+        );
+        assert_eq!(title(&doc).as_deref(), Some("Example News - Storm hits coast - Live"));
+        assert_eq!(
+            title_with_site_name(&doc, Some("example news")).as_deref(),
+            Some("Storm hits coast - Live")
+        );
+
+        let site_names = vec!["Example News".to_string()];
+        assert_eq!(
+            split_title("Storm hits coast - Live | Example News", &site_names, "Storm hits coast | Example News"),
+            "Storm hits coast"
+        );
+        assert_eq!(
+            split_title("Storm hits coastline - Live | Example News", &site_names, "Storm hits coast"),
+            "Storm hits coastline - Live"
+        );
+
+        let doc = Document::from(r#"<html><head><title>Covid-19 cases rise in the north</title></head></html>"#);
+        assert_eq!(title(&doc).as_deref(), Some("Covid-19 cases rise in the north"));
+    }
+}
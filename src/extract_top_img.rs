@@ -1,15 +1,170 @@
+use std::cmp::Reverse;
+use std::sync::Arc;
+
 use select::document::Document;
+use select::node::Node;
 use select::predicate::{Attr, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
 use url::Url;
-use crate::extract_meta::meta_content;
+use crate::extract_base_url::resolve_base;
+use crate::extract_meta::head_meta;
+use crate::extract_microdata::microdata_value;
+use crate::clean::ExtractionFilters;
+use crate::extract_node::article_node_adaptive_with_filters;
+use crate::image::parse_dimension;
+use crate::text::{image_predicate, image_src, TextContainer};
+use crate::Language;
 
-/// Extract the 'top img' as specified by the website.
-pub fn meta_img_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
-    let options = Url::options().base_url(base_url);
-    if let Some(meta) = meta_content(doc, Attr("property", "og:image")) {
-        if let Ok(url) = options.parse(&*meta) {
-            return Some(url);
+/// Images with a width or height below this number of pixels are not
+/// considered as top image.
+pub const MIN_TOP_IMAGE_DIMENSION: u32 = 200;
+
+/// Score of the first image in the article node, divided by the position for
+/// the following images.
+pub const TOP_IMAGE_POSITION_WEIGHT: f64 = 20.0;
+
+/// Score of an image whose area reaches [`TOP_IMAGE_REFERENCE_AREA`].
+pub const TOP_IMAGE_AREA_WEIGHT: f64 = 30.0;
+
+/// Area in pixels from which larger images don't score any higher.
+pub const TOP_IMAGE_REFERENCE_AREA: f64 = 640.0 * 360.0;
+
+/// Share of [`TOP_IMAGE_AREA_WEIGHT`] for images without `width` and
+/// `height` attributes.
+pub const TOP_IMAGE_UNKNOWN_AREA_SHARE: f64 = 0.5;
+
+/// Penalty for images inside a `<nav>`, `<header>`, `<footer>` or `<aside>`.
+pub const TOP_IMAGE_NAV_PENALTY: f64 = 40.0;
+
+/// Parts of an image's file name that indicate a logo or sprite.
+const LOGO_FILE_NAME_PARTS: [&str; 3] = ["logo", "sprite", "icon"];
+
+/// An `og:image` together with its structured properties.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct OgImage {
+    /// The `og:image:secure_url`, or the `og:image` if there is none.
+    pub url: Url,
+    /// The `og:image:width` in pixels.
+    pub width: Option<u32>,
+    /// The `og:image:height` in pixels.
+    pub height: Option<u32>,
+    /// The `og:image:alt`.
+    pub alt: Option<String>,
+}
+
+impl OgImage {
+    /// The declared area in pixels, if both the width and height are
+    /// declared.
+    pub fn area(&self) -> Option<u64> {
+        Some(u64::from(self.width?) * u64::from(self.height?))
+    }
+}
+
+/// The structured properties of an `og:image` before its urls are resolved.
+#[derive(Default)]
+struct OgImageProperties<'a> {
+    url: &'a str,
+    secure_url: Option<&'a str>,
+    width: Option<u32>,
+    height: Option<u32>,
+    alt: Option<&'a str>,
+}
+
+/// All `og:image`s of the document in order.
+///
+/// Like the Open Graph protocol specifies, the structured properties like
+/// `og:image:width` belong to the `og:image` declared before them, so
+/// properties before the first `og:image` are ignored. The
+/// `og:image:secure_url` is preferred over the `og:image`, and an
+/// `og:image:url` that repeats the `og:image` is the same image.
+pub fn og_images(doc: &Document, base_url: Option<&Url>) -> Vec<OgImage> {
+    let mut groups: Vec<OgImageProperties> = Vec::new();
+    for meta in head_meta(doc) {
+        let (Some(property), Some(content)) = (
+            meta.attr("property").or_else(|| meta.attr("name")),
+            meta.attr("content").map(str::trim).filter(|content| !content.is_empty()),
+        ) else {
+            continue;
+        };
+        let property = property.trim().to_lowercase();
+        let Some(property) = property.strip_prefix("og:image") else {
+            continue;
+        };
+        if property.is_empty() || property == ":url" {
+            let repeated = property == ":url" && groups.last().map(|group| group.url) == Some(content);
+            if !repeated {
+                groups.push(OgImageProperties {
+                    url: content,
+                    ..Default::default()
+                });
+            }
+            continue;
+        }
+        let Some(group) = groups.last_mut() else {
+            continue;
+        };
+        match property {
+            ":secure_url" => group.secure_url = Some(content),
+            ":width" => group.width = parse_dimension(content),
+            ":height" => group.height = parse_dimension(content),
+            ":alt" => group.alt = Some(content),
+            _ => {}
+        }
+    }
+
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    let mut images: Vec<OgImage> = Vec::with_capacity(groups.len());
+    for group in groups {
+        let Some(url) = group
+            .secure_url
+            .and_then(|secure_url| options.parse(secure_url).ok())
+            .or_else(|| options.parse(group.url).ok())
+        else {
+            continue;
+        };
+        if images.iter().any(|image| image.url == url) {
+            continue;
         }
+        images.push(OgImage {
+            url,
+            width: group.width,
+            height: group.height,
+            alt: group.alt.map(str::to_string),
+        });
+    }
+    images
+}
+
+/// The `og:image` with the largest declared area, the first one if none
+/// declares its size.
+pub fn largest_og_image(doc: &Document, base_url: Option<&Url>) -> Option<OgImage> {
+    og_images(doc, base_url)
+        .into_iter()
+        .enumerate()
+        .max_by_key(|(position, image)| (image.area(), Reverse(*position)))
+        .map(|(_, image)| image)
+}
+
+/// Extract the 'top img' as specified by the website: the `og:image` with
+/// the largest declared area, see [`largest_og_image`], the schema.org
+/// `image` microdata or an image `<link>`.
+pub fn meta_img_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+    largest_og_image(doc, base_url)
+        .map(|image| image.url)
+        .or_else(|| linked_img_url(doc, base_url))
+}
+
+/// The schema.org `image` microdata or an image `<link>`.
+fn linked_img_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    if let Some(image) = microdata_value(doc, "image")
+        && let Ok(url) = options.parse(image.trim())
+    {
+        return Some(url);
     }
     doc.find(
         Name("link").and(
@@ -21,4 +176,208 @@ pub fn meta_img_url(doc: &Document, base_url: Option<&Url>) -> Option<Url> {
     .filter_map(|node| node.attr("href"))
     .filter_map(|href| options.parse(href).ok())
     .next()
-}
\ No newline at end of file
+}
+
+/// Select the article's top image.
+///
+/// The `og:image` is used unless it is missing or looks like a logo, by its
+/// file name or its `og:image:width` and `og:image:height`. Otherwise the
+/// images of the article node are scored by their position, their size and
+/// whether they are part of the navigation, and the best one is returned.
+/// If there is no candidate in the article node, this falls back to
+/// [`meta_img_url`].
+pub fn top_image(doc: &Document, base_url: Option<&Url>, lang: Language) -> Option<Url> {
+    top_image_with_filters(doc, base_url, lang, None)
+}
+
+/// Like [`top_image`], but the images are scored in the article node
+/// selected with the `filters`, and the images they deny are skipped.
+pub(crate) fn top_image_with_filters(
+    doc: &Document,
+    base_url: Option<&Url>,
+    lang: Language,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Option<Url> {
+    // the og:images are only extracted once, the linked image has no size
+    let og_image = largest_og_image(doc, base_url);
+    let too_small = og_image
+        .as_ref()
+        .map(|image| {
+            [image.width, image.height]
+                .iter()
+                .flatten()
+                .any(|dimension| *dimension < MIN_TOP_IMAGE_DIMENSION)
+        })
+        .unwrap_or_default();
+    let meta_img = og_image
+        .map(|image| image.url)
+        .or_else(|| linked_img_url(doc, base_url));
+    if let Some(img) = &meta_img
+        && !too_small
+        && !looks_like_logo(img)
+    {
+        return meta_img;
+    }
+
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    article_node_adaptive_with_filters(doc, lang, filters)
+        .and_then(|(node, _)| {
+            node.find(image_predicate())
+                .filter(|img| !img.is_noise_node())
+                .filter(|img| !filters.map(|filters| filters.denies_node_or_ancestor(img)).unwrap_or_default())
+                .filter_map(|img| Some((options.parse(image_src(&img)?).ok()?, img)))
+                .filter(|(url, img)| !looks_like_logo(url) && !is_too_small(img))
+                .enumerate()
+                .map(|(position, (url, img))| (score_image(&img, position), url))
+                .max_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, url)| url)
+        })
+        .or(meta_img)
+}
+
+/// Whether the file name of the image url indicates a logo or a sprite.
+pub fn looks_like_logo(url: &Url) -> bool {
+    let file_name = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .unwrap_or_default()
+        .to_lowercase();
+    LOGO_FILE_NAME_PARTS
+        .iter()
+        .any(|part| file_name.contains(part))
+}
+
+/// The `width` or `height` attribute of the image in pixels.
+fn dimension(img: &Node, attr: &str) -> Option<u32> {
+    parse_dimension(img.attr(attr)?)
+}
+
+fn is_too_small(img: &Node) -> bool {
+    [dimension(img, "width"), dimension(img, "height")]
+        .iter()
+        .flatten()
+        .any(|dimension| *dimension < MIN_TOP_IMAGE_DIMENSION)
+}
+
+/// Scores the image at the `position` of the article's images.
+fn score_image(img: &Node, position: usize) -> f64 {
+    let mut score = TOP_IMAGE_POSITION_WEIGHT / (position + 1) as f64;
+
+    score += match (dimension(img, "width"), dimension(img, "height")) {
+        (Some(width), Some(height)) => {
+            let area = f64::from(width) * f64::from(height);
+            TOP_IMAGE_AREA_WEIGHT * (area / TOP_IMAGE_REFERENCE_AREA).min(1.0)
+        }
+        _ => TOP_IMAGE_AREA_WEIGHT * TOP_IMAGE_UNKNOWN_AREA_SHARE,
+    };
+
+    let mut parent = img.parent();
+    while let Some(node) = parent {
+        if Name("nav")
+            .or(Name("header"))
+            .or(Name("footer"))
+            .or(Name("aside"))
+            .matches(&node)
+        {
+            score -= TOP_IMAGE_NAV_PENALTY;
+            break;
+        }
+        parent = node.parent();
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = r#"<div itemprop="articleBody">
+        <nav><img src="/img/section.jpg" width="800" height="400"></nav>
+        <p>The harbour will be expanded over the next ten years, the city council decided.</p>
+        <img src="/img/share-icon.png" width="800" height="400">
+        <img src="/img/avatar.jpg" width="48" height="48">
+        <img src="/img/harbour.jpg" width="800" height="450">
+        <img src="/img/map.jpg" width="1200" height="800">
+        </div>"#;
+
+    fn doc(head: &str) -> Document {
+        Document::from(format!("<html><head>{}</head><body>{}</body></html>", head, BODY).as_str())
+    }
+
+    #[test]
+    fn og_image() {
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let doc = doc(r#"<meta property="og:image" content="/img/share.jpg">"#);
+        assert_eq!(
+            top_image(&doc, Some(&base_url), Language::English).unwrap().as_str(),
+            "https://example.com/img/share.jpg"
+        );
+    }
+
+    #[test]
+    fn og_images_with_their_properties() {
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let document = doc(r#"<meta property="og:image:width" content="999">
+            <meta property="og:image" content="http://example.com/img/square.jpg">
+            <meta property="og:image:secure_url" content="https://example.com/img/square.jpg">
+            <meta property="og:image:width" content="600">
+            <meta property="og:image:height" content="600">
+            <meta property="og:image" content="/img/wide.jpg">
+            <meta property="og:image:url" content="/img/wide.jpg">
+            <meta property="og:image:width" content="1200">
+            <meta property="og:image:height" content="630">
+            <meta property="og:image:alt" content="The new quay of the harbour">
+            <meta property="og:image" content="/img/unsized.jpg">"#);
+        assert_eq!(
+            og_images(&document, Some(&base_url)),
+            vec![
+                OgImage {
+                    url: Url::parse("https://example.com/img/square.jpg").unwrap(),
+                    width: Some(600),
+                    height: Some(600),
+                    alt: None,
+                },
+                OgImage {
+                    url: Url::parse("https://example.com/img/wide.jpg").unwrap(),
+                    width: Some(1200),
+                    height: Some(630),
+                    alt: Some("The new quay of the harbour".to_string()),
+                },
+                OgImage {
+                    url: Url::parse("https://example.com/img/unsized.jpg").unwrap(),
+                    width: None,
+                    height: None,
+                    alt: None,
+                },
+            ]
+        );
+        assert_eq!(
+            meta_img_url(&document, Some(&base_url)).unwrap().as_str(),
+            "https://example.com/img/wide.jpg"
+        );
+
+        let document = doc(r#"<meta property="og:image" content="/img/first.jpg">
+            <meta property="og:image" content="/img/second.jpg">"#);
+        assert_eq!(
+            meta_img_url(&document, Some(&base_url)).unwrap().as_str(),
+            "https://example.com/img/first.jpg"
+        );
+    }
+
+    #[test]
+    fn scores_body_images_for_logo() {
+        let base_url = Url::parse("https://example.com/").unwrap();
+        for head in [
+            r#"<meta property="og:image" content="/img/site-logo.png">"#,
+            r#"<meta property="og:image" content="/img/share.jpg">
+               <meta property="og:image:width" content="120">"#,
+            "",
+        ] {
+            assert_eq!(
+                top_image(&doc(head), Some(&base_url), Language::English).unwrap().as_str(),
+                "https://example.com/img/harbour.jpg"
+            );
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+
+use url::Url;
+use select::document::Document;
+use select::predicate::Attr;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::extract_base_url::resolve_base;
+use crate::extract_meta::meta_content;
+
+/// The Twitter Card of the page, the preview shown when it's shared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct TwitterCard {
+    /// The type of the card, like `summary_large_image`.
+    pub card: Option<String>,
+    /// The handle of the site, like `@example`.
+    pub site: Option<String>,
+    /// The handle of the author, like `@janedoe`.
+    pub creator: Option<String>,
+    pub image: Option<Url>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+/// The `twitter:*` metadata of the document, `None` if it has none.
+///
+/// The properties are read from `<meta name>` and `<meta property>`, both
+/// spellings occur. The image, `twitter:image` or the older
+/// `twitter:image:src`, is resolved against the `<base>` of the document or
+/// the `base_url`.
+pub fn twitter_card(doc: &Document, base_url: Option<&Url>) -> Option<TwitterCard> {
+    let text = |name| twitter_meta(doc, name).map(Cow::into_owned);
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
+    let card = TwitterCard {
+        card: text("twitter:card"),
+        site: text("twitter:site"),
+        creator: text("twitter:creator"),
+        image: ["twitter:image", "twitter:image:src"]
+            .into_iter()
+            .filter_map(|name| twitter_meta(doc, name))
+            .find_map(|image| options.parse(&image).ok()),
+        title: text("twitter:title"),
+        description: text("twitter:description"),
+    };
+    (card != TwitterCard::default()).then_some(card)
+}
+
+/// The content of the `twitter:*` meta tag with the `name` or `property`.
+pub(crate) fn twitter_meta<'a>(doc: &'a Document, name: &str) -> Option<Cow<'a, str>> {
+    meta_content(doc, Attr("name", name)).or_else(|| meta_content(doc, Attr("property", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_and_property_spellings() {
+        let doc = Document::from(
+            r#"<html><head>
+            <meta name="twitter:card" content="summary_large_image">
+            <meta property="twitter:site" content="@example">
+            <meta name="twitter:creator" content="@janedoe">
+            <meta property="twitter:image:src" content="/img/harbour.jpg">
+            <meta name="twitter:title" content=" Harbour expansion approved ">
+            <meta property="twitter:description" content="The council approved the expansion.">
+            </head><body></body></html>"#,
+        );
+        let base = Url::parse("https://example.com/news/harbour.html").unwrap();
+        assert_eq!(
+            twitter_card(&doc, Some(&base)),
+            Some(TwitterCard {
+                card: Some("summary_large_image".to_string()),
+                site: Some("@example".to_string()),
+                creator: Some("@janedoe".to_string()),
+                image: Some(Url::parse("https://example.com/img/harbour.jpg").unwrap()),
+                title: Some("Harbour expansion approved".to_string()),
+                description: Some("The council approved the expansion.".to_string()),
+            })
+        );
+        assert_eq!(twitter_card(&Document::from("<html><head></head></html>"), Some(&base)), None);
+    }
+}
@@ -3,6 +3,7 @@ use std::collections::HashSet;
 use url::Url;
 use select::document::Document;
 use select::predicate::{Name};
+use crate::extract_base_url::resolve_base;
 
 /// Extract the `href` attribute for all `<a>` tags of the document.
 pub fn all_urls<'a>(doc: &'a Document) -> Vec<Cow<'a, str>> {
@@ -16,10 +17,11 @@ pub fn all_urls<'a>(doc: &'a Document) -> Vec<Cow<'a, str>> {
 
 /// Extract all of the images of the document.
 pub fn image_urls(doc: &Document, base_url: Option<&Url>) -> Vec<Url> {
-    let options = Url::options().base_url(base_url);
+    let base = resolve_base(doc, base_url);
+    let options = Url::options().base_url(base.as_ref());
     // TODO extract `picture` and source media
     doc.find(Name("img"))
-        .filter_map(|n| n.attr("href").map(str::trim))
+        .filter_map(|n| n.attr("src").map(str::trim))
         .filter_map(|url| options.parse(url).ok())
         .collect()
 }
\ No newline at end of file
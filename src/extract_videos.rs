@@ -1,13 +1,45 @@
+use std::sync::Arc;
+
+use url::Url;
 use select::document::Document;
 use crate::Language;
-use crate::video::VideoNode;
-use crate::extract_node::article_node;
+use crate::clean::ExtractionFilters;
+use crate::extract_base_url::resolve_base;
+use crate::video::{Video, VideoNode};
+use crate::extract_node::article_node_adaptive_with_filters;
 
 /// All video content in the article.
 pub fn videos<'a>(doc: &'a Document, lang: Option<Language>) -> Vec<VideoNode<'a>> {
-    if let Some(node) = article_node(doc, lang.unwrap_or_default()) {
-        node.videos()
-    } else {
-        Vec::new()
-    }
+    videos_with_filters(doc, lang, None)
+}
+
+/// Like [`videos`], of the article node selected with the `filters`, so the
+/// videos belong to the node of the text.
+pub(crate) fn videos_with_filters<'a>(
+    doc: &'a Document,
+    lang: Option<Language>,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Vec<VideoNode<'a>> {
+    article_node_adaptive_with_filters(doc, lang.unwrap_or_default(), filters)
+        .map(|(node, _)| node.videos())
+        .unwrap_or_default()
+}
+
+/// All videos in the article with their provider, id, poster and
+/// dimensions.
+pub fn videos_detailed(doc: &Document, base_url: Option<&Url>, lang: Option<Language>) -> Vec<Video> {
+    videos_detailed_with_filters(doc, base_url, lang, None)
+}
+
+/// Like [`videos_detailed`], of the article node selected with the
+/// `filters`.
+pub(crate) fn videos_detailed_with_filters(
+    doc: &Document,
+    base_url: Option<&Url>,
+    lang: Option<Language>,
+    filters: Option<&Arc<ExtractionFilters>>,
+) -> Vec<Video> {
+    article_node_adaptive_with_filters(doc, lang.unwrap_or_default(), filters)
+        .map(|(node, _)| node.videos_detailed(resolve_base(doc, base_url).as_ref()))
+        .unwrap_or_default()
 }
@@ -0,0 +1,128 @@
+//! Metadata of the HTTP response a document was downloaded with, for the
+//! provenance of articles.
+
+use chrono::{DateTime, Utc};
+use mime::Mime;
+use http::header::{HeaderMap, CONTENT_TYPE};
+use http::StatusCode;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The response an article was downloaded with.
+///
+/// Articles extracted from local html, like with [`crate::Article::new`],
+/// have none. With the `serde` feature only the `final_url`, `status`,
+/// `fetched_at` and `redirect_chain` are serialized.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct FetchInfo {
+    /// The url of the response, after following redirects.
+    pub final_url: Url,
+    /// The status of the response.
+    #[cfg_attr(feature = "serde0", serde(with = "status_code"))]
+    pub status: StatusCode,
+    /// The media type of the `Content-Type` header.
+    #[cfg_attr(feature = "serde0", serde(skip))]
+    pub content_type: Option<Mime>,
+    /// When the response was received.
+    pub fetched_at: DateTime<Utc>,
+    /// The headers of the response.
+    #[cfg_attr(feature = "serde0", serde(skip))]
+    pub headers: HeaderMap,
+    /// The urls of the stub pages that redirected to the `final_url` with a
+    /// `<meta http-equiv="refresh">`, in the order they were downloaded.
+    #[cfg_attr(feature = "serde0", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub redirect_chain: Vec<Url>,
+}
+
+impl FetchInfo {
+    /// A response from the `final_url` with the `status` and `headers`,
+    /// received now.
+    pub fn new(final_url: Url, status: StatusCode, headers: HeaderMap) -> Self {
+        let content_type = headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+        Self {
+            final_url,
+            status,
+            content_type,
+            fetched_at: Utc::now(),
+            headers,
+            redirect_chain: Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "net")]
+    pub(crate) fn from_response(response: &reqwest::Response) -> Self {
+        Self::new(response.url().clone(), response.status(), response.headers().clone())
+    }
+
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub(crate) fn from_blocking_response(response: &reqwest::blocking::Response) -> Self {
+        Self::new(response.url().clone(), response.status(), response.headers().clone())
+    }
+
+    /// The `target` of the refresh of this stub page, with the stub appended
+    /// to the `redirect_chain`.
+    pub(crate) fn refreshed(self, mut target: FetchInfo) -> FetchInfo {
+        target.redirect_chain = self.redirect_chain;
+        target.redirect_chain.push(self.final_url);
+        target
+    }
+
+    /// Whether the response came from another url than the `requested` one.
+    pub fn is_redirected(&self, requested: &Url) -> bool {
+        self.final_url != *requested
+    }
+}
+
+/// Serializes the status as its number.
+#[cfg(feature = "serde0")]
+mod status_code {
+    use http::StatusCode;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(status: &StatusCode, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u16(status.as_u16())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<StatusCode, D::Error> {
+        StatusCode::from_u16(u16::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn fetch_info_of_response() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html; charset=windows-1251"));
+        let requested = Url::parse("http://example.com/news/harbour").unwrap();
+        let info = FetchInfo::new(
+            Url::parse("https://example.com/news/harbour.html").unwrap(),
+            StatusCode::OK,
+            headers,
+        );
+        assert_eq!(info.content_type.as_ref().map(Mime::essence_str), Some("text/html"));
+        assert!(info.is_redirected(&requested));
+
+        #[cfg(feature = "serde0")]
+        {
+            let json = serde_json::to_value(&info).unwrap();
+            assert_eq!(json["status"], 200);
+            assert_eq!(json["final_url"], "https://example.com/news/harbour.html");
+            assert!(json.get("headers").is_none());
+            let restored: FetchInfo = serde_json::from_value(json).unwrap();
+            assert_eq!(restored.final_url, info.final_url);
+            assert_eq!(restored.fetched_at, info.fetched_at);
+            assert!(restored.content_type.is_none());
+        }
+    }
+}
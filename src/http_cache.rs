@@ -0,0 +1,206 @@
+//! Conditional requests for pages that were downloaded before.
+//!
+//! If a [`HttpCache`] holds an entry for a url, the request for it carries
+//! the `If-None-Match` and `If-Modified-Since` headers and a
+//! `304 Not Modified` response is answered with the cached body.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use reqwest::header::{
+    HeaderMap, HeaderValue, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::{StatusCode, Url};
+
+/// A page body stored together with the validators of its response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedEntry {
+    /// The `ETag` header of the response.
+    pub etag: Option<String>,
+    /// The `Last-Modified` header of the response.
+    pub last_modified: Option<String>,
+    /// The status of the response.
+    pub status: StatusCode,
+    /// The headers of the response, a revalidated page is served with them
+    /// instead of the ones of the `304 Not Modified` response.
+    pub headers: HeaderMap,
+    /// The body of the response.
+    pub body: Bytes,
+}
+
+impl CachedEntry {
+    /// The entry for a response with the `status`, `headers` and `body`,
+    /// `None` if the response has neither an `ETag` nor a `Last-Modified`
+    /// header.
+    pub fn from_response(status: StatusCode, headers: &HeaderMap, body: Bytes) -> Option<Self> {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+        Some(Self {
+            etag,
+            last_modified,
+            status,
+            headers: headers.clone(),
+            body,
+        })
+    }
+
+    /// The `Content-Type` header of the response, with the charset the body
+    /// is decoded with.
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok())
+    }
+
+    /// The headers that make a request conditional on this entry.
+    pub fn conditional_headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let values = [
+            (IF_NONE_MATCH, &self.etag),
+            (IF_MODIFIED_SINCE, &self.last_modified),
+        ];
+        for (name, value) in values {
+            if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                headers.insert(name, value);
+            }
+        }
+        headers
+    }
+}
+
+/// Storage for the pages of previous downloads.
+///
+/// See [`crate::ExtrablattBuilder::http_cache`].
+pub trait HttpCache: fmt::Debug + Send + Sync {
+    /// The stored entry for the `url`.
+    fn get(&self, url: &Url) -> Option<CachedEntry>;
+
+    /// Store the `entry` for the `url`, replacing a previous one.
+    fn put(&self, url: &Url, entry: CachedEntry);
+}
+
+/// A [`HttpCache`] that keeps all entries in memory.
+#[derive(Debug, Default)]
+pub struct MemoryHttpCache {
+    entries: Mutex<HashMap<Url, CachedEntry>>,
+}
+
+impl MemoryHttpCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored entries.
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|entries| entries.len()).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl HttpCache for MemoryHttpCache {
+    fn get(&self, url: &Url) -> Option<CachedEntry> {
+        self.entries.lock().ok()?.get(url).cloned()
+    }
+
+    fn put(&self, url: &Url, entry: CachedEntry) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(url.clone(), entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+
+    use crate::test_server::serve;
+    use crate::Extrablatt;
+
+    use super::*;
+
+    #[test]
+    fn conditional_headers() {
+        let mut headers = HeaderMap::new();
+        assert!(CachedEntry::from_response(StatusCode::OK, &headers, Bytes::new()).is_none());
+
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        let entry = CachedEntry::from_response(StatusCode::OK, &headers, Bytes::from_static(b"body")).unwrap();
+        let conditional = entry.conditional_headers();
+        assert_eq!(conditional.get(IF_NONE_MATCH).unwrap(), "\"abc\"");
+        assert!(conditional.get(IF_MODIFIED_SINCE).is_none());
+    }
+
+    #[tokio::test]
+    async fn not_modified_reuses_cached_body() {
+        let not_modified = Arc::new(AtomicUsize::new(0));
+        let url = {
+            let not_modified = not_modified.clone();
+            serve(move |request| {
+                if request.header("if-none-match") == Some("\"v1\"") {
+                    not_modified.fetch_add(1, Ordering::SeqCst);
+                    return "HTTP/1.1 304 Not Modified\r\nETag: \"v1\"\r\nConnection: close\r\n\r\n"
+                        .to_string();
+                }
+                let body = if request.path == "/news" {
+                    "<a href=\"/news/harbour-expansion-approved-by-the-council.html\">Harbour</a>"
+                } else {
+                    "<html><head><title>Harbour café</title></head><body><div itemprop=\"articleBody\">\
+                     <p>The harbour will be expanded over the next ten years.</p></div></body></html>"
+                };
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=windows-1252\r\nETag: \"v1\"\r\n\
+                     Connection: close\r\n\r\n{}",
+                    body
+                )
+            })
+        };
+
+        let cache = Arc::new(MemoryHttpCache::new());
+        let crawl = || async {
+            let mut paper = Extrablatt::builder(url.clone())
+                .unwrap()
+                .categories(false)
+                .include_categories(vec![url.join("news").unwrap()])
+                .http_cache(cache.clone())
+                .build()
+                .await
+                .unwrap();
+            paper.download_all_remaining_categories().await;
+            let mut articles: Vec<_> = paper.article_stream().collect().await;
+            assert_eq!(articles.len(), 1);
+            articles.pop().unwrap().unwrap()
+        };
+
+        let first = crawl().await;
+        assert!(!first.from_cache);
+        // the utf-8 bytes of the `é` read as windows-1252
+        assert_eq!(first.content.title.as_deref(), Some("Harbour cafÃ©"));
+        // the main page, the category and the article
+        assert_eq!(cache.len(), 3);
+        assert_eq!(not_modified.load(Ordering::SeqCst), 0);
+
+        let second = crawl().await;
+        assert!(second.from_cache);
+        assert_eq!(second.content.title, first.content.title);
+        // served with the status and headers of the cached response
+        let fetch_info = second.fetch_info.as_ref().unwrap();
+        assert_eq!(fetch_info.status, StatusCode::OK);
+        assert_eq!(fetch_info.content_type.as_ref().map(|mime| mime.essence_str()), Some("text/html"));
+        assert_eq!(not_modified.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,7 +1,298 @@
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::article::ArticleContent;
+
+/// Query parameters that only select a rendition of the same image.
+const RENDITION_QUERY_PARAMS: [&str; 12] = [
+    "w", "h", "width", "height", "q", "quality", "fit", "crop", "resize", "auto", "fm", "dpr",
+];
+
+/// Rules [`crate::text::ArticleTextNode::images_with_filter`] drops the
+/// images of an article by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ImageFilter {
+    /// Min `width` and `height` attribute of an image in pixels, `0` keeps
+    /// all sizes. Images without the attributes are kept.
+    pub min_size: u32,
+    /// Keep only the first of images with the same resolved url.
+    pub dedup: bool,
+    /// Drop inline `data:` images, like the placeholders of lazy loaded
+    /// images.
+    pub drop_data_uris: bool,
+    /// Drop `.svg` images, like icons, unless they are the only images left.
+    pub drop_svg: bool,
+}
+
+impl ImageFilter {
+    /// Default for [`ImageFilter::min_size`].
+    pub const DEFAULT_MIN_SIZE: u32 = 100;
+
+    /// A filter that keeps all images.
+    pub fn none() -> Self {
+        Self {
+            min_size: 0,
+            dedup: false,
+            drop_data_uris: false,
+            drop_svg: false,
+        }
+    }
+
+    /// Set the [`ImageFilter::min_size`].
+    pub fn min_size(mut self, min_size: u32) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Set whether repeated urls are dropped, see [`ImageFilter::dedup`].
+    pub fn dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    /// Set whether `data:` images are dropped, see
+    /// [`ImageFilter::drop_data_uris`].
+    pub fn drop_data_uris(mut self, drop_data_uris: bool) -> Self {
+        self.drop_data_uris = drop_data_uris;
+        self
+    }
+
+    /// Set whether `.svg` images are dropped, see [`ImageFilter::drop_svg`].
+    pub fn drop_svg(mut self, drop_svg: bool) -> Self {
+        self.drop_svg = drop_svg;
+        self
+    }
+
+    /// The urls of the `images` that pass the filter, in their order.
+    ///
+    /// Each image is given with its `width` and `height` attribute.
+    pub fn apply<I>(&self, images: I) -> Vec<Url>
+    where
+        I: IntoIterator<Item = (Url, Option<u32>, Option<u32>)>,
+    {
+        let mut seen = HashSet::new();
+        let mut kept = Vec::new();
+        let mut svgs = Vec::new();
+        for (url, width, height) in images {
+            if self.drop_data_uris && url.scheme() == "data" {
+                continue;
+            }
+            if [width, height].iter().flatten().any(|size| *size < self.min_size) {
+                continue;
+            }
+            if self.dedup && !seen.insert(url.clone()) {
+                continue;
+            }
+            if self.drop_svg && is_svg(&url) {
+                svgs.push(url);
+            } else {
+                kept.push(url);
+            }
+        }
+        if kept.is_empty() { svgs } else { kept }
+    }
+}
+
+impl Default for ImageFilter {
+    /// Drops repeated urls, `data:` and `.svg` images and images smaller
+    /// than [`ImageFilter::DEFAULT_MIN_SIZE`].
+    fn default() -> Self {
+        Self {
+            min_size: Self::DEFAULT_MIN_SIZE,
+            dedup: true,
+            drop_data_uris: true,
+            drop_svg: true,
+        }
+    }
+}
+
+fn is_svg(url: &Url) -> bool {
+    url.path().to_lowercase().ends_with(".svg")
+}
+
+/// Parse a `width` or `height` attribute like `640` or `640px`.
+pub(crate) fn parse_dimension(value: &str) -> Option<u32> {
+    value.trim().trim_end_matches("px").trim().parse().ok()
+}
+
 #[derive(Debug, Clone)]
 pub struct Image {
     pub url: Url,
     pub caption: Option<String>,
 }
+
+/// An image of the article with the text that describes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ArticleImage {
+    pub url: Url,
+    /// Text of the `<figcaption>` without the credit.
+    pub caption: Option<String>,
+    /// The photo credit, like `Photo: Jane Doe/Reuters`.
+    pub credit: Option<String>,
+    /// The `alt` attribute of the `<img>`.
+    pub alt: Option<String>,
+}
+
+/// Counts in how many articles of a crawl each image occurs to detect site
+/// furniture like logos, byline avatars and promo images.
+///
+/// The registry can be serialized and passed to the next crawl with
+/// [`crate::ExtrablattBuilder::image_registry`], so recurring furniture is
+/// known from the first article on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ImageRegistry {
+    /// Number of articles each normalized image url occurred in.
+    occurrences: HashMap<Url, usize>,
+    /// Number of registered articles.
+    articles: usize,
+}
+
+impl ImageRegistry {
+    /// Min number of registered articles before any image is considered
+    /// furniture.
+    pub const MIN_ARTICLES: usize = 5;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of registered articles.
+    pub fn articles(&self) -> usize {
+        self.articles
+    }
+
+    /// Number of articles the image occurred in.
+    pub fn occurrences(&self, url: &Url) -> usize {
+        self.occurrences
+            .get(&normalize_image_url(url))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// All normalized image urls with the number of articles they occurred in.
+    pub fn iter(&self) -> impl Iterator<Item = (&Url, usize)> {
+        self.occurrences.iter().map(|(url, count)| (url, *count))
+    }
+
+    /// Register all images of a single article.
+    pub fn register<'a, I: IntoIterator<Item = &'a Url>>(&mut self, images: I) {
+        let images: HashSet<_> = images.into_iter().map(normalize_image_url).collect();
+        for url in images {
+            *self.occurrences.entry(url).or_default() += 1;
+        }
+        self.articles += 1;
+    }
+
+    /// Whether the image occurred in more than `max_pct` percent of the
+    /// registered articles.
+    pub fn is_furniture(&self, url: &Url, max_pct: usize) -> bool {
+        self.articles >= Self::MIN_ARTICLES && self.occurrences(url) * 100 > max_pct * self.articles
+    }
+
+    /// Remove all furniture from the images, the top image and the
+    /// thumbnail of the article.
+    pub fn remove_furniture(&self, content: &mut ArticleContent<'_>, max_pct: usize) {
+        content.images.retain(|url| !self.is_furniture(url, max_pct));
+        if content.top_image.as_ref().map(|url| self.is_furniture(url, max_pct)).unwrap_or_default() {
+            content.top_image = None;
+        }
+        if content.thumbnail.as_ref().map(|url| self.is_furniture(url, max_pct)).unwrap_or_default() {
+            content.thumbnail = None;
+        }
+    }
+}
+
+/// Normalize the url of an image, so different renditions of the same image
+/// are considered equal.
+///
+/// Removes the fragment and all query parameters that only select the size
+/// or quality of the image.
+pub fn normalize_image_url(url: &Url) -> Url {
+    let mut url = url.clone();
+    url.set_fragment(None);
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| !RENDITION_QUERY_PARAMS.contains(&key.to_lowercase().as_str()))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn furniture_images() {
+        let logo = Url::parse("https://example.com/logo.png?w=120").unwrap();
+        let mut registry = ImageRegistry::new();
+        for i in 0..ImageRegistry::MIN_ARTICLES {
+            let photo = Url::parse(&format!("https://example.com/photo-{}.jpg", i)).unwrap();
+            registry.register(vec![&logo, &logo, &photo]);
+        }
+        assert_eq!(registry.articles(), ImageRegistry::MIN_ARTICLES);
+
+        let resized_logo = Url::parse("https://example.com/logo.png?w=640#top").unwrap();
+        assert_eq!(registry.occurrences(&resized_logo), ImageRegistry::MIN_ARTICLES);
+        assert!(registry.is_furniture(&resized_logo, 50));
+
+        let mut content = ArticleContent::builder()
+            .images(vec![
+                resized_logo.clone(),
+                Url::parse("https://example.com/photo-0.jpg").unwrap(),
+            ])
+            .top_image(resized_logo)
+            .build();
+        registry.remove_furniture(&mut content, 50);
+        assert_eq!(
+            content.images,
+            vec![Url::parse("https://example.com/photo-0.jpg").unwrap()]
+        );
+        assert!(content.top_image.is_none());
+    }
+
+    #[test]
+    fn filter_images() {
+        let url = |path: &str| Url::parse(&format!("https://example.com/{}", path)).unwrap();
+        let images = vec![
+            (url("photo.jpg"), Some(800), Some(450)),
+            (Url::parse("data:image/gif;base64,R0lGODlhAQABAAAAACw=").unwrap(), None, None),
+            (url("icons/share.svg"), None, None),
+            (url("photo.jpg"), None, None),
+            (url("avatar.jpg"), Some(48), Some(48)),
+            (url("map.png"), Some(1200), None),
+        ];
+        assert_eq!(
+            ImageFilter::default().apply(images.clone()),
+            vec![url("photo.jpg"), url("map.png")]
+        );
+        assert_eq!(
+            ImageFilter::default().min_size(0).dedup(false).apply(images.clone()),
+            vec![url("photo.jpg"), url("photo.jpg"), url("avatar.jpg"), url("map.png")]
+        );
+        assert_eq!(ImageFilter::none().apply(images.clone()).len(), images.len());
+        assert_eq!(
+            ImageFilter::default().apply(vec![(url("chart.svg"), None, None)]),
+            vec![url("chart.svg")]
+        );
+    }
+
+    #[test]
+    fn normalize_keeps_identifying_query() {
+        let url = Url::parse("https://example.com/img?id=42&width=300").unwrap();
+        assert_eq!(normalize_image_url(&url).as_str(), "https://example.com/img?id=42");
+    }
+}
@@ -1,15 +1,43 @@
+use std::fmt;
 use std::slice::Iter;
 use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use lazy_static::lazy_static;
 
 #[cfg(feature = "serde0")]
 use serde::{Deserialize, Serialize};
 
+use std::collections::HashSet;
+
 #[cfg(feature = "stopwords")]
 use crate::nlp::*;
 use crate::text::{ArticleTextNodeExtractor, WordsStats};
+use url::Url;
+
+lazy_static! {
+    /// The languages registered with [`Language::register`].
+    static ref REGISTERED_LANGUAGES: RwLock<Vec<Language>> = RwLock::new(Vec::new());
+}
+
+/// A language detected from the text of a document.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct DetectedLanguage {
+    pub language: Language,
+    /// Share of the stopword hits that belong to `language`, between `0.0`
+    /// and `1.0`.
+    pub confidence: f64,
+}
 
+/// The language of a document.
+///
+/// Cloning is cheap, the stopwords are `'static` or behind the `Arc` of a
+/// [`Language::custom`]. Only [`Language::Other`] and
+/// [`Language::Custom`] own their name.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde0", serde(rename_all = "snake_case"))]
 pub enum Language {
     Arabic,
     Russian,
@@ -37,9 +65,24 @@ pub enum Language {
     Greek,
     Ukrainian,
     Other(String),
+    /// A language with stopwords of its own, see [`Language::custom`].
+    ///
+    /// The stopwords are serialized as a sorted list.
+    Custom {
+        name: String,
+        #[cfg_attr(feature = "serde0", serde(with = "sorted_stopwords"))]
+        stopwords: Arc<HashSet<String>>,
+    },
 }
 
 impl Language {
+    /// Max number of words of a text that are considered for language
+    /// detection.
+    pub const DETECTION_SAMPLE_WORDS: usize = 2000;
+
+    /// Min confidence of a detected language.
+    pub const MIN_DETECTION_CONFIDENCE: f64 = 0.3;
+
     /// All known languages.
     pub fn known_languages() -> Iter<'static, Language> {
         static LANGUAGES: [Language; 25] = [
@@ -100,6 +143,7 @@ impl Language {
             Language::Greek => "el",
             Language::Ukrainian => "uk",
             Language::Other(s) => s.as_str(),
+            Language::Custom { name, .. } => name.as_str(),
         }
     }
 
@@ -131,50 +175,179 @@ impl Language {
             Language::Greek => "Greek",
             Language::Ukrainian => "Ukrainian",
             Language::Other(s) => s.as_str(),
+            Language::Custom { name, .. } => name.as_str(),
+        }
+    }
+
+    /// A language with the `stopwords`, like Czech as `cs`, or a built-in
+    /// language like `ru` with domain terms added to its stopwords.
+    ///
+    /// The `name` and the stopwords are lowercased, the stopwords
+    /// deduplicated. The stopwords are used to count stopwords and select
+    /// the article node. Add the language to an extractor with
+    /// [`crate::DefaultExtractor::with_custom_language`] to resolve its name
+    /// from the page and detect it, or [`Language::register`] it to parse its
+    /// name everywhere.
+    pub fn custom<I, S>(name: &str, stopwords: I) -> Language
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let stopwords = stopwords
+            .into_iter()
+            .map(|word| word.as_ref().trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        Language::Custom {
+            name: name.trim().to_lowercase(),
+            stopwords: Arc::new(stopwords),
         }
     }
 
+    /// Register the [`Language::Custom`], so [`Language::from_str`] parses
+    /// its name to it, like the language a page declares. It replaces a
+    /// language registered before with the same name. Other languages are
+    /// returned without registering them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use extrablatt_v2::Language;
+    /// let czech = Language::custom("cs", ["a", "je", "se", "na", "v"]).register();
+    /// assert_eq!("cs-CZ".parse::<Language>(), Ok(czech));
+    /// ```
+    pub fn register(self) -> Self {
+        if let Language::Custom { name, .. } = &self {
+            let mut registered = REGISTERED_LANGUAGES.write().unwrap_or_else(|err| err.into_inner());
+            registered.retain(|lang| lang.identifier() != name);
+            registered.push(self.clone());
+        }
+        self
+    }
+
+    /// The registered language with the lowercase `name`, see
+    /// [`Language::register`].
+    fn registered(name: &str) -> Option<Self> {
+        REGISTERED_LANGUAGES
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .find(|lang| lang.identifier() == name)
+            .cloned()
+    }
+
     /// Counts the number of stopwords in the text, if stopwords for that
     /// language are available.
     pub fn stopword_count(&self, txt: &str) -> Option<WordsStats> {
         #[cfg(feature = "stopwords")]
-        if let Some(stopwords_set) = self.stopwords_set() {
-            let (word_count, stopword_count) = ArticleTextNodeExtractor::words(txt).fold(
-                (0usize, 0usize),
-                |(word_count, mut stopword_count), word| {
-                    if stopwords_set.contains(word) {
-                        stopword_count += 1;
-                    }
-
-                    (word_count + 1, stopword_count)
-                },
-            );
-            let cnt = word_count + stopword_count;
-            Some(WordsStats {
-                word_count,
-                stopword_count,
-                avg_word_length: match cnt {
-                    0 => 0.0,
-                    _ => word_count as f64 / cnt as f64,
-                },
+        {
+            self.stopwords_set()
+                .map(|stopwords_set| WordsStats::count(txt, |word| stopwords_set.contains(word)))
+        }
+
+        // every word counts as a stopword without the stopword lists
+        #[cfg(not(feature = "stopwords"))]
+        Some(WordsStats::count(txt, |_| true))
+    }
+
+    /// Detect the language of the text by its stopwords.
+    ///
+    /// See [`Language::detect_with_confidence`].
+    pub fn detect(text: &str) -> Option<Language> {
+        Self::detect_with_confidence(text).map(|detected| detected.language)
+    }
+
+    /// Detect the language of the text by counting the stopwords of all
+    /// known languages in the first [`Language::DETECTION_SAMPLE_WORDS`]
+    /// words.
+    ///
+    /// A stopword shared by several languages counts proportionally towards
+    /// each of them. Returns `None` if there are less than
+    /// [`ArticleTextNodeExtractor::MINIMUM_STOPWORD_COUNT`] stopwords or the
+    /// best language stays below [`Language::MIN_DETECTION_CONFIDENCE`].
+    pub fn detect_with_confidence(text: &str) -> Option<DetectedLanguage> {
+        Self::detect_with_custom(text, &[])
+    }
+
+    /// Like [`Language::detect_with_confidence`], but also considers the
+    /// `custom` languages, see [`Language::custom`].
+    ///
+    /// A custom language with the name of a known language replaces it.
+    pub fn detect_with_custom(text: &str, custom: &[Language]) -> Option<DetectedLanguage> {
+        #[cfg(feature = "stopwords")]
+        {
+            let known = Language::known_languages()
+                .filter(|lang| !custom.iter().any(|custom| custom.identifier() == lang.identifier()));
+            let languages: Vec<_> = custom
+                .iter()
+                .chain(known)
+                .filter_map(|lang| lang.stopwords_set().map(|set| (lang, set)))
+                .collect();
+            let mut scores = vec![0f64; languages.len()];
+            let mut stopword_count = 0;
+            for word in ArticleTextNodeExtractor::words(text).take(Self::DETECTION_SAMPLE_WORDS) {
+                let word = word.to_lowercase();
+                let hits: Vec<_> = languages
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, set))| set.contains(word.as_str()))
+                    .map(|(idx, _)| idx)
+                    .collect();
+                if hits.is_empty() {
+                    continue;
+                }
+                stopword_count += 1;
+                for idx in &hits {
+                    scores[*idx] += 1.0 / hits.len() as f64;
+                }
+            }
+            if stopword_count < ArticleTextNodeExtractor::MINIMUM_STOPWORD_COUNT {
+                return None;
+            }
+
+            let (best, score) = scores
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))?;
+            let confidence = score / stopword_count as f64;
+            if confidence < Self::MIN_DETECTION_CONFIDENCE {
+                return None;
+            }
+            Some(DetectedLanguage {
+                language: languages[best].0.clone(),
+                confidence,
             })
-        } else {
+        }
+
+        #[cfg(not(feature = "stopwords"))]
+        {
+            let _ = (text, custom);
             None
         }
+    }
+
+    /// Whether the lowercase `word` is a stopword of the language.
+    ///
+    /// Always `false` without stopwords for the language.
+    pub fn is_stopword(&self, word: &str) -> bool {
+        #[cfg(feature = "stopwords")]
+        {
+            self.stopwords_set()
+                .map(|set| set.contains(word))
+                .unwrap_or_default()
+        }
 
         #[cfg(not(feature = "stopwords"))]
         {
-            let ctn = ArticleTextNodeExtractor::words(txt).count();
-            Some(WordsStats {
-                word_count: ctn,
-                stopword_count: ctn,
-            })
+            let _ = word;
+            false
         }
     }
 
     #[cfg(feature = "stopwords")]
-    /// Get the stopwords for a language.
-    pub fn stopwords(&self) -> Option<&[&str]> {
+    /// Get the built-in stopwords for a language, without the ones of a
+    /// [`Language::custom`].
+    pub fn stopwords(&self) -> Option<&'static [&'static str]> {
         match self {
             Language::Arabic => Some(&ARABIC_STOPWORDS),
             Language::Russian => Some(&RUSSIAN_STOPWORDS),
@@ -202,11 +375,24 @@ impl Language {
             Language::Greek => Some(&GREEK_STOPWORDS),
             Language::Ukrainian => Some(&UKRAINIAN_STOPWORDS),
             Language::Other(_) => None,
+            Language::Custom { name, .. } => name.parse::<Language>().ok()?.stopwords(),
         }
     }
 
     #[cfg(feature = "stopwords")]
-    fn stopwords_set(&self) -> Option<&std::collections::HashSet<&'static str>> {
+    fn stopwords_set(&self) -> Option<StopwordSet<'_>> {
+        let set = StopwordSet {
+            builtin: self.builtin_stopwords_set(),
+            custom: match self {
+                Language::Custom { stopwords, .. } => Some(stopwords),
+                _ => None,
+            },
+        };
+        (set.builtin.is_some() || set.custom.is_some()).then_some(set)
+    }
+
+    #[cfg(feature = "stopwords")]
+    fn builtin_stopwords_set(&self) -> Option<&'static HashSet<&'static str>> {
         match self {
             Language::Arabic => Some(&ARABIC_STOPWORDS_SET),
             Language::Russian => Some(&RUSSIAN_STOPWORDS_SET),
@@ -234,6 +420,7 @@ impl Language {
             Language::Greek => Some(&GREEK_STOPWORDS_SET),
             Language::Ukrainian => Some(&UKRAINIAN_STOPWORDS_SET),
             Language::Other(_) => None,
+            Language::Custom { name, .. } => name.parse::<Language>().ok()?.builtin_stopwords_set(),
         }
     }
 }
@@ -241,35 +428,95 @@ impl Language {
 impl FromStr for Language {
     type Err = Language;
 
+    /// Parse a language tag like `en`, `en-US`, `pt_BR` or `zh-Hans-CN`, a
+    /// three letter ISO 639-2 code like `deu` or `ger`, or an English name
+    /// like `german`, all case-insensitive.
+    ///
+    /// Only the primary subtag of a tag is considered. A
+    /// [`Language::register`]ed language is preferred over a built-in one
+    /// with the same name. The error is [`Language::Other`] with the whole
+    /// tag, if neither the tag nor its primary subtag is a registered or
+    /// supported language.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "ar" | "arabic" => Ok(Language::Arabic),
-            "ru" | "russian" => Ok(Language::Russian),
-            "nl" | "dutch" => Ok(Language::Dutch),
-            "de" | "german" => Ok(Language::German),
-            "en" | "english" => Ok(Language::English),
-            "es" | "spanish" => Ok(Language::Spanish),
-            "fr" | "french" => Ok(Language::French),
-            "he" | "hebrew" => Ok(Language::Hebrew),
-            "it" | "italian" => Ok(Language::Italian),
-            "ko" | "korean" => Ok(Language::Korean),
-            "no" | "norwegian" => Ok(Language::Norwegian),
-            "fa" | "persian" => Ok(Language::Persian),
-            "pl" | "polish" => Ok(Language::Polish),
-            "pt" | "portuguese" => Ok(Language::Portuguese),
-            "sv" | "swedish" => Ok(Language::Swedish),
-            "hu" | "hungarian" => Ok(Language::Hungarian),
-            "fi" | "finnish" => Ok(Language::Finnish),
-            "da" | "danish" => Ok(Language::Danish),
-            "zh" | "chinese" => Ok(Language::Chinese),
-            "id" | "indonesian" => Ok(Language::Indonesian),
-            "vi" | "vietnamese" => Ok(Language::Vietnamese),
-            "sw" | "swahili" => Ok(Language::Swahili),
-            "tr" | "turkish" => Ok(Language::Turkish),
-            "el" | "greek" => Ok(Language::Greek),
-            "uk" | "ukrainian" => Ok(Language::Ukrainian),
-            s => Err(Language::Other(s.to_string())),
-        }
+        let tag = s.trim().to_lowercase();
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        [tag.as_str(), primary]
+            .into_iter()
+            .find_map(Language::registered)
+            .or_else(|| Language::from_code(&tag))
+            .or_else(|| Language::from_code(primary))
+            .ok_or_else(|| Language::Other(s.to_string()))
+    }
+}
+
+impl Language {
+    /// The language of a lowercase ISO 639-1 or ISO 639-2 code or English
+    /// name.
+    fn from_code(code: &str) -> Option<Self> {
+        let lang = match code {
+            "ar" | "ara" | "arabic" => Language::Arabic,
+            "ru" | "rus" | "russian" => Language::Russian,
+            "nl" | "nld" | "dut" | "dutch" => Language::Dutch,
+            "de" | "deu" | "ger" | "german" => Language::German,
+            "en" | "eng" | "english" => Language::English,
+            "es" | "spa" | "spanish" => Language::Spanish,
+            "fr" | "fra" | "fre" | "french" => Language::French,
+            "he" | "iw" | "heb" | "hebrew" => Language::Hebrew,
+            "it" | "ita" | "italian" => Language::Italian,
+            "ko" | "kor" | "korean" => Language::Korean,
+            "no" | "nb" | "nn" | "nor" | "nob" | "nno" | "norwegian" => Language::Norwegian,
+            "fa" | "fas" | "per" | "persian" => Language::Persian,
+            "pl" | "pol" | "polish" => Language::Polish,
+            "pt" | "por" | "portuguese" => Language::Portuguese,
+            "sv" | "swe" | "swedish" => Language::Swedish,
+            "hu" | "hun" | "hungarian" => Language::Hungarian,
+            "fi" | "fin" | "finnish" => Language::Finnish,
+            "da" | "dan" | "danish" => Language::Danish,
+            "zh" | "zho" | "chi" | "chinese" => Language::Chinese,
+            "id" | "in" | "ind" | "indonesian" => Language::Indonesian,
+            "vi" | "vie" | "vietnamese" => Language::Vietnamese,
+            "sw" | "swa" | "swh" | "swahili" => Language::Swahili,
+            "tr" | "tur" | "turkish" => Language::Turkish,
+            "el" | "ell" | "gre" | "greek" => Language::Greek,
+            "uk" | "ukr" | "ukrainian" => Language::Ukrainian,
+            _ => return None,
+        };
+        Some(lang)
+    }
+}
+
+/// Serializes the stopwords of a [`Language::Custom`] as a sorted list.
+#[cfg(feature = "serde0")]
+mod sorted_stopwords {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(stopwords: &Arc<HashSet<String>>, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut sorted: Vec<&String> = stopwords.iter().collect();
+        sorted.sort();
+        sorted.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Arc<HashSet<String>>, D::Error> {
+        Vec::<String>::deserialize(deserializer).map(|stopwords| Arc::new(stopwords.into_iter().collect()))
+    }
+}
+
+/// The built-in stopwords of a language together with the ones of a
+/// [`Language::custom`].
+#[cfg(feature = "stopwords")]
+struct StopwordSet<'a> {
+    builtin: Option<&'static HashSet<&'static str>>,
+    custom: Option<&'a HashSet<String>>,
+}
+
+#[cfg(feature = "stopwords")]
+impl StopwordSet<'_> {
+    fn contains(&self, word: &str) -> bool {
+        self.builtin.map(|set| set.contains(word)).unwrap_or_default()
+            || self.custom.map(|set| set.contains(word)).unwrap_or_default()
     }
 }
 
@@ -278,3 +525,181 @@ impl Default for Language {
         Language::English
     }
 }
+
+/// Languages of the pages of a multilingual site that differ from the
+/// language of the site, by url.
+///
+/// See [`crate::ExtrablattBuilder::language_for_host_path`] and
+/// [`crate::ExtrablattBuilder::language_resolver`].
+#[derive(Clone, Default)]
+pub struct LanguageOverrides {
+    /// Host, path prefix and the language of the pages below it.
+    paths: Vec<(String, String, Language)>,
+    resolver: Option<LanguageResolver>,
+}
+
+/// Resolves the language of a page by its url.
+type LanguageResolver = Arc<dyn Fn(&Url) -> Option<Language> + Send + Sync>;
+
+impl LanguageOverrides {
+    /// Use the `language` for the pages on the `host` whose path starts with
+    /// the `path_prefix` segments, like `/ar` for `/ar/news/1.html` but not
+    /// `/archive`.
+    pub fn insert<H: Into<String>, P: Into<String>>(&mut self, host: H, path_prefix: P, language: Language) {
+        let prefix = path_prefix.into();
+        let prefix = format!("/{}", prefix.trim_matches('/'));
+        self.paths.push((host.into().to_lowercase(), prefix, language));
+    }
+
+    /// Use the language the `resolver` returns for a url, if no path prefix
+    /// matches.
+    pub fn set_resolver<F>(&mut self, resolver: F)
+    where
+        F: Fn(&Url) -> Option<Language> + Send + Sync + 'static,
+    {
+        self.resolver = Some(Arc::new(resolver));
+    }
+
+    /// The language of the page at the `url`: of the longest matching path
+    /// prefix, else of the resolver.
+    pub fn language(&self, url: &Url) -> Option<Language> {
+        let host = url.host_str()?.to_lowercase();
+        let path = url.path();
+        self.paths
+            .iter()
+            .filter(|(h, prefix, _)| {
+                *h == host
+                    && (prefix == "/"
+                        || path
+                            .strip_prefix(prefix.as_str())
+                            .map(|rest| rest.is_empty() || rest.starts_with('/'))
+                            .unwrap_or_default())
+            })
+            .max_by_key(|(_, prefix, _)| prefix.len())
+            .map(|(_, _, language)| language.clone())
+            .or_else(|| self.resolver.as_ref().and_then(|resolver| resolver(url)))
+    }
+}
+
+impl fmt::Debug for LanguageOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LanguageOverrides")
+            .field("paths", &self.paths)
+            .field("resolver", &self.resolver.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+#[cfg(all(test, feature = "stopwords"))]
+mod tests {
+    use select::document::Document;
+
+    use super::*;
+
+    #[test]
+    fn detect_language() {
+        assert_eq!(
+            Language::detect(
+                "Президент заявил, что в ближайшее время правительство не будет повышать \
+                 налоги. Он также отметил, что экономика страны растёт и что это хорошо для \
+                 всех граждан, которые живут в регионах."
+            ),
+            Some(Language::Russian)
+        );
+        assert_eq!(
+            Language::detect(
+                "Die Bundesregierung hat am Mittwoch beschlossen, dass die Steuern nicht erhöht \
+                 werden. Der Minister sagte, es gebe keinen Grund für eine Erhöhung, weil die \
+                 Wirtschaft wächst und die Einnahmen steigen."
+            ),
+            Some(Language::German)
+        );
+        let detected = Language::detect_with_confidence(
+            "The government said on Wednesday that taxes will not rise this year. The minister \
+             added that there is no reason for an increase because the economy is growing.",
+        )
+        .unwrap();
+        assert_eq!(detected.language, Language::English);
+        assert!(detected.confidence >= Language::MIN_DETECTION_CONFIDENCE);
+    }
+
+    #[test]
+    fn language_tags() {
+        for (tag, lang) in [
+            ("en-US", Language::English),
+            ("EN_gb", Language::English),
+            ("pt-BR", Language::Portuguese),
+            ("zh-Hans-CN", Language::Chinese),
+            ("deu", Language::German),
+            ("ger", Language::German),
+            ("nb-NO", Language::Norwegian),
+            ("ru_RU", Language::Russian),
+            ("French", Language::French),
+        ] {
+            assert_eq!(tag.parse::<Language>(), Ok(lang), "{}", tag);
+        }
+        assert_eq!("tlh-Latn".parse::<Language>(), Err(Language::Other("tlh-Latn".to_string())));
+    }
+
+    #[test]
+    fn custom_language() {
+        let lang = Language::custom("XQ", ["Zor", "zor", " BLEN ", "quix", "vrak", "plim", "drox", ""]);
+        assert_eq!(lang.identifier(), "xq");
+        assert!(matches!(&lang, Language::Custom { stopwords, .. } if stopwords.len() == 6));
+        // not registered
+        assert_eq!("xq".parse::<Language>(), Err(Language::Other("xq".to_string())));
+        let stats = lang.stopword_count("zor drambel blen").unwrap();
+        assert_eq!(stats.stopword_count, 2);
+        assert_eq!(stats.word_count, 3);
+        // the characters per word, (3 + 7 + 4) / 3
+        assert!((stats.avg_word_length - 14.0 / 3.0).abs() < 1e-9);
+
+        let doc = Document::from(include_str!("../tests/fixtures/multilingual/custom.html"));
+        let node = ArticleTextNodeExtractor::calculate_best_node(&doc, lang.clone()).unwrap();
+        assert_eq!(node.attr("id"), Some("main"));
+        assert_eq!(Language::detect(&node.text()), None);
+        assert_eq!(
+            Language::detect_with_custom(&node.text(), std::slice::from_ref(&lang)).map(|detected| detected.language),
+            Some(lang)
+        );
+    }
+
+    #[test]
+    fn extend_builtin_stopwords() {
+        let lang = Language::custom("ru", ["Госдума"]);
+        assert!(!Language::Russian.is_stopword("госдума"));
+        assert!(lang.is_stopword("госдума"));
+        assert!(lang.is_stopword("что"));
+        assert_eq!(lang.stopwords(), Language::Russian.stopwords());
+    }
+
+    #[test]
+    fn registered_language() {
+        let lang = Language::custom("xr", ["zor", "blen"]).register();
+        assert_eq!("XR-latn".parse::<Language>(), Ok(lang.clone()));
+        assert_eq!(Language::English.register(), Language::English);
+
+        // a registered language replaces the built-in one, no other test
+        // parses `sw`
+        let swahili = Language::custom("sw", ["habari"]);
+        assert_eq!("sw".parse::<Language>(), Ok(Language::Swahili));
+        swahili.clone().register();
+        assert_eq!("sw-KE".parse::<Language>(), Ok(swahili));
+        REGISTERED_LANGUAGES.write().unwrap().retain(|lang| lang.identifier() != "sw");
+    }
+
+    #[cfg(feature = "serde0")]
+    #[test]
+    fn serialize_custom_stopwords() {
+        let lang = Language::custom("xs", ["zor", "blen"]);
+        let json = serde_json::to_string(&lang).unwrap();
+        assert_eq!(json, r#"{"custom":{"name":"xs","stopwords":["blen","zor"]}}"#);
+        assert_eq!(serde_json::from_str::<Language>(&json).unwrap(), lang);
+    }
+
+    #[test]
+    fn detect_too_few_stopwords() {
+        assert_eq!(Language::detect("Bundesregierung Mittwoch Steuern"), None);
+        assert_eq!(Language::detect(""), None);
+    }
+}
@@ -119,38 +119,107 @@ pub use select;
 
 pub use crate::article::{Article, PureArticle};
 pub use crate::category::Category;
+#[cfg(feature = "net")]
+pub use crate::error::{ErrorResponse, ExtrablattError};
+#[cfg(feature = "net")]
 pub use crate::extrablatt::{ArticleStream, Config, Extrablatt, ExtrablattBuilder};
 pub use crate::extract::{DefaultExtractor, Extractor};
 pub use crate::language::Language;
+#[cfg(feature = "net")]
+pub use crate::retry::RetryPolicy;
 
 pub mod extract_meta;
+pub mod extract_description;
 pub mod extract_title;
 pub mod extract_pb_date;
 pub mod extract_authors;
 pub mod extract_node;
 pub mod extract_favicon;
 pub mod extract_meta_language;
+pub mod extract_microdata;
 pub mod extract_thumbnail;
 pub mod extract_top_img;
 pub mod extract_urls;
 pub mod extract_base_url;
 pub mod extract_meta_data;
+pub mod extract_keywords;
+pub mod extract_json_ld;
+pub mod extract_og_article;
+pub mod extract_section;
+pub mod extract_breadcrumbs;
+pub mod extract_comments;
 pub mod extract_canonical;
+pub mod extract_ids;
+pub mod extract_recipe;
 pub mod extract_videos;
+pub mod extract_audios;
+pub mod extract_amp;
+pub mod extract_meta_refresh;
+pub mod extract_feeds;
+pub mod extract_hreflang;
+pub mod extract_publisher;
+pub mod extract_twitter_card;
 pub mod article;
+pub mod audio;
+#[cfg(feature = "rayon")]
+pub mod batch;
+pub mod block;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
 pub mod category;
+pub mod charset;
 pub mod clean;
+pub mod completeness;
+#[cfg(feature = "cookies")]
+pub mod cookies;
+#[cfg(feature = "net")]
+pub mod crawl_state;
 pub mod date;
+pub mod date_locale;
+pub mod dedup;
+pub mod dialogue;
+pub mod embed;
+#[cfg(feature = "net")]
 mod error;
+#[cfg(feature = "net")]
 pub mod extrablatt;
+pub mod fetch_info;
+#[cfg(feature = "net")]
+pub mod http_cache;
 pub mod extract;
 pub mod image;
 pub mod language;
+#[cfg(feature = "net")]
+pub mod monitor;
+#[cfg(feature = "newspaper")]
+pub mod newspaper;
+pub mod paywall;
 #[cfg(feature = "stopwords")]
 mod stopwords;
 pub mod text;
+#[cfg(feature = "net")]
+pub mod throttle;
+#[cfg(feature = "net")]
+mod trace;
+pub mod url_class;
+#[cfg(feature = "net")]
+pub mod user_agent;
 pub mod video;
+#[cfg(feature = "net")]
 mod proxy;
+pub mod promo;
+mod public_suffix;
+pub mod quote;
+pub mod reference;
+#[cfg(feature = "net")]
+pub mod retry;
+pub mod sink;
+pub mod source_span;
+pub mod stats;
+pub mod summarize;
+#[cfg(test)]
+mod test_server;
+pub mod wayback;
 
 pub mod nlp {
 
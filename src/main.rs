@@ -1,11 +1,21 @@
+use std::io::Read;
 use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
 
 use futures::{stream, StreamExt};
+use select::document::Document;
+use select::predicate::Name;
 use structopt::StructOpt;
 use url::Url;
 
 use extrablatt_v2::article::PureArticle;
-use extrablatt_v2::{Article, Category, Config, Extrablatt};
+use extrablatt_v2::block::{blocks, to_markdown};
+use extrablatt_v2::charset::decode;
+use extrablatt_v2::{Article, Category, Config, DefaultExtractor, Extrablatt, ExtrablattError, Language};
+
+/// The url of local html without a `--url`.
+const LOCAL_URL: &str = "http://localhost/";
 
 #[allow(missing_docs)]
 #[derive(Debug, StructOpt)]
@@ -46,11 +56,25 @@ enum App {
         )]
         output: Option<PathBuf>,
     },
+    #[structopt(
+        name = "extract",
+        about = "Extract a single article from a url, a html file or stdin and print it."
+    )]
+    Extract {
+        #[structopt(
+            name = "input",
+            help = "The url of the article, the path of a html file or `-` to read the html from stdin."
+        )]
+        input: Input,
+        #[structopt(flatten)]
+        opts: ExtractOpts,
+    },
 }
 
 impl App {
     async fn run(self) -> anyhow::Result<()> {
         let (out, articles) = match self {
+            App::Extract { input, opts } => return opts.run(input).await,
             App::Site { url, opts } => {
                 let config = opts.as_config();
                 (
@@ -169,7 +193,185 @@ impl Opts {
     }
 }
 
+/// Where the html of the article comes from.
+#[derive(Debug, Clone)]
+enum Input {
+    Url(Url),
+    File(PathBuf),
+    Stdin,
+}
+
+impl FromStr for Input {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "-" {
+            return Ok(Input::Stdin);
+        }
+        match Url::parse(s) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => Ok(Input::Url(url)),
+            _ => Ok(Input::File(PathBuf::from(s))),
+        }
+    }
+}
+
+/// How the extracted article is printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// The [`PureArticle`] as json.
+    Json,
+    /// The text of the article.
+    Text,
+    /// The title and the paragraphs, headings, lists, tables and quotes of
+    /// the article.
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "text" => Ok(Format::Text),
+            "markdown" | "md" => Ok(Format::Markdown),
+            s => Err(format!("unknown format `{}`, expected json, text or markdown", s)),
+        }
+    }
+}
+
+fn parse_language(s: &str) -> Language {
+    s.parse().unwrap_or_else(|other| other)
+}
+
+#[derive(Debug, Clone, StructOpt)]
+pub struct ExtractOpts {
+    #[structopt(
+        long = "lang",
+        help = "The language of the article, like `en`, detected if not set.",
+        parse(from_str = parse_language)
+    )]
+    lang: Option<Language>,
+    #[structopt(
+        long = "format",
+        default_value = "json",
+        possible_values = &["json", "text", "markdown", "md"],
+        help = "Print the article as json, only its text or as markdown."
+    )]
+    format: Format,
+    #[structopt(long = "timeout", help = "The timeout of the request in seconds.")]
+    timeout: Option<u64>,
+    #[structopt(long = "proxy", help = "The proxy for the request, like `socks5://127.0.0.1:9050`.")]
+    proxy: Option<String>,
+    #[structopt(long = "user-agent", help = "The user-agent used for the request.")]
+    user_agent: Option<String>,
+    #[structopt(
+        long = "url",
+        help = "The url of a local html page, to resolve its relative links."
+    )]
+    url: Option<Url>,
+    #[structopt(
+        long = "min-words",
+        help = "Fail if the text of the article has less words."
+    )]
+    min_word_count: Option<usize>,
+}
+
+impl ExtractOpts {
+    /// Extracts the article of the `input` and prints it to stdout.
+    ///
+    /// Fails with [`ExtrablattError::IncompleteArticle`] if the article has
+    /// less than the `--min-words`.
+    async fn run(self, input: Input) -> anyhow::Result<()> {
+        let article = match input {
+            Input::Url(url) => self.download(url).await?,
+            Input::File(path) => self.extract(&tokio::fs::read(&path).await?)?,
+            Input::Stdin => {
+                let mut body = Vec::new();
+                std::io::stdin().read_to_end(&mut body)?;
+                self.extract(&body)?
+            }
+        };
+
+        let mut config = Config::builder();
+        if let Some(min_word_count) = self.min_word_count {
+            config = config.min_word_count(min_word_count);
+        }
+        let incompleteness = config.build().incompleteness(&article.content);
+        if !incompleteness.is_empty() {
+            return Err(ExtrablattError::IncompleteArticle {
+                article: Box::new(article.into_pure()),
+                incompleteness,
+            }
+            .into());
+        }
+
+        match self.format {
+            Format::Json => println!("{}", serde_json::to_string_pretty(&article.into_pure())?),
+            Format::Text => println!("{}", article.content.text.as_deref().unwrap_or_default()),
+            Format::Markdown => println!("{}", Self::markdown(&article)),
+        }
+        Ok(())
+    }
+
+    async fn download(&self, url: Url) -> anyhow::Result<Article> {
+        let mut builder = Article::builder(url)?;
+        if let Some(lang) = self.lang.clone() {
+            builder = builder.language(lang);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(Duration::from_secs(timeout));
+        }
+        if let Some(proxy) = self.proxy.clone() {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(user_agent) = self.user_agent.clone() {
+            builder = builder.browser_user_agent(user_agent);
+        }
+        Ok(builder.get().await?)
+    }
+
+    /// Extracts the article from local html, transcoded to UTF-8 according
+    /// to its `<meta>` charset.
+    fn extract(&self, body: &[u8]) -> anyhow::Result<Article> {
+        let url = self.url.clone().unwrap_or_else(|| Url::parse(LOCAL_URL).unwrap());
+        Ok(Article::with_extractor_and_lang(
+            url,
+            decode(body, None),
+            &DefaultExtractor::default(),
+            self.lang.clone().unwrap_or_default(),
+        )?)
+    }
+
+    /// The title as heading followed by the blocks of the extracted html of
+    /// the article, or the lines of its text if the html has none.
+    fn markdown(article: &Article) -> String {
+        let blocks = article
+            .content
+            .html
+            .as_deref()
+            .map(|html| {
+                let doc = Document::from(html);
+                doc.find(Name("body"))
+                    .next()
+                    .map(|body| blocks(&body, |_| false))
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default();
+        let body = if blocks.is_empty() {
+            let text = article.content.text.as_deref().unwrap_or_default();
+            text.lines().filter(|line| !line.trim().is_empty()).collect::<Vec<_>>().join("\n\n")
+        } else {
+            to_markdown(&blocks)
+        };
+        match article.content.title.as_deref() {
+            Some(title) => format!("# {}\n\n{}", title, body),
+            None => body,
+        }
+    }
+}
+
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    Ok(App::from_args().run().await?)
+async fn main() -> anyhow::Result<()> {
+    App::from_args().run().await
 }
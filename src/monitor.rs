@@ -0,0 +1,540 @@
+//! Monitor a news site continuously and yield only the articles that weren't
+//! seen before.
+//!
+//! Each cycle downloads the main page and, if enabled, the known
+//! [`Extrablatt::categories`], collects their article urls and downloads those that aren't in the
+//! [`MonitorState`] yet. The state is saved after each cycle and when the
+//! stream is dropped, so a monitor that is stopped and started again
+//! continues where it left off.
+//!
+//! ```no_run
+//! use std::time::Duration;
+//!
+//! use extrablatt_v2::monitor::{MonitorConfig, MonitorEvent};
+//! use futures::StreamExt;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let site = extrablatt_v2::Extrablatt::builder("https://some-news.com/")?.build().await?;
+//! let config = MonitorConfig {
+//!     interval: Duration::from_secs(300),
+//!     state_path: Some("seen.json".into()),
+//!     ..Default::default()
+//! };
+//! let mut monitor = Box::pin(site.monitor(config)?);
+//! while let Some(event) = monitor.next().await {
+//!     match event {
+//!         Ok(MonitorEvent::Article(article)) => println!("new '{:?}'", article.content.title),
+//!         Ok(MonitorEvent::Cycle(stats)) => println!("{:?}", stats),
+//!         Err(err) => println!("{}", err),
+//!     }
+//! }
+//! #   Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use futures::stream::{self, Stream};
+use futures::StreamExt;
+use reqwest::Url;
+use select::document::Document;
+use serde_json::{json, Value};
+use wasm_timer::{Delay, Instant};
+
+use crate::article::Article;
+use crate::error::ExtrablattError;
+use crate::extract::Extractor;
+use crate::Extrablatt;
+
+/// Configuration of [`Extrablatt::monitor`].
+#[derive(Debug, Clone)]
+pub struct MonitorConfig {
+    /// Time to wait between the end of a cycle and the start of the next.
+    pub interval: Duration,
+    /// Whether to also download the category pages for new article urls,
+    /// instead of only the main page.
+    pub categories: bool,
+    /// Max number of articles to download per cycle, the remaining new
+    /// articles are downloaded in the following cycles.
+    pub max_new_per_cycle: Option<usize>,
+    /// Where to persist the urls of the seen articles between cycles and
+    /// runs. Without a path the state is only kept in memory.
+    pub state_path: Option<PathBuf>,
+    /// Stop after this number of cycles, runs until the stream is dropped
+    /// otherwise.
+    pub max_cycles: Option<usize>,
+    /// Number of cycles an article that fails is requested in, before it
+    /// counts as seen.
+    pub max_attempts: usize,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(600),
+            categories: false,
+            max_new_per_cycle: None,
+            state_path: None,
+            max_cycles: None,
+            max_attempts: 3,
+        }
+    }
+}
+
+/// An item of the stream returned by [`Extrablatt::monitor`].
+#[derive(Debug)]
+pub enum MonitorEvent {
+    /// An article that wasn't seen before.
+    Article(Box<Article>),
+    /// A cycle finished, after all of its articles were yielded.
+    Cycle(CycleStats),
+}
+
+/// Statistics of a single monitor cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleStats {
+    /// Number of the cycle, starting at 1.
+    pub cycle: usize,
+    /// Article urls found on the main page and the categories.
+    pub discovered: usize,
+    /// Article urls that weren't seen before.
+    pub new: usize,
+    /// Articles requested in this cycle, at most
+    /// [`MonitorConfig::max_new_per_cycle`].
+    pub requested: usize,
+    /// Requested articles that were extracted successfully.
+    pub articles: usize,
+    /// Requested articles that failed to download or didn't fulfill the
+    /// configured requirements.
+    pub failures: usize,
+    /// Category pages that failed to download.
+    pub category_failures: usize,
+    /// Time spent on requests and extraction.
+    pub duration: Duration,
+}
+
+/// The urls of all articles a monitor has already yielded.
+#[derive(Debug, Clone, Default)]
+pub struct MonitorState {
+    /// Where the state is persisted.
+    path: Option<PathBuf>,
+    /// Urls of the seen articles.
+    seen: HashSet<String>,
+    /// Whether there are changes that aren't saved yet.
+    dirty: bool,
+}
+
+impl MonitorState {
+    /// Load the state from `path`, or start with an empty state if the file
+    /// doesn't exist yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ExtrablattError> {
+        let path = path.as_ref().to_path_buf();
+        let seen = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Value>(&bytes)
+                .map_err(std::io::Error::from)
+                .map_err(|error| ExtrablattError::MonitorState {
+                    path: path.clone(),
+                    error,
+                })?
+                .get("seen")
+                .and_then(Value::as_array)
+                .map(|urls| {
+                    urls.iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(error) => return Err(ExtrablattError::MonitorState { path, error }),
+        };
+        Ok(Self {
+            path: Some(path),
+            seen,
+            dirty: false,
+        })
+    }
+
+    /// Whether the article was already seen.
+    pub fn contains(&self, url: &Url) -> bool {
+        self.seen.contains(url.as_str())
+    }
+
+    /// Mark the article as seen.
+    pub fn insert(&mut self, url: &Url) {
+        if self.seen.insert(url.to_string()) {
+            self.dirty = true;
+        }
+    }
+
+    /// Number of seen articles.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Whether no article was seen yet.
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Write the state to its path, if it has one and there are unsaved
+    /// changes.
+    pub fn save(&mut self) -> Result<(), ExtrablattError> {
+        let Some(path) = self.path.as_ref().filter(|_| self.dirty) else {
+            return Ok(());
+        };
+        let mut seen: Vec<_> = self.seen.iter().collect();
+        seen.sort();
+        std::fs::write(path, json!({ "seen": seen }).to_string()).map_err(|error| {
+            ExtrablattError::MonitorState {
+                path: path.clone(),
+                error,
+            }
+        })?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+impl<TExtractor: Extractor + Unpin> Extrablatt<TExtractor> {
+    /// Converts the newspaper into a stream that checks the site every
+    /// [`MonitorConfig::interval`] and yields each article that wasn't seen
+    /// before, followed by the [`CycleStats`] of the cycle.
+    ///
+    /// An article counts as seen once it was yielded successfully, or as
+    /// error in [`MonitorConfig::max_attempts`] cycles. The failed attempts
+    /// are only counted in memory. Dropping the stream stops the monitor and
+    /// saves the state.
+    ///
+    /// Fails if the state at [`MonitorConfig::state_path`] can't be read.
+    pub fn monitor(
+        self,
+        config: MonitorConfig,
+    ) -> Result<impl Stream<Item = Result<MonitorEvent, ExtrablattError>>, ExtrablattError> {
+        let state = match &config.state_path {
+            Some(path) => MonitorState::load(path)?,
+            None => MonitorState::default(),
+        };
+        let monitor = Monitor {
+            paper: self,
+            config,
+            state,
+            cycle: 0,
+            attempts: HashMap::new(),
+            pending: VecDeque::new(),
+            stats: None,
+        };
+        Ok(stream::unfold(monitor, |mut monitor| async move {
+            let event = monitor.next_event().await?;
+            Some((event, monitor))
+        }))
+    }
+}
+
+/// The state of a running monitor.
+struct Monitor<TExtractor: Extractor> {
+    paper: Extrablatt<TExtractor>,
+    config: MonitorConfig,
+    state: MonitorState,
+    /// Number of started cycles.
+    cycle: usize,
+    /// Number of failed attempts of the articles that aren't seen yet.
+    attempts: HashMap<Url, usize>,
+    /// Results of the current cycle that weren't yielded yet.
+    pending: VecDeque<(Url, Result<Article, ExtrablattError>)>,
+    /// Statistics of the current cycle, yielded after its results.
+    stats: Option<CycleStats>,
+}
+
+impl<TExtractor: Extractor> Monitor<TExtractor> {
+    async fn next_event(&mut self) -> Option<Result<MonitorEvent, ExtrablattError>> {
+        loop {
+            if let Some((url, result)) = self.pending.pop_front() {
+                self.attempt(&url, result.is_ok());
+                return Some(result.map(|article| MonitorEvent::Article(Box::new(article))));
+            }
+            if let Some(stats) = self.stats.take() {
+                return Some(self.state.save().map(|_| MonitorEvent::Cycle(stats)));
+            }
+            if self.config.max_cycles.is_some_and(|max| self.cycle >= max) {
+                return None;
+            }
+            if self.cycle > 0 {
+                let _ = Delay::new(self.config.interval).await;
+            }
+            self.cycle += 1;
+            if let Err(err) = self.run_cycle().await {
+                return Some(Err(err));
+            }
+        }
+    }
+
+    /// Mark the article as seen if it was extracted, or failed too often.
+    fn attempt(&mut self, url: &Url, success: bool) {
+        if !success {
+            let attempts = self.attempts.entry(url.clone()).or_default();
+            *attempts += 1;
+            if *attempts < self.config.max_attempts {
+                return;
+            }
+        }
+        self.attempts.remove(url);
+        self.state.insert(url);
+    }
+
+    /// Download the main page and categories and queue the new articles.
+    async fn run_cycle(&mut self) -> Result<(), ExtrablattError> {
+        let started = Instant::now();
+        let concurrency = self.paper.config().concurrency();
+        self.paper.refresh_homepage().await?;
+
+        let mut pages = Vec::new();
+        let mut category_failures = 0;
+        if self.config.categories {
+            let paper = &self.paper;
+            let results = stream::iter(
+                paper
+                    .categories()
+                    .keys()
+                    .map(|category| paper.get_document(category.url.clone())),
+            )
+            .buffer_unordered(concurrency)
+            .collect::<Vec<_>>()
+            .await;
+            for result in results {
+                match result {
+                    Ok(page) => pages.push(page.doc),
+                    Err(_) => category_failures += 1,
+                }
+            }
+        }
+
+        let mut uniques = HashSet::new();
+        let paper = &self.paper;
+        let article_urls = |doc: &Document| {
+            paper
+                .extractor()
+                .article_urls(doc, Some(paper.base_url()))
+                .into_iter()
+                .map(|article| article.url)
+        };
+        let urls: Vec<Url> = article_urls(paper.main_page())
+            .chain(
+                pages
+                    .iter()
+                    .flat_map(article_urls)
+                    .filter(|url| paper.is_wanted_url(url)),
+            )
+            .filter(|url| uniques.insert(url.clone()))
+            .collect();
+        let discovered = urls.len();
+        let new: Vec<Url> = urls
+            .into_iter()
+            .filter(|url| !self.state.contains(url))
+            .collect();
+        let new_count = new.len();
+        let requested: Vec<Url> = new
+            .into_iter()
+            .take(self.config.max_new_per_cycle.unwrap_or(usize::MAX))
+            .collect();
+
+        let paper = &self.paper;
+        let responses = stream::iter(requested.iter().cloned().map(|url| async move {
+            let result = paper.get_document(url.clone()).await;
+            (url, result)
+        }))
+        .buffered(concurrency)
+        .collect::<Vec<_>>()
+        .await;
+
+        let mut stats = CycleStats {
+            cycle: self.cycle,
+            discovered,
+            new: new_count,
+            requested: requested.len(),
+            articles: 0,
+            failures: 0,
+            category_failures,
+            duration: Duration::default(),
+        };
+        for (url, response) in responses {
+            let result = response
+                .map_err(|(_, err)| err)
+                .and_then(|page| self.paper.article_from_document(url.clone(), page));
+            if result.is_ok() {
+                stats.articles += 1;
+            } else {
+                stats.failures += 1;
+            }
+            self.pending.push_back((url, result));
+        }
+        stats.duration = started.elapsed();
+        self.stats = Some(stats);
+        Ok(())
+    }
+}
+
+impl<TExtractor: Extractor> Drop for Monitor<TExtractor> {
+    fn drop(&mut self) {
+        if let Err(err) = self.state.save() {
+            log::warn!("{}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_server::{self, html};
+    use crate::RetryPolicy;
+
+    use super::*;
+
+    /// Serves the `front` page at `/` and an article for every other path.
+    fn serve(front: Arc<Mutex<String>>) -> Url {
+        test_server::serve(move |request| {
+            if request.path == "/" {
+                html(front.lock().unwrap().as_str())
+            } else {
+                html(format!(
+                    "<html><head><title>{}</title></head><body><div itemprop=\"articleBody\">\
+                     <p>The harbour will be expanded over the next ten years.</p></div></body></html>",
+                    request.path
+                ))
+            }
+        })
+    }
+
+    fn front_page(paths: &[&str]) -> String {
+        let links: String = paths
+            .iter()
+            .map(|path| format!("<a href=\"{}\">{}</a>", path, path))
+            .collect();
+        format!("<html><body>{}</body></html>", links)
+    }
+
+    const FIRST: &str = "/news/harbour-expansion-approved-by-the-council.html";
+    const SECOND: &str = "/news/new-ferry-line-opens-next-spring.html";
+    const THIRD: &str = "/news/mayor-visits-the-harbour-construction-site.html";
+
+    fn paths(events: &[MonitorEvent]) -> Vec<String> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                MonitorEvent::Article(article) => Some(article.url.path().to_string()),
+                MonitorEvent::Cycle(_) => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn yields_only_new_articles() {
+        let front = Arc::new(Mutex::new(front_page(&[FIRST, SECOND])));
+        let url = serve(Arc::clone(&front));
+        let state_path = std::env::temp_dir().join(format!(
+            "extrablatt-monitor-{}.json",
+            url.port().unwrap()
+        ));
+        let site = Extrablatt::builder(url)
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await
+            .unwrap();
+        let config = MonitorConfig {
+            interval: Duration::from_millis(10),
+            state_path: Some(state_path.clone()),
+            max_cycles: Some(2),
+            ..Default::default()
+        };
+        let mut monitor = Box::pin(site.monitor(config).unwrap());
+
+        let mut first_cycle = Vec::new();
+        while let Some(event) = monitor.next().await {
+            let event = event.unwrap();
+            let done = matches!(event, MonitorEvent::Cycle(_));
+            first_cycle.push(event);
+            if done {
+                break;
+            }
+        }
+        let mut first_paths = paths(&first_cycle);
+        first_paths.sort();
+        assert_eq!(first_paths, vec![FIRST.to_string(), SECOND.to_string()]);
+
+        *front.lock().unwrap() = front_page(&[THIRD, FIRST, SECOND]);
+        let second_cycle: Vec<_> = monitor.map(Result::unwrap).collect().await;
+        assert_eq!(paths(&second_cycle), vec![THIRD.to_string()]);
+        match second_cycle.last() {
+            Some(MonitorEvent::Cycle(stats)) => {
+                assert_eq!(stats.cycle, 2);
+                assert_eq!(stats.discovered, 3);
+                assert_eq!(stats.new, 1);
+                assert_eq!(stats.articles, 1);
+            }
+            event => panic!("expected cycle stats, got {:?}", event),
+        }
+
+        let state = MonitorState::load(&state_path).unwrap();
+        assert_eq!(state.len(), 3);
+        std::fs::remove_file(state_path).unwrap();
+    }
+    #[tokio::test]
+    async fn retries_failed_articles() {
+        let front = front_page(&[FIRST, SECOND, THIRD]);
+        let second_requests = Arc::new(Mutex::new(0));
+        let requests = Arc::clone(&second_requests);
+        let url = test_server::serve(move |request| match request.path.as_str() {
+            "/" => html(&front),
+            SECOND => {
+                let mut requests = requests.lock().unwrap();
+                *requests += 1;
+                if *requests == 1 {
+                    test_server::status("503 Service Unavailable")
+                } else {
+                    html("<html><head><title>Ferry</title></head><body><div itemprop=\"articleBody\">\
+                          <p>The new ferry line opens next spring.</p></div></body></html>")
+                }
+            }
+            THIRD => test_server::status("404 Not Found"),
+            _ => html("<html><head><title>Harbour</title></head><body><div itemprop=\"articleBody\">\
+                       <p>The harbour will be expanded over the next ten years.</p></div></body></html>"),
+        });
+        let site = Extrablatt::builder(url)
+            .unwrap()
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .await
+            .unwrap();
+        let config = MonitorConfig {
+            interval: Duration::from_millis(10),
+            max_cycles: Some(3),
+            max_attempts: 2,
+            ..Default::default()
+        };
+        let events: Vec<_> = Box::pin(site.monitor(config).unwrap()).collect().await;
+        let stats: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Ok(MonitorEvent::Cycle(stats)) => Some((stats.requested, stats.articles, stats.failures)),
+                _ => None,
+            })
+            .collect();
+        // the unavailable article succeeds in the second cycle, the missing
+        // one is given up after two attempts
+        assert_eq!(stats, vec![(3, 1, 2), (2, 1, 1), (0, 0, 0)]);
+        let mut articles: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                Ok(MonitorEvent::Article(article)) => Some(article.url.path().to_string()),
+                _ => None,
+            })
+            .collect();
+        articles.sort();
+        assert_eq!(articles, vec![FIRST.to_string(), SECOND.to_string()]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Output in the JSON shape of python's
+//! [newspaper3k](https://github.com/codelucas/newspaper) and
+//! [newspaper4k](https://github.com/AndyTheFactory/newspaper4k), for
+//! pipelines that are migrated from them.
+//!
+//! | newspaper field    | source                                                    |
+//! |--------------------|-----------------------------------------------------------|
+//! | `url`              | [`PureArticle::url`]                                      |
+//! | `title`            | [`ArticleContent::title`], else `""`                      |
+//! | `authors`          | [`ArticleContent::authors`]                               |
+//! | `publish_date`     | published date of [`ArticleContent::publishing_date`] as ISO 8601 with its offset, else `null` |
+//! | `top_image`        | [`ArticleContent::top_image`], else `""`                  |
+//! | `meta_img`         | [`ArticleContent::thumbnail`], else `top_image`           |
+//! | `images`           | `top_image` followed by [`ArticleContent::images`]        |
+//! | `movies`           | [`ArticleContent::videos`]                                |
+//! | `keywords`         | [`ArticleContent::keywords`]                              |
+//! | `meta_keywords`    | [`ArticleContent::keywords`]                              |
+//! | `tags`             | always `[]`, tags are merged into the keywords            |
+//! | `summary`          | always `""`, there is no summarizer                       |
+//! | `text`             | [`ArticleContent::text`], else `""`                       |
+//! | `meta_description` | [`ArticleContent::description`], else `""`                |
+//! | `meta_lang`        | [`ArticleContent::language`], else `""`                   |
+//! | `language`         | [`PureArticle::language`]                                 |
+//!
+//! All urls are strings, missing lists are empty.
+
+use serde_json::{json, Value};
+
+use crate::article::{ArticleContent, PureArticle};
+use crate::date::{ArticleDate, Date};
+use crate::extract_description::DescriptionSource;
+
+impl PureArticle {
+    /// The article in the JSON shape of newspaper's `Article.to_json()`.
+    ///
+    /// See [`crate::newspaper`] for the mapping of the fields.
+    pub fn to_newspaper_json(&self) -> Value {
+        let content = &self.content;
+        let top_image = content.top_image.as_ref().map(|url| url.to_string());
+        let mut images: Vec<String> = top_image.iter().cloned().collect();
+        images.extend(
+            content
+                .images
+                .iter()
+                .map(|url| url.to_string())
+                .filter(|url| Some(url) != top_image.as_ref()),
+        );
+
+        json!({
+            "url": self.url.as_str(),
+            "language": self.language.identifier(),
+            "title": content.title.as_deref().unwrap_or_default(),
+            "top_image": top_image.clone().unwrap_or_default(),
+            "meta_img": content
+                .thumbnail
+                .as_ref()
+                .map(|url| url.to_string())
+                .or(top_image)
+                .unwrap_or_default(),
+            "images": images,
+            "movies": content.videos.iter().map(|url| url.as_str()).collect::<Vec<_>>(),
+            "keywords": keywords(content),
+            "meta_keywords": keywords(content),
+            "tags": Vec::<String>::new(),
+            "authors": content.authors,
+            "publish_date": content.publishing_date.as_ref().map(iso_date),
+            "summary": "",
+            "meta_description": content
+                .description
+                .as_deref()
+                .filter(|_| content.description_source != Some(DescriptionSource::Excerpt))
+                .unwrap_or_default(),
+            "meta_lang": content
+                .language
+                .as_ref()
+                .map(|lang| lang.identifier())
+                .unwrap_or_default(),
+            "text": content.text.as_deref().unwrap_or_default(),
+        })
+    }
+}
+
+fn keywords<'a>(content: &'a ArticleContent<'_>) -> Vec<&'a str> {
+    content.keywords.iter().map(|k| k.as_ref()).collect()
+}
+
+/// The date the article was published in ISO 8601, like python's
+/// `datetime.isoformat()`.
+fn iso_date(date: &ArticleDate) -> String {
+    if let Some(date) = date.published_with_offset() {
+        return date.format("%Y-%m-%dT%H:%M:%S%:z").to_string();
+    }
+    match &date.published {
+        Date::Date(date) => date.format("%Y-%m-%d").to_string(),
+        Date::DateTime(date) => date.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+    use url::Url;
+
+    use crate::extract::{DefaultExtractor, Extractor};
+
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../tests/fixtures/newspaper/harbour.html");
+    /// Regenerated from newspaper3k by `tests/fixtures/newspaper/generate.py`.
+    const GOLDEN: &str = include_str!("../tests/fixtures/newspaper/harbour.json");
+
+    fn fixture_article() -> PureArticle {
+        let url = Url::parse("https://example.com/news/harbour.html").unwrap();
+        let doc = Document::from(FIXTURE);
+        let content = DefaultExtractor::default()
+            .article_content(&doc, Some(&url), None)
+            .into_owned();
+        PureArticle {
+            url,
+            language: content.language.clone().unwrap_or_default(),
+            content,
+            fetch_info: None,
+        }
+    }
+
+    #[test]
+    fn golden_newspaper_json() {
+        let json = fixture_article().to_newspaper_json();
+        let golden: Value = serde_json::from_str(GOLDEN).unwrap();
+
+        let fields = golden.as_object().unwrap();
+        assert_eq!(
+            json.as_object().unwrap().keys().collect::<Vec<_>>(),
+            fields.keys().collect::<Vec<_>>()
+        );
+        for (field, expected) in fields {
+            assert_eq!(&json[field], expected, "field {}", field);
+        }
+    }
+
+    #[test]
+    fn empty_defaults() {
+        let article = PureArticle {
+            url: Url::parse("https://example.com/").unwrap(),
+            content: ArticleContent::builder().build(),
+            language: Default::default(),
+            fetch_info: None,
+        };
+        let json = article.to_newspaper_json();
+        assert_eq!(json["title"], "");
+        assert_eq!(json["top_image"], "");
+        assert_eq!(json["publish_date"], Value::Null);
+        assert_eq!(json["movies"], json!([]));
+        assert_eq!(json["authors"], json!([]));
+        assert_eq!(json["summary"], "");
+    }
+}
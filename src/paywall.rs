@@ -0,0 +1,273 @@
+//! Heuristics for articles behind a paywall, whose page only holds a teaser
+//! of the text.
+
+use select::document::Document;
+use select::node::Node;
+use select::predicate::{Any, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::extract_json_ld::{json_ld, values};
+use crate::text::class_parts;
+
+/// Parts of class and id tokens of the containers of paywalls, like
+/// `paywall-overlay` or `piano-offer`.
+const PAYWALL_PARTS: [&str; 3] = ["paywall", "paywalled", "piano"];
+
+/// Class and id tokens of the modal of the Piano paywall.
+const PAYWALL_TOKENS: [&str; 2] = ["tp-modal", "tp-backdrop"];
+
+/// Parts of class and id tokens of the containers of metered content, like
+/// `meteredContent`.
+const METERED_PARTS: [&str; 1] = ["metered"];
+
+/// Lowercase phrases of prompts to subscribe or to sign in to read on.
+///
+/// The Russian ones are stems of the word forms of "subscription",
+/// "subscriber" and "subscribe", that don't match "подписал" ("signed").
+const SUBSCRIBE_PHRASES: [&str; 15] = [
+    "subscribe",
+    "subscription",
+    "subscriber",
+    "sign in to read",
+    "log in to read",
+    "continue reading",
+    "abonnieren",
+    "abonnement",
+    "abonnez",
+    "suscríbete",
+    "подписк",
+    "подписчик",
+    "подпишитесь",
+    "подписаться",
+    "оформите",
+];
+
+/// Max number of chars of an element that prompts to subscribe, longer
+/// elements are paragraphs of the article that mention subscriptions.
+const MAX_PROMPT_LEN: usize = 200;
+
+/// A text that is shorter than this multiple of the description is a
+/// teaser.
+const TEASER_RATIO: usize = 2;
+
+/// Whether the full text of an article is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum ContentAccess {
+    /// The page holds the full text.
+    Free,
+    /// The page holds the full text, but a meter hides it after a number of
+    /// free articles.
+    Metered,
+    /// The page only holds a teaser of the text.
+    Paywalled,
+    /// There is no text to judge.
+    #[default]
+    Unknown,
+}
+
+impl ContentAccess {
+    /// Whether the text of the article is only a teaser.
+    pub fn is_paywalled(&self) -> bool {
+        *self == ContentAccess::Paywalled
+    }
+}
+
+/// Whether the `text` the article `node` of the `doc` holds is the full text
+/// of the article.
+///
+/// Uses the first of these that applies:
+///   1. The `isAccessibleForFree` of a JSON-LD object or of its `hasPart`
+///   2. A container with a class or id like `paywall`, `piano` or `tp-modal`
+///      marks a paywall, like `meteredContent` a meter
+///   3. A text that ends with an ellipsis or is shorter than twice the
+///      `description`, while the node prompts to subscribe, is a teaser
+///
+/// Articles without text are [`ContentAccess::Unknown`], all others
+/// [`ContentAccess::Free`].
+pub fn content_access(
+    doc: &Document,
+    node: Option<&Node>,
+    text: Option<&str>,
+    description: Option<&str>,
+) -> ContentAccess {
+    if let Some(free) = json_ld_accessible_for_free(doc) {
+        return if free {
+            ContentAccess::Free
+        } else {
+            ContentAccess::Paywalled
+        };
+    }
+    if doc.find(Any).any(|node| is_container(&node, &PAYWALL_PARTS) || has_paywall_token(&node)) {
+        return ContentAccess::Paywalled;
+    }
+    if doc.find(Any).any(|node| is_container(&node, &METERED_PARTS)) {
+        return ContentAccess::Metered;
+    }
+    let text = match text.map(str::trim).filter(|text| !text.is_empty()) {
+        Some(text) => text,
+        None => return ContentAccess::Unknown,
+    };
+    if node.map(prompts_to_subscribe).unwrap_or_default() {
+        let truncated = text.ends_with('…') || text.ends_with("...");
+        let teaser = description
+            .map(|description| text.chars().count() < description.trim().chars().count() * TEASER_RATIO)
+            .unwrap_or_default();
+        if truncated || teaser {
+            return ContentAccess::Paywalled;
+        }
+    }
+    ContentAccess::Free
+}
+
+/// The `isAccessibleForFree` of the JSON-LD objects, `false` if the object
+/// or one of its `hasPart`s isn't accessible for free.
+fn json_ld_accessible_for_free(doc: &Document) -> Option<bool> {
+    json_ld(doc).iter().find_map(|object| {
+        let parts = values(object, "hasPart");
+        if parts.iter().any(|part| accessible_for_free(part) == Some(false)) {
+            return Some(false);
+        }
+        accessible_for_free(object)
+    })
+}
+
+/// The `isAccessibleForFree` of the object, a boolean or a string like
+/// `"False"`.
+fn accessible_for_free(object: &Value) -> Option<bool> {
+    match object.get("isAccessibleForFree")? {
+        Value::Bool(free) => Some(*free),
+        Value::String(free) => free.trim().to_lowercase().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Whether a class or id token of the node has one of the `parts`.
+fn is_container(node: &Node, parts: &[&str]) -> bool {
+    tokens(node).any(|token| class_parts(token).iter().any(|part| parts.contains(&part.as_str())))
+}
+
+fn has_paywall_token(node: &Node) -> bool {
+    tokens(node).any(|token| PAYWALL_TOKENS.contains(&token.to_lowercase().as_str()))
+}
+
+fn tokens<'a>(node: &Node<'a>) -> impl Iterator<Item = &'a str> {
+    ["class", "id"]
+        .into_iter()
+        .filter_map(|attr| node.attr(attr))
+        .flat_map(str::split_whitespace)
+}
+
+/// Whether a short link, button or paragraph of the `node` prompts to
+/// subscribe.
+fn prompts_to_subscribe(node: &Node) -> bool {
+    let prompt = Name("a")
+        .or(Name("button"))
+        .or(Name("p"))
+        .or(Name("span"))
+        .or(Name("h2"))
+        .or(Name("h3"))
+        .or(Name("div"));
+    node.find(prompt).any(|element| {
+        let text = element.text();
+        let text = text.trim();
+        !text.is_empty()
+            && text.chars().count() <= MAX_PROMPT_LEN
+            && SUBSCRIBE_PHRASES.iter().any(|phrase| text.to_lowercase().contains(phrase))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extract::{DefaultExtractor, Extractor};
+    use crate::extrablatt::Config;
+    use crate::Language;
+
+    use super::*;
+
+    fn access(html: &str) -> ContentAccess {
+        let doc = Document::from(html);
+        DefaultExtractor::default().article_content_in_language(&doc, None, Language::English).access
+    }
+
+    #[test]
+    fn paywall_signals() {
+        let body = "<div itemprop=\"articleBody\"><p>The council approved the expansion of the harbour on \
+            Tuesday. Construction is expected to start next spring and to last for three years.</p></div>";
+        assert_eq!(access(&format!("<html><body>{}</body></html>", body)), ContentAccess::Free);
+        assert_eq!(
+            access(&format!(
+                r#"<html><head><script type="application/ld+json">{{"@type": "NewsArticle", "isAccessibleForFree": "False",
+                "hasPart": {{"@type": "WebPageElement", "isAccessibleForFree": "False", "cssSelector": ".locked"}}}}
+                </script></head><body>{}</body></html>"#,
+                body
+            )),
+            ContentAccess::Paywalled
+        );
+        assert_eq!(
+            access(&format!("<html><body>{}<div class=\"tp-modal\"></div></body></html>", body)),
+            ContentAccess::Paywalled
+        );
+        assert_eq!(
+            access(&format!("<html><body><section class=\"meteredContent\">{}</section></body></html>", body)),
+            ContentAccess::Metered
+        );
+        assert_eq!(access("<html><body></body></html>"), ContentAccess::Unknown);
+
+        let doc = Document::from(
+            format!("<html><body><div class=\"paywall\">{}</div></body></html>", body).as_str(),
+        );
+        let content = DefaultExtractor::default().article_content_in_language(&doc, None, Language::English);
+        assert!(Config::default().is_complete(&content));
+        assert!(!Config::builder().reject_paywalled(true).build().is_complete(&content));
+    }
+
+    #[test]
+    fn truncated_teaser() {
+        let doc = Document::from(
+            r#"<html><head><meta name="description" content="The council approved the expansion of the harbour."></head>
+            <body><article><p>The council approved the expansion of the harbour on Tuesday. Construction is expected
+            to start next spring and, according to the mayor, the costs…</p>
+            <p class="cta"><a href="/subscribe">Subscribe to continue reading</a></p></article></body></html>"#,
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        let text = "The council approved the expansion of the harbour on Tuesday. Construction is expected \
+            to start next spring and, according to the mayor, the costs…";
+        assert_eq!(
+            content_access(&doc, Some(&article), Some(text), None),
+            ContentAccess::Paywalled
+        );
+        assert_eq!(
+            content_access(&doc, Some(&article), Some("The council approved the expansion."), Some(
+                "The council approved the expansion of the harbour."
+            )),
+            ContentAccess::Paywalled
+        );
+        assert_eq!(
+            content_access(&doc, None, Some(text), None),
+            ContentAccess::Free
+        );
+
+        let doc = Document::from(
+            r#"<html><body><article><p>Мэр подписал указ о расширении порта. Строительство начнётся весной…</p>
+            </article></body></html>"#,
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        let text = "Мэр подписал указ о расширении порта. Строительство начнётся весной…";
+        assert_eq!(
+            content_access(&doc, Some(&article), Some(text), None),
+            ContentAccess::Free
+        );
+        let doc = Document::from(
+            r#"<html><body><article><p>Мэр подписал указ о расширении порта. Строительство начнётся весной…</p>
+            <p><a href="/subscribe">Читать дальше можно по подписке</a></p></article></body></html>"#,
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        assert_eq!(
+            content_access(&doc, Some(&article), Some(text), None),
+            ContentAccess::Paywalled
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use select::node::Node;
+use select::predicate::Name;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::text::TextContainer;
+
+/// Hosts of link shorteners and networks that are only used for affiliate
+/// links.
+const AFFILIATE_HOSTS: [&str; 10] = [
+    "amzn.to",
+    "amzn.eu",
+    "go.skimresources.com",
+    "click.linksynergy.com",
+    "shareasale.com",
+    "awin1.com",
+    "anrdoezrs.net",
+    "tkqlhce.com",
+    "howl.me",
+    "ali.ski",
+];
+
+/// Query parameters that carry an affiliate or tracking id.
+///
+/// `tag` is only treated as affiliate param on amazon hosts, `erid` is the
+/// token of the russian ad register.
+const AFFILIATE_QUERY_PARAMS: [&str; 7] = [
+    "affid",
+    "aff_id",
+    "affiliate_id",
+    "clickref",
+    "subid",
+    "ascsubtag",
+    "erid",
+];
+
+/// Labels that mark a paragraph as paid content.
+const AD_LABELS: [&str; 5] = ["реклама", "sponsored", "advertisement", "anzeige", "publicité"];
+
+/// Min share of link text in a nofollow paragraph before it is considered a
+/// promotional footer, unless it is one of the last paragraphs.
+pub const PROMO_FOOTER_MIN_LINK_DENSITY: f64 = 0.7;
+
+/// Number of paragraphs at the end of an article in which nofollow
+/// paragraphs are promotional footers regardless of their link density.
+pub const PROMO_FOOTER_TAIL_PARAGRAPHS: usize = 2;
+
+/// Labels of paragraphs that list the sources of an article.
+const SOURCE_LABELS: [&str; 10] = [
+    "source:",
+    "source :",
+    "sources",
+    "reference",
+    "further reading",
+    "источник",
+    "по материалам",
+    "quelle",
+    "fuente",
+    "bron",
+];
+
+/// A paragraph that lists the sources of an article, like
+/// `Sources: <a>Reuters</a>, <a>AP</a>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Attribution {
+    /// The text of the paragraph.
+    pub text: String,
+    /// All links of the paragraph.
+    pub links: Vec<Url>,
+}
+
+/// A promotional paragraph of an article, like a call-to-action or an ad.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct PromoBlock {
+    /// The text of the paragraph.
+    pub text: String,
+    /// All links of the paragraph.
+    pub links: Vec<Url>,
+}
+
+/// Whether the url is an affiliate link, either by its host or by a
+/// tracking parameter.
+pub fn is_affiliate_link(url: &Url) -> bool {
+    let host = url.host_str().unwrap_or_default().trim_start_matches("www.");
+    if AFFILIATE_HOSTS.iter().any(|h| host == *h || host.ends_with(&format!(".{}", h))) {
+        return true;
+    }
+    let is_amazon = host.split('.').any(|label| label == "amazon");
+    url.query_pairs().any(|(key, _)| {
+        let key = key.to_lowercase();
+        AFFILIATE_QUERY_PARAMS.contains(&key.as_str()) || (is_amazon && key == "tag")
+    })
+}
+
+/// Whether the text starts with a label that marks it as paid content, like
+/// `Реклама. ООО «Ромашка», erid: 2Vtzqx...`.
+pub fn is_ad_labeled(text: &str) -> bool {
+    let text = text.trim_start().to_lowercase();
+    AD_LABELS.iter().any(|label| text.starts_with(label)) || text.contains("erid:")
+}
+
+/// Whether the paragraph is the `index`th of `total` paragraphs and a
+/// promotional footer.
+///
+/// All links of a promotional footer are `rel="nofollow"` and it's either
+/// mostly link text or one of the last [`PROMO_FOOTER_TAIL_PARAGRAPHS`]
+/// paragraphs. Lists of sources are never promotional.
+pub fn is_promotional_footer(para: &Node, index: usize, total: usize) -> bool {
+    let mut links = para.find(Name("a")).peekable();
+    let all_nofollow = links.peek().is_some()
+        && links.all(|link| {
+            link.attr("rel")
+                .map(|rel| rel.contains("nofollow"))
+                .unwrap_or_default()
+        });
+    all_nofollow
+        && !is_source_list(para)
+        && (para.link_density() > PROMO_FOOTER_MIN_LINK_DENSITY
+            || index + PROMO_FOOTER_TAIL_PARAGRAPHS >= total)
+}
+
+/// Whether the paragraph lists the sources of the article: it starts with a
+/// label like `Sources:` and contains links.
+pub fn is_source_list(para: &Node) -> bool {
+    let text = para.text().trim_start().to_lowercase();
+    SOURCE_LABELS.iter().any(|label| text.starts_with(label))
+        && para.find(Name("a")).next().is_some()
+}
+
+/// The paragraph as [`Attribution`] if it lists the sources of the article.
+pub fn attribution(para: &Node, base_url: Option<&Url>) -> Option<Attribution> {
+    if !is_source_list(para) {
+        return None;
+    }
+    Some(Attribution {
+        text: para.text().split_whitespace().collect::<Vec<_>>().join(" "),
+        links: links(para, base_url),
+    })
+}
+
+/// The links of the node, resolved against the `base_url`.
+pub fn links(node: &Node, base_url: Option<&Url>) -> Vec<Url> {
+    let options = Url::options().base_url(base_url);
+    node.find(Name("a"))
+        .filter_map(|link| link.attr("href"))
+        .filter_map(|href| options.parse(href.trim()).ok())
+        .collect()
+}
+
+/// The `index`th of `total` paragraphs as [`PromoBlock`] if it is a
+/// promotional footer, labeled as ad or contains affiliate links.
+pub fn promo_block(
+    para: &Node,
+    index: usize,
+    total: usize,
+    base_url: Option<&Url>,
+) -> Option<PromoBlock> {
+    let text = para.text().split_whitespace().collect::<Vec<_>>().join(" ");
+    let links = links(para, base_url);
+    if is_promotional_footer(para, index, total)
+        || is_ad_labeled(&text)
+        || links.iter().any(is_affiliate_link)
+    {
+        Some(PromoBlock { text, links })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+
+    use super::*;
+
+    #[test]
+    fn affiliate_links() {
+        let url = |s| Url::parse(s).unwrap();
+        assert!(is_affiliate_link(&url("https://amzn.to/3xYz")));
+        assert!(is_affiliate_link(&url("https://www.amazon.de/dp/B01?tag=site-21")));
+        assert!(is_affiliate_link(&url("https://shop.example.com/p/1?erid=2Vtzqx")));
+        assert!(!is_affiliate_link(&url("https://example.com/page?tag=news")));
+        assert!(!is_affiliate_link(&url("https://www.amazon.com/dp/B01")));
+    }
+
+    #[test]
+    fn nofollow_sources_are_not_promotional() {
+        let doc = Document::from(
+            r#"<p>Sources: <a rel="nofollow" href="https://reuters.com/a">Reuters report on the harbour</a>,
+            <a rel="nofollow" href="https://apnews.com/b">AP</a></p>
+            <p>The council will vote again in May, with the <a rel="nofollow" href="/plan">plan</a> published before.</p>
+            <p>More in our <a rel="nofollow" href="/newsletter">newsletter</a></p>
+            <p>Closing remarks of the article that aren't linked.</p>"#,
+        );
+        let paras: Vec<_> = doc.find(Name("p")).collect();
+        assert!(!is_promotional_footer(&paras[0], 0, 4));
+        assert!(attribution(&paras[0], None).is_some());
+        assert!(!is_promotional_footer(&paras[1], 1, 4));
+        assert!(is_promotional_footer(&paras[2], 2, 4));
+    }
+
+    #[test]
+    fn promo_paragraphs() {
+        let doc = Document::from(
+            r#"<p>Реклама. ООО «Ромашка», erid: 2Vtzqx</p>
+            <p>Buy it <a href="https://amzn.to/3xYz">here</a>.</p>
+            <p>Скачать <a rel="nofollow" href="/app">приложение</a></p>
+            <p>The phone has a <a href="/review">good camera</a>.</p>"#,
+        );
+        let base = Url::parse("https://example.com/").unwrap();
+        let blocks: Vec<_> = doc
+            .find(Name("p"))
+            .enumerate()
+            .filter_map(|(i, p)| promo_block(&p, i, 4, Some(&base)))
+            .collect();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[1].text, "Buy it here.");
+        assert_eq!(blocks[2].links, vec![Url::parse("https://example.com/app").unwrap()]);
+    }
+}
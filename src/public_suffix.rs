@@ -0,0 +1,46 @@
+//! Registrable domains of hosts according to Mozilla's
+//! [Public Suffix List](https://publicsuffix.org).
+
+use std::net::IpAddr;
+
+use lazy_static::lazy_static;
+use publicsuffix::{List, Psl};
+
+lazy_static! {
+    /// The vendored snapshot of the list, see `data/public_suffix_list.dat`.
+    static ref LIST: List = include_str!("../data/public_suffix_list.dat")
+        .parse()
+        .expect("the vendored public suffix list is valid");
+}
+
+/// The registrable domain of the `host`: its public suffix and one more
+/// label, like `example.co.uk` for `news.example.co.uk`.
+///
+/// IP addresses and hosts that are a public suffix themselves are returned
+/// unchanged.
+pub(crate) fn registrable_domain(host: &str) -> &str {
+    let host = host.trim_end_matches('.');
+    if host.starts_with('[') || host.parse::<IpAddr>().is_ok() {
+        return host;
+    }
+    LIST.domain(host.as_bytes())
+        .map(|domain| &host[host.len() - domain.as_bytes().len()..])
+        .unwrap_or(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registrable_domains() {
+        assert_eq!(registrable_domain("www.example.com"), "example.com");
+        assert_eq!(registrable_domain("news.example.co.uk"), "example.co.uk");
+        assert_eq!(registrable_domain("news.example.com.au."), "example.com.au");
+        assert_eq!(registrable_domain("paper.github.io"), "paper.github.io");
+        assert_eq!(registrable_domain("city.kawasaki.jp"), "city.kawasaki.jp");
+        assert_eq!(registrable_domain("co.uk"), "co.uk");
+        assert_eq!(registrable_domain("127.0.0.1"), "127.0.0.1");
+        assert_eq!(registrable_domain("[::1]"), "[::1]");
+    }
+}
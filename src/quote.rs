@@ -0,0 +1,147 @@
+use select::node::{Data, Node};
+use select::predicate::{Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::embed::is_social_embed;
+use crate::text::normalize_whitespace;
+
+/// Classes of the elements that highlight a statement of the article.
+pub const PULL_QUOTE_CLASSES: [&str; 3] = ["pullquote", "pull-quote", "pull_quote"];
+
+/// A statement the article highlights in a `<blockquote>` or a pull quote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Quote {
+    /// The quoted text without the attribution.
+    pub text: String,
+    /// Who is quoted, taken from a `<cite>` or `<footer>` within the quote or
+    /// the `<figcaption>` of its figure.
+    pub attribution: Option<String>,
+}
+
+/// All quotes within the `node`, in document order.
+///
+/// Collects `<blockquote>` elements that don't embed social media posts, see
+/// [`crate::embed::is_social_embed`], and elements with one of the
+/// [`PULL_QUOTE_CLASSES`]. Quotes nested in another quote are part of the
+/// outer one.
+pub fn quotes(node: &Node) -> Vec<Quote> {
+    let mut collected: Vec<Node> = Vec::new();
+    for candidate in node.find(is_quote) {
+        if collected.iter().any(|quote| is_ancestor(quote, &candidate)) {
+            continue;
+        }
+        collected.push(candidate);
+    }
+    collected.iter().filter_map(quote).collect()
+}
+
+/// Whether the node is a quote.
+fn is_quote(node: &Node) -> bool {
+    if Name("blockquote").matches(node) {
+        return !is_social_embed(node);
+    }
+    has_class(node, &PULL_QUOTE_CLASSES)
+}
+
+fn has_class(node: &Node, classes: &[&str]) -> bool {
+    node.attr("class")
+        .map(|class| {
+            class
+                .split_whitespace()
+                .any(|token| classes.iter().any(|c| token.eq_ignore_ascii_case(c)))
+        })
+        .unwrap_or_default()
+}
+
+fn is_ancestor(ancestor: &Node, node: &Node) -> bool {
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        if parent.index() == ancestor.index() {
+            return true;
+        }
+        current = parent.parent();
+    }
+    false
+}
+
+fn quote(node: &Node) -> Option<Quote> {
+    let mut text = String::new();
+    quote_text(*node, &mut text);
+    let text = normalize_whitespace(&text);
+    if text.is_empty() {
+        return None;
+    }
+    let attribution = node
+        .find(Name("cite").or(Name("footer")))
+        .next()
+        .or_else(|| {
+            let figure = node.parent().filter(|parent| Name("figure").matches(parent))?;
+            figure.find(Name("figcaption")).next()
+        })
+        .map(|cite| {
+            normalize_whitespace(&cite.text())
+                .trim_start_matches(['—', '–', '-', '~'])
+                .trim()
+                .to_string()
+        })
+        .filter(|attribution| !attribution.is_empty());
+    Some(Quote { text, attribution })
+}
+
+/// The text of the node without its `<cite>` and `<footer>` elements.
+fn quote_text(node: Node, text: &mut String) {
+    match node.data() {
+        Data::Text(txt) => text.push_str(txt),
+        Data::Element(..) => {
+            if Name("cite").or(Name("footer")).or(Name("script")).matches(&node) {
+                return;
+            }
+            // keep paragraphs apart
+            text.push(' ');
+            for child in node.children() {
+                quote_text(child, text);
+            }
+        }
+        Data::Comment(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+
+    use super::*;
+
+    #[test]
+    fn blockquotes_and_pull_quotes() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>The council approved the expansion of the harbour on Tuesday.</p>
+            <blockquote><p>This is a historic day for the city.</p><footer>— Mayor Jane Doe</footer></blockquote>
+            <blockquote class="twitter-tweet"><p>Big news for the harbour!</p></blockquote>
+            <aside class="pullquote"><blockquote>We waited twenty years for this.</blockquote></aside>
+            <figure><blockquote>It will create jobs.</blockquote><figcaption>Port director</figcaption></figure>
+            </article></body></html>"#,
+        );
+        let article = doc.find(Name("article")).next().unwrap();
+        assert_eq!(
+            quotes(&article),
+            vec![
+                Quote {
+                    text: "This is a historic day for the city.".to_string(),
+                    attribution: Some("Mayor Jane Doe".to_string()),
+                },
+                Quote {
+                    text: "We waited twenty years for this.".to_string(),
+                    attribution: None,
+                },
+                Quote {
+                    text: "It will create jobs.".to_string(),
+                    attribution: Some("Port director".to_string()),
+                },
+            ]
+        );
+    }
+}
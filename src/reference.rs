@@ -0,0 +1,247 @@
+use select::node::Node;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::public_suffix::registrable_domain;
+
+/// Hosts of social networks and messengers, subdomains like
+/// `mobile.twitter.com` included.
+pub const SOCIAL_HOSTS: [&str; 21] = [
+    "twitter.com",
+    "x.com",
+    "t.co",
+    "facebook.com",
+    "fb.com",
+    "fb.me",
+    "instagram.com",
+    "threads.net",
+    "tiktok.com",
+    "linkedin.com",
+    "reddit.com",
+    "bsky.app",
+    "mastodon.social",
+    "t.me",
+    "telegram.me",
+    "wa.me",
+    "whatsapp.com",
+    "vk.com",
+    "ok.ru",
+    "pinterest.com",
+    "youtube.com",
+];
+
+/// Where a link of an article leads to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum RefKind {
+    /// Another page of the article's site, like `news.example.co.uk` for an
+    /// article on `www.example.co.uk`.
+    Internal,
+    /// A page of another site.
+    External,
+    /// A profile or post of a social network, see [`SOCIAL_HOSTS`].
+    Social,
+}
+
+impl RefKind {
+    /// The kind of the `url` of a link in the article at the `article_url`.
+    ///
+    /// Links to the article's own site, with the same registrable domain by
+    /// the public suffix list, are internal, even if the site is a social
+    /// network. So `news.example.co.uk` is internal to `www.example.co.uk`,
+    /// but `b.github.io` isn't to `a.github.io`. Without an `article_url`
+    /// every other link is external.
+    pub fn of(url: &Url, article_url: Option<&Url>) -> Self {
+        let site = |url: &Url| url.host_str().map(|host| registrable_domain(host).to_lowercase());
+        if article_url.and_then(site).is_some_and(|article_site| site(url) == Some(article_site)) {
+            RefKind::Internal
+        } else if is_social_url(url) {
+            RefKind::Social
+        } else {
+            RefKind::External
+        }
+    }
+}
+
+/// Whether the host of the `url` is one of the [`SOCIAL_HOSTS`] or their
+/// subdomains.
+pub fn is_social_url(url: &Url) -> bool {
+    url.host_str()
+        .map(|host| SOCIAL_HOSTS.iter().any(|social| host_matches(host, social)))
+        .unwrap_or_default()
+}
+
+/// A link of the article with its anchor text and kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Reference {
+    pub url: Url,
+    pub kind: RefKind,
+    /// The text of the `<a>`, with collapsed whitespace.
+    pub anchor_text: String,
+    /// Whether the `rel` of the link contains `nofollow`, `sponsored` or
+    /// `ugc`, which don't endorse the target.
+    pub nofollow: bool,
+}
+
+impl Reference {
+    /// The reference of the `<a>` node that links to the `url`.
+    pub fn from_node(node: &Node, url: Url, article_url: Option<&Url>) -> Self {
+        let nofollow = node
+            .attr("rel")
+            .map(|rel| {
+                rel.split_whitespace().any(|rel| {
+                    ["nofollow", "sponsored", "ugc"]
+                        .iter()
+                        .any(|value| rel.eq_ignore_ascii_case(value))
+                })
+            })
+            .unwrap_or_default();
+        Self {
+            kind: RefKind::of(&url, article_url),
+            anchor_text: node.text().split_whitespace().collect::<Vec<_>>().join(" "),
+            nofollow,
+            url,
+        }
+    }
+}
+
+/// References grouped by their [`RefKind`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct ClassifiedRefs {
+    pub internal: Vec<Url>,
+    pub external: Vec<Url>,
+    pub social: Vec<Url>,
+}
+
+/// Group the `refs` of the article at the `article_url` by their
+/// [`RefKind`], keeping their order.
+pub fn classify_references(refs: &[Url], article_url: &Url) -> ClassifiedRefs {
+    let mut classified = ClassifiedRefs::default();
+    for url in refs {
+        match RefKind::of(url, Some(article_url)) {
+            RefKind::Internal => classified.internal.push(url.clone()),
+            RefKind::External => classified.external.push(url.clone()),
+            RefKind::Social => classified.social.push(url.clone()),
+        }
+    }
+    classified
+}
+
+/// Hosts the references of articles are restricted to or skipped for.
+///
+/// A host matches itself and its subdomains, `example.com` also matches
+/// `www.example.com`. Denied hosts take precedence, if any hosts are allowed
+/// the references to all others are skipped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostFilter {
+    allow: Vec<String>,
+    deny: Vec<String>,
+}
+
+impl HostFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep only references to the `host` and the other allowed hosts.
+    pub fn allow<T: AsRef<str>>(mut self, host: T) -> Self {
+        self.allow.push(normalize_host(host.as_ref()));
+        self
+    }
+
+    /// Skip references to the `host`, like an ad network.
+    pub fn deny<T: AsRef<str>>(mut self, host: T) -> Self {
+        self.deny.push(normalize_host(host.as_ref()));
+        self
+    }
+
+    /// Whether references to the `url` are kept.
+    pub fn allows(&self, url: &Url) -> bool {
+        let host = match url.host_str() {
+            Some(host) => host,
+            None => return self.allow.is_empty(),
+        };
+        if self.deny.iter().any(|denied| host_matches(host, denied)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|allowed| host_matches(host, allowed))
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Whether the `host` is the `domain` or one of its subdomains.
+fn host_matches(host: &str, domain: &str) -> bool {
+    let host = host.trim_end_matches('.');
+    host.eq_ignore_ascii_case(domain)
+        || (host.len() > domain.len()
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+            && host[host.len() - domain.len()..].eq_ignore_ascii_case(domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+    use select::predicate::Name;
+
+    use super::*;
+
+    #[test]
+    fn classify() {
+        let article = Url::parse("https://www.example.co.uk/news/harbour").unwrap();
+        let refs: Vec<Url> = [
+            "https://news.example.co.uk/harbour/map",
+            "https://example.co.uk/",
+            "https://other.co.uk/report.pdf",
+            "https://mobile.twitter.com/harbour_city",
+            "https://t.me/harbour_news",
+            "https://notx.com/",
+        ]
+        .iter()
+        .map(|url| Url::parse(url).unwrap())
+        .collect();
+        let classified = classify_references(&refs, &article);
+        assert_eq!(classified.internal, refs[..2]);
+        assert_eq!(classified.social, refs[3..5]);
+        assert_eq!(classified.external, vec![refs[2].clone(), refs[5].clone()]);
+    }
+
+    #[test]
+    fn classify_by_public_suffix() {
+        let url = |url: &str| Url::parse(url).unwrap();
+        let kind = |link: &str, article: &str| RefKind::of(&url(link), Some(&url(article)));
+        assert_eq!(kind("https://b.github.io/", "https://a.github.io/post"), RefKind::External);
+        assert_eq!(kind("https://a.github.io/about", "https://a.github.io/post"), RefKind::Internal);
+        assert_eq!(kind("https://shop.example.com.au/", "https://www.example.com.au/"), RefKind::Internal);
+        assert_eq!(kind("https://other.com.au/", "https://www.example.com.au/"), RefKind::External);
+        assert_eq!(kind("https://b.blogspot.com/", "https://a.blogspot.com/"), RefKind::External);
+    }
+
+    #[test]
+    fn reference_of_link() {
+        let doc = Document::from(r#"<a href="https://other.com/" rel="Sponsored noopener"> The  report </a>"#);
+        let node = doc.find(Name("a")).next().unwrap();
+        let reference = Reference::from_node(&node, Url::parse("https://other.com/").unwrap(), None);
+        assert_eq!(reference.anchor_text, "The report");
+        assert_eq!(reference.kind, RefKind::External);
+        assert!(reference.nofollow);
+    }
+
+    #[test]
+    fn host_filter() {
+        let url = |url: &str| Url::parse(url).unwrap();
+        let filter = HostFilter::new().deny("ads.example.net");
+        assert!(!filter.allows(&url("https://cdn.ads.example.net/click")));
+        assert!(filter.allows(&url("https://example.net/")));
+
+        let filter = HostFilter::new().allow("Example.com").deny("shop.example.com");
+        assert!(filter.allows(&url("https://www.example.com/news")));
+        assert!(!filter.allows(&url("https://shop.example.com/")));
+        assert!(!filter.allows(&url("https://badexample.com/")));
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, Response, StatusCode, Url};
+use wasm_timer::Delay;
+
+/// Controls whether and when failed downloads are repeated.
+///
+/// Retries are delayed with an exponential backoff with jitter, starting at
+/// `base_delay` and capped at `max_delay`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Max number of retries after the first attempt.
+    pub max_retries: usize,
+    /// Delay before the first retry, doubled for each following retry.
+    pub base_delay: Duration,
+    /// Response status codes that are retried.
+    pub retry_on: Vec<StatusCode>,
+    /// Whether to wait as long as the `Retry-After` header of a response
+    /// demands instead of the backoff delay.
+    pub honor_retry_after: bool,
+    /// The longest delay before a retry.
+    ///
+    /// If the `Retry-After` header of a response demands a longer delay, the
+    /// request is not retried.
+    pub max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Default number of retries.
+    pub const DEFAULT_MAX_RETRIES: usize = 3;
+
+    /// Default delay before the first retry.
+    pub const DEFAULT_BASE_DELAY_MS: u64 = 500;
+
+    /// Default for the longest delay before a retry.
+    pub const DEFAULT_MAX_DELAY_SECS: u64 = 60;
+
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Whether a response with the `status` should be retried.
+    pub fn is_retryable(&self, status: StatusCode) -> bool {
+        self.retry_on.contains(&status)
+    }
+
+    /// The delay before the `retry`th retry, starting at `0`.
+    ///
+    /// Half of the exponential delay is fixed, the other half is random. The
+    /// delay never exceeds `max_delay`.
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let factor = u32::try_from(retry)
+            .ok()
+            .and_then(|retry| 1u32.checked_shl(retry))
+            .unwrap_or(u32::MAX);
+        let half = self.base_delay.saturating_mul(factor) / 2;
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(retry);
+        let jitter = hasher.finish() as f64 / u64::MAX as f64;
+        (half + half.mul_f64(jitter)).min(self.max_delay)
+    }
+
+    /// The delay before the `retry`th retry of a request that got a
+    /// response with the `headers`.
+    ///
+    /// Returns `None` if the response demands a delay longer than
+    /// `max_delay`.
+    fn delay(&self, headers: &HeaderMap, retry: usize) -> Option<Duration> {
+        if self.honor_retry_after
+            && let Some(delay) = retry_after(headers)
+        {
+            return (delay <= self.max_delay).then_some(delay);
+        }
+        Some(self.backoff(retry))
+    }
+
+    /// Sends a GET request to the `url` and repeats it according to this
+    /// policy.
+    ///
+    /// Returns the final result together with the number of attempts made.
+    pub(crate) async fn get(&self, client: &Client, url: Url) -> (Result<Response, reqwest::Error>, usize) {
+        self.get_with_headers(client, url, HeaderMap::new()).await
+    }
+
+    /// Like [`RetryPolicy::get`], but sends the additional `headers` with
+    /// each attempt.
+    pub(crate) async fn get_with_headers(
+        &self,
+        client: &Client,
+        url: Url,
+        headers: HeaderMap,
+    ) -> (Result<Response, reqwest::Error>, usize) {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let result = client.get(url.clone()).headers(headers.clone()).send().await;
+            let can_retry = attempts <= self.max_retries;
+            let delay = match &result {
+                Ok(response) if can_retry && self.is_retryable(response.status()) => {
+                    match self.delay(response.headers(), attempts - 1) {
+                        Some(delay) => delay,
+                        None => return (result, attempts),
+                    }
+                }
+                Err(error) if can_retry && (error.is_timeout() || error.is_connect()) => {
+                    self.backoff(attempts - 1)
+                }
+                _ => return (result, attempts),
+            };
+            let _ = Delay::new(delay).await;
+        }
+    }
+
+    /// Like [`RetryPolicy::get`], but blocks the current thread while
+    /// waiting for responses and retries.
+    #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+    pub(crate) fn get_blocking(
+        &self,
+        client: &reqwest::blocking::Client,
+        url: Url,
+    ) -> (Result<reqwest::blocking::Response, reqwest::Error>, usize) {
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let result = client.get(url.clone()).send();
+            let can_retry = attempts <= self.max_retries;
+            let delay = match &result {
+                Ok(response) if can_retry && self.is_retryable(response.status()) => {
+                    match self.delay(response.headers(), attempts - 1) {
+                        Some(delay) => delay,
+                        None => return (result, attempts),
+                    }
+                }
+                Err(error) if can_retry && (error.is_timeout() || error.is_connect()) => {
+                    self.backoff(attempts - 1)
+                }
+                _ => return (result, attempts),
+            };
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: Self::DEFAULT_MAX_RETRIES,
+            base_delay: Duration::from_millis(Self::DEFAULT_BASE_DELAY_MS),
+            retry_on: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            honor_retry_after: true,
+            max_delay: Duration::from_secs(Self::DEFAULT_MAX_DELAY_SECS),
+        }
+    }
+}
+
+/// The delay requested by the `Retry-After` header of a response.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    parse_retry_after(headers.get(RETRY_AFTER)?.to_str().ok()?)
+}
+
+/// Parses a `Retry-After` value, either delay seconds or a http date.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    // dates in the past mean no delay
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::article::{Article, ArticleError};
+    use crate::test_server::serve_in_order;
+
+    use super::*;
+
+    const UNAVAILABLE: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    const UNAVAILABLE_FOR_AN_HOUR: &str =
+        "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 3600\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+    const OK: &str = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nConnection: close\r\n\r\n\
+        <html><head><meta property=\"og:title\" content=\"Retried\"></head><body></body></html>";
+
+    fn fast_policy(max_retries: usize) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(10),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_unavailable() {
+        let url = serve_in_order(vec![UNAVAILABLE, UNAVAILABLE, OK]);
+        let article = Article::builder(url)
+            .unwrap()
+            .retry_policy(fast_policy(3))
+            .get()
+            .await
+            .unwrap();
+        assert_eq!(article.content.title.as_deref(), Some("Retried"));
+    }
+
+    #[tokio::test]
+    async fn reports_attempts() {
+        let url = serve_in_order(vec![UNAVAILABLE, UNAVAILABLE, OK]);
+        let err = Article::builder(url)
+            .unwrap()
+            .retry_policy(fast_policy(1))
+            .get()
+            .await
+            .unwrap_err();
+        match err {
+            ArticleError::UnsuccessfulRequest(msg) => assert!(msg.ends_with("after 2 attempt(s)")),
+            err => panic!("unexpected error {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn gives_up_on_long_retry_after() {
+        let url = serve_in_order(vec![UNAVAILABLE_FOR_AN_HOUR, OK]);
+        let err = Article::builder(url)
+            .unwrap()
+            .retry_policy(fast_policy(3))
+            .get()
+            .await
+            .unwrap_err();
+        match err {
+            ArticleError::UnsuccessfulRequest(msg) => assert!(msg.ends_with("after 1 attempt(s)")),
+            err => panic!("unexpected error {}", err),
+        }
+    }
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = fast_policy(3);
+        for retry in 0..4 {
+            let max = Duration::from_millis(10 << retry);
+            let delay = policy.backoff(retry);
+            assert!(delay >= max / 2 && delay <= max);
+        }
+        assert!(policy.backoff(usize::MAX) > Duration::ZERO);
+        assert!(policy.backoff(usize::MAX) <= policy.max_delay);
+    }
+
+    #[test]
+    fn retry_after_values() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
@@ -0,0 +1,202 @@
+//! Writing extracted articles to disk as they complete.
+//!
+//! An [`ArticleSink`] receives every article of a crawl right after it's
+//! extracted, see [`crate::Extrablatt::download_articles_into`], so the
+//! articles don't pile up in memory:
+//!
+//! ```no_run
+//! # use extrablatt_v2::Extrablatt;
+//! # use extrablatt_v2::sink::NdjsonSink;
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut site = Extrablatt::builder("https://some-news.com/")?.build().await?;
+//! site.download_all_remaining_categories().await;
+//! let mut sink = NdjsonSink::new(std::fs::File::create("articles.ndjson")?);
+//! let written = site.download_articles_into(&mut sink).await?;
+//! #   Ok(())
+//! # }
+//! ```
+
+use std::io;
+#[cfg(feature = "serde0")]
+use std::io::{BufWriter, Write};
+#[cfg(feature = "serde0")]
+use std::path::{Path, PathBuf};
+
+use crate::article::PureArticle;
+
+/// A destination of extracted articles.
+pub trait ArticleSink {
+    /// Write the `article`.
+    fn write(&mut self, article: &PureArticle) -> io::Result<()>;
+
+    /// Write the `article` together with the response `body` it was
+    /// extracted from, as it was received.
+    ///
+    /// The default ignores the body.
+    fn write_with_body(&mut self, article: &PureArticle, body: &[u8]) -> io::Result<()> {
+        let _ = body;
+        self.write(article)
+    }
+
+    /// Flush everything written, called once after the last article.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<S: ArticleSink + ?Sized> ArticleSink for &mut S {
+    fn write(&mut self, article: &PureArticle) -> io::Result<()> {
+        (**self).write(article)
+    }
+
+    fn write_with_body(&mut self, article: &PureArticle, body: &[u8]) -> io::Result<()> {
+        (**self).write_with_body(article, body)
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        (**self).finish()
+    }
+}
+
+/// Writes every article as JSON on its own line, known as newline-delimited
+/// JSON or JSON Lines.
+#[cfg(feature = "serde0")]
+#[derive(Debug)]
+pub struct NdjsonSink<W: Write> {
+    writer: W,
+}
+
+#[cfg(feature = "serde0")]
+impl<W: Write> NdjsonSink<W> {
+    /// Write the articles to the `writer`, wrap files into a
+    /// [`std::io::BufWriter`].
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// The writer the articles are written to.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Unwrap the writer, without flushing it, see [`ArticleSink::finish`].
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "serde0")]
+impl<W: Write> ArticleSink for NdjsonSink<W> {
+    fn write(&mut self, article: &PureArticle) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, article)?;
+        self.writer.write_all(b"\n")
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Writes the html of every article and its JSON into a directory.
+///
+/// The n-th article is written to `{n:06}.json`, starting with `000001`, and
+/// its response body, if written with [`ArticleSink::write_with_body`],
+/// unchanged to `{n:06}.html`. If the directory already holds numbered
+/// articles the numbering continues after the highest one, so earlier
+/// archives aren't overwritten.
+#[cfg(feature = "serde0")]
+#[derive(Debug)]
+pub struct HtmlArchiveSink {
+    dir: PathBuf,
+    /// The highest number of the articles already in the directory.
+    first: usize,
+    count: usize,
+}
+
+#[cfg(feature = "serde0")]
+impl HtmlArchiveSink {
+    /// Write the articles into the `dir`, which is created if it doesn't
+    /// exist.
+    pub fn new<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let mut last = 0;
+        for entry in std::fs::read_dir(dir)? {
+            let name = entry?.file_name();
+            let number = name
+                .to_str()
+                .and_then(|name| name.strip_suffix(".json"))
+                .filter(|number| number.len() == 6)
+                .and_then(|number| number.parse::<usize>().ok());
+            if let Some(number) = number {
+                last = last.max(number);
+            }
+        }
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            first: last,
+            count: 0,
+        })
+    }
+
+    /// The directory the articles are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The number of articles written by this sink.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The number of the last written article.
+    fn number(&self) -> usize {
+        self.first + self.count
+    }
+
+    fn write_json(&mut self, article: &PureArticle) -> io::Result<()> {
+        self.count += 1;
+        let file = std::fs::File::create(self.dir.join(format!("{:06}.json", self.number())))?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, article)?;
+        writer.flush()
+    }
+}
+
+#[cfg(feature = "serde0")]
+impl ArticleSink for HtmlArchiveSink {
+    fn write(&mut self, article: &PureArticle) -> io::Result<()> {
+        self.write_json(article)
+    }
+
+    fn write_with_body(&mut self, article: &PureArticle, body: &[u8]) -> io::Result<()> {
+        self.write_json(article)?;
+        std::fs::write(self.dir.join(format!("{:06}.html", self.number())), body)
+    }
+}
+
+#[cfg(all(test, feature = "serde0"))]
+mod tests {
+    use super::*;
+    use crate::Article;
+
+    #[test]
+    fn ndjson_lines() {
+        let html = r#"<html><head><title>Harbour expansion approved</title></head><body>
+            <div itemprop="articleBody"><p>The harbour will be expanded over the next ten years.</p></div>
+            </body></html>"#;
+        let mut sink = NdjsonSink::new(Vec::new());
+        for n in 0..2 {
+            let url = format!("https://example.com/news/harbour-{}.html", n);
+            sink.write(&Article::new(url.as_str(), html).unwrap().into_pure()).unwrap();
+        }
+        sink.finish().unwrap();
+
+        let output = String::from_utf8(sink.into_inner()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let article: PureArticle = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(article.url.as_str(), "https://example.com/news/harbour-1.html");
+    }
+}
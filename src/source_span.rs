@@ -0,0 +1,137 @@
+use std::ops::Range;
+
+use select::node::Node;
+
+/// Locate the `node` of a parsed document in the `raw` html it was parsed
+/// from and return the byte span from its opening to its closing tag.
+///
+/// [`select`] doesn't keep source offsets, so the span is searched for: the
+/// opening tag must contain the node's `id`, `class` and `itemprop` values, the
+/// first and last word of the node's text must be inside the span and the
+/// end is the matching closing tag, found by counting the opening and closing
+/// tags of the same name.
+///
+/// The span is approximate: tags of the same name inside comments, scripts
+/// or attribute values, unclosed tags the parser fixed up and identical
+/// sibling elements can shift it. Returns `None` if no matching span was
+/// found.
+pub fn approximate_source_span(raw: &str, node: &Node) -> Option<Range<usize>> {
+    let tag = node.name()?.to_ascii_lowercase();
+    let lower = raw.to_ascii_lowercase();
+    let anchors: Vec<String> = ["id", "class", "itemprop"]
+        .iter()
+        .filter_map(|attr| node.attr(attr))
+        .map(|value| value.to_ascii_lowercase())
+        .collect();
+    let text = node.text();
+    let first_word = snippet(text.split_whitespace());
+    let last_word = snippet(text.split_whitespace().rev());
+
+    let mut offset = 0;
+    while let Some(pos) = find_tag(&lower[offset..], &format!("<{}", tag)) {
+        let start = offset + pos;
+        offset = start + 1;
+        let open_end = match lower[start..].find('>') {
+            Some(end) => start + end + 1,
+            None => break,
+        };
+        let open_tag = &lower[start..open_end];
+        if !anchors.iter().all(|value| open_tag.contains(value.as_str())) {
+            continue;
+        }
+        let Some(end) = closing_tag_end(&lower, open_end, &tag) else {
+            continue;
+        };
+        let inner = &raw[open_end..end];
+        let contains = |word: &Option<&str>| word.map(|w| inner.contains(w)).unwrap_or(true);
+        if contains(&first_word) && contains(&last_word) {
+            return Some(start..end);
+        }
+    }
+    None
+}
+
+/// The first word that reads the same in the raw html, without characters
+/// that may be escaped.
+fn snippet<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    words.find(|word| word.chars().count() >= 3 && word.chars().all(char::is_alphanumeric))
+}
+
+/// Position of the opening or closing tag `prefix` like `<div` or `</div`,
+/// that isn't the prefix of a longer tag name.
+fn find_tag(html: &str, prefix: &str) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(pos) = html[offset..].find(prefix) {
+        let pos = offset + pos;
+        let next = html[pos + prefix.len()..].chars().next();
+        if matches!(next, Some(c) if c.is_whitespace() || c == '>' || c == '/') {
+            return Some(pos);
+        }
+        offset = pos + prefix.len();
+    }
+    None
+}
+
+/// The end of the closing tag of `tag` that matches an opening tag ending
+/// at `from`.
+fn closing_tag_end(html: &str, from: usize, tag: &str) -> Option<usize> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}", tag);
+    let mut depth = 1;
+    let mut offset = from;
+    loop {
+        let next_open = find_tag(&html[offset..], &open).map(|pos| offset + pos);
+        let next_close = find_tag(&html[offset..], &close).map(|pos| offset + pos)?;
+        match next_open {
+            Some(pos) if pos < next_close => {
+                depth += 1;
+                offset = pos + open.len();
+            }
+            _ => {
+                depth -= 1;
+                let end = next_close + html[next_close..].find('>')? + 1;
+                if depth == 0 {
+                    return Some(end);
+                }
+                offset = end;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+    use select::predicate::Name;
+
+    use crate::extract_node::article_node_adaptive;
+    use crate::text::ArticleTextNode;
+    use crate::Language;
+
+    use super::*;
+
+    #[test]
+    fn span_reparses_to_same_text() {
+        let raw = r#"<html><head><title>Harbour</title></head><body>
+            <div class="nav"><div>Home</div></div>
+            <DIV itemprop="articleBody" class="story-body" id="story">
+                <p>The harbour will be expanded over the next ten years, the city council
+                decided on Tuesday after a long debate.</p>
+                <div class="quote"><p>"It is a good day for the city," the mayor said.</p></div>
+                <p>The first ships are expected to use the new quay in the spring &amp; summer.</p>
+            </DIV>
+            <div class="footer">Contact</div>
+            </body></html>"#;
+        let doc = Document::from(raw);
+        let (node, _) = article_node_adaptive(&doc, Language::English).unwrap();
+        let span = approximate_source_span(raw, &node).unwrap();
+
+        let slice = &raw[span];
+        assert!(slice.starts_with("<DIV itemprop=\"articleBody\""));
+        assert!(slice.ends_with("</DIV>"));
+
+        let reparsed = Document::from(slice);
+        let reparsed = ArticleTextNode::new(reparsed.find(Name("div")).next().unwrap());
+        assert_eq!(reparsed.clean_text(), node.clean_text());
+    }
+}
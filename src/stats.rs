@@ -0,0 +1,146 @@
+use select::document::Document;
+use select::node::Data;
+use select::predicate::Any;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+/// Cheap statistics of a document to reject pages that are obviously no
+/// articles before the full extraction.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct DocumentStats {
+    /// Number of all nodes, including text nodes.
+    pub node_count: usize,
+    /// Number of chars of all text outside of `<script>` and `<style>`.
+    pub text_length: usize,
+    /// Number of `<p>` elements.
+    pub paragraph_count: usize,
+    /// Number of `<a>` elements.
+    pub link_count: usize,
+    /// Share of the bytes of `<script>` and `<style>` content of all text
+    /// bytes, between `0.0` and `1.0`.
+    pub script_share: f64,
+    /// Whether there is an `og:type` meta tag with the value `article`.
+    pub has_og_article: bool,
+    /// Whether there is an `itemprop="articleBody"` node.
+    pub has_article_body: bool,
+    /// Whether there is a `<h1>`.
+    pub has_h1: bool,
+}
+
+impl DocumentStats {
+    /// Min number of paragraphs of an article without explicit markers.
+    pub const MIN_PARAGRAPHS: usize = 3;
+
+    /// Min number of chars of text of an article without explicit markers.
+    pub const MIN_TEXT_LENGTH: usize = 500;
+
+    /// Max number of links per paragraph before a page is considered a
+    /// listing.
+    pub const MAX_LINKS_PER_PARAGRAPH: f64 = 3.0;
+
+    /// Max share of script bytes.
+    pub const MAX_SCRIPT_SHARE: f64 = 0.9;
+
+    /// Collect the statistics in a single pass over all nodes.
+    pub fn compute(doc: &Document) -> Self {
+        let mut stats = DocumentStats::default();
+        let (mut text_bytes, mut script_bytes) = (0usize, 0usize);
+        for node in doc.find(Any) {
+            stats.node_count += 1;
+            match node.data() {
+                Data::Text(text) => {
+                    let in_script = node
+                        .parent()
+                        .and_then(|parent| parent.name())
+                        .map(|name| name == "script" || name == "style")
+                        .unwrap_or_default();
+                    if in_script {
+                        script_bytes += text.len();
+                    } else {
+                        text_bytes += text.len();
+                        stats.text_length += text.trim().chars().count();
+                    }
+                }
+                Data::Element(..) => match node.name() {
+                    Some("p") => stats.paragraph_count += 1,
+                    Some("a") => stats.link_count += 1,
+                    Some("h1") => stats.has_h1 = true,
+                    Some("meta")
+                        if node.attr("property") == Some("og:type")
+                            && node.attr("content").map(str::trim) == Some("article") =>
+                    {
+                        stats.has_og_article = true
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+            if node.attr("itemprop") == Some("articleBody") {
+                stats.has_article_body = true;
+            }
+        }
+        if text_bytes + script_bytes > 0 {
+            stats.script_share = script_bytes as f64 / (text_bytes + script_bytes) as f64;
+        }
+        stats
+    }
+
+    /// Links per paragraph, or the number of links if there are no
+    /// paragraphs.
+    pub fn link_density(&self) -> f64 {
+        self.link_count as f64 / self.paragraph_count.max(1) as f64
+    }
+
+    /// Simple heuristic whether the document might be an article.
+    ///
+    /// Pages with explicit article markers pass, all others need enough
+    /// paragraphs and text and must not be dominated by links or scripts.
+    pub fn looks_like_article(&self) -> bool {
+        if self.has_article_body || self.has_og_article {
+            return true;
+        }
+        self.paragraph_count >= Self::MIN_PARAGRAPHS
+            && self.text_length >= Self::MIN_TEXT_LENGTH
+            && self.link_density() <= Self::MAX_LINKS_PER_PARAGRAPH
+            && self.script_share <= Self::MAX_SCRIPT_SHARE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn article_stats() {
+        let paragraph = "<p>The council approved the budget after a long debate about the costs \
+            of the new tram line, which will connect the university with the main station.</p>";
+        let html = format!(
+            r#"<html><head><script>var x = 1;</script></head><body><h1>Budget</h1>{}</body></html>"#,
+            paragraph.repeat(4)
+        );
+        let stats = DocumentStats::compute(&Document::from(html.as_str()));
+        assert_eq!(stats.paragraph_count, 4);
+        assert!(stats.has_h1);
+        assert!(!stats.has_og_article);
+        assert!(stats.script_share > 0.0 && stats.script_share < 0.1);
+        assert!(stats.looks_like_article());
+    }
+
+    #[test]
+    fn listing_stats() {
+        let item = r#"<li><a href="/a">Story</a> <a href="/b">More</a></li>"#;
+        let html = format!(
+            "<html><body><p>Latest news</p><ul>{}</ul></body></html>",
+            item.repeat(30)
+        );
+        let stats = DocumentStats::compute(&Document::from(html.as_str()));
+        assert_eq!(stats.link_count, 60);
+        assert!(!stats.looks_like_article());
+
+        let doc = Document::from(
+            r#"<html><head><meta property="og:type" content="article"></head><body></body></html>"#,
+        );
+        assert!(DocumentStats::compute(&doc).looks_like_article());
+    }
+}
@@ -0,0 +1,208 @@
+//! A naive extractive summary: the sentences of the text that share the most
+//! keywords with the title and the text itself.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::text::ArticleTextNodeExtractor;
+use crate::Language;
+
+/// Number of keywords of the text that count towards the score of a
+/// sentence.
+pub const SUMMARY_KEYWORDS: usize = 10;
+
+/// Number of words of a sentence with the best length score.
+pub const IDEAL_SENTENCE_WORDS: usize = 20;
+
+/// Abbreviations, lowercase and without the trailing `.`, that don't end a
+/// sentence.
+const ABBREVIATIONS: [&str; 34] = [
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "mt", "gen", "gov", "sen", "rep", "lt", "col", "sgt", "capt",
+    "inc", "ltd", "co", "corp", "vs", "etc", "approx", "dept", "est", "fig", "jan", "feb", "aug", "sept", "oct", "nov",
+    "dec",
+];
+
+/// Russian abbreviations, see [`ABBREVIATIONS`].
+const RUSSIAN_ABBREVIATIONS: [&str; 13] = [
+    "г", "гг", "им", "др", "пр", "ул", "тыс", "млн", "млрд", "руб", "проф", "акад", "св",
+];
+
+/// The `max_sentences` sentences of the `text` that summarize it best, in
+/// their original order.
+///
+/// Each sentence is scored by:
+///   * the share of the title's keywords it contains
+///   * the [`SUMMARY_KEYWORDS`] most frequent keywords of the text it
+///     contains, see [`ArticleTextNodeExtractor::keywords`]
+///   * its position, earlier sentences score higher
+///   * how close its length is to [`IDEAL_SENTENCE_WORDS`]
+pub fn summarize(text: &str, title: &str, lang: Language, max_sentences: usize) -> Vec<String> {
+    let sentences = sentences(text);
+    if sentences.len() <= max_sentences {
+        return sentences;
+    }
+    let terms = |text: &str| -> HashSet<String> {
+        ArticleTextNodeExtractor::keywords(text, lang.clone(), usize::MAX)
+            .into_iter()
+            .map(|(term, _)| term)
+            .collect()
+    };
+    let title_terms = terms(title);
+    let keywords: HashMap<String, f64> =
+        ArticleTextNodeExtractor::keywords(text, lang.clone(), SUMMARY_KEYWORDS).into_iter().collect();
+
+    let mut scored: Vec<(usize, f64)> = sentences
+        .iter()
+        .enumerate()
+        .map(|(idx, sentence)| {
+            let sentence_terms = terms(sentence);
+            let title_score = match title_terms.len() {
+                0 => 0.0,
+                len => sentence_terms.intersection(&title_terms).count() as f64 / len as f64,
+            };
+            let keyword_score = match sentence_terms.len() {
+                0 => 0.0,
+                len => {
+                    sentence_terms.iter().filter_map(|term| keywords.get(term)).sum::<f64>() / len as f64
+                }
+            };
+            let position_score = 1.0 - idx as f64 / sentences.len() as f64;
+            let words = ArticleTextNodeExtractor::words(sentence).count() as f64;
+            let ideal = IDEAL_SENTENCE_WORDS as f64;
+            let length_score = (1.0 - (ideal - words).abs() / ideal).max(0.0);
+            let score = (title_score * 1.5 + keyword_score * 2.0 + position_score + length_score * 0.5) / 5.0;
+            (idx, score)
+        })
+        .collect();
+    scored.sort_by(|(idx_a, a), (idx_b, b)| b.total_cmp(a).then(idx_a.cmp(idx_b)));
+    let mut top: Vec<usize> = scored.into_iter().take(max_sentences).map(|(idx, _)| idx).collect();
+    top.sort_unstable();
+    top.into_iter().map(|idx| sentences[idx].clone()).collect()
+}
+
+/// The sentences of the `text`, with normalized whitespace.
+///
+/// A sentence ends with `.`, `!`, `?` or `…`, followed by closing quotes or
+/// brackets, whitespace and a capital letter, digit, opening quote or dash,
+/// or at the end of a line. Periods of abbreviations like `Dr.`, `U.S.` or
+/// `т. е.`, of initials and of decimal numbers don't end a sentence.
+pub fn sentences(text: &str) -> Vec<String> {
+    text.lines().flat_map(line_sentences).collect()
+}
+
+fn line_sentences(line: &str) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut idx = 0;
+    while idx < chars.len() {
+        if !is_terminal(chars[idx]) {
+            idx += 1;
+            continue;
+        }
+        let period = idx;
+        while idx < chars.len() && (is_terminal(chars[idx]) || is_closing(chars[idx])) {
+            idx += 1;
+        }
+        let end = idx;
+        while idx < chars.len() && chars[idx].is_whitespace() {
+            idx += 1;
+        }
+        let starts_sentence = match chars.get(idx) {
+            Some(next) => {
+                end < idx
+                    && (next.is_uppercase() || next.is_ascii_digit() || is_opening(*next))
+                    && !(chars[period] == '.' && end == period + 1 && is_abbreviation(&chars[start..period]))
+            }
+            None => true,
+        };
+        if starts_sentence {
+            push_sentence(&mut sentences, &chars[start..end]);
+            start = idx;
+        }
+    }
+    push_sentence(&mut sentences, &chars[start..]);
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, chars: &[char]) {
+    let sentence = chars.iter().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" ");
+    if !sentence.is_empty() {
+        sentences.push(sentence);
+    }
+}
+
+/// Whether the last word of the `preceding` text is an abbreviation or an
+/// initial.
+fn is_abbreviation(preceding: &[char]) -> bool {
+    let word: String = preceding
+        .iter()
+        .rev()
+        .take_while(|c| !c.is_whitespace() && !is_opening(**c))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    let word = word.to_lowercase();
+    // initials like `J.` and abbreviations with inner periods like `U.S.`
+    if word.split('.').all(|part| part.chars().count() == 1 && part.chars().all(char::is_alphabetic)) {
+        return true;
+    }
+    ABBREVIATIONS.contains(&word.as_str()) || RUSSIAN_ABBREVIATIONS.contains(&word.as_str())
+}
+
+fn is_terminal(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '…')
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(c, '"' | '\'' | '»' | '”' | '’' | ')' | ']')
+}
+
+fn is_opening(c: char) -> bool {
+    matches!(c, '"' | '\'' | '«' | '“' | '‘' | '(' | '[' | '—' | '–' | '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_sentences() {
+        assert_eq!(
+            sentences("The U.S. economy grew 2.5 percent in May. Dr. Smith agreed! Did J. R. Doe? \"Yes.\" Done"),
+            vec![
+                "The U.S. economy grew 2.5 percent in May.",
+                "Dr. Smith agreed!",
+                "Did J. R. Doe?",
+                "\"Yes.\"",
+                "Done",
+            ]
+        );
+        assert_eq!(
+            sentences("В 2024 г. порт вырос на 3,5 %, т. е. быстрее. — Это рекорд, сказал А. С. Пушкин… Все рады."),
+            vec![
+                "В 2024 г. порт вырос на 3,5 %, т. е. быстрее.",
+                "— Это рекорд, сказал А. С. Пушкин…",
+                "Все рады.",
+            ]
+        );
+    }
+
+    #[test]
+    fn summarize_by_title() {
+        let text = "The council approved the expansion of the harbour on Tuesday. \
+            The weather was mild and sunny. \
+            The expansion of the harbour will add four new berths for container ships. \
+            Several residents attended the meeting. \
+            Construction of the harbour expansion starts next spring.";
+        let summary = summarize(text, "Council approves harbour expansion", Language::English, 2);
+        assert_eq!(
+            summary,
+            vec![
+                "The council approved the expansion of the harbour on Tuesday.",
+                "The expansion of the harbour will add four new berths for container ships.",
+            ]
+        );
+        assert_eq!(summarize("One sentence.", "", Language::English, 3), vec!["One sentence."]);
+    }
+}
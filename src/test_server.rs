@@ -0,0 +1,119 @@
+//! A minimal http server to test downloads without network access.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use url::Url;
+
+/// A request received by the server started with [`serve`].
+#[derive(Debug, Clone)]
+pub(crate) struct Request {
+    /// The path of the requested url, including the query.
+    pub path: String,
+    /// The lowercased request line and headers.
+    pub head: String,
+}
+
+impl Request {
+    /// The value of the header with the lowercase `name`.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.head.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key == name).then(|| value.trim())
+        })
+    }
+}
+
+/// Serves every request on a local port with the raw http response the
+/// `handler` creates for it.
+///
+/// Each connection is handled on its own thread. Returns the root url of the
+/// server.
+pub(crate) fn serve<F>(handler: F) -> Url
+where
+    F: Fn(&Request) -> String + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    let handler = Arc::new(handler);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let handler = handler.clone();
+            std::thread::spawn(move || {
+                let mut buf = [0; 4096];
+                let n = stream.read(&mut buf).unwrap_or_default();
+                let head = String::from_utf8_lossy(&buf[..n]).to_lowercase();
+                let path = head.split_whitespace().nth(1).unwrap_or("/").to_string();
+                let response = handler(&Request { path, head });
+                let _ = stream.write_all(response.as_bytes());
+            });
+        }
+    });
+    url
+}
+
+/// Serves the raw `responses` in order, one per request.
+///
+/// Requests after the last response are answered with `404 Not Found`.
+pub(crate) fn serve_in_order(responses: Vec<&'static str>) -> Url {
+    let next = AtomicUsize::new(0);
+    serve(move |_| {
+        responses
+            .get(next.fetch_add(1, Ordering::SeqCst))
+            .map(|response| response.to_string())
+            .unwrap_or_else(|| status("404 Not Found"))
+    })
+}
+
+/// Serves the html of the `pages` by their path.
+///
+/// Requests for all other paths are answered with `404 Not Found`.
+pub(crate) fn serve_pages(pages: Vec<(&'static str, &'static str)>) -> Url {
+    serve(move |request| {
+        pages
+            .iter()
+            .find(|(path, _)| *path == request.path)
+            .map(|(_, body)| html(body))
+            .unwrap_or_else(|| status("404 Not Found"))
+    })
+}
+
+/// A `200 OK` response with the html `body`.
+pub(crate) fn html(body: impl AsRef<str>) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nConnection: close\r\n\r\n{}",
+        body.as_ref()
+    )
+}
+
+/// An empty response with the `status`, like `404 Not Found`.
+pub(crate) fn status(status: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn answers_by_request() {
+        let url = serve(|request| match (request.path.as_str(), request.header("x-test")) {
+            ("/news", Some("1")) => html("<p>News</p>"),
+            _ => status("404 Not Found"),
+        });
+        let client = reqwest::Client::new();
+
+        let response = client.get(url.join("news").unwrap()).header("X-Test", "1").send().await.unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.text().await.unwrap(), "<p>News</p>");
+
+        let response = client.get(url.join("news").unwrap()).send().await.unwrap();
+        assert_eq!(response.status(), 404);
+    }
+}
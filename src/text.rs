@@ -1,12 +1,33 @@
+use std::cell::{OnceCell, RefCell};
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
+use std::sync::Arc;
 
+use fnv::FnvHashMap;
+use lazy_static::lazy_static;
+use regex::Regex;
 use select::document::Document;
-use select::node::Node;
+use select::node::{Data, Node};
 use select::predicate::{Attr, Class, Name, Predicate};
-
-use crate::clean::{DefaultDocumentCleaner, DocumentCleaner};
-use crate::video::VideoNode;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::block::{blocks, ContentBlock};
+use crate::clean::{CleanNodeIter, DefaultDocumentCleaner, DocumentCleaner, ExtractionFilters};
+use crate::dialogue::{detect_dialogue, Turn};
+use crate::extract_amp::is_amp_noise;
+use crate::image::{parse_dimension, ArticleImage, ImageFilter};
+use crate::quote::{quotes, Quote};
+use crate::embed::{embeds, is_social_embed, SocialEmbed};
+use crate::extract_comments::is_comment_section;
+use crate::promo::{
+    attribution, is_affiliate_link, is_promotional_footer, is_source_list, links, promo_block,
+    Attribution, PromoBlock,
+};
+use crate::video::{Video, VideoNode};
+use crate::audio::{Audio, AudioNode};
+use crate::extract_json_ld::json_ld_of;
+use crate::reference::Reference;
 use crate::Language;
 use url::Url;
 
@@ -27,6 +48,89 @@ pub const ARTICLE_BODY_ATTR: &[(&str, &str)] = &[
     ("data-role", "content"),
 ];
 
+/// The attribute key-value pairs of the node that holds the article's text,
+/// starting with [`ARTICLE_BODY_ATTR`].
+///
+/// If exactly one node within the `<body>` matches, it's taken as the article
+/// node without scoring, unless it fails a sanity check, see
+/// [`crate::extract_node::is_plausible_article_body`]. Generic pairs like
+/// `id="content"` also match wrappers of the whole page on some sites, they
+/// can be removed or the shortcut disabled to always score the text nodes.
+///
+/// # Example
+///
+/// ```
+/// # use extrablatt_v2::clean::ExtractionFilters;
+/// # use extrablatt_v2::text::ArticleBodyAttrs;
+/// # use extrablatt_v2::DefaultExtractor;
+/// let attrs = ArticleBodyAttrs::default()
+///     .remove("id", "content")
+///     .add("class", "story-body");
+/// let extractor =
+///     DefaultExtractor::default().with_filters(ExtractionFilters::default().article_body_attrs(attrs));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArticleBodyAttrs {
+    attrs: Vec<(String, String)>,
+    shortcut: bool,
+}
+
+impl ArticleBodyAttrs {
+    /// No pairs, so the text nodes are always scored.
+    pub fn empty() -> Self {
+        Self {
+            attrs: Vec::new(),
+            shortcut: true,
+        }
+    }
+
+    /// Also take the node with the `value` of the attribute `key`.
+    pub fn add<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        let pair = (key.into(), value.into());
+        if !self.attrs.contains(&pair) {
+            self.attrs.push(pair);
+        }
+        self
+    }
+
+    /// No longer take the node with the `value` of the attribute `key`.
+    pub fn remove(mut self, key: &str, value: &str) -> Self {
+        self.attrs.retain(|(k, v)| k != key || v != value);
+        self
+    }
+
+    /// Whether a matching node is taken without scoring, `true` by default.
+    pub fn shortcut(mut self, shortcut: bool) -> Self {
+        self.shortcut = shortcut;
+        self
+    }
+
+    /// Whether a matching node is taken without scoring, see
+    /// [`ArticleBodyAttrs::shortcut`].
+    pub fn is_shortcut(&self) -> bool {
+        self.shortcut
+    }
+
+    /// Whether the node has one of the pairs, the values are compared
+    /// exactly.
+    pub fn matches(&self, node: &Node) -> bool {
+        self.attrs.iter().any(|(k, v)| node.attr(k) == Some(v.as_str()))
+    }
+
+    /// The attribute `key` and `value` pairs a node is taken by.
+    pub fn attrs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attrs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl Default for ArticleBodyAttrs {
+    fn default() -> Self {
+        ARTICLE_BODY_ATTR
+            .iter()
+            .fold(Self::empty(), |attrs, &(k, v)| attrs.add(k, v))
+    }
+}
+
 /// Negative attributes that indicate non-content sections
 pub const NON_CONTENT_ATTR: &[(&str, &str)] = &[
     ("class", "sidebar"),
@@ -45,6 +149,37 @@ pub const NON_CONTENT_ATTR: &[(&str, &str)] = &[
     ("data-role", "sidebar"),
 ];
 
+/// Subtrees of the article node whose share of link text is above this are
+/// pruned from the text, if they are shorter than
+/// [`PRUNE_MAX_TEXT_LENGTH`]. See [`ArticleTextNode::prune`].
+pub const PRUNE_LINK_DENSITY: f64 = 0.5;
+
+/// Subtrees of the article node with at least this many chars of text are
+/// never pruned for their link density.
+pub const PRUNE_MAX_TEXT_LENGTH: usize = 200;
+
+/// Tags that are never part of [`ArticleTextNode::clean_html`], regardless of
+/// their content.
+pub const CLEAN_HTML_DROP_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "frame", "object", "embed", "form",
+    "input", "button", "select", "textarea", "nav", "aside", "link", "meta", "base", "svg",
+    "math",
+];
+
+/// Url schemes that are kept in [`ArticleTextNode::clean_html`].
+const CLEAN_HTML_URL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Elements without a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "br", "col", "hr", "img", "source", "track", "wbr",
+];
+
+/// Classes of elements that are hidden, or only visible to screen readers.
+pub const HIDDEN_CLASSES: [&str; 5] = ["hidden", "sr-only", "visually-hidden", "screen-reader-text", "print-only"];
+
+/// Classes of elements that are only read by screen readers.
+const SCREEN_READER_CLASSES: [&str; 3] = ["sr-only", "visually-hidden", "screen-reader-text"];
+
 pub const PUNCTUATION: &str = r###",."'!?&-/:;()#$%*+<=>@[\]^_`{|}~"###;
 
 pub trait TextContainer<'a> {
@@ -78,85 +213,384 @@ impl<'a> TextContainer<'a> for Node<'a> {
     }
 
     fn is_noise_node(&self) -> bool {
-        // Check for script, style, link, meta tags - these should NEVER be processed
-        if Name("script").or(Name("style")).or(Name("link")).or(Name("meta")).or(Name("noscript")).or(Name("figcaption")).or(Name("figure")).matches(self) {
-            return true;
-        }
+        is_noise_element(self) || std::iter::successors(self.parent(), Node::parent).any(|parent| is_noise_element(&parent))
+    }
+}
 
-        // Check for image caption attributes
-        if self.attr("data-image-caption").is_some() {
-            return true;
+/// Parts of class names, split at `-` and `_`, that mark footers, sidebars
+/// and teasers, which often contain ads and related content.
+pub const NOISE_CLASS_PARTS: [&str; 7] = [
+    "footer",
+    "sidebar",
+    "widget",
+    "widgets",
+    "recommendation",
+    "recommendations",
+    "articlebottom",
+];
+
+lazy_static! {
+    /// Compound noise classes like `article-bottom` or `related-articles`.
+    static ref RE_NOISE_CLASS: Regex = Regex::new(r"(?:^|[-_])(?:article[-_]bottom|related[-_][a-z0-9])").unwrap();
+}
+
+/// Whether one of the tokens of the `class` attribute marks noise.
+///
+/// Tokens are compared by their parts, so `page-footer` and `pageFooter` are
+/// noise while `article-footerless` is not.
+pub fn is_noise_class(class: &str) -> bool {
+    class.split_whitespace().any(|token| {
+        let parts = class_parts(token);
+        parts.iter().any(|part| NOISE_CLASS_PARTS.contains(&part.as_str()))
+            || RE_NOISE_CLASS.is_match(&parts.join("-"))
+    })
+}
+
+/// The lowercase parts of a class token, split at `-`, `_` and camel case
+/// humps.
+pub(crate) fn class_parts(token: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut prev_lower = false;
+    for c in token.chars() {
+        if (c == '-' || c == '_' || (c.is_uppercase() && prev_lower)) && !part.is_empty() {
+            parts.push(std::mem::take(&mut part));
         }
+        if c != '-' && c != '_' {
+            part.extend(c.to_lowercase());
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    if !part.is_empty() {
+        parts.push(part);
+    }
+    parts
+}
 
-        // Check for ad containers (structural filtering)
-        if self.attr("data-creative").is_some() {
-            return true;
+/// Whether the node itself is noise, regardless of its ancestors.
+fn is_noise_element(node: &Node) -> bool {
+    // script, style, link and meta tags should never be processed
+    if Name("script").or(Name("style")).or(Name("link")).or(Name("meta")).or(Name("noscript")).or(Name("figcaption")).or(Name("figure")).matches(node) {
+        return true;
+    }
+    // AMP ads, consent dialogs and layout placeholders
+    if is_amp_noise(node) {
+        return true;
+    }
+    // image captions and ad containers
+    if node.attr("data-image-caption").is_some() || node.attr("data-creative").is_some() {
+        return true;
+    }
+    if node.attr("class").map(is_noise_class).unwrap_or_default() {
+        return true;
+    }
+    // embedded social media posts
+    if is_social_embed(node) {
+        return true;
+    }
+    // comment sections, including the embeds of comment providers
+    if is_comment_section(node) {
+        return true;
+    }
+    // hidden duplicates, print-only blocks and screen reader texts
+    if is_hidden_element(node) {
+        return true;
+    }
+    // tracking pixels and invisible images
+    is_invisible_image(node)
+}
+
+/// Caches whether nodes are noise by their index, so the ancestors of a
+/// node are evaluated only once for all of its descendants.
+///
+/// Only use a cache for nodes of the same document.
+#[derive(Debug, Clone, Default)]
+pub struct NoiseCache {
+    verdicts: RefCell<FnvHashMap<usize, bool>>,
+    /// User hooks that reject additional nodes and texts.
+    filters: Option<Arc<ExtractionFilters>>,
+}
+
+impl NoiseCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache that also treats the nodes the `filters` deny as noise.
+    pub fn with_filters(filters: Option<Arc<ExtractionFilters>>) -> Self {
+        Self {
+            verdicts: Default::default(),
+            filters,
         }
+    }
 
-        // Check for footer/bottom sections that often contain ads and related content
-        if let Some(class) = self.attr("class") {
-            let class_lower = class.to_lowercase();
-            // Be specific to avoid false positives - check for clear footer/bottom patterns
-            if class_lower.contains("articlebottom") ||
-               class_lower.contains("article-bottom") ||
-               class_lower.contains("footer") ||
-               class_lower.contains("sidebar") ||
-               class_lower.contains("widget") ||
-               class_lower.contains("related-") ||
-               class_lower.contains("recommendation") {
-                return true;
+    /// Whether the `deny_text` hook of the filters rejects the `text`.
+    pub fn denies_text(&self, text: &str) -> bool {
+        self.filters
+            .as_ref()
+            .map(|filters| filters.denies_text(text))
+            .unwrap_or_default()
+    }
+
+    /// Same as [`TextContainer::is_noise_node`].
+    pub fn is_noise(&self, node: &Node) -> bool {
+        let mut verdicts = self.verdicts.borrow_mut();
+        let mut uncached = Vec::new();
+        let mut verdict = false;
+        let mut current = Some(*node);
+        while let Some(node) = current {
+            if let Some(cached) = verdicts.get(&node.index()) {
+                verdict = *cached;
+                break;
             }
+            uncached.push(node);
+            current = node.parent();
         }
+        for node in uncached.into_iter().rev() {
+            verdict = verdict
+                || is_noise_element(&node)
+                || self.filters.as_ref().map(|filters| filters.denies_node(&node)).unwrap_or_default();
+            verdicts.insert(node.index(), verdict);
+        }
+        verdict
+    }
+}
 
-        // Check for tracking pixels and invisible images
-        if Name("img").matches(self) {
-            if let Some(style) = self.attr("style") {
-                if style.contains("display: none") ||
-                   style.contains("visibility: hidden") ||
-                   (style.contains("position: absolute") && style.contains("left: -9999px")) {
-                    return true;
-                }
-            }
+/// Classes of the elements that hold the credit of a photo.
+pub const IMAGE_CREDIT_CLASSES: [&str; 4] = ["credit", "copyright", "source", "photographer"];
+
+/// Whether the node holds the credit of a photo.
+fn is_image_credit(node: &Node) -> bool {
+    if let Some(itemprop) = node.attr("itemprop") {
+        if itemprop == "creditText" || itemprop == "copyrightHolder" {
+            return true;
         }
+    }
+    // compared by the parts of the tokens, so `discredit-banner` is no credit
+    node.attr("class")
+        .map(|class| {
+            class.split_whitespace().any(|token| {
+                class_parts(token).iter().any(|part| IMAGE_CREDIT_CLASSES.contains(&part.as_str()))
+            })
+        })
+        .unwrap_or_default()
+}
 
-        // Check if node is inside a script, style, figcaption, footer, or ad container tag
-        let mut current = self.parent();
-        while let Some(parent) = current {
-            if Name("script").or(Name("style")).or(Name("noscript")).or(Name("figcaption")).or(Name("figure")).matches(&parent) {
-                return true;
-            }
-            // Also check for data-image-caption attribute on parents
-            if parent.attr("data-image-caption").is_some() {
-                return true;
-            }
-            // Check if parent is an ad container (structural filtering)
-            if parent.attr("data-creative").is_some() {
-                return true;
+/// Matches `<img>` and the image components of AMP pages.
+pub(crate) fn image_predicate() -> impl Predicate {
+    Name("img").or(Name("amp-img")).or(Name("amp-anim"))
+}
+
+/// The url of an image: its `src`, `data-src` or the first candidate of the
+/// `srcset`.
+///
+/// A `data:` placeholder in the `src` of a lazy loaded image is only used if
+/// there is no other url.
+pub(crate) fn image_src<'a>(node: &Node<'a>) -> Option<&'a str> {
+    let attr = |name: &str| node.attr(name).map(str::trim).filter(|url| !url.is_empty());
+    let src = attr("src");
+    src.filter(|url| !url.starts_with("data:"))
+        .or_else(|| attr("data-src"))
+        .or_else(|| node.attr("srcset")?.split(',').next()?.split_whitespace().next())
+        .or(src)
+}
+
+/// Whether the node is a tracking pixel or an otherwise invisible image.
+fn is_invisible_image(node: &Node) -> bool {
+    if !Name("img").matches(node) {
+        return false;
+    }
+    if let Some(style) = node.attr("style") {
+        is_hiding_style(style) ||
+        (style.contains("position: absolute") && style.contains("left: -9999px"))
+    } else {
+        false
+    }
+}
+
+/// Whether the element isn't rendered or only read by screen readers: it has
+/// a `hidden` attribute, `aria-hidden="true"`, an inline style with
+/// `display: none` or `visibility: hidden`, or one of the
+/// [`HIDDEN_CLASSES`].
+///
+/// The `<html>` and `<body>` are never hidden, pages with a modal dialog
+/// mark them `aria-hidden` while it's open.
+pub fn is_hidden_element(node: &Node) -> bool {
+    if Name("html").or(Name("body")).matches(node) {
+        return false;
+    }
+    node.attr("hidden").is_some()
+        || is_aria_hidden(node)
+        || node.attr("style").map(is_hiding_style).unwrap_or_default()
+        || has_hidden_class(node)
+}
+
+/// The text of the node without the text of its descendants that aren't
+/// rendered, see [`is_hidden_text_element`].
+pub fn visible_text(node: &Node) -> String {
+    if node.descendants().all(|child| child.index() == node.index() || !is_hidden_text_element(&child)) {
+        return node.text();
+    }
+    node.descendants()
+        .filter_map(|child| child.as_text().map(|text| (child, text)))
+        .filter(|(child, _)| {
+            !std::iter::successors(child.parent(), Node::parent)
+                .take_while(|parent| parent.index() != node.index())
+                .any(|parent| is_hidden_text_element(&parent))
+        })
+        .map(|(_, text)| text)
+        .collect()
+}
+
+/// Whether the element inside a paragraph isn't rendered, like
+/// [`is_hidden_element`], but more lenient, as hiding visible text loses
+/// words of the body.
+///
+/// An `aria-hidden` element is only hidden without a sibling with the text
+/// for screen readers, like the drop cap in
+/// `<span aria-hidden="true">T</span><span class="sr-only">The</span>he`,
+/// and the bare `hidden` class doesn't count, "read more" expanders remove
+/// it with a script.
+fn is_hidden_text_element(node: &Node) -> bool {
+    if Name("html").or(Name("body")).matches(node) {
+        return false;
+    }
+    node.attr("hidden").is_some()
+        || (is_aria_hidden(node) && !has_screen_reader_sibling(node))
+        || node.attr("style").map(is_hiding_style).unwrap_or_default()
+        || (has_hidden_class(node) && !has_class(node, "hidden"))
+}
+
+/// Whether the element is marked `aria-hidden="true"`.
+fn is_aria_hidden(node: &Node) -> bool {
+    node.attr("aria-hidden")
+        .map(|hidden| hidden.trim().eq_ignore_ascii_case("true"))
+        .unwrap_or_default()
+}
+
+/// Whether the previous or next sibling element is only read by screen
+/// readers, the alternative of an `aria-hidden` element.
+fn has_screen_reader_sibling(node: &Node) -> bool {
+    let is_element = |sibling: &Node| sibling.name().is_some();
+    let screen_reader_only = |sibling: Node| SCREEN_READER_CLASSES.iter().any(|class| has_class(&sibling, class));
+    let prev = std::iter::successors(node.prev(), Node::prev).find(is_element);
+    let next = std::iter::successors(node.next(), Node::next).find(is_element);
+    prev.into_iter().chain(next).any(screen_reader_only)
+}
+
+/// Whether the element has the `class` token.
+fn has_class(node: &Node, class: &str) -> bool {
+    node.attr("class")
+        .map(|classes| classes.split_whitespace().any(|token| token == class))
+        .unwrap_or_default()
+}
+
+/// Whether the node has one of the [`HIDDEN_CLASSES`].
+///
+/// Classes with responsive variants, like `hidden md:block`, only hide the
+/// element on some screens and don't count.
+pub fn has_hidden_class(node: &Node) -> bool {
+    node.attr("class")
+        .map(|class| {
+            class.split_whitespace().any(|token| HIDDEN_CLASSES.contains(&token))
+                && !class.split_whitespace().any(|token| token.contains(':'))
+        })
+        .unwrap_or_default()
+}
+
+/// Whether an inline `style` declares `display: none` or `visibility:
+/// hidden`, with any whitespace, case and `!important`.
+pub fn is_hiding_style(style: &str) -> bool {
+    style.split(';').any(|declaration| {
+        let Some((property, value)) = declaration.split_once(':') else {
+            return false;
+        };
+        let value = value.trim().to_ascii_lowercase();
+        let value = value.trim_end_matches("!important").trim_end();
+        match property.trim().to_ascii_lowercase().as_str() {
+            "display" => value == "none",
+            "visibility" => value == "hidden" || value == "collapse",
+            _ => false,
+        }
+    })
+}
+
+/// Whether the paragraph, or element with paragraphs, is mostly links, see
+/// [`ArticleTextNode::prune`].
+fn is_link_cluster(node: &Node, stats: &LinkStats) -> bool {
+    let is_paragraph = Name("p").matches(node);
+    if (!is_paragraph && !stats.has_paragraph) || stats.visible_chars == 0 {
+        return false;
+    }
+    (is_paragraph && stats.visible_link_chars == stats.visible_chars)
+        || (stats.visible_chars < PRUNE_MAX_TEXT_LENGTH && stats.link_density() > PRUNE_LINK_DENSITY)
+}
+
+/// The lengths of the text and of the link text of a subtree, for
+/// [`is_link_cluster`].
+#[derive(Debug, Clone, Copy, Default)]
+struct LinkStats {
+    /// Chars of the text, like [`TextContainer::text_content_length`].
+    chars: usize,
+    /// Chars of the text of the links, like in [`TextContainer::link_density`].
+    link_chars: usize,
+    /// Non whitespace chars of the text.
+    visible_chars: usize,
+    /// Non whitespace chars of the text of the links.
+    visible_link_chars: usize,
+    /// Whether the subtree has a `<p>` below its root.
+    has_paragraph: bool,
+}
+
+impl LinkStats {
+    /// The stats of the `root` and all nodes below it by their index,
+    /// computed bottom up in a single walk.
+    fn collect(root: Node) -> FnvHashMap<usize, LinkStats> {
+        let mut order = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            stack.extend(node.children());
+        }
+        let mut stats: FnvHashMap<usize, LinkStats> = FnvHashMap::default();
+        // every node comes after its ancestors
+        for node in order.into_iter().rev() {
+            let mut own = LinkStats::default();
+            if let Some(text) = node.as_text() {
+                own.chars = text.chars().count();
+                own.visible_chars = text.chars().filter(|c| !c.is_whitespace()).count();
             }
-            // Check for footer/bottom sections in parent chain
-            if let Some(class) = parent.attr("class") {
-                let class_lower = class.to_lowercase();
-                // Be specific to avoid false positives - check for clear footer/bottom patterns
-                if class_lower.contains("articlebottom") ||
-                   class_lower.contains("article-bottom") ||
-                   class_lower.contains("footer") ||
-                   class_lower.contains("sidebar") ||
-                   class_lower.contains("widget") ||
-                   class_lower.contains("related-") ||
-                   class_lower.contains("recommendation") {
-                    return true;
+            for child in node.children() {
+                let child_stats = stats.get(&child.index()).copied().unwrap_or_default();
+                own.chars += child_stats.chars;
+                own.visible_chars += child_stats.visible_chars;
+                own.link_chars += child_stats.link_chars;
+                own.visible_link_chars += child_stats.visible_link_chars;
+                own.has_paragraph |= child_stats.has_paragraph || child.name() == Some("p");
+                if child.name() == Some("a") {
+                    own.link_chars += child_stats.chars;
+                    own.visible_link_chars += child_stats.visible_chars;
                 }
             }
-            current = parent.parent();
+            stats.insert(node.index(), own);
         }
+        stats
+    }
 
-        false
+    fn link_density(&self) -> f64 {
+        if self.chars == 0 {
+            return 1.0;
+        }
+        self.link_chars as f64 / self.chars as f64
     }
 }
 
 pub struct TextNodeFind<'a> {
     document: &'a Document,
     next: usize,
+    noise: NoiseCache,
+    /// Whether a node is within a bad or non-content node, by its index.
+    excluded: FnvHashMap<usize, bool>,
 }
 
 impl<'a> TextNodeFind<'a> {
@@ -219,8 +653,38 @@ impl<'a> TextNodeFind<'a> {
         NON_CONTENT_ATTR.iter().any(|&(k, v)| Attr(k, v).matches(node))
     }
 
-    fn new(document: &'a Document) -> Self {
-        Self { document, next: 0 }
+    fn new(document: &'a Document, filters: Option<Arc<ExtractionFilters>>) -> Self {
+        Self {
+            document,
+            next: 0,
+            noise: NoiseCache::with_filters(filters),
+            excluded: FnvHashMap::default(),
+        }
+    }
+
+    /// Whether the node or one of its ancestors is bad, non-content or
+    /// noise.
+    ///
+    /// The ancestors are checked instead of skipping the number of
+    /// descendants of an excluded node, which assumes that each subtree is a
+    /// contiguous range of the document's nodes.
+    fn is_excluded(&mut self, node: &Node<'a>) -> bool {
+        let mut uncached = Vec::new();
+        let mut verdict = false;
+        let mut current = Some(*node);
+        while let Some(node) = current {
+            if let Some(cached) = self.excluded.get(&node.index()) {
+                verdict = *cached;
+                break;
+            }
+            uncached.push(node);
+            current = node.parent();
+        }
+        for node in uncached.into_iter().rev() {
+            verdict = verdict || Self::is_bad(&node) || Self::is_non_content_by_attr(&node);
+            self.excluded.insert(node.index(), verdict);
+        }
+        verdict || self.noise.is_noise(node)
     }
 }
 
@@ -231,13 +695,8 @@ impl<'a> Iterator for TextNodeFind<'a> {
         while self.next < self.document.nodes.len() {
             let node = self.document.nth(self.next).unwrap();
             self.next += 1;
-            
-            if Self::is_bad(&node) || Self::is_non_content_by_attr(&node) || node.is_noise_node() {
-                self.next += node.descendants().count();
-                continue;
-            }
-            
-            if Self::is_text_node(&node) {
+
+            if Self::is_text_node(&node) && !self.is_excluded(&node) {
                 return Some(node);
             }
         }
@@ -249,89 +708,394 @@ impl<'a> Iterator for TextNodeFind<'a> {
 pub struct ArticleTextNode<'a> {
     inner: Node<'a>,
     confidence_score: f64,
+    /// Whether paragraphs that list the sources are part of the text.
+    attributions_in_text: bool,
+    /// How the node was selected.
+    selection: NodeSelection,
+    /// Number of scored text nodes.
+    candidate_count: usize,
+    /// The highest scores of the scored nodes, descending.
+    top_scores: Vec<usize>,
+    /// Which nodes of the document are noise.
+    noise: NoiseCache,
+    /// The indices of the subtrees of [`ArticleTextNode::prune`], computed
+    /// once for the text, blocks and dialogue of the node.
+    pruned: OnceCell<HashSet<usize>>,
 }
 
 impl<'a> ArticleTextNode<'a> {
     pub fn new(inner: Node<'a>) -> Self {
-        Self {
-            inner,
-            confidence_score: 1.0,
-        }
+        Self::with_confidence(inner, 1.0).selection(NodeSelection::ArticleBodyAttr)
     }
 
     pub fn with_confidence(inner: Node<'a>, confidence_score: f64) -> Self {
         Self {
             inner,
             confidence_score,
+            attributions_in_text: true,
+            selection: NodeSelection::Scored,
+            candidate_count: 0,
+            top_scores: Vec::new(),
+            noise: NoiseCache::new(),
+            pruned: OnceCell::new(),
+        }
+    }
+
+    /// Set how the node was selected.
+    pub fn selection(mut self, selection: NodeSelection) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    /// Treat the nodes and texts the `filters` deny as noise.
+    pub fn filters(mut self, filters: Option<Arc<ExtractionFilters>>) -> Self {
+        self.noise = NoiseCache::with_filters(filters);
+        self
+    }
+
+    /// Record the number of scored text nodes and their highest scores.
+    fn scored(mut self, candidate_count: usize, top_scores: Vec<usize>) -> Self {
+        self.candidate_count = candidate_count;
+        self.top_scores = top_scores;
+        self
+    }
+
+    /// How the node was selected and how much of its text is noise.
+    ///
+    /// `clean_text` is the node's [`ArticleTextNode::clean_text`], passed in
+    /// so it isn't computed twice.
+    pub fn diagnostics(&self, clean_text: &str) -> Diagnostics {
+        let visible_chars = |text: &str| text.chars().filter(|c| !c.is_whitespace()).count();
+        let attr = |name| self.inner.attr(name).map(str::to_string);
+        Diagnostics {
+            selection: self.selection,
+            confidence: self.confidence_score,
+            tag: self.inner.name().map(str::to_string),
+            class: attr("class"),
+            id: attr("id"),
+            candidate_count: self.candidate_count,
+            top_scores: self.top_scores.clone(),
+            discarded_chars: visible_chars(&self.inner.text())
+                .saturating_sub(visible_chars(clean_text)),
+            user_agent: None,
         }
     }
 
+    /// Set whether paragraphs that list the sources of the article, like
+    /// `Sources: Reuters, AP`, are part of the text or only available from
+    /// [`ArticleTextNode::attributions`].
+    ///
+    /// Default is `true`.
+    pub fn attributions_in_text(mut self, attributions_in_text: bool) -> Self {
+        self.attributions_in_text = attributions_in_text;
+        self
+    }
+
     pub fn confidence_score(&self) -> f64 {
         self.confidence_score
     }
 
     /// Enhanced clean_text that aggressively filters out noise
     pub fn clean_text(&self) -> String {
-        let raw_text = self.extract_clean_text();
+        let raw_text = self.extract_clean_text(None);
         Self::post_process_text(&raw_text)
     }
 
-    /// Extract text while filtering out noise nodes
-    fn extract_clean_text(&self) -> String {
-        let mut text_parts = Vec::new();
+    /// Like [`ArticleTextNode::clean_text`], but paragraphs the `cleaner`
+    /// considers removable, or that are within such a node, are left out and
+    /// the text of every paragraph passes the cleaner's
+    /// [`DocumentCleaner::transform_text`].
+    pub fn clean_text_with_cleaner(&self, cleaner: &dyn DocumentCleaner) -> String {
+        let raw_text = self.extract_clean_text(Some(cleaner));
+        Self::post_process_text(&raw_text)
+    }
 
-        // Newspaper4k-style: extract only from paragraph tags within the selected node
-        for para in self.inner.find(Name("p")) {
-            if para.is_noise_node() {
-                continue;
+    /// Serialize the node into sanitized html.
+    ///
+    /// Every descendant that is a noise node or one of
+    /// [`CLEAN_HTML_DROP_TAGS`] is removed together with its subtree. Inline
+    /// event handlers (`on*`), `style` and `srcset` attributes are stripped,
+    /// relative `href` and `src` values are resolved against the `base_url`
+    /// and urls with a scheme other than [`CLEAN_HTML_URL_SCHEMES`] (like
+    /// `javascript:` or `data:`) are dropped, so the result is safe to render.
+    pub fn clean_html(&self, base_url: Option<&Url>) -> String {
+        let mut html = String::new();
+        Self::write_clean_html(self.inner, base_url, &self.noise, true, &mut html);
+        html
+    }
+
+    fn write_clean_html(
+        node: Node,
+        base_url: Option<&Url>,
+        noise: &NoiseCache,
+        is_root: bool,
+        html: &mut String,
+    ) {
+        match node.data() {
+            Data::Text(txt) => html.push_str(&escape_html(txt, false)),
+            Data::Comment(_) => {}
+            Data::Element(name, attrs) => {
+                let tag = name.local.as_ref();
+                if CLEAN_HTML_DROP_TAGS.contains(&tag) || (!is_root && noise.is_noise(&node)) {
+                    return;
+                }
+                html.push('<');
+                html.push_str(tag);
+                for (attr_name, value) in attrs.iter() {
+                    let attr = attr_name.local.as_ref();
+                    let lower = attr.to_lowercase();
+                    if lower.starts_with("on") || lower == "style" || lower == "srcset" {
+                        continue;
+                    }
+                    let value: &str = value;
+                    let value = if lower == "href" || lower == "src" {
+                        match Self::clean_url(value, base_url) {
+                            Some(value) => value,
+                            None => continue,
+                        }
+                    } else {
+                        value.to_string()
+                    };
+                    html.push(' ');
+                    html.push_str(attr);
+                    html.push_str("=\"");
+                    html.push_str(&escape_html(&value, true));
+                    html.push('"');
+                }
+                html.push('>');
+                if VOID_ELEMENTS.contains(&tag) {
+                    return;
+                }
+                for child in node.children() {
+                    Self::write_clean_html(child, base_url, noise, false, html);
+                }
+                html.push_str("</");
+                html.push_str(tag);
+                html.push('>');
             }
+        }
+    }
 
+    /// Resolve the `href` or `src` `value` against the `base_url`.
+    ///
+    /// The value is parsed first, so that the scheme is checked the same way
+    /// a browser would see it (tabs and newlines inside the scheme are
+    /// ignored). Returns `None` for every url whose scheme is not in
+    /// [`CLEAN_HTML_URL_SCHEMES`] or that can't be parsed at all. Relative
+    /// urls are kept as they are if there is no `base_url`.
+    fn clean_url(value: &str, base_url: Option<&Url>) -> Option<String> {
+        match Url::options().base_url(base_url).parse(value) {
+            Ok(url) if CLEAN_HTML_URL_SCHEMES.contains(&url.scheme()) => Some(url.into()),
+            Ok(_) => None,
+            Err(url::ParseError::RelativeUrlWithoutBase) => Some(value.trim().to_string()),
+            Err(_) => None,
+        }
+    }
+
+    /// All paragraphs of the node that hold article content.
+    fn content_paragraphs(&self) -> Vec<Node<'a>> {
+        let paragraphs = self.paragraphs();
+        let total = paragraphs.len();
+        let pruned = self.pruned_indices();
+        paragraphs
+            .into_iter()
+            .enumerate()
             // Structural filtering: skip promotional footer paragraphs
-            if Self::is_promotional_footer(&para) {
+            .filter(|(index, para)| !is_promotional_footer(para, *index, total))
+            .filter(|(_, para)| self.attributions_in_text || !is_source_list(para))
+            .filter(|(_, para)| !self.is_within(para, pruned))
+            .map(|(_, para)| para)
+            .collect()
+    }
+
+    /// The subtrees of the node that are left out of the text, because they
+    /// are mostly links, like share buttons, "Read more" links and lists of
+    /// related articles.
+    ///
+    /// A subtree is pruned if its link density is above
+    /// [`PRUNE_LINK_DENSITY`] and its text is shorter than
+    /// [`PRUNE_MAX_TEXT_LENGTH`], or if it is a paragraph that consists of
+    /// links only. Lists of sources are kept. Only the outermost pruned
+    /// subtrees are returned, in document order.
+    pub fn prune(&self) -> Vec<Node<'a>> {
+        let stats = LinkStats::collect(self.inner);
+        let mut pruned = Vec::new();
+        let mut stack: Vec<Node<'a>> = self.inner.children().collect();
+        stack.reverse();
+        while let Some(node) = stack.pop() {
+            let node_stats = stats.get(&node.index()).copied().unwrap_or_default();
+            if is_link_cluster(&node, &node_stats) && !is_source_list(&node) {
+                // the nodes below are pruned with it
+                pruned.push(node);
                 continue;
             }
+            let len = stack.len();
+            stack.extend(node.children());
+            stack[len..].reverse();
+        }
+        pruned
+    }
 
-            // Use .text() to get all text content from paragraph and its children
-            let text = para.text();
-            let trimmed = text.trim();
-            if !trimmed.is_empty() && !Self::is_noise_text(trimmed) {
-                text_parts.push(trimmed.to_string());
+    /// The indices of the subtrees of [`ArticleTextNode::prune`].
+    fn pruned_indices(&self) -> &HashSet<usize> {
+        self.pruned
+            .get_or_init(|| self.prune().iter().map(Node::index).collect())
+    }
+
+    /// Whether the node or one of its ancestors below the article node is
+    /// one of the `indices`.
+    fn is_within(&self, node: &Node, indices: &HashSet<usize>) -> bool {
+        std::iter::successors(Some(*node), Node::parent)
+            .take_while(|node| node.index() != self.inner.index())
+            .any(|node| indices.contains(&node.index()))
+    }
+
+    /// All paragraphs of the node that aren't noise.
+    fn paragraphs(&self) -> Vec<Node<'a>> {
+        self.inner
+            .find(Name("p"))
+            .filter(|para| !self.noise.is_noise(para))
+            .collect()
+    }
+
+    /// The paragraphs of the node that list the sources of the article.
+    pub fn attributions(&self, base_url: Option<&Url>) -> Vec<Attribution> {
+        self.paragraphs()
+            .iter()
+            .filter_map(|para| attribution(para, base_url))
+            .collect()
+    }
+
+    /// The blockquotes and pull quotes of the node, see
+    /// [`crate::quote::quotes`].
+    ///
+    /// The quotes remain part of the text, this is an additional view.
+    pub fn quotes(&self) -> Vec<Quote> {
+        quotes(&self.inner)
+    }
+
+    /// The paragraphs, headings, lists, tables and quotes of the node in
+    /// document order, see [`crate::block::blocks`].
+    ///
+    /// The structured alternative to [`ArticleTextNode::clean_text`], that
+    /// skips the same noise nodes, link clusters and noise texts.
+    pub fn blocks(&self) -> Vec<ContentBlock> {
+        let pruned = self.pruned_indices();
+        let mut blocks = blocks(&self.inner, |node| {
+            self.noise.is_noise(node) || pruned.contains(&node.index())
+        });
+        blocks.retain_mut(|block| match block {
+            ContentBlock::List { items, .. } => {
+                items.retain(|item| !self.noise.denies_text(item.trim()));
+                !items.is_empty()
             }
-        }
+            _ => block
+                .text()
+                .map(|text| !Self::is_noise_text(text) && !self.noise.denies_text(text))
+                .unwrap_or(true),
+        });
+        blocks
+    }
 
-        text_parts.join(" ")
+    /// The posts of social networks embedded in the node, see
+    /// [`crate::embed::embeds`].
+    ///
+    /// They aren't part of the text.
+    pub fn embeds(&self, base_url: Option<&Url>) -> Vec<SocialEmbed> {
+        embeds(&self.inner, base_url)
     }
 
-    /// Check if a paragraph is promotional footer content based on link attributes.
-    /// Promotional footers typically only contain links with rel="nofollow" attribute.
-    fn is_promotional_footer(para: &Node) -> bool {
-        let links: Vec<_> = para.find(Name("a")).collect();
+    /// The turns of the text if it is formatted as an interview or Q&A.
+    ///
+    /// Empty if no dialogue was detected.
+    pub fn dialogue(&self) -> Vec<Turn> {
+        detect_dialogue(&self.content_paragraphs())
+    }
 
-        // If there are no links, it's not a promotional footer
-        if links.is_empty() {
-            return false;
+    /// Extract text while filtering out noise nodes
+    fn extract_clean_text(&self, cleaner: Option<&dyn DocumentCleaner>) -> String {
+        let (paragraphs, text_parts) = self.paragraph_texts(cleaner);
+        Self::join_paragraph_texts(&paragraphs, &text_parts)
+    }
+
+    /// The cleaned text and the cleaned text of each paragraph, like
+    /// [`ArticleTextNode::clean_text`] or
+    /// [`ArticleTextNode::clean_text_with_cleaner`] if a `cleaner` is set,
+    /// from a single walk over the paragraphs.
+    pub(crate) fn clean_text_and_paragraphs(&self, cleaner: Option<&dyn DocumentCleaner>) -> (String, Vec<String>) {
+        let (paragraphs, text_parts) = self.paragraph_texts(cleaner);
+        let text = Self::post_process_text(&Self::join_paragraph_texts(&paragraphs, &text_parts));
+        let paragraphs = text_parts
+            .iter()
+            .map(|text| Self::post_process_text(text))
+            .filter(|text| !text.is_empty())
+            .collect();
+        (text, paragraphs)
+    }
+
+    fn join_paragraph_texts(paragraphs: &[Node], text_parts: &[String]) -> String {
+        // keep the turns of an interview on separate lines
+        if detect_dialogue(paragraphs).is_empty() {
+            text_parts.join(" ")
+        } else {
+            text_parts.join("\n")
         }
+    }
+
+    /// The paragraphs of the text and their texts, noise texts are skipped.
+    fn paragraph_texts(&self, cleaner: Option<&dyn DocumentCleaner>) -> (Vec<Node<'a>>, Vec<String>) {
+        let mut text_parts = Vec::new();
 
-        // Check if ALL links have rel="nofollow" or rel="noopener nofollow"
-        let all_nofollow = links.iter().all(|link| {
-            if let Some(rel) = link.attr("rel") {
-                rel.contains("nofollow")
-            } else {
-                false
+        // Newspaper4k-style: extract only from paragraph tags within the selected node
+        let mut paragraphs = self.content_paragraphs();
+        if let Some(cleaner) = cleaner {
+            paragraphs.retain(|para| !self.is_removed_by(para, cleaner));
+        }
+        for para in &paragraphs {
+            // all text content of the paragraph and its visible children
+            let mut text = visible_text(para).trim().to_string();
+            if let Some(cleaner) = cleaner {
+                cleaner.transform_text(*para, &mut text);
             }
-        });
+            let trimmed = text.trim();
+            if !trimmed.is_empty() && !Self::is_noise_text(trimmed) && !self.noise.denies_text(trimmed) {
+                text_parts.push(trimmed.to_string());
+            }
+        }
+        (paragraphs, text_parts)
+    }
 
-        // If all links are nofollow, it's likely promotional content
-        all_nofollow
+    /// The promotional paragraphs of the node.
+    ///
+    /// These are the promotional footers that are kept out of the text, and
+    /// paragraphs that are labeled as ad or contain affiliate links, which
+    /// remain part of the text.
+    pub fn promotional_content(&self, base_url: Option<&Url>) -> Vec<PromoBlock> {
+        let paragraphs = self.paragraphs();
+        let total = paragraphs.len();
+        paragraphs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, para)| promo_block(para, index, total, base_url))
+            .filter(|block| !block.text.is_empty())
+            .collect()
     }
-    
+
+    /// Number of affiliate links within the node's descendants.
+    pub fn affiliate_link_count(&self, base_url: Option<&Url>) -> usize {
+        links(&self.inner, base_url)
+            .iter()
+            .filter(|url| is_affiliate_link(url))
+            .count()
+    }
+
     /// Extract text while filtering out noise nodes
     fn extract_content_text(&self) -> String {
         let mut text_parts = Vec::new();
         
         // First, try to extract from proper paragraph structures
         let paragraphs: Vec<_> = self.inner.find(Name("p"))
-            .filter(|n| !n.is_noise_node())
+            .filter(|n| !self.noise.is_noise(n))
             .filter(|n| n.text_content_length() >= 20) // Minimum reasonable paragraph length
             .collect();
         
@@ -349,7 +1113,7 @@ impl<'a> ArticleTextNode<'a> {
         // If no good paragraphs found, fall back to general text extraction
         if text_parts.is_empty() {
             for node in self.inner.descendants() {
-                if node.is_noise_node() {
+                if self.noise.is_noise(&node) {
                     continue;
                 }
                 
@@ -567,29 +1331,188 @@ impl<'a> ArticleTextNode<'a> {
         (alpha_count as f64 / total_chars as f64) < 0.3
     }
 
-    /// Extract all of the images of the document.
+    /// Extract all of the images of the document, without repeated urls,
+    /// `data:` placeholders, SVG icons and small images, see
+    /// [`ImageFilter::default`].
     pub fn images(&self, base_url: Option<&Url>) -> Vec<Url> {
+        self.images_with_filter(base_url, &ImageFilter::default())
+    }
+
+    /// Like [`ArticleTextNode::images`], but the images are dropped by the
+    /// rules of the `filter`.
+    pub fn images_with_filter(&self, base_url: Option<&Url>, filter: &ImageFilter) -> Vec<Url> {
+        let options = Url::options().base_url(base_url);
+        let dimension = |n: &Node, attr: &str| n.attr(attr).and_then(parse_dimension);
+        filter.apply(
+            self.inner
+                .find(image_predicate())
+                .filter(|n| !self.noise.is_noise(n) && !self.is_denied_image(n))
+                .filter_map(|n| {
+                    let url = options.parse(image_src(&n)?).ok()?;
+                    Some((url, dimension(&n, "width"), dimension(&n, "height")))
+                }),
+        )
+    }
+
+    /// Extract all images of the node together with their caption, credit
+    /// and alt text.
+    ///
+    /// Unlike [`ArticleTextNode::images`] this includes the images inside a
+    /// `<figure>`, paired with the text of the figure's `<figcaption>`.
+    pub fn images_detailed(&self, base_url: Option<&Url>) -> Vec<ArticleImage> {
         let options = Url::options().base_url(base_url);
         self.inner
-            .find(Name("img"))
-            .filter(|n| !n.is_noise_node())
-            .filter_map(|n| n.attr("src").or_else(|| n.attr("data-src")).map(str::trim))
-            .filter(|url| !url.is_empty())
-            .filter_map(|url| options.parse(url).ok())
+            .find(image_predicate())
+            .filter_map(|img| {
+                let figure = self.figure_of(&img);
+                let visible = match figure {
+                    Some(figure) => {
+                        !is_invisible_image(&img)
+                            && figure.parent().map(|n| !self.noise.is_noise(&n)).unwrap_or(true)
+                    }
+                    None => !self.noise.is_noise(&img),
+                };
+                if !visible || self.is_denied_image(&img) {
+                    return None;
+                }
+                let url = image_src(&img).and_then(|url| options.parse(url).ok())?;
+                let alt = img
+                    .attr("alt")
+                    .map(str::trim)
+                    .filter(|alt| !alt.is_empty())
+                    .map(str::to_string);
+                let (caption, credit) = figure.map(|n| Self::figure_caption(&n)).unwrap_or_default();
+                Some(ArticleImage {
+                    url,
+                    caption,
+                    credit,
+                    alt,
+                })
+            })
             .collect()
     }
 
+    /// Whether the `deny_text` hook of the filters rejects the alt text of
+    /// the image.
+    fn is_denied_image(&self, img: &Node) -> bool {
+        img.attr("alt")
+            .map(|alt| self.noise.denies_text(alt.trim()))
+            .unwrap_or_default()
+    }
+
+    /// The closest `<figure>` that contains the node within this node.
+    fn figure_of(&self, node: &Node<'a>) -> Option<Node<'a>> {
+        let mut current = node.parent();
+        while let Some(parent) = current {
+            if Name("figure").matches(&parent) {
+                return Some(parent);
+            }
+            if parent.index() == self.inner.index() {
+                break;
+            }
+            current = parent.parent();
+        }
+        None
+    }
+
+    /// The caption and the credit of a `<figure>`.
+    fn figure_caption(figure: &Node) -> (Option<String>, Option<String>) {
+        fn caption_text(node: &Node, text: &mut String) {
+            for child in node.children() {
+                if let Some(t) = child.as_text() {
+                    text.push_str(t);
+                } else if !is_image_credit(&child) {
+                    caption_text(&child, text);
+                }
+            }
+        }
+        let normalize = |text: String| {
+            Some(text.split_whitespace().collect::<Vec<_>>().join(" ")).filter(|s| !s.is_empty())
+        };
+
+        let caption = figure.find(Name("figcaption")).next().and_then(|n| {
+            let mut text = String::new();
+            caption_text(&n, &mut text);
+            normalize(text)
+        });
+        let credit = figure
+            .find(|n: &Node| is_image_credit(n))
+            .next()
+            .and_then(|n| normalize(n.text()));
+        (caption, credit)
+    }
+
+    /// Whether the `cleaner` removes the `node` or one of its ancestors
+    /// within this node.
+    fn is_removed_by(&self, node: &Node, cleaner: &dyn DocumentCleaner) -> bool {
+        let mut current = Some(*node);
+        while let Some(node) = current {
+            if cleaner.is_removable(node) {
+                return true;
+            }
+            if node.index() == self.inner.index() {
+                break;
+            }
+            current = node.parent();
+        }
+        false
+    }
+
     /// Extract all the links within the node's descendants
-    pub fn references(&self) -> Vec<Url> {
+    ///
+    /// Relative links are resolved against the `base_url` and deduplicated
+    /// after resolution. Fragment-only, `javascript:` and `mailto:` links are
+    /// skipped.
+    pub fn references(&self, base_url: Option<&Url>) -> Vec<Url> {
+        self.references_with_cleaner(base_url, &DefaultDocumentCleaner)
+    }
+
+    /// Like [`ArticleTextNode::references`], but skips the links within
+    /// nodes the `cleaner` considers removable.
+    pub fn references_with_cleaner(&self, base_url: Option<&Url>, cleaner: &dyn DocumentCleaner) -> Vec<Url> {
+        self.reference_links(base_url, cleaner)
+            .into_iter()
+            .map(|(_, url)| url)
+            .collect()
+    }
+
+    /// Like [`ArticleTextNode::references`], but with the anchor text, the
+    /// `rel` and the [`crate::reference::RefKind`] of each link relative to the
+    /// `article_url`.
+    pub fn references_detailed(&self, base_url: Option<&Url>, article_url: Option<&Url>) -> Vec<Reference> {
+        self.references_detailed_with_cleaner(base_url, article_url, &DefaultDocumentCleaner)
+    }
+
+    /// Like [`ArticleTextNode::references_detailed`], but skips the links
+    /// within nodes the `cleaner` considers removable.
+    pub fn references_detailed_with_cleaner(
+        &self,
+        base_url: Option<&Url>,
+        article_url: Option<&Url>,
+        cleaner: &dyn DocumentCleaner,
+    ) -> Vec<Reference> {
+        self.reference_links(base_url, cleaner)
+            .into_iter()
+            .map(|(node, url)| Reference::from_node(&node, url, article_url))
+            .collect()
+    }
+
+    /// The `<a>` nodes of the references with their resolved url, the
+    /// first node of each url.
+    fn reference_links<'c>(&self, base_url: Option<&Url>, cleaner: &'c dyn DocumentCleaner) -> Vec<(Node<'c>, Url)>
+    where
+        'a: 'c,
+    {
+        let options = Url::options().base_url(base_url);
         let mut uniques = HashSet::new();
-        DefaultDocumentCleaner
-            .iter_clean_nodes(self.inner)
+        CleanNodeIter::new(cleaner, self.inner)
             .filter(|n| Name("a").matches(n))
-            .filter(|n| !n.is_noise_node())
-            .filter_map(|n| n.attr("href").map(str::trim))
-            .filter(|href| !href.is_empty())
-            .filter(|href| uniques.insert(*href))
-            .filter_map(|url| Url::parse(url).ok())
+            .filter(|n| !self.noise.is_noise(n))
+            .filter_map(|n| n.attr("href").map(|href| (n, href.trim())))
+            .filter(|(_, href)| !href.is_empty() && !href.starts_with('#'))
+            .filter_map(|(n, url)| options.parse(url).ok().map(|url| (n, url)))
+            .filter(|(_, url)| !matches!(url.scheme(), "javascript" | "mailto"))
+            .filter(|(_, url)| uniques.insert(url.clone()))
             .collect()
     }
 
@@ -598,14 +1521,14 @@ impl<'a> ArticleTextNode<'a> {
         let mut videos: Vec<_> = self
             .inner
             .find(VideoNode::node_predicate())
-            .filter(|n| !n.is_noise_node())
+            .filter(|n| !self.noise.is_noise(n))
             .map(VideoNode::new)
             .collect();
 
         videos.extend(
             self.inner
                 .find(Name("embed"))
-                .filter(|n| !n.is_noise_node())
+                .filter(|n| !self.noise.is_noise(n))
                 .filter(|n| {
                     if let Some(parent) = n.parent() {
                         parent.name() != Some("object")
@@ -617,6 +1540,47 @@ impl<'a> ArticleTextNode<'a> {
         );
         videos
     }
+
+    /// The videos of the node with their provider, id, poster and
+    /// dimensions.
+    ///
+    /// Relative urls are resolved against the `base_url`, videos without a
+    /// source are skipped.
+    pub fn videos_detailed(&self, base_url: Option<&Url>) -> Vec<Video> {
+        self.videos()
+            .iter()
+            .filter_map(|video| Video::from_node(video, base_url))
+            .collect()
+    }
+
+    /// Extract all the nodes that hold audio data, players in sidebars and
+    /// other noise like audio ads are skipped.
+    pub fn audios(&self) -> Vec<AudioNode<'a>> {
+        self.inner
+            .find(AudioNode::node_predicate())
+            .filter(|n| !self.noise.is_noise(n))
+            .map(AudioNode::new)
+            .collect()
+    }
+
+    /// The audios of the node with their type, duration and title.
+    ///
+    /// Relative urls are resolved against the `base_url`, audios without a
+    /// source are skipped and each url is only reported once.
+    pub fn audios_detailed(&self, base_url: Option<&Url>) -> Vec<Audio> {
+        let audios = self.audios();
+        if audios.is_empty() {
+            return Vec::new();
+        }
+        // parsed once for all audios of the document
+        let json_ld = json_ld_of(&self.inner);
+        let mut uniques = HashSet::new();
+        audios
+            .iter()
+            .filter_map(|audio| Audio::from_node(audio, base_url, &json_ld))
+            .filter(|audio| uniques.insert(audio.url.clone()))
+            .collect()
+    }
 }
 
 impl<'a> Deref for ArticleTextNode<'a> {
@@ -634,6 +1598,8 @@ impl ArticleTextNodeExtractor {
     pub const MAX_STEPSAWAY_FROM_NODE: usize = 3;
     pub const MIN_TEXT_LENGTH: usize = 50;
     pub const MAX_LINK_DENSITY: f64 = 0.5;
+    /// Min number of chars of a keyword.
+    pub const MIN_KEYWORD_LENGTH: usize = 3;
 
     pub fn article_body_predicate() -> for<'r, 's> fn(&'r Node<'s>) -> bool {
         |node| {
@@ -642,15 +1608,151 @@ impl ArticleTextNodeExtractor {
     }
 
     pub fn calculate_best_node(doc: &Document, lang: Language) -> Option<ArticleTextNode> {
-        // Try to find explicit article body markers (only for itemprop="articleBody")
+        Self::calculate_best_node_with_trace(doc, lang).0
+    }
+
+    /// Like [`ArticleTextNodeExtractor::calculate_best_node`], but also
+    /// returns the ranking the node was selected from, see
+    /// [`ArticleTextNodeExtractor::rank_candidates`].
+    ///
+    /// The ranking is empty if the node is marked with
+    /// `itemprop="articleBody"`, otherwise its first entry is the selected
+    /// node.
+    pub fn calculate_best_node_with_trace(
+        doc: &Document,
+        lang: Language,
+    ) -> (Option<ArticleTextNode<'_>>, Vec<CandidateScore>) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("calculate_best_node").entered();
         if let Some(article_node) = doc.find(Attr("itemprop", "articleBody")).next() {
-            return Some(ArticleTextNode::with_confidence(article_node, 0.95));
+            return (
+                Some(
+                    ArticleTextNode::with_confidence(article_node, 0.95)
+                        .selection(NodeSelection::ItempropArticleBody),
+                ),
+                Vec::new(),
+            );
         }
 
-        let mut starting_boost = 1.0;
+        let candidates = Self::candidates(doc, &lang, ExtractionProfile::Strict, None);
+        let scores = Self::score_candidates(&lang, candidates.iter());
+        let ranking = Self::ranking(doc, &lang, &scores);
+        (Self::select_best(doc, &scores, None), ranking)
+    }
+
+    /// All nodes that received a score from the text nodes that pass the
+    /// thresholds of the `profile`, with the highest score first. Nodes
+    /// with the same score are in document order.
+    ///
+    /// This is the table [`ArticleTextNodeExtractor::calculate_best_node`]
+    /// selects the first node of.
+    pub fn rank_candidates(doc: &Document, lang: Language, profile: ExtractionProfile) -> Vec<CandidateScore> {
+        let candidates = Self::candidates(doc, &lang, profile, None);
+        let scores = Self::score_candidates(&lang, candidates.iter());
+        Self::ranking(doc, &lang, &scores)
+    }
+
+    /// The [`CandidateScore`] of each scored node.
+    fn ranking(doc: &Document, lang: &Language, scores: &NodeScores) -> Vec<CandidateScore> {
+        scores
+            .ranked
+            .iter()
+            .filter_map(|&(node_index, score)| {
+                let node = Node::new(doc, node_index)?;
+                let text = node.text();
+                Some(CandidateScore {
+                    node_index,
+                    tag: node.name().map(str::to_string),
+                    class: node.attr("class").map(str::to_string),
+                    id: node.attr("id").map(str::to_string),
+                    score,
+                    stopwords: lang
+                        .stopword_count(&text)
+                        .map(|stats| stats.stopword_count)
+                        .unwrap_or_default(),
+                    link_density: node.link_density(),
+                    text_len: text.len(),
+                })
+            })
+            .collect()
+    }
+
+    /// Like [`ArticleTextNodeExtractor::calculate_best_node`], but repeats
+    /// the scoring with the [`ExtractionProfile::Relaxed`] thresholds if the
+    /// strict pass finds no text node or its text is shorter than
+    /// [`ExtractionProfile::MIN_STRICT_TEXT_LEN`].
+    ///
+    /// The candidates are only collected once for both passes. Returns the
+    /// node together with the profile that selected it.
+    pub fn calculate_best_node_adaptive(
+        doc: &Document,
+        lang: Language,
+    ) -> Option<(ArticleTextNode, ExtractionProfile)> {
+        Self::calculate_best_node_adaptive_with_filters(doc, lang, None)
+    }
 
-        let txt_nodes: Vec<_> = ArticleTextNodeExtractor::nodes_to_check(doc)
-            .filter(|n| !n.is_noise_node())
+    /// Like [`ArticleTextNodeExtractor::calculate_best_node_adaptive`], but
+    /// the nodes and texts the `filters` deny are skipped as well, also by
+    /// the selected node.
+    pub fn calculate_best_node_adaptive_with_filters<'a>(
+        doc: &'a Document,
+        lang: Language,
+        filters: Option<&Arc<ExtractionFilters>>,
+    ) -> Option<(ArticleTextNode<'a>, ExtractionProfile)> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("calculate_best_node").entered();
+        if let Some(article_node) = doc
+            .find(Attr("itemprop", "articleBody"))
+            .find(|node| !filters.map(|filters| filters.denies_node_or_ancestor(node)).unwrap_or_default())
+        {
+            return Some((
+                ArticleTextNode::with_confidence(article_node, 0.95)
+                    .selection(NodeSelection::ItempropArticleBody)
+                    .filters(filters.cloned()),
+                ExtractionProfile::Strict,
+            ));
+        }
+
+        let candidates = Self::candidates(doc, &lang, ExtractionProfile::Relaxed, filters);
+        let strict_candidates: Vec<_> = candidates
+            .iter()
+            .filter(|candidate| candidate.is_accepted_by(ExtractionProfile::Strict))
+            .collect();
+        let strict = if strict_candidates.is_empty() {
+            None
+        } else {
+            Self::best_candidate(doc, &lang, strict_candidates.into_iter(), filters)
+        };
+        let strict_text_len = strict
+            .as_ref()
+            .map(|node| node.clean_text().chars().count())
+            .unwrap_or_default();
+        if strict_text_len >= ExtractionProfile::MIN_STRICT_TEXT_LEN {
+            return strict.map(|node| (node, ExtractionProfile::Strict));
+        }
+
+        let relaxed = Self::best_candidate(doc, &lang, candidates.iter(), filters);
+        match (strict, relaxed) {
+            (_, Some(relaxed))
+                if relaxed.clean_text().chars().count() > strict_text_len =>
+            {
+                Some((relaxed, ExtractionProfile::Relaxed))
+            }
+            (Some(strict), _) => Some((strict, ExtractionProfile::Strict)),
+            (None, relaxed) => relaxed.map(|node| (node, ExtractionProfile::Relaxed)),
+        }
+    }
+
+    /// All text nodes that pass the thresholds of the `profile` and aren't
+    /// denied by the `filters`.
+    fn candidates<'a>(
+        doc: &'a Document,
+        lang: &Language,
+        profile: ExtractionProfile,
+        filters: Option<&Arc<ExtractionFilters>>,
+    ) -> Vec<TextCandidate<'a>> {
+        // noise nodes are already skipped by `nodes_to_check`
+        ArticleTextNodeExtractor::nodes_to_check(doc, filters)
             .filter_map(|node| {
                 // Extract text ONCE and reuse for all subsequent checks
                 let text = node.text();
@@ -658,7 +1760,7 @@ impl ArticleTextNodeExtractor {
 
                 // Cheap checks first - fail fast before expensive operations
                 // 1. Check length (cheapest - just len())
-                if text_len < Self::MIN_TEXT_LENGTH {
+                if text_len < profile.min_text_length() {
                     return None;
                 }
 
@@ -666,21 +1768,89 @@ impl ArticleTextNodeExtractor {
                 if text.trim().is_empty() || ArticleTextNode::is_noise_text(&text) {
                     return None;
                 }
+                if filters.map(|filters| filters.denies_text(text.trim())).unwrap_or_default() {
+                    return None;
+                }
 
                 // 3. Check link density (medium cost - requires DOM traversal)
                 let link_density = node.link_density();
-                if link_density > Self::MAX_LINK_DENSITY {
+                if link_density > profile.max_link_density() {
                     return None;
                 }
 
                 // 4. Stopword counting LAST (most expensive operation!)
-                if let Some(stats) = lang.stopword_count(&text) {
-                    if stats.stopword_count >= Self::MINIMUM_STOPWORD_COUNT {
-                        let score = Self::calculate_node_score(&node, stats.stopword_count);
-                        return Some((node, stats, score));
-                    }
+                let stats = lang.stopword_count(&text)?;
+                if stats.stopword_count < profile.minimum_stopword_count() {
+                    return None;
                 }
-                None
+                Some(TextCandidate {
+                    node,
+                    text_len,
+                    link_density,
+                    stats,
+                })
+            })
+            .collect()
+    }
+
+    /// Score the candidates and select the best node.
+    fn best_candidate<'a, 'b>(
+        doc: &'a Document,
+        lang: &Language,
+        candidates: impl Iterator<Item = &'b TextCandidate<'a>>,
+        filters: Option<&Arc<ExtractionFilters>>,
+    ) -> Option<ArticleTextNode<'a>>
+    where
+        'a: 'b,
+    {
+        Self::select_best(doc, &Self::score_candidates(lang, candidates), filters)
+    }
+
+    /// The highest scoring node of the `scores`, that skips the nodes and
+    /// texts the `filters` deny.
+    fn select_best<'a>(
+        doc: &'a Document,
+        scores: &NodeScores,
+        filters: Option<&Arc<ExtractionFilters>>,
+    ) -> Option<ArticleTextNode<'a>> {
+        let (best_index, best_score) = scores.ranked.first().copied().unwrap_or((0, 0));
+
+        // Calculate confidence based on score and other factors
+        let confidence = Self::calculate_confidence(best_score, scores.candidate_count);
+
+        let top_scores: Vec<_> = scores.ranked.iter().take(3).map(|(_, score)| *score).collect();
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            candidates = scores.candidate_count,
+            node_index = best_index,
+            score = best_score,
+            confidence,
+            "selected article node"
+        );
+
+        Some(
+            ArticleTextNode::with_confidence(Node::new(doc, best_index).unwrap(), confidence)
+                .scored(scores.candidate_count, top_scores)
+                .filters(filters.cloned()),
+        )
+    }
+
+    /// Score the candidates and propagate the scores to their parents.
+    fn score_candidates<'a, 'b>(
+        lang: &Language,
+        candidates: impl Iterator<Item = &'b TextCandidate<'a>>,
+    ) -> NodeScores
+    where
+        'a: 'b,
+    {
+        let mut starting_boost = 1.0;
+
+        let txt_nodes: Vec<_> = candidates
+            .map(|candidate| {
+                let score =
+                    Self::calculate_node_score(&candidate.node, candidate.stats.stopword_count);
+                (candidate.node, &candidate.stats, score)
             })
             .collect();
 
@@ -692,7 +1862,7 @@ impl ArticleTextNodeExtractor {
         for (i, (node, stats, base_score)) in txt_nodes.iter().enumerate() {
             let mut boost_score = 0.0;
 
-            if ArticleTextNodeExtractor::is_boostable(node, lang.clone()) {
+            if ArticleTextNodeExtractor::is_boostable(node, lang) {
                 boost_score = (1.0 / starting_boost) * 50.0;
                 starting_boost += 1.0;
             }
@@ -720,20 +1890,16 @@ impl ArticleTextNodeExtractor {
             Self::propagate_score_to_parents(node, upscore, &mut nodes_scores);
         }
 
-        // Find the best scoring node
-        let (best_index, best_score) = nodes_scores
-            .iter()
-            .max_by_key(|(_, (score, _))| score)
-            .map(|(&idx, &(score, _))| (idx, score))
-            .unwrap_or((0, 0));
-
-        // Calculate confidence based on score and other factors
-        let confidence = Self::calculate_confidence(best_score, nodes_number);
-
-        Some(ArticleTextNode::with_confidence(
-            Node::new(doc, best_index).unwrap(),
-            confidence,
-        ))
+        // the best scoring node first, ties in document order
+        let mut ranked: Vec<_> = nodes_scores
+            .into_iter()
+            .map(|(idx, (score, _))| (idx, score))
+            .collect();
+        ranked.sort_unstable_by(|(idx_a, a), (idx_b, b)| b.cmp(a).then(idx_a.cmp(idx_b)));
+        NodeScores {
+            ranked,
+            candidate_count: nodes_number,
+        }
     }
 
     fn calculate_node_score(node: &Node, stopword_count: usize) -> usize {
@@ -843,12 +2009,15 @@ impl ArticleTextNodeExtractor {
     }
 
     /// Returns all nodes we want to search on like paragraphs and tables
-    fn nodes_to_check(doc: &Document) -> impl Iterator<Item = Node> {
-        TextNodeFind::new(doc)
+    fn nodes_to_check<'a>(
+        doc: &'a Document,
+        filters: Option<&Arc<ExtractionFilters>>,
+    ) -> impl Iterator<Item = Node<'a>> {
+        TextNodeFind::new(doc, filters.cloned())
     }
 
     /// Enhanced boostable check that considers both previous and next siblings
-    fn is_boostable(node: &Node, lang: Language) -> bool {
+    fn is_boostable(node: &Node, lang: &Language) -> bool {
         let mut steps_away = 0;
         
         // Check previous siblings
@@ -857,7 +2026,7 @@ impl ArticleTextNodeExtractor {
             if steps_away >= Self::MAX_STEPSAWAY_FROM_NODE {
                 break;
             }
-            if Self::is_quality_paragraph(&sibling, lang.clone()) {
+            if Self::is_quality_paragraph(&sibling, lang) {
                 return true;
             }
             steps_away += 1;
@@ -871,7 +2040,7 @@ impl ArticleTextNodeExtractor {
             if steps_away >= Self::MAX_STEPSAWAY_FROM_NODE {
                 break;
             }
-            if Self::is_quality_paragraph(&sibling, lang.clone()) {
+            if Self::is_quality_paragraph(&sibling, lang) {
                 return true;
             }
             steps_away += 1;
@@ -881,7 +2050,7 @@ impl ArticleTextNodeExtractor {
         false
     }
 
-    fn is_quality_paragraph(node: &Node, lang: Language) -> bool {
+    fn is_quality_paragraph(node: &Node, lang: &Language) -> bool {
         if node.link_density() > Self::MAX_LINK_DENSITY {
             return false;
         }
@@ -902,6 +2071,93 @@ impl ArticleTextNodeExtractor {
         txt.split(|c: char| c.is_whitespace() || is_punctuation(c))
             .filter(|s| !s.is_empty())
     }
+
+    /// The `n` most frequent words of the `text` that aren't stopwords of
+    /// the `lang`, with their frequency relative to the most frequent one.
+    ///
+    /// Words are lowercased, stripped of punctuation and must have at least
+    /// [`Self::MIN_KEYWORD_LENGTH`] chars and one letter. Words of the same
+    /// frequency are ordered by their first occurrence.
+    pub fn keywords(text: &str, lang: Language, n: usize) -> Vec<(String, f64)> {
+        let terms = Self::keyword_clauses(text, &lang).into_iter().flatten().flatten();
+        Self::rank_terms(terms, n)
+    }
+
+    /// Like [`Self::keywords`], but ranks pairs of adjacent words, like
+    /// `harbour expansion`, that are both keywords.
+    ///
+    /// Pairs don't span stopwords or the end of a sentence.
+    pub fn keyword_bigrams(text: &str, lang: Language, n: usize) -> Vec<(String, f64)> {
+        let terms = Self::keyword_clauses(text, &lang).into_iter().flat_map(|clause| {
+            clause
+                .windows(2)
+                .filter_map(|pair| match pair {
+                    [Some(first), Some(second)] => Some(format!("{} {}", first, second)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        });
+        Self::rank_terms(terms, n)
+    }
+
+    /// The normalized words of each sentence or clause of the `text`, `None`
+    /// for words that can't be keywords.
+    fn keyword_clauses(text: &str, lang: &Language) -> Vec<Vec<Option<String>>> {
+        text.split(['.', '!', '?', ';', ':', '\n'])
+            .map(|clause| {
+                Self::words(clause)
+                    .map(|word| {
+                        let word = word
+                            .trim_matches(|c: char| is_punctuation(c) || !c.is_alphanumeric())
+                            .to_lowercase();
+                        (word.chars().count() >= Self::MIN_KEYWORD_LENGTH
+                            && word.chars().any(char::is_alphabetic)
+                            && !lang.is_stopword(&word))
+                        .then_some(word)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The `n` most frequent `terms` with scores from `0.0` to `1.0`.
+    fn rank_terms(terms: impl Iterator<Item = String>, n: usize) -> Vec<(String, f64)> {
+        let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+        for (idx, term) in terms.enumerate() {
+            counts.entry(term).or_insert((0, idx)).0 += 1;
+        }
+        let mut ranked: Vec<_> = counts.into_iter().collect();
+        ranked.sort_by(|(_, (a, first_a)), (_, (b, first_b))| b.cmp(a).then(first_a.cmp(first_b)));
+        let max = ranked.first().map(|(_, (count, _))| *count).unwrap_or(1) as f64;
+        ranked
+            .into_iter()
+            .take(n)
+            .map(|(term, (count, _))| (term, count as f64 / max))
+            .collect()
+    }
+}
+
+/// Escape the special html characters of `s`, including quotes when `s` is
+/// used as an attribute value.
+fn escape_html(s: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            '\u{a0}' => escaped.push_str("&nbsp;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The `text` with all runs of whitespace collapsed into single spaces and
+/// without leading and trailing whitespace.
+pub(crate) fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
 }
 
 /// Whether the char is a punctuation.
@@ -920,10 +2176,9 @@ pub fn author_text(node: Node) -> String {
 
     let mut string = String::new();
     let mut descendants = node.descendants();
-    let hidden = Class("hidden").or(Class("sr-only")).or(Class("visually-hidden"));
 
     'outer: while let Some(child) = descendants.next() {
-        if hidden.matches(&child) || child.is_noise_node() {
+        if has_hidden_class(&child) || child.is_noise_node() {
             // skip every node under this bad node
             for ignore in child.descendants() {
                 if let Some(next) = descendants.next() {
@@ -945,6 +2200,143 @@ pub fn author_text(node: Node) -> String {
     string.trim().to_string()
 }
 
+/// The scores of the nodes the text nodes propagated their scores to.
+struct NodeScores {
+    /// Node index and score, the highest score first.
+    ranked: Vec<(usize, usize)>,
+    /// Number of scored text nodes.
+    candidate_count: usize,
+}
+
+/// A node of the ranking the article node is selected from, see
+/// [`ArticleTextNodeExtractor::rank_candidates`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct CandidateScore {
+    /// The index of the node in the document, see [`Node::index`].
+    pub node_index: usize,
+    /// The tag name of the node.
+    pub tag: Option<String>,
+    /// The `class` attribute of the node.
+    pub class: Option<String>,
+    /// The `id` attribute of the node.
+    pub id: Option<String>,
+    /// The score the text nodes within propagated to the node.
+    pub score: usize,
+    /// Number of stopwords of the node's text.
+    pub stopwords: usize,
+    /// Share of the node's text that is link text, see
+    /// [`TextContainer::link_density`].
+    pub link_density: f64,
+    /// Length of the node's text in bytes.
+    pub text_len: usize,
+}
+
+/// A text node that passed the thresholds of an [`ExtractionProfile`].
+struct TextCandidate<'a> {
+    node: Node<'a>,
+    text_len: usize,
+    link_density: f64,
+    stats: WordsStats,
+}
+
+impl TextCandidate<'_> {
+    fn is_accepted_by(&self, profile: ExtractionProfile) -> bool {
+        self.text_len >= profile.min_text_length()
+            && self.link_density <= profile.max_link_density()
+            && self.stats.stopword_count >= profile.minimum_stopword_count()
+    }
+}
+
+/// How the article's text node was selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde0", serde(rename_all = "snake_case"))]
+pub enum NodeSelection {
+    /// The only node that matches one of the [`ARTICLE_BODY_ATTR`]
+    /// selectors.
+    ArticleBodyAttr,
+    /// The first node marked with `itemprop="articleBody"`.
+    ItempropArticleBody,
+    /// The best of the scored text nodes.
+    Scored,
+}
+
+/// Details about the selection of the article's text node, see
+/// [`ArticleTextNode::diagnostics`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Diagnostics {
+    /// How the node was selected.
+    pub selection: NodeSelection,
+    /// Confidence in the selected node, between `0.0` and `1.0`.
+    pub confidence: f64,
+    /// The tag name of the node.
+    pub tag: Option<String>,
+    /// The `class` attribute of the node.
+    pub class: Option<String>,
+    /// The `id` attribute of the node.
+    pub id: Option<String>,
+    /// Number of scored text nodes, `0` unless the node was scored.
+    pub candidate_count: usize,
+    /// The three highest scores, descending.
+    pub top_scores: Vec<usize>,
+    /// Number of non-whitespace chars of the node's text that the noise
+    /// filters removed.
+    pub discarded_chars: usize,
+    /// The user agent the article was requested with, if the
+    /// [`crate::Extrablatt`] rotates user agents.
+    pub user_agent: Option<String>,
+}
+
+/// The thresholds a text node must pass to be considered for the article
+/// node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde0", serde(rename_all = "snake_case"))]
+pub enum ExtractionProfile {
+    /// The default thresholds of [`ArticleTextNodeExtractor`].
+    Strict,
+    /// Lower minimum text length and stopword count, higher max link density.
+    Relaxed,
+}
+
+impl ExtractionProfile {
+    /// Texts of the strict pass shorter than this are extracted again with
+    /// the relaxed profile.
+    pub const MIN_STRICT_TEXT_LEN: usize = 200;
+
+    pub fn min_text_length(&self) -> usize {
+        match self {
+            ExtractionProfile::Strict => ArticleTextNodeExtractor::MIN_TEXT_LENGTH,
+            ExtractionProfile::Relaxed => 25,
+        }
+    }
+
+    pub fn max_link_density(&self) -> f64 {
+        match self {
+            ExtractionProfile::Strict => ArticleTextNodeExtractor::MAX_LINK_DENSITY,
+            ExtractionProfile::Relaxed => 0.7,
+        }
+    }
+
+    pub fn minimum_stopword_count(&self) -> usize {
+        match self {
+            ExtractionProfile::Strict => ArticleTextNodeExtractor::MINIMUM_STOPWORD_COUNT,
+            ExtractionProfile::Relaxed => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for ExtractionProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtractionProfile::Strict => write!(f, "strict profile"),
+            ExtractionProfile::Relaxed => write!(f, "relaxed profile"),
+        }
+    }
+}
+
 /// Enhanced statistic about words for a text.
 #[derive(Debug, Clone)]
 pub struct WordsStats {
@@ -952,6 +2344,615 @@ pub struct WordsStats {
     pub word_count: usize,
     /// All the stop words.
     pub stopword_count: usize,
-    /// Average word length
+    /// Average number of characters of the words.
     pub avg_word_length: f64,
-}
\ No newline at end of file
+}
+
+impl WordsStats {
+    /// Count the words of the `txt`, see [`ArticleTextNodeExtractor::words`],
+    /// and the ones that are stopwords.
+    pub(crate) fn count<F: Fn(&str) -> bool>(txt: &str, is_stopword: F) -> Self {
+        let (mut word_count, mut stopword_count, mut char_count) = (0usize, 0usize, 0usize);
+        for word in ArticleTextNodeExtractor::words(txt) {
+            word_count += 1;
+            char_count += word.chars().count();
+            if is_stopword(word) {
+                stopword_count += 1;
+            }
+        }
+        WordsStats {
+            word_count,
+            stopword_count,
+            avg_word_length: match word_count {
+                0 => 0.0,
+                _ => char_count as f64 / word_count as f64,
+            },
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_class_tokens() {
+        for class in ["page-footer", "Sidebar left", "c-widget", "related-articles", "article-bottom", "articleBottom", "pageFooter"] {
+            assert!(is_noise_class(class), "{}", class);
+        }
+        for class in ["article-footerless", "subheader-text", "widgetized-story", "unrelated", "related", "bottom-line"] {
+            assert!(!is_noise_class(class), "{}", class);
+        }
+
+        let doc = Document::from(
+            r#"<html><body><article class="article-footerless">
+            <div class="story-body"><p id="kept">The council approved the expansion of the harbour.</p></div>
+            <div class="site-footer"><div><p id="dropped">Subscribe to our newsletter.</p></div></div>
+            </article></body></html>"#,
+        );
+        let cache = NoiseCache::new();
+        let kept = doc.find(Attr("id", "kept")).next().unwrap();
+        let dropped = doc.find(Attr("id", "dropped")).next().unwrap();
+        assert!(!kept.is_noise_node());
+        assert!(dropped.is_noise_node());
+        assert!(!cache.is_noise(&kept));
+        assert!(cache.is_noise(&dropped));
+        // answered from the cached verdicts of the ancestors
+        assert!(cache.is_noise(&dropped.parent().unwrap()));
+        assert!(!cache.is_noise(&kept.parent().unwrap()));
+    }
+
+    #[test]
+    fn image_credit_classes() {
+        let doc = Document::from(
+            r#"<html><body><figcaption>
+            <span class="image-credit">Photo: Jane Doe</span>
+            <span class="photoCredit">Photo: John Roe</span>
+            <span class="discredit-banner">The claims were discredited.</span>
+            <span class="sourced">Read more</span>
+            </figcaption></body></html>"#,
+        );
+        let credits: Vec<_> = doc.find(Name("span")).map(|span| is_image_credit(&span)).collect();
+        assert_eq!(credits, vec![true, true, false, false]);
+    }
+
+    #[test]
+    fn images_with_captions() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>The new bridge was opened on Monday after three years of construction work.</p>
+            <figure>
+                <img src="/img/bridge.jpg" alt="The bridge at night">
+                <figcaption>The bridge lit up after the opening. <span class="image-credit">Photo: Jane Doe/Reuters</span></figcaption>
+            </figure>
+            <img src="/img/map.png">
+            </article></body></html>"#,
+        );
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+
+        let images = node.images_detailed(Some(&base_url));
+        assert_eq!(
+            images,
+            vec![
+                ArticleImage {
+                    url: Url::parse("https://example.com/img/bridge.jpg").unwrap(),
+                    caption: Some("The bridge lit up after the opening.".to_string()),
+                    credit: Some("Photo: Jane Doe/Reuters".to_string()),
+                    alt: Some("The bridge at night".to_string()),
+                },
+                ArticleImage {
+                    url: Url::parse("https://example.com/img/map.png").unwrap(),
+                    caption: None,
+                    credit: None,
+                    alt: None,
+                },
+            ]
+        );
+        assert_eq!(
+            node.images(Some(&base_url)),
+            vec![Url::parse("https://example.com/img/map.png").unwrap()]
+        );
+        assert!(!node.clean_text().contains("lit up"));
+    }
+
+    #[test]
+    fn text_nodes_skip_excluded_subtrees() {
+        let mut doc = Document::from(
+            r#"<html><body>
+            <aside><p>Most read: the ten best ferries of the season.</p>
+              <div class="content"><p>Sign up for our newsletter.</p></div></aside>
+            <div class="article-content">
+              <p>The harbour will be expanded, the city council decided on Tuesday.</p>
+              <nav><p>Next article</p></nav>
+              <p>The first ships are expected in the spring.</p>
+            </div>
+            <footer><p>Imprint</p></footer>
+            <p>Construction will last for ten years.</p>
+            </body></html>"#,
+        );
+        // a paragraph of the aside after the end of the document, so the
+        // subtree of the aside isn't a contiguous range of the nodes
+        let aside = doc.find(Name("aside")).next().unwrap().index();
+        let paragraph = doc.find(Name("p")).next().unwrap().index();
+        let (index, last) = (doc.nodes.len(), doc.nodes[aside].last_child.unwrap());
+        let mut appended = doc.nodes[paragraph].clone();
+        let mut text = doc.nodes[paragraph + 1].clone();
+        appended.index = index;
+        appended.parent = Some(aside);
+        appended.prev = Some(last);
+        appended.next = None;
+        appended.first_child = Some(index + 1);
+        appended.last_child = Some(index + 1);
+        text.index = index + 1;
+        text.parent = Some(index);
+        doc.nodes[last].next = Some(index);
+        doc.nodes[aside].last_child = Some(index);
+        doc.nodes.extend([appended, text]);
+
+        let texts: Vec<String> = TextNodeFind::new(&doc, None)
+            .filter(|node| Name("p").matches(node))
+            .map(|node| node.text())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "The harbour will be expanded, the city council decided on Tuesday.",
+                "The first ships are expected in the spring.",
+                "Construction will last for ten years.",
+            ]
+        );
+    }
+
+    #[test]
+    fn gallery_images() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>The harbour will be expanded, the city council decided on Tuesday after a long debate.</p>
+            <img src="/img/quay.jpg" width="800" height="450">
+            <div class="gallery">
+              <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" data-src="/img/quay.jpg">
+              <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=" data-src="/img/crane.jpg">
+              <img src="data:image/gif;base64,R0lGODlhAQABAAAAACw=">
+              <img src="/img/ship.jpg" width="640px">
+            </div>
+            <img src="/icons/share.svg"><img src="/img/avatar.jpg" width="48" height="48">
+            </article></body></html>"#,
+        );
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let url = |path: &str| base_url.join(path).unwrap();
+        assert_eq!(
+            node.images(Some(&base_url)),
+            vec![url("/img/quay.jpg"), url("/img/crane.jpg"), url("/img/ship.jpg")]
+        );
+        assert_eq!(
+            node.images_with_filter(Some(&base_url), &ImageFilter::default().dedup(false).min_size(0)),
+            vec![
+                url("/img/quay.jpg"),
+                url("/img/quay.jpg"),
+                url("/img/crane.jpg"),
+                url("/img/ship.jpg"),
+                url("/img/avatar.jpg"),
+            ]
+        );
+        assert_eq!(node.images_with_filter(Some(&base_url), &ImageFilter::none()).len(), 7);
+    }
+
+    #[test]
+    fn promotional_content() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>The new phone has a larger battery and a brighter screen than its predecessor.</p>
+            <p>Buy it <a href="https://amzn.to/3xYz">here</a> or at <a href="https://shop.example.com/p?affid=7">the shop</a>.</p>
+            <p>Get our <a rel="nofollow" href="/app">app</a></p>
+            </article></body></html>"#,
+        );
+        let base_url = Url::parse("https://example.com/").unwrap();
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+
+        let promo = node.promotional_content(Some(&base_url));
+        assert_eq!(promo.len(), 2);
+        assert_eq!(promo[1].text, "Get our app");
+        assert_eq!(node.affiliate_link_count(Some(&base_url)), 2);
+        let text = node.clean_text();
+        assert!(text.contains("Buy it here"));
+        assert!(!text.contains("Get our app"));
+    }
+
+    #[test]
+    fn keyword_frequencies() {
+        let text = "The harbour expansion was approved. The harbour expansion will cost millions, \
+            and the harbour will double in size.";
+        let keywords = ArticleTextNodeExtractor::keywords(text, Language::English, 3);
+        assert_eq!(keywords[0], ("harbour".to_string(), 1.0));
+        assert_eq!(keywords[1].0, "expansion");
+        assert!((keywords[1].1 - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(keywords.len(), 3);
+
+        let bigrams = ArticleTextNodeExtractor::keyword_bigrams(text, Language::English, 5);
+        assert_eq!(bigrams[0], ("harbour expansion".to_string(), 1.0));
+        assert!(bigrams.iter().all(|(bigram, _)| bigram != "approved harbour"));
+
+        let text = "Порт расширят. Расширение порта одобрено, порт станет больше. Café, café and CAFÉ!";
+        let keywords = ArticleTextNodeExtractor::keywords(text, Language::Russian, 2);
+        assert_eq!(keywords[0], ("café".to_string(), 1.0));
+        assert_eq!(keywords[1].0, "порт");
+    }
+
+    #[test]
+    fn link_clusters_are_pruned() {
+        let doc = Document::from(
+            r##"<html><body><article>
+            <p>The city council approved the expansion of the harbour on Tuesday, after a
+            <a href="/politics/debate.html">long debate</a> about its costs.</p>
+            <p>Construction is expected to start next spring and to last for three years.</p>
+            <p><a href="https://facebook.com/share">Share on Facebook</a> <a href="https://twitter.com/share">Share on X</a></p>
+            <p><a href="/news/harbour-more.html">Read more</a></p>
+            <div><p>Related:</p><ul>
+            <li><a href="/news/harbour-plans.html">Harbour plans revealed</a></li>
+            <li><a href="/news/ferry.html">New ferry line to the islands</a></li>
+            </ul></div>
+            </article></body></html>"##,
+        );
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        assert_eq!(node.prune().len(), 3);
+        let text = node.clean_text();
+        assert!(text.contains("long debate"));
+        assert!(text.ends_with("to last for three years."));
+        for noise in ["Share on", "Read more", "Related", "ferry"] {
+            assert!(!text.contains(noise), "{} in {}", noise, text);
+        }
+    }
+
+    #[test]
+    fn references_are_resolved() {
+        let doc = Document::from(
+            r##"<html><body><article>
+            <p>The <a href="/politics/story.html">council</a> decided on the
+            <a href="../politics/story.html">harbour</a> after a
+            <a href="https://example.com/politics/story.html">long debate</a>.</p>
+            <p><a href="#comments">Comments</a>, <a href="javascript:void(0)">share</a>,
+            <a href="mailto:news@example.com">mail</a> or <a href="other.html">read more</a>.</p>
+            </article></body></html>"##,
+        );
+        let base_url = Url::parse("https://example.com/news/harbour.html").unwrap();
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        assert_eq!(
+            node.references(Some(&base_url)),
+            vec![
+                Url::parse("https://example.com/politics/story.html").unwrap(),
+                Url::parse("https://example.com/news/other.html").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn nofollow_sources_are_preserved() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>The harbour will be expanded over the next ten years, the city council decided.</p>
+            <p>Sources: <a rel="nofollow" href="https://reuters.com/harbour">Reuters</a>, <a rel="nofollow" href="https://apnews.com/harbour">AP</a></p>
+            <p>Download <a rel="nofollow" href="/app">our app</a></p>
+            </article></body></html>"#,
+        );
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        let text = node.clean_text();
+        assert!(text.contains("Sources: Reuters, AP"));
+        assert!(!text.contains("our app"));
+
+        let attributions = node.attributions(None);
+        assert_eq!(attributions.len(), 1);
+        assert_eq!(attributions[0].links.len(), 2);
+
+        let node = node.attributions_in_text(false);
+        assert!(!node.clean_text().contains("Sources"));
+    }
+
+    #[test]
+    fn relaxed_profile_for_brief_news() {
+        let doc = Document::from(
+            r#"<html><body><div>
+            <p>The bridge is closed for repairs.</p>
+            <p>It will open again in the spring.</p>
+            <p>Buses run on a detour until then.</p>
+            </div></body></html>"#,
+        );
+        let (node, profile) =
+            ArticleTextNodeExtractor::calculate_best_node_adaptive(&doc, Language::English).unwrap();
+        assert_eq!(profile, ExtractionProfile::Relaxed);
+        assert_eq!(profile.to_string(), "relaxed profile");
+        assert!(node.clean_text().contains("detour"));
+
+        let doc = Document::from(
+            r#"<html><body><div>
+            <p>The city council voted on Tuesday to approve the new budget, which includes
+            funding for public transit and for the schools that have been waiting for it.</p>
+            <p>It was the first time in years that the measure passed without opposition from
+            any of the members of the council, who had been divided over the plan.</p>
+            </div></body></html>"#,
+        );
+        let (_, profile) =
+            ArticleTextNodeExtractor::calculate_best_node_adaptive(&doc, Language::English).unwrap();
+        assert_eq!(profile, ExtractionProfile::Strict);
+    }
+
+    #[test]
+    fn clean_html_is_sanitized() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <h2 style="color: red">Heading</h2>
+            <p onclick="steal()">Some <a href="/politics/story.html" onmouseover="x()">story</a> &amp; more.</p>
+            <script>alert(1)</script>
+            <p><a href="javascript:alert(1)">bad</a><img src="img/photo.jpg" onerror="x()"></p>
+            <div class="sidebar"><p>Ad</p></div>
+            <nav><a href="/">Home</a></nav>
+            </article></body></html>"#,
+        );
+        let base_url = Url::parse("https://example.com/news/").unwrap();
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        let html = node.clean_html(Some(&base_url));
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("onmouseover"));
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("style="));
+        assert!(!html.contains("<nav"));
+        assert!(!html.contains("Ad</p>"));
+        assert!(html.contains(r#"<a href="https://example.com/politics/story.html">story</a>"#));
+        assert!(html.contains(r#"<img src="https://example.com/news/img/photo.jpg">"#));
+        assert!(html.contains("&amp; more."));
+        assert!(html.starts_with("<article>"));
+    }
+
+    #[test]
+    fn clean_html_drops_obfuscated_script_urls() {
+        let doc = Document::from(
+            "<html><body><article>\
+            <p><a href=\"java\tscript:alert(1)\">tab</a></p>\
+            <p><a href=\"java\nscript:alert(2)\">newline</a></p>\
+            <p><a href=\"javascript&#58;alert(3)\">colon</a></p>\
+            <p><a href=\"&#106;avascript:alert(4)\">letter</a></p>\
+            <p><a href=\" JAVASCRIPT:alert(5)\">upper</a></p>\
+            <p><img src=\"data:text/html;base64,PHNjcmlwdD4=\"></p>\
+            <p><a href=\"vbscript:msgbox(6)\">vb</a></p>\
+            <svg><a href=\"/svg\">svg</a></svg>\
+            <math><mi>x</mi></math>\
+            <p><a href=\"mailto:news@example.com\">mail</a> <a href=\"story.html\">story</a></p>\
+            </article></body></html>",
+        );
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+
+        let html = node.clean_html(None);
+        assert!(!html.contains("script:"));
+        assert!(!html.contains("alert"));
+        assert!(!html.contains("msgbox"));
+        assert!(!html.contains("data:"));
+        assert!(!html.contains("<svg"));
+        assert!(!html.contains("<math"));
+        assert_eq!(html.matches("href=").count(), 2);
+        assert!(html.contains(r#"<a href="mailto:news@example.com">mail</a>"#));
+        assert!(html.contains(r#"<a href="story.html">story</a>"#));
+
+        let base_url = Url::parse("https://example.com/news/").unwrap();
+        let html = node.clean_html(Some(&base_url));
+        assert!(!html.contains("alert"));
+        assert!(html.contains(r#"<a href="https://example.com/news/story.html">story</a>"#));
+    }
+
+    #[test]
+    fn selection_diagnostics() {
+        let paragraph = "<p>The city council decided on Tuesday that the harbour will be expanded over the \
+            next ten years, and the first ships are expected to use the new quay in the spring.</p>";
+        let doc = Document::from(
+            format!(
+                r#"<html><body><div class="wrapper" id="main">{p}{p}{p}</div>
+                <div class="teaser"><p>Short teaser text that is much too short.</p></div></body></html>"#,
+                p = paragraph
+            )
+            .as_str(),
+        );
+        let node = ArticleTextNodeExtractor::calculate_best_node(&doc, Language::English).unwrap();
+        let diagnostics = node.diagnostics(&node.clean_text());
+        assert_eq!(diagnostics.selection, NodeSelection::Scored);
+        assert_eq!(diagnostics.class.as_deref(), Some("wrapper"));
+        assert_eq!(diagnostics.id.as_deref(), Some("main"));
+        assert_eq!(diagnostics.candidate_count, 3);
+        assert!(!diagnostics.top_scores.is_empty() && diagnostics.top_scores.len() <= 3);
+        assert!(diagnostics.top_scores.windows(2).all(|w| w[0] >= w[1]));
+
+        let doc = Document::from(
+            r#"<html><body><section itemprop="articleBody"><p>The harbour will be expanded.</p>
+            <div class="share">Share on Facebook</div></section></body></html>"#,
+        );
+        let node = ArticleTextNodeExtractor::calculate_best_node(&doc, Language::English).unwrap();
+        let diagnostics = node.diagnostics(&node.clean_text());
+        assert_eq!(diagnostics.selection, NodeSelection::ItempropArticleBody);
+        assert_eq!(diagnostics.tag.as_deref(), Some("section"));
+        assert_eq!(diagnostics.candidate_count, 0);
+        assert_eq!(diagnostics.confidence, 0.95);
+    }
+
+    #[test]
+    fn candidate_ranking_trace() {
+        let paragraph = "<p>The city council decided on Tuesday that the harbour will be expanded over the \
+            next ten years, and the first ships are expected to use the new quay in the spring.</p>";
+        let doc = Document::from(
+            format!(
+                r#"<html><body><main><div class="wrapper" id="main">{p}{p}{p}</div>
+                <aside class="related">{p}</aside></main></body></html>"#,
+                p = paragraph
+            )
+            .as_str(),
+        );
+        let (node, ranking) = ArticleTextNodeExtractor::calculate_best_node_with_trace(&doc, Language::English);
+        let node = node.unwrap();
+        assert_eq!(ranking[0].node_index, node.index());
+        assert_eq!(ranking[0].class.as_deref(), Some("wrapper"));
+        assert_eq!(ranking[0].tag.as_deref(), Some("div"));
+        assert!(ranking[0].stopwords > 0 && ranking[0].text_len > 0);
+        assert!(ranking.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(
+            ArticleTextNodeExtractor::rank_candidates(&doc, Language::English, ExtractionProfile::Strict),
+            ranking
+        );
+        assert_eq!(
+            node.diagnostics(&node.clean_text()).top_scores,
+            ranking.iter().take(3).map(|candidate| candidate.score).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            ArticleTextNodeExtractor::calculate_best_node(&doc, Language::English).map(|node| node.index()),
+            Some(node.index())
+        );
+    }
+
+    #[test]
+    fn blocks_skip_noise() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <h3>Standings</h3>
+            <table><tr><th>Team</th><th>Points</th></tr><tr><td>Harbour FC</td><td>12</td></tr></table>
+            <div class="sidebar"><p>Read more of our sports coverage.</p></div>
+            <ol><li>Preheat the oven</li><li>Bake for an hour</li></ol>
+            </article></body></html>"#,
+        );
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        assert_eq!(
+            node.blocks(),
+            vec![
+                ContentBlock::Heading {
+                    level: 3,
+                    text: "Standings".to_string()
+                },
+                ContentBlock::Table {
+                    headers: vec!["Team".to_string(), "Points".to_string()],
+                    rows: vec![vec!["Harbour FC".to_string(), "12".to_string()]],
+                },
+                ContentBlock::List {
+                    ordered: true,
+                    items: vec!["Preheat the oven".to_string(), "Bake for an hour".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extraction_filters() {
+        let paragraph = "<p>The city council decided on Tuesday that the harbour will be expanded over the \
+            next ten years, and the first ships are expected to use the new quay in the spring.</p>";
+        let doc = Document::from(
+            format!(
+                r#"<html><body><div class="wrapper">{p}{p}{p}
+                <div data-widget="poll"><p>Vote now in our poll about the future of the harbour and the city.</p></div>
+                <p>Advertisement: the best ferry tickets of the season, only this week in our shop.</p>
+                <img src="/img/quay.jpg" alt="The new quay"><img src="/img/ad.jpg" alt="Advertisement">
+                </div></body></html>"#,
+                p = paragraph
+            )
+            .as_str(),
+        );
+        let base_url = Url::parse("https://example.com/news/").unwrap();
+
+        let (node, _) = ArticleTextNodeExtractor::calculate_best_node_adaptive(&doc, Language::English).unwrap();
+        let text = node.clean_text();
+        assert!(text.contains("Vote now"));
+        assert!(text.contains("Advertisement:"));
+        assert_eq!(node.images(Some(&base_url)).len(), 2);
+
+        let filters = Arc::new(
+            ExtractionFilters::default()
+                .deny_node(|node| node.attr("data-widget").is_some())
+                .deny_text(|text| text.starts_with("Advertisement")),
+        );
+        let (filtered, _) = ArticleTextNodeExtractor::calculate_best_node_adaptive_with_filters(
+            &doc,
+            Language::English,
+            Some(&filters),
+        )
+        .unwrap();
+        assert_eq!(filtered.index(), node.index());
+        let filtered_text = filtered.clean_text();
+        assert!(filtered_text.contains("first ships"));
+        assert!(!filtered_text.contains("Vote now"));
+        assert!(!filtered_text.contains("Advertisement"));
+        assert_eq!(
+            filtered.images(Some(&base_url)),
+            vec![Url::parse("https://example.com/img/quay.jpg").unwrap()]
+        );
+
+        // an article body within a denied container isn't selected
+        let doc = Document::from(
+            format!(
+                r#"<html><body><div class="wrapper">{p}{p}{p}</div>
+                <div data-widget="related"><div itemprop="articleBody">{p}</div></div></body></html>"#,
+                p = paragraph
+            )
+            .as_str(),
+        );
+        let (node, _) = ArticleTextNodeExtractor::calculate_best_node_adaptive_with_filters(
+            &doc,
+            Language::English,
+            Some(&filters),
+        )
+        .unwrap();
+        assert_eq!(node.attr("class"), Some("wrapper"));
+    }
+
+    #[test]
+    fn audios_skip_sidebar_ads() {
+        let doc = Document::from(
+            r#"<html><body><article>
+            <p>In this episode we talk about the expansion of the harbour.</p>
+            <audio controls><source src="/podcast/ep12.mp3" type="audio/mpeg"></audio>
+            <div class="player" data-audio-url="/podcast/ep12.mp3"></div>
+            <aside class="sidebar"><audio src="https://ads.example.net/spot.mp3" autoplay></audio></aside>
+            </article></body></html>"#,
+        );
+        let node = ArticleTextNode::new(doc.find(Name("article")).next().unwrap());
+        assert_eq!(node.audios().len(), 2);
+        let base = Url::parse("https://example.com/podcast/12").unwrap();
+        let audios = node.audios_detailed(Some(&base));
+        assert_eq!(audios.len(), 1);
+        assert_eq!(audios[0].url.as_str(), "https://example.com/podcast/ep12.mp3");
+        assert_eq!(audios[0].mime.as_deref(), Some("audio/mpeg"));
+    }
+
+    #[test]
+    fn hidden_elements() {
+        assert!(is_hiding_style("color: red; DISPLAY : none !important"));
+        assert!(is_hiding_style("visibility:hidden"));
+        assert!(!is_hiding_style("display: block; content: 'display: none'"));
+
+        let doc = Document::from(include_str!("../tests/fixtures/body/hidden_duplicate.html"));
+        let node = ArticleTextNode::new(doc.find(Class("article-body")).next().unwrap());
+        let text = node.clean_text();
+        assert_eq!(text.matches("approved the expansion of the harbour").count(), 1);
+        assert_eq!(text.matches("Construction of the new quay").count(), 1);
+        assert_eq!(text.matches("Local residents had protested").count(), 1);
+        assert!(!text.contains("the mayor said."));
+        assert!(!text.contains("Share this article"));
+        assert!(!text.contains("Opens in a new"));
+
+        let doc = Document::from(r#"<div class="hidden md:block"><p>Desktop only</p></div>"#);
+        assert!(!has_hidden_class(&doc.find(Name("div")).next().unwrap()));
+    }
+
+    #[test]
+    fn drop_cap_with_screen_reader_text() {
+        let doc = Document::from(
+            r#"<p><span aria-hidden="true">T</span><span class="sr-only">The</span>he council approved the
+            expansion of the harbour.<span class="hidden"> It will take ten years.</span></p>"#,
+        );
+        let para = doc.find(Name("p")).next().unwrap();
+        let text = normalize_whitespace(&visible_text(&para));
+        assert_eq!(
+            text,
+            "The council approved the expansion of the harbour. It will take ten years."
+        );
+        // the noise checks still skip the screen reader text
+        let sr_only = doc.find(Class("sr-only")).next().unwrap();
+        assert!(is_hidden_element(&sr_only));
+        assert!(is_hidden_element(&sr_only.prev().unwrap()));
+    }
+}
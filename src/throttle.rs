@@ -0,0 +1,256 @@
+//! Limits for the requests of an [`crate::Extrablatt`], so crawling a site
+//! doesn't look like an attack.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex as SyncMutex};
+use std::time::Duration;
+
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::lock::Mutex;
+use futures::StreamExt;
+use reqwest::Url;
+use wasm_timer::{Delay, Instant};
+
+/// Limits the number of requests in flight and spaces the requests to the
+/// same host.
+///
+/// Clones share their limits.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    /// The available permits, one for each request that may be in flight.
+    permits: Arc<Mutex<UnboundedReceiver<()>>>,
+    /// Returns the permits of finished requests.
+    release: UnboundedSender<()>,
+    /// Minimal time between the start of two requests to the same host.
+    request_delay: Duration,
+    /// The earliest time the next request to each host may start.
+    next_requests: Arc<SyncMutex<HashMap<String, Instant>>>,
+}
+
+impl Throttle {
+    /// Default number of requests in flight.
+    pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 5;
+
+    /// Allow at most `max_concurrent_requests`, at least one, in flight and
+    /// start requests to the same host at least `request_delay` apart.
+    pub fn new(max_concurrent_requests: usize, request_delay: Duration) -> Self {
+        let (release, permits) = mpsc::unbounded();
+        for _ in 0..max_concurrent_requests.max(1) {
+            let _ = release.unbounded_send(());
+        }
+        Self {
+            permits: Arc::new(Mutex::new(permits)),
+            release,
+            request_delay,
+            next_requests: Default::default(),
+        }
+    }
+
+    /// Minimal time between the start of two requests to the same host.
+    pub fn request_delay(&self) -> Duration {
+        self.request_delay
+    }
+
+    /// Wait until a request to the `url` may be sent.
+    ///
+    /// The request counts as in flight until the returned permit is
+    /// dropped.
+    pub async fn acquire(&self, url: &Url) -> ThrottlePermit {
+        let _ = self.permits.lock().await.next().await;
+        let permit = ThrottlePermit {
+            release: self.release.clone(),
+        };
+
+        if !self.request_delay.is_zero() {
+            let host = url.host_str().unwrap_or_default().to_string();
+            let start = {
+                let mut next_requests = self.next_requests.lock().unwrap();
+                let now = Instant::now();
+                let start = match next_requests.get(&host) {
+                    Some(next) if *next > now => *next,
+                    _ => now,
+                };
+                next_requests.insert(host, start + self.request_delay);
+                start
+            };
+            let now = Instant::now();
+            if start > now {
+                let _ = Delay::new(start.duration_since(now)).await;
+            }
+        }
+        permit
+    }
+
+    /// The time left until the next request to each host may start, for
+    /// hosts whose delay hasn't elapsed yet.
+    pub fn pending_delays(&self) -> Vec<(String, Duration)> {
+        let now = Instant::now();
+        self.next_requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, next)| **next > now)
+            .map(|(host, next)| (host.clone(), next.duration_since(now)))
+            .collect()
+    }
+
+    /// Don't start requests to the `host` before `delay` from now.
+    ///
+    /// Has no effect without a [`Throttle::request_delay`].
+    pub fn delay_host(&self, host: &str, delay: Duration) {
+        let next = Instant::now() + delay;
+        let mut next_requests = self.next_requests.lock().unwrap();
+        let entry = next_requests.entry(host.to_string()).or_insert(next);
+        if *entry < next {
+            *entry = next;
+        }
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Throttle::new(Self::DEFAULT_MAX_CONCURRENT_REQUESTS, Duration::default())
+    }
+}
+
+/// A request in flight, see [`Throttle::acquire`].
+#[derive(Debug)]
+pub struct ThrottlePermit {
+    release: UnboundedSender<()>,
+}
+
+impl Drop for ThrottlePermit {
+    fn drop(&mut self) {
+        let _ = self.release.unbounded_send(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_server::{html, serve};
+    use crate::Extrablatt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn spaces_requests_to_same_host() {
+        let throttle = Throttle::new(5, Duration::from_millis(50));
+        let url = Url::parse("https://example.com/a").unwrap();
+        let other = Url::parse("https://example.org/a").unwrap();
+
+        let start = Instant::now();
+        let _first = throttle.acquire(&url).await;
+        let _other = throttle.acquire(&other).await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+        let _second = throttle.acquire(&url).await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        let _third = throttle.acquire(&url).await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn releases_permits() {
+        let throttle = Throttle::new(1, Duration::default());
+        let url = Url::parse("https://example.com/a").unwrap();
+        drop(throttle.acquire(&url).await);
+        let _permit = throttle.acquire(&url).await;
+    }
+
+    #[tokio::test]
+    async fn limits_requests_in_flight() {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let url = {
+            let (in_flight, max_in_flight) = (in_flight.clone(), max_in_flight.clone());
+            serve(move |request| {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                let body = if request.path == "/news" {
+                    (0..6)
+                        .map(|i| {
+                            format!(
+                                "<a href=\"/news/harbour-expansion-approved-by-the-council-{}.html\">Harbour</a>",
+                                i
+                            )
+                        })
+                        .collect::<String>()
+                } else {
+                    format!(
+                        "<html><head><title>{}</title></head><body><div itemprop=\"articleBody\">\
+                         <p>The harbour will be expanded over the next ten years.</p></div></body></html>",
+                        request.path
+                    )
+                };
+                std::thread::sleep(Duration::from_millis(50));
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                html(body)
+            })
+        };
+
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("news").unwrap()])
+            .max_concurrent_requests(2)
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        assert_eq!(paper.articles().len(), 6);
+
+        assert_eq!(paper.download_articles().await.successes().count(), 6);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[tokio::test]
+    async fn spaces_crawled_articles() {
+        let requests: Arc<SyncMutex<Vec<Instant>>> = Default::default();
+        let url = {
+            let requests = requests.clone();
+            serve(move |request| {
+                let body = if request.path == "/news" {
+                    (0..4)
+                        .map(|i| {
+                            format!(
+                                "<a href=\"/news/harbour-expansion-approved-by-the-council-{}.html\">Harbour</a>",
+                                i
+                            )
+                        })
+                        .collect::<String>()
+                } else {
+                    if request.path.starts_with("/news/") {
+                        requests.lock().unwrap().push(Instant::now());
+                    }
+                    format!(
+                        "<html><head><title>{}</title></head><body><div itemprop=\"articleBody\">\
+                         <p>The harbour will be expanded over the next ten years.</p></div></body></html>",
+                        request.path
+                    )
+                };
+                html(body)
+            })
+        };
+
+        let mut paper = Extrablatt::builder(url.clone())
+            .unwrap()
+            .categories(false)
+            .include_categories(vec![url.join("news").unwrap()])
+            .request_delay(Duration::from_millis(60))
+            .build()
+            .await
+            .unwrap();
+        paper.download_all_remaining_categories().await;
+        assert_eq!(paper.download_articles().await.successes().count(), 4);
+
+        let mut requests = requests.lock().unwrap().clone();
+        requests.sort();
+        assert_eq!(requests.len(), 4);
+        for pair in requests.windows(2) {
+            // some slack for the time the request takes to arrive
+            let gap = pair[1].duration_since(pair[0]);
+            assert!(gap >= Duration::from_millis(50), "requests only {:?} apart", gap);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! `tracing` instrumentation of downloads and document parsing.
+//!
+//! Without the `tracing` feature the helpers only forward to the
+//! instrumented code.
+
+use std::fmt::Debug;
+use std::future::Future;
+
+use reqwest::{StatusCode, Url};
+use select::document::Document;
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
+#[cfg(feature = "tracing")]
+use wasm_timer::Instant;
+
+/// Run the `download` of the `url` within a `fetch` span and emit an event
+/// with the `status` of its response and the duration when it finishes.
+///
+/// The size of the body is recorded in the span by [`record_bytes`].
+#[cfg(feature = "tracing")]
+pub(crate) async fn fetch<T, E, F, S>(url: &Url, download: F, status: S) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Debug,
+    S: FnOnce(&T) -> StatusCode,
+{
+    let span = tracing::debug_span!("fetch", url = %url, bytes = tracing::field::Empty);
+    let started = Instant::now();
+    let result = download.instrument(span.clone()).await;
+    let duration_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        Ok(value) => tracing::debug!(
+            parent: &span,
+            url = %url,
+            status = status(value).as_u16(),
+            duration_ms,
+            "fetched"
+        ),
+        Err(error) => tracing::debug!(parent: &span, url = %url, duration_ms, ?error, "fetch failed"),
+    }
+    result
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline]
+pub(crate) async fn fetch<T, E, F, S>(_url: &Url, download: F, _status: S) -> Result<T, E>
+where
+    F: Future<Output = Result<T, E>>,
+    E: Debug,
+    S: FnOnce(&T) -> StatusCode,
+{
+    download.await
+}
+
+/// Record the size of a downloaded body in the current `fetch` span.
+#[inline]
+pub(crate) fn record_bytes(bytes: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("bytes", bytes);
+    #[cfg(not(feature = "tracing"))]
+    let _ = bytes;
+}
+
+/// Parse the `html` within a `parse` span and emit an event with the number
+/// of nodes and the duration.
+#[inline]
+pub(crate) fn parse(html: &str) -> Document {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("parse", bytes = html.len()).entered();
+    #[cfg(feature = "tracing")]
+    let started = Instant::now();
+    let doc = Document::from(html);
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        nodes = doc.nodes.len(),
+        duration_ms = started.elapsed().as_millis() as u64,
+        "parsed document"
+    );
+    doc
+}
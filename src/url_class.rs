@@ -0,0 +1,257 @@
+//! Classify article urls before they are downloaded, so archives and static
+//! pages are skipped and likely articles are downloaded first.
+
+use std::fmt;
+use std::sync::Arc;
+
+use url::Url;
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
+
+use crate::date::{RE_DATE_SEGMENTS_M_D_Y, RE_DATE_SEGMENTS_Y_M_D};
+
+/// Path segments of listings like tag pages, author archives and search
+/// results.
+pub const NON_ARTICLE_SEGMENTS: [&str; 14] = [
+    "tag", "tags", "author", "authors", "search", "category", "categories", "topic", "topics", "page",
+    "archive", "archives", "profile", "people",
+];
+
+/// Last path segments of static pages of a site.
+pub const STATIC_PAGE_SEGMENTS: [&str; 14] = [
+    "about", "contact", "imprint", "impressum", "privacy", "terms", "login", "register", "subscribe",
+    "newsletter", "sitemap", "faq", "jobs", "careers",
+];
+
+/// Min number of digits of a path segment that is an article id.
+pub const MIN_ARTICLE_ID_DIGITS: usize = 5;
+
+/// Min number of words of a hyphenated slug of an article.
+pub const MIN_SLUG_WORDS: usize = 4;
+
+/// The rule that rejected an article url.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum RejectRule {
+    /// A path segment of [`NON_ARTICLE_SEGMENTS`].
+    NonArticleSegment,
+    /// The last path segment is one of [`STATIC_PAGE_SEGMENTS`].
+    StaticPage,
+    /// Rejected by the [`ArticleUrlFilter`] of the
+    /// [`crate::ExtrablattBuilder::article_url_filter`].
+    Custom,
+}
+
+/// How likely an url points to an article.
+///
+/// Likely articles are downloaded before ambiguous ones, rejected urls
+/// aren't downloaded at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub enum UrlClass {
+    /// A date in the path, a numeric id or a long slug.
+    Likely,
+    /// Neither likely an article nor rejected.
+    Ambiguous,
+    /// Not an article, so it isn't downloaded.
+    Rejected(RejectRule),
+}
+
+impl UrlClass {
+    /// Whether the url isn't downloaded.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, UrlClass::Rejected(_))
+    }
+}
+
+/// Classify the `url` by its path.
+///
+/// The url is rejected if the last segment is one of
+/// [`STATIC_PAGE_SEGMENTS`] or, unless the path contains a date like
+/// `/2024/05/`, a segment is one of [`NON_ARTICLE_SEGMENTS`]. It's likely an
+/// article if the path contains a date, a segment with at least
+/// [`MIN_ARTICLE_ID_DIGITS`] digits or a slug of [`MIN_SLUG_WORDS`] words.
+pub fn classify_article_url(url: &Url) -> UrlClass {
+    let segments: Vec<String> = url
+        .path_segments()
+        .map(|segments| {
+            segments
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_lowercase)
+                .collect()
+        })
+        .unwrap_or_default();
+    let has_date = RE_DATE_SEGMENTS_Y_M_D.is_match(url.path())
+        || RE_DATE_SEGMENTS_M_D_Y.is_match(url.path())
+        || segments.windows(2).any(|pair| is_year(&pair[0]) && is_month(&pair[1]));
+    if !has_date && segments.iter().any(|segment| NON_ARTICLE_SEGMENTS.contains(&segment.as_str())) {
+        return UrlClass::Rejected(RejectRule::NonArticleSegment);
+    }
+    let Some(last) = segments.last() else {
+        return UrlClass::Ambiguous;
+    };
+    let stem = last.split('.').next().unwrap_or_default();
+    if STATIC_PAGE_SEGMENTS.contains(&stem) {
+        return UrlClass::Rejected(RejectRule::StaticPage);
+    }
+    let has_id = segments.iter().any(|segment| {
+        segment
+            .split(|c: char| !c.is_ascii_digit())
+            .any(|digits| digits.len() >= MIN_ARTICLE_ID_DIGITS)
+    });
+    let has_slug = segments.iter().any(|segment| {
+        segment
+            .split('.')
+            .next()
+            .map(|slug| slug.split(['-', '_']).filter(|word| !word.is_empty()).count() >= MIN_SLUG_WORDS)
+            .unwrap_or_default()
+    });
+    if has_date || has_id || has_slug {
+        UrlClass::Likely
+    } else {
+        UrlClass::Ambiguous
+    }
+}
+
+/// Whether the `url` likely points to an article, see
+/// [`classify_article_url`].
+pub fn looks_like_article_url(url: &Url) -> bool {
+    classify_article_url(url) == UrlClass::Likely
+}
+
+fn is_year(segment: &str) -> bool {
+    segment.len() == 4 && (segment.starts_with("19") || segment.starts_with("20")) && segment.parse::<u16>().is_ok()
+}
+
+fn is_month(segment: &str) -> bool {
+    segment.len() <= 2 && matches!(segment.parse::<u8>(), Ok(1..=12))
+}
+
+/// Decides the [`UrlClass`] of the article urls of a crawl.
+///
+/// See [`crate::ExtrablattBuilder::article_url_filter`].
+#[derive(Clone)]
+pub struct ArticleUrlFilter(Arc<dyn Fn(&Url) -> UrlClass + Send + Sync>);
+
+impl ArticleUrlFilter {
+    /// Classify with the `filter`.
+    pub fn new<F>(filter: F) -> Self
+    where
+        F: Fn(&Url) -> UrlClass + Send + Sync + 'static,
+    {
+        Self(Arc::new(filter))
+    }
+
+    /// The class of the url.
+    pub fn classify(&self, url: &Url) -> UrlClass {
+        (self.0)(url)
+    }
+}
+
+impl Default for ArticleUrlFilter {
+    /// Classifies with [`classify_article_url`].
+    fn default() -> Self {
+        Self::new(classify_article_url)
+    }
+}
+
+impl fmt::Debug for ArticleUrlFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ArticleUrlFilter(..)")
+    }
+}
+
+/// Number of article urls per class during a crawl.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct UrlClassStats {
+    /// Queued urls that are likely articles.
+    pub likely: usize,
+    /// Queued urls that are neither likely articles nor rejected.
+    pub ambiguous: usize,
+    /// Urls rejected by [`RejectRule::NonArticleSegment`].
+    pub non_article_segment: usize,
+    /// Urls rejected by [`RejectRule::StaticPage`].
+    pub static_page: usize,
+    /// Urls rejected by [`RejectRule::Custom`].
+    pub custom: usize,
+}
+
+impl UrlClassStats {
+    /// Count an url of the `class`.
+    pub fn count(&mut self, class: UrlClass) {
+        let count = match class {
+            UrlClass::Likely => &mut self.likely,
+            UrlClass::Ambiguous => &mut self.ambiguous,
+            UrlClass::Rejected(RejectRule::NonArticleSegment) => &mut self.non_article_segment,
+            UrlClass::Rejected(RejectRule::StaticPage) => &mut self.static_page,
+            UrlClass::Rejected(RejectRule::Custom) => &mut self.custom,
+        };
+        *count += 1;
+    }
+
+    /// Number of all rejected urls.
+    pub fn rejected(&self) -> usize {
+        self.non_article_segment + self.static_page + self.custom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(url: &str) -> UrlClass {
+        classify_article_url(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn classify_urls() {
+        assert_eq!(classify("https://example.com/2024/05/harbour"), UrlClass::Likely);
+        assert_eq!(classify("https://example.com/news/2024-05-14/harbour"), UrlClass::Likely);
+        assert_eq!(classify("https://example.com/politics/a-1234567"), UrlClass::Likely);
+        assert_eq!(
+            classify("https://example.com/news/harbour-expansion-approved-today.html"),
+            UrlClass::Likely
+        );
+        assert_eq!(
+            classify("https://example.com/people/2024/05/14/harbour-expansion"),
+            UrlClass::Likely
+        );
+        assert_eq!(classify("https://example.com/news/harbour"), UrlClass::Ambiguous);
+        assert_eq!(classify("https://example.com/"), UrlClass::Ambiguous);
+        assert_eq!(
+            classify("https://example.com/tag/harbour-expansion-approved-today"),
+            UrlClass::Rejected(RejectRule::NonArticleSegment)
+        );
+        assert_eq!(
+            classify("https://example.com/author/jane-doe"),
+            UrlClass::Rejected(RejectRule::NonArticleSegment)
+        );
+        assert_eq!(
+            classify("https://example.com/search?q=harbour"),
+            UrlClass::Rejected(RejectRule::NonArticleSegment)
+        );
+        assert_eq!(
+            classify("https://example.com/about.html"),
+            UrlClass::Rejected(RejectRule::StaticPage)
+        );
+        assert!(looks_like_article_url(&Url::parse("https://example.com/2024/05/harbour").unwrap()));
+        assert!(UrlClass::Likely < UrlClass::Ambiguous);
+    }
+
+    #[test]
+    fn count_classes() {
+        let mut stats = UrlClassStats::default();
+        for class in [
+            UrlClass::Likely,
+            UrlClass::Ambiguous,
+            UrlClass::Rejected(RejectRule::StaticPage),
+            UrlClass::Rejected(RejectRule::Custom),
+        ] {
+            stats.count(class);
+        }
+        assert_eq!(stats.likely, 1);
+        assert_eq!(stats.static_page, 1);
+        assert_eq!(stats.rejected(), 2);
+    }
+}
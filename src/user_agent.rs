@@ -0,0 +1,163 @@
+//! Rotation of the `User-Agent` header across requests, for sites that block
+//! the default user agent.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use reqwest::header::HeaderValue;
+
+use crate::error::ExtrablattError;
+
+/// Current desktop and mobile browsers, used by
+/// [`crate::ExtrablattBuilder::browser_like_user_agent`].
+pub const BROWSER_USER_AGENTS: [&str; 7] = [
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:133.0) Gecko/20100101 Firefox/133.0",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.1 Safari/605.1.15",
+    "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36 Edg/131.0.0.0",
+    "Mozilla/5.0 (iPhone; CPU iPhone OS 18_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.1 Mobile/15E148 Safari/604.1",
+    "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Mobile Safari/537.36",
+];
+
+/// The order in which a [`UserAgentPool`] hands out its user agents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    /// One after another, starting over after the last one.
+    #[default]
+    RoundRobin,
+    /// Randomly, the same `seed` yields the same sequence.
+    Random { seed: u64 },
+}
+
+/// User agents that are sent in turn, one per request.
+#[derive(Debug)]
+pub struct UserAgentPool {
+    agents: Vec<HeaderValue>,
+    rotation: Rotation,
+    /// Number of user agents handed out so far.
+    requests: AtomicUsize,
+}
+
+impl UserAgentPool {
+    /// A pool of the `agents`, each must be a non empty string of visible
+    /// ascii chars and spaces, surrounding whitespace is removed.
+    ///
+    /// An empty pool leaves the user agent of the client in place.
+    pub fn new<I, T>(agents: I, rotation: Rotation) -> Result<Self, ExtrablattError>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let agents = agents
+            .into_iter()
+            .map(|agent| {
+                let agent = agent.into();
+                let trimmed = agent.trim();
+                // `HeaderValue` also accepts opaque non ascii bytes
+                match HeaderValue::from_str(trimmed) {
+                    Ok(value) if !trimmed.is_empty() && trimmed.is_ascii() => Ok(value),
+                    _ => Err(ExtrablattError::UserAgentParseError { user_agent: agent }),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            agents,
+            rotation,
+            requests: AtomicUsize::new(0),
+        })
+    }
+
+    /// A pool of the [`BROWSER_USER_AGENTS`].
+    pub fn browser_like(rotation: Rotation) -> Self {
+        Self {
+            agents: BROWSER_USER_AGENTS.iter().map(|agent| HeaderValue::from_static(agent)).collect(),
+            rotation,
+            requests: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.agents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.agents.is_empty()
+    }
+
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// The user agent for the next request, `None` if the pool is empty.
+    pub fn next(&self) -> Option<&HeaderValue> {
+        if self.agents.is_empty() {
+            return None;
+        }
+        let request = self.requests.fetch_add(1, Ordering::Relaxed);
+        let idx = match self.rotation {
+            Rotation::RoundRobin => request % self.agents.len(),
+            Rotation::Random { seed } => {
+                (splitmix64(seed, request as u64) % self.agents.len() as u64) as usize
+            }
+        };
+        self.agents.get(idx)
+    }
+}
+
+impl Default for UserAgentPool {
+    fn default() -> Self {
+        Self {
+            agents: Vec::new(),
+            rotation: Rotation::default(),
+            requests: AtomicUsize::new(0),
+        }
+    }
+}
+
+/// The string of a user agent of a [`UserAgentPool`].
+pub(crate) fn user_agent_str(user_agent: &HeaderValue) -> Option<String> {
+    user_agent.to_str().ok().map(str::to_string)
+}
+
+/// The `step`-th output of the splitmix64 generator started at `seed`, see
+/// <https://prng.di.unimi.it/splitmix64.c>.
+fn splitmix64(seed: u64, step: u64) -> u64 {
+    let mut z = seed.wrapping_add(step.wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence(pool: &UserAgentPool, len: usize) -> Vec<&str> {
+        (0..len).map(|_| pool.next().unwrap().to_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn rotate_user_agents() {
+        let pool = UserAgentPool::new(vec!["a/1", "b/2", "c/3"], Rotation::RoundRobin).unwrap();
+        assert_eq!(sequence(&pool, 4), vec!["a/1", "b/2", "c/3", "a/1"]);
+
+        let random = |seed| UserAgentPool::new(vec!["a/1", "b/2", "c/3"], Rotation::Random { seed }).unwrap();
+        let (first, second) = (random(7), random(7));
+        let drawn = sequence(&first, 20);
+        assert_eq!(drawn, sequence(&second, 20));
+        assert!(["a/1", "b/2", "c/3"].iter().all(|agent| drawn.contains(agent)));
+
+        assert!(UserAgentPool::default().next().is_none());
+        assert_eq!(UserAgentPool::browser_like(Rotation::RoundRobin).len(), BROWSER_USER_AGENTS.len());
+    }
+
+    #[test]
+    fn invalid_user_agents() {
+        for agent in ["", "  ", "bad\nagent", "bücher/1"] {
+            match UserAgentPool::new(vec!["a/1", agent], Rotation::RoundRobin) {
+                Err(ExtrablattError::UserAgentParseError { user_agent }) => assert_eq!(user_agent, agent),
+                other => panic!("expected an error for {:?}, got {:?}", agent, other),
+            }
+        }
+    }
+}
@@ -1,14 +1,23 @@
+use std::borrow::Cow;
 use std::ops::Deref;
 
 use select::node::Node;
 use select::predicate::{Attr, Name, Predicate};
+#[cfg(feature = "serde0")]
+use serde::{Deserialize, Serialize};
 use url::{ParseError, Url};
 
-#[derive(Debug, Clone)]
+use crate::image::parse_dimension;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
 pub enum VideoProvider {
     Youtube,
     Vimeo,
     Dailymotion,
+    Rutube,
+    /// A `<video>` whose file is served by the site itself.
+    SelfHosted,
     Other(String),
 }
 
@@ -20,11 +29,129 @@ impl VideoProvider {
         if s.contains("vimeo") {
             return Ok(VideoProvider::Vimeo);
         }
-        if s.contains("dailymotion") {
+        if s.contains("dailymotion") || s == "dai.ly" {
             return Ok(VideoProvider::Dailymotion);
         }
+        if s.contains("rutube") {
+            return Ok(VideoProvider::Rutube);
+        }
         Err(VideoProvider::Other(s.to_string()))
     }
+
+    /// The id of the video in the embed or watch `url` of this provider.
+    pub fn video_id(&self, url: &Url) -> Option<String> {
+        let segments: Vec<&str> = url.path_segments()?.filter(|s| !s.is_empty()).collect();
+        let after = |name: &str| {
+            segments
+                .iter()
+                .position(|segment| *segment == name)
+                .and_then(|pos| segments.get(pos + 1))
+                .copied()
+        };
+        let id = match self {
+            VideoProvider::Youtube => {
+                if url.host_str() == Some("youtu.be") {
+                    segments.first().copied()
+                } else if let Some((_, id)) = url.query_pairs().find(|(key, _)| key == "v") {
+                    return Some(id.into_owned()).filter(|id| !id.is_empty());
+                } else {
+                    // playlist embeds have no video id, see `playlist_id`
+                    after("embed")
+                        .filter(|id| *id != "videoseries")
+                        .or_else(|| after("shorts"))
+                        .or_else(|| after("v"))
+                }
+            }
+            VideoProvider::Vimeo => after("video").or_else(|| {
+                segments
+                    .iter()
+                    .copied()
+                    .find(|segment| segment.chars().all(|c| c.is_ascii_digit()))
+            }),
+            VideoProvider::Dailymotion => {
+                let id = if url.host_str() == Some("dai.ly") {
+                    segments.first().copied()
+                } else {
+                    after("video")
+                };
+                // the title may be appended to the id: `x7tgad0_some-title`
+                id.map(|id| id.split('_').next().unwrap_or(id))
+            }
+            VideoProvider::Rutube => after("embed").or_else(|| after("video")),
+            VideoProvider::SelfHosted | VideoProvider::Other(_) => None,
+        };
+        id.filter(|id| !id.is_empty()).map(str::to_string)
+    }
+
+    /// The id of the playlist in the embed or watch `url` of this provider,
+    /// the `list` parameter of YouTube urls.
+    pub fn playlist_id(&self, url: &Url) -> Option<String> {
+        match self {
+            VideoProvider::Youtube => url
+                .query_pairs()
+                .find(|(key, _)| key == "list")
+                .map(|(_, id)| id.trim().to_string())
+                .filter(|id| !id.is_empty()),
+            _ => None,
+        }
+    }
+}
+
+/// Attributes of a `<source>` that label its resolution, like `720p`, in
+/// the order they are checked.
+pub const RESOLUTION_ATTRS: [&str; 5] = ["label", "res", "size", "data-res", "data-quality"];
+
+/// A file of a `<video>`, one of several qualities or formats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct VideoSource {
+    pub url: Url,
+    /// The `type` of the `<source>`, like `video/mp4`.
+    pub mime: Option<String>,
+    /// The label of the resolution, see [`RESOLUTION_ATTRS`].
+    pub res_label: Option<String>,
+}
+
+/// The data of a video of an article, independent of the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde0", derive(Serialize, Deserialize))]
+pub struct Video {
+    /// The url of the embed or the video file.
+    pub src: Url,
+    pub provider: Option<VideoProvider>,
+    /// The id of the video at its provider.
+    pub video_id: Option<String>,
+    /// The preview image.
+    pub poster: Option<Url>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// All files of a `<video>`, see [`VideoNode::sources`].
+    #[cfg_attr(feature = "serde0", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub sources: Vec<VideoSource>,
+    /// The languages and urls of the subtitle tracks of a `<video>`, see
+    /// [`VideoNode::subtitles`].
+    #[cfg_attr(feature = "serde0", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub subtitles: Vec<(String, Url)>,
+    /// The id of the playlist an embed plays.
+    #[cfg_attr(feature = "serde0", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub playlist_id: Option<String>,
+}
+
+impl Video {
+    /// The video of the `node`, `None` if it has no source.
+    pub fn from_node(node: &VideoNode, base_url: Option<&Url>) -> Option<Self> {
+        Some(Self {
+            src: node.source_url(base_url)?,
+            provider: node.provider(),
+            video_id: node.video_id(),
+            poster: node.poster(base_url),
+            width: node.width(),
+            height: node.height(),
+            sources: node.sources(base_url),
+            subtitles: node.subtitles(base_url),
+            playlist_id: node.playlist_id(),
+        })
+    }
 }
 
 pub struct VideoNode<'a> {
@@ -34,7 +161,10 @@ pub struct VideoNode<'a> {
 impl<'a> VideoNode<'a> {
     /// The common predicate to identify Video node
     pub fn node_predicate() -> impl Predicate {
-        Name("iframe").or(Name("object").or(Name("video")))
+        Name("iframe")
+            .or(Name("object").or(Name("video")))
+            .or(Name("amp-youtube").or(Name("amp-vimeo")).or(Name("amp-dailymotion")))
+            .or(Name("amp-video").or(Name("amp-iframe")))
     }
 
     pub fn new(inner: Node<'a>) -> Self {
@@ -49,21 +179,183 @@ impl<'a> VideoNode<'a> {
         self.inner.attr("height")
     }
 
-    pub fn get_src(&self) -> Option<&str> {
-        if Some("object") == self.inner.name() {
-            self.inner
+    /// The width in pixels.
+    pub fn width(&self) -> Option<u32> {
+        parse_dimension(self.get_width()?)
+    }
+
+    /// The height in pixels.
+    pub fn height(&self) -> Option<u32> {
+        parse_dimension(self.get_height()?)
+    }
+
+    /// The source of the video.
+    ///
+    /// For the AMP player components of the video platforms the embed url is
+    /// built from their `data-videoid`.
+    pub fn get_src(&self) -> Option<Cow<'a, str>> {
+        match self.inner.name() {
+            Some("object") => self
+                .inner
                 .find(Name("param").and(Attr("name", "movie")))
                 .filter_map(|n| n.attr("value"))
                 .next()
-        } else {
-            self.inner.attr("src")
+                .map(Cow::Borrowed),
+            Some("amp-youtube") => self
+                .data_video_id()
+                .map(|id| format!("https://www.youtube.com/embed/{}", id).into()),
+            Some("amp-vimeo") => self
+                .data_video_id()
+                .map(|id| format!("https://player.vimeo.com/video/{}", id).into()),
+            Some("amp-dailymotion") => self
+                .data_video_id()
+                .map(|id| format!("https://www.dailymotion.com/embed/video/{}", id).into()),
+            Some("video") | Some("amp-video") => self
+                .inner
+                .attr("src")
+                .or_else(|| {
+                    self.inner
+                        .find(Name("source"))
+                        .filter_map(|n| n.attr("src"))
+                        .next()
+                })
+                .map(Cow::Borrowed),
+            _ => self.inner.attr("src").map(Cow::Borrowed),
         }
     }
 
+    /// The preview image of a `<video>` or `<amp-video>`.
+    pub fn get_poster(&self) -> Option<&'a str> {
+        match self.inner.name() {
+            Some("video") | Some("amp-video") => self
+                .inner
+                .attr("poster")
+                .map(str::trim)
+                .filter(|poster| !poster.is_empty()),
+            _ => None,
+        }
+    }
+
+    /// The preview image, resolved against the `base_url`.
+    pub fn poster(&self, base_url: Option<&Url>) -> Option<Url> {
+        Url::options().base_url(base_url).parse(self.get_poster()?).ok()
+    }
+
+    /// The source of the video, resolved against the `base_url`.
+    ///
+    /// For `<video>` tags without a `src` the first `<source>` child is
+    /// used.
+    pub fn source_url(&self, base_url: Option<&Url>) -> Option<Url> {
+        self.get_src_url(base_url)?.ok()
+    }
+
+    /// The platform that hosts the video.
+    ///
+    /// `<video>` tags with a relative source are [`VideoProvider::SelfHosted`].
+    pub fn provider(&self) -> Option<VideoProvider> {
+        match self.inner.name() {
+            Some("amp-youtube") => return Some(VideoProvider::Youtube),
+            Some("amp-vimeo") => return Some(VideoProvider::Vimeo),
+            Some("amp-dailymotion") => return Some(VideoProvider::Dailymotion),
+            _ => {}
+        }
+        let is_video_tag = matches!(self.inner.name(), Some("video") | Some("amp-video"));
+        let src = self.get_src()?;
+        match Url::parse(src.trim()) {
+            Ok(url) => match VideoProvider::from_host(url.host_str()?) {
+                Ok(provider) => Some(provider),
+                Err(_) if is_video_tag => Some(VideoProvider::SelfHosted),
+                Err(other) => Some(other),
+            },
+            Err(_) if is_video_tag => Some(VideoProvider::SelfHosted),
+            Err(_) => None,
+        }
+    }
+
+    /// The id of the video at its provider, parsed from the embed url.
+    pub fn video_id(&self) -> Option<String> {
+        if let Some(id) = self.data_video_id() {
+            return Some(id.to_string());
+        }
+        let url = Url::parse(self.get_src()?.trim()).ok()?;
+        self.provider()?.video_id(&url)
+    }
+
+    /// The id of the playlist the embed plays, like the `list` parameter of
+    /// a YouTube embed.
+    pub fn playlist_id(&self) -> Option<String> {
+        let url = Url::parse(self.get_src()?.trim()).ok()?;
+        self.provider()?.playlist_id(&url)
+    }
+
+    /// The `src` of a `<video>` and all of its `<source>` children, resolved
+    /// against the `base_url`, without repeated urls.
+    pub fn sources(&self, base_url: Option<&Url>) -> Vec<VideoSource> {
+        if !matches!(self.inner.name(), Some("video") | Some("amp-video")) {
+            return Vec::new();
+        }
+        let options = Url::options().base_url(base_url);
+        let attr = |node: &Node<'a>, name: &str| {
+            node.attr(name)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let mut sources: Vec<VideoSource> = Vec::new();
+        for node in std::iter::once(self.inner).chain(self.inner.find(Name("source"))) {
+            let Some(url) = node.attr("src").and_then(|src| options.parse(src.trim()).ok()) else {
+                continue;
+            };
+            if sources.iter().any(|source| source.url == url) {
+                continue;
+            }
+            sources.push(VideoSource {
+                url,
+                mime: attr(&node, "type"),
+                res_label: RESOLUTION_ATTRS.iter().find_map(|name| attr(&node, name)),
+            });
+        }
+        sources
+    }
+
+    /// The language and url of the `<track>` children of a `<video>` with
+    /// subtitles or captions, resolved against the `base_url`.
+    ///
+    /// Tracks without a `kind` are subtitles. The language is the `srclang`,
+    /// or else the `label`, tracks with neither are skipped.
+    pub fn subtitles(&self, base_url: Option<&Url>) -> Vec<(String, Url)> {
+        let options = Url::options().base_url(base_url);
+        self.inner
+            .find(Name("track"))
+            .filter(|track| {
+                matches!(
+                    track.attr("kind").map(|kind| kind.trim().to_lowercase()).as_deref(),
+                    None | Some("subtitles") | Some("captions")
+                )
+            })
+            .filter_map(|track| {
+                let language = track
+                    .attr("srclang")
+                    .or_else(|| track.attr("label"))
+                    .map(str::trim)
+                    .filter(|language| !language.is_empty())?;
+                let url = options.parse(track.attr("src")?.trim()).ok()?;
+                Some((language.to_string(), url))
+            })
+            .collect()
+    }
+
+    fn data_video_id(&self) -> Option<&'a str> {
+        self.inner
+            .attr("data-videoid")
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+    }
+
     pub fn get_src_url(&self, base_url: Option<&Url>) -> Option<Result<Url, ParseError>> {
         if let Some(url) = self.get_src() {
             let options = Url::options().base_url(base_url);
-            Some(options.parse(url))
+            Some(options.parse(&url))
         } else {
             None
         }
@@ -92,3 +384,122 @@ impl<'a> Deref for VideoNode<'a> {
         &self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use select::document::Document;
+
+    use super::*;
+
+    fn videos(html: &str) -> Vec<Video> {
+        let doc = Document::from(html);
+        let base = Url::parse("https://example.com/news/").unwrap();
+        doc.find(VideoNode::node_predicate())
+            .filter_map(|node| Video::from_node(&VideoNode::new(node), Some(&base)))
+            .collect()
+    }
+
+    #[test]
+    fn structured_videos() {
+        let videos = videos(
+            r#"<body>
+            <iframe src="https://www.youtube.com/embed/dQw4w9WgXcQ?rel=0" width="560" height="315"></iframe>
+            <iframe src="https://player.vimeo.com/video/76979871"></iframe>
+            <iframe src="https://www.dailymotion.com/embed/video/x7tgad0"></iframe>
+            <iframe src="https://rutube.ru/play/embed/a1b2c3d4e5"></iframe>
+            <video poster="/img/crowd.jpg" width="640px"><source src="/video/crowd.mp4" type="video/mp4"></video>
+            <amp-youtube data-videoid="abc_123" layout="responsive"></amp-youtube>
+            </body>"#,
+        );
+        let ids: Vec<_> = videos.iter().map(|video| video.video_id.as_deref()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                Some("dQw4w9WgXcQ"),
+                Some("76979871"),
+                Some("x7tgad0"),
+                Some("a1b2c3d4e5"),
+                None,
+                Some("abc_123")
+            ]
+        );
+        let providers: Vec<_> = videos.iter().map(|video| video.provider.clone()).collect();
+        assert_eq!(
+            providers,
+            vec![
+                Some(VideoProvider::Youtube),
+                Some(VideoProvider::Vimeo),
+                Some(VideoProvider::Dailymotion),
+                Some(VideoProvider::Rutube),
+                Some(VideoProvider::SelfHosted),
+                Some(VideoProvider::Youtube)
+            ]
+        );
+        assert_eq!((videos[0].width, videos[0].height), (Some(560), Some(315)));
+
+        let self_hosted = &videos[4];
+        assert_eq!(self_hosted.src.as_str(), "https://example.com/video/crowd.mp4");
+        assert_eq!(self_hosted.poster.as_ref().map(Url::as_str), Some("https://example.com/img/crowd.jpg"));
+        assert_eq!(self_hosted.width, Some(640));
+    }
+
+    #[test]
+    fn sources_subtitles_and_playlists() {
+        let videos = videos(
+            r#"<body>
+            <video poster="/img/storm.jpg">
+              <source src="/video/storm-1080.webm" type="video/webm" label="1080p">
+              <source src="/video/storm-720.mp4" type="video/mp4" res="720">
+              <source src="/video/storm-720.mp4" type="video/mp4">
+              <track kind="subtitles" srclang="en" src="/subs/storm.en.vtt">
+              <track src="subs/storm.de.vtt" label="Deutsch">
+              <track kind="chapters" srclang="en" src="/subs/chapters.vtt">
+              <track kind="captions" src="/subs/unlabeled.vtt">
+            </video>
+            <iframe src="https://www.youtube.com/embed/videoseries?list=PLx0sYbCqOb8TBPRdmBHs5Iftvv9TPboYG"></iframe>
+            <iframe src="https://www.youtube.com/embed/dQw4w9WgXcQ?list=PLabc"></iframe>
+            </body>"#,
+        );
+        let video = &videos[0];
+        let sources: Vec<_> = video
+            .sources
+            .iter()
+            .map(|source| (source.url.as_str(), source.mime.as_deref(), source.res_label.as_deref()))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ("https://example.com/video/storm-1080.webm", Some("video/webm"), Some("1080p")),
+                ("https://example.com/video/storm-720.mp4", Some("video/mp4"), Some("720")),
+            ]
+        );
+        let subtitles: Vec<_> = video
+            .subtitles
+            .iter()
+            .map(|(lang, url)| (lang.as_str(), url.as_str()))
+            .collect();
+        assert_eq!(
+            subtitles,
+            vec![
+                ("en", "https://example.com/subs/storm.en.vtt"),
+                ("Deutsch", "https://example.com/news/subs/storm.de.vtt"),
+            ]
+        );
+        assert_eq!(video.playlist_id, None);
+
+        assert_eq!(videos[1].video_id, None);
+        assert_eq!(videos[1].playlist_id.as_deref(), Some("PLx0sYbCqOb8TBPRdmBHs5Iftvv9TPboYG"));
+        assert_eq!(videos[2].video_id.as_deref(), Some("dQw4w9WgXcQ"));
+        assert_eq!(videos[2].playlist_id.as_deref(), Some("PLabc"));
+        assert!(videos[1].sources.is_empty() && videos[1].subtitles.is_empty());
+    }
+
+    #[test]
+    fn watch_urls() {
+        let id = |url: &str, provider: VideoProvider| provider.video_id(&Url::parse(url).unwrap());
+        assert_eq!(id("https://www.youtube.com/watch?v=dQw4w9WgXcQ", VideoProvider::Youtube).as_deref(), Some("dQw4w9WgXcQ"));
+        assert_eq!(id("https://youtu.be/dQw4w9WgXcQ", VideoProvider::Youtube).as_deref(), Some("dQw4w9WgXcQ"));
+        assert_eq!(id("https://vimeo.com/76979871", VideoProvider::Vimeo).as_deref(), Some("76979871"));
+        assert_eq!(id("https://www.dailymotion.com/video/x7tgad0_harbour", VideoProvider::Dailymotion).as_deref(), Some("x7tgad0"));
+    }
+}
@@ -0,0 +1,128 @@
+use url::Url;
+
+use crate::article::ArticleContent;
+
+/// Hosts of the Wayback Machine.
+const WAYBACK_HOSTS: [&str; 2] = ["web.archive.org", "wayback.archive.org"];
+
+/// Whether the url points to a snapshot of the Wayback Machine, like
+/// `https://web.archive.org/web/20200101000000/https://example.com/`.
+pub fn is_wayback_url(url: &Url) -> bool {
+    url.host_str()
+        .map(|host| WAYBACK_HOSTS.contains(&host))
+        .unwrap_or_default()
+        && unwrap_wayback_path(url).is_some()
+}
+
+/// The original url of a Wayback Machine snapshot url.
+///
+/// Returns `None` if the url is not a snapshot url.
+pub fn wayback_original_url(url: &Url) -> Option<Url> {
+    if is_wayback_url(url) {
+        unwrap_wayback_path(url)
+    } else {
+        None
+    }
+}
+
+/// Extracts the original url from a path in the form of
+/// `/web/<timestamp>[<modifier>_]/<original url>`, regardless of the host.
+///
+/// Links in archived pages are rewritten to this form, so after resolving
+/// them against the original url, they point to the original host with the
+/// archive's prefix.
+pub fn unwrap_wayback_path(url: &Url) -> Option<Url> {
+    let path = url.path().strip_prefix("/web/")?;
+    let (timestamp, original) = path.split_once('/')?;
+    let digits = timestamp.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '_');
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // `https://` is collapsed to `https:/` in some paths
+    let original = if let Some(rest) = original.strip_prefix("http:/").filter(|s| !s.starts_with('/')) {
+        format!("http://{}", rest)
+    } else if let Some(rest) = original.strip_prefix("https:/").filter(|s| !s.starts_with('/')) {
+        format!("https://{}", rest)
+    } else {
+        original.to_string()
+    };
+    let mut original = Url::parse(&original).ok()?;
+    if original.query().is_none() {
+        original.set_query(url.query());
+    }
+    Some(original)
+}
+
+/// Replace all rewritten Wayback Machine links of the content with their
+/// original urls.
+pub fn unwrap_wayback_links(content: &mut ArticleContent<'_>) {
+    let unwrap = |url: &mut Url| {
+        if let Some(original) = unwrap_wayback_path(url) {
+            *url = original;
+        }
+    };
+    content.images.iter_mut().for_each(unwrap);
+    content.references.iter_mut().for_each(unwrap);
+    content.videos.iter_mut().for_each(unwrap);
+    content.top_image.iter_mut().for_each(unwrap);
+    content.thumbnail.iter_mut().for_each(unwrap);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::extract::{DefaultExtractor, Extractor};
+    use select::document::Document;
+
+    use super::*;
+
+    #[test]
+    fn original_url() {
+        let url = Url::parse("https://web.archive.org/web/20200101120000/https://example.com/news/a.html").unwrap();
+        assert_eq!(
+            wayback_original_url(&url).unwrap().as_str(),
+            "https://example.com/news/a.html"
+        );
+        let url = Url::parse("https://web.archive.org/web/20200101120000im_/https:/example.com/img/a.jpg").unwrap();
+        assert_eq!(
+            wayback_original_url(&url).unwrap().as_str(),
+            "https://example.com/img/a.jpg"
+        );
+        assert!(wayback_original_url(&Url::parse("https://example.com/web/1/x").unwrap()).is_none());
+    }
+
+    #[test]
+    fn archived_snapshot_links() {
+        let doc = Document::from(
+            r#"<html><head><link rel="canonical" href="/news/a.html"></head><body><div itemprop="articleBody">
+            <p>The harbour will be expanded over the next ten years, the city announced on Monday.
+            The first <a href="/web/20200101120000/https://example.com/news/plan.html">plan</a>
+            was presented to the <a href="council.html">council</a> in the spring.</p>
+            <img src="/web/20200101120000im_/https://example.com/img/harbour.jpg">
+            <img src="img/map.png">
+            </div></body></html>"#,
+        );
+        let archive_url = Url::parse("https://web.archive.org/web/20200101120000/https://example.com/news/a.html").unwrap();
+        let base = wayback_original_url(&archive_url).unwrap();
+        let mut content = DefaultExtractor::default().article_content(&doc, Some(&base), None);
+        unwrap_wayback_links(&mut content);
+
+        assert_eq!(
+            content.images,
+            vec![
+                Url::parse("https://example.com/img/harbour.jpg").unwrap(),
+                Url::parse("https://example.com/news/img/map.png").unwrap(),
+            ]
+        );
+        assert_eq!(
+            content.references,
+            vec![
+                Url::parse("https://example.com/news/plan.html").unwrap(),
+                Url::parse("https://example.com/news/council.html").unwrap(),
+            ]
+        );
+        assert_eq!(
+            DefaultExtractor::default().canonical_link(&doc, Some(&base)).unwrap().as_str(),
+            "https://example.com/news/a.html"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Runs the `extract` command of the binary over the fixtures.
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+#[allow(dead_code)]
+#[path = "../src/test_server.rs"]
+mod test_server;
+
+const FIXTURE: &str = "tests/fixtures/newspaper/harbour.html";
+
+fn extract(args: &[&str], stdin: Option<&str>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_extrablatt_v2"))
+        .arg("extract")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut input = child.stdin.take().unwrap();
+    input.write_all(stdin.unwrap_or_default().as_bytes()).unwrap();
+    drop(input);
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// Serves the `html` as the article, or `404 Not Found` if there is none.
+fn serve(html: Option<String>) -> String {
+    let url = test_server::serve(move |_| match &html {
+        Some(html) => test_server::html(html),
+        None => test_server::status("404 Not Found"),
+    });
+    format!("{}news/harbour.html", url)
+}
+
+#[test]
+fn extract_file_as_json() {
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout(&extract(&[FIXTURE, "--url", "https://example.com/news/harbour.html"], None)))
+            .unwrap();
+    assert_eq!(json["url"], "https://example.com/news/harbour.html");
+    assert_eq!(json["content"]["title"], "Harbour expansion approved");
+    assert!(json["content"]["text"].as_str().unwrap().contains("new quay"));
+}
+
+#[test]
+fn extract_stdin_as_text_and_markdown() {
+    let html = std::fs::read_to_string(FIXTURE).unwrap();
+    let text = stdout(&extract(&["-", "--format", "text"], Some(&html)));
+    assert!(text.starts_with("The harbour will be expanded"));
+    assert!(!text.contains('{'));
+
+    let markdown = stdout(&extract(&["-", "--format", "markdown", "--lang", "en"], Some(&html)));
+    assert!(markdown.starts_with("# Harbour expansion approved\n\nThe harbour will be expanded"));
+    assert_eq!(stdout(&extract(&["-", "--format", "md", "--lang", "en"], Some(&html))), markdown);
+}
+
+#[test]
+fn extract_url() {
+    let html = std::fs::read_to_string(FIXTURE).unwrap();
+    let url = serve(Some(html));
+    let text = stdout(&extract(&[&url, "--format", "text", "--timeout", "5", "--user-agent", "test"], None));
+    assert!(text.contains("new quay"));
+}
+
+#[test]
+fn failures_exit_with_an_error() {
+    let url = serve(None);
+    assert!(!extract(&[&url], None).status.success());
+    assert!(!extract(&["tests/fixtures/missing.html"], None).status.success());
+
+    let incomplete = extract(&[FIXTURE, "--min-words", "1000"], None);
+    assert!(!incomplete.status.success());
+    assert!(String::from_utf8_lossy(&incomplete.stderr).contains("word_count"));
+    assert!(incomplete.stdout.is_empty());
+}
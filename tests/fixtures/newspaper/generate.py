@@ -0,0 +1,43 @@
+"""Regenerate harbour.json from the output of newspaper3k.
+
+    pip install newspaper3k
+    python tests/fixtures/newspaper/generate.py > tests/fixtures/newspaper/harbour.json
+"""
+
+import json
+import pathlib
+
+from newspaper import Article
+
+URL = "https://example.com/news/harbour.html"
+HTML = (pathlib.Path(__file__).parent / "harbour.html").read_text()
+
+article = Article(URL, language="en")
+article.download(input_html=HTML)
+article.parse()
+article.nlp()
+
+print(
+    json.dumps(
+        {
+            "url": article.url,
+            "language": article.config.language,
+            "title": article.title,
+            "top_image": article.top_image,
+            "meta_img": article.meta_img,
+            "images": sorted(article.images),
+            "movies": article.movies,
+            "keywords": article.keywords,
+            "meta_keywords": article.meta_keywords,
+            "tags": sorted(article.tags),
+            "authors": article.authors,
+            "publish_date": article.publish_date.isoformat() if article.publish_date else None,
+            "summary": article.summary,
+            "meta_description": article.meta_description,
+            "meta_lang": article.meta_lang,
+            "text": article.text,
+        },
+        indent=2,
+        ensure_ascii=False,
+    )
+)
@@ -0,0 +1,18 @@
+//! Extraction of local html without the `net` feature on
+//! `wasm32-unknown-unknown`, run with
+//! `wasm-pack test --node -- --no-default-features --test wasm`.
+#![cfg(target_arch = "wasm32")]
+
+use extrablatt_v2::Article;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const FIXTURE: &str = include_str!("fixtures/newspaper/harbour.html");
+
+#[wasm_bindgen_test]
+fn extract_local_html() {
+    let article = Article::new("https://example.com/news/harbour.html", FIXTURE).unwrap();
+    assert_eq!(article.content.title.as_deref(), Some("Harbour expansion approved"));
+    assert!(article.content.text.as_deref().unwrap_or_default().contains("new quay"));
+    assert!(article.fetch_info.is_none());
+    assert!(Article::new("not a url", FIXTURE).is_err());
+}